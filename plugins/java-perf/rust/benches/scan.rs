@@ -0,0 +1,39 @@
+//! criterion 基准: `cargo bench`
+//!
+//! 工作负载与 `java-perf bench` 共用 (src/bench.rs)。
+
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use java_perf::ast_engine;
+use java_perf::bench::{self, SAMPLE_SOURCE};
+
+/// 合成项目规模，可用 JAVA_PERF_BENCH_FILES 覆盖
+fn project_files() -> usize {
+    std::env::var("JAVA_PERF_BENCH_FILES").ok().and_then(|v| v.parse().ok()).unwrap_or(5000)
+}
+
+fn single_file(c: &mut Criterion) {
+    c.bench_function("single_file", |b| {
+        b.iter(|| ast_engine::analyze_source_issues(SAMPLE_SOURCE, "OrderService.java"))
+    });
+}
+
+fn radar_scan(c: &mut Criterion) {
+    let dir = tempfile::tempdir().expect("tempdir");
+    bench::generate_project(dir.path(), project_files()).expect("generate project");
+    let path = dir.path().to_string_lossy().to_string();
+
+    let mut group = c.benchmark_group("radar_scan");
+    group.sample_size(10);
+    group.bench_function("synthetic_project", |b| b.iter(|| ast_engine::collect_issues(&path).unwrap()));
+    group.finish();
+}
+
+fn symbol_table_merge(c: &mut Criterion) {
+    let files = project_files();
+    c.bench_function("symbol_table_merge", |b| {
+        b.iter_batched(|| bench::symbol_tables(files), bench::merge_symbol_tables, BatchSize::LargeInput)
+    });
+}
+
+criterion_group!(benches, single_file, radar_scan, symbol_table_merge);
+criterion_main!(benches);
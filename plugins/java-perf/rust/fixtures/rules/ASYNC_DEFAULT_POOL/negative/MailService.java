@@ -0,0 +1,13 @@
+package com.acme.mail;
+
+import org.springframework.scheduling.annotation.Async;
+
+public class MailService {
+    @Async("mailExecutor")
+    public void send(String to) {
+        deliver(to);
+    }
+
+    private void deliver(String to) {
+    }
+}
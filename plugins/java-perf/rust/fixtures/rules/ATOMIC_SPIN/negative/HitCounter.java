@@ -0,0 +1,11 @@
+package com.acme.metrics;
+
+import java.util.concurrent.atomic.LongAdder;
+
+public class HitCounter {
+    private final LongAdder hits = new LongAdder();
+
+    public void hit() {
+        hits.increment();
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.metrics;
+
+import java.util.concurrent.atomic.AtomicLong;
+
+public class HitCounter {
+    private final AtomicLong hits = new AtomicLong();
+
+    public void hit() {
+        hits.incrementAndGet();
+    }
+}
@@ -0,0 +1,13 @@
+package com.acme.service;
+
+public class OrderService {
+    private final OrderRepository orderRepository;
+
+    public OrderService(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    public long count() {
+        return orderRepository.count();
+    }
+}
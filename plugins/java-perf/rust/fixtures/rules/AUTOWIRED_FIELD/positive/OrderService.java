@@ -0,0 +1,12 @@
+package com.acme.service;
+
+import org.springframework.beans.factory.annotation.Autowired;
+
+public class OrderService {
+    @Autowired
+    private OrderRepository orderRepository;
+
+    public long count() {
+        return orderRepository.count();
+    }
+}
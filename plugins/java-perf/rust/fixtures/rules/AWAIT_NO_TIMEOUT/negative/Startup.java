@@ -0,0 +1,12 @@
+package com.acme.async;
+
+import java.util.concurrent.CountDownLatch;
+import java.util.concurrent.TimeUnit;
+
+public class Startup {
+    private final CountDownLatch ready = new CountDownLatch(1);
+
+    public boolean waitReady() throws InterruptedException {
+        return ready.await(30, TimeUnit.SECONDS);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.async;
+
+import java.util.concurrent.CountDownLatch;
+
+public class Startup {
+    private final CountDownLatch ready = new CountDownLatch(1);
+
+    public void waitReady() throws InterruptedException {
+        ready.await();
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.io;
+
+import java.io.IOException;
+import java.io.InputStream;
+import java.nio.file.Files;
+import java.nio.file.Paths;
+
+public class Archiver {
+    public InputStream open(String path) throws IOException {
+        return Files.newInputStream(Paths.get(path));
+    }
+}
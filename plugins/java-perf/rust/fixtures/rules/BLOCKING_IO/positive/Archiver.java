@@ -0,0 +1,11 @@
+package com.acme.io;
+
+import java.io.FileInputStream;
+import java.io.IOException;
+import java.io.InputStream;
+
+public class Archiver {
+    public InputStream open(String path) throws IOException {
+        return new FileInputStream(path);
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.catalog;
+
+import org.springframework.cache.annotation.Cacheable;
+
+public class ProductService {
+    @Cacheable(value = "products", key = "#id + ':' + #locale")
+    public Product find(long id, String locale) {
+        return load(id, locale);
+    }
+
+    private Product load(long id, String locale) {
+        return null;
+    }
+}
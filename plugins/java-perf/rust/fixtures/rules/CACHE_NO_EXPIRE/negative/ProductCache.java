@@ -0,0 +1,20 @@
+package com.acme.cache;
+
+import com.github.benmanes.caffeine.cache.Cache;
+import com.github.benmanes.caffeine.cache.Caffeine;
+import java.time.Duration;
+
+public class ProductCache {
+    public Cache<String, String> build() {
+        return Caffeine.newBuilder()
+                .recordStats()
+                .expireAfterWrite(Duration.ofMinutes(10))
+                .build();
+    }
+
+    public Cache<String, String> sized() {
+        Caffeine<Object, Object> builder = Caffeine.newBuilder();
+        builder.maximumSize(10_000);
+        return builder.build();
+    }
+}
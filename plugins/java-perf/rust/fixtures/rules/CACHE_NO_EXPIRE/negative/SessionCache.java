@@ -0,0 +1,15 @@
+package com.acme.cache;
+
+import com.github.benmanes.caffeine.cache.Cache;
+
+public class SessionCache {
+    private final CacheFactory factory;
+
+    public SessionCache(CacheFactory factory) {
+        this.factory = factory;
+    }
+
+    public Cache<String, String> build() {
+        return factory.sessions();
+    }
+}
@@ -0,0 +1,10 @@
+package com.acme.cache;
+
+import com.github.benmanes.caffeine.cache.Cache;
+import com.github.benmanes.caffeine.cache.Caffeine;
+
+public class SessionCache {
+    public Cache<String, String> build() {
+        return Caffeine.newBuilder().build();
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.util;
+
+import java.util.ArrayList;
+import java.util.List;
+
+public class Mapper {
+    public List<String> names(List<User> users) {
+        List<String> names = new ArrayList<>(users.size());
+        names.addAll(users.stream().map(User::getName).toList());
+        return names;
+    }
+}
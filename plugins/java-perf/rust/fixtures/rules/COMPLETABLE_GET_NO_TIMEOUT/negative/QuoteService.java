@@ -0,0 +1,10 @@
+package com.acme.async;
+
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.TimeUnit;
+
+public class QuoteService {
+    public String quote(CompletableFuture<String> pending) throws Exception {
+        return pending.get(2, TimeUnit.SECONDS);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.async;
+
+import java.util.concurrent.CompletableFuture;
+
+public class QuoteService {
+    public String quote(CompletableFuture<String> pending) throws Exception {
+        return pending.get();
+    }
+}
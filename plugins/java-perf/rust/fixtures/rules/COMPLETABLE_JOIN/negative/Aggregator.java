@@ -0,0 +1,10 @@
+package com.acme.async;
+
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.TimeUnit;
+
+public class Aggregator {
+    public CompletableFuture<String> await(CompletableFuture<String> future) {
+        return future.orTimeout(5, TimeUnit.SECONDS);
+    }
+}
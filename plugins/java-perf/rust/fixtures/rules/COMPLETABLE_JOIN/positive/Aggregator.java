@@ -0,0 +1,9 @@
+package com.acme.async;
+
+import java.util.concurrent.CompletableFuture;
+
+public class Aggregator {
+    public String await(CompletableFuture<String> future) {
+        return future.join();
+    }
+}
@@ -0,0 +1,41 @@
+package com.acme.order;
+
+import java.sql.Connection;
+import java.sql.PreparedStatement;
+import java.sql.SQLException;
+import javax.sql.DataSource;
+import org.springframework.jdbc.datasource.DataSourceUtils;
+
+public class LegacyOrderDao {
+    private final DataSource dataSource;
+
+    public LegacyOrderDao(DataSource dataSource) {
+        this.dataSource = dataSource;
+    }
+
+    public void markShipped(long id) throws SQLException {
+        try (Connection conn = dataSource.getConnection();
+             PreparedStatement ps = conn.prepareStatement("UPDATE orders SET status = 'SHIPPED' WHERE id = ?")) {
+            ps.setLong(1, id);
+            ps.executeUpdate();
+        }
+    }
+
+    public void archive(long id) throws SQLException {
+        Connection conn = DataSourceUtils.getConnection(dataSource);
+        try {
+            PreparedStatement ps = conn.prepareStatement("DELETE FROM orders WHERE id = ?");
+            ps.setLong(1, id);
+            ps.executeUpdate();
+        } finally {
+            DataSourceUtils.releaseConnection(conn, dataSource);
+        }
+    }
+
+    // 连接交给调用方关闭
+    public Connection open() throws SQLException {
+        Connection conn = dataSource.getConnection();
+        conn.setAutoCommit(false);
+        return conn;
+    }
+}
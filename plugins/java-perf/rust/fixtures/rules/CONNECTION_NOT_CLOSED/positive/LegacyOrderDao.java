@@ -0,0 +1,22 @@
+package com.acme.order;
+
+import java.sql.Connection;
+import java.sql.PreparedStatement;
+import java.sql.SQLException;
+import javax.sql.DataSource;
+
+public class LegacyOrderDao {
+    private final DataSource dataSource;
+
+    public LegacyOrderDao(DataSource dataSource) {
+        this.dataSource = dataSource;
+    }
+
+    public void markShipped(long id) throws SQLException {
+        Connection conn = dataSource.getConnection();
+        PreparedStatement ps = conn.prepareStatement("UPDATE orders SET status = 'SHIPPED' WHERE id = ?");
+        ps.setLong(1, id);
+        ps.executeUpdate();
+        conn.close();
+    }
+}
@@ -0,0 +1,17 @@
+package com.acme.dao;
+
+import java.sql.Connection;
+import java.sql.SQLException;
+import javax.sql.DataSource;
+
+public class ReportDao {
+    private final DataSource dataSource;
+
+    public ReportDao(DataSource dataSource) {
+        this.dataSource = dataSource;
+    }
+
+    public Connection connect() throws SQLException {
+        return dataSource.getConnection();
+    }
+}
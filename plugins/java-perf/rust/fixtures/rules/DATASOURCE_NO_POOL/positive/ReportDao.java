@@ -0,0 +1,11 @@
+package com.acme.dao;
+
+import java.sql.Connection;
+import java.sql.DriverManager;
+import java.sql.SQLException;
+
+public class ReportDao {
+    public Connection connect(String url) throws SQLException {
+        return DriverManager.getConnection(url);
+    }
+}
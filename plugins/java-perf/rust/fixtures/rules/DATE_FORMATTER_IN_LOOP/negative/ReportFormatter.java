@@ -0,0 +1,17 @@
+package com.acme.report;
+
+import java.time.LocalDate;
+import java.time.format.DateTimeFormatter;
+import java.util.List;
+
+public class ReportFormatter {
+    private static final DateTimeFormatter DAY = DateTimeFormatter.ofPattern("yyyy-MM-dd");
+
+    public int width(List<LocalDate> dates) {
+        int width = 0;
+        for (LocalDate date : dates) {
+            width = width + date.format(DAY).length();
+        }
+        return width;
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.report;
+
+import java.time.LocalDate;
+import java.time.format.DateTimeFormatter;
+import java.util.List;
+
+public class ReportFormatter {
+    public int width(List<LocalDate> dates) {
+        int width = 0;
+        for (LocalDate date : dates) {
+            width = width + date.format(DateTimeFormatter.ofPattern("yyyy-MM-dd")).length();
+        }
+        return width;
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.util;
+
+import java.util.Date;
+import java.util.List;
+
+public class Stamper {
+    public long stamp(List<Record> records) {
+        Date now = new Date();
+        long last = 0;
+        for (Record record : records) {
+            last = record.stamp(now);
+        }
+        return last;
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.util;
+
+import java.util.Date;
+import java.util.List;
+
+public class Stamper {
+    public long stamp(List<Record> records) {
+        long last = 0;
+        for (Record record : records) {
+            last = record.stamp(new Date());
+        }
+        return last;
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.billing;
+
+import java.time.LocalDate;
+import java.time.ZoneId;
+
+public class BillingClock {
+    private static final ZoneId BILLING_ZONE = ZoneId.of("Europe/Berlin");
+
+    public LocalDate today() {
+        return LocalDate.now(BILLING_ZONE);
+    }
+}
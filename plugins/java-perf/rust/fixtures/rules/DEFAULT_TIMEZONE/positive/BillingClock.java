@@ -0,0 +1,10 @@
+package com.acme.billing;
+
+import java.time.LocalDate;
+import java.time.ZoneId;
+
+public class BillingClock {
+    public LocalDate today() {
+        return LocalDate.now(ZoneId.systemDefault());
+    }
+}
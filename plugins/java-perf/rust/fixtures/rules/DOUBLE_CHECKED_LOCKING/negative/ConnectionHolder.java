@@ -0,0 +1,11 @@
+package com.acme.db;
+
+public class ConnectionHolder {
+    private static class Holder {
+        static final Connection INSTANCE = Connection.open();
+    }
+
+    public static Connection get() {
+        return Holder.INSTANCE;
+    }
+}
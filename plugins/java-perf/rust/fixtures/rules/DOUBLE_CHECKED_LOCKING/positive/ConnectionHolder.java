@@ -0,0 +1,17 @@
+// also-triggers: SYNC_BLOCK
+package com.acme.db;
+
+public class ConnectionHolder {
+    private static Connection instance;
+
+    public static Connection get() {
+        if (instance == null) {
+            synchronized (ConnectionHolder.class) {
+                if (instance == null) {
+                    instance = Connection.open();
+                }
+            }
+        }
+        return instance;
+    }
+}
@@ -0,0 +1,17 @@
+package com.acme.search;
+
+import org.apache.http.HttpHost;
+import org.elasticsearch.client.RestClient;
+import org.elasticsearch.client.RestHighLevelClient;
+import org.springframework.context.annotation.Bean;
+import org.springframework.context.annotation.Configuration;
+import org.springframework.context.annotation.Lazy;
+
+@Configuration
+public class SearchConfig {
+    @Bean
+    @Lazy
+    public RestHighLevelClient searchClient() {
+        return new RestHighLevelClient(RestClient.builder(HttpHost.create("http://es:9200")));
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.search;
+
+import org.apache.http.HttpHost;
+import org.elasticsearch.client.RestClient;
+import org.elasticsearch.client.RestHighLevelClient;
+import org.springframework.context.annotation.Bean;
+import org.springframework.context.annotation.Configuration;
+
+@Configuration
+public class SearchConfig {
+    @Bean
+    public RestHighLevelClient searchClient() {
+        return new RestHighLevelClient(RestClient.builder(HttpHost.create("http://es:9200")));
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.EmitterProcessor;
+
+public class EventBus {
+    public EmitterProcessor<String> channel() {
+        return EmitterProcessor.create();
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.config;
+
+public class ConfigReader {
+    public int port(String value) {
+        try {
+            return Integer.parseInt(value);
+        } catch (NumberFormatException e) {
+            throw new IllegalArgumentException("invalid port: " + value, e);
+        }
+    }
+}
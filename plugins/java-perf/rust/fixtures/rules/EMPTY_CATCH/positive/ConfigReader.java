@@ -0,0 +1,12 @@
+package com.acme.config;
+
+public class ConfigReader {
+    public int port(String value) {
+        int port = 8080;
+        try {
+            port = Integer.parseInt(value);
+        } catch (NumberFormatException e) {
+        }
+        return port;
+    }
+}
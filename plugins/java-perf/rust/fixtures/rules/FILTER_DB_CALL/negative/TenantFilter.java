@@ -0,0 +1,23 @@
+package com.acme.web;
+
+import jakarta.servlet.FilterChain;
+import jakarta.servlet.ServletException;
+import jakarta.servlet.http.HttpServletRequest;
+import jakarta.servlet.http.HttpServletResponse;
+import java.io.IOException;
+import org.springframework.web.filter.OncePerRequestFilter;
+
+public class TenantFilter extends OncePerRequestFilter {
+    private final TenantCache tenantCache;
+
+    public TenantFilter(TenantCache tenantCache) {
+        this.tenantCache = tenantCache;
+    }
+
+    @Override
+    protected void doFilterInternal(HttpServletRequest request, HttpServletResponse response, FilterChain chain)
+            throws ServletException, IOException {
+        request.setAttribute("tenant", tenantCache.lookup(request.getServerName()));
+        chain.doFilter(request, response);
+    }
+}
@@ -0,0 +1,10 @@
+package com.acme.nativeio;
+
+public class NativeHandle implements AutoCloseable {
+    private long address;
+
+    @Override
+    public void close() {
+        address = 0;
+    }
+}
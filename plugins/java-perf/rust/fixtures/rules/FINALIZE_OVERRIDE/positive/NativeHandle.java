@@ -0,0 +1,10 @@
+package com.acme.nativeio;
+
+public class NativeHandle {
+    private long address;
+
+    @Override
+    protected void finalize() {
+        address = 0;
+    }
+}
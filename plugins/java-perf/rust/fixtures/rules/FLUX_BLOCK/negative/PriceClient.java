@@ -0,0 +1,9 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Mono;
+
+public class PriceClient {
+    public Mono<Long> price(Mono<Long> quote) {
+        return quote.map(p -> p * 2);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Mono;
+
+public class PriceClient {
+    public Long price(Mono<Long> quote) {
+        return quote.block();
+    }
+}
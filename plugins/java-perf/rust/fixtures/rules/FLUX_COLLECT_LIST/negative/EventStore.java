@@ -0,0 +1,10 @@
+package com.acme.reactive;
+
+import java.util.List;
+import reactor.core.publisher.Flux;
+
+public class EventStore {
+    public Flux<List<String>> batches(Flux<String> events) {
+        return events.buffer(100);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.reactive;
+
+import java.util.List;
+import reactor.core.publisher.Flux;
+import reactor.core.publisher.Mono;
+
+public class EventStore {
+    public Mono<List<String>> all(Flux<String> events) {
+        return events.collectList();
+    }
+}
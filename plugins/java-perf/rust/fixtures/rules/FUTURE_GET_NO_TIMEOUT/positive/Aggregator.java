@@ -0,0 +1,10 @@
+package com.acme.async;
+
+import java.util.concurrent.ExecutionException;
+import java.util.concurrent.Future;
+
+public class Aggregator {
+    public String await(Future<String> future) throws InterruptedException, ExecutionException {
+        return future.get();
+    }
+}
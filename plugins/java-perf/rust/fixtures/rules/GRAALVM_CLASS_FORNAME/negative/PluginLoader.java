@@ -0,0 +1,15 @@
+package com.acme.plugin;
+
+import java.util.Map;
+
+public class PluginLoader {
+    private final Map<String, Class<?>> registry;
+
+    public PluginLoader(Map<String, Class<?>> registry) {
+        this.registry = registry;
+    }
+
+    public Class<?> load(String name) {
+        return registry.get(name);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.plugin;
+
+import java.util.function.Function;
+
+public class Dispatcher {
+    public Object call(Function<Object, Object> handler, Object target) {
+        return handler.apply(target);
+    }
+}
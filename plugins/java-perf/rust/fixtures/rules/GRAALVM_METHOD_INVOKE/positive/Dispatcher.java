@@ -0,0 +1,9 @@
+package com.acme.plugin;
+
+import java.lang.reflect.Method;
+
+public class Dispatcher {
+    public Object call(Method handler, Object target) throws Exception {
+        return handler.invoke(target);
+    }
+}
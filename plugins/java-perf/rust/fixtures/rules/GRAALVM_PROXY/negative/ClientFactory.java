@@ -0,0 +1,9 @@
+package com.acme.plugin;
+
+import java.util.function.Function;
+
+public class ClientFactory {
+    public <T> T create(Class<T> api, Function<Class<T>, T> constructor) {
+        return constructor.apply(api);
+    }
+}
@@ -0,0 +1,10 @@
+package com.acme.plugin;
+
+import java.lang.reflect.InvocationHandler;
+import java.lang.reflect.Proxy;
+
+public class ClientFactory {
+    public Object create(Class<?> api, InvocationHandler handler) {
+        return Proxy.newProxyInstance(api.getClassLoader(), new Class<?>[] {api}, handler);
+    }
+}
@@ -0,0 +1,20 @@
+package com.acme.client;
+
+import java.time.Duration;
+import org.springframework.boot.web.client.RestTemplateBuilder;
+import org.springframework.http.client.reactive.ClientHttpConnector;
+import org.springframework.web.client.RestTemplate;
+import org.springframework.web.reactive.function.client.WebClient;
+
+public class GatewayConfig {
+    public RestTemplate restTemplate() {
+        return new RestTemplateBuilder()
+                .setConnectTimeout(Duration.ofSeconds(2))
+                .setReadTimeout(Duration.ofSeconds(5))
+                .build();
+    }
+
+    public WebClient webClient(ClientHttpConnector connector) {
+        return WebClient.builder().clientConnector(connector).build();
+    }
+}
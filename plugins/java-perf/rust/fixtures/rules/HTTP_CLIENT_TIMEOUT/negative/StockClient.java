@@ -0,0 +1,13 @@
+package com.acme.client;
+
+public class StockClient {
+    private final StockGateway gateway;
+
+    public StockClient(StockGateway gateway) {
+        this.gateway = gateway;
+    }
+
+    public String stock(String sku) {
+        return gateway.lookup(sku);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.client;
+
+import org.springframework.web.reactive.function.client.WebClient;
+
+public class GatewayConfig {
+    public WebClient webClient() {
+        return WebClient.builder().baseUrl("http://inventory").build();
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.client;
+
+import java.net.http.HttpClient;
+
+public class StockClient {
+    public HttpClient client() {
+        return HttpClient.newHttpClient();
+    }
+}
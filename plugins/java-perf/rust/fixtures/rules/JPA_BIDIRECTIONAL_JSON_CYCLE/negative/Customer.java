@@ -0,0 +1,13 @@
+package com.acme.domain;
+
+import com.fasterxml.jackson.annotation.JsonIgnore;
+import jakarta.persistence.Entity;
+import jakarta.persistence.OneToMany;
+import java.util.List;
+
+@Entity
+public class Customer {
+    @JsonIgnore
+    @OneToMany(mappedBy = "customer")
+    private List<Order> orders;
+}
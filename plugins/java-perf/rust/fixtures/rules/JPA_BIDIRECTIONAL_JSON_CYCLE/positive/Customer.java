@@ -0,0 +1,11 @@
+package com.acme.domain;
+
+import jakarta.persistence.Entity;
+import jakarta.persistence.OneToMany;
+import java.util.List;
+
+@Entity
+public class Customer {
+    @OneToMany(mappedBy = "customer")
+    private List<Order> orders;
+}
@@ -0,0 +1,7 @@
+package com.acme.io;
+
+public class Buffers {
+    public byte[] allocate() {
+        return new byte[8192];
+    }
+}
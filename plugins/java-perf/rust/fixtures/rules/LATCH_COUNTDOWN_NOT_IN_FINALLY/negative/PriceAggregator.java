@@ -0,0 +1,28 @@
+package com.acme.pricing;
+
+import java.util.List;
+import java.util.concurrent.CountDownLatch;
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.TimeUnit;
+
+public class PriceAggregator {
+    private final ExecutorService executor;
+
+    public PriceAggregator(ExecutorService executor) {
+        this.executor = executor;
+    }
+
+    public void refresh(List<Supplier> suppliers) throws InterruptedException {
+        CountDownLatch latch = new CountDownLatch(suppliers.size());
+        for (Supplier supplier : suppliers) {
+            executor.submit(() -> {
+                try {
+                    supplier.fetchPrices();
+                } finally {
+                    latch.countDown();
+                }
+            });
+        }
+        latch.await(30, TimeUnit.SECONDS);
+    }
+}
@@ -0,0 +1,13 @@
+package com.acme.dao;
+
+public class UserDao {
+    private static final String SEARCH = """
+        SELECT id, name
+        FROM users
+        WHERE name LIKE '%smith'
+        """;
+
+    public String query() {
+        return SEARCH;
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.media;
+
+import java.util.ArrayList;
+import java.util.List;
+
+public class Playlist {
+    private final List<String> tracks = new ArrayList<>();
+
+    public String track(int index) {
+        return tracks.get(index);
+    }
+}
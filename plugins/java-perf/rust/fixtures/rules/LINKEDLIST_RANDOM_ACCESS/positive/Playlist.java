@@ -0,0 +1,11 @@
+package com.acme.media;
+
+import java.util.LinkedList;
+
+public class Playlist {
+    private final LinkedList<String> tracks = new LinkedList<>();
+
+    public String track(int index) {
+        return tracks.get(index);
+    }
+}
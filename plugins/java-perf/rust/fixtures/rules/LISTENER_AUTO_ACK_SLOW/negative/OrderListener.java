@@ -0,0 +1,18 @@
+package com.acme.mq;
+
+import org.springframework.kafka.annotation.KafkaListener;
+import org.springframework.kafka.support.Acknowledgment;
+
+public class OrderListener {
+    private final OrderRepository orderRepository;
+
+    public OrderListener(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    @KafkaListener(topics = "orders")
+    public void onOrder(String payload, Acknowledgment ack) {
+        orderRepository.saveRaw(payload);
+        ack.acknowledge();
+    }
+}
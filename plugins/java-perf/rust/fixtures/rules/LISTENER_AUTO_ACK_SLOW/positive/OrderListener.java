@@ -0,0 +1,16 @@
+package com.acme.mq;
+
+import org.springframework.kafka.annotation.KafkaListener;
+
+public class OrderListener {
+    private final OrderRepository orderRepository;
+
+    public OrderListener(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    @KafkaListener(topics = "orders")
+    public void onOrder(String payload) {
+        orderRepository.saveRaw(payload);
+    }
+}
@@ -0,0 +1,18 @@
+package com.acme.mq;
+
+import org.springframework.kafka.annotation.KafkaListener;
+import org.springframework.kafka.support.Acknowledgment;
+
+public class OrderListener {
+    private final OrderHandler handler;
+
+    public OrderListener(OrderHandler handler) {
+        this.handler = handler;
+    }
+
+    @KafkaListener(topics = "orders")
+    public void onOrder(String payload, Acknowledgment ack) {
+        handler.handle(payload);
+        ack.acknowledge();
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.mq;
+
+import org.springframework.kafka.annotation.KafkaListener;
+import org.springframework.kafka.support.Acknowledgment;
+
+public class OrderListener {
+    @KafkaListener(topics = "orders")
+    public void onOrder(String payload, Acknowledgment ack) throws InterruptedException {
+        Thread.sleep(500);
+        ack.acknowledge();
+    }
+}
@@ -0,0 +1,18 @@
+package com.acme.mq;
+
+import org.springframework.kafka.annotation.KafkaListener;
+import org.springframework.kafka.support.Acknowledgment;
+
+public class InvoiceListener {
+    private final InvoiceHandler handler;
+
+    public InvoiceListener(InvoiceHandler handler) {
+        this.handler = handler;
+    }
+
+    @KafkaListener(topics = "invoices")
+    public void onInvoice(String payload, Acknowledgment ack) {
+        handler.handle(payload);
+        ack.acknowledge();
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.mq;
+
+import java.util.concurrent.ArrayBlockingQueue;
+import java.util.concurrent.BlockingQueue;
+import org.springframework.amqp.rabbit.annotation.RabbitListener;
+
+public class EventCollector {
+    private final BlockingQueue<String> pending = new ArrayBlockingQueue<>(1000);
+
+    @RabbitListener(queues = "events")
+    public void onEvent(String event) {
+        pending.offer(event);
+    }
+}
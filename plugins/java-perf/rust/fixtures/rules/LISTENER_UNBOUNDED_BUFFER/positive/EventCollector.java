@@ -0,0 +1,14 @@
+package com.acme.mq;
+
+import java.util.ArrayList;
+import java.util.List;
+import org.springframework.amqp.rabbit.annotation.RabbitListener;
+
+public class EventCollector {
+    private final List<String> pending = new ArrayList<>();
+
+    @RabbitListener(queues = "events")
+    public void onEvent(String event) {
+        pending.add(event);
+    }
+}
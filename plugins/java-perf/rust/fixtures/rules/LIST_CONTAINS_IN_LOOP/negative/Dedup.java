@@ -0,0 +1,16 @@
+package com.acme.util;
+
+import java.util.HashSet;
+import java.util.List;
+import java.util.Set;
+
+public class Dedup {
+    public int common(List<String> left, List<String> right) {
+        Set<String> lookup = new HashSet<>(right);
+        int hits = 0;
+        for (String item : left) {
+            hits = lookup.contains(item) ? hits + 1 : hits;
+        }
+        return hits;
+    }
+}
@@ -0,0 +1,13 @@
+package com.acme.util;
+
+import java.util.List;
+
+public class Dedup {
+    public int common(List<String> left, List<String> right) {
+        int hits = 0;
+        for (String item : left) {
+            hits = right.contains(item) ? hits + 1 : hits;
+        }
+        return hits;
+    }
+}
@@ -0,0 +1,17 @@
+package com.acme.stock;
+
+import java.util.concurrent.locks.ReentrantLock;
+
+public class Inventory {
+    private final ReentrantLock lock = new ReentrantLock();
+    private int stock;
+
+    public void take() {
+        lock.lock();
+        try {
+            stock--;
+        } finally {
+            lock.unlock();
+        }
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.stock;
+
+import java.util.concurrent.locks.ReentrantLock;
+
+public class Inventory {
+    private final ReentrantLock lock = new ReentrantLock();
+    private int stock;
+
+    public void take() {
+        lock.lock();
+        stock--;
+        lock.unlock();
+    }
+}
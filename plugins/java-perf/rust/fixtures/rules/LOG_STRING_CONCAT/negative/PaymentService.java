@@ -0,0 +1,12 @@
+package com.acme.payment;
+
+import org.slf4j.Logger;
+import org.slf4j.LoggerFactory;
+
+public class PaymentService {
+    private static final Logger log = LoggerFactory.getLogger(PaymentService.class);
+
+    public void pay(String orderId) {
+        log.info("paying order {}", orderId);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.util;
+
+public class Matrix {
+    public long sum(int[] cells) {
+        long total = 0;
+        for (int i = 0; i < cells.length; i++) {
+            total = total + cells[i];
+        }
+        return total;
+    }
+}
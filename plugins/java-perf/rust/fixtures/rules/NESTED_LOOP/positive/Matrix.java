@@ -0,0 +1,13 @@
+package com.acme.util;
+
+public class Matrix {
+    public long sum(int[][] cells) {
+        long total = 0;
+        for (int i = 0; i < cells.length; i++) {
+            for (int j = 0; j < cells[i].length; j++) {
+                total = total + cells[i][j];
+            }
+        }
+        return total;
+    }
+}
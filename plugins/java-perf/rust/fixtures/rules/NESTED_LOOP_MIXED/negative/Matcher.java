@@ -0,0 +1,15 @@
+// reports-as: NESTED_LOOP
+package com.acme.util;
+
+import java.util.List;
+import java.util.Set;
+
+public class Matcher {
+    public int matches(List<String> left, Set<String> right) {
+        int hits = 0;
+        for (String a : left) {
+            hits = right.contains(a) ? hits + 1 : hits;
+        }
+        return hits;
+    }
+}
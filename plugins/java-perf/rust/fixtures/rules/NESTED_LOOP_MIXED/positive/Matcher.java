@@ -0,0 +1,16 @@
+// reports-as: NESTED_LOOP
+package com.acme.util;
+
+import java.util.List;
+
+public class Matcher {
+    public int matches(List<String> left, List<String> right) {
+        int hits = 0;
+        for (String a : left) {
+            for (String b : right) {
+                hits = a.equals(b) ? hits + 1 : hits;
+            }
+        }
+        return hits;
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.service;
+
+import java.util.List;
+
+public class OrderService {
+    private final OrderRepository orderRepository;
+
+    public OrderService(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    public List<Order> loadAll(List<Long> ids) {
+        return orderRepository.findAllById(ids);
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.service;
+
+public class OrderService {
+    private final OrderRepository orderRepository;
+
+    public OrderService(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    public void loadAll(long[] ids) {
+        for (int i = 0; i < ids.length; i++) {
+            orderRepository.findById(ids[i]);
+        }
+    }
+}
@@ -0,0 +1,14 @@
+// reports-as: N_PLUS_ONE
+package com.acme.service;
+
+import java.util.List;
+
+public class OrderService {
+    public long total(List<Long> amounts) {
+        long sum = 0;
+        for (Long amount : amounts) {
+            sum = sum + amount;
+        }
+        return sum;
+    }
+}
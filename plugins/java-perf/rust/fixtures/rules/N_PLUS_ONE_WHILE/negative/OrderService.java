@@ -0,0 +1,13 @@
+// reports-as: N_PLUS_ONE
+package com.acme.service;
+
+public class OrderService {
+    public int countDown(int n) {
+        int steps = 0;
+        while (n > 0) {
+            n = n / 2;
+            steps++;
+        }
+        return steps;
+    }
+}
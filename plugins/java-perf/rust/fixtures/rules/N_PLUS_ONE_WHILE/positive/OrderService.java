@@ -0,0 +1,18 @@
+// reports-as: N_PLUS_ONE
+package com.acme.service;
+
+import java.util.Iterator;
+
+public class OrderService {
+    private final OrderRepository orderRepository;
+
+    public OrderService(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    public void loadAll(Iterator<Long> ids) {
+        while (ids.hasNext()) {
+            orderRepository.findById(ids.next());
+        }
+    }
+}
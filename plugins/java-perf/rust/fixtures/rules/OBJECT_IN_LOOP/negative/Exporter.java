@@ -0,0 +1,14 @@
+package com.acme.export;
+
+import java.util.List;
+
+public class Exporter {
+    public int export(List<String> rows) {
+        StringBuilder line = new StringBuilder();
+        for (String row : rows) {
+            line.setLength(0);
+            line.append(row);
+        }
+        return line.length();
+    }
+}
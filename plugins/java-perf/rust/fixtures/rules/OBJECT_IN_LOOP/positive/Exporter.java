@@ -0,0 +1,14 @@
+package com.acme.export;
+
+import java.util.List;
+
+public class Exporter {
+    public int export(List<String> rows) {
+        int size = 0;
+        for (String row : rows) {
+            StringBuilder line = new StringBuilder(row);
+            size = size + line.length();
+        }
+        return size;
+    }
+}
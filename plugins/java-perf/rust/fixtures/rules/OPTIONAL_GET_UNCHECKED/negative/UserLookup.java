@@ -0,0 +1,20 @@
+package com.acme.user;
+
+import java.util.Optional;
+
+public class UserLookup {
+    public String name(Optional<String> found) {
+        return found.orElseThrow();
+    }
+
+    public String label(Optional<String> found) {
+        if (!found.isPresent()) {
+            throw new IllegalArgumentException("missing");
+        }
+        return found.get();
+    }
+
+    public String title(Optional<String> found) {
+        return found.isEmpty() ? "-" : found.get();
+    }
+}
@@ -0,0 +1,19 @@
+package com.acme.user;
+
+import java.util.Optional;
+
+public class UserLookup {
+    public String name(Optional<String> found) {
+        return found.get();
+    }
+
+    public String label(Optional<String> found) {
+        if (found.isPresent()) {
+            audit(found);
+        }
+        return found.get();
+    }
+
+    private void audit(Optional<String> found) {
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Flux;
+
+public class Crunch {
+    public Flux<Integer> doubled(Flux<Integer> numbers) {
+        return numbers.map(n -> n * 2);
+    }
+}
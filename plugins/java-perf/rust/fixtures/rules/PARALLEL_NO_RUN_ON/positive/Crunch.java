@@ -0,0 +1,10 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Flux;
+import reactor.core.publisher.ParallelFlux;
+
+public class Crunch {
+    public ParallelFlux<Integer> split(Flux<Integer> numbers) {
+        return numbers.parallel();
+    }
+}
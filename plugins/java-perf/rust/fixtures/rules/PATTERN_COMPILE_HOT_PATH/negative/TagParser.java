@@ -0,0 +1,16 @@
+package com.acme.text;
+
+import java.util.List;
+import java.util.regex.Pattern;
+
+public class TagParser {
+    private static final Pattern TAG = Pattern.compile("#[a-z]+");
+
+    public int count(List<String> lines) {
+        int hits = 0;
+        for (String line : lines) {
+            hits = TAG.matcher(line).find() ? hits + 1 : hits;
+        }
+        return hits;
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.text;
+
+import java.util.List;
+import java.util.regex.Pattern;
+
+public class TagParser {
+    public int count(List<String> lines, String tag) {
+        int hits = 0;
+        for (String line : lines) {
+            hits = Pattern.compile(tag).matcher(line).find() ? hits + 1 : hits;
+        }
+        return hits;
+    }
+}
@@ -0,0 +1,24 @@
+package com.acme.pricing;
+
+import java.math.BigDecimal;
+import java.util.Map;
+import java.util.concurrent.CompletableFuture;
+import java.util.concurrent.ConcurrentHashMap;
+import javax.annotation.PostConstruct;
+import org.springframework.stereotype.Service;
+import org.springframework.web.client.RestTemplate;
+
+@Service
+public class RateCache {
+    private final RestTemplate restTemplate;
+    private final Map<String, BigDecimal> rates = new ConcurrentHashMap<>();
+
+    public RateCache(RestTemplate restTemplate) {
+        this.restTemplate = restTemplate;
+    }
+
+    @PostConstruct
+    public void load() {
+        CompletableFuture.runAsync(() -> rates.putAll(restTemplate.getForObject("http://fx/rates", Map.class)));
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.auth;
+
+import java.util.concurrent.ThreadLocalRandom;
+
+public class TokenGenerator {
+    public int next() {
+        return ThreadLocalRandom.current().nextInt();
+    }
+}
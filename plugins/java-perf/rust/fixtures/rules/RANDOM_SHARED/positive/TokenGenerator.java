@@ -0,0 +1,11 @@
+package com.acme.auth;
+
+import java.util.Random;
+
+public class TokenGenerator {
+    private static final Random random = new Random();
+
+    public int next() {
+        return random.nextInt();
+    }
+}
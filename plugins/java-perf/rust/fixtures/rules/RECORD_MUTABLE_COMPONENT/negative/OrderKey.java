@@ -0,0 +1,9 @@
+package com.acme.order;
+
+import java.util.List;
+
+public record OrderKey(String tenant, List<String> skus) {
+    public OrderKey {
+        skus = List.copyOf(skus);
+    }
+}
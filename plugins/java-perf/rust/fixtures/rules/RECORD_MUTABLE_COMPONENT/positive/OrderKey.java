@@ -0,0 +1,6 @@
+package com.acme.order;
+
+import java.util.List;
+
+public record OrderKey(String tenant, List<String> skus) {
+}
@@ -0,0 +1,21 @@
+package com.acme.session;
+
+import java.util.Map;
+import org.springframework.data.redis.core.StringRedisTemplate;
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.PathVariable;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class SessionController {
+    private final StringRedisTemplate redisTemplate;
+
+    public SessionController(StringRedisTemplate redisTemplate) {
+        this.redisTemplate = redisTemplate;
+    }
+
+    @GetMapping("/sessions/{id}")
+    public Map<Object, Object> session(@PathVariable String id) {
+        return redisTemplate.opsForHash().entries("session:" + id);
+    }
+}
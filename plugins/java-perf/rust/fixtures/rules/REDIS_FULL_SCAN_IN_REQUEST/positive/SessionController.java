@@ -0,0 +1,20 @@
+package com.acme.session;
+
+import java.util.Set;
+import org.springframework.data.redis.core.StringRedisTemplate;
+import org.springframework.web.bind.annotation.GetMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class SessionController {
+    private final StringRedisTemplate redisTemplate;
+
+    public SessionController(StringRedisTemplate redisTemplate) {
+        this.redisTemplate = redisTemplate;
+    }
+
+    @GetMapping("/sessions")
+    public Set<String> sessions() {
+        return redisTemplate.keys("session:*");
+    }
+}
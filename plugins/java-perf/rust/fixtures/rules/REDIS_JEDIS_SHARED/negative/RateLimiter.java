@@ -0,0 +1,20 @@
+package com.acme.limit;
+
+import org.springframework.stereotype.Service;
+import redis.clients.jedis.Jedis;
+import redis.clients.jedis.JedisPool;
+
+@Service
+public class RateLimiter {
+    private final JedisPool pool;
+
+    public RateLimiter(JedisPool pool) {
+        this.pool = pool;
+    }
+
+    public long hit(String user) {
+        try (Jedis jedis = pool.getResource()) {
+            return jedis.incr("rate:" + user);
+        }
+    }
+}
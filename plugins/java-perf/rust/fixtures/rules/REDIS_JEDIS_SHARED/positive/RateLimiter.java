@@ -0,0 +1,13 @@
+package com.acme.limit;
+
+import org.springframework.stereotype.Service;
+import redis.clients.jedis.Jedis;
+
+@Service
+public class RateLimiter {
+    private final Jedis jedis = new Jedis("redis", 6379);
+
+    public long hit(String user) {
+        return jedis.incr("rate:" + user);
+    }
+}
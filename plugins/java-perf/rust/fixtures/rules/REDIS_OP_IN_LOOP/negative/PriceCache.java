@@ -0,0 +1,18 @@
+package com.acme.price;
+
+import java.util.List;
+import java.util.stream.Collectors;
+import org.springframework.data.redis.core.StringRedisTemplate;
+
+public class PriceCache {
+    private final StringRedisTemplate redisTemplate;
+
+    public PriceCache(StringRedisTemplate redisTemplate) {
+        this.redisTemplate = redisTemplate;
+    }
+
+    public List<String> prices(List<String> skus) {
+        List<String> keys = skus.stream().map(sku -> "price:" + sku).collect(Collectors.toList());
+        return redisTemplate.opsForValue().multiGet(keys);
+    }
+}
@@ -0,0 +1,21 @@
+package com.acme.price;
+
+import java.util.ArrayList;
+import java.util.List;
+import org.springframework.data.redis.core.StringRedisTemplate;
+
+public class PriceCache {
+    private final StringRedisTemplate redisTemplate;
+
+    public PriceCache(StringRedisTemplate redisTemplate) {
+        this.redisTemplate = redisTemplate;
+    }
+
+    public List<String> prices(List<String> skus) {
+        List<String> prices = new ArrayList<>(skus.size());
+        for (String sku : skus) {
+            prices.add(redisTemplate.opsForValue().get("price:" + sku));
+        }
+        return prices;
+    }
+}
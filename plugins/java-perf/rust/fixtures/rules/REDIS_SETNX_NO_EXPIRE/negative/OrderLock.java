@@ -0,0 +1,16 @@
+package com.acme.order;
+
+import java.time.Duration;
+import org.springframework.data.redis.core.StringRedisTemplate;
+
+public class OrderLock {
+    private final StringRedisTemplate redisTemplate;
+
+    public OrderLock(StringRedisTemplate redisTemplate) {
+        this.redisTemplate = redisTemplate;
+    }
+
+    public boolean tryLock(String orderId) {
+        return Boolean.TRUE.equals(redisTemplate.opsForValue().setIfAbsent("lock:order:" + orderId, "1", Duration.ofSeconds(30)));
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.validation;
+
+import java.util.regex.Pattern;
+
+public class EmailValidator {
+    private static final Pattern EMAIL = Pattern.compile("^[a-zA-Z0-9]+@example\\.com$");
+
+    public boolean valid(String email) {
+        return EMAIL.matcher(email).matches();
+    }
+}
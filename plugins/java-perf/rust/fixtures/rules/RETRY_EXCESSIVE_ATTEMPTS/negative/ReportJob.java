@@ -0,0 +1,18 @@
+package com.acme.report;
+
+import org.springframework.retry.annotation.Backoff;
+import org.springframework.retry.annotation.Retryable;
+
+public class ReportJob {
+    private final ReportRenderer renderer;
+
+    public ReportJob(ReportRenderer renderer) {
+        this.renderer = renderer;
+    }
+
+    // 本地渲染重试，不放大下游流量
+    @Retryable(maxAttempts = 10, backoff = @Backoff(delay = 200, multiplier = 2))
+    public byte[] render(long id) {
+        return renderer.render(id);
+    }
+}
@@ -0,0 +1,16 @@
+package com.acme.order;
+
+import io.github.resilience4j.retry.annotation.Retry;
+
+public class OrderWriter {
+    private final OrderRepository orderRepository;
+
+    public OrderWriter(OrderRepository orderRepository) {
+        this.orderRepository = orderRepository;
+    }
+
+    @Retry(name = "orders")
+    public Order create(Order order) {
+        return orderRepository.save(order);
+    }
+}
@@ -0,0 +1,18 @@
+package com.acme.payment;
+
+import org.springframework.retry.annotation.Backoff;
+import org.springframework.retry.annotation.Retryable;
+import org.springframework.web.client.RestTemplate;
+
+public class PaymentGateway {
+    private final RestTemplate restTemplate;
+
+    public PaymentGateway(RestTemplate restTemplate) {
+        this.restTemplate = restTemplate;
+    }
+
+    @Retryable(retryFor = RemoteException.class, maxAttempts = 3, backoff = @Backoff(delay = 200, multiplier = 2))
+    public String status(String id) {
+        return restTemplate.getForObject("/payments/" + id, String.class);
+    }
+}
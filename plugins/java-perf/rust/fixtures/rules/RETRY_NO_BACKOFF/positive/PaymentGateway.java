@@ -0,0 +1,17 @@
+package com.acme.payment;
+
+import org.springframework.retry.annotation.Retryable;
+import org.springframework.web.client.RestTemplate;
+
+public class PaymentGateway {
+    private final RestTemplate restTemplate;
+
+    public PaymentGateway(RestTemplate restTemplate) {
+        this.restTemplate = restTemplate;
+    }
+
+    @Retryable(retryFor = RemoteException.class, maxAttempts = 3)
+    public String status(String id) {
+        return restTemplate.getForObject("/payments/" + id, String.class);
+    }
+}
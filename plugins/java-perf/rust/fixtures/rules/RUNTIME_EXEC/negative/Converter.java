@@ -0,0 +1,9 @@
+package com.acme.ops;
+
+import java.io.IOException;
+
+public class Converter {
+    public Process convert(String file) throws IOException {
+        return new ProcessBuilder("convert", file).start();
+    }
+}
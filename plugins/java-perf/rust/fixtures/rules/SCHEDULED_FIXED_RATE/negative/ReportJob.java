@@ -0,0 +1,13 @@
+package com.acme.job;
+
+import org.springframework.scheduling.annotation.Scheduled;
+
+public class ReportJob {
+    @Scheduled(fixedDelay = 5000)
+    public void run() {
+        generate();
+    }
+
+    private void generate() {
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.exec;
+
+import java.util.concurrent.ScheduledThreadPoolExecutor;
+
+public class Scheduler {
+    private final ScheduledThreadPoolExecutor executor = new ScheduledThreadPoolExecutor(0);
+
+    public ScheduledThreadPoolExecutor executor() {
+        return executor;
+    }
+}
@@ -0,0 +1,13 @@
+package com.acme.dao;
+
+public class UserDao {
+    private static final String FIND_ALL = """
+        SELECT *
+        FROM users
+        WHERE active = 1
+        """;
+
+    public String query() {
+        return FIND_ALL;
+    }
+}
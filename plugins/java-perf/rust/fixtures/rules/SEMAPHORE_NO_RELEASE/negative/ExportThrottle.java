@@ -0,0 +1,31 @@
+package com.acme.export;
+
+import java.util.concurrent.Semaphore;
+import java.util.concurrent.TimeUnit;
+
+public class ExportThrottle {
+    private final Semaphore permits = new Semaphore(4);
+
+    public byte[] export(Report report) throws InterruptedException {
+        if (!permits.tryAcquire(5, TimeUnit.SECONDS)) {
+            throw new IllegalStateException("export busy");
+        }
+        try {
+            return report.render();
+        } finally {
+            permits.release();
+        }
+    }
+
+    public boolean tryExport(Report report) throws InterruptedException {
+        if (!permits.tryAcquire(1, TimeUnit.SECONDS)) {
+            return false;
+        }
+        try {
+            report.render();
+            return true;
+        } finally {
+            permits.release();
+        }
+    }
+}
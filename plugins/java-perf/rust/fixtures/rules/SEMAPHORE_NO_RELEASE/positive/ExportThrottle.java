@@ -0,0 +1,17 @@
+package com.acme.export;
+
+import java.util.concurrent.Semaphore;
+import java.util.concurrent.TimeUnit;
+
+public class ExportThrottle {
+    private final Semaphore permits = new Semaphore(4);
+
+    public byte[] export(Report report) throws InterruptedException {
+        if (!permits.tryAcquire(5, TimeUnit.SECONDS)) {
+            throw new IllegalStateException("export busy");
+        }
+        byte[] data = report.render();
+        permits.release();
+        return data;
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.events;
+
+import com.fasterxml.jackson.databind.ObjectMapper;
+
+public class EventPublisher {
+    private static final ObjectMapper MAPPER = new ObjectMapper();
+
+    public String encode(Object event) throws Exception {
+        return MAPPER.writeValueAsString(event);
+    }
+}
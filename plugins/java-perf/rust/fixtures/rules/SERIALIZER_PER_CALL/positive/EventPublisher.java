@@ -0,0 +1,10 @@
+package com.acme.events;
+
+import com.fasterxml.jackson.databind.ObjectMapper;
+
+public class EventPublisher {
+    public String encode(Object event) throws Exception {
+        ObjectMapper mapper = new ObjectMapper();
+        return mapper.writeValueAsString(event);
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.util;
+
+import java.time.LocalDate;
+import java.time.format.DateTimeFormatter;
+
+public class DateUtil {
+    private static final DateTimeFormatter ISO = DateTimeFormatter.ISO_LOCAL_DATE;
+
+    public String format(LocalDate date) {
+        return ISO.format(date);
+    }
+}
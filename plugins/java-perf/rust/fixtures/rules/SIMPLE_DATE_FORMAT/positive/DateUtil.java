@@ -0,0 +1,10 @@
+package com.acme.util;
+
+import java.text.SimpleDateFormat;
+import java.util.Date;
+
+public class DateUtil {
+    public String format(Date date) {
+        return new SimpleDateFormat("yyyy-MM-dd").format(date);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Sinks;
+
+public class Notifier {
+    public Sinks.Many<String> sink() {
+        return Sinks.many().multicast().onBackpressureBuffer();
+    }
+}
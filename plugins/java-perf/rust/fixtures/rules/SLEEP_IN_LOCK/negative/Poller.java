@@ -0,0 +1,7 @@
+package com.acme.util;
+
+public class Poller {
+    public void pause() throws InterruptedException {
+        Thread.sleep(100);
+    }
+}
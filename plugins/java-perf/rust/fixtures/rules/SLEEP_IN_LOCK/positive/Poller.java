@@ -0,0 +1,12 @@
+// also-triggers: SYNC_BLOCK
+package com.acme.util;
+
+public class Poller {
+    private final Object monitor = new Object();
+
+    public void pause() throws InterruptedException {
+        synchronized (monitor) {
+            Thread.sleep(100);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.cache;
+
+import java.lang.ref.WeakReference;
+
+public class ImageCache {
+    public WeakReference<byte[]> wrap(byte[] image) {
+        return new WeakReference<byte[]>(image);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.cache;
+
+import java.lang.ref.SoftReference;
+
+public class ImageCache {
+    public SoftReference<byte[]> wrap(byte[] image) {
+        return new SoftReference<byte[]>(image);
+    }
+}
@@ -0,0 +1,18 @@
+package com.acme.order;
+
+import java.util.List;
+import org.springframework.jdbc.core.JdbcTemplate;
+
+public class OrderDao {
+    private static final String ORDER_TABLE = "orders";
+
+    private final JdbcTemplate jdbcTemplate;
+
+    public OrderDao(JdbcTemplate jdbcTemplate) {
+        this.jdbcTemplate = jdbcTemplate;
+    }
+
+    public List<Order> findByCustomer(String customerId) {
+        return jdbcTemplate.query("SELECT id, total FROM " + ORDER_TABLE + " WHERE customer_id = ?", new OrderRowMapper(), customerId);
+    }
+}
@@ -0,0 +1,16 @@
+package com.acme.order;
+
+import java.util.List;
+import org.springframework.jdbc.core.JdbcTemplate;
+
+public class OrderDao {
+    private final JdbcTemplate jdbcTemplate;
+
+    public OrderDao(JdbcTemplate jdbcTemplate) {
+        this.jdbcTemplate = jdbcTemplate;
+    }
+
+    public List<Order> findByCustomer(String customerId) {
+        return jdbcTemplate.query("SELECT id, total FROM orders WHERE customer_id = '" + customerId + "'", new OrderRowMapper());
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.cache;
+
+import java.util.HashMap;
+import java.util.Map;
+
+public class UserCache {
+    private final Map<Long, String> names = new HashMap<>();
+
+    public void put(long id, String name) {
+        names.put(id, name);
+    }
+}
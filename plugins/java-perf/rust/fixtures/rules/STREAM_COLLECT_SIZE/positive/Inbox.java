@@ -0,0 +1,9 @@
+package com.acme.mail;
+
+import java.util.List;
+
+public class Inbox {
+    public int unread(List<Message> messages) {
+        return messages.stream().filter(Message::isUnread).toList().size();
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.mail;
+
+import java.util.List;
+
+public class Inbox {
+    public boolean hasUnread(List<Message> messages) {
+        return messages.stream().anyMatch(Message::isUnread);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.stats;
+
+import java.util.List;
+import java.util.Map;
+import java.util.stream.Collectors;
+
+public class OrderStats {
+    public Map<Status, Long> summary(List<Order> orders) {
+        return orders.stream().collect(Collectors.groupingBy(Order::getStatus, Collectors.counting()));
+    }
+}
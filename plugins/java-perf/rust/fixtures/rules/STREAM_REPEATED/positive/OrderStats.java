@@ -0,0 +1,12 @@
+package com.acme.stats;
+
+import java.util.List;
+
+public class OrderStats {
+    public String summary(List<Order> orders) {
+        long paid = orders.stream().filter(Order::isPaid).count();
+        long shipped = orders.stream().filter(Order::isShipped).count();
+        long refunded = orders.stream().filter(Order::isRefunded).count();
+        return paid + "/" + shipped + "/" + refunded;
+    }
+}
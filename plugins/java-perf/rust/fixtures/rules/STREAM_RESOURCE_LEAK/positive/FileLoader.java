@@ -0,0 +1,16 @@
+package com.acme.io;
+
+import java.io.BufferedReader;
+import java.io.IOException;
+import java.io.Reader;
+
+public class FileLoader {
+    public String firstLine(Reader source) {
+        try {
+            BufferedReader reader = new BufferedReader(source);
+            return reader.readLine();
+        } catch (IOException e) {
+            throw new IllegalStateException(e);
+        }
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.export;
+
+import java.util.List;
+
+public class CsvWriter {
+    public String join(List<String> cells) {
+        return String.join(",", cells);
+    }
+}
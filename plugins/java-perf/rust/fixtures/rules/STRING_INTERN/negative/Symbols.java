@@ -0,0 +1,7 @@
+package com.acme.util;
+
+public class Symbols {
+    public String canonical(String name) {
+        return name.trim();
+    }
+}
@@ -0,0 +1,15 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Flux;
+
+public class EventRelay {
+    public void relay(Flux<String> events) {
+        events.subscribe(e -> forward(e), err -> report(err));
+    }
+
+    private void forward(String e) {
+    }
+
+    private void report(Throwable err) {
+    }
+}
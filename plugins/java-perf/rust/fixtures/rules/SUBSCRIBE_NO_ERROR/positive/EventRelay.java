@@ -0,0 +1,12 @@
+package com.acme.reactive;
+
+import reactor.core.publisher.Flux;
+
+public class EventRelay {
+    public void relay(Flux<String> events) {
+        events.subscribe(e -> forward(e));
+    }
+
+    private void forward(String e) {
+    }
+}
@@ -0,0 +1,17 @@
+package com.acme.events;
+
+import java.util.List;
+
+public class EventRouter {
+    public long route(List<Event> events) {
+        long weight = 0;
+        for (Event event : events) {
+            long w = switch (event) {
+                case Created c -> c.size();
+                case Deleted d -> 1;
+            };
+            weight = weight + w;
+        }
+        return weight;
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.session;
+
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+import org.springframework.stereotype.Service;
+
+@Service
+public class SessionRegistry {
+    private final Map<String, String> sessions = new ConcurrentHashMap<>();
+
+    public void register(String id, String user) {
+        sessions.put(id, user);
+    }
+}
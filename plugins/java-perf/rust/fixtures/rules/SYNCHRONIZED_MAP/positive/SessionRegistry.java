@@ -0,0 +1,15 @@
+package com.acme.session;
+
+import java.util.Collections;
+import java.util.HashMap;
+import java.util.Map;
+import org.springframework.stereotype.Service;
+
+@Service
+public class SessionRegistry {
+    private final Map<String, String> sessions = Collections.synchronizedMap(new HashMap<>());
+
+    public void register(String id, String user) {
+        sessions.put(id, user);
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.util;
+
+import java.util.Map;
+import java.util.concurrent.ConcurrentHashMap;
+
+public class Registry {
+    private final Map<String, String> entries = new ConcurrentHashMap<>();
+
+    public void put(String key, String value) {
+        entries.put(key, value);
+    }
+}
@@ -0,0 +1,14 @@
+package com.acme.util;
+
+import java.util.HashMap;
+import java.util.Map;
+
+public class Registry {
+    private final Map<String, String> entries = new HashMap<>();
+
+    public void put(String key, String value) {
+        synchronized (entries) {
+            entries.put(key, value);
+        }
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.util;
+
+import java.util.concurrent.atomic.LongAdder;
+
+public class Counter {
+    private final LongAdder count = new LongAdder();
+
+    public void increment() {
+        count.increment();
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.util;
+
+public class Counter {
+    private long count;
+
+    public synchronized void increment() {
+        count++;
+    }
+}
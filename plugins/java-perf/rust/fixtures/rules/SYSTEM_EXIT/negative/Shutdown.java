@@ -0,0 +1,7 @@
+package com.acme.ops;
+
+public class Shutdown {
+    public void abort() {
+        throw new IllegalStateException("fatal configuration error");
+    }
+}
@@ -0,0 +1,20 @@
+package com.acme.order;
+
+import org.junit.jupiter.api.BeforeEach;
+import org.junit.jupiter.api.Test;
+import org.testcontainers.containers.PostgreSQLContainer;
+
+class OrderRepositoryIT {
+    private PostgreSQLContainer<?> postgres;
+
+    @BeforeEach
+    void startDatabase() {
+        postgres = new PostgreSQLContainer<>("postgres:16-alpine");
+        postgres.start();
+    }
+
+    @Test
+    void savesOrder() {
+        assertNotNull(postgres.getJdbcUrl());
+    }
+}
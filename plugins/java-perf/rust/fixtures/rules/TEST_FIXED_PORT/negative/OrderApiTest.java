@@ -0,0 +1,14 @@
+package com.acme.order;
+
+import org.junit.jupiter.api.Test;
+import org.springframework.boot.test.web.server.LocalServerPort;
+
+class OrderApiTest {
+    @LocalServerPort
+    private int port;
+
+    @Test
+    void listsOrders() {
+        assertEquals(200, client.get(baseUrl(port)).status());
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.order;
+
+import org.junit.jupiter.api.Test;
+
+class OrderApiTest {
+    private static final String BASE_URL = "http://localhost:8080/orders";
+
+    @Test
+    void listsOrders() {
+        assertEquals(200, client.get(BASE_URL).status());
+    }
+}
@@ -0,0 +1,16 @@
+package com.acme.cart;
+
+import java.util.List;
+import org.junit.jupiter.api.Test;
+
+class CartServiceTest {
+    private static final String SKU = "sku-1";
+
+    private List<String> added;
+
+    @Test
+    void addsItem() {
+        added = List.of(SKU);
+        assertEquals(1, added.size());
+    }
+}
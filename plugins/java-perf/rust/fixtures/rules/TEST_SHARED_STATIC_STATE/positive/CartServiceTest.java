@@ -0,0 +1,13 @@
+package com.acme.cart;
+
+import org.junit.jupiter.api.Test;
+
+class CartServiceTest {
+    private static Cart cart = new Cart();
+
+    @Test
+    void addsItem() {
+        cart.add("sku-1");
+        assertEquals(1, cart.size());
+    }
+}
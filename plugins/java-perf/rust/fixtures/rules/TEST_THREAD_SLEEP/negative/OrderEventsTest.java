@@ -0,0 +1,14 @@
+package com.acme.order;
+
+import static org.awaitility.Awaitility.await;
+
+import java.time.Duration;
+import org.junit.jupiter.api.Test;
+
+class OrderEventsTest {
+    @Test
+    void publishesEvent() {
+        service.placeOrder(order);
+        await().atMost(Duration.ofSeconds(5)).until(listener::received);
+    }
+}
@@ -0,0 +1,12 @@
+package com.acme.order;
+
+import org.junit.jupiter.api.Test;
+
+class OrderEventsTest {
+    @Test
+    void publishesEvent() throws Exception {
+        service.placeOrder(order);
+        Thread.sleep(2000);
+        assertTrue(listener.received());
+    }
+}
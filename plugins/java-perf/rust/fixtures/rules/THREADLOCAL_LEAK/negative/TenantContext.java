@@ -0,0 +1,14 @@
+package com.acme.context;
+
+public class TenantContext {
+    private static final ThreadLocal<String> TENANT = new ThreadLocal<>();
+
+    public void runAs(String tenant, Runnable task) {
+        TENANT.set(tenant);
+        try {
+            task.run();
+        } finally {
+            TENANT.remove();
+        }
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.context;
+
+public class TenantContext {
+    private static final ThreadLocal<String> TENANT = new ThreadLocal<>();
+
+    public void bind(String tenant) {
+        TENANT.set(tenant);
+    }
+}
@@ -0,0 +1,21 @@
+package com.acme.web;
+
+import java.util.concurrent.Executor;
+import org.springframework.web.bind.annotation.PostMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class ExportController {
+    private final Executor exportExecutor;
+    private final ExportTask task;
+
+    public ExportController(Executor exportExecutor, ExportTask task) {
+        this.exportExecutor = exportExecutor;
+        this.task = task;
+    }
+
+    @PostMapping("/export")
+    public void export() {
+        exportExecutor.execute(task);
+    }
+}
@@ -0,0 +1,18 @@
+package com.acme.web;
+
+import org.springframework.web.bind.annotation.PostMapping;
+import org.springframework.web.bind.annotation.RestController;
+
+@RestController
+public class ExportController {
+    private final ExportTask task;
+
+    public ExportController(ExportTask task) {
+        this.task = task;
+    }
+
+    @PostMapping("/export")
+    public void export() {
+        new Thread(task, "export-worker").start();
+    }
+}
@@ -0,0 +1,10 @@
+package com.acme.ops;
+
+public class Heartbeat {
+    public Thread start(Runnable beat) {
+        Thread thread = new Thread(beat, "heartbeat");
+        thread.setDaemon(true);
+        thread.start();
+        return thread;
+    }
+}
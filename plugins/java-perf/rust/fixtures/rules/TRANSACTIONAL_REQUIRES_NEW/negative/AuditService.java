@@ -0,0 +1,10 @@
+package com.acme.audit;
+
+import org.springframework.transaction.annotation.Transactional;
+
+public class AuditService {
+    @Transactional(readOnly = true)
+    public AuditEntry record(String action) {
+        return new AuditEntry(action);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.audit;
+
+import org.springframework.transaction.annotation.Propagation;
+import org.springframework.transaction.annotation.Transactional;
+
+public class LedgerService {
+    @Transactional(propagation = Propagation.REQUIRED, timeout = 5)
+    public LedgerEntry post(String entry) {
+        return new LedgerEntry(entry);
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.audit;
+
+import org.springframework.transaction.annotation.Propagation;
+import org.springframework.transaction.annotation.Transactional;
+
+public class AuditService {
+    @Transactional(propagation = Propagation.REQUIRES_NEW)
+    public AuditEntry record(String action) {
+        return new AuditEntry(action);
+    }
+}
@@ -0,0 +1,16 @@
+package com.acme.bank;
+
+import org.springframework.transaction.annotation.Transactional;
+
+public class TransferService {
+    private final AccountRepository accounts;
+
+    public TransferService(AccountRepository accounts) {
+        this.accounts = accounts;
+    }
+
+    @Transactional
+    public long transfer(long from, long to, long amount) {
+        return accounts.move(from, to, amount);
+    }
+}
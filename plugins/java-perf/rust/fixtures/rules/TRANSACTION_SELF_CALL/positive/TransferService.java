@@ -0,0 +1,14 @@
+package com.acme.bank;
+
+import org.springframework.transaction.annotation.Transactional;
+
+public class TransferService {
+    @Transactional(rollbackFor = Exception.class)
+    public void transfer(long from, long to, long amount) {
+        debit(from, amount);
+    }
+
+    @Transactional
+    public void debit(long account, long amount) {
+    }
+}
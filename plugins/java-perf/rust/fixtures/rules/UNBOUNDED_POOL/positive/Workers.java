@@ -0,0 +1,10 @@
+package com.acme.exec;
+
+import java.util.concurrent.ExecutorService;
+import java.util.concurrent.Executors;
+
+public class Workers {
+    public ExecutorService pool() {
+        return Executors.newCachedThreadPool();
+    }
+}
@@ -0,0 +1,11 @@
+package com.acme.util;
+
+import java.util.concurrent.atomic.AtomicIntegerArray;
+
+public class Flags {
+    private final AtomicIntegerArray enabled = new AtomicIntegerArray(8);
+
+    public void enable(int i) {
+        enabled.incrementAndGet(i);
+    }
+}
@@ -0,0 +1,9 @@
+package com.acme.util;
+
+public class Flags {
+    private volatile boolean[] enabled = new boolean[8];
+
+    public void enable(int i) {
+        enabled[i] = true;
+    }
+}
@@ -23,56 +23,214 @@
 //! 9. Rayon reduce 并行合并符号表 (v9.4)
 //! 10. CallGraph 调用链追踪 (v9.4)
 
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
-use std::path::Path;
-use std::sync::Mutex;
+use std::path::{Path, PathBuf};
+use std::sync::{Condvar, Mutex};
+use std::time::Instant;
 use walkdir::WalkDir;
 use rayon::prelude::*;
 
-use crate::scanner::{CodeAnalyzer, Issue as ScannerIssue, Severity as ScannerSeverity};
-use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
-use crate::scanner::config::LineBasedConfigAnalyzer;
-use crate::scanner::dockerfile::DockerfileAnalyzer;
+use crate::effective_config;
+use crate::i18n;
+use crate::metrics::MethodMetrics;
+use crate::reachability;
+use crate::rule_pack;
+use crate::index_store;
+use crate::issue_link::IssueLinks;
+use crate::ownership::{self, IssueOwner};
+use crate::rules::advisory;
+use crate::rules::batch_insert;
+use crate::rules::cache_policy;
+use crate::rules::complexity_budget;
+use crate::rules::effort::{self, Effort};
+use crate::rules::http_timeout;
+use crate::rules::javap_verify;
+use crate::rules::log_level;
+use crate::rules::resilience;
+use crate::rules::schedule;
+use crate::rules::layer_weight::LayerWeighting;
+use crate::rules::profile::{self, RuleProfile};
+use crate::rules::severity_override;
+use crate::rules::source_map;
+use crate::rules::taxonomy::{self, Tag};
+use crate::rules::test_code::{self, TestCodeMode};
+use crate::scanner::{Issue as ScannerIssue, Severity as ScannerSeverity};
+use crate::scanner::tree_sitter_java::{JavaTreeSitterAnalyzer, SELF_RECEIVER};
+use crate::scanner::language::{AnalyzerKind, Analyzers, LanguageRegistry};
+use crate::scanner::config::{ConfigFileAnalyzer, PlaceholderEnv};
 use crate::taint::{CallGraph, MethodSig, LayerType};
-use crate::symbol_table::{LayerType as SymbolLayerType, ImportIndex};
-use std::collections::HashMap;
+use crate::symbol_table::{LayerType as SymbolLayerType, ImportIndex, SymbolTable};
+use std::collections::{BTreeMap, HashMap};
 
 // ============================================================================
 // 规则定义
 // ============================================================================
 
-/// 问题严重级别
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// 问题严重级别 (声明顺序即优先级，P0 最高；`--min-severity p1` 保留 P0 与 P1)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, clap::ValueEnum)]
 pub enum Severity {
-    P0, // 严重
-    P1, // 警告
+    P0,   // 严重
+    P1,   // 警告
+    P2,   // v9.6: 建议
+    Info, // v9.6: 提示 (代码风格/可维护性，不直接影响性能)
+}
+
+impl Severity {
+    /// 报告中的级别标记
+    pub fn emoji(self) -> &'static str {
+        match self {
+            Severity::P0 => "🔴",
+            Severity::P1 => "🟡",
+            Severity::P2 => "🔵",
+            Severity::Info => "⚪",
+        }
+    }
 }
 
 /// AST 检测问题
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AstIssue {
     pub severity: Severity,
+    #[serde(rename = "id")]
     pub issue_type: String,
     pub file: String,
     pub line: usize,
+    /// v9.6: 完整范围 (1-based，end_column 开区间；0 表示未知)
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
     pub description: String,
+    /// v9.6: `scan --owners` 时附加的 git blame / CODEOWNERS 归属
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<IssueOwner>,
+    /// v9.6: 预估修复工作量 (内置值，`.java-perf.toml [effort]` 可覆盖)
+    pub effort: Effort,
+}
+
+/// 扫描结果 (v9.6: 与报告格式解耦，供 Markdown/JSON/SARIF 共用)
+#[derive(Debug, Clone)]
+pub struct ScanResult {
+    pub file_count: usize,
+    pub issues: Vec<AstIssue>,
+    /// v9.6: 构建元数据 (`--meta key=value`)，如 git sha、构建号、服务名；只含用户提供的数据
+    pub meta: BTreeMap<String, String>,
+    /// v9.6: 扫描过程信息 (所用档案、测试源码处理等)，与用户元数据分开，不受 `--meta` 影响
+    pub info: ScanInfo,
+    /// v9.6: 分阶段耗时 (`--timing` 时存在)
+    pub timing: Option<ScanTiming>,
+    /// v9.6: Phase 2 计算的每个 Java 方法的复杂度度量 (JSON `metrics` 段)
+    pub metrics: Vec<MethodMetrics>,
+}
+
+/// 扫描过程信息 (JSON `scan` 段)，未发生的项为空
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ScanInfo {
+    /// 生效的规则档案
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+    /// 测试源码处理方式 (exclude 时附跳过的文件数)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub test_code: Option<String>,
+    /// `--verify-with-javap` 摘要
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub javap_verify: Option<String>,
+    /// `--owners` 找到的 CODEOWNERS 文件，存在时输出各团队问题数
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub codeowners: Option<String>,
+    /// `--owned-paths` / `--team` 隐藏的问题数 (按负责团队)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owned_hidden: Option<String>,
+    /// `scan-jar --package` 过滤
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// MCP `radar_scan` 命中缓存
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub cached: bool,
+}
+
+impl ScanInfo {
+    /// Markdown 报告中展示的 `key=value` 项
+    pub fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs: Vec<(&'static str, String)> = [
+            ("profile", &self.profile),
+            ("test_code", &self.test_code),
+            ("javap_verify", &self.javap_verify),
+            ("codeowners", &self.codeowners),
+            ("owned_hidden", &self.owned_hidden),
+            ("package", &self.package),
+        ].into_iter().filter_map(|(key, value)| Some((key, value.clone()?))).collect();
+        if self.cached {
+            pairs.push(("cached", "true".to_string()));
+        }
+        pairs
+    }
+}
+
+impl ScanResult {
+    /// v9.6: 只保留级别不低于 `min` 的问题 (`--min-severity`)
+    pub fn retain_min_severity(&mut self, min: Severity) {
+        self.issues.retain(|issue| issue.severity <= min);
+    }
+
+    /// v9.6: 只保留带有任一给定标签的问题 (`--only-tags`)
+    pub fn retain_tags(&mut self, tags: &[Tag]) {
+        self.issues.retain(|issue| taxonomy::has_any(&issue.issue_type, tags));
+    }
+}
+
+/// 扫描耗时统计 (v9.6: `scan --timing`)，单位毫秒
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ScanTiming {
+    /// 遍历目录
+    pub walk_ms: f64,
+    /// Phase 1: 符号表 / 调用图索引
+    pub index_ms: f64,
+    /// Phase 1 是否直接加载了 `.java-perf/index.bin`
+    pub index_cached: bool,
+    /// Phase 2: 深度分析
+    pub analyze_ms: f64,
+    /// 报告生成 (由调用方在渲染后填写)
+    pub report_ms: f64,
+    /// 最慢的文件 (Phase 2 单文件耗时，最多 10 个)
+    pub slowest_files: Vec<(String, f64)>,
+    /// 每条 AST 规则的累计查询耗时，降序
+    pub rules: Vec<(String, f64)>,
+}
+
+/// 最慢文件列表长度
+const SLOWEST_FILES: usize = 10;
+
+fn millis(d: std::time::Duration) -> f64 {
+    d.as_secs_f64() * 1000.0
 }
 
 // v9.1: Regex 规则已全部迁移到 tree_sitter_java.rs
 // 现在所有 Java 规则都通过 Tree-sitter AST 分析实现
 
-// Helper to convert ScannerIssue to AstIssue
-fn convert_issue(issue: ScannerIssue) -> AstIssue {
-    let sev = match issue.severity {
+pub(crate) fn convert_severity(severity: ScannerSeverity) -> Severity {
+    match severity {
         ScannerSeverity::P0 => Severity::P0,
         ScannerSeverity::P1 => Severity::P1,
-    };
+        ScannerSeverity::P2 => Severity::P2,
+        ScannerSeverity::Info => Severity::Info,
+    }
+}
+
+// Helper to convert ScannerIssue to AstIssue
+pub(crate) fn convert_issue(issue: ScannerIssue) -> AstIssue {
+    let effort = effort::builtin(&issue.id);
     AstIssue {
-        severity: sev,
+        severity: convert_severity(issue.severity),
         issue_type: issue.id,
         file: issue.file,
         line: issue.line,
+        column: issue.column,
+        end_line: issue.end_line,
+        end_column: issue.end_column,
         description: issue.description,
+        owner: None,
+        effort,
     }
 }
 
@@ -97,110 +255,146 @@ fn convert_issue(issue: ScannerIssue) -> AstIssue {
 /// compact: true 时只返回 P0，每个 issue 只有 id/file/line
 /// max_p1: compact=false 时最多返回的 P1 数量
 pub fn radar_scan(code_path: &str, compact: bool, max_p1: usize) -> Result<Value, Box<dyn std::error::Error>> {
+    let result = collect_issues(code_path)?;
+    Ok(render_markdown(&result, compact, max_p1))
+}
+
+/// 扫描选项 (v9.6)
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// 规则档案，为空时从 pom.xml / build.gradle 推断；所用档案记录在 `scan.profile`
+    pub profiles: Vec<RuleProfile>,
+    /// WASM 脚本规则目录，为空时使用 `<扫描根目录>/.java-perf/rules` (存在时)
+    pub rules_dir: Option<PathBuf>,
+    /// 统计分阶段/文件/规则耗时，结果写入 `ScanResult::timing`
+    pub timing: bool,
+    /// 扫描线程数，为空时使用 rayon 全局线程池 (CPU 核数)
+    pub threads: Option<usize>,
+    /// 同时读取的文件数上限，为空或 0 时不限制 (共享 CI 机器/网络盘上避免 IO 打满)
+    pub io_throttle: Option<usize>,
+    /// 按所在类层级升级问题级别 (目录扫描时生效)，默认使用内置加权表
+    pub layer_weighting: LayerWeighting,
+    /// 测试源码处理方式 (目录扫描时生效)，默认跳过 `src/test` 等测试源码
+    pub test_code: TestCodeMode,
+    /// 附加 git blame 作者/提交与 CODEOWNERS 负责团队 (目录扫描时生效)
+    pub owners: bool,
+    /// 规则级别覆盖 (`--severity RULE=LEVEL`)，优先于 `.java-perf.toml [severity]`
+    pub severity_overrides: Vec<(String, Severity)>,
+    /// 用 javap 在编译产物上校验 N+1 / 无界线程池的接收者类型 (目录扫描时生效)
+    pub verify_with_javap: bool,
+    /// javap 校验的附加 classpath (项目 `target/classes` 等自动加入)
+    pub classpath: Vec<PathBuf>,
+    /// 配置文件 `${VAR:default}` 占位符使用的变量文件 (`KEY=VALUE`)，为空时只使用默认值
+    pub env_file: Option<PathBuf>,
+    /// 按这些 Spring profile 计算生效配置后执行配置规则 (目录扫描时生效)，为空时逐文件检查
+    pub spring_profiles: Vec<String>,
+    /// 保留不可达方法中问题的级别 (默认在目录扫描时降低一级，见 [`crate::reachability`])
+    pub keep_unreachable: bool,
+}
+
+/// 文件读取并发限制 (计数信号量)
+struct IoThrottle {
+    limit: Option<usize>,
+    in_flight: Mutex<usize>,
+    released: Condvar,
+}
+
+impl IoThrottle {
+    fn new(limit: Option<usize>) -> Self {
+        Self { limit: limit.filter(|&n| n > 0), in_flight: Mutex::new(0), released: Condvar::new() }
+    }
+
+    fn read(&self, path: &Path) -> std::io::Result<String> {
+        let Some(limit) = self.limit else {
+            return std::fs::read_to_string(path);
+        };
+
+        {
+            let mut in_flight = self.in_flight.lock().unwrap_or_else(|e| e.into_inner());
+            while *in_flight >= limit {
+                in_flight = self.released.wait(in_flight).unwrap_or_else(|e| e.into_inner());
+            }
+            *in_flight += 1;
+        }
+
+        let result = std::fs::read_to_string(path);
+
+        *self.in_flight.lock().unwrap_or_else(|e| e.into_inner()) -= 1;
+        self.released.notify_one();
+        result
+    }
+}
+
+/// 执行双遍扫描并返回原始问题列表 (不做格式化)
+pub fn collect_issues(code_path: &str) -> Result<ScanResult, Box<dyn std::error::Error>> {
+    collect_issues_with(code_path, &ScanOptions::default())
+}
+
+/// 按扫描选项执行双遍扫描 (v9.6)
+///
+/// 指定 `threads` 时在独立的 rayon 线程池中执行，不影响全局线程池。
+pub fn collect_issues_with(code_path: &str, options: &ScanOptions) -> Result<ScanResult, Box<dyn std::error::Error>> {
+    let result = match options.threads {
+        Some(threads) => rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()?
+            .install(|| scan_project(code_path, options)),
+        None => scan_project(code_path, options),
+    };
+    result.map_err(|e| e as Box<dyn std::error::Error>)
+}
+
+/// 双遍扫描主体 (错误类型需要 Send 才能从线程池返回)
+fn scan_project(code_path: &str, options: &ScanOptions) -> Result<ScanResult, Box<dyn std::error::Error + Send + Sync>> {
     let path = Path::new(code_path);
+    let io = IoThrottle::new(options.io_throttle);
     let is_dir = path.is_dir();
+    let started = Instant::now();
     
     // 收集所有待扫描文件
-    let entries: Vec<_> = WalkDir::new(path)
+    let mut entries: Vec<_> = WalkDir::new(path)
         .follow_links(true)
         .into_iter()
         .filter_map(|e| e.ok())
         .filter(|e| e.file_type().is_file())
         .collect();
 
+    // v9.6: 默认跳过测试源码 (按相对路径判断，不受扫描根目录所在位置影响)
+    let mut test_files_skipped = 0;
+    if is_dir && options.test_code == TestCodeMode::Exclude {
+        let before = entries.len();
+        entries.retain(|e| !test_code::is_test_source(e.path().strip_prefix(path).unwrap_or(e.path())));
+        test_files_skipped = before - entries.len();
+    }
+
     let file_count = entries.len();
+    let walk_elapsed = started.elapsed();
+
+    // v9.6: 按语言注册表识别文件，只实例化项目中出现的语言对应的分析器
+    let registry = LanguageRegistry::builtin();
+    let kinds: Vec<Option<AnalyzerKind>> = entries.iter().map(|e| registry.analyzer_for(e.path())).collect();
 
     // 初始化分析器 (Arc 共享，只编译一次 queries)
-    let java_analyzer = std::sync::Arc::new(JavaTreeSitterAnalyzer::new()?);
-    let config_analyzer = LineBasedConfigAnalyzer::new().ok();
-    let docker_analyzer = DockerfileAnalyzer::new().ok();
+    let java_analyzer = std::sync::Arc::new(build_java_analyzer(path, is_dir, options)?);
+    let mut analyzers = Analyzers::for_kinds(kinds.iter().flatten().copied().filter(|&k| k != AnalyzerKind::Java));
+    let env = options.env_file.as_deref().map(PlaceholderEnv::load).transpose()?.unwrap_or_default();
+    if options.env_file.is_some() {
+        analyzers.replace(AnalyzerKind::Config, Box::new(ConfigFileAnalyzer::with_env(env.clone())?));
+    }
 
-    // === Phase 1: Indexing (构建全局符号表 + 调用图 + ImportIndex) ===
-    // v9.4: 使用 Rayon reduce 并行合并 SymbolTable 和 CallGraph
-    // v9.7: 收集 per-file ImportIndex 用于 FQN 解析
-    
-    /// Per-file import index storage
-    /// Maps file path (as String) to ImportIndex for that file
-    type ImportIndexMap = HashMap<String, ImportIndex>;
-    
-    let (symbol_table, call_graph, _import_indices) = if is_dir {
-        // 筛选 Java 文件
-        let java_files: Vec<_> = entries.iter()
-            .filter(|e| e.path().extension().and_then(|e| e.to_str()) == Some("java"))
+    // === Phase 1: Indexing (构建全局符号表 + 调用图) ===
+    let index_started = Instant::now();
+    let (ProjectIndex { symbol_table, call_graph }, index_cached) = if is_dir {
+        let java_files: Vec<&Path> = entries.iter()
+            .zip(&kinds)
+            .filter(|(_, kind)| **kind == Some(AnalyzerKind::Java))
+            .map(|(e, _)| e.path())
             .collect();
-            
-        if !java_files.is_empty() {
-            // 使用 reduce 并行两两合并
-            java_files.par_iter()
-                .map(|entry| {
-                    let mut local_table = crate::symbol_table::SymbolTable::new();
-                    let mut local_graph = CallGraph::new();
-                    let mut local_import_indices: ImportIndexMap = HashMap::new();
-                    
-                    if let Ok(content) = std::fs::read_to_string(entry.path()) {
-                        // 1. 提取符号和类信息 (v9.6: now includes ImportIndex)
-                        if let Ok((Some(type_info), bindings, import_index)) = java_analyzer.extract_symbols(&content, entry.path()) {
-                            let class_name = type_info.name.clone();
-                            let class_fqn = type_info.fqn.clone(); // v9.8: Use FQN for CallGraph
-                            let file_path_str = entry.path().to_string_lossy().to_string();
-                            
-                            // v9.7: Store ImportIndex for this file
-                            local_import_indices.insert(file_path_str, import_index.clone());
-                            
-                            // 根据 SymbolTable 的 LayerType 转换为 taint 的 LayerType
-                            let layer = match type_info.layer {
-                                SymbolLayerType::Controller => LayerType::Controller,
-                                SymbolLayerType::Service => LayerType::Service,
-                                SymbolLayerType::Repository => LayerType::Repository,
-                                _ => LayerType::Unknown,
-                            };
-                            
-                            // v9.8: 注册到 CallGraph 使用 FQN
-                            local_graph.register_class(&class_fqn, entry.path().to_path_buf(), layer);
-                            // Also register with simple name for backward compatibility
-                            local_graph.register_class(&class_name, entry.path().to_path_buf(), layer);
-                            
-                            // 注册到 SymbolTable (v9.7: use FQN-based registration)
-                            local_table.register_class_fqn(type_info);
-                            for binding in bindings {
-                                local_table.register_field(&class_name, binding);
-                            }
-                            
-                            // 2. 提取调用点并构建 CallGraph
-                            // v9.8: Use FQN resolution for call sites
-                            if let Ok(call_sites) = java_analyzer.extract_call_sites(&content, entry.path()) {
-                                for (caller_method, receiver, callee_method, line) in call_sites {
-                                    // v9.8: 构建调用关系，使用 FQN 解析
-                                    // Caller uses the class FQN directly
-                                    let caller = MethodSig::new_fqn(&class_fqn, &caller_method);
-                                    
-                                    // Callee: Try to resolve receiver to FQN using ImportIndex and local SymbolTable
-                                    // Note: receiver 可能是字段名，需要通过 SymbolTable 解析实际类型
-                                    let callee = MethodSig::resolve(&receiver, &callee_method, &import_index, &local_table);
-                                    
-                                    local_graph.add_call(caller, callee, entry.path().to_path_buf(), line);
-                                }
-                            }
-                        }
-                    }
-                    (local_table, local_graph, local_import_indices)
-                })
-                .reduce(
-                    || (crate::symbol_table::SymbolTable::new(), CallGraph::new(), HashMap::new()),
-                    |(mut acc_table, mut acc_graph, mut acc_imports), (table, graph, imports)| {
-                        acc_table.merge(table);
-                        acc_graph.merge(graph);
-                        // v9.7: Merge ImportIndex maps (per-file, no cross-contamination)
-                        acc_imports.extend(imports);
-                        (acc_table, acc_graph, acc_imports)
-                    }
-                )
-        } else {
-            (crate::symbol_table::SymbolTable::new(), CallGraph::new(), HashMap::new())
-        }
+        load_or_build_index(&java_analyzer, path, &java_files, &io)
     } else {
-        (crate::symbol_table::SymbolTable::new(), CallGraph::new(), HashMap::new())
+        (ProjectIndex::default(), false)
     };
+    let index_elapsed = index_started.elapsed();
     
     let symbol_table_ref = &symbol_table;
     let call_graph_ref = &call_graph; // v9.4: 用于 N+1 验证
@@ -208,60 +402,60 @@ pub fn radar_scan(code_path: &str, compact: bool, max_p1: usize) -> Result<Value
     // === Phase 2: Deep Analysis (深度扫描) ===
     // 使用 Mutex 保护共享状态 (rayon 并行安全)
     let issues: Mutex<Vec<AstIssue>> = Mutex::new(Vec::new());
+    let metrics: Mutex<Vec<MethodMetrics>> = Mutex::new(Vec::new());
+    let file_timings: Mutex<Vec<(String, f64)>> = Mutex::new(Vec::new());
+    let analyze_started = Instant::now();
 
     // 并行处理文件
-    entries.par_iter().for_each(|entry| {
+    entries.par_iter().zip(&kinds).for_each(|(entry, kind)| {
+        let file_started = options.timing.then(Instant::now);
         let file_path = entry.path();
-        let file_name_str = file_path.file_name()
-            .map(|n| n.to_string_lossy().to_string())
-            .unwrap_or_default();
-        let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
 
         // 本线程的 issues
         let mut local_issues: Vec<AstIssue> = Vec::new();
+        let mut local_metrics: Vec<MethodMetrics> = Vec::new();
 
-        if ext == "java" {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // v9.4: 传入 SymbolTable 和 CallGraph 用于语义分析和 N+1 验证
-                let symbol_ctx = if is_dir { Some(symbol_table_ref) } else { None };
-                let cg_ctx = if is_dir { Some(call_graph_ref) } else { None };
+        match kind {
+            Some(AnalyzerKind::Java) => {
+                if let Ok(content) = io.read(file_path) {
+                    // v9.4: 传入 SymbolTable 和 CallGraph 用于语义分析和 N+1 验证
+                    let symbol_ctx = if is_dir { Some(symbol_table_ref) } else { None };
+                    let cg_ctx = if is_dir { Some(call_graph_ref) } else { None };
 
-                if let Ok(ast_results) = java_analyzer.analyze_with_context(&content, file_path, symbol_ctx, cg_ctx) {
-                    local_issues.extend(ast_results.into_iter().map(convert_issue));
-                }
-            }
-        } else if ["yml", "yaml", "properties"].contains(&ext) {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // 3. Config Analysis
-                if let Some(analyzer) = &config_analyzer {
-                    // v9.5: 优先使用结构化 YAML 解析
-                    if ["yml", "yaml"].contains(&ext) {
-                        let structured_issues = analyzer.analyze_yaml_structured(&content, &file_name_str);
-                        if !structured_issues.is_empty() {
-                            local_issues.extend(structured_issues.into_iter().map(convert_issue));
-                        } else {
-                            // 备用：行匹配
-                            if let Ok(config_results) = analyzer.analyze(&content, file_path) {
-                                local_issues.extend(config_results.into_iter().map(convert_issue));
-                            }
-                        }
-                    } else {
-                        // properties 文件继续使用行匹配
-                        if let Ok(config_results) = analyzer.analyze(&content, file_path) {
-                            local_issues.extend(config_results.into_iter().map(convert_issue));
-                        }
+                    if let Ok((ast_results, method_metrics)) = java_analyzer.analyze_with_metrics(&content, file_path, symbol_ctx, cg_ctx) {
+                        local_issues.extend(ast_results.into_iter().map(convert_issue));
+                        local_metrics = method_metrics;
                     }
                 }
             }
-        } else if file_name_str == "Dockerfile" || file_name_str.starts_with("Dockerfile.") {
-            if let Ok(content) = std::fs::read_to_string(file_path) {
-                // 4. Dockerfile Analysis (v5.1 NEW)
-                if let Some(analyzer) = &docker_analyzer {
-                    if let Ok(docker_results) = analyzer.analyze(&content, file_path) {
-                        local_issues.extend(docker_results.into_iter().map(convert_issue));
-                    }
+            // 配置 / Dockerfile / 日志配置 / compose / CI 流水线
+            Some(kind) => {
+                if let Ok(content) = io.read(file_path) {
+                    local_issues.extend(analyzers.analyze(*kind, &content, file_path).into_iter().map(convert_issue));
                 }
             }
+            None => {}
+        }
+
+        // v9.6: 目录扫描时使用相对扫描根目录的路径 (CI 注解/SARIF 需要可定位的文件)
+        let display_path = if is_dir {
+            file_path.strip_prefix(path).ok().map(|rel| rel.to_string_lossy().replace('\\', "/"))
+        } else {
+            None
+        };
+        if let Some(rel) = &display_path {
+            for issue in &mut local_issues {
+                issue.file = rel.clone();
+            }
+            for method in &mut local_metrics {
+                method.file = rel.clone();
+            }
+        }
+
+        if let Some(file_started) = file_started {
+            let name = display_path.unwrap_or_else(|| file_path.to_string_lossy().to_string());
+            let mut timings = file_timings.lock().unwrap_or_else(|e| e.into_inner());
+            timings.push((name, millis(file_started.elapsed())));
         }
 
         // 合并到全局 issues
@@ -270,108 +464,591 @@ pub fn radar_scan(code_path: &str, compact: bool, max_p1: usize) -> Result<Value
             let mut global = issues.lock().unwrap_or_else(|e| e.into_inner());
             global.extend(local_issues);
         }
+        if !local_metrics.is_empty() {
+            metrics.lock().unwrap_or_else(|e| e.into_inner()).extend(local_metrics);
+        }
     });
 
+    let analyze_elapsed = analyze_started.elapsed();
+
     // 安全地解包：如果 mutex 被 poisoned，仍然获取内部数据
-    let issues = issues.into_inner().unwrap_or_else(|e| e.into_inner());
+    let mut issues = issues.into_inner().unwrap_or_else(|e| e.into_inner());
+    let mut metrics = metrics.into_inner().unwrap_or_else(|e| e.into_inner());
+    metrics.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+
+    // v9.6: target/generated-sources 等生成代码上的问题映射回源文件 (先于测试源码过滤与层级加权)
+    if is_dir {
+        source_map::apply(&mut issues, path);
+    }
+
+    // v9.6: 按 Spring profile 的生效配置执行配置规则 (替换逐文件检查的结果)
+    if is_dir && !options.spring_profiles.is_empty() {
+        effective_config::apply(&mut issues, path, &options.spring_profiles, env);
+    }
+
+    // v9.6: resilience4j 注解关联配置文件 (需 Phase 1 方法注解)，追加的问题同样经过档案与测试源码过滤
+    if is_dir {
+        resilience::apply(&mut issues, path, &symbol_table);
+    }
+
+    // v9.6: 默认单线程调度器上触发时刻重叠的 @Scheduled(cron) 任务 (需 Phase 1 方法注解)
+    if is_dir {
+        schedule::apply(&mut issues, path, &symbol_table);
+    }
+
+    // v9.6: 方法度量超出 .java-perf.toml 复杂度预算 (单文件扫描读取文件所在目录的配置)
+    let config_root = if is_dir { path } else { path.parent().unwrap_or(path) };
+    complexity_budget::apply(&mut issues, &metrics, config_root);
+
+    // v9.6: 规则档案过滤与级别调整
+    let profiles = if options.profiles.is_empty() {
+        RuleProfile::infer_from_path(path)
+    } else {
+        options.profiles.clone()
+    };
+    profile::apply(&profiles, &mut issues);
+
+    // v9.6: 测试源码只保留测试专用规则 (test-rules 模式)
+    if is_dir {
+        test_code::apply(options.test_code, &mut issues);
+    }
+
+    // v9.6: 循环内 save()/insert() 关联 JDBC 批处理配置
+    if is_dir {
+        batch_insert::apply(&mut issues, path);
+    }
+
+    // v9.6: 项目级 HTTP 超时配置 (spring.http.client.* / 定制器 Bean) 覆盖所有客户端
+    if is_dir {
+        http_timeout::apply(&mut issues, path);
+    }
+
+    // v9.6: spring.cache.caffeine.spec 设置了过期/容量时，交给 CacheManager 的 Caffeine 构建器由规格决定策略
+    if is_dir {
+        cache_policy::apply(&mut issues, path);
+    }
+
+    // v9.6: 生产 root 级别为 DEBUG 时，debug()/trace() 中的字符串拼接会被真正执行
+    if is_dir {
+        log_level::apply(&mut issues, path);
+    }
+
+    // v9.6: 构建文件中的依赖版本命中离线公告库 (resources/advisories.json + .java-perf/advisories.json)
+    if is_dir {
+        advisory::apply(&mut issues, path);
+    }
+
+    // v9.6: 层级加权 (需要 Phase 1 符号表)
+    if is_dir {
+        options.layer_weighting.apply(&mut issues, path, &symbol_table);
+    }
+
+    // v9.6: 调用图上无调用方的 Service / Repository 方法中的问题降低一级 (晚于层级加权，避免再被升级)
+    if is_dir && !options.keep_unreachable {
+        reachability::apply(&mut issues, &metrics, &symbol_table, &call_graph);
+    }
+
+    // v9.6: javap 校验接收者类型，确认的升级为 P0、排除的移除 (先于级别覆盖，用户覆盖优先)
+    let javap_summary = (is_dir && options.verify_with_javap).then(|| {
+        javap_verify::apply(&mut issues, path, &options.classpath)
+            .map(|summary| summary.display())
+            .unwrap_or_else(|| "skipped (javap not available)".to_string())
+    });
+
+    // v9.6: 规则级别覆盖 (.java-perf.toml [severity] + --severity)，最后执行以覆盖档案与层级加权的结果
+    severity_override::apply(&mut issues, config_root, &options.severity_overrides);
+    // v9.6: 修复工作量覆盖 (.java-perf.toml [effort])
+    effort::apply(&mut issues, config_root);
+
+    // v9.6: 并行扫描顺序不确定，按位置排序保证输出稳定 (diff/fingerprint 依赖)
+    issues.sort_by(|a, b| {
+        (&a.file, a.line, a.column, &a.issue_type).cmp(&(&b.file, b.line, b.column, &b.issue_type))
+    });
+
+    let mut info = ScanInfo {
+        profile: Some(profile::display(&profiles)),
+        test_code: is_dir.then(|| match options.test_code {
+            TestCodeMode::Exclude => format!("exclude ({test_files_skipped} files skipped)"),
+            mode => mode.name().to_string(),
+        }),
+        javap_verify: javap_summary,
+        ..Default::default()
+    };
+
+    // v9.6: 问题归属 (git blame + CODEOWNERS)
+    if is_dir && options.owners {
+        info.codeowners = ownership::attach(path, &mut issues);
+    }
+
+    let timing = options.timing.then(|| {
+        let mut slowest_files = file_timings.into_inner().unwrap_or_else(|e| e.into_inner());
+        slowest_files.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        slowest_files.truncate(SLOWEST_FILES);
+        ScanTiming {
+            walk_ms: millis(walk_elapsed),
+            index_ms: millis(index_elapsed),
+            index_cached,
+            analyze_ms: millis(analyze_elapsed),
+            report_ms: 0.0,
+            slowest_files,
+            rules: java_analyzer.rule_timings().into_iter().map(|(id, d)| (id.to_string(), millis(d))).collect(),
+        }
+    });
+
+    Ok(ScanResult { file_count, issues, meta: BTreeMap::new(), info, timing, metrics })
+}
+
+/// 渲染耗时统计 (v9.6: `scan --timing`)
+pub fn render_timing(timing: &ScanTiming) -> String {
+    let mut out = String::from("### ⏱️ Timing\n\n| Phase | ms |\n|---|---|\n");
+    let index_phase = if timing.index_cached { "phase-1 index (cached)" } else { "phase-1 index" };
+    for (phase, ms) in [
+        ("walk", timing.walk_ms),
+        (index_phase, timing.index_ms),
+        ("phase-2 analyze", timing.analyze_ms),
+        ("report", timing.report_ms),
+    ] {
+        out.push_str(&format!("| {phase} | {ms:.1} |\n"));
+    }
+
+    if !timing.slowest_files.is_empty() {
+        out.push_str("\n**Slowest files**\n\n| File | ms |\n|---|---|\n");
+        for (file, ms) in &timing.slowest_files {
+            out.push_str(&format!("| `{file}` | {ms:.1} |\n"));
+        }
+    }
+
+    if !timing.rules.is_empty() {
+        out.push_str("\n**Rules (cumulative query time)**\n\n| Rule | ms |\n|---|---|\n");
+        for (rule, ms) in &timing.rules {
+            out.push_str(&format!("| `{rule}` | {ms:.2} |\n"));
+        }
+    }
+    out
+}
+
+/// 项目索引 (Phase 1 产物): 全局符号表 + 调用图
+///
+/// v9.6: 独立出来供 `verify_issue` / 调用链查询复用，可持久化到 `.java-perf/index.bin`
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct ProjectIndex {
+    pub symbol_table: SymbolTable,
+    pub call_graph: CallGraph,
+}
+
+/// 只执行 Phase 1，为目录构建项目索引 (v9.6)
+///
+/// `.java-perf/index.bin` 新鲜时直接加载。
+pub fn index_project(code_path: &str) -> Result<ProjectIndex, Box<dyn std::error::Error>> {
+    let path = project_dir(code_path)?;
+    let java_files = java_files_under(path, TestCodeMode::default());
+    let java_files: Vec<&Path> = java_files.iter().map(PathBuf::as_path).collect();
+
+    let analyzer = JavaTreeSitterAnalyzer::new()?;
+    Ok(load_or_build_index(&analyzer, path, &java_files, &IoThrottle::new(None)).0)
+}
+
+/// `java-perf index`: 重建项目索引并写入 `.java-perf/index.bin` (v9.6)
+///
+/// 文件列表按 `test_code` 过滤，与使用相同 `--test-code` 的 `scan` 一致才能命中索引。
+pub fn write_index(code_path: &str, test_code: TestCodeMode) -> Result<Value, Box<dyn std::error::Error>> {
+    let started = Instant::now();
+    let path = project_dir(code_path)?;
+    let java_files = java_files_under(path, test_code);
+    let java_files: Vec<&Path> = java_files.iter().map(PathBuf::as_path).collect();
+
+    let analyzer = JavaTreeSitterAnalyzer::new()?;
+    let index = build_index(&analyzer, &canonical_paths(&java_files), &IoThrottle::new(None));
+    let classes = index.symbol_table.classes.len();
+    let call_sites: usize = index.call_graph.outgoing.values().map(Vec::len).sum();
+    let index_file = index_store::save(path, &java_files, index)?;
+    let elapsed_ms = millis(started.elapsed());
+
+    Ok(json!({
+        "index": index_file.to_string_lossy(),
+        "files": java_files.len(),
+        "classes": classes,
+        "call_sites": call_sites,
+        "elapsed_ms": elapsed_ms,
+        "report": format!(
+            "## 🗂️ Index\n\n- **文件**: {}\n- **Java 文件**: {}\n- **类**: {classes}\n- **调用点**: {call_sites}\n- **耗时**: {elapsed_ms:.1} ms\n",
+            index_file.display(), java_files.len()
+        ),
+    }))
+}
+
+fn project_dir(code_path: &str) -> Result<&Path, Box<dyn std::error::Error>> {
+    let path = Path::new(code_path);
+    if !path.is_dir() {
+        return Err(format!("Project directory not found: {code_path}").into());
+    }
+    Ok(path)
+}
+
+/// 项目下的 `.java` 文件，`Exclude` 时与 `scan` 一样跳过测试源码
+fn java_files_under(path: &Path, test_code: TestCodeMode) -> Vec<PathBuf> {
+    WalkDir::new(path)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().and_then(|e| e.to_str()) == Some("java"))
+        .filter(|e| test_code != TestCodeMode::Exclude || !test_code::is_test_source(e.path().strip_prefix(path).unwrap_or(e.path())))
+        .map(|e| e.into_path())
+        .collect()
+}
+
+/// 索引中记录规范化的绝对路径，持久化后与工作目录无关
+fn canonical_paths(files: &[&Path]) -> Vec<PathBuf> {
+    files.iter().map(|f| std::fs::canonicalize(f).unwrap_or_else(|_| f.to_path_buf())).collect()
+}
+
+/// 新鲜的持久化索引优先，否则在内存中重建 (不写回)；返回 (索引, 是否命中缓存)
+fn load_or_build_index(
+    java_analyzer: &JavaTreeSitterAnalyzer,
+    root: &Path,
+    java_files: &[&Path],
+    io: &IoThrottle,
+) -> (ProjectIndex, bool) {
+    match index_store::load_fresh(root, java_files) {
+        Some(index) => (index, true),
+        None => (build_index(java_analyzer, &canonical_paths(java_files), io), false),
+    }
+}
+
+/// Phase 1: 并行提取符号与调用点，构建全局符号表 + 调用图
+///
+/// v9.4: 使用 Rayon reduce 并行合并 SymbolTable 和 CallGraph
+/// v9.7: 收集 per-file ImportIndex 用于 FQN 解析
+fn build_index(java_analyzer: &JavaTreeSitterAnalyzer, java_files: &[PathBuf], io: &IoThrottle) -> ProjectIndex {
+    /// Per-file import index storage
+    /// Maps file path (as String) to ImportIndex for that file
+    type ImportIndexMap = HashMap<String, ImportIndex>;
+
+    let (symbol_table, call_graph, _import_indices): (SymbolTable, CallGraph, ImportIndexMap) =
+        // 使用 reduce 并行两两合并
+        java_files.par_iter()
+            .map(|file| {
+                let mut local_table = SymbolTable::new();
+                let mut local_graph = CallGraph::new();
+                let mut local_import_indices: ImportIndexMap = HashMap::new();
+            
+                if let Ok(content) = io.read(file) {
+                    // 1. 提取符号和类信息 (v9.6: now includes ImportIndex)
+                    if let Ok((Some(type_info), bindings, import_index)) = java_analyzer.extract_symbols(&content, file) {
+                        let class_name = type_info.name.clone();
+                        let class_fqn = type_info.fqn.clone(); // v9.8: Use FQN for CallGraph
+                        let file_path_str = file.to_string_lossy().to_string();
+                    
+                        // v9.7: Store ImportIndex for this file
+                        local_import_indices.insert(file_path_str, import_index.clone());
+                    
+                        // 根据 SymbolTable 的 LayerType 转换为 taint 的 LayerType
+                        let layer = match type_info.layer {
+                            SymbolLayerType::Controller => LayerType::Controller,
+                            SymbolLayerType::Service => LayerType::Service,
+                            SymbolLayerType::Repository => LayerType::Repository,
+                            _ => LayerType::Unknown,
+                        };
+                    
+                        // v9.8: 注册到 CallGraph 使用 FQN
+                        local_graph.register_class(&class_fqn, file.to_path_buf(), layer);
+                        // Also register with simple name for backward compatibility
+                        local_graph.register_class(&class_name, file.to_path_buf(), layer);
+                    
+                        // 注册到 SymbolTable (v9.7: use FQN-based registration)
+                        local_table.register_class_fqn(type_info);
+                        for binding in bindings {
+                            local_table.register_field(&class_name, binding);
+                        }
+                        // v9.6: 方法注册 (返回类型/参数供 UNBOUNDED_QUERY_RESULT 等规则使用)
+                        if let Ok(methods) = java_analyzer.extract_methods(&content, &class_name) {
+                            for method in methods {
+                                local_table.register_method(&class_name, method);
+                            }
+                        }
+                    
+                        // 2. 提取调用点并构建 CallGraph
+                        // v9.8: Use FQN resolution for call sites
+                        if let Ok(call_sites) = java_analyzer.extract_call_sites(&content, file) {
+                            for (caller_method, receiver, callee_method, line) in call_sites {
+                                // v9.8: 构建调用关系，使用 FQN 解析
+                                // Caller uses the class FQN directly
+                                let caller = MethodSig::new_fqn(&class_fqn, &caller_method);
+                            
+                                // Callee: Try to resolve receiver to FQN using ImportIndex and local SymbolTable
+                                // Note: receiver 可能是字段名，需要通过 SymbolTable 解析实际类型
+                                let callee = if receiver == SELF_RECEIVER {
+                                    MethodSig::new_fqn(&class_fqn, &callee_method)
+                                } else {
+                                    MethodSig::resolve(&receiver, &callee_method, &import_index, &local_table)
+                                };
+                            
+                                local_graph.add_call(caller, callee, file.to_path_buf(), line);
+                            }
+                        }
+                    }
+                }
+                (local_table, local_graph, local_import_indices)
+            })
+            .reduce(
+                || (SymbolTable::new(), CallGraph::new(), HashMap::new()),
+                |(mut acc_table, mut acc_graph, mut acc_imports), (table, graph, imports)| {
+                    acc_table.merge(table);
+                    acc_graph.merge(graph);
+                    // v9.7: Merge ImportIndex maps (per-file, no cross-contamination)
+                    acc_imports.extend(imports);
+                    (acc_table, acc_graph, acc_imports)
+                }
+            );
+
+    // v9.6: 全局符号表就绪后，把 `UNRESOLVED:字段名` 接收者链接到字段类型
+    let mut call_graph = call_graph;
+    call_graph.relink(|site| resolve_receiver(&symbol_table, site));
+
+    ProjectIndex { symbol_table, call_graph }
+}
+
+/// 接收者 → 类 FQN: 先按调用方类的字段类型解析，再按类名 (静态调用) 解析
+fn resolve_receiver(symbol_table: &SymbolTable, site: &crate::taint::CallSite) -> Option<String> {
+    if !site.callee.is_unresolved() {
+        return None;
+    }
+    let receiver = site.callee.simple_class_name();
+    let type_name = symbol_table.fields
+        .get(&(site.caller.simple_class_name().to_string(), receiver.to_string()))
+        .map(|binding| binding.type_name.as_str())
+        .unwrap_or(receiver);
+    symbol_table.lookup_by_simple_name(type_name).first().map(|info| info.fqn.clone())
+}
+
+/// 创建 Java 分析器，按需加载脚本规则 (v9.6)
+///
+/// 目录扫描时先加载 `java-perf update` 安装的规则包规则，再加载项目规则目录
+pub(crate) fn build_java_analyzer(root: &Path, is_dir: bool, options: &ScanOptions) -> Result<JavaTreeSitterAnalyzer, Box<dyn std::error::Error + Send + Sync>> {
+    let default_dir = is_dir.then(|| root.join(".java-perf").join("rules")).filter(|d| d.is_dir());
+    let project_dir = options.rules_dir.clone().or(default_dir);
+    let pack_dir = is_dir.then(rule_pack::installed_rules).flatten();
+    let builder = JavaTreeSitterAnalyzer::builder().with_rule_timing(options.timing);
+
+    #[cfg(feature = "script-rules")]
+    {
+        let mut builder = builder;
+        for rules_dir in pack_dir.iter().chain(&project_dir) {
+            builder = builder.with_scripted_rules(rules_dir)?;
+        }
+        Ok(builder.build()?)
+    }
+    #[cfg(not(feature = "script-rules"))]
+    {
+        if let Some(rules_dir) = project_dir {
+            return Err(format!("scripted rules in {} require the `script-rules` feature", rules_dir.display()).into());
+        }
+        if let Some(pack_dir) = pack_dir {
+            tracing::warn!("忽略规则包规则 {} (需要 `script-rules` feature)", pack_dir.display());
+        }
+        Ok(builder.build()?)
+    }
+}
+
+/// 生成 Markdown 报告
+///
+/// compact: true 时只返回 P0，每个 issue 只有 id/file/line
+/// max_p1: compact=false 时最多返回的 P1 数量
+pub fn render_markdown(result: &ScanResult, compact: bool, max_p1: usize) -> Value {
+    render_markdown_with_links(result, compact, max_p1, None)
+}
+
+/// v9.6: 生成 Markdown 报告，问题位置渲染为 IDE / GitHub 链接 (`--link-format`)
+pub fn render_markdown_with_links(result: &ScanResult, compact: bool, max_p1: usize, links: Option<&IssueLinks>) -> Value {
+    let issues = &result.issues;
+    let file_count = result.file_count;
     let p0_count = issues.iter().filter(|i| matches!(i.severity, Severity::P0)).count();
     let p1_count = issues.iter().filter(|i| matches!(i.severity, Severity::P1)).count();
+    // v9.6: P2 建议 / Info 提示
+    let p2_count = issues.iter().filter(|i| matches!(i.severity, Severity::P2)).count();
+    let info_count = issues.iter().filter(|i| matches!(i.severity, Severity::Info)).count();
+
+    // 元数据块 (有 --meta 时输出) + 扫描信息
+    let mut meta_block = String::new();
+    if !result.meta.is_empty() {
+        let pairs: Vec<String> = result.meta.iter().map(|(k, v)| format!("`{k}={v}`")).collect();
+        meta_block.push_str(&format!("{}: {}\n\n", i18n::text("report.scan.meta", "**元数据**"), pairs.join(" ")));
+    }
+    let info = result.info.pairs();
+    if !info.is_empty() {
+        let pairs: Vec<String> = info.iter().map(|(k, v)| format!("`{k}={v}`")).collect();
+        meta_block.push_str(&format!("{}: {}\n\n", i18n::text("report.scan.info", "**扫描信息**"), pairs.join(" ")));
+    }
 
     // === 根据 compact 模式生成不同报告 ===
     if compact {
         // 紧凑模式：只返回 P0，精简格式
+        let counts = i18n::format(
+            "report.scan.counts_compact",
+            "**P0**: {p0} | **P1**: {p1} | **文件**: {files}",
+            &[("p0", &p0_count), ("p1", &p1_count), ("files", &file_count)],
+        );
         let mut report = format!(
-            "## 🛰️ 雷达扫描 (v9.1 AST 引擎)\n\n**P0**: {p0_count} | **P1**: {p1_count} | **文件**: {file_count}\n\n"
+            "{}\n\n{meta_block}{counts}\n\n",
+            i18n::text("report.scan.title_compact", "## 🛰️ 雷达扫描 (v9.1 AST 引擎)")
         );
 
         if p0_count > 0 {
             for issue in issues.iter().filter(|i| matches!(i.severity, Severity::P0)) {
-                report.push_str(&format!(
-                    "- `{}` {}:{}\n",
-                    issue.issue_type, issue.file, issue.line
-                ));
+                let location = match links {
+                    Some(links) => links.markdown(&issue.file, issue.line),
+                    None => format!("{}:{}", issue.file, issue.line),
+                };
+                report.push_str(&format!("- `{}` {location}\n", issue.issue_type));
             }
         } else {
-            report.push_str("✅ 无 P0 问题\n");
+            report.push_str(i18n::text("report.scan.no_p0", "✅ 无 P0 问题"));
+            report.push('\n');
         }
 
         if p1_count > 0 {
-            report.push_str(&format!("\n*（{p1_count} 个 P1 警告已省略，使用 compact=false 查看）*\n"));
+            let omitted = i18n::format(
+                "report.scan.p1_omitted",
+                "*（{count} 个 P1 警告已省略，使用 compact=false 查看）*",
+                &[("count", &p1_count)],
+            );
+            report.push_str(&format!("\n{omitted}\n"));
+        }
+        if p2_count + info_count > 0 {
+            let omitted = i18n::format(
+                "report.scan.advisory_omitted",
+                "*（{count} 个 P2 建议 / Info 提示已省略）*",
+                &[("count", &(p2_count + info_count))],
+            );
+            report.push_str(&format!("\n{omitted}\n"));
         }
 
-        Ok(json!(report))
+        json!(report)
     } else {
         // 完整模式
+        let summary = i18n::format(
+            "report.scan.summary",
+            "**扫描**: {files} 个文件\n**发现**: {total} 个嫌疑点 (P0: {p0}, P1: {p1})",
+            &[("files", &file_count), ("total", &issues.len()), ("p0", &p0_count), ("p1", &p1_count)],
+        );
         let mut report = format!(
-            "## 🛰️ 雷达扫描结果 (v9.1 AST 引擎)\n\n\
-            **扫描**: {} 个文件\n\
-            **发现**: {} 个嫌疑点 (P0: {}, P1: {})\n\n",
-            file_count, issues.len(), p0_count, p1_count
+            "{}\n\n{meta_block}{summary}\n",
+            i18n::text("report.scan.title", "## 🛰️ 雷达扫描结果 (v9.1 AST 引擎)")
         );
+        if p2_count + info_count > 0 {
+            report.push_str(&i18n::format(
+                "report.scan.summary_advisory",
+                "**建议/提示**: P2: {p2}, Info: {info}",
+                &[("p2", &p2_count), ("info", &info_count)],
+            ));
+            report.push('\n');
+        }
+        // v9.6: 修复计划 (按预估工作量汇总)
+        if !issues.is_empty() {
+            report.push_str(&i18n::format(
+                "report.scan.summary_effort",
+                "**修复计划**: {plan}",
+                &[("plan", &effort::Plan::of(issues).describe())],
+            ));
+            report.push('\n');
+        }
+        report.push('\n');
 
-        if p0_count > 0 {
-            report.push_str("### 🔴 P0 严重嫌疑\n\n");
-            for issue in issues.iter().filter(|i| matches!(i.severity, Severity::P0)) {
+        // v9.6: 按规则主分类分组 (见 rules::taxonomy)；P0 全部列出，其余按 P1 → P2 → Info 共显示前 max_p1 个
+        let mut rest: Vec<&AstIssue> = issues.iter().filter(|i| i.severity != Severity::P0).collect();
+        rest.sort_by_key(|i| i.severity);
+        let hidden = rest.len().saturating_sub(max_p1);
+        let mut groups: BTreeMap<(bool, Option<Tag>), Vec<&AstIssue>> = BTreeMap::new();
+        for issue in issues.iter().filter(|i| i.severity == Severity::P0).chain(rest.into_iter().take(max_p1)) {
+            let category = taxonomy::category(&issue.issue_type);
+            groups.entry((category.is_none(), category)).or_default().push(issue);
+        }
+        // 含更高级别问题的分类在前，同级别按分类声明顺序，未分类的规则最后
+        let mut groups: Vec<_> = groups.into_iter().collect();
+        groups.sort_by_key(|((other, category), group)| (group.iter().map(|i| i.severity).min(), *other, *category));
+
+        for ((_, category), mut group) in groups {
+            group.sort_by_key(|i| i.severity);
+            let title = category.map_or_else(|| i18n::text("report.scan.category_other", "📦 其他").to_string(), Tag::title);
+            if !report.ends_with("\n\n") {
+                report.push('\n');
+            }
+            report.push_str(&format!("### {title} ({})\n\n", group.len()));
+            for issue in group {
+                let location = match links {
+                    Some(links) => links.markdown(&issue.file, issue.line),
+                    None => format!("`{}:{}`", issue.file, issue.line),
+                };
                 report.push_str(&format!(
-                    "- **{}** - `{}:{}` - {}\n",
-                    issue.issue_type, issue.file, issue.line, issue.description
+                    "- {} **{}** - {location} - {}\n",
+                    issue.severity.emoji(), issue.issue_type, issue.description
                 ));
             }
-            report.push('\n');
+        }
+        if hidden > 0 {
+            let note = i18n::format(
+                "report.scan.hidden",
+                "*（另有 {count} 个 P1/P2/Info 问题未显示，调大 max_p1 查看）*",
+                &[("count", &hidden)],
+            );
+            report.push_str(&format!("\n{note}\n"));
         }
 
-        if p1_count > 0 {
-            report.push_str(&format!("### 🟡 P1 警告 (显示前 {max_p1})\n\n"));
-            for issue in issues.iter().filter(|i| matches!(i.severity, Severity::P1)).take(max_p1) {
-                report.push_str(&format!(
-                    "- **{}** - `{}:{}` - {}\n",
-                    issue.issue_type, issue.file, issue.line, issue.description
-                ));
+        // v9.6: 有 CODEOWNERS 时输出每个团队的问题数
+        if result.info.codeowners.is_some() {
+            report.push_str(&format!("\n{}\n\n", i18n::text("report.scan.teams_header", "### 👥 各团队问题数")));
+            report.push_str(i18n::text("report.scan.teams_columns", "| 团队 | P0 | P1 | 合计 |"));
+            report.push_str("\n|------|----|----|------|\n");
+            for (team, count) in ownership::team_summary(issues) {
+                report.push_str(&format!("| {team} | {} | {} | {} |\n", count.p0, count.p1, count.total));
             }
         }
 
-        Ok(json!(report))
+        json!(report)
     }
 }
 
 /// 单文件扫描 (v9.1: 仅使用 Tree-sitter AST 分析)
 pub fn scan_source_code(code: &str, file_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let issues = analyze_source_issues(code, file_path);
+    Ok(render_file_markdown(&issues, file_path))
+}
+
+/// 单文件分析，返回原始问题列表
+pub fn analyze_source_issues(code: &str, file_path: &str) -> Vec<AstIssue> {
     let mut issues = Vec::new();
     let path = Path::new(file_path);
-    let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-
-    if ext == "java" {
-        // v9.1: 仅使用 AST 分析（所有 Regex 规则已迁移）
-        if let Ok(analyzer) = JavaTreeSitterAnalyzer::new() {
-             if let Ok(res) = analyzer.analyze(code, path) {
-                 issues.extend(res.into_iter().map(convert_issue));
-             }
-        }
-    } else if ["yml", "yaml", "properties"].contains(&ext) {
-        // Config
-        if let Ok(analyzer) = LineBasedConfigAnalyzer::new() {
-             if let Ok(res) = analyzer.analyze(code, path) {
-                 issues.extend(res.into_iter().map(convert_issue));
-             }
+
+    // v9.6: 与目录扫描共用语言注册表分派
+    if let Some(kind) = LanguageRegistry::builtin().analyzer_for(path) {
+        if let Ok(analyzer) = kind.instantiate() {
+            if let Ok(res) = analyzer.analyze(code, path) {
+                issues.extend(res.into_iter().map(convert_issue));
+            }
         }
     }
 
-    let mut report = format!("## 🛰️ 扫描: {file_path}\n\n");
+    issues
+}
+
+/// 生成单文件 Markdown 报告
+fn render_file_markdown(issues: &[AstIssue], file_path: &str) -> Value {
+    let mut report = i18n::format("report.file.title", "## 🛰️ 扫描: {file}", &[("file", &file_path)]);
+    report.push_str("\n\n");
 
     if issues.is_empty() {
-        report.push_str("✅ 未发现明显性能问题\n");
+        report.push_str(i18n::text("report.file.clean", "✅ 未发现明显性能问题"));
+        report.push('\n');
     } else {
-        for issue in &issues {
-            let emoji = match issue.severity {
-                Severity::P0 => "🔴",
-                Severity::P1 => "🟡",
-            };
+        let line_label = i18n::text("report.file.line", "行");
+        for issue in issues {
+            let emoji = issue.severity.emoji();
             report.push_str(&format!(
-                "{} **{}** (行 {}) - {}\n",
-                emoji, issue.issue_type, issue.line, issue.description
+                "{} **{}** ({} {}) - {}\n",
+                emoji, issue.issue_type, line_label, issue.line, issue.description
             ));
         }
     }
 
-    Ok(json!(report))
+    json!(report)
 }
@@ -0,0 +1,238 @@
+//! 性能基准 (v9.6)
+//!
+//! `benches/scan.rs` (criterion) 与 `java-perf bench` 子命令共用这里的工作负载：
+//! - `single_file`: 单文件 AST 分析
+//! - `radar_scan`: 合成项目 (默认 5k 文件) 全量双遍扫描
+//! - `symbol_table_merge`: SymbolTable 两两 reduce 合并
+//!
+//! `java-perf bench` 输出 JSON 结果，可保存为 baseline 并在之后对比回归。
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use crate::ast_engine;
+use crate::symbol_table::{SymbolTable, TypeInfo, VarBinding};
+
+/// 单文件基准使用的样例代码 (覆盖 N+1、锁、循环拼接等常见规则)
+pub const SAMPLE_SOURCE: &str = r#"
+package com.bench.svc;
+
+import com.bench.dao.UserDao;
+import java.util.*;
+
+@Service
+public class OrderService {
+    @Autowired
+    private UserDao userDao;
+    private static final Map<String, Object> CACHE = new HashMap<>();
+    private final ThreadLocal<String> ctx = new ThreadLocal<>();
+
+    public void process(List<Long> ids) {
+        for (Long id : ids) {
+            userDao.findById(id);
+        }
+        for (int i = 0; i < ids.size(); i++) {
+            for (int j = 0; j < ids.size(); j++) {
+                compare(ids.get(i), ids.get(j));
+            }
+        }
+    }
+
+    public synchronized void update() {
+        ctx.set("x");
+    }
+
+    public String join(List<String> parts) {
+        String s = "";
+        for (String p : parts) {
+            s += p;
+        }
+        return s;
+    }
+}
+"#;
+
+/// 生成合成 Java 项目: 每 10 个文件中 1 个 DAO，其余为调用 DAO 的 Service
+pub fn generate_project(root: &Path, files: usize) -> std::io::Result<()> {
+    for i in 0..files {
+        let pkg = format!("svc{}", i % 50);
+        let dir = root.join("src/main/java/com/bench").join(&pkg);
+        std::fs::create_dir_all(&dir)?;
+
+        let (name, body) = if i % 10 == 0 {
+            let name = format!("UserDao{i}");
+            (name.clone(), format!(
+                "package com.bench.{pkg};\n\n@Repository\npublic interface {name} {{\n    Object findById(Long id);\n}}\n"
+            ))
+        } else {
+            let name = format!("Service{i}");
+            let dao = format!("UserDao{}", i / 10 * 10);
+            (name.clone(), SAMPLE_SOURCE
+                .replace("package com.bench.svc;", &format!("package com.bench.{pkg};"))
+                .replace("OrderService", &name)
+                .replace("UserDao", &dao))
+        };
+        std::fs::write(dir.join(format!("{name}.java")), body)?;
+    }
+    Ok(())
+}
+
+/// 构造 n 个单类符号表 (用于 merge 基准)
+pub fn symbol_tables(n: usize) -> Vec<SymbolTable> {
+    (0..n).map(|i| {
+        let mut table = SymbolTable::new();
+        let class = format!("Service{i}");
+        let mut info = TypeInfo::new_with_package(&class, Some("com.bench"), PathBuf::from(format!("{class}.java")), 1);
+        info.add_annotation("Service");
+        table.register_class_fqn(info);
+        table.register_field(&class, VarBinding::new("userDao", "UserDao", true));
+        table
+    }).collect()
+}
+
+/// 并行 reduce 合并符号表 (与 Phase 1 相同策略)
+pub fn merge_symbol_tables(tables: Vec<SymbolTable>) -> SymbolTable {
+    tables.into_par_iter().reduce(SymbolTable::new, |mut a, b| {
+        a.merge(b);
+        a
+    })
+}
+
+/// 单项基准结果 (毫秒)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BenchResult {
+    pub name: String,
+    pub iterations: usize,
+    pub mean_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+}
+
+fn measure(name: &str, iterations: usize, mut f: impl FnMut()) -> BenchResult {
+    f(); // 预热
+    let samples: Vec<f64> = (0..iterations.max(1)).map(|_| {
+        let start = Instant::now();
+        f();
+        start.elapsed().as_secs_f64() * 1000.0
+    }).collect();
+
+    BenchResult {
+        name: name.to_string(),
+        iterations: samples.len(),
+        mean_ms: samples.iter().sum::<f64>() / samples.len() as f64,
+        min_ms: samples.iter().cloned().fold(f64::INFINITY, f64::min),
+        max_ms: samples.iter().cloned().fold(0.0, f64::max),
+    }
+}
+
+/// 运行全部基准
+pub fn run_all(files: usize, iterations: usize) -> Result<Vec<BenchResult>, Box<dyn std::error::Error>> {
+    let project = std::env::temp_dir().join(format!("java-perf-bench-{}", std::process::id()));
+    generate_project(&project, files)?;
+    let project_path = project.to_string_lossy().to_string();
+
+    let results = vec![
+        measure("single_file", iterations * 10, || {
+            ast_engine::analyze_source_issues(SAMPLE_SOURCE, "OrderService.java");
+        }),
+        measure("radar_scan", iterations, || {
+            let _ = ast_engine::collect_issues(&project_path);
+        }),
+        measure("symbol_table_merge", iterations * 10, || {
+            merge_symbol_tables(symbol_tables(files));
+        }),
+    ];
+
+    let _ = std::fs::remove_dir_all(&project);
+    Ok(results)
+}
+
+/// 与 baseline 对比，返回 (名称, baseline, 当前, 变化百分比)，仅包含超过阈值的回归
+pub fn regressions(current: &[BenchResult], baseline: &[BenchResult], threshold_pct: f64) -> Vec<(String, f64, f64, f64)> {
+    current.iter().filter_map(|cur| {
+        let base = baseline.iter().find(|b| b.name == cur.name)?;
+        if base.mean_ms <= 0.0 {
+            return None;
+        }
+        let change = (cur.mean_ms - base.mean_ms) / base.mean_ms * 100.0;
+        (change > threshold_pct).then(|| (cur.name.clone(), base.mean_ms, cur.mean_ms, change))
+    }).collect()
+}
+
+/// `java-perf bench` 入口
+pub fn run_bench(
+    files: usize,
+    iterations: usize,
+    baseline: Option<&str>,
+    save_baseline: Option<&str>,
+    threshold_pct: f64,
+) -> Result<Value, Box<dyn std::error::Error>> {
+    let results = run_all(files, iterations)?;
+
+    if let Some(path) = save_baseline {
+        std::fs::write(path, serde_json::to_string_pretty(&results)?)?;
+    }
+
+    let mut report = format!("## ⏱️ Benchmark ({files} files, {iterations} iterations)\n\n");
+    report.push_str("| Benchmark | Mean (ms) | Min (ms) | Max (ms) |\n|---|---|---|---|\n");
+    for r in &results {
+        report.push_str(&format!("| `{}` | {:.2} | {:.2} | {:.2} |\n", r.name, r.mean_ms, r.min_ms, r.max_ms));
+    }
+
+    let mut regressed = Vec::new();
+    if let Some(path) = baseline {
+        let base: Vec<BenchResult> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        regressed = regressions(&results, &base, threshold_pct);
+        report.push('\n');
+        if regressed.is_empty() {
+            report.push_str(&format!("✅ No regression over {threshold_pct}% against `{path}`\n"));
+        } else {
+            report.push_str(&format!("🔴 Regressions over {threshold_pct}% against `{path}`:\n"));
+            for (name, base, cur, change) in &regressed {
+                report.push_str(&format!("- `{name}`: {base:.2} ms → {cur:.2} ms (+{change:.1}%)\n"));
+            }
+        }
+    }
+
+    Ok(json!({
+        "results": results,
+        "regressions": regressed.iter().map(|(name, base, cur, change)| json!({
+            "name": name, "baseline_ms": base, "current_ms": cur, "change_pct": change,
+        })).collect::<Vec<_>>(),
+        "report": report,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, mean_ms: f64) -> BenchResult {
+        BenchResult { name: name.to_string(), iterations: 1, mean_ms, min_ms: mean_ms, max_ms: mean_ms }
+    }
+
+    #[test]
+    fn test_regressions_threshold() {
+        let baseline = vec![result("a", 100.0), result("b", 100.0)];
+        let current = vec![result("a", 105.0), result("b", 130.0), result("c", 1.0)];
+        let r = regressions(&current, &baseline, 10.0);
+        assert_eq!(r.len(), 1);
+        assert_eq!(r[0].0, "b");
+        assert!((r[0].3 - 30.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_generate_project_and_merge() {
+        let dir = tempfile::tempdir().unwrap();
+        generate_project(dir.path(), 20).unwrap();
+        let scan = ast_engine::collect_issues(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(scan.file_count, 20);
+        assert!(scan.issues.iter().any(|i| i.issue_type.starts_with("N_PLUS_ONE")));
+
+        let merged = merge_symbol_tables(symbol_tables(20));
+        assert_eq!(merged.lookup_by_simple_name("Service3").len(), 1);
+    }
+}
@@ -0,0 +1,845 @@
+//! Bytecode 模块 - 基于 `javap -c -p` 输出的字节码级检测 (v9.6)
+//!
+//! 🔬 法医取证：对没有源码的 JAR/WAR 也能给出线索
+//!
+//! - 列举归档中的 `.class` (含 WAR 的 `WEB-INF/classes`、Spring Boot 的 `BOOT-INF/classes`)
+//! - 按包 glob 过滤后批量调用 javap (`jar:file:...!/...` URL，无需解压)
+//! - 解析方法与指令，检测：
+//!   1. **同步方法**: `synchronized` 修饰的方法 (热点包中的锁竞争)
+//!   2. **超大方法**: 字节码 > 8000 字节，HotSpot 默认不编译 (`DontCompileHugeMethods` 默认开启)
+//!   3. **循环内字符串拼接**: 回跳区间内的 `new StringBuilder` / `makeConcatWithConstants`
+//!   4. **无界线程池**: `Executors.newCachedThreadPool` 等
+//!   5. **ThreadLocal 泄漏**: 类中调用 `ThreadLocal.set` 但从未 `remove`
+//!   6. **循环内加锁**: 回跳区间内的 `monitorenter`
+//!
+//! 单个类 (`javap --class Svc.class`) 额外输出方法画像 ([`MethodProfile`])：字节码大小对应的
+//! JIT 内联档位、异常表密度、`monitorenter` 次数、字符串拼接点，并据此给出排序后的热点方法发现。
+//!
+//! `scan-jar` 只对第三方依赖运行其中与并发/资源相关的精简规则集 (`DEPENDENCY_RULES`)。
+
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::jdk_engine;
+use crate::rules::effort;
+
+/// HotSpot `HugeMethodLimit` (字节)，超过的方法不会被 JIT 编译
+pub const HUGE_METHOD_LIMIT: usize = 8000;
+/// HotSpot `MaxInlineSize` (字节)，不超过的方法无论调用频率都会被内联
+pub const MAX_INLINE_SIZE: usize = 35;
+/// HotSpot `FreqInlineSize` (字节)，超过的方法即使是热点也不会被内联
+pub const FREQ_INLINE_SIZE: usize = 325;
+/// 异常表项达到该数量且密度 (每 100 字节) 达到 [`EXCEPTION_DENSITY_LIMIT`] 时报告
+const EXCEPTION_HANDLERS_MIN: usize = 3;
+const EXCEPTION_DENSITY_LIMIT: f64 = 2.0;
+/// 单个方法中 `monitorenter` 达到该数量时报告 (嵌套/多段加锁)
+const MONITORS_MIN: usize = 2;
+/// 单个方法中字符串拼接点达到该数量时报告
+const CONCAT_SITES_MIN: usize = 3;
+/// 方法画像表展示的方法数
+const TOP_METHODS: usize = 15;
+/// 单次 javap 调用的类数量 (避免命令行过长)
+const JAVAP_BATCH: usize = 200;
+/// 归档中最多分析的类数量
+const MAX_CLASSES: usize = 5000;
+/// 类目录前缀 (WAR / Spring Boot fat jar)
+const CLASS_ROOTS: &[&str] = &["WEB-INF/classes/", "BOOT-INF/classes/"];
+/// 无界线程池工厂方法 (与源码规则 UNBOUNDED_POOL 一致)
+const UNBOUNDED_POOL_FACTORIES: &[&str] = &["newCachedThreadPool", "newScheduledThreadPool", "newSingleThreadExecutor"];
+
+/// `scan-jar` 规则集: 第三方依赖中值得关注的并发/资源问题
+pub const DEPENDENCY_RULES: &[&str] = &[
+    "BC_UNBOUNDED_POOL",
+    "BC_THREADLOCAL_LEAK",
+    "BC_SYNC_METHOD",
+    "BC_MONITOR_IN_LOOP",
+];
+
+/// 一条字节码指令
+#[derive(Debug, Clone, PartialEq)]
+pub struct Instruction {
+    pub offset: usize,
+    pub opcode: String,
+    /// 操作数 (如跳转目标、常量池索引)
+    pub operand: String,
+    /// javap 注释 (`// Method java/lang/StringBuilder."<init>":()V`)
+    pub comment: String,
+}
+
+/// 一个方法的字节码
+#[derive(Debug, Clone, Default)]
+pub struct JavapMethod {
+    pub class: String,
+    /// 方法声明 (javap 原样输出，不含结尾分号)
+    pub signature: String,
+    pub is_synchronized: bool,
+    pub instructions: Vec<Instruction>,
+    /// 异常表中 catch 具体类型的项数 (finally 与 synchronized 块生成的 `any` 项不计)
+    pub exception_handlers: usize,
+}
+
+impl JavapMethod {
+    /// 方法名 (构造器为 `<init>`，静态初始化块为 `<clinit>`)
+    pub fn name(&self) -> &str {
+        if self.signature == "static {}" {
+            return "<clinit>";
+        }
+        let head = self.signature.split('(').next().unwrap_or("");
+        let last = head.rsplit(' ').next().unwrap_or(head);
+        // javap 以类全名输出构造器 (默认包中的类没有 `.`)
+        if last.contains('.') || last == self.class { "<init>" } else { last }
+    }
+
+    /// 字节码长度估算: 最后一条指令偏移 + 1
+    pub fn code_size(&self) -> usize {
+        self.instructions.last().map(|i| i.offset + 1).unwrap_or(0)
+    }
+
+    /// 回跳形成的循环区间 [目标, 跳转指令偏移]
+    pub fn loop_ranges(&self) -> Vec<(usize, usize)> {
+        self.instructions.iter()
+            .filter(|i| i.opcode.starts_with("goto") || i.opcode.starts_with("if"))
+            .filter_map(|i| {
+                let target: usize = i.operand.trim().parse().ok()?;
+                (target < i.offset).then_some((target, i.offset))
+            })
+            .collect()
+    }
+}
+
+/// 字节码检测结果
+#[derive(Debug, Clone, Serialize)]
+pub struct BytecodeFinding {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub class: String,
+    pub method: String,
+    pub description: String,
+    /// 归档内位置 (`lib.jar!/com/acme/Svc.class`)，由 `analyze_archive` 填写
+    pub file: String,
+}
+
+/// 解析 `javap -c -p` 输出 (可包含多个类)
+pub fn parse_javap(output: &str) -> Vec<JavapMethod> {
+    let mut methods = Vec::new();
+    let mut class = String::new();
+    let mut current: Option<JavapMethod> = None;
+    let mut in_code = false;
+    let mut in_exceptions = false;
+
+    for line in output.lines() {
+        let trimmed = line.trim();
+
+        // 类声明: 顶格 `public class a.b.C extends ... {`
+        if !line.starts_with(' ') && trimmed.ends_with('{') {
+            if let Some(name) = class_name(trimmed) {
+                class = name;
+            }
+            continue;
+        }
+
+        // 成员声明: 两个空格缩进，以 `;` 结尾
+        if line.starts_with("  ") && !line.starts_with("   ") && trimmed.ends_with(';') {
+            methods.extend(current.take());
+            in_code = false;
+            in_exceptions = false;
+            let decl = trimmed.trim_end_matches(';');
+            // 方法或静态初始化块 (`static {};`)，字段声明跳过
+            if decl.contains('(') || decl == "static {}" {
+                current = Some(JavapMethod {
+                    class: class.clone(),
+                    signature: decl.to_string(),
+                    is_synchronized: decl.split('(').next().unwrap_or("").split_whitespace().any(|w| w == "synchronized"),
+                    instructions: Vec::new(),
+                    exception_handlers: 0,
+                });
+            }
+            continue;
+        }
+
+        if trimmed == "Code:" {
+            in_code = true;
+            continue;
+        }
+
+        if trimmed == "Exception table:" {
+            in_code = false;
+            in_exceptions = true;
+            continue;
+        }
+
+        // 异常表: 表头 `from to target type` 后每行 `0 10 13 Class java/lang/Exception` (finally 为 `any`)
+        if in_exceptions {
+            let fields: Vec<&str> = trimmed.split_whitespace().collect();
+            if fields.len() >= 4 && fields[..3].iter().all(|f| f.parse::<usize>().is_ok()) {
+                if let (Some(m), true) = (current.as_mut(), fields[3] != "any") {
+                    m.exception_handlers += 1;
+                }
+            } else if fields.first() != Some(&"from") {
+                in_exceptions = false;
+            }
+            continue;
+        }
+
+        if in_code {
+            match parse_instruction(trimmed) {
+                Some(ins) => {
+                    if let Some(m) = current.as_mut() {
+                        m.instructions.push(ins);
+                    }
+                }
+                // 异常表/行号表等，指令段结束
+                None if !trimmed.is_empty() && !trimmed.chars().next().is_some_and(|c| c.is_ascii_digit()) => in_code = false,
+                None => {}
+            }
+        }
+
+        if trimmed == "}" {
+            methods.extend(current.take());
+            in_code = false;
+            in_exceptions = false;
+        }
+    }
+    methods.extend(current);
+    methods
+}
+
+fn class_name(decl: &str) -> Option<String> {
+    let words: Vec<&str> = decl.split_whitespace().collect();
+    let pos = words.iter().position(|w| matches!(*w, "class" | "interface" | "enum" | "record"))?;
+    let name = words.get(pos + 1)?;
+    Some(name.split('<').next().unwrap_or(name).to_string())
+}
+
+fn parse_instruction(line: &str) -> Option<Instruction> {
+    let (offset, rest) = line.split_once(':')?;
+    let offset: usize = offset.trim().parse().ok()?;
+    let (code, comment) = match rest.split_once("//") {
+        Some((code, comment)) => (code, comment.trim()),
+        None => (rest, ""),
+    };
+    let mut parts = code.split_whitespace();
+    let opcode = parts.next()?.to_string();
+    let operand = parts.collect::<Vec<_>>().join(" ");
+    Some(Instruction { offset, opcode, operand, comment: comment.to_string() })
+}
+
+/// 对解析出的方法运行字节码规则
+pub fn detect(methods: &[JavapMethod]) -> Vec<BytecodeFinding> {
+    let mut findings = Vec::new();
+
+    for m in methods {
+        let finding = |id, severity, description: String| BytecodeFinding {
+            id,
+            severity,
+            class: m.class.clone(),
+            method: m.name().to_string(),
+            description,
+            file: String::new(),
+        };
+
+        if m.is_synchronized {
+            findings.push(finding("BC_SYNC_METHOD", Severity::P1, "synchronized 方法，热点路径上全部调用串行化".to_string()));
+        }
+
+        let size = m.code_size();
+        if size > HUGE_METHOD_LIMIT {
+            findings.push(finding("BC_HUGE_METHOD", Severity::P0, format!(
+                "方法字节码约 {size} 字节 (> {HUGE_METHOD_LIMIT})，JIT 不会编译/内联，始终解释执行"
+            )));
+        }
+
+        let loops = m.loop_ranges();
+        let concat = m.instructions.iter().find(|i| {
+            let in_loop = loops.iter().any(|(start, end)| (*start..=*end).contains(&i.offset));
+            in_loop && is_string_concat(i)
+        });
+        if let Some(ins) = concat {
+            findings.push(finding("BC_CONCAT_IN_LOOP", Severity::P1, format!(
+                "循环内字符串拼接 (偏移 {})，每次迭代创建新的 StringBuilder/String", ins.offset
+            )));
+        }
+
+        let monitor = m.instructions.iter().find(|i| {
+            i.opcode == "monitorenter" && loops.iter().any(|(start, end)| (*start..=*end).contains(&i.offset))
+        });
+        if let Some(ins) = monitor {
+            findings.push(finding("BC_MONITOR_IN_LOOP", Severity::P1, format!(
+                "循环内 synchronized 块 (偏移 {})，每次迭代加锁/解锁", ins.offset
+            )));
+        }
+
+        for ins in m.instructions.iter().filter(|i| i.opcode == "invokestatic") {
+            if let Some(factory) = UNBOUNDED_POOL_FACTORIES.iter()
+                .find(|f| ins.comment.contains(&format!("java/util/concurrent/Executors.{f}:")))
+            {
+                findings.push(finding("BC_UNBOUNDED_POOL", Severity::P0, format!(
+                    "Executors.{factory}() 无界线程池/队列，突发流量下线程或任务堆积"
+                )));
+            }
+        }
+    }
+
+    // 类级规则: ThreadLocal.set 但整个类没有 remove
+    let mut thread_locals: BTreeMap<&str, (Option<&JavapMethod>, bool)> = BTreeMap::new();
+    for m in methods {
+        for ins in &m.instructions {
+            let state = thread_locals.entry(m.class.as_str()).or_default();
+            if ins.comment.contains("java/lang/ThreadLocal.set:") {
+                state.0.get_or_insert(m);
+            } else if ins.comment.contains("java/lang/ThreadLocal.remove:") {
+                state.1 = true;
+            }
+        }
+    }
+    for (class, (setter, removed)) in thread_locals {
+        if let (Some(m), false) = (setter, removed) {
+            findings.push(BytecodeFinding {
+                id: "BC_THREADLOCAL_LEAK",
+                severity: Severity::P0,
+                class: class.to_string(),
+                method: m.name().to_string(),
+                description: "ThreadLocal.set() 但类中没有 remove()，线程池复用线程时泄漏".to_string(),
+                file: String::new(),
+            });
+        }
+    }
+
+    findings
+}
+
+fn is_string_concat(ins: &Instruction) -> bool {
+    (ins.opcode == "new" && ins.comment.ends_with("java/lang/StringBuilder"))
+        || (ins.opcode == "invokedynamic" && ins.comment.contains("makeConcatWithConstants"))
+}
+
+/// JIT 内联档位 (按字节码大小)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InlineTier {
+    /// <= `MaxInlineSize`: 总是内联
+    Always,
+    /// <= `FreqInlineSize`: 热点时内联
+    WhenHot,
+    /// 超过 `FreqInlineSize`: 可编译但不会被内联
+    Never,
+    /// 超过 `HugeMethodLimit`: 不编译，始终解释执行
+    NotCompiled,
+}
+
+impl InlineTier {
+    pub fn of(code_size: usize) -> Self {
+        match code_size {
+            s if s <= MAX_INLINE_SIZE => InlineTier::Always,
+            s if s <= FREQ_INLINE_SIZE => InlineTier::WhenHot,
+            s if s <= HUGE_METHOD_LIMIT => InlineTier::Never,
+            _ => InlineTier::NotCompiled,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            InlineTier::Always => "✅ 总是内联",
+            InlineTier::WhenHot => "🔥 热点时内联",
+            InlineTier::Never => "⚠️ 不内联",
+            InlineTier::NotCompiled => "⛔ 不编译",
+        }
+    }
+}
+
+/// 单个方法的字节码画像
+#[derive(Debug, Clone, Serialize)]
+pub struct MethodProfile {
+    pub class: String,
+    pub method: String,
+    pub code_size: usize,
+    pub inline: InlineTier,
+    pub exception_handlers: usize,
+    /// 每 100 字节字节码的异常表项数
+    pub exception_density: f64,
+    pub monitorenter: usize,
+    /// `makeConcatWithConstants` / `new StringBuilder` 出现次数
+    pub string_concat: usize,
+}
+
+/// 方法画像 (按字节码大小降序；`abstract`/`native` 方法没有字节码，不列出)
+pub fn profile(methods: &[JavapMethod]) -> Vec<MethodProfile> {
+    let mut profiles: Vec<MethodProfile> = methods.iter()
+        .filter(|m| !m.instructions.is_empty())
+        .map(|m| {
+            let code_size = m.code_size();
+            MethodProfile {
+                class: m.class.clone(),
+                method: m.name().to_string(),
+                code_size,
+                inline: InlineTier::of(code_size),
+                exception_handlers: m.exception_handlers,
+                exception_density: m.exception_handlers as f64 * 100.0 / code_size as f64,
+                monitorenter: m.instructions.iter().filter(|i| i.opcode == "monitorenter").count(),
+                string_concat: m.instructions.iter().filter(|i| is_string_concat(i)).count(),
+            }
+        })
+        .collect();
+    profiles.sort_by(|a, b| b.code_size.cmp(&a.code_size).then_with(|| a.method.cmp(&b.method)));
+    profiles
+}
+
+/// 基于方法画像的热点方法发现，按严重程度与量级排序
+///
+/// 超大方法 (`BC_HUGE_METHOD`) 已由 [`detect`] 报告，这里只覆盖内联阈值、异常表、加锁与拼接密度。
+pub fn hot_method_findings(profiles: &[MethodProfile]) -> Vec<BytecodeFinding> {
+    let mut ranked: Vec<(usize, BytecodeFinding)> = Vec::new();
+    for p in profiles {
+        let mut push = |magnitude: usize, id, description: String| ranked.push((magnitude, BytecodeFinding {
+            id,
+            severity: Severity::P1,
+            class: p.class.clone(),
+            method: p.method.clone(),
+            description,
+            file: String::new(),
+        }));
+
+        if p.inline == InlineTier::Never {
+            push(p.code_size, "BC_NOT_INLINABLE", format!(
+                "方法字节码约 {} 字节 (> FreqInlineSize {FREQ_INLINE_SIZE})，即使是热点也不会被内联，可拆分出热路径",
+                p.code_size
+            ));
+        }
+        if p.exception_handlers >= EXCEPTION_HANDLERS_MIN && p.exception_density >= EXCEPTION_DENSITY_LIMIT {
+            push(p.exception_handlers, "BC_EXCEPTION_DENSE", format!(
+                "异常表 {} 项 (每 100 字节 {:.1} 项)，异常用于控制流或 try 块过碎",
+                p.exception_handlers, p.exception_density
+            ));
+        }
+        if p.monitorenter >= MONITORS_MIN {
+            push(p.monitorenter, "BC_MULTIPLE_MONITORS", format!(
+                "{} 处 monitorenter，多段/嵌套 synchronized 增加锁竞争与死锁风险",
+                p.monitorenter
+            ));
+        }
+        if p.string_concat >= CONCAT_SITES_MIN {
+            push(p.string_concat, "BC_STRING_CONCAT_HEAVY", format!(
+                "{} 处字符串拼接，热点路径上考虑复用 StringBuilder 或延迟格式化 (日志占位符)",
+                p.string_concat
+            ));
+        }
+    }
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| a.1.method.cmp(&b.1.method)));
+    ranked.into_iter().map(|(_, f)| f).collect()
+}
+
+/// 单个类的分析结果: 规则发现 + 热点方法发现 + 方法画像
+#[derive(Debug, Serialize)]
+pub struct ClassReport {
+    pub methods: Vec<MethodProfile>,
+    pub findings: Vec<BytecodeFinding>,
+}
+
+impl ClassReport {
+    pub fn new(methods: &[JavapMethod]) -> Self {
+        let profiles = profile(methods);
+        let mut findings = detect(methods);
+        findings.extend(hot_method_findings(&profiles));
+        // 稳定排序: P0 在前，同级保持规则/量级顺序
+        findings.sort_by_key(|f| f.severity);
+        Self { methods: profiles, findings }
+    }
+
+    /// 渲染 Markdown 报告
+    pub fn render(&self, class_path: &str) -> String {
+        let total: usize = self.methods.iter().map(|m| m.code_size).sum();
+        let mut out = format!("## 🔬 字节码分析: {class_path}\n\n**方法**: {} | **字节码**: 约 {total} 字节\n\n", self.methods.len());
+
+        out.push_str("### 发现\n\n");
+        if self.findings.is_empty() {
+            out.push_str("✅ 未发现字节码层面的问题\n");
+        }
+        for f in &self.findings {
+            let emoji = f.severity.emoji();
+            out.push_str(&format!("{emoji} **{}** `{}.{}` - {}\n", f.id, f.class, f.method, f.description));
+        }
+
+        if !self.methods.is_empty() {
+            out.push_str(&format!(
+                "\n### 方法画像 (按字节码大小，前 {TOP_METHODS} 个)\n\n\
+                | 方法 | 字节码 | JIT 内联 | 异常表 | monitorenter | 字符串拼接 |\n\
+                |------|--------|----------|--------|--------------|------------|\n"
+            ));
+            for m in self.methods.iter().take(TOP_METHODS) {
+                out.push_str(&format!(
+                    "| `{}` | {} | {} | {} | {} | {} |\n",
+                    m.method, m.code_size, m.inline.label(), m.exception_handlers, m.monitorenter, m.string_concat
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// 包 glob 转正则: `*` 匹配一段包名，`**` 匹配任意层级 (如 `com.acme.**`)
+pub fn package_glob(glob: &str) -> Result<Regex, regex::Error> {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                pattern.push_str(".*");
+            }
+            '*' => pattern.push_str("[^.]*"),
+            c => pattern.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    // `com.acme` 同时匹配包内所有类
+    if !glob.contains('*') {
+        pattern.push_str(r"(\..*)?");
+    }
+    pattern.push('$');
+    Regex::new(&pattern)
+}
+
+/// 归档中的类条目: (条目路径, 类全名)
+pub fn list_classes(archive: &Path) -> Result<Vec<(String, String)>, Box<dyn std::error::Error>> {
+    // 只读取中央目录，不解压条目内容
+    let zip = zip::ZipArchive::new(std::fs::File::open(archive)?)?;
+    let mut classes = Vec::new();
+    for name in zip.file_names() {
+        let Some(stem) = name.strip_suffix(".class") else { continue };
+        if stem.ends_with("module-info") || stem.ends_with("package-info") {
+            continue;
+        }
+        let rel = CLASS_ROOTS.iter().find_map(|root| stem.strip_prefix(root)).unwrap_or(stem);
+        // 多版本 JAR 与 WAR/BOOT-INF 内嵌的 lib 不在此处理
+        if rel.starts_with("META-INF/") || rel.starts_with("WEB-INF/") || rel.starts_with("BOOT-INF/") {
+            continue;
+        }
+        classes.push((name.to_string(), rel.replace('/', ".")));
+    }
+    classes.sort_by(|a, b| a.1.cmp(&b.1));
+    Ok(classes)
+}
+
+/// 分析 JAR/WAR: 列举类 → 包过滤 → 批量 javap → 检测
+pub fn analyze_archive(javap: &str, archive: &Path, package: Option<&str>) -> Result<ArchiveReport, Box<dyn std::error::Error>> {
+    let filter = package.map(package_glob).transpose()?;
+    let all = list_classes(archive)?;
+    let total = all.len();
+    let mut selected: Vec<(String, String)> = all.into_iter()
+        .filter(|(_, class)| filter.as_ref().is_none_or(|re| re.is_match(class)))
+        .collect();
+    let truncated = selected.len() > MAX_CLASSES;
+    selected.truncate(MAX_CLASSES);
+
+    let archive_abs = std::fs::canonicalize(archive)?;
+    let entries: HashMap<&str, &str> = selected.iter().map(|(entry, class)| (class.as_str(), entry.as_str())).collect();
+    let mut methods = Vec::new();
+    for batch in selected.chunks(JAVAP_BATCH) {
+        let urls: Vec<String> = batch.iter()
+            .map(|(entry, _)| format!("jar:file:{}!/{}", archive_abs.display(), entry))
+            .collect();
+        let mut cmd = std::process::Command::new(javap);
+        let output = jdk_engine::run_with_timeout(cmd.args(["-c", "-p"]).args(&urls), jdk_engine::DEFAULT_TIMEOUT)?;
+        if !output.status.success() && output.stdout.is_empty() {
+            return Err(format!("javap failed: {}", String::from_utf8_lossy(&output.stderr)).into());
+        }
+        methods.extend(parse_javap(&String::from_utf8_lossy(&output.stdout)));
+    }
+
+    let mut findings = detect(&methods);
+    for f in &mut findings {
+        // 内部类在 javap 中显示为 `Outer$Inner`，与条目名一致
+        let entry = entries.get(f.class.as_str()).copied().unwrap_or_default();
+        f.file = format!("{}!/{}", archive.display(), entry);
+    }
+
+    Ok(ArchiveReport {
+        total_classes: total,
+        analyzed_classes: selected.len(),
+        truncated,
+        methods: methods.len(),
+        findings,
+    })
+}
+
+/// 归档分析结果
+#[derive(Debug, Serialize)]
+pub struct ArchiveReport {
+    pub total_classes: usize,
+    pub analyzed_classes: usize,
+    /// 超过 `MAX_CLASSES` 被截断
+    pub truncated: bool,
+    pub methods: usize,
+    pub findings: Vec<BytecodeFinding>,
+}
+
+impl ArchiveReport {
+    /// 转换为扫描问题 (复用 JSON/SARIF 等报告格式)，方法名放入描述
+    pub fn to_issues(&self) -> Vec<AstIssue> {
+        self.findings.iter().map(|f| AstIssue {
+            severity: f.severity,
+            issue_type: f.id.to_string(),
+            file: f.file.clone(),
+            line: 0,
+            column: 0,
+            end_line: 0,
+            end_column: 0,
+            description: format!("{}.{}: {}", f.class, f.method, f.description),
+            owner: None,
+            effort: effort::builtin(f.id),
+        }).collect()
+    }
+
+    /// 渲染 Markdown 报告
+    pub fn render(&self, archive: &str, package: Option<&str>) -> String {
+        let mut out = format!("## 🔬 字节码分析: {archive}\n\n");
+        out.push_str(&format!(
+            "**类**: {}/{} (过滤: `{}`) | **方法**: {}\n\n",
+            self.analyzed_classes, self.total_classes, package.unwrap_or("*"), self.methods
+        ));
+        if self.truncated {
+            out.push_str(&format!("> [!NOTE]\n> 仅分析前 {MAX_CLASSES} 个类，请用 `--package` 缩小范围\n\n"));
+        }
+
+        if self.findings.is_empty() {
+            out.push_str("✅ 未发现字节码层面的问题\n");
+            return out;
+        }
+
+        let mut findings: Vec<&BytecodeFinding> = self.findings.iter().collect();
+        findings.sort_by_key(|f| f.severity);
+        for f in findings {
+            let emoji = f.severity.emoji();
+            out.push_str(&format!("{emoji} **{}** `{}.{}` - {}\n", f.id, f.class, f.method, f.description));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"Compiled from "Svc.java"
+public class com.hot.Svc {
+  private static final java.lang.ThreadLocal<java.lang.String> CTX;
+
+  public synchronized void update();
+    Code:
+       0: getstatic     #19                 // Field CTX:Ljava/lang/ThreadLocal;
+       3: ldc           #23                 // String x
+       5: invokevirtual #25                 // Method java/lang/ThreadLocal.set:(Ljava/lang/Object;)V
+       8: return
+
+  public java.lang.String join(java.util.List<java.lang.String>);
+    Code:
+       0: ldc           #31                 // String
+       2: astore_2
+      10: aload_3
+      16: ifeq          42
+      30: aload_2
+      33: invokedynamic #51,  0             // InvokeDynamic #0:makeConcatWithConstants:(Ljava/lang/String;Ljava/lang/String;)Ljava/lang/String;
+      38: astore_2
+      39: goto          10
+      42: aload_2
+      43: areturn
+
+  public java.lang.String once(java.lang.String);
+    Code:
+       0: new           #55                 // class java/lang/StringBuilder
+       3: areturn
+}
+Compiled from "Pool.java"
+public class com.dep.Pool {
+  static final java.util.concurrent.ExecutorService SHARED;
+
+  public com.dep.Pool();
+    Code:
+       0: aload_0
+       5: invokestatic  #7                  // Method java/util/concurrent/Executors.newCachedThreadPool:()Ljava/util/concurrent/ExecutorService;
+      11: return
+
+  public void each(java.util.List<java.lang.Object>);
+    Code:
+       2: aload_1
+       3: monitorenter
+       4: aload_1
+       5: monitorexit
+       8: goto          2
+      11: return
+
+  static {};
+    Code:
+       0: invokestatic  #7                  // Method java/util/concurrent/Executors.newSingleThreadExecutor:()Ljava/util/concurrent/ExecutorService;
+       3: putstatic     #8                  // Field SHARED:Ljava/util/concurrent/ExecutorService;
+       6: return
+}
+"#;
+
+    #[test]
+    fn test_parse_javap() {
+        let methods = parse_javap(SAMPLE);
+        assert_eq!(methods.len(), 6);
+        assert_eq!(methods[0].class, "com.hot.Svc");
+        assert_eq!(methods[0].name(), "update");
+        assert!(methods[0].is_synchronized);
+        assert_eq!(methods[1].name(), "join");
+        assert_eq!(methods[1].code_size(), 44);
+        assert_eq!(methods[1].loop_ranges(), vec![(10, 39)]);
+    }
+
+    #[test]
+    fn test_detect_findings() {
+        let findings = detect(&parse_javap(SAMPLE));
+        let ids: Vec<_> = findings.iter().map(|f| (f.id, f.method.as_str())).collect();
+        assert_eq!(ids, vec![
+            ("BC_SYNC_METHOD", "update"),
+            ("BC_CONCAT_IN_LOOP", "join"),
+            ("BC_UNBOUNDED_POOL", "<init>"),
+            ("BC_MONITOR_IN_LOOP", "each"),
+            ("BC_UNBOUNDED_POOL", "<clinit>"),
+            ("BC_THREADLOCAL_LEAK", "update"),
+        ]);
+        assert_eq!(findings[5].class, "com.hot.Svc");
+
+        // 同类中有 remove() 时不报 ThreadLocal 泄漏
+        let with_remove = SAMPLE.replace(
+            "       8: return\n",
+            "       6: invokevirtual #26                 // Method java/lang/ThreadLocal.remove:()V\n       8: return\n",
+        );
+        assert!(detect(&parse_javap(&with_remove)).iter().all(|f| f.id != "BC_THREADLOCAL_LEAK"));
+
+        let huge = JavapMethod {
+            class: "A".into(),
+            signature: "void big()".into(),
+            instructions: vec![Instruction { offset: 9000, opcode: "return".into(), operand: String::new(), comment: String::new() }],
+            ..Default::default()
+        };
+        let findings = detect(&[huge]);
+        assert_eq!(findings[0].id, "BC_HUGE_METHOD");
+        assert_eq!(findings[0].severity, Severity::P0);
+    }
+
+    const HANDLERS: &str = r#"Compiled from "Parser.java"
+public class com.hot.Parser {
+  public int parse(java.lang.String);
+    Code:
+       0: aload_1
+       1: invokestatic  #7                  // Method java/lang/Integer.parseInt:(Ljava/lang/String;)I
+       4: ireturn
+       5: astore_2
+       6: iconst_0
+       7: ireturn
+       8: astore_2
+       9: iconst_m1
+      10: ireturn
+      11: astore_2
+      12: iconst_m1
+      13: ireturn
+      14: astore_2
+      15: aload_2
+      16: athrow
+    Exception table:
+       from    to  target type
+           0     4     5   Class java/lang/NumberFormatException
+           0     4     8   Class java/lang/NullPointerException
+           0     4    11   Class java/lang/IllegalStateException
+           0     4    14   any
+    LineNumberTable:
+      line 5: 0
+
+  public void log(java.lang.Object, java.lang.Object);
+    Code:
+       0: aload_0
+       1: monitorenter
+       2: aload_1
+       3: monitorenter
+       4: aload_1
+       5: invokedynamic #13,  0             // InvokeDynamic #0:makeConcatWithConstants:(Ljava/lang/Object;)Ljava/lang/String;
+       8: pop
+       9: aload_2
+      10: invokedynamic #13,  0             // InvokeDynamic #0:makeConcatWithConstants:(Ljava/lang/Object;)Ljava/lang/String;
+      13: pop
+      14: new           #17                 // class java/lang/StringBuilder
+      17: monitorexit
+     400: return
+
+  public abstract void close();
+}
+"#;
+
+    #[test]
+    fn test_exception_table_and_profile() {
+        let methods = parse_javap(HANDLERS);
+        assert_eq!(methods.len(), 3);
+        assert_eq!(methods[0].exception_handlers, 3);
+        assert_eq!(methods[0].instructions.len(), 15);
+        assert_eq!(methods[1].exception_handlers, 0);
+
+        let profiles = profile(&methods);
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].method, "log");
+        assert_eq!(profiles[0].code_size, 401);
+        assert_eq!(profiles[0].inline, InlineTier::Never);
+        assert_eq!(profiles[0].monitorenter, 2);
+        assert_eq!(profiles[0].string_concat, 3);
+        assert_eq!(profiles[1].inline, InlineTier::Always);
+        assert_eq!(InlineTier::of(FREQ_INLINE_SIZE), InlineTier::WhenHot);
+        assert_eq!(InlineTier::of(HUGE_METHOD_LIMIT + 1), InlineTier::NotCompiled);
+
+        // 按量级排序: 字节码大小 401 > 异常表 3 项 = 拼接 3 处 > monitorenter 2 处
+        let findings = hot_method_findings(&profiles);
+        let ids: Vec<_> = findings.iter().map(|f| (f.id, f.method.as_str())).collect();
+        assert_eq!(ids, vec![
+            ("BC_NOT_INLINABLE", "log"),
+            ("BC_STRING_CONCAT_HEAVY", "log"),
+            ("BC_EXCEPTION_DENSE", "parse"),
+            ("BC_MULTIPLE_MONITORS", "log"),
+        ]);
+
+        let report = ClassReport::new(&methods).render("Parser.class");
+        assert!(report.contains("| `log` | 401 | ⚠️ 不内联 | 0 | 2 | 3 |"));
+        assert!(report.contains("🟡 **BC_EXCEPTION_DENSE** `com.hot.Parser.parse`"));
+    }
+
+    #[test]
+    fn test_package_glob() {
+        let re = package_glob("com.acme.*").unwrap();
+        assert!(re.is_match("com.acme.Svc"));
+        assert!(!re.is_match("com.acme.sub.Svc"));
+
+        let re = package_glob("com.acme.**").unwrap();
+        assert!(re.is_match("com.acme.sub.Svc"));
+
+        let re = package_glob("com.acme").unwrap();
+        assert!(re.is_match("com.acme.sub.Svc"));
+        assert!(!re.is_match("com.acmex.Svc"));
+    }
+
+    #[test]
+    fn test_list_classes_war() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let war = dir.path().join("app.war");
+        let mut zip = zip::ZipWriter::new(std::fs::File::create(&war).unwrap());
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for name in [
+            "WEB-INF/classes/com/acme/Svc.class",
+            "WEB-INF/classes/com/acme/package-info.class",
+            "WEB-INF/lib/dep.jar",
+            "META-INF/versions/11/com/acme/Svc.class",
+            "index.html",
+        ] {
+            zip.start_file(name, options).unwrap();
+            zip.write_all(b"x").unwrap();
+        }
+        zip.finish().unwrap();
+
+        let classes = list_classes(&war).unwrap();
+        assert_eq!(classes, vec![("WEB-INF/classes/com/acme/Svc.class".to_string(), "com.acme.Svc".to_string())]);
+    }
+}
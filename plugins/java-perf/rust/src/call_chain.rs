@@ -0,0 +1,141 @@
+//! Call Chain 模块 - 调用链影响面查询 (v9.6)
+//!
+//! 🎯 给定 类 + 方法，基于 Phase 1 调用图返回:
+//! - **upstream**: 所有能到达该方法的 Controller 入口路径 (谁会受影响)
+//! - **downstream**: 该方法最终触达的 Repository 调用路径 (会打到哪些 DAO)
+//!
+//! 供 Agent 在 Sniper 阶段评估一个被标记方法的影响面 (blast radius)。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::ast_engine;
+use crate::taint::{CallGraph, LayerType, MethodSig};
+
+/// 默认追踪深度
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// 每个方向最多返回的路径数
+const MAX_PATHS: usize = 20;
+
+/// 路径上的一个方法
+#[derive(Debug, Serialize)]
+struct Hop {
+    method: String,
+    layer: LayerType,
+}
+
+/// 一个目标方法的调用链
+#[derive(Debug, Serialize)]
+struct MethodChain {
+    method: String,
+    layer: LayerType,
+    upstream: Vec<Vec<Hop>>,
+    downstream: Vec<Vec<Hop>>,
+    /// 直接调用者 (一跳)
+    callers: Vec<String>,
+    /// 直接被调用方 (一跳)
+    callees: Vec<String>,
+}
+
+/// 查询 `class.method` 的上游 Controller 路径与下游 Repository 路径
+///
+/// `class` 可以是 FQN 或简单类名；同名类各自返回一条结果。
+pub fn get_call_chain(root: &str, class: &str, method: &str, max_depth: usize) -> Result<Value, Box<dyn std::error::Error>> {
+    let index = ast_engine::index_project(root)?;
+    let graph = &index.call_graph;
+
+    let targets = graph.find_methods(class, method);
+    if targets.is_empty() {
+        return Err(format!("Method not found in call graph: {class}.{method}").into());
+    }
+
+    let chains: Vec<MethodChain> = targets.iter().map(|target| chain_for(graph, target, max_depth)).collect();
+    let report = render_report(&chains);
+    Ok(json!({
+        "query": { "class": class, "method": method, "max_depth": max_depth },
+        "methods": chains,
+        "report": report,
+    }))
+}
+
+fn chain_for(graph: &CallGraph, target: &MethodSig, max_depth: usize) -> MethodChain {
+    let hops = |path: Vec<MethodSig>| -> Vec<Hop> {
+        path.into_iter().map(|m| Hop { layer: graph.layer_of(&m), method: m.full_name() }).collect()
+    };
+    let mut upstream = graph.trace_from_layer(target, LayerType::Controller, max_depth);
+    let mut downstream = graph.trace_to_layer(target, LayerType::Repository, max_depth);
+    // HashMap 遍历顺序不稳定，排序后输出
+    for paths in [&mut upstream, &mut downstream] {
+        paths.sort_by_key(|p| p.iter().map(MethodSig::full_name).collect::<Vec<_>>());
+        paths.truncate(MAX_PATHS);
+    }
+
+    let neighbours = |sites: Option<&Vec<crate::taint::CallSite>>, pick: fn(&crate::taint::CallSite) -> &MethodSig| {
+        let mut names: Vec<String> = sites.into_iter().flatten().map(|s| pick(s).full_name()).collect();
+        names.sort();
+        names.dedup();
+        names
+    };
+
+    MethodChain {
+        method: target.full_name(),
+        layer: graph.layer_of(target),
+        upstream: upstream.into_iter().map(hops).collect(),
+        downstream: downstream.into_iter().map(hops).collect(),
+        callers: neighbours(graph.incoming.get(target), |s| &s.caller),
+        callees: neighbours(graph.outgoing.get(target), |s| &s.callee),
+    }
+}
+
+fn render_report(chains: &[MethodChain]) -> String {
+    let path_str = |path: &[Hop]| path.iter().map(|h| h.method.as_str()).collect::<Vec<_>>().join(" → ");
+    let mut out = String::new();
+    for chain in chains {
+        out.push_str(&format!("## 🔗 调用链: {} ({:?})\n\n", chain.method, chain.layer));
+        out.push_str(&format!("**上游 Controller 入口** ({}):\n", chain.upstream.len()));
+        if chain.upstream.is_empty() {
+            out.push_str("- (无: 未找到从 Controller 到达该方法的路径)\n");
+        }
+        for path in &chain.upstream {
+            out.push_str(&format!("- {}\n", path_str(path)));
+        }
+        out.push_str(&format!("\n**下游 Repository 调用** ({}):\n", chain.downstream.len()));
+        if chain.downstream.is_empty() {
+            out.push_str("- (无: 该方法不会到达 Repository 层)\n");
+        }
+        for path in &chain.downstream {
+            out.push_str(&format!("- {}\n", path_str(path)));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get_call_chain_upstream_and_downstream() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("UserDao.java"), "package com.acme;\n\n@Repository\npublic interface UserDao {\n    Object findById(Long id);\n}\n").unwrap();
+        std::fs::write(pkg.join("UserService.java"), "package com.acme;\n\n@Service\npublic class UserService {\n    @Autowired\n    private UserDao userDao;\n\n    public void load(java.util.List<Long> ids) {\n        for (Long id : ids) {\n            userDao.findById(id);\n        }\n    }\n}\n").unwrap();
+        std::fs::write(pkg.join("UserController.java"), "package com.acme;\n\n@RestController\npublic class UserController {\n    @Autowired\n    private UserService userService;\n\n    public Object get() {\n        return this.userService.load(null);\n    }\n}\n").unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let v = get_call_chain(root, "UserService", "load", DEFAULT_MAX_DEPTH).unwrap();
+        let chain = &v["methods"][0];
+        assert_eq!(chain["method"], "com.acme.UserService.load");
+        assert_eq!(chain["layer"], "Service");
+        assert_eq!(chain["upstream"][0][0]["method"], "com.acme.UserController.get");
+        assert_eq!(chain["upstream"][0][0]["layer"], "Controller");
+        assert_eq!(chain["downstream"][0][1]["method"], "com.acme.UserDao.findById");
+        assert_eq!(chain["callers"][0], "com.acme.UserController.get");
+        assert!(v["report"].as_str().unwrap().contains("UserController.get → com.acme.UserService.load"));
+
+        assert!(get_call_chain(root, "UserService", "missing", DEFAULT_MAX_DEPTH).is_err());
+    }
+}
@@ -1,12 +1,45 @@
 //! 检查清单知识库
 //!
 //! 来自 checklist-data.ts 的核心诊断知识
+//!
+//! v9.6: 数据在 `resources/checklist.yaml`，编译时嵌入；同格式的扩展文件可以添加公司内部的
+//! 检查章节、映射自定义症状，按以下顺序查找 (只加载第一个)：
+//! 1. `checklist --kb <file>`
+//! 2. 环境变量 `JAVA_PERF_CHECKLIST`
+//! 3. 当前目录的 `.java-perf/checklist.yaml`
+//!
+//! 合并规则: 与内置同 id 的章节整体替换，新 id 追加在末尾；症状的章节列表与关键词追加到同名症状 (去重)。
+//! `java-perf update` 安装的规则包中的 `checklist.yaml` 按同样规则先合并到内置数据上，扩展文件最后合并。
+//!
+//! 症状参数既可以是症状名 (`timeout`)，也可以是自由文本描述 (`接口偶发超时`)：
+//! 后者按 `keywords` 匹配，再回退到与症状名的编辑距离 (拼写错误)，见 [`KnowledgeBase::resolve_symptom`]。
+//! 扩展文件无法解析时打印警告并只使用内置数据；`checklist --validate` 给出完整的错误列表。
 
-use serde::Serialize;
+use once_cell::sync::{Lazy, OnceCell};
+use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use crate::ast_engine::ScanOptions;
+use crate::i18n;
+use crate::rules::catalog::{self, RuleEntry};
+use crate::rules::profile;
+use crate::rules::taxonomy::Tag;
+use crate::rule_pack;
+
+/// 知识库格式版本
+const KB_VERSION: u32 = 1;
+/// 扩展文件的默认位置 (相对当前目录)
+const DEFAULT_EXTENSION: &str = ".java-perf/checklist.yaml";
+/// 指定扩展文件的环境变量
+const EXTENSION_ENV: &str = "JAVA_PERF_CHECKLIST";
+/// 允许的章节优先级
+const PRIORITIES: &[&str] = &["P0", "P1", "P2"];
 
 /// 检查项
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CheckItem {
     pub desc: String,
     pub verify: Option<String>,
@@ -16,7 +49,8 @@ pub struct CheckItem {
 }
 
 /// 检查章节
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct CheckSection {
     pub id: String,
     pub title: String,
@@ -24,231 +58,320 @@ pub struct CheckSection {
     pub items: Vec<CheckItem>,
 }
 
-/// 症状到章节的映射
-pub fn get_sections_for_symptom(symptom: &str) -> Vec<&'static str> {
-    match symptom {
-        "memory" => vec!["5", "0", "4"],  // 内存 -> 内存与缓存, 代码放大, 资源池
-        "cpu" => vec!["0", "1"],           // CPU -> 代码放大, 锁与并发
-        "slow" => vec!["0", "2", "3"],     // 慢 -> 代码放大, IO阻塞, 外部调用
-        "resource" => vec!["4", "2"],      // 资源 -> 资源池, IO阻塞
-        "backlog" => vec!["0", "4"],       // 积压 -> 代码放大, 资源池
-        "gc" => vec!["5", "0"],            // GC -> 内存缓存, 代码放大
-        _ => vec![],
+/// 检查清单知识库: 章节 + 症状到章节的映射
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnowledgeBase {
+    pub version: u32,
+    #[serde(default)]
+    pub symptoms: BTreeMap<String, Vec<String>>,
+    /// 症状 -> 自由文本描述中的关键词
+    #[serde(default)]
+    pub keywords: BTreeMap<String, Vec<String>>,
+    #[serde(default)]
+    pub sections: Vec<CheckSection>,
+}
+
+static BUILTIN: Lazy<KnowledgeBase> = Lazy::new(|| {
+    serde_yaml::from_str(include_str!("../resources/checklist.yaml")).expect("invalid built-in checklist")
+});
+
+/// `checklist --kb` 指定的扩展文件
+static EXTENSION: OnceCell<PathBuf> = OnceCell::new();
+
+impl KnowledgeBase {
+    /// 解析扩展文件
+    pub fn from_file(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+        serde_yaml::from_str(&content).map_err(|e| format!("{}: {e}", path.display()))
+    }
+
+    /// 症状对应的章节 ID (未知症状返回空)
+    pub fn sections_for(&self, symptom: &str) -> &[String] {
+        self.symptoms.get(symptom).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 合并扩展: 同 id 章节替换，新章节追加；症状映射追加
+    pub fn merge(&mut self, extension: KnowledgeBase) {
+        for section in extension.sections {
+            match self.sections.iter_mut().find(|s| s.id == section.id) {
+                Some(existing) => *existing = section,
+                None => self.sections.push(section),
+            }
+        }
+        for (target, extra) in [(&mut self.symptoms, extension.symptoms), (&mut self.keywords, extension.keywords)] {
+            for (symptom, values) in extra {
+                let entry = target.entry(symptom).or_default();
+                for value in values {
+                    if !entry.contains(&value) {
+                        entry.push(value);
+                    }
+                }
+            }
+        }
+    }
+
+    /// 把症状参数解析为症状名
+    ///
+    /// 已知症状名原样返回；否则按关键词命中数排序返回全部命中的症状；
+    /// 都没有命中时取编辑距离最近的症状名 (长度 >= 6 允许 2 处差异，否则 1 处)。
+    pub fn resolve_symptom(&self, input: &str) -> Vec<String> {
+        let text = input.trim().to_lowercase();
+        if self.symptoms.contains_key(&text) {
+            return vec![text];
+        }
+
+        let mut hits: Vec<(usize, &String)> = self.keywords.iter()
+            .filter(|(symptom, _)| self.symptoms.contains_key(*symptom))
+            .map(|(symptom, words)| (words.iter().filter(|w| keyword_matches(&text, w)).count(), symptom))
+            .filter(|(n, _)| *n > 0)
+            .collect();
+        hits.sort_by_key(|(n, _)| std::cmp::Reverse(*n));
+        if !hits.is_empty() {
+            return hits.into_iter().map(|(_, s)| s.clone()).collect();
+        }
+
+        let allowed = if text.chars().count() >= 6 { 2 } else { 1 };
+        self.symptoms.keys()
+            .map(|s| (edit_distance(&text, s), s))
+            .filter(|(d, _)| *d <= allowed)
+            .min_by_key(|(d, _)| *d)
+            .map(|(_, s)| vec![s.clone()])
+            .unwrap_or_default()
     }
+
+    /// 结构校验，返回错误列表 (空表示通过)
+    ///
+    /// `base`: 扩展文件校验时传入内置知识库，症状可以引用内置章节。
+    pub fn validate(&self, base: Option<&KnowledgeBase>) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.version != KB_VERSION {
+            errors.push(format!("version: 不支持的版本 {} (当前为 {KB_VERSION})", self.version));
+        }
+
+        let mut ids = HashSet::new();
+        for (i, section) in self.sections.iter().enumerate() {
+            let at = format!("sections[{i}]");
+            if section.id.trim().is_empty() {
+                errors.push(format!("{at}.id: 不能为空"));
+            } else if !ids.insert(section.id.as_str()) {
+                errors.push(format!("{at}.id: 重复的章节 id \"{}\"", section.id));
+            }
+            if section.title.trim().is_empty() {
+                errors.push(format!("{at}.title: 不能为空"));
+            }
+            if !PRIORITIES.contains(&section.priority.as_str()) {
+                errors.push(format!("{at}.priority: \"{}\" 不是 {}", section.priority, PRIORITIES.join("/")));
+            }
+            if section.items.is_empty() {
+                errors.push(format!("{at}.items: 至少需要一个检查项"));
+            }
+            for (n, item) in section.items.iter().enumerate() {
+                if item.desc.trim().is_empty() {
+                    errors.push(format!("{at}.items[{n}].desc: 不能为空"));
+                }
+            }
+        }
+
+        let known = |id: &str| ids.contains(id) || base.is_some_and(|b| b.sections.iter().any(|s| s.id == id));
+        for (symptom, sections) in &self.symptoms {
+            if symptom.is_empty() || !symptom.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-') {
+                errors.push(format!("symptoms.{symptom}: 症状名只能包含小写字母、数字和 '-'"));
+            }
+            if sections.is_empty() {
+                errors.push(format!("symptoms.{symptom}: 至少映射一个章节"));
+            }
+            for id in sections.iter().filter(|id| !known(id)) {
+                errors.push(format!("symptoms.{symptom}: 引用了不存在的章节 \"{id}\""));
+            }
+        }
+        for (symptom, words) in &self.keywords {
+            if !self.symptoms.contains_key(symptom) && !base.is_some_and(|b| b.symptoms.contains_key(symptom)) {
+                errors.push(format!("keywords.{symptom}: 症状未在 symptoms 中定义"));
+            }
+            if words.iter().any(|w| w.trim().is_empty()) {
+                errors.push(format!("keywords.{symptom}: 关键词不能为空"));
+            }
+        }
+        errors
+    }
+}
+
+/// 关键词是否出现在 (已小写的) 文本中: ASCII 关键词按整词匹配，其他按子串匹配
+fn keyword_matches(text: &str, keyword: &str) -> bool {
+    let keyword = keyword.to_lowercase();
+    if !keyword.is_ascii() {
+        return text.contains(&keyword);
+    }
+    let is_word = |c: char| c.is_ascii_alphanumeric() || c == '_';
+    text.match_indices(&keyword).any(|(i, _)| {
+        let before = text[..i].chars().next_back();
+        let after = text[i + keyword.len()..].chars().next();
+        !before.is_some_and(is_word) && !after.is_some_and(is_word)
+    })
 }
 
-/// 获取所有检查清单数据
+/// Levenshtein 编辑距离 (按字符)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut cur = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            cur[j + 1] = (prev[j] + usize::from(ca != *cb)).min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        prev = cur;
+    }
+    prev[b.len()]
+}
+
+/// 指定扩展文件 (CLI `checklist --kb`，启动时调用一次)
+pub fn set_extension(path: PathBuf) {
+    let _ = EXTENSION.set(path);
+}
+
+/// 生效的扩展文件: --kb > JAVA_PERF_CHECKLIST > ./.java-perf/checklist.yaml (存在时)
+pub fn extension_path() -> Option<PathBuf> {
+    EXTENSION.get().cloned()
+        .or_else(|| std::env::var_os(EXTENSION_ENV).filter(|v| !v.is_empty()).map(PathBuf::from))
+        .or_else(|| Some(PathBuf::from(DEFAULT_EXTENSION)).filter(|p| p.is_file()))
+}
+
+/// 内置知识库 (按 --lang 本地化) 合并已安装规则包与扩展文件
+pub fn knowledge_base() -> KnowledgeBase {
+    let mut kb = BUILTIN.clone();
+    localize_sections(&mut kb.sections);
+    if let Some(path) = rule_pack::installed_checklist() {
+        match KnowledgeBase::from_file(&path) {
+            Ok(pack) => kb.merge(pack),
+            Err(e) => tracing::warn!("忽略规则包检查清单 {e}"),
+        }
+    }
+    if let Some(path) = extension_path() {
+        match KnowledgeBase::from_file(&path) {
+            Ok(extension) => kb.merge(extension),
+            Err(e) => tracing::warn!("忽略检查清单扩展 {e} (用 checklist --validate 查看详情)"),
+        }
+    }
+    kb
+}
+
+/// 校验检查清单扩展文件 (规则包安装前调用)，返回全部错误
+pub fn validate_extension_file(path: &Path) -> Result<(), Vec<String>> {
+    let extension = KnowledgeBase::from_file(path).map_err(|e| vec![e])?;
+    let errors = extension.validate(Some(&BUILTIN));
+    if errors.is_empty() { Ok(()) } else { Err(errors) }
+}
+
+/// 获取所有检查清单数据 (按 --lang 本地化，含扩展章节)
 pub fn get_checklist_data() -> Vec<CheckSection> {
-    vec![
-        CheckSection {
-            id: "0".to_string(),
-            title: "代码级放大效应".to_string(),
-            priority: "P0".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "循环内 IO/计算（for/while 内的 DB 查询、RPC）".to_string(),
-                    verify: Some("grep -n \"for.*{\" | 检查内部是否有 dao/rpc 调用".to_string()),
-                    threshold: None,
-                    fix: Some("批量查询替代循环查询".to_string()),
-                    why: Some("循环100次 x 每次10ms = 1秒".to_string()),
-                },
-                CheckItem {
-                    desc: "集合笛卡尔积（嵌套循环 O(N*M)）".to_string(),
-                    verify: Some("搜索嵌套 for 循环".to_string()),
-                    threshold: Some("N*M > 10000 需优化".to_string()),
-                    fix: Some("用 Map 降到 O(N+M)".to_string()),
-                    why: Some("100x100=1万次".to_string()),
-                },
-                CheckItem {
-                    desc: "频繁对象创建（循环内 new 对象）".to_string(),
-                    verify: Some("async-profiler -e alloc".to_string()),
-                    threshold: None,
-                    fix: Some("对象池/复用".to_string()),
-                    why: Some("频繁 new 导致 GC 压力".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "1".to_string(),
-            title: "锁与并发".to_string(),
-            priority: "P0".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "锁粒度过大（synchronized 方法或大代码块）".to_string(),
-                    verify: Some("jstack | grep -A 20 \"BLOCKED\"".to_string()),
-                    threshold: None,
-                    fix: Some("细化锁粒度/读写锁".to_string()),
-                    why: Some("大锁让并发变串行".to_string()),
-                },
-                CheckItem {
-                    desc: "死锁风险（嵌套锁获取顺序不一致）".to_string(),
-                    verify: Some("jstack | grep \"deadlock\"".to_string()),
-                    threshold: None,
-                    fix: None,
-                    why: Some("线程A持有锁1等锁2，线程B持有锁2等锁1".to_string()),
-                },
-                CheckItem {
-                    desc: "ReentrantLock 未在 finally 中释放".to_string(),
-                    verify: Some("搜索 lock.lock() 调用，检查是否有 finally { unlock }".to_string()),
-                    threshold: None,
-                    fix: Some("lock.lock(); try { ... } finally { lock.unlock(); }".to_string()),
-                    why: Some("异常时锁不释放，其他线程永久等待".to_string()),
-                },
-                CheckItem {
-                    desc: "synchronized 块内 Thread.sleep()".to_string(),
-                    verify: Some("搜索 synchronized 块内的 sleep 调用".to_string()),
-                    threshold: None,
-                    fix: Some("将 sleep 移出 synchronized 块或使用 wait/notify".to_string()),
-                    why: Some("持锁睡眠导致其他线程长时间阻塞".to_string()),
-                },
-                CheckItem {
-                    desc: "Future.get() 无超时参数".to_string(),
-                    verify: Some("搜索 .get() 调用，检查是否有超时参数".to_string()),
-                    threshold: None,
-                    fix: Some("使用 future.get(timeout, TimeUnit.SECONDS)".to_string()),
-                    why: Some("无超时会导致线程永久阻塞".to_string()),
-                },
-                CheckItem {
-                    desc: "CountDownLatch.await() / Semaphore.acquire() 无超时".to_string(),
-                    verify: Some("搜索 .await() 或 .acquire() 调用".to_string()),
-                    threshold: None,
-                    fix: Some("使用 await(timeout, unit) 或 tryAcquire(timeout, unit)".to_string()),
-                    why: Some("无超时可能永久等待".to_string()),
-                },
-                CheckItem {
-                    desc: "CompletableFuture.join() 同步阻塞".to_string(),
-                    verify: Some("搜索 .join() 调用".to_string()),
-                    threshold: None,
-                    fix: Some("使用 orTimeout() 或 completeOnTimeout()".to_string()),
-                    why: Some("join() 永久阻塞，无法设置超时".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "2".to_string(),
-            title: "IO 与阻塞".to_string(),
-            priority: "P0".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "同步 IO（NIO/Netty 线程中混入阻塞操作）".to_string(),
-                    verify: Some("检查 EventLoop 线程内是否有 JDBC/File IO".to_string()),
-                    threshold: None,
-                    fix: None,
-                    why: Some("EventLoop 线程被阻塞后，该线程上的所有连接都无法处理".to_string()),
-                },
-                CheckItem {
-                    desc: "资源未关闭（InputStream/Connection 未 close）".to_string(),
-                    verify: Some("lsof -p PID | wc -l".to_string()),
-                    threshold: Some("句柄 > 10000 告警".to_string()),
-                    fix: Some("try-with-resources".to_string()),
-                    why: Some("资源泄露导致句柄耗尽".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "3".to_string(),
-            title: "外部调用".to_string(),
-            priority: "P1".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "无超时设置（HTTPClient, Dubbo, DB 连接）".to_string(),
-                    verify: Some("搜索 timeout/connectTimeout 配置".to_string()),
-                    threshold: None,
-                    fix: Some("统一配置超时 3-5s".to_string()),
-                    why: Some("无超时的请求可能永久等待".to_string()),
-                },
-                CheckItem {
-                    desc: "同步串行调用（多下游串行）".to_string(),
-                    verify: Some("arthas: trace 检查调用链".to_string()),
-                    threshold: None,
-                    fix: Some("CompletableFuture 并行".to_string()),
-                    why: Some("串行 A+B+C = 300ms，并行 = max(A,B,C) = 100ms".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "4".to_string(),
-            title: "资源池管理".to_string(),
-            priority: "P0".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "无界线程池（Executors.newCachedThreadPool）".to_string(),
-                    verify: Some("arthas: thread -n 10".to_string()),
-                    threshold: Some("线程 > 200 告警".to_string()),
-                    fix: Some("ThreadPoolExecutor 有界".to_string()),
-                    why: Some("无界池遇到流量洪峰无限创建线程".to_string()),
-                },
-                CheckItem {
-                    desc: "池资源泄露（获取后未归还）".to_string(),
-                    verify: Some("jstack | grep pool".to_string()),
-                    threshold: None,
-                    fix: Some("finally 归还".to_string()),
-                    why: Some("每次请求泄露1个连接，池很快被占满".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "5".to_string(),
-            title: "内存与缓存".to_string(),
-            priority: "P0".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "无界缓存（static Map 无 TTL/Size 限制）".to_string(),
-                    verify: Some("jmap -histo:live | head -20".to_string()),
-                    threshold: None,
-                    fix: Some("Caffeine/Guava Cache".to_string()),
-                    why: Some("只增不删的缓存是内存泄露".to_string()),
-                },
-                CheckItem {
-                    desc: "ThreadLocal 泄露（请求结束未 remove）".to_string(),
-                    verify: Some("搜索 ThreadLocal 未配对 remove()".to_string()),
-                    threshold: None,
-                    fix: Some("finally 中 remove()".to_string()),
-                    why: Some("线程池复用线程，ThreadLocal 不清理导致内存累积".to_string()),
-                },
-                CheckItem {
-                    desc: "大对象分配（一次性加载大文件/全量表）".to_string(),
-                    verify: Some("MAT 分析 Dominator Tree".to_string()),
-                    threshold: Some("单对象 > 10MB 关注".to_string()),
-                    fix: None,
-                    why: Some("大对象直接进入老年代，触发 Full GC".to_string()),
-                },
-            ],
-        },
-        CheckSection {
-            id: "6".to_string(),
-            title: "异常处理".to_string(),
-            priority: "P2".to_string(),
-            items: vec![
-                CheckItem {
-                    desc: "异常吞没（catch 后仅打印）".to_string(),
-                    verify: Some("搜索 catch.*{.*e.printStackTrace".to_string()),
-                    threshold: None,
-                    fix: None,
-                    why: Some("异常被吞掉导致问题难以追溯".to_string()),
-                },
-            ],
-        },
-    ]
+    knowledge_base().sections
+}
+
+/// 用当前语言目录覆盖检查清单文本
+///
+/// 消息 ID: `checklist.<章节>.title`, `checklist.<章节>.<序号>.<字段>`
+fn localize_sections(sections: &mut [CheckSection]) {
+    fn apply(field: &mut String, id: String) {
+        if let Some(text) = i18n::lookup(&id) {
+            *field = text.to_string();
+        }
+    }
+
+    for section in sections.iter_mut() {
+        apply(&mut section.title, format!("checklist.{}.title", section.id));
+        for (n, item) in section.items.iter_mut().enumerate() {
+            let prefix = format!("checklist.{}.{}", section.id, n);
+            apply(&mut item.desc, format!("{prefix}.desc"));
+            for (name, field) in [
+                ("verify", &mut item.verify),
+                ("threshold", &mut item.threshold),
+                ("fix", &mut item.fix),
+                ("why", &mut item.why),
+            ] {
+                if let Some(value) = field {
+                    apply(value, format!("{prefix}.{name}"));
+                }
+            }
+        }
+    }
+}
+
+/// 校验内置知识库与生效的扩展文件 (`checklist --validate`)
+pub fn validate_knowledge_base() -> Result<Value, Box<dyn std::error::Error>> {
+    let mut errors: Vec<String> = BUILTIN.validate(None).into_iter().map(|e| format!("[内置] {e}")).collect();
+    let extension = extension_path();
+    let mut kb = BUILTIN.clone();
+    if let Some(path) = &extension {
+        match KnowledgeBase::from_file(path) {
+            Ok(ext) => {
+                errors.extend(ext.validate(Some(&BUILTIN)).into_iter().map(|e| format!("[{}] {e}", path.display())));
+                kb.merge(ext);
+            }
+            Err(e) => errors.push(e),
+        }
+    }
+
+    let source = extension.as_ref().map_or_else(|| "内置".to_string(), |p| format!("内置 + {}", p.display()));
+    let mut report = format!(
+        "## 📋 检查清单知识库校验\n\n**来源**: {source} | **章节**: {} | **症状**: {}\n\n",
+        kb.sections.len(),
+        kb.symptoms.len(),
+    );
+    if errors.is_empty() {
+        report.push_str("✅ 校验通过\n");
+    } else {
+        report.push_str(&format!("❌ {} 个错误:\n\n", errors.len()));
+        for e in &errors {
+            report.push_str(&format!("- {e}\n"));
+        }
+    }
+
+    Ok(json!({
+        "valid": errors.is_empty(),
+        "extension": extension.map(|p| p.display().to_string()),
+        "sections": kb.sections.len(),
+        "symptoms": kb.symptoms.keys().collect::<Vec<_>>(),
+        "errors": errors,
+        "report": report,
+    }))
 }
 
 /// 获取检查清单（按症状）
 /// 
 /// compact: true 时只返回检查项描述，省略 verify/fix/why
 pub fn get_checklist(symptoms: &[&str], priority_filter: Option<&str>, compact: bool) -> Result<Value, Box<dyn std::error::Error>> {
-    let all_data = get_checklist_data();
+    let kb = knowledge_base();
+
+    // 解析症状 (症状名或自由文本描述)
+    let mut resolved: Vec<String> = Vec::new();
+    let mut described: Vec<(&str, Vec<String>)> = Vec::new();
+    let mut unknown: Vec<&str> = Vec::new();
+    for input in symptoms.iter().map(|s| s.trim()).filter(|s| !s.is_empty()) {
+        let names = kb.resolve_symptom(input);
+        if names.is_empty() {
+            unknown.push(input);
+        } else if names.len() > 1 || names[0] != input {
+            described.push((input, names.clone()));
+        }
+        for name in names {
+            if !resolved.contains(&name) {
+                resolved.push(name);
+            }
+        }
+    }
     
     // 收集相关章节ID
-    let mut section_ids: std::collections::HashSet<&str> = std::collections::HashSet::new();
-    for symptom in symptoms {
-        for id in get_sections_for_symptom(symptom) {
+    let mut section_ids: HashSet<&str> = HashSet::new();
+    for symptom in &resolved {
+        for id in kb.sections_for(symptom) {
             section_ids.insert(id);
         }
     }
     
     let mut result_sections: Vec<&CheckSection> = Vec::new();
     
-    for section in &all_data {
+    for section in &kb.sections {
         if section_ids.contains(section.id.as_str()) {
             // 优先级过滤
             if let Some(filter) = priority_filter {
@@ -261,107 +384,434 @@ pub fn get_checklist(symptoms: &[&str], priority_filter: Option<&str>, compact:
     }
     
     // 根据 compact 模式生成不同报告
-    if compact {
-        // 紧凑模式
-        let mut report = format!(
-            "## 🔍 检查清单 (紧凑模式) - 症状: {}\n\n",
-            symptoms.join(", ")
-        );
-        
-        for section in &result_sections {
-            let emoji = match section.priority.as_str() {
-                "P0" => "🔴",
-                "P1" => "🟡",
-                _ => "🔵",
-            };
+    let mut report = if compact {
+        i18n::format(
+            "report.checklist.title_compact",
+            "## 🔍 检查清单 (紧凑模式) - 症状: {symptoms}",
+            &[("symptoms", &resolved.join(", "))],
+        )
+    } else {
+        i18n::format(
+            "report.checklist.title",
+            "## 🔍 检查清单 (症状: {symptoms})",
+            &[("symptoms", &resolved.join(", "))],
+        )
+    };
+    report.push_str("\n\n");
+    if !described.is_empty() {
+        report.push_str(&format!("**{}**:\n", i18n::text("report.checklist.resolved", "从描述推断的症状")));
+        for (input, names) in &described {
+            report.push_str(&format!("- `{input}` → {}\n", names.join(", ")));
+        }
+        report.push('\n');
+    }
+    if !unknown.is_empty() {
+        let available: Vec<&str> = kb.symptoms.keys().map(String::as_str).collect();
+        report.push_str(&format!(
+            "⚠️ {}: {}\n\n",
+            i18n::format("report.checklist.unknown", "未识别的症状 (可用: {available})", &[("available", &available.join(", "))]),
+            unknown.iter().map(|s| format!("`{s}`")).collect::<Vec<_>>().join(", "),
+        ));
+    }
+    render_sections(&mut report, &result_sections, compact);
+
+    Ok(json!(report))
+}
+
+/// 渲染检查清单章节
+///
+/// compact: true 时只输出检查项描述；否则输出 验证/修复 细节
+fn render_sections(report: &mut String, sections: &[&CheckSection], compact: bool) {
+    let verify_label = i18n::text("report.checklist.verify", "验证");
+    let fix_label = i18n::text("report.checklist.fix", "修复");
+
+    for section in sections {
+        let emoji = match section.priority.as_str() {
+            "P0" => "🔴",
+            "P1" => "🟡",
+            _ => "🔵",
+        };
+
+        if compact {
             report.push_str(&format!("**{} {}**\n", emoji, section.title));
-            
             for item in &section.items {
                 report.push_str(&format!("- {}\n", item.desc));
             }
-            report.push('\n');
-        }
-        
-        Ok(json!(report))
-    } else {
-        // 完整模式
-        let mut report = format!(
-            "## 🔍 检查清单 (症状: {})\n\n",
-            symptoms.join(", ")
-        );
-        
-        for section in &result_sections {
-            report.push_str(&format!(
-                "### {} {} ({})\n\n",
-                match section.priority.as_str() {
-                    "P0" => "🔴",
-                    "P1" => "🟡",
-                    _ => "🔵",
-                },
-                section.title,
-                section.priority
-            ));
-            
+        } else {
+            report.push_str(&format!("### {} {} ({})\n\n", emoji, section.title, section.priority));
             for item in &section.items {
                 report.push_str(&format!("- **{}**\n", item.desc));
                 if let Some(verify) = &item.verify {
-                    report.push_str(&format!("  - 验证: `{verify}`\n"));
+                    report.push_str(&format!("  - {verify_label}: `{verify}`\n"));
                 }
                 if let Some(fix) = &item.fix {
-                    report.push_str(&format!("  - 修复: {fix}\n"));
+                    report.push_str(&format!("  - {fix_label}: {fix}\n"));
                 }
             }
-            report.push('\n');
         }
-        
-        Ok(json!(report))
+        report.push('\n');
+    }
+}
+
+// ============================================================================
+// v9.6: 从扫描报告推断症状
+// ============================================================================
+
+/// 规则 ID 到症状的映射 (按问题家族归类)
+///
+/// 未归类的规则不参与推断。
+pub fn symptom_for_rule(rule_id: &str) -> Option<&'static str> {
+    let symptom = match rule_id {
+        // 积压: 无界/默认线程池、任务堆积、背压缺失、池过小、重试放大
+        "UNBOUNDED_POOL" | "ASYNC_DEFAULT_POOL" | "SCHEDULED_FIXED_RATE" | "SCHEDULED_CRON_OVERLAP" | "EMITTER_UNBOUNDED"
+        | "SINKS_MANY" | "TOMCAT_THREADS_LOW" | "DB_POOL_SMALL" | "LISTENER_BLOCKING_CALL"
+        | "LISTENER_AUTO_ACK_SLOW" | "LISTENER_TRANSACTIONAL" | "THREAD_IN_REQUEST_SCOPE"
+        | "RETRY_NO_BACKOFF" | "RETRY_EXCESSIVE_ATTEMPTS" | "CIRCUIT_BREAKER_TINY_WINDOW"
+        | "SEMAPHORE_NO_RELEASE" => "backlog",
+        // 内存: 静态集合、ThreadLocal、无过期缓存、无界收集
+        "STATIC_COLLECTION" | "THREADLOCAL_LEAK" | "MDC_LEAK" | "CACHE_NO_EXPIRE" | "FLUX_COLLECT_LIST"
+        | "STRING_INTERN" | "LISTENER_UNBOUNDED_BUFFER" | "UNBOUNDED_QUERY_RESULT" => "memory",
+        // GC: 频繁分配、大数组、finalize、软引用
+        "OBJECT_IN_LOOP" | "LARGE_ARRAY" | "FINALIZE_OVERRIDE" | "SOFT_REFERENCE"
+        | "COLLECTION_NO_CAPACITY" | "DATE_IN_LOOP" | "CALENDAR_PER_CALL" | "STREAM_COLLECT_SIZE"
+        | "MAX_ALLOCATIONS_IN_LOOP" => "gc",
+        // CPU: 嵌套循环、锁竞争、自旋
+        "NESTED_LOOP" | "NESTED_LOOP_MIXED" | "SYNC_METHOD" | "SYNC_BLOCK" | "ATOMIC_SPIN"
+        | "STRING_CONCAT_LOOP" | "DOUBLE_CHECKED_LOCKING" | "REGEX_REDOS"
+        | "PATTERN_COMPILE_HOT_PATH" | "LIST_CONTAINS_IN_LOOP" | "LINKEDLIST_RANDOM_ACCESS"
+        | "SYNCHRONIZED_MAP" | "SERIALIZER_PER_CALL" | "SERIALIZE_IN_LOOP"
+        | "DATE_FORMATTER_IN_LOOP" | "SCHEDULED_POOL_ZERO_CORE" | "STREAM_COUNT_FOR_EXISTENCE"
+        | "STREAM_REPEATED" | "RECORD_MUTABLE_COMPONENT" | "SWITCH_PATTERN_NO_DEFAULT"
+        | "LOG_ROOT_DEBUG" | "LOG_CALLER_DATA_PATTERN" | "MAX_LOOP_DEPTH" => "cpu",
+        // 慢: 循环 IO、无超时阻塞、慢 SQL
+        "N_PLUS_ONE" | "N_PLUS_ONE_WHILE" | "N_PLUS_ONE_FOREACH" | "SAVE_IN_LOOP_NO_BATCH" | "FUTURE_GET_NO_TIMEOUT"
+        | "AWAIT_NO_TIMEOUT" | "COMPLETABLE_JOIN" | "COMPLETABLE_GET_NO_TIMEOUT" | "FLUX_BLOCK"
+        | "BLOCKING_IO" | "SLEEP_IN_LOCK" | "HTTP_CLIENT_TIMEOUT" | "SELECT_STAR"
+        | "LIKE_LEADING_WILDCARD" | "SQL_STRING_CONCAT" | "DB_CONNECTION_TIMEOUT_MISSING" | "DB_CONNECTION_TIMEOUT_LONG"
+        | "REDIS_TIMEOUT_MISSING" | "FILTER_DB_CALL" | "FILTER_HTTP_CALL"
+        | "JPA_BIDIRECTIONAL_JSON_CYCLE" | "REDIS_FULL_SCAN_IN_REQUEST" | "REDIS_OP_IN_LOOP"
+        | "LOG_SYNC_FILE_APPENDER" | "LATCH_COUNTDOWN_NOT_IN_FINALLY" => "slow",
+        // 资源: 未关闭资源、无连接池、锁未释放
+        "STREAM_RESOURCE_LEAK" | "DATASOURCE_NO_POOL" | "LOCK_METHOD_CALL" | "JPA_OPEN_IN_VIEW"
+        | "REDIS_SETNX_NO_EXPIRE" | "REDIS_JEDIS_SHARED" | "LOG_NO_ROLLING_POLICY"
+        | "CONNECTION_NOT_CLOSED" => "resource",
+        // 启动慢: 扫描过宽、初始化回调中远程调用、重量级客户端提前建连
+        "COMPONENT_SCAN_BROAD" | "POSTCONSTRUCT_REMOTE_CALL" | "EAGER_HEAVY_BEAN" => "startup-slow",
+        _ => return None,
+    };
+    Some(symptom)
+}
+
+/// 推断出的症状
+#[derive(Debug, Clone, Serialize)]
+pub struct InferredSymptom {
+    pub symptom: &'static str,
+    /// 加权得分 (P0 = 3, P1 = 1)
+    pub score: usize,
+    /// 触发该症状的规则 ID (去重，按出现顺序)
+    pub rules: Vec<String>,
+}
+
+/// 从 (规则 ID, 严重级别) 列表推断症状，按得分降序
+pub fn infer_symptoms<'a>(issues: impl IntoIterator<Item = (&'a str, &'a str)>) -> Vec<InferredSymptom> {
+    let mut inferred: Vec<InferredSymptom> = Vec::new();
+
+    for (rule_id, severity) in issues {
+        let Some(symptom) = symptom_for_rule(rule_id) else { continue };
+        let weight = if severity == "P0" { 3 } else { 1 };
+
+        let entry = match inferred.iter_mut().position(|s| s.symptom == symptom) {
+            Some(idx) => &mut inferred[idx],
+            None => {
+                inferred.push(InferredSymptom { symptom, score: 0, rules: Vec::new() });
+                inferred.last_mut().unwrap()
+            }
+        };
+        entry.score += weight;
+        if !entry.rules.iter().any(|r| r == rule_id) {
+            entry.rules.push(rule_id.to_string());
+        }
     }
+
+    // 稳定排序: 同分时保持首次出现顺序
+    inferred.sort_by_key(|s| std::cmp::Reverse(s.score));
+    inferred
+}
+
+/// 根据扫描报告生成检查清单
+///
+/// report: `scan --format json` 的输出 (也接受 `--json` 包装后的 `{"data": ...}`)。
+/// 章节按关联症状的得分排序，得分高的优先。
+pub fn get_checklist_from_report(report: &Value, compact: bool) -> Result<Value, Box<dyn std::error::Error>> {
+    let issues = report["issues"].as_array()
+        .or_else(|| report["data"]["issues"].as_array())
+        .ok_or("scan report has no 'issues' array (expected `scan --format json` output)")?;
+
+    let inferred = infer_symptoms(issues.iter().filter_map(|i| {
+        Some((i["id"].as_str()?, i["severity"].as_str().unwrap_or("P1")))
+    }));
+
+    let mut out = i18n::text("report.checklist.inferred_title", "## 🔍 检查清单 (基于扫描报告推断)").to_string();
+    out.push_str("\n\n");
+
+    if inferred.is_empty() {
+        out.push_str(i18n::text("report.checklist.inferred_none", "✅ 扫描报告中没有可归类的问题，无法推断症状"));
+        out.push('\n');
+        return Ok(json!(out));
+    }
+
+    out.push_str(&format!("**{}**:\n", i18n::text("report.checklist.inferred_symptoms", "推断症状")));
+    for s in &inferred {
+        out.push_str(&format!("- `{}` ({}) ← {}\n", s.symptom, s.score, s.rules.join(", ")));
+    }
+    out.push('\n');
+
+    // 章节得分 = 关联症状得分之和
+    let kb = knowledge_base();
+    let mut scored: Vec<(usize, &CheckSection)> = kb.sections.iter()
+        .map(|section| {
+            let score = inferred.iter()
+                .filter(|s| kb.sections_for(s.symptom).contains(&section.id))
+                .map(|s| s.score)
+                .sum();
+            (score, section)
+        })
+        .filter(|(score, _)| *score > 0)
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let sections: Vec<&CheckSection> = scored.into_iter().map(|(_, s)| s).collect();
+    render_sections(&mut out, &sections, compact);
+
+    Ok(json!(out))
 }
 
 /// 获取所有反模式
-pub fn get_all_antipatterns() -> Result<Value, Box<dyn std::error::Error>> {
-    let patterns = vec![
-        // 原有反模式
-        ("N+1 Query", "循环内执行数据库查询", "批量查询替代"),
-        ("Nested Loop", "嵌套循环导致 O(N*M) 复杂度", "使用 Map/Set 优化"),
-        ("ThreadLocal Leak", "ThreadLocal 未调用 remove()", "finally 中 remove()"),
-        ("Unbounded Pool", "使用 newCachedThreadPool 无界池", "ThreadPoolExecutor 有界"),
-        ("Unbounded Cache", "static Map 无 TTL/Size 限制", "使用 Caffeine/Guava"),
-        ("Sync Method", "synchronized 方法级锁", "细化到代码块级别"),
-        ("No Timeout", "HTTP/RPC 调用无超时", "统一配置 3-5s 超时"),
-        ("Exception Swallow", "catch 后空处理或仅打印", "正确处理或抛出"),
-        ("Resource Leak", "InputStream/Connection 未关闭", "try-with-resources"),
-        ("Large Object", "一次性加载大对象 >10MB", "分页/流式处理"),
-        ("Blocking IO", "NIO 线程中混入阻塞操作", "异步化处理"),
-        ("CAS Spin", "高竞争 Atomic 自旋", "使用 LongAdder"),
-        
-        // v7.0 新增 - Spring
-        ("@Async Default Pool", "@Async 未指定线程池", "配置自定义 Executor"),
-        ("@Scheduled FixedRate", "fixedRate 任务堆积风险", "改用 fixedDelay 或加锁"),
-        ("Field Injection", "@Autowired 字段注入", "改用构造器注入"),
-        
-        // v7.0 新增 - 响应式
-        ("Flux.block()", "响应式中阻塞调用", "使用 subscribeOn 异步"),
-        ("subscribe() No Error", "subscribe() 未处理错误", "添加 error consumer"),
-        ("collectList() OOM", "无界收集可能 OOM", "限制 buffer 或用 window"),
-        
-        // v7.0 新增 - GC
-        ("finalize()", "重写 finalize() 方法", "使用 Cleaner 或 try-finally"),
-        ("String.intern()", "过度使用 intern()", "检查必要性"),
-        ("Large Array", "大数组直接进老年代", "对象池或分块"),
-        
-        // v7.0 新增 - 数据库
-        ("SELECT *", "全字段查询", "明确指定字段"),
-        ("LIKE '%xxx'", "前导通配符全表扫描", "改用全文索引"),
-    ];
-    
-    let mut report = "## ⚠️ 反模式清单\n\n".to_string();
-    report.push_str("| 反模式 | 描述 | 修复建议 |\n");
-    report.push_str("|--------|------|----------|\n");
-    
-    for (name, desc, fix) in patterns {
-        report.push_str(&format!("| `{name}` | {desc} | {fix} |\n"));
+///
+/// v9.6: 由项目实际注册的规则生成 (见 [`catalog`])，按主分类分组；
+/// 返回 `{profile, summary, rules, report}`，`report` 为 Markdown 表格。
+pub fn get_all_antipatterns(path: &str, options: &ScanOptions) -> Result<Value, Box<dyn std::error::Error>> {
+    let catalog = catalog::build(Path::new(path), options)?;
+    let profile = profile::display(&catalog.profiles);
+    let enabled = catalog.rules.iter().filter(|r| r.enabled).count();
+    let disabled = catalog.rules.len() - enabled;
+
+    let mut report = format!("{}\n\n", i18n::text("report.antipatterns.title", "## ⚠️ 反模式清单"));
+    report.push_str(&i18n::format(
+        "report.antipatterns.summary",
+        "档案: `{profile}` | 规则: {total} (启用 {enabled}，未启用 {disabled})",
+        &[("profile", &profile), ("total", &catalog.rules.len()), ("enabled", &enabled), ("disabled", &disabled)],
+    ));
+    report.push('\n');
+
+    // 按主分类分组 (标签声明顺序，未登记的规则在最后)，组内保持注册顺序
+    let mut groups: BTreeMap<Option<Tag>, Vec<&RuleEntry>> = BTreeMap::new();
+    for rule in &catalog.rules {
+        groups.entry(rule.tags.first().copied()).or_default().push(rule);
+    }
+    let mut groups: Vec<_> = groups.into_iter().collect();
+    groups.sort_by_key(|(tag, _)| tag.is_none());
+
+    for (tag, rules) in groups {
+        let title = tag.map(Tag::title).unwrap_or_else(|| i18n::text("report.scan.category_other", "📦 其他").to_string());
+        report.push_str(&format!("\n### {title} ({})\n\n", rules.len()));
+        report.push_str(i18n::text("report.antipatterns.header", "| 规则 | 级别 | 描述 | 修复建议 | 启用 |"));
+        report.push('\n');
+        report.push_str("|------|------|------|----------|------|\n");
+        for rule in rules {
+            let level = if rule.severity == rule.default_severity {
+                format!("{} {:?}", rule.severity.emoji(), rule.severity)
+            } else {
+                i18n::format("report.antipatterns.overridden", "{emoji} {level} (默认 {default})", &[
+                    ("emoji", &rule.severity.emoji()),
+                    ("level", &format!("{:?}", rule.severity)),
+                    ("default", &format!("{:?}", rule.default_severity)),
+                ])
+            };
+            report.push_str(&format!(
+                "| `{}` | {level} | {} | {} | {} |\n",
+                rule.id,
+                rule.description.replace('|', "\\|"),
+                rule.fix.unwrap_or("-").replace('|', "\\|"),
+                if rule.enabled { "✅" } else { "⛔" },
+            ));
+        }
+    }
+
+    Ok(json!({
+        "profile": profile,
+        "summary": { "total": catalog.rules.len(), "enabled": enabled, "disabled": disabled },
+        "rules": catalog.rules,
+        "report": report,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_infer_symptoms_ranked() {
+        let inferred = infer_symptoms(vec![
+            ("UNBOUNDED_POOL", "P0"),
+            ("STATIC_COLLECTION", "P1"),
+            ("THREADLOCAL_LEAK", "P1"),
+            ("NESTED_LOOP", "P1"),
+            ("UNBOUNDED_POOL", "P0"),
+            ("SYSTEM_EXIT", "P0"), // 未归类
+        ]);
+        let names: Vec<&str> = inferred.iter().map(|s| s.symptom).collect();
+        assert_eq!(names, vec!["backlog", "memory", "cpu"]);
+        assert_eq!(inferred[0].score, 6);
+        assert_eq!(inferred[0].rules, vec!["UNBOUNDED_POOL"]);
+        assert_eq!(inferred[1].rules, vec!["STATIC_COLLECTION", "THREADLOCAL_LEAK"]);
+    }
+
+    #[test]
+    fn test_checklist_from_report() {
+        let report = json!({
+            "data": { "issues": [
+                { "id": "THREADLOCAL_LEAK", "severity": "P0" },
+                { "id": "NESTED_LOOP", "severity": "P1" },
+            ]}
+        });
+        let out = get_checklist_from_report(&report, true).unwrap();
+        let text = out.as_str().unwrap();
+        assert!(text.contains("`memory` (3) ← THREADLOCAL_LEAK"));
+        // 内存相关章节 (5) 排在 锁与并发 (1) 之前
+        let memory = text.find("内存与缓存").unwrap();
+        let locks = text.find("锁与并发").unwrap();
+        assert!(memory < locks);
+    }
+
+    #[test]
+    fn test_antipatterns_from_rule_registry() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(".java-perf.toml"), "[severity]\nSELECT_STAR = \"p2\"\n").unwrap();
+        let options = ScanOptions { profiles: vec![crate::rules::profile::RuleProfile::Standard], ..Default::default() };
+        let out = get_all_antipatterns(dir.path().to_str().unwrap(), &options).unwrap();
+
+        assert_eq!(out["profile"], "standard");
+        assert!(out["summary"]["disabled"].as_u64().unwrap() > 0);
+        let flux = out["rules"].as_array().unwrap().iter().find(|r| r["id"] == "FLUX_BLOCK").unwrap();
+        assert_eq!(flux["enabled"], false);
+        assert_eq!(flux["tags"], json!(["reactive", "concurrency"]));
+
+        let report = out["report"].as_str().unwrap();
+        assert!(report.contains("### 🗄️ 数据库"));
+        assert!(report.contains("| `SELECT_STAR` | 🔵 P2 (默认 P1) |"));
+        assert!(report.lines().any(|l| l.starts_with("| `FLUX_BLOCK` |") && l.ends_with("| ⛔ |")));
+    }
+
+    #[test]
+    fn test_builtin_knowledge_base_is_valid() {
+        assert_eq!(BUILTIN.validate(None), Vec::<String>::new());
+        assert_eq!(BUILTIN.sections.len(), 12);
+        assert_eq!(BUILTIN.symptoms.len(), 12);
+        assert_eq!(BUILTIN.sections_for("memory"), ["5", "0", "4"]);
+        assert!(BUILTIN.sections_for("unknown").is_empty());
+    }
+
+    #[test]
+    fn test_extension_merge_and_validate() {
+        let extension: KnowledgeBase = serde_yaml::from_str(r#"
+version: 1
+symptoms:
+  memory: ["5", "acme-mq"]
+  mq-lag: ["acme-mq", "4"]
+sections:
+  - id: "6"
+    title: "异常处理 (公司规范)"
+    priority: P1
+    items:
+      - desc: "catch 后必须上报监控"
+  - id: acme-mq
+    title: "内部 MQ 客户端"
+    priority: P0
+    items:
+      - desc: "消费者未设置 maxPollRecords"
+        fix: "按处理耗时设置批量上限"
+"#).unwrap();
+        assert!(extension.validate(Some(&BUILTIN)).is_empty());
+        // 单独校验时引用内置章节 "4"/"5" 报错
+        assert_eq!(extension.validate(None).len(), 2);
+
+        let mut kb = BUILTIN.clone();
+        kb.merge(extension);
+        assert_eq!(kb.sections.len(), 13);
+        assert_eq!(kb.sections[6].title, "异常处理 (公司规范)");
+        assert_eq!(kb.sections_for("memory"), ["5", "0", "4", "acme-mq"]);
+        assert_eq!(kb.sections_for("mq-lag"), ["acme-mq", "4"]);
+    }
+
+    #[test]
+    fn test_resolve_symptom() {
+        let kb = BUILTIN.clone();
+        assert_eq!(kb.resolve_symptom("timeout"), ["timeout"]);
+        assert_eq!(kb.resolve_symptom(" Disk-IO "), ["disk-io"]);
+        assert_eq!(kb.resolve_symptom("接口偶发超时"), ["p99-spike", "timeout"]);
+        assert_eq!(kb.resolve_symptom("java.net.SocketException: Connection reset by peer"), ["connection-reset"]);
+        assert_eq!(kb.resolve_symptom("P99 spikes every 5 minutes"), ["p99-spike"]);
+        assert_eq!(kb.resolve_symptom("服务启动要 3 分钟"), ["startup-slow"]);
+        // ASCII 关键词按整词匹配: "gc" 不命中 "logcat"，"lag" 不命中 "flag"
+        assert!(kb.resolve_symptom("logcat flag").is_empty());
+        // 拼写错误
+        assert_eq!(kb.resolve_symptom("memroy"), ["memory"]);
+        assert_eq!(kb.resolve_symptom("timout"), ["timeout"]);
+        assert!(kb.resolve_symptom("banana").is_empty());
+    }
+
+    #[test]
+    fn test_checklist_with_described_symptoms() {
+        let out = get_checklist(&["接口偶发超时", "banana"], None, true).unwrap();
+        let text = out.as_str().unwrap();
+        assert!(text.contains("症状: p99-spike, timeout"));
+        assert!(text.contains("- `接口偶发超时` → p99-spike, timeout"));
+        assert!(text.contains("未识别的症状"));
+        assert!(text.contains("延迟毛刺 (P99)"));
+        assert!(text.contains("超时与重试"));
+        assert!(!text.contains("启动耗时"));
+    }
+
+    #[test]
+    fn test_validate_reports_errors() {
+        let kb: KnowledgeBase = serde_yaml::from_str(r#"
+version: 2
+symptoms:
+  Memory: []
+  slow: ["missing"]
+sections:
+  - { id: "a", title: "", priority: P9, items: [] }
+  - { id: "a", title: "重复", priority: P0, items: [{ desc: " " }] }
+"#).unwrap();
+        let errors = kb.validate(None);
+        assert_eq!(errors, vec![
+            "version: 不支持的版本 2 (当前为 1)",
+            "sections[0].title: 不能为空",
+            "sections[0].priority: \"P9\" 不是 P0/P1/P2",
+            "sections[0].items: 至少需要一个检查项",
+            "sections[1].id: 重复的章节 id \"a\"",
+            "sections[1].items[0].desc: 不能为空",
+            "symptoms.Memory: 症状名只能包含小写字母、数字和 '-'",
+            "symptoms.Memory: 至少映射一个章节",
+            "symptoms.slow: 引用了不存在的章节 \"missing\"",
+        ]);
+
+        let kb: KnowledgeBase = serde_yaml::from_str("version: 1\nkeywords:\n  mq-lag: [\"kafka\", \"\"]\n").unwrap();
+        assert_eq!(kb.validate(Some(&BUILTIN)), vec![
+            "keywords.mq-lag: 症状未在 symptoms 中定义",
+            "keywords.mq-lag: 关键词不能为空",
+        ]);
+
+        // 未知字段 (拼写错误) 在解析阶段拒绝
+        let typo = serde_yaml::from_str::<KnowledgeBase>("version: 1\nsections:\n  - { id: x, title: t, priority: P0, items: [{ descr: d }] }\n");
+        assert!(typo.unwrap_err().to_string().contains("unknown field `descr`"));
+    }
+
+    #[test]
+    fn test_checklist_from_report_requires_issues() {
+        assert!(get_checklist_from_report(&json!({ "foo": 1 }), true).is_err());
+        let out = get_checklist_from_report(&json!({ "issues": [] }), true).unwrap();
+        assert!(out.as_str().unwrap().contains("无法推断症状"));
     }
-    
-    Ok(json!(report))
 }
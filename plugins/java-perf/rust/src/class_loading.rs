@@ -0,0 +1,378 @@
+//! 类加载与 Metaspace 取证 (Class Loading) - v9.6
+//!
+//! `log --file` 在流式读取日志的同时识别 `-verbose:class` / `-Xlog:class+load` 与 GC 日志中的
+//! Metaspace 记录，日志中存在类加载信息时追加一节报告：
+//! - `CL_REPEATED_LOAD`: 同名类被反复加载 (每次重新部署/每个请求创建 ClassLoader，旧加载器无法回收)
+//! - `CL_PROXY_EXPLOSION`: CGLIB / JDK Proxy / ByteBuddy / Javassist 生成类数量失控
+//! - `CL_REFLECTION_ACCESSORS`: 反射膨胀生成的 `GeneratedMethodAccessor` 过多
+//! - `CL_METASPACE_GROWTH`: Metaspace 持续增长，或预热后仍在大量加载类
+//! - `CL_METASPACE_OOM`: `OutOfMemoryError: Metaspace` / `Compressed class space`
+//!
+//! 每项发现关联扫描器中的 GraalVM/反射规则 (`scan --profile native-image`)，从运行时现象回到源码位置。
+//!
+//! 支持的格式：
+//! ```text
+//! [Loaded com.acme.Order from file:/app/lib/order.jar]                           (JDK 8)
+//! [12.345s][info][class,load] com.acme.Order source: jar:file:/app/app.jar!/      (JDK 9+)
+//! [12.345s][info][gc,metaspace] GC(3) Metaspace: 21372K(21888K)->21372K(21888K)  (JDK 9+)
+//! 2026-10-17T10:00:00.000+0000: 12.345: [Full GC ... [Metaspace: 20456K->20456K(1067008K)] ...  (JDK 8)
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::ast_engine::Severity;
+
+/// 同名类加载次数达到该值视为重复加载 (两个加载器加载同一类在应用服务器中很常见)
+const REPEATED_LOAD_MIN: usize = 3;
+/// 生成代理类数量阈值
+const PROXY_CLASSES_MIN: usize = 500;
+/// 反射访问器类数量阈值
+const REFLECTION_ACCESSORS_MIN: usize = 100;
+/// 启动预热时长 (秒)，之后的类加载计入稳态加载速率
+const WARMUP_SECS: f64 = 300.0;
+/// 预热后类加载速率阈值 (个/分钟)
+const STEADY_LOADS_PER_MIN: f64 = 50.0;
+/// Metaspace 增速阈值 (MB/分钟)
+const METASPACE_MB_PER_MIN: f64 = 1.0;
+/// 报告中列出的条目数
+const TOP_N: usize = 10;
+
+static LOAD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[Loaded (\S+) from|\[class,load\s*\]\s+(\S+) source:").unwrap()
+});
+
+static UNLOAD_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[Unloading class (\S+)|\[class,unload\s*\]\s+unloading class (\S+)").unwrap()
+});
+
+/// Metaspace 使用量: 取 `->` 之后的值 (GC 后)
+static METASPACE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Metaspace: \d+K(?:\(\d+K\))?->(\d+)K").unwrap()
+});
+
+/// JVM 运行时长: 统一日志 `[12.345s]` 或 JDK 8 GC 日志 `: 12.345: [`
+static UPTIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[(\d+(?:\.\d+)?)s\]|^(?:\S+: )?(\d+\.\d+): \[").unwrap()
+});
+
+static JDK_PROXY_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"\$Proxy\d+$").unwrap());
+
+/// 生成类的来源
+const PROXY_MARKERS: &[(&str, &str)] = &[
+    ("$$EnhancerBySpringCGLIB$$", "CGLIB"),
+    ("$$SpringCGLIB$$", "CGLIB"),
+    ("$$EnhancerByCGLIB$$", "CGLIB"),
+    ("$$FastClassBySpringCGLIB$$", "CGLIB"),
+    ("$ByteBuddy$", "ByteBuddy"),
+    ("$HibernateProxy$", "ByteBuddy"),
+    ("_$$_jvst", "Javassist"),
+];
+
+const REFLECTION_ACCESSORS: &[&str] = &[
+    "GeneratedMethodAccessor",
+    "GeneratedConstructorAccessor",
+    "GeneratedSerializationConstructorAccessor",
+];
+
+/// 类加载发现
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassLoadFinding {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: String,
+    /// 示例类 / 分类计数
+    pub evidence: Vec<String>,
+    /// 关联的扫描规则
+    pub related_rules: &'static [&'static str],
+}
+
+/// 流式统计
+#[derive(Debug, Default)]
+pub struct ClassLoadStats {
+    loads: HashMap<String, usize>,
+    total_loads: usize,
+    unloads: usize,
+    proxies: BTreeMap<&'static str, usize>,
+    reflection_accessors: usize,
+    /// 预热 (`WARMUP_SECS`) 之后的加载次数
+    steady_loads: usize,
+    last_uptime: Option<f64>,
+    /// (运行时长, GC 后 Metaspace KB)
+    metaspace: Vec<(Option<f64>, u64)>,
+    oom: Option<String>,
+}
+
+impl ClassLoadStats {
+    /// 处理一行日志，返回该行是否为类加载/卸载/Metaspace 记录
+    ///
+    /// 这些记录中的 `java.lang.NullPointerException source: jrt:/java.base` 等不是异常，调用方应跳过异常归类。
+    pub fn observe(&mut self, line: &str) -> bool {
+        if line.contains("OutOfMemoryError: Metaspace") || line.contains("OutOfMemoryError: Compressed class space") {
+            self.oom.get_or_insert_with(|| line.trim().chars().take(150).collect());
+        }
+        let relevant = line.contains("Loaded ") || line.contains("class,load") || line.contains("Metaspace: ")
+            || line.contains("Unloading class") || line.contains("class,unload");
+        if !relevant {
+            return false;
+        }
+
+        let uptime = UPTIME_REGEX.captures(line)
+            .and_then(|c| c.get(1).or_else(|| c.get(2)))
+            .and_then(|m| m.as_str().parse::<f64>().ok());
+        if uptime.is_some() {
+            self.last_uptime = uptime;
+        }
+
+        if let Some(caps) = LOAD_REGEX.captures(line) {
+            let Some(class) = caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str()) else { return false };
+            self.total_loads += 1;
+            if uptime.is_some_and(|t| t > WARMUP_SECS) {
+                self.steady_loads += 1;
+            }
+            if let Some(kind) = proxy_kind(class) {
+                *self.proxies.entry(kind).or_insert(0) += 1;
+            } else if REFLECTION_ACCESSORS.iter().any(|a| class.contains(a)) {
+                self.reflection_accessors += 1;
+            } else {
+                *self.loads.entry(class.to_string()).or_insert(0) += 1;
+            }
+        } else if UNLOAD_REGEX.is_match(line) {
+            self.unloads += 1;
+        } else if let Some(caps) = METASPACE_REGEX.captures(line) {
+            if let Ok(kb) = caps[1].parse() {
+                self.metaspace.push((uptime, kb));
+            }
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// 日志中是否包含类加载/Metaspace 信息
+    pub fn is_relevant(&self) -> bool {
+        self.total_loads > 0 || !self.metaspace.is_empty() || self.oom.is_some()
+    }
+
+    /// 运行规则
+    pub fn findings(&self) -> Vec<ClassLoadFinding> {
+        let mut findings = Vec::new();
+
+        if let Some(line) = &self.oom {
+            findings.push(ClassLoadFinding {
+                id: "CL_METASPACE_OOM",
+                severity: Severity::P0,
+                description: "Metaspace 溢出：类元数据无法回收，通常由 ClassLoader 泄漏或动态生成类失控引起".to_string(),
+                evidence: vec![line.clone()],
+                related_rules: &["GRAALVM_PROXY", "GRAALVM_CLASS_FORNAME"],
+            });
+        }
+
+        let mut repeated: Vec<(&String, &usize)> = self.loads.iter().filter(|(_, n)| **n >= REPEATED_LOAD_MIN).collect();
+        repeated.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        if !repeated.is_empty() {
+            findings.push(ClassLoadFinding {
+                id: "CL_REPEATED_LOAD",
+                severity: Severity::P0,
+                description: format!(
+                    "{} 个类被加载 {REPEATED_LOAD_MIN} 次以上 (共卸载 {} 次)：反复创建 ClassLoader (热部署、脚本引擎、每请求加载插件)，旧加载器被引用时无法回收",
+                    repeated.len(), self.unloads
+                ),
+                evidence: repeated.iter().take(TOP_N).map(|(c, n)| format!("{c} ×{n}")).collect(),
+                related_rules: &["GRAALVM_CLASS_FORNAME"],
+            });
+        }
+
+        let proxies: usize = self.proxies.values().sum();
+        if proxies >= PROXY_CLASSES_MIN {
+            findings.push(ClassLoadFinding {
+                id: "CL_PROXY_EXPLOSION",
+                severity: Severity::P0,
+                description: format!(
+                    "加载了 {proxies} 个动态代理/字节码生成类：检查原型作用域 Bean 的 AOP 代理、每次调用 Proxy.newProxyInstance / new Enhancer 未缓存"
+                ),
+                evidence: self.proxies.iter().map(|(k, n)| format!("{k}: {n}")).collect(),
+                related_rules: &["GRAALVM_PROXY"],
+            });
+        }
+
+        if self.reflection_accessors >= REFLECTION_ACCESSORS_MIN {
+            findings.push(ClassLoadFinding {
+                id: "CL_REFLECTION_ACCESSORS",
+                severity: Severity::P1,
+                description: format!(
+                    "加载了 {} 个反射访问器类 (反射调用超过 sun.reflect.inflationThreshold 后生成)：热点路径缓存 Method/MethodHandle，避免每次反射查找",
+                    self.reflection_accessors
+                ),
+                evidence: Vec::new(),
+                related_rules: &["GRAALVM_METHOD_INVOKE"],
+            });
+        }
+
+        let mut growth = Vec::new();
+        if let Some(rate) = self.metaspace_rate() {
+            if rate >= METASPACE_MB_PER_MIN {
+                growth.push(format!("Metaspace 增速 {rate:.1} MB/min ({})", self.metaspace_series()));
+            }
+        }
+        if let Some(rate) = self.steady_load_rate() {
+            if rate >= STEADY_LOADS_PER_MIN {
+                growth.push(format!("启动 {WARMUP_SECS:.0}s 后仍加载 {} 个类 ({rate:.0} 个/min)", self.steady_loads));
+            }
+        }
+        if !growth.is_empty() {
+            findings.push(ClassLoadFinding {
+                id: "CL_METASPACE_GROWTH",
+                severity: Severity::P0,
+                description: "Metaspace 持续增长：预热后类仍在不断加载，最终触发 Metaspace Full GC 或 OOM".to_string(),
+                evidence: growth,
+                related_rules: &["GRAALVM_PROXY", "GRAALVM_CLASS_FORNAME", "GRAALVM_METHOD_INVOKE"],
+            });
+        }
+
+        findings
+    }
+
+    /// Metaspace 增速 (MB/分钟)，需要至少 3 个带时间戳的样本且跨度超过 1 分钟
+    fn metaspace_rate(&self) -> Option<f64> {
+        let timed: Vec<(f64, u64)> = self.metaspace.iter().filter_map(|(t, kb)| Some(((*t)?, *kb))).collect();
+        let (first, last) = (timed.first()?, timed.last()?);
+        let minutes = (last.0 - first.0) / 60.0;
+        if timed.len() < 3 || minutes < 1.0 {
+            return None;
+        }
+        Some((last.1 as f64 - first.1 as f64) / 1024.0 / minutes)
+    }
+
+    fn metaspace_series(&self) -> String {
+        let (Some(first), Some(last)) = (self.metaspace.first(), self.metaspace.last()) else { return String::new() };
+        format!("{:.1} MB → {:.1} MB", first.1 as f64 / 1024.0, last.1 as f64 / 1024.0)
+    }
+
+    /// 预热后的类加载速率 (个/分钟)
+    fn steady_load_rate(&self) -> Option<f64> {
+        let minutes = (self.last_uptime? - WARMUP_SECS) / 60.0;
+        (self.steady_loads > 0 && minutes >= 1.0).then(|| self.steady_loads as f64 / minutes)
+    }
+
+    /// 渲染报告小节
+    pub fn render(&self) -> String {
+        let mut out = format!(
+            "\n## 🧬 类加载与 Metaspace\n\n**加载**: {} | **卸载**: {} | **代理类**: {} | **反射访问器**: {}",
+            self.total_loads, self.unloads, self.proxies.values().sum::<usize>(), self.reflection_accessors,
+        );
+        if !self.metaspace.is_empty() {
+            out.push_str(&format!(" | **Metaspace**: {}", self.metaspace_series()));
+        }
+        out.push_str("\n\n");
+
+        let findings = self.findings();
+        if findings.is_empty() {
+            out.push_str("✅ 未发现类加载异常\n");
+            return out;
+        }
+        for f in &findings {
+            let emoji = f.severity.emoji();
+            out.push_str(&format!("{emoji} **{}** - {}\n", f.id, f.description));
+            for e in &f.evidence {
+                out.push_str(&format!("  - `{e}`\n"));
+            }
+            let rules: Vec<String> = f.related_rules.iter().map(|r| format!("`{r}`")).collect();
+            out.push_str(&format!(
+                "  - 关联扫描规则: {} (`java-perf scan --profile native-image` 定位源码)\n",
+                rules.join(", ")
+            ));
+        }
+        out
+    }
+}
+
+/// 生成类的来源 (CGLIB / JDK Proxy / ByteBuddy / Javassist)
+fn proxy_kind(class: &str) -> Option<&'static str> {
+    PROXY_MARKERS.iter()
+        .find(|(marker, _)| class.contains(marker))
+        .map(|(_, kind)| *kind)
+        .or_else(|| JDK_PROXY_REGEX.is_match(class).then_some("JDK Proxy"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe_all(lines: &[String]) -> ClassLoadStats {
+        let mut stats = ClassLoadStats::default();
+        for line in lines {
+            stats.observe(line);
+        }
+        stats
+    }
+
+    fn ids(stats: &ClassLoadStats) -> Vec<&'static str> {
+        stats.findings().iter().map(|f| f.id).collect()
+    }
+
+    #[test]
+    fn test_repeated_load_jdk8() {
+        let mut lines: Vec<String> = (0..3)
+            .map(|i| format!("[Loaded com.acme.plugin.Rule from file:/tmp/plugin-{i}.jar]"))
+            .collect();
+        lines.push("[Loaded java.lang.Object from /usr/lib/jvm/jre/lib/rt.jar]".to_string());
+        lines.push("[Unloading class com.acme.plugin.Rule 0x00000007c0060828]".to_string());
+        lines.push("[Loaded com.sun.proxy.$Proxy12 from __JVM_DefineClass__]".to_string());
+
+        let stats = observe_all(&lines);
+        assert!(stats.is_relevant());
+        assert_eq!(ids(&stats), vec!["CL_REPEATED_LOAD"]);
+        let finding = &stats.findings()[0];
+        assert_eq!(finding.evidence, vec!["com.acme.plugin.Rule ×3"]);
+        assert!(finding.description.contains("共卸载 1 次"));
+        assert_eq!(stats.proxies["JDK Proxy"], 1);
+    }
+
+    #[test]
+    fn test_proxy_explosion_and_reflection() {
+        let mut lines: Vec<String> = (0..PROXY_CLASSES_MIN)
+            .map(|i| format!("[1.{i:03}s][info][class,load] com.acme.Order$$EnhancerBySpringCGLIB$${i:x} source: __JVM_DefineClass__"))
+            .collect();
+        lines.extend((0..REFLECTION_ACCESSORS_MIN)
+            .map(|i| format!("[2.000s][info][class,load] jdk.internal.reflect.GeneratedMethodAccessor{i} source: __JVM_DefineClass__")));
+
+        let stats = observe_all(&lines);
+        assert_eq!(ids(&stats), vec!["CL_PROXY_EXPLOSION", "CL_REFLECTION_ACCESSORS"]);
+        assert_eq!(stats.findings()[0].related_rules, &["GRAALVM_PROXY"]);
+        assert!(stats.render().contains("`GRAALVM_METHOD_INVOKE`"));
+    }
+
+    #[test]
+    fn test_metaspace_growth_and_oom() {
+        let lines: Vec<String> = [(60, 20_480), (180, 40_960), (300, 61_440)]
+            .iter()
+            .map(|(t, kb)| format!("[{t}.000s][info][gc,metaspace] GC(3) Metaspace: {kb}K({kb}K)->{kb}K({kb}K) NonClass: 1K(1K)->1K(1K)"))
+            .chain(["java.lang.OutOfMemoryError: Metaspace".to_string()])
+            .collect();
+        let stats = observe_all(&lines);
+        assert_eq!(ids(&stats), vec!["CL_METASPACE_OOM", "CL_METASPACE_GROWTH"]);
+        assert_eq!(stats.findings()[1].evidence, vec!["Metaspace 增速 10.0 MB/min (20.0 MB → 60.0 MB)"]);
+
+        // JDK 8 GC 日志格式
+        let jdk8 = observe_all(&[
+            "2026-10-17T10:00:00.000+0000: 12.345: [Full GC (Metadata GC Threshold) [PSYoungGen: 1K->0K(2K)] [Metaspace: 20456K->20400K(1067008K)], 0.1 secs]".to_string(),
+        ]);
+        assert_eq!(jdk8.metaspace, vec![(Some(12.345), 20_400)]);
+    }
+
+    #[test]
+    fn test_steady_state_loading() {
+        let lines: Vec<String> = (0..200)
+            .map(|i| format!("[{}.000s][info][class,load] com.acme.Script{i} source: __JVM_DefineClass__", 300 + i))
+            .collect();
+        // 预热后 199 个类 / 3.3 分钟
+        assert_eq!(ids(&observe_all(&lines)), vec!["CL_METASPACE_GROWTH"]);
+        assert!(!observe_all(&["INFO Started OrderApplication in 3.2 seconds".to_string()]).is_relevant());
+        let mut stats = ClassLoadStats::default();
+        assert!(stats.observe("[0.050s][info][class,load] java.lang.NullPointerException source: jrt:/java.base"));
+        assert!(!stats.observe("java.lang.NullPointerException: order is null"));
+    }
+}
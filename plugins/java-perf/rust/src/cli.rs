@@ -3,10 +3,21 @@
 //! 提供命令行接口，默认输出人类可读格式
 //! 使用 --json 参数可输出 JSON 格式
 
-use crate::{ast_engine, checklist, forensic, jdk_engine};
+use crate::{ast_engine, bench, call_chain, checklist, config_diff, crash, doctor, effective_config, forensic, genbench, hprof, jdk_engine, log_timeline, mcp, metrics, native_config, ownership, reachability, redact, report, rule_pack, scan_diff, verify};
+use crate::issue_link::{IssueLinks, LinkFormat};
+use crate::report::ReportFormat;
+use crate::rules::layer_weight::{self, LayerWeighting};
+use crate::ast_engine::Severity;
+use crate::rules::profile::RuleProfile;
+use crate::rules::severity_override;
+use crate::rules::taxonomy::{self, Tag};
+use crate::rules::test_code::TestCodeMode;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+use crate::symbol_table::LayerType;
 use anyhow::Result;
 use serde_json::{json, Value};
-use clap::Subcommand;
+use clap::{Args, Subcommand};
+use std::path::PathBuf;
 
 /// CLI Commands
 #[derive(Subcommand, Debug, Clone)]
@@ -21,9 +32,110 @@ pub enum Command {
         #[arg(long)]
         full: bool,
 
-        /// 最多返回的 P1 数量 (--full 模式)
+        /// 最多返回的 P1/P2/Info 数量 (--full 模式，P0 全部返回)
         #[arg(long, default_value = "5")]
         max_p1: usize,
+
+        /// 报告格式: markdown | json | sarif | github | gitlab | checkstyle (机器可读格式输出全部问题及完整范围)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+
+        /// 构建元数据，可重复: --meta git_sha=abc123 --meta service=order
+        /// (写入 markdown / json / sarif；github / gitlab / checkstyle 格式没有元数据字段，不输出)
+        #[arg(long, value_parser = report::parse_meta)]
+        meta: Vec<(String, String)>,
+
+        /// 规则档案，可重复: all | standard | reactive | native-image (默认从 pom.xml/build.gradle 推断)
+        #[arg(long, value_enum)]
+        profile: Vec<RuleProfile>,
+
+        /// WASM 脚本规则目录 (默认 <path>/.java-perf/rules)
+        #[arg(long)]
+        rules_dir: Option<PathBuf>,
+
+        /// 输出分阶段耗时、最慢 10 个文件和每条规则的累计查询耗时
+        #[arg(long)]
+        timing: bool,
+
+        /// 扫描线程数 (默认 CPU 核数)，共享 CI 机器上可调低
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        threads: Option<u16>,
+
+        /// 同时读取的文件数上限 (默认不限制)
+        #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+        io_throttle: Option<u16>,
+
+        /// 覆盖层级加权，可重复: --layer-weight OBJECT_IN_LOOP=controller+service (=none 关闭该规则)
+        #[arg(long, value_parser = layer_weight::parse_weight)]
+        layer_weight: Vec<(String, Vec<LayerType>)>,
+
+        /// 关闭层级加权 (不按 Controller/Service 层级升级问题级别)
+        #[arg(long, conflicts_with = "layer_weight")]
+        no_layer_weight: bool,
+
+        /// 测试源码 (src/test 等): exclude 跳过 | test-rules 只查不稳定测试模式 | include 按生产代码扫描
+        #[arg(long, value_enum, default_value_t = TestCodeMode::Exclude)]
+        test_code: TestCodeMode,
+
+        /// 附加 git blame 作者/提交；存在 CODEOWNERS 时按团队汇总问题数
+        #[arg(long)]
+        owners: bool,
+
+        /// 覆盖规则级别，可重复: --severity AUTOWIRED_FIELD=p1 (优先于 .java-perf.toml [severity])
+        #[arg(long = "severity", value_name = "RULE=LEVEL", value_parser = severity_override::parse_override)]
+        severity_overrides: Vec<(String, Severity)>,
+
+        /// 只报告不低于该级别的问题: p0 | p1 | p2 | info (默认全部)
+        #[arg(long, value_enum)]
+        min_severity: Option<Severity>,
+
+        /// 只报告带有任一标签的问题 (逗号分隔): concurrency,memory,gc,cpu,db,io,reactive,config,logging,docker,build,spark,security-adjacent,test
+        #[arg(long, value_enum, value_delimiter = ',')]
+        only_tags: Vec<Tag>,
+
+        /// 用 javap 在编译产物上校验 N+1 / 无界线程池的接收者类型: 确认的升级为 P0，排除的丢弃 (需先编译)
+        #[arg(long)]
+        verify_with_javap: bool,
+
+        /// javap 校验的附加 classpath 目录/JAR，可重复 (target/classes、build/classes/java/main 自动加入)
+        #[arg(long, requires = "verify_with_javap")]
+        classpath: Vec<PathBuf>,
+
+        /// Markdown 报告中问题位置渲染为链接: idea | vscode | github (github 需要 --repo-url)
+        #[arg(long, value_enum)]
+        link_format: Option<LinkFormat>,
+
+        /// GitHub 仓库地址，如 https://github.com/acme/shop (--link-format github)
+        #[arg(long, required_if_eq("link_format", "github"))]
+        repo_url: Option<String>,
+
+        /// GitHub 链接使用的 commit/分支 (默认 --meta git_sha，其次 git rev-parse HEAD)
+        #[arg(long)]
+        commit: Option<String>,
+
+        /// 只保留这些路径下的问题，可重复 (CODEOWNERS 语法，相对扫描根目录): --owned-paths order/ --owned-paths '**/payment/**'
+        #[arg(long)]
+        owned_paths: Vec<String>,
+
+        /// 只保留 CODEOWNERS 中归属这些团队的问题，可重复: --team @acme/orders
+        #[arg(long)]
+        team: Vec<String>,
+
+        /// 配置文件占位符 `${VAR:default}` 的变量文件 (KEY=VALUE 每行一个)，未提供时按默认值校验
+        #[arg(long)]
+        env_file: Option<PathBuf>,
+
+        /// 按 Spring profile 计算生效配置后执行配置规则 (application.yml + application-{profile}.yml 等，逗号分隔多个): --spring-profile prod
+        #[arg(long, value_delimiter = ',')]
+        spring_profile: Vec<String>,
+
+        /// 保留不可达方法中问题的级别 (默认: 调用图上无调用方的 Service/Repository 方法中的问题降低一级)
+        #[arg(long)]
+        keep_unreachable: bool,
+
+        /// JSON 报告按旧版布局输出 (`report_version`)，升级后现有集成不受新字段影响: --compat 1
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..=report::REPORT_VERSION as i64))]
+        compat: Option<u8>,
     },
 
     /// 🔍 单文件分析
@@ -31,48 +143,288 @@ pub enum Command {
         /// 文件路径
         #[arg(short, long)]
         file: String,
+
+        /// 报告格式: markdown | json | sarif | github | gitlab | checkstyle
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
     },
 
     /// 📋 获取检查清单
     Checklist {
-        /// 症状列表 (逗号分隔): memory,cpu,slow,resource,backlog,gc
-        #[arg(short, long)]
-        symptoms: String,
+        /// 症状列表 (逗号分隔): memory,cpu,slow,resource,backlog,gc,p99-spike,timeout,connection-reset,disk-io,network,startup-slow、自定义症状或自由文本描述
+        #[arg(short, long, required_unless_present_any = ["report", "validate"], conflicts_with = "report")]
+        symptoms: Option<String>,
+
+        /// 扫描报告 (`scan --format json` 输出)，从问题家族推断症状
+        #[arg(long)]
+        report: Option<String>,
 
         /// 显示完整信息（默认紧凑模式）
         #[arg(long)]
         full: bool,
+
+        /// 扩展知识库 YAML (默认 $JAVA_PERF_CHECKLIST 或 ./.java-perf/checklist.yaml)
+        #[arg(long)]
+        kb: Option<PathBuf>,
+
+        /// 校验内置知识库与扩展文件 (结构、优先级、症状引用的章节)，存在错误时退出码为 1
+        #[arg(long, conflicts_with_all = ["symptoms", "report"])]
+        validate: bool,
+    },
+
+    /// ⚠️ 列出所有反模式 (项目实际注册的规则: 级别、分类、修复建议、是否启用)
+    Antipatterns {
+        /// 项目路径 (读取构建文件推断档案、`.java-perf.toml [severity]` 与脚本规则)
+        #[arg(long, default_value = ".")]
+        path: String,
+
+        /// 规则档案，可重复: all | standard | reactive | native-image (默认从 pom.xml/build.gradle 推断)
+        #[arg(long, value_enum)]
+        profile: Vec<RuleProfile>,
+
+        /// WASM 脚本规则目录 (默认 <path>/.java-perf/rules)
+        #[arg(long)]
+        rules_dir: Option<PathBuf>,
+    },
+
+    /// 📊 对比两次扫描 (`scan --format json` 输出): 新增 / 已解决 / 未变化，新增 P0 时退出码为 1
+    DiffReport {
+        /// 基线扫描报告
+        old: String,
+
+        /// 本次扫描报告
+        new: String,
+    },
+
+    /// ⚖️ 配置漂移: 解析两份 Spring 配置后按配置项对比，列出连接池/超时/缓存 TTL 差异及 prod 缺失的配置项
+    ConfigDiff {
+        /// prod 配置 (application-prod.yml / .properties)
+        prod: String,
+
+        /// staging 配置
+        staging: String,
     },
 
-    /// ⚠️ 列出所有反模式
-    Antipatterns,
+    /// 🧩 Spring 生效配置: 按 profile 合并 application*.yml/properties，列出每个配置项的生效值及来源文件:行
+    EffectiveConfig {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 激活的 profile (逗号分隔，靠后的优先)，不指定时只使用通用配置
+        #[arg(long, value_delimiter = ',')]
+        profile: Vec<String>,
+
+        /// 只列出 key 包含该字符串的配置项 (按宽松绑定归一后匹配)
+        #[arg(long)]
+        key: Option<String>,
+    },
 
     /// 🔬 分析日志文件
     Log {
         /// 日志文件路径
+        #[arg(short, long, required_unless_present = "files", conflicts_with = "files")]
+        file: Option<String>,
+
+        /// 按时间戳关联多个日志 (逗号分隔，如 app1.log,app2.log,gc.log)：GC 停顿 ↔ 错误突增、上游超时 ↔ 下游异常、合并时间线
+        #[arg(long, value_delimiter = ',', num_args = 1..)]
+        files: Vec<String>,
+
+        /// 跟踪文件新增内容 (tail -f)，结束时输出窗口内指纹与已有内容的对比
+        #[arg(long, conflicts_with = "files")]
+        follow: bool,
+
+        /// 还原单个请求的轨迹: 按时间列出 --file/--files 中带该 Trace ID 的记录，并指出最慢间隔
+        #[arg(long, conflicts_with = "follow")]
+        trace: Option<String>,
+
+        /// 跟踪时长，如 30s / 5m (最长 10m)
+        #[arg(long, default_value = "60s", value_parser = jdk_engine::parse_interval, requires = "follow")]
+        duration: std::time::Duration,
+    },
+
+    /// 🗂️ 构建项目索引 (符号表 + 调用图) 并写入 .java-perf/index.bin，供 scan / verify / call-chain 复用
+    Index {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 测试源码处理方式，须与 scan 的 --test-code 一致才能复用索引
+        #[arg(long, value_enum, default_value_t = TestCodeMode::Exclude)]
+        test_code: TestCodeMode,
+    },
+
+    /// 🎯 Sniper 单点验证: 在完整语义上下文中确认雷达扫描报告的问题
+    Verify {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 问题所在文件 (相对项目路径或绝对路径)
+        #[arg(short, long)]
+        file: String,
+
+        /// 问题行号
+        #[arg(short, long)]
+        line: usize,
+
+        /// 规则 ID，如 N_PLUS_ONE
+        #[arg(long)]
+        id: String,
+    },
+
+    /// 🔗 调用链影响面: 上游 Controller 入口 + 下游 Repository 调用
+    CallChain {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 类名 (FQN 或简单类名)
+        #[arg(short, long)]
+        class: String,
+
+        /// 方法名
+        #[arg(short, long)]
+        method: String,
+
+        /// 最大追踪深度
+        #[arg(long, default_value_t = call_chain::DEFAULT_MAX_DEPTH)]
+        max_depth: usize,
+    },
+
+    /// 🪦 候选废弃代码: 调用图上无调用方的 Service/Repository 方法，及全部方法不可达且未被字段引用的 Bean
+    Unused {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+    },
+
+    /// 📐 方法度量: 圈复杂度、循环嵌套深度、分配次数、语句数，被标记的方法按风险排序
+    Metrics {
+        /// Java 文件路径
         #[arg(short, long)]
         file: String,
     },
 
+    /// 🧊 生成 GraalVM native-image 配置骨架 (reflect-config.json / proxy-config.json)
+    NativeConfig {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 写出目录 (如 src/main/resources/META-INF/native-image)，与已有配置合并；不指定时只输出预览
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// ⏱️ 为扫描报告中的热点生成 JMH 基准骨架 (写入 src/jmh/java，与被测类同包)
+    Genbench {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+
+        /// 问题标识 RULE_ID@file:line，如 STRING_CONCAT_LOOP@src/main/java/com/acme/Report.java:42
+        #[arg(long)]
+        issue: String,
+
+        /// 只输出基准源码，不写文件
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// 🔍 列出本机 Java 进程 (jps -lv / jcmd -l): PID、主类、JVM 参数、运行时长
+    Ps {
+        #[command(flatten)]
+        target: JvmTargetArgs,
+
+        #[command(flatten)]
+        safety: SafetyArgs,
+    },
+
     /// 🔬 分析线程 Dump (jstack)
     Jstack {
-        /// Java 进程 PID
+        /// Java 进程 PID (容器内的 PID，通常为 1)
         #[arg(short, long)]
         pid: u32,
+
+        #[command(flatten)]
+        target: JvmTargetArgs,
+
+        #[command(flatten)]
+        safety: SafetyArgs,
     },
 
     /// 🔬 分析字节码 (javap)
     Javap {
-        /// 类路径或 .class 文件
+        /// 类路径、.class 文件或 JAR/WAR
         #[arg(short, long)]
         class: String,
+
+        /// JAR/WAR 中要分析的包 glob: com.acme.* | com.acme.** | com.acme
+        #[arg(long)]
+        package: Option<String>,
     },
 
     /// 🔬 分析堆内存 (jmap)
     Jmap {
-        /// Java 进程 PID
+        /// Java 进程 PID (容器内的 PID，通常为 1)
         #[arg(short, long)]
         pid: u32,
+
+        /// 只统计存活对象 (jmap -histo:live，会触发 Full GC，需要 --force)
+        #[arg(long)]
+        live: bool,
+
+        /// 写出堆转储 (.hprof) 到本地路径而不是输出直方图 (容器中转储后复制出来；暂停 JVM，需要 --force)
+        #[arg(long, conflicts_with_all = ["samples", "live"])]
+        dump: Option<String>,
+
+        /// 采集的直方图份数，>1 时对比各次采样并报告增长最快的类 (泄漏嫌疑)
+        #[arg(long, default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+        samples: u32,
+
+        /// 采样间隔: 30s | 2m | 500ms
+        #[arg(long, default_value = "30s", value_parser = jdk_engine::parse_interval)]
+        interval: std::time::Duration,
+
+        #[command(flatten)]
+        target: JvmTargetArgs,
+
+        #[command(flatten)]
+        safety: SafetyArgs,
+    },
+
+    /// 📦 扫描第三方依赖 JAR (javap 字节码启发式: 无界线程池 / ThreadLocal / 同步热点)
+    ScanJar {
+        /// JAR 路径，可重复: --jar a.jar --jar b.jar
+        #[arg(long, required = true)]
+        jar: Vec<String>,
+
+        /// 只分析匹配的包: com.acme.* | com.acme.** | com.acme
+        #[arg(long)]
+        package: Option<String>,
+
+        /// 报告格式: markdown | json | sarif | github | gitlab | checkstyle
+        #[arg(long, value_enum, default_value_t = ReportFormat::Markdown)]
+        format: ReportFormat,
+    },
+
+    /// 🔬 离线分析堆转储 (.hprof): 重复字符串 + 超大集合
+    Hprof {
+        /// 堆转储文件路径
+        #[arg(short, long)]
+        file: String,
+
+        /// 超大集合阈值 (元素数)
+        #[arg(long, default_value = "10000")]
+        min_entries: u64,
+    },
+
+    /// 💥 分析 JVM 崩溃日志 (hs_err_pid*.log): 崩溃原因、栈顶帧、内存映射、GC 状态、疑似子系统
+    Crash {
+        /// hs_err 日志路径 (传入 .hprof 时只读取文件头)
+        #[arg(short, long)]
+        file: String,
     },
 
     /// 📋 项目摘要
@@ -82,63 +434,401 @@ pub enum Command {
         path: String,
     },
 
+    /// 🧭 技术栈侦测: 构建工具、JDK 目标版本、Spring Boot 版本、MVC/WebFlux、JPA/MyBatis 及自动启用的规则档案
+    Detect {
+        /// 项目路径
+        #[arg(short, long, default_value = ".")]
+        path: String,
+    },
+
+    /// 📦 更新规则包: 下载 (或 --offline 读取) 签名的脚本规则与检查清单，校验签名后安装到 ~/.java-perf/rule-pack
+    Update {
+        /// 规则包地址 (签名为 <url>.sig)
+        #[arg(long, env = "JAVA_PERF_UPDATE_URL", conflicts_with = "offline", required_unless_present = "offline")]
+        url: Option<String>,
+
+        /// 隔离网络环境: 本地规则包 zip (签名为同目录的 <zip>.sig)
+        #[arg(long)]
+        offline: Option<PathBuf>,
+
+        /// 受信任的 Ed25519 公钥 (十六进制，可重复或逗号分隔)
+        #[arg(long = "key", env = "JAVA_PERF_PACK_KEYS", value_delimiter = ',')]
+        keys: Vec<String>,
+
+        /// 只校验签名与内容，不安装
+        #[arg(long)]
+        check: bool,
+
+        /// 确认用名称不同的规则包替换已安装的包
+        #[arg(long)]
+        replace: bool,
+    },
+
     /// ℹ️ 引擎状态
     Status,
+
+    /// 📐 输出 `scan --format json` 报告的 JSON Schema (报告的 `$schema` 字段指向其 `$id`)，供看板 / MCP 客户端校验与生成代码
+    Schema {
+        /// 报告布局版本 (默认当前版本；旧版对应 `scan --compat N` 的输出)
+        #[arg(long, value_parser = clap::value_parser!(u32).range(1..=report::REPORT_VERSION as i64))]
+        report_version: Option<u32>,
+    },
+
+    /// 🩺 环境自检: JAVA_HOME、JDK 工具、JVM attach、缓存目录写权限、tree-sitter 语法版本
+    Doctor {
+        /// 项目路径 (检查 .java-perf 缓存目录写权限)
+        #[arg(short, long, default_value = ".")]
+        path: String,
+    },
+
+    /// 🔌 MCP Server (stdio JSON-RPC，`--listen` 时为 HTTP)，供 Agent 客户端调用工具与诊断剧本
+    Mcp {
+        /// 通过 HTTP 提供服务的地址，如 127.0.0.1:8808 (非回环地址必须配置 --token)
+        #[arg(long)]
+        listen: Option<String>,
+
+        /// HTTP 请求须携带的 Bearer token
+        #[arg(long, env = "JAVA_PERF_MCP_TOKEN", hide_env_values = true, requires = "listen")]
+        token: Option<String>,
+
+        /// 允许工具读写的目录 (可重复或逗号分隔，默认不限制)
+        #[arg(long = "allow-path", env = "JAVA_PERF_MCP_ALLOW_PATHS", value_delimiter = ',')]
+        allow_paths: Vec<PathBuf>,
+
+        /// 允许 jstack/jmap attach 的 PID (可重复或逗号分隔，默认不限制)
+        #[arg(long = "allow-pid", env = "JAVA_PERF_MCP_ALLOW_PIDS", value_delimiter = ',')]
+        allow_pids: Vec<u32>,
+
+        /// 允许 docker/kubectl exec 的容器名或 Pod (`namespace/pod`，可重复或逗号分隔，默认不限制)
+        #[arg(long = "allow-host", env = "JAVA_PERF_MCP_ALLOW_HOSTS", value_delimiter = ',')]
+        allow_hosts: Vec<String>,
+    },
+
+    /// ⏱️ 本地性能基准 (单文件 / 全量扫描 / 符号表合并)
+    Bench {
+        /// 合成项目文件数
+        #[arg(long, default_value = "5000")]
+        files: usize,
+
+        /// 每项基准的迭代次数
+        #[arg(long, default_value = "5")]
+        iterations: usize,
+
+        /// 对比的 baseline JSON
+        #[arg(long)]
+        baseline: Option<String>,
+
+        /// 将本次结果保存为 baseline JSON
+        #[arg(long)]
+        save_baseline: Option<String>,
+
+        /// 回归阈值 (百分比)
+        #[arg(long, default_value = "10")]
+        threshold: f64,
+    },
+}
+
+/// 容器中的目标 JVM (v9.6): 通过 docker exec / kubectl exec 运行 JDK 工具
+#[derive(Args, Debug, Clone, Default)]
+pub struct JvmTargetArgs {
+    /// Docker 容器 (与 --pod 一起使用时为 Pod 内的容器名)
+    #[arg(long)]
+    container: Option<String>,
+
+    /// Kubernetes Pod
+    #[arg(long)]
+    pod: Option<String>,
+
+    /// Kubernetes 命名空间
+    #[arg(short, long)]
+    namespace: Option<String>,
+
+    /// docker exec 用户 (默认使用目标 JVM 进程的属主)
+    #[arg(short, long)]
+    user: Option<String>,
+}
+
+impl JvmTargetArgs {
+    fn host(self) -> Result<jdk_engine::JvmHost> {
+        jdk_engine::JvmHost::new(self.container, self.pod, self.namespace, self.user).map_err(anyhow::Error::msg)
+    }
+}
+
+/// 生产安全选项 (v9.6)
+#[derive(Args, Debug, Clone)]
+pub struct SafetyArgs {
+    /// 确认执行会暂停目标 JVM 的操作 (histo:live 触发 Full GC、堆转储)
+    #[arg(long)]
+    force: bool,
+
+    /// 单个 JDK 工具的超时，超时后终止子进程: 60s | 5m
+    #[arg(long, default_value = "60s", value_parser = jdk_engine::parse_interval)]
+    timeout: std::time::Duration,
+}
+
+impl From<SafetyArgs> for jdk_engine::Safety {
+    fn from(args: SafetyArgs) -> Self {
+        Self { force: args.force, timeout: args.timeout }
+    }
 }
 
 /// 处理 CLI 命令
 ///
 /// json_output: 是否输出 JSON 格式（默认 false，输出人类可读格式）
 pub fn handle_command(cmd: Command, json_output: bool) -> Result<()> {
+    // diff-report 出现新增 P0、doctor 存在失败项、checklist --validate 发现错误时，输出报告后以退出码 1 结束
+    let mut exit_failure = false;
     let result = match cmd {
-        Command::Scan { path, full, max_p1 } => {
-            // full=false means compact=true (default)
-            ast_engine::radar_scan(&path, !full, max_p1)
+        Command::Scan { path, full, max_p1, format, meta, profile, rules_dir, timing, threads, io_throttle, layer_weight, no_layer_weight, test_code, owners, severity_overrides, min_severity, only_tags, verify_with_javap, classpath, link_format, repo_url, commit, owned_paths, team, env_file, spring_profile, keep_unreachable, compat } => {
+            let layer_weighting = if no_layer_weight {
+                LayerWeighting::disabled()
+            } else {
+                LayerWeighting::default().with_overrides(layer_weight)
+            };
+            let options = ast_engine::ScanOptions {
+                profiles: profile,
+                rules_dir,
+                timing,
+                threads: threads.map(usize::from),
+                io_throttle: io_throttle.map(usize::from),
+                layer_weighting,
+                test_code,
+                owners,
+                severity_overrides,
+                verify_with_javap,
+                classpath,
+                env_file,
+                spring_profiles: spring_profile,
+                keep_unreachable,
+            };
+            ast_engine::collect_issues_with(&path, &options).and_then(|mut result| {
+                result.meta.extend(meta);
+                let links = link_format
+                    .map(|format| {
+                        let commit = commit.as_deref().or(result.meta.get("git_sha").map(String::as_str));
+                        IssueLinks::new(format, std::path::Path::new(&path), repo_url.as_deref(), commit)
+                    })
+                    .transpose()?;
+                if let Some(min) = min_severity {
+                    result.retain_min_severity(min);
+                }
+                if !only_tags.is_empty() {
+                    result.retain_tags(&only_tags);
+                }
+                let filter = ownership::OwnershipFilter::new(&owned_paths, &team)?;
+                if !filter.is_empty() {
+                    let pruned = ownership::prune(std::path::Path::new(&path), &mut result.issues, &filter)?;
+                    result.info.owned_hidden = Some(pruned.describe());
+                }
+                let started = std::time::Instant::now();
+                // full=false means compact=true (default)
+                let output = match (format, &links) {
+                    (ReportFormat::Markdown, Some(links)) => ast_engine::render_markdown_with_links(&result, !full, max_p1, Some(links)),
+                    (ReportFormat::Json, _) => report::render_json_version(&result, compat.map_or(report::REPORT_VERSION, u32::from)),
+                    _ => render_report(&result, format, !full, max_p1),
+                };
+                Ok(match result.timing.take() {
+                    Some(mut timing) => {
+                        timing.report_ms = started.elapsed().as_secs_f64() * 1000.0;
+                        attach_timing(output, format, &timing)
+                    }
+                    None => output,
+                })
+            })
         }
 
-        Command::Analyze { file } => {
+        Command::Analyze { file, format } => {
             let content = std::fs::read_to_string(&file)?;
-            ast_engine::scan_source_code(&content, &file)
+            match format {
+                ReportFormat::Markdown => ast_engine::scan_source_code(&content, &file),
+                _ => {
+                    let issues = ast_engine::analyze_source_issues(&content, &file);
+                    let result = ast_engine::ScanResult { file_count: 1, issues, meta: Default::default(), info: Default::default(), timing: None, metrics: Vec::new() };
+                    Ok(render_report(&result, format, false, usize::MAX))
+                }
+            }
         }
 
-        Command::Checklist { symptoms, full } => {
-            let symptoms_vec: Vec<&str> = symptoms.split(',').map(|s| s.trim()).collect();
-            checklist::get_checklist(&symptoms_vec, None, !full)
+        Command::Checklist { symptoms, report, full, kb, validate } => {
+            if let Some(kb) = kb {
+                checklist::set_extension(kb);
+            }
+            if validate {
+                checklist::validate_knowledge_base().map(|v| {
+                    exit_failure = v["valid"] == false;
+                    if json_output { v } else { v["report"].clone() }
+                })
+            } else if let Some(report_path) = report {
+                let content = std::fs::read_to_string(&report_path)?;
+                let report: Value = serde_json::from_str(&content)?;
+                checklist::get_checklist_from_report(&report, !full)
+            } else {
+                let symptoms = symptoms.unwrap_or_default();
+                let symptoms_vec: Vec<&str> = symptoms.split(',').map(|s| s.trim()).collect();
+                checklist::get_checklist(&symptoms_vec, None, !full)
+            }
         }
 
-        Command::Antipatterns => {
-            checklist::get_all_antipatterns()
+        Command::Antipatterns { path, profile, rules_dir } => {
+            let options = ast_engine::ScanOptions { profiles: profile, rules_dir, ..Default::default() };
+            checklist::get_all_antipatterns(&path, &options)
+                .map(|v| if json_output { v } else { v["report"].clone() })
         }
 
-        Command::Log { file } => {
-            forensic::analyze_log(&file)
+        Command::DiffReport { old, new } => {
+            scan_diff::compare_files(&old, &new).map(|v| {
+                exit_failure = v["summary"]["new_p0"].as_u64().unwrap_or(0) > 0;
+                if json_output { v } else { v["report"].clone() }
+            })
         }
 
-        Command::Jstack { pid } => {
-            jdk_engine::analyze_thread_dump(pid)
+        Command::ConfigDiff { prod, staging } => {
+            config_diff::compare_files(&prod, &staging)
+                .map(|v| if json_output { v } else { v["report"].clone() })
         }
 
-        Command::Javap { class } => {
-            jdk_engine::analyze_bytecode(&class)
+        Command::EffectiveConfig { path, profile, key } => {
+            effective_config::report(&path, &profile, key.as_deref())
+                .map(|v| if json_output { v } else { v["report"].clone() })
         }
 
-        Command::Jmap { pid } => {
-            jdk_engine::analyze_heap(pid)
+        Command::Log { file, files, follow, duration, trace } => match (file, trace) {
+            (file, Some(trace)) => log_timeline::trace_journey(&file.map_or(files, |f| vec![f]), &trace),
+            (Some(file), None) if follow => forensic::follow_log(&file, duration),
+            (Some(file), None) => forensic::analyze_log(&file),
+            (None, None) => log_timeline::correlate_logs(&files),
+        },
+
+        Command::Index { path, test_code } => {
+            ast_engine::write_index(&path, test_code)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Verify { path, file, line, id } => {
+            verify::verify_issue(&path, &file, line, &id)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Unused { path } => {
+            reachability::report(&path)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::CallChain { path, class, method, max_depth } => {
+            call_chain::get_call_chain(&path, &class, &method, max_depth)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Metrics { file } => {
+            metrics::analyze_file(&file)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::NativeConfig { path, out } => {
+            native_config::generate(&path, out.as_deref())
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Genbench { path, issue, dry_run } => {
+            genbench::generate(&path, &issue, dry_run)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Ps { target, safety } => {
+            jdk_engine::list_jvms(&target.host()?, &safety.into())
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Jstack { pid, target, safety } => {
+            jdk_engine::analyze_thread_dump(pid, &target.host()?, &safety.into())
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Javap { class, package } => {
+            jdk_engine::analyze_bytecode(&class, package.as_deref())
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Jmap { pid, live, dump, samples, interval, target, safety } => {
+            let host = target.host()?;
+            let safety = safety.into();
+            if samples > 1 {
+                jdk_engine::analyze_heap_trend(pid, &host, samples, interval, live, &safety)
+                    .map(|v| if json_output { v } else { v["report"].clone() })
+            } else {
+                jdk_engine::analyze_heap(pid, &host, live, dump.as_deref(), &safety)
+            }
+        }
+
+        Command::ScanJar { jar, package, format } => {
+            scan_jars(&jar, package.as_deref(), format)
+        }
+
+        Command::Hprof { file, min_entries } => {
+            hprof::analyze_hprof(&file, min_entries)
+        }
+
+        Command::Crash { file } => {
+            crash::analyze_crash(&file)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Bench { files, iterations, baseline, save_baseline, threshold } => {
+            bench::run_bench(files, iterations, baseline.as_deref(), save_baseline.as_deref(), threshold)
+                .map(|v| if json_output { v } else { v["report"].clone() })
         }
 
         Command::Summary { path } => {
             get_project_summary(&path, json_output)
         }
 
+        Command::Detect { path } => {
+            detect_project_stack(&path)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Doctor { path } => {
+            let v = doctor::run_doctor(&path);
+            exit_failure = v["failed"].as_u64().unwrap_or(0) > 0;
+            Ok(if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Mcp { listen, token, allow_paths, allow_pids, allow_hosts } => {
+            // 长期运行，消息直接写 stdout / socket，不走下方的结果输出
+            let policy = mcp::AccessPolicy::new(&allow_paths, &allow_pids, &allow_hosts)?;
+            return match listen {
+                Some(addr) => mcp::serve_http(&addr, token, policy),
+                None => mcp::serve(&policy),
+            }.map_err(|e| anyhow::anyhow!("{e}"));
+        }
+
+        Command::Update { url, offline, keys, check, replace } => {
+            let source = match (offline, url) {
+                (Some(path), _) => rule_pack::Source::Offline(path),
+                (None, url) => rule_pack::Source::Url(url.unwrap_or_default()),
+            };
+            rule_pack::update(&source, &keys, check, replace)
+                .map(|v| if json_output { v } else { v["report"].clone() })
+        }
+
+        Command::Schema { report_version } => {
+            let version = report_version.unwrap_or(report::REPORT_VERSION);
+            Ok(json!(report::schema(version).unwrap_or_default().trim_end()))
+        }
+
         Command::Status => {
             let version = env!("CARGO_PKG_VERSION");
+            // 规则数取自实际注册表：AST 规则来自分析器，其余 (配置/日志/Dockerfile/CI 等) 来自标签注册表
+            let ast_rules = JavaTreeSitterAnalyzer::new()?.rule_ids();
+            let file_rules: Vec<&str> = taxonomy::REGISTRY.iter()
+                .map(|(id, _)| *id)
+                .filter(|id| !ast_rules.contains(id))
+                .collect();
             let status = json!({
                 "version": version,
                 "engine": "Rust Radar-Sniper",
-                "ast_rules": ["N_PLUS_ONE", "NESTED_LOOP", "SYNC_METHOD", "THREADLOCAL_LEAK",
-                    "STREAM_RESOURCE_LEAK", "SLEEP_IN_LOCK", "LOCK_METHOD_CALL"],
-                "regex_rules": ["FUTURE_GET_NO_TIMEOUT", "AWAIT_NO_TIMEOUT", "REENTRANT_LOCK_RISK",
-                    "COMPLETABLE_JOIN", "LOG_STRING_CONCAT", "DATASOURCE_NO_POOL"],
+                "ast_rules": ast_rules,
+                "file_rules": file_rules,
                 "jdk_tools": {
                     "jstack": jdk_engine::check_tool_available("jstack"),
                     "jmap": jdk_engine::check_tool_available("jmap"),
@@ -152,10 +842,12 @@ pub fn handle_command(cmd: Command, json_output: bool) -> Result<()> {
                 Ok(json!(format!(
                     "Java Perf v{}\n\
                     Engine: Rust Radar-Sniper (Tree-sitter AST)\n\
-                    AST Rules: 48 | Config Rules: 7 | Dockerfile Rules: 5\n\
+                    AST Rules: {} | Project File Rules: {}\n\
                     Features: Rule Suppression, Two-Pass Semantic Analysis, CallGraph\n\
                     JDK Tools: jstack={}, jmap={}, javap={}",
                     version,
+                    ast_rules.len(),
+                    file_rules.len(),
                     jdk_engine::check_tool_available("jstack"),
                     jdk_engine::check_tool_available("jmap"),
                     jdk_engine::check_tool_available("javap")
@@ -165,9 +857,10 @@ pub fn handle_command(cmd: Command, json_output: bool) -> Result<()> {
 
     };
 
-    // 输出结果
+    // 输出结果 (v9.6: 统一脱敏)
     match result {
-        Ok(value) => {
+        Ok(mut value) => {
+            redact::redact_value(&mut value);
             if json_output {
                 // JSON 格式：包装 success 字段
                 let output = json!({
@@ -179,16 +872,20 @@ pub fn handle_command(cmd: Command, json_output: bool) -> Result<()> {
                 // 人类可读格式：直接输出内容
                 print_value(&value);
             }
+            if exit_failure {
+                std::process::exit(1);
+            }
         }
         Err(e) => {
+            let e = redact::redact(&e.to_string());
             if json_output {
                 let output = json!({
                     "success": false,
-                    "error": e.to_string()
+                    "error": e
                 });
                 println!("{}", serde_json::to_string_pretty(&output)?);
             } else {
-                println!("✅ Engine Status: ACTIVE (v8.0.0 Deep Semantic)");
+                eprintln!("❌ {e}");
             }
             std::process::exit(1);
         }
@@ -197,6 +894,65 @@ pub fn handle_command(cmd: Command, json_output: bool) -> Result<()> {
     Ok(())
 }
 
+/// 按格式渲染扫描报告 (compact/max_p1 仅影响 Markdown)
+fn render_report(result: &ast_engine::ScanResult, format: ReportFormat, compact: bool, max_p1: usize) -> Value {
+    match format {
+        ReportFormat::Markdown => ast_engine::render_markdown(result, compact, max_p1),
+        ReportFormat::Json => report::render_json(result),
+        ReportFormat::Sarif => report::render_sarif(result),
+        ReportFormat::Github => report::render_github(result),
+        ReportFormat::Gitlab => report::render_gitlab(result),
+        ReportFormat::Checkstyle => report::render_checkstyle(result),
+    }
+}
+
+/// 扫描多个依赖 JAR: Markdown 逐个输出，其它格式合并为一份报告
+fn scan_jars(jars: &[String], package: Option<&str>, format: ReportFormat) -> Result<Value, Box<dyn std::error::Error>> {
+    let mut sections = Vec::new();
+    let mut result = ast_engine::ScanResult {
+        file_count: 0,
+        issues: Vec::new(),
+        meta: Default::default(),
+        info: Default::default(),
+        timing: None,
+        metrics: Vec::new(),
+    };
+
+    for jar in jars {
+        let archive = jdk_engine::scan_jar(jar, package)?;
+        sections.push(archive.render(jar, package));
+        result.file_count += archive.analyzed_classes;
+        result.issues.extend(archive.to_issues());
+    }
+
+    if let Some(package) = package {
+        result.info.package = Some(package.to_string());
+    }
+
+    Ok(match format {
+        ReportFormat::Markdown => json!(sections.join("\n")),
+        _ => render_report(&result, format, false, usize::MAX),
+    })
+}
+
+/// 附加 `--timing` 统计: Markdown 追加到报告末尾，JSON 写入 `timing` 字段，
+/// 其它机器可读格式 (SARIF/GitHub/GitLab/Checkstyle) 输出到 stderr 以免破坏 stdout
+fn attach_timing(output: Value, format: ReportFormat, timing: &ast_engine::ScanTiming) -> Value {
+    match (format, output) {
+        (ReportFormat::Markdown, Value::String(s)) => {
+            Value::String(format!("{s}\n{}", ast_engine::render_timing(timing)))
+        }
+        (ReportFormat::Json, Value::Object(mut map)) => {
+            map.insert("timing".to_string(), json!(timing));
+            Value::Object(map)
+        }
+        (_, other) => {
+            eprintln!("{}", ast_engine::render_timing(timing));
+            other
+        }
+    }
+}
+
 /// 打印 Value，智能处理字符串和其他类型
 fn print_value(value: &Value) {
     match value {
@@ -205,6 +961,29 @@ fn print_value(value: &Value) {
     }
 }
 
+/// 技术栈侦测 (v9.6): 结论、依据与据此启用的规则档案 (`scan` 未指定 `--profile` 时使用同一推断)
+fn detect_project_stack(code_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = std::path::Path::new(code_path);
+    if !path.is_dir() {
+        return Err(format!("Not a directory: {code_path}").into());
+    }
+    let stack = crate::project_detector::detect_stack(path);
+    let profiles: Vec<&str> = RuleProfile::infer(&stack).iter().map(|p| p.name()).collect();
+
+    let mut report = format!(
+        "## 🧭 Stack Detection: {code_path}\n\n{}\n\n**Rule Profiles**: {}\n",
+        crate::project_detector::render_stack(&stack),
+        profiles.join("+"),
+    );
+    if !stack.evidence.is_empty() {
+        report.push_str("\n### Evidence\n\n");
+        for evidence in &stack.evidence {
+            report.push_str(&format!("- {evidence}\n"));
+        }
+    }
+    Ok(json!({ "path": code_path, "stack": stack, "profiles": profiles, "report": report }))
+}
+
 /// 获取项目摘要
 fn get_project_summary(code_path: &str, json_output: bool) -> Result<Value, Box<dyn std::error::Error>> {
     use std::path::Path;
@@ -240,12 +1019,14 @@ fn get_project_summary(code_path: &str, json_output: bool) -> Result<Value, Box<
     // 2. 深度项目侦测 (ProjectDetector)
     let stack = crate::project_detector::detect_stack(path);
     let strategy_hint = crate::project_detector::generate_strategy_hint(&stack);
+    let profiles = crate::rules::profile::display(&RuleProfile::infer(&stack));
 
     if json_output {
         Ok(json!({
             "path": code_path,
             "files": { "java": java_files, "xml": xml_files, "yaml": yml_files },
             "stack": stack,
+            "profiles": profiles,
             "strategy_hint": strategy_hint
         }))
     } else {
@@ -255,20 +1036,16 @@ fn get_project_summary(code_path: &str, json_output: bool) -> Result<Value, Box<
             ----------------------------------------\n\
             File Stats: {} Java, {} XML, {} YAML\n\
             Detected Stack:\n\
-            - Build Tool: {}\n\
-            - JDK Version: {}\n\
-            - Spring Boot: {}\n\
-            - Reactive:    {}\n\
+            {}\n\
+            - Rule Profile: {}\n\
             ----------------------------------------\n\
             🤖 Analysis Strategy Hint:\n\
             {}\n\
             ",
             code_path, 
             java_files, xml_files, yml_files,
-            if stack.build_tool.is_empty() { "Unknown" } else { &stack.build_tool },
-            stack.jdk_version,
-            if stack.is_spring_boot { "Yes" } else { "No" },
-            if stack.is_reactive { "Yes" } else { "No" },
+            crate::project_detector::render_stack(&stack),
+            profiles,
             strategy_hint
         );
 
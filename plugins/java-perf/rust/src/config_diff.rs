@@ -0,0 +1,356 @@
+//! 环境间配置漂移检查 (Config Drift) - v9.6
+//!
+//! `config-diff application-prod.yml application-staging.yml`: 解析两份 Spring 配置后按配置项语义对比，
+//! 而不是逐行文本 diff：
+//! - key 按 Spring 宽松绑定归一 (`maximumPoolSize` / `maximum_pool_size` → `maximum-pool-size`)，
+//!   YAML 多文档依次合并，列表展开为 `key[0]`
+//! - 值相同但写法不同的不算差异 (`30s` 与 `30000ms`、`10` 与 `10.0`)
+//! - 连接池/线程数、超时、缓存 TTL 相关的差异单独列出，数值可比时给出 prod/staging 倍数
+//! - staging 有而 prod 缺失的配置项 (prod 回落到默认值) 标记为需确认
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::i18n;
+use crate::scanner::config::{normalize_key, property_entries};
+
+/// 性能相关配置项的分类及 key 中的关键词 (按顺序取第一个命中的)
+const PERF_CATEGORIES: &[(&str, &[&str])] = &[
+    ("timeout", &["timeout", "max-wait", "connection-wait", "keep-alive", "max-lifetime"]),
+    ("cache", &["ttl", "time-to-live", "expire", "cache"]),
+    ("pool", &[
+        "pool", "threads", "max-connections", "max-active", "max-idle", "min-idle", "minimum-idle",
+        "accept-count", "queue-capacity", "concurrency", "batch-size", "fetch-size", "prefetch",
+    ]),
+];
+
+/// 一份配置: 归一化 key → (值, 行号)
+type Flat = BTreeMap<String, (String, Option<usize>)>;
+
+/// 对比两份配置文件 (YAML 或 .properties)
+pub fn compare_files(prod_path: &str, staging_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let prod = load(prod_path)?;
+    let staging = load(staging_path)?;
+    let mut result = compare(&prod, &staging);
+    result["prod"] = json!(prod_path);
+    result["staging"] = json!(staging_path);
+    result["report"] = json!(render_markdown(&result, prod_path, staging_path));
+    Ok(result)
+}
+
+fn load(path: &str) -> Result<Flat, String> {
+    let content = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+    let yaml = matches!(Path::new(path).extension().and_then(|e| e.to_str()), Some("yml" | "yaml"));
+    let mut flat = Flat::new();
+    if yaml {
+        let lines: HashMap<String, usize> = property_entries(&content, true)
+            .into_iter()
+            .map(|e| (normalize_key(&e.key), e.line))
+            .collect();
+        for doc in serde_yaml::Deserializer::from_str(&content) {
+            let doc = serde_yaml::Value::deserialize(doc).map_err(|e| format!("{path}: {e}"))?;
+            let mut values = BTreeMap::new();
+            flatten(&doc, String::new(), &mut values);
+            for (key, value) in values {
+                let line = lines.get(&key).copied();
+                flat.insert(key, (value, line));
+            }
+        }
+    } else {
+        for entry in property_entries(&content, false) {
+            flat.insert(normalize_key(&entry.key), (entry.value, Some(entry.line)));
+        }
+    }
+    Ok(flat)
+}
+
+/// 展开 YAML 为 `a.b.c` → 值
+fn flatten(value: &serde_yaml::Value, prefix: String, out: &mut BTreeMap<String, String>) {
+    use serde_yaml::Value as Y;
+    let join = |key: &str| if prefix.is_empty() { normalize_key(key) } else { format!("{prefix}.{}", normalize_key(key)) };
+    match value {
+        Y::Mapping(map) => {
+            for (key, value) in map {
+                let key = match key {
+                    Y::String(s) => s.clone(),
+                    Y::Number(n) => n.to_string(),
+                    Y::Bool(b) => b.to_string(),
+                    _ => continue,
+                };
+                flatten(value, join(&key), out);
+            }
+        }
+        Y::Sequence(items) => {
+            for (idx, item) in items.iter().enumerate() {
+                flatten(item, format!("{prefix}[{idx}]"), out);
+            }
+        }
+        Y::Tagged(tagged) => flatten(&tagged.value, prefix, out),
+        Y::Null => {
+            out.insert(prefix, String::new());
+        }
+        Y::String(s) => {
+            out.insert(prefix, s.clone());
+        }
+        Y::Number(n) => {
+            out.insert(prefix, n.to_string());
+        }
+        Y::Bool(b) => {
+            out.insert(prefix, b.to_string());
+        }
+    }
+}
+
+/// 性能相关分类 (pool | timeout | cache)
+fn category(key: &str) -> Option<&'static str> {
+    PERF_CATEGORIES
+        .iter()
+        .find(|(_, words)| words.iter().any(|w| key.contains(w)))
+        .map(|(name, _)| *name)
+}
+
+/// 数值与单位类别: 时长统一为毫秒 (`30s` → (30000, true))，纯数字 (10 → (10, false))
+fn magnitude(value: &str) -> Option<(f64, bool)> {
+    let value = value.trim().to_ascii_lowercase();
+    let split = value.find(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-')).unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number.parse().ok()?;
+    let factor = match unit.trim() {
+        "" => return Some((number, false)),
+        "ms" => 1.0,
+        "s" => 1_000.0,
+        "m" => 60_000.0,
+        "h" => 3_600_000.0,
+        "d" => 86_400_000.0,
+        _ => return None,
+    };
+    Some((number * factor, true))
+}
+
+fn same_value(a: &str, b: &str) -> bool {
+    a.trim() == b.trim() || matches!((magnitude(a), magnitude(b)), (Some(x), Some(y)) if x == y)
+}
+
+/// prod/staging 倍数 (两边是同类数值且 staging 不为 0 时)
+fn ratio(prod: &str, staging: &str) -> Option<f64> {
+    match (magnitude(prod), magnitude(staging)) {
+        (Some((p, pu)), Some((s, su))) if pu == su && s != 0.0 => Some((p / s * 100.0).round() / 100.0),
+        _ => None,
+    }
+}
+
+/// 对比两份展开后的配置
+fn compare(prod: &Flat, staging: &Flat) -> Value {
+    let mut perf = Vec::new();
+    let mut other = Vec::new();
+    let mut missing = Vec::new();
+    let mut identical = 0;
+    for (key, (staging_value, staging_line)) in staging {
+        let category = category(key);
+        match prod.get(key) {
+            Some((prod_value, _)) if same_value(prod_value, staging_value) => identical += 1,
+            Some((prod_value, prod_line)) => {
+                let mut entry = json!({
+                    "key": key,
+                    "prod": prod_value,
+                    "staging": staging_value,
+                    "prod_line": prod_line,
+                    "staging_line": staging_line,
+                });
+                match category {
+                    Some(category) => {
+                        entry["category"] = json!(category);
+                        entry["ratio"] = json!(ratio(prod_value, staging_value));
+                        perf.push(entry);
+                    }
+                    None => other.push(entry),
+                }
+            }
+            None => missing.push(json!({
+                "key": key,
+                "staging": staging_value,
+                "staging_line": staging_line,
+                "category": category,
+            })),
+        }
+    }
+    let only_in_prod: Vec<Value> = prod
+        .iter()
+        .filter(|(key, _)| !staging.contains_key(*key))
+        .map(|(key, (value, line))| json!({ "key": key, "prod": value, "prod_line": line }))
+        .collect();
+    let perf_missing = missing.iter().filter(|m| !m["category"].is_null()).count();
+
+    json!({
+        "summary": {
+            "perf_divergent": perf.len(),
+            "divergent": perf.len() + other.len(),
+            "missing_in_prod": missing.len(),
+            "perf_missing_in_prod": perf_missing,
+            "only_in_prod": only_in_prod.len(),
+            "identical": identical,
+        },
+        "perf_divergent": perf,
+        "divergent": other,
+        "missing_in_prod": missing,
+        "only_in_prod": only_in_prod,
+    })
+}
+
+fn render_markdown(result: &Value, prod_path: &str, staging_path: &str) -> String {
+    let summary = &result["summary"];
+    let count = |key: &str| summary[key].as_u64().unwrap_or(0);
+    let text = |v: &Value| v.as_str().unwrap_or("").to_string();
+    let name = |path: &str| Path::new(path).file_name().map_or(path.to_string(), |n| n.to_string_lossy().to_string());
+
+    let mut out = i18n::format(
+        "report.config_diff.title",
+        "## ⚖️ 配置漂移: {prod} vs {staging}",
+        &[("prod", &name(prod_path)), ("staging", &name(staging_path))],
+    );
+    out.push_str("\n\n");
+    out.push_str(&i18n::format(
+        "report.config_diff.summary",
+        "**性能相关差异**: {perf} | **其它差异**: {other} | **prod 缺失**: {missing} (性能相关 {perf_missing}) | **仅 prod**: {only_prod} | **相同**: {identical}",
+        &[
+            ("perf", &count("perf_divergent")),
+            ("other", &(count("divergent") - count("perf_divergent"))),
+            ("missing", &count("missing_in_prod")),
+            ("perf_missing", &count("perf_missing_in_prod")),
+            ("only_prod", &count("only_in_prod")),
+            ("identical", &count("identical")),
+        ],
+    ));
+    out.push_str("\n\n");
+
+    let perf = result["perf_divergent"].as_array().map(Vec::as_slice).unwrap_or_default();
+    if !perf.is_empty() {
+        out.push_str(i18n::text("report.config_diff.perf_header", "### 🔥 性能相关差异"));
+        out.push_str("\n\n");
+        out.push_str(i18n::text("report.config_diff.perf_columns", "| 配置项 | 分类 | prod | staging | prod/staging |"));
+        out.push_str("\n|--------|------|------|---------|--------------|\n");
+        for entry in perf {
+            let ratio = entry["ratio"].as_f64().map_or("-".to_string(), |r| format!("×{r}"));
+            out.push_str(&format!(
+                "| `{}` | {} | `{}` | `{}` | {ratio} |\n",
+                text(&entry["key"]), text(&entry["category"]), text(&entry["prod"]), text(&entry["staging"])
+            ));
+        }
+        out.push('\n');
+    }
+
+    let missing = result["missing_in_prod"].as_array().map(Vec::as_slice).unwrap_or_default();
+    if !missing.is_empty() {
+        out.push_str(i18n::text("report.config_diff.missing_header", "### ⚠️ staging 有而 prod 缺失 (prod 使用默认值)"));
+        out.push_str("\n\n");
+        for entry in missing {
+            let category = entry["category"].as_str().map_or(String::new(), |c| format!(" ({c})"));
+            out.push_str(&format!("- `{}` = `{}`{category}\n", text(&entry["key"]), text(&entry["staging"])));
+        }
+        out.push('\n');
+    }
+
+    let other = result["divergent"].as_array().map(Vec::as_slice).unwrap_or_default();
+    if !other.is_empty() {
+        out.push_str(i18n::text("report.config_diff.other_header", "### 📝 其它差异"));
+        out.push_str("\n\n");
+        for entry in other {
+            out.push_str(&format!("- `{}`: `{}` → `{}`\n", text(&entry["key"]), text(&entry["prod"]), text(&entry["staging"])));
+        }
+        out.push('\n');
+    }
+
+    if perf.is_empty() && missing.is_empty() {
+        out.push_str(i18n::text("report.config_diff.no_perf_drift", "✅ 无性能相关的配置漂移"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flat(yaml: &str) -> Flat {
+        let mut values = BTreeMap::new();
+        for doc in serde_yaml::Deserializer::from_str(yaml) {
+            flatten(&serde_yaml::Value::deserialize(doc).unwrap(), String::new(), &mut values);
+        }
+        values.into_iter().map(|(k, v)| (k, (v, None))).collect()
+    }
+
+    #[test]
+    fn test_semantic_config_diff() {
+        let prod = flat(r#"
+spring:
+  datasource:
+    hikari:
+      maximumPoolSize: 10
+      connection-timeout: 30s
+  redis:
+    timeout: 2000
+server:
+  port: 8080
+"#);
+        let staging = flat(r#"
+spring:
+  datasource:
+    hikari:
+      maximum_pool_size: 40
+      connection-timeout: 30000ms
+  redis:
+    timeout: 500
+  cache:
+    redis:
+      time-to-live: 10m
+server:
+  port: 8081
+management:
+  endpoints:
+    web:
+      exposure:
+        include: [health, prometheus]
+"#);
+        let result = compare(&prod, &staging);
+        assert_eq!(result["summary"]["perf_divergent"], 2);
+        assert_eq!(result["summary"]["divergent"], 3);
+        assert_eq!(result["summary"]["identical"], 1);
+        assert_eq!(result["summary"]["missing_in_prod"], 3);
+        assert_eq!(result["summary"]["perf_missing_in_prod"], 1);
+
+        let perf = &result["perf_divergent"];
+        assert_eq!(perf[0]["key"], "spring.datasource.hikari.maximum-pool-size");
+        assert_eq!(perf[0]["category"], "pool");
+        assert_eq!(perf[0]["ratio"], 0.25);
+        assert_eq!(perf[1]["key"], "spring.redis.timeout");
+        assert_eq!(perf[1]["ratio"], 4.0);
+        assert_eq!(result["divergent"][0]["key"], "server.port");
+        assert_eq!(result["missing_in_prod"][2]["key"], "spring.cache.redis.time-to-live");
+        assert_eq!(result["missing_in_prod"][2]["category"], "cache");
+        assert_eq!(result["missing_in_prod"][0]["key"], "management.endpoints.web.exposure.include[0]");
+
+        let report = render_markdown(&result, "config/application-prod.yml", "application-staging.yml");
+        assert!(report.contains("| `spring.redis.timeout` | timeout | `2000` | `500` | ×4 |"), "{report}");
+        assert!(report.contains("- `spring.cache.redis.time-to-live` = `10m` (cache)\n"), "{report}");
+        assert!(report.contains("- `server.port`: `8080` → `8081`\n"), "{report}");
+    }
+
+    #[test]
+    fn test_load_properties_and_yaml_lines() {
+        let dir = std::env::temp_dir().join(format!("java-perf-config-diff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let prod = dir.join("application-prod.properties");
+        let staging = dir.join("application-staging.yml");
+        std::fs::write(&prod, "spring.datasource.hikari.maximum-pool-size=20\nspring.kafka.listener.concurrency=3\n").unwrap();
+        std::fs::write(&staging, "spring:\n  datasource:\n    hikari:\n      maximum-pool-size: 20\n  kafka:\n    listener:\n      concurrency: 12\n").unwrap();
+
+        let result = compare_files(prod.to_str().unwrap(), staging.to_str().unwrap()).unwrap();
+        assert_eq!(result["summary"]["identical"], 1);
+        let entry = &result["perf_divergent"][0];
+        assert_eq!(entry["key"], "spring.kafka.listener.concurrency");
+        assert_eq!((entry["prod_line"].as_u64(), entry["staging_line"].as_u64()), (Some(2), Some(7)));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
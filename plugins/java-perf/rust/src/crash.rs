@@ -0,0 +1,651 @@
+//! JVM 崩溃取证 (Crash) - v9.6
+//!
+//! `crash --file hs_err_pid1234.log` 解析 HotSpot 致命错误日志，覆盖两类现场：
+//! - **信号崩溃**: `SIGSEGV`/`SIGBUS`/`EXCEPTION_ACCESS_VIOLATION`，按问题栈帧判断 JIT / GC / JNI / Unsafe
+//! - **内存耗尽**: 本地内存分配失败 (`mmap`/`malloc`)、无法创建线程、`-XX:+CrashOnOutOfMemoryError` 的堆/Metaspace OOM
+//!
+//! 报告包含崩溃原因、崩溃线程与栈顶帧、内存映射摘要 (第三方本地库)、GC 状态，
+//! 给出疑似子系统并映射到 `get_checklist` 症状与检查清单章节。
+//!
+//! 传入 `.hprof` (`-XX:+HeapDumpOnOutOfMemoryError` 的产物) 时只读取文件头，确认格式后引导到 `hprof` 命令。
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::checklist;
+use crate::hprof;
+
+/// 报告中展示的栈帧数
+const TOP_FRAMES: usize = 10;
+
+/// `#  SIGSEGV (0xb) at pc=0x00007f3a2c4d5e6f, pid=1234, tid=5678`
+static SIGNAL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^#\s+([A-Z_]+) \((0x[0-9a-fA-F]+)\) at pc=(0x[0-9a-fA-F]+), pid=(\d+)").unwrap()
+});
+
+/// `#  Out of Memory Error (os_linux.cpp:2749), pid=1234, tid=5678`
+static PID_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"pid=(\d+)").unwrap());
+
+/// `GC Heap History (20 events):`
+static GC_EVENTS_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^GC Heap History \((\d+) events\)").unwrap());
+
+/// JDK 自带与操作系统的本地库目录，之外的库视为第三方 (JNI)
+const SYSTEM_LIB_DIRS: &[&str] = &["/lib/", "/lib64/", "/usr/lib/", "/usr/lib64/", "/system/", "C:\\Windows\\"];
+
+/// HotSpot GC 实现的 C++ 符号前缀
+const GC_SYMBOLS: &[&str] = &[
+    "G1", "ParallelScavenge", "PSScavenge", "PSParallelCompact", "PSPromotion", "ParNew", "CMS",
+    "Shenandoah", "ZBarrier", "ZMark", "ZHeap", "ZRelocate", "SerialHeap", "DefNewGeneration",
+    "MarkSweep", "GCTaskThread", "WorkerThread", "CollectedHeap", "OopMapSet", "frame::oops_do",
+];
+
+/// 崩溃类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrashKind {
+    /// 信号/访问违例
+    Signal,
+    /// 本地内存或 Java 堆耗尽
+    OutOfMemory,
+    /// JVM 内部断言/致命错误
+    InternalError,
+}
+
+/// 解析出的 hs_err 日志
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashLog {
+    pub kind: CrashKind,
+    /// 头部的错误描述 (信号行或 OOM 原因)
+    pub reason: String,
+    pub signal: Option<String>,
+    pub pid: Option<u32>,
+    pub jre_version: Option<String>,
+    pub vm: Option<String>,
+    /// `# Problematic frame:` 的下一行
+    pub problematic_frame: Option<String>,
+    pub current_thread: Option<String>,
+    pub native_frames: Vec<String>,
+    pub java_frames: Vec<String>,
+    pub siginfo: Option<String>,
+    /// 从 `# Java VM:` 中提取的收集器，如 `g1 gc`
+    pub gc: Option<String>,
+    /// `Heap:` 小节
+    pub heap: Vec<String>,
+    pub gc_events: Option<usize>,
+    pub java_threads: usize,
+    /// `Memory: 4k page, physical ...`
+    pub memory: Option<String>,
+    pub jvm_args: Option<String>,
+    /// `Dynamic libraries:` 映射行数
+    pub mappings: usize,
+    /// 不属于 JDK/操作系统的本地库
+    pub third_party_libraries: Vec<String>,
+}
+
+/// 疑似子系统
+#[derive(Debug, Clone, Serialize)]
+pub struct Suspect {
+    pub subsystem: &'static str,
+    pub title: &'static str,
+    pub evidence: String,
+    pub fix: String,
+    /// `get_checklist` 的症状参数
+    pub symptom: Option<&'static str>,
+}
+
+/// 对应的检查清单章节
+#[derive(Debug, Clone, Serialize)]
+pub struct SectionRef {
+    pub section: String,
+    pub title: String,
+}
+
+/// 分析 hs_err 日志或识别堆转储文件头
+pub fn analyze_crash(path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let file = Path::new(path);
+    if !file.exists() {
+        return Err(format!("Crash log not found: {path}").into());
+    }
+    if let Ok(header) = hprof::read_header(file) {
+        return Ok(heap_dump_head(path, &header, file.metadata()?.len()));
+    }
+
+    let content = String::from_utf8_lossy(&std::fs::read(file)?).into_owned();
+    let log = parse(&content).ok_or_else(|| format!("Not a HotSpot error log (hs_err_pid*.log): {path}"))?;
+    let suspect = diagnose(&log);
+    let sections = suspect.symptom.map(checklist_sections).unwrap_or_default();
+    let report = render(path, &log, &suspect, &sections);
+
+    Ok(json!({
+        "file": path,
+        "crash": log,
+        "suspect": suspect,
+        "checklist": sections,
+        "report": report,
+    }))
+}
+
+/// 解析 hs_err 日志，没有 HotSpot 致命错误头部时返回 None
+pub fn parse(content: &str) -> Option<CrashLog> {
+    let lines: Vec<&str> = content.lines().collect();
+    if !lines.iter().any(|l| l.contains("A fatal error has been detected") || l.contains("insufficient memory for the Java Runtime")) {
+        return None;
+    }
+
+    let mut log = CrashLog {
+        kind: CrashKind::InternalError,
+        reason: String::new(),
+        signal: None,
+        pid: None,
+        jre_version: None,
+        vm: None,
+        problematic_frame: None,
+        current_thread: None,
+        native_frames: Vec::new(),
+        java_frames: Vec::new(),
+        siginfo: None,
+        gc: None,
+        heap: Vec::new(),
+        gc_events: None,
+        java_threads: 0,
+        memory: None,
+        jvm_args: None,
+        mappings: 0,
+        third_party_libraries: Vec::new(),
+    };
+
+    // === 头部 (# 开头) ===
+    let mut oom_reason = None;
+    let mut error_detail = None;
+    let mut error_location = None;
+    for (i, line) in lines.iter().enumerate().take_while(|(_, l)| l.starts_with('#') || l.trim().is_empty()) {
+        let text = line.trim_start_matches('#').trim();
+        if let Some(caps) = SIGNAL_REGEX.captures(line) {
+            log.kind = CrashKind::Signal;
+            log.signal = Some(caps[1].to_string());
+            log.pid = caps[4].parse().ok();
+            log.reason = text.to_string();
+        } else if text.starts_with("Native memory allocation") || text.starts_with("Cannot create ") {
+            oom_reason.get_or_insert_with(|| text.to_string());
+        } else if let Some(rest) = text.strip_prefix("fatal error:").or_else(|| text.strip_prefix("Error:")) {
+            error_detail.get_or_insert_with(|| rest.trim().to_string());
+        } else if text.starts_with("Out of Memory Error") || text.starts_with("Internal Error") {
+            log.pid = log.pid.or_else(|| PID_REGEX.captures(text).and_then(|c| c[1].parse().ok()));
+            error_location.get_or_insert_with(|| text.to_string());
+        } else if let Some(v) = text.strip_prefix("JRE version:") {
+            log.jre_version = Some(v.trim().to_string());
+        } else if let Some(v) = text.strip_prefix("Java VM:") {
+            log.vm = Some(v.trim().to_string());
+            log.gc = v.split([',', '(', ')']).map(str::trim).find(|p| p.ends_with(" gc")).map(str::to_string);
+        } else if text == "Problematic frame:" {
+            log.problematic_frame = lines.get(i + 1).map(|l| l.trim_start_matches('#').trim().to_string());
+        }
+    }
+    if log.kind != CrashKind::Signal {
+        let oom = oom_reason.is_some() || lines.iter().any(|l| l.contains("insufficient memory"))
+            || error_detail.as_deref().is_some_and(|r| r.contains("OutOfMemory"));
+        log.kind = if oom { CrashKind::OutOfMemory } else { CrashKind::InternalError };
+        log.reason = oom_reason.or(error_detail).or(error_location).unwrap_or_default();
+    }
+
+    // === 正文小节 ===
+    let mut i = 0;
+    while i < lines.len() {
+        let line = lines[i];
+        if line.starts_with("Current thread (") {
+            log.current_thread = line.split_once("):").map(|(_, t)| t.trim().to_string());
+        } else if line.starts_with("Native frames:") {
+            log.native_frames = block(&lines, i + 1);
+        } else if line.starts_with("Java frames:") {
+            log.java_frames = block(&lines, i + 1);
+        } else if line.starts_with("siginfo:") {
+            log.siginfo = Some(line.trim_start_matches("siginfo:").trim().to_string());
+        } else if line.trim_end() == "Heap:" {
+            log.heap = block(&lines, i + 1);
+        } else if let Some(caps) = GC_EVENTS_REGEX.captures(line) {
+            log.gc_events = caps[1].parse().ok();
+        } else if line.starts_with("Java Threads:") {
+            log.java_threads = block(&lines, i + 1).iter().filter(|l| l.contains("JavaThread")).count();
+        } else if line.starts_with("Memory: ") {
+            log.memory = Some(line.trim().to_string());
+        } else if let Some(args) = line.strip_prefix("jvm_args: ") {
+            log.jvm_args = Some(args.trim().to_string());
+        } else if line.starts_with("Dynamic libraries:") {
+            let maps = block(&lines, i + 1);
+            log.mappings = maps.len();
+            log.third_party_libraries = third_party_libraries(&maps);
+            i += maps.len();
+        }
+        i += 1;
+    }
+
+    Some(log)
+}
+
+/// 从 `start` 开始到空行为止的非空行
+fn block(lines: &[&str], start: usize) -> Vec<String> {
+    lines.iter()
+        .skip(start)
+        .take_while(|l| !l.trim().is_empty())
+        .map(|l| l.trim_end().to_string())
+        .collect()
+}
+
+/// 内存映射中的第三方本地库 (去重，保持出现顺序)
+///
+/// JDK 目录由 libjvm 所在路径推出，JDK 与操作系统目录下的库不计入。
+fn third_party_libraries(maps: &[String]) -> Vec<String> {
+    let path_of = |line: &String| line.split_whitespace().last().filter(|p| p.contains('/') || p.contains('\\')).map(str::to_string);
+    let is_library = |p: &str| p.ends_with(".so") || p.contains(".so.") || p.ends_with(".dll") || p.ends_with(".dylib");
+    let jdk_home = maps.iter()
+        .filter_map(path_of)
+        .find(|p| p.contains("libjvm.") || p.ends_with("jvm.dll"))
+        .and_then(|p| p.find("/lib/").or_else(|| p.find("\\bin\\")).map(|idx| p[..idx].to_string()));
+
+    let mut libraries: Vec<String> = Vec::new();
+    for path in maps.iter().filter_map(path_of) {
+        let system = SYSTEM_LIB_DIRS.iter().any(|d| path.starts_with(d))
+            || jdk_home.as_deref().is_some_and(|home| path.starts_with(home));
+        if is_library(&path) && !system && !libraries.contains(&path) {
+            libraries.push(path);
+        }
+    }
+    libraries
+}
+
+/// 栈帧类型: `C` 本地代码、`V` JVM、`J` JIT 编译代码、`j` 解释执行
+fn frame_kind(frame: &str) -> Option<char> {
+    frame.chars().next().filter(|c| matches!(c, 'C' | 'V' | 'J' | 'j' | 'v' | 'A'))
+}
+
+/// 本地帧中的库名: `C  [libfoo.so+0x1234]  sym` -> `libfoo.so`
+fn frame_library(frame: &str) -> Option<&str> {
+    let start = frame.find('[')? + 1;
+    let end = start + frame[start..].find(['+', ']'])?;
+    Some(&frame[start..end])
+}
+
+/// 判断疑似子系统
+pub fn diagnose(log: &CrashLog) -> Suspect {
+    let reason = log.reason.as_str();
+    let frames: Vec<&str> = log.problematic_frame.iter().map(String::as_str)
+        .chain(log.native_frames.iter().map(String::as_str))
+        .collect();
+    let thread = log.current_thread.as_deref().unwrap_or("");
+    let top_frame = log.problematic_frame.as_deref().or(frames.first().copied()).unwrap_or("");
+    let evidence_frame = |label: &str| format!("{label}: `{top_frame}`");
+
+    if reason.contains("Java heap space") || reason.contains("GC overhead limit") {
+        return Suspect {
+            subsystem: "heap",
+            title: "Java 堆耗尽",
+            evidence: format!("`{reason}`"),
+            fix: "配合 -XX:+HeapDumpOnOutOfMemoryError 获取堆转储，用 `java-perf hprof --file` 查看超大集合与重复字符串".to_string(),
+            symptom: Some("memory"),
+        };
+    }
+    if reason.contains("Metaspace") || reason.contains("Compressed class space") {
+        return Suspect {
+            subsystem: "metaspace",
+            title: "Metaspace 耗尽",
+            evidence: format!("`{reason}`"),
+            fix: "以 -Xlog:class+load,gc+metaspace 重启并用 `java-perf log --file` 检查 ClassLoader 泄漏与代理类爆炸".to_string(),
+            symptom: Some("memory"),
+        };
+    }
+    if reason.contains("create native thread") || reason.contains("pthread_create") || reason.starts_with("Cannot create ") {
+        return Suspect {
+            subsystem: "threads",
+            title: "无法创建本地线程",
+            evidence: format!("`{reason}`，崩溃时 Java 线程 {} 个", log.java_threads),
+            fix: "检查无界线程池与线程泄漏 (`java-perf scan` 的 UNBOUNDED_POOL / THREAD_IN_REQUEST_SCOPE)，核对 ulimit -u、容器 pids 限制与 -Xss".to_string(),
+            symptom: Some("resource"),
+        };
+    }
+    if log.kind == CrashKind::OutOfMemory {
+        let memory = log.memory.as_deref().map(|m| format!("；{m}")).unwrap_or_default();
+        return Suspect {
+            subsystem: "native-memory",
+            title: "本地内存耗尽",
+            evidence: format!("`{reason}`{memory}"),
+            fix: "进程总内存 ≈ -Xmx + MaxMetaspaceSize + 线程数 × Xss + MaxDirectMemorySize + 代码缓存，核对是否超过物理内存/容器限制；用 -XX:NativeMemoryTracking=summary 定位增长区域".to_string(),
+            symptom: Some("resource"),
+        };
+    }
+    if reason.contains("StackOverflow") || log.siginfo.as_deref().is_some_and(|s| s.contains("stack overflow")) {
+        return Suspect {
+            subsystem: "stack",
+            title: "栈溢出",
+            evidence: format!("`{reason}`"),
+            fix: "检查无终止条件的递归与过深的调用链，必要时调大 -Xss".to_string(),
+            symptom: Some("cpu"),
+        };
+    }
+
+    if frame_kind(top_frame) == Some('J') || thread.contains("CompilerThread") {
+        return Suspect {
+            subsystem: "jit",
+            title: "JIT 编译器 / 编译代码",
+            evidence: evidence_frame(if thread.contains("CompilerThread") { "崩溃于编译线程，问题帧" } else { "问题帧为 JIT 编译代码" }),
+            fix: "升级到同一大版本的最新 JDK 补丁；临时用 -XX:CompileCommand=exclude,<类>::<方法> 排除触发崩溃的方法".to_string(),
+            symptom: Some("cpu"),
+        };
+    }
+    if frames.iter().any(|f| f.contains("Unsafe_") || f.contains("misc.Unsafe"))
+        || log.java_frames.iter().any(|f| f.contains("misc.Unsafe"))
+    {
+        return Suspect {
+            subsystem: "unsafe",
+            title: "Unsafe / 堆外内存访问",
+            evidence: evidence_frame("栈中存在 Unsafe 内存操作，问题帧"),
+            fix: "检查 DirectByteBuffer/Netty ByteBuf 释放后继续使用 (use-after-free)、越界写入，开启 -Dio.netty.leakDetection.level=paranoid 复现".to_string(),
+            symptom: Some("memory"),
+        };
+    }
+    if frame_kind(top_frame) == Some('V')
+        && (GC_SYMBOLS.iter().any(|s| top_frame.contains(s)) || thread.contains("GC") || thread.contains("G1 "))
+    {
+        let gc = log.gc.as_deref().map(|g| format!(" ({g})")).unwrap_or_default();
+        return Suspect {
+            subsystem: "gc",
+            title: "垃圾回收器",
+            evidence: evidence_frame(&format!("问题帧位于 GC 代码{gc}")),
+            fix: "多为 JVM 缺陷或堆被本地代码写坏：升级 JDK 补丁版本，临时切换收集器，用 -XX:+VerifyBeforeGC -XX:+VerifyAfterGC 复现".to_string(),
+            symptom: Some("gc"),
+        };
+    }
+    if frame_kind(top_frame) == Some('C') {
+        let library = frame_library(top_frame).unwrap_or("?");
+        let third_party = log.third_party_libraries.iter().any(|l| l.ends_with(library));
+        return Suspect {
+            subsystem: "jni",
+            title: "JNI 本地库",
+            evidence: if third_party {
+                evidence_frame(&format!("问题帧位于第三方本地库 `{library}`"))
+            } else {
+                evidence_frame(&format!("问题帧位于本地库 `{library}`"))
+            },
+            fix: format!("升级或替换 `{library}` 对应的依赖 (如 netty-tcnative、rocksdbjni、JNA)，用 -Xcheck:jni 复现"),
+            symptom: Some("resource"),
+        };
+    }
+
+    Suspect {
+        subsystem: "jvm",
+        title: "JVM 内部错误",
+        evidence: if top_frame.is_empty() { format!("`{reason}`") } else { evidence_frame("问题帧") },
+        fix: "升级到同一大版本的最新 JDK 补丁，并按 hs_err 头部提示向 JDK 供应商报告".to_string(),
+        symptom: None,
+    }
+}
+
+/// 症状对应的检查清单章节
+fn checklist_sections(symptom: &str) -> Vec<SectionRef> {
+    let kb = checklist::knowledge_base();
+    kb.sections_for(symptom)
+        .iter()
+        .map(|section| SectionRef {
+            section: section.clone(),
+            title: kb.sections.iter().find(|s| &s.id == section).map(|s| s.title.clone()).unwrap_or_default(),
+        })
+        .collect()
+}
+
+fn render(path: &str, log: &CrashLog, suspect: &Suspect, sections: &[SectionRef]) -> String {
+    let mut report = format!("## 💥 JVM 崩溃分析: {path}\n\n**原因**: {}", log.reason);
+    if let Some(pid) = log.pid {
+        report.push_str(&format!(" | **PID**: {pid}"));
+    }
+    if let Some(jre) = &log.jre_version {
+        report.push_str(&format!("\n**JRE**: {jre}"));
+    }
+    report.push_str("\n\n");
+
+    report.push_str(&format!("### 🎯 疑似子系统: {} (`{}`)\n\n- 依据: {}\n- 建议: {}\n", suspect.title, suspect.subsystem, suspect.evidence, suspect.fix));
+    if let Some(symptom) = suspect.symptom {
+        let titles: Vec<String> = sections.iter().map(|s| format!("§{} {}", s.section, s.title)).collect();
+        report.push_str(&format!("- 检查清单: {} (`get_checklist` 症状 `{symptom}`)\n", titles.join(", ")));
+    }
+    report.push('\n');
+
+    report.push_str("### 🧵 崩溃线程\n\n");
+    if let Some(thread) = &log.current_thread {
+        report.push_str(&format!("`{thread}`\n\n"));
+    }
+    let frames = if log.native_frames.is_empty() { &log.java_frames } else { &log.native_frames };
+    if frames.is_empty() {
+        report.push_str(&format!("问题帧: `{}`\n\n", log.problematic_frame.as_deref().unwrap_or("-")));
+    } else {
+        report.push_str("```\n");
+        for frame in frames.iter().take(TOP_FRAMES) {
+            report.push_str(&format!("{frame}\n"));
+        }
+        if frames.len() > TOP_FRAMES {
+            report.push_str(&format!("... {} more\n", frames.len() - TOP_FRAMES));
+        }
+        report.push_str("```\n\n");
+    }
+    if let Some(siginfo) = &log.siginfo {
+        report.push_str(&format!("**siginfo**: `{siginfo}`\n\n"));
+    }
+
+    report.push_str(&format!("### 🗺️ 内存映射\n\n**映射**: {} | **Java 线程**: {}\n", log.mappings, log.java_threads));
+    if let Some(memory) = &log.memory {
+        report.push_str(&format!("**{memory}**\n"));
+    }
+    if !log.third_party_libraries.is_empty() {
+        report.push_str("\n第三方本地库:\n");
+        for lib in &log.third_party_libraries {
+            report.push_str(&format!("- `{lib}`\n"));
+        }
+    }
+    report.push('\n');
+
+    report.push_str(&format!(
+        "### ♻️ GC 状态\n\n**收集器**: {} | **GC 事件**: {}\n",
+        log.gc.as_deref().unwrap_or("-"),
+        log.gc_events.map_or_else(|| "-".to_string(), |n| n.to_string()),
+    ));
+    if !log.heap.is_empty() {
+        report.push_str("\n```\n");
+        for line in &log.heap {
+            report.push_str(&format!("{line}\n"));
+        }
+        report.push_str("```\n");
+    }
+    report
+}
+
+/// 堆转储: 只报告文件头，完整分析交给 `hprof`
+fn heap_dump_head(path: &str, header: &hprof::HprofHeader, size: u64) -> Value {
+    let dumped_at = chrono::DateTime::from_timestamp_millis(header.timestamp_ms as i64)
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let report = format!(
+        "## 💥 堆转储: {path}\n\n\
+        **格式**: {} | **ID 大小**: {} 字节 | **转储时间**: {dumped_at} | **文件大小**: {:.1} MB\n\n\
+        这是 OOM 时写出的堆转储而不是 hs_err 日志，用 `java-perf hprof --file {path}` 分析重复字符串与超大集合\n",
+        header.version,
+        header.id_size,
+        size as f64 / (1u64 << 20) as f64,
+    );
+    json!({
+        "file": path,
+        "heap_dump": {
+            "version": header.version,
+            "id_size": header.id_size,
+            "timestamp_ms": header.timestamp_ms,
+            "size": size,
+        },
+        "report": report,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SEGV_JNI: &str = r#"#
+# A fatal error has been detected by the Java Runtime Environment:
+#
+#  SIGSEGV (0xb) at pc=0x00007f3a2c4d5e6f, pid=1234, tid=5678
+#
+# JRE version: OpenJDK Runtime Environment Temurin-17.0.9+9 (17.0.9+9) (build 17.0.9+9)
+# Java VM: OpenJDK 64-Bit Server VM Temurin-17.0.9+9 (17.0.9+9, mixed mode, sharing, tiered, compressed oops, compressed class ptrs, g1 gc, linux-amd64)
+# Problematic frame:
+# C  [librocksdbjni123.so+0x4d5e6f]  rocksdb::DBImpl::Get+0x1f
+#
+# If you would like to submit a bug report, please visit:
+#
+
+---------------  T H R E A D  ---------------
+
+Current thread (0x00007f3a40012345):  JavaThread "http-nio-8080-exec-3" daemon [_thread_in_native, id=5678, stack(0x00007f3a1c000000,0x00007f3a1c100000)]
+
+Stack: [0x00007f3a1c000000,0x00007f3a1c100000],  sp=0x00007f3a1c0fe0a0,  free space=1016k
+Native frames: (J=compiled Java code, A=aot compiled Java code, j=interpreted, Vv=VM code, C=native code)
+C  [librocksdbjni123.so+0x4d5e6f]  rocksdb::DBImpl::Get+0x1f
+C  [librocksdbjni123.so+0x3a1b2c]  Java_org_rocksdb_RocksDB_get__J_3BII+0x5c
+j  org.rocksdb.RocksDB.get(J[BII)[B+0
+j  com.acme.cache.LocalStore.read(Ljava/lang/String;)[B+12
+
+siginfo: si_signo: 11 (SIGSEGV), si_code: 1 (SEGV_MAPERR), si_addr: 0x0000000000000008
+
+---------------  P R O C E S S  ---------------
+
+Java Threads: ( => current thread )
+  0x00007f3a40001000 JavaThread "main" [_thread_blocked, id=1235, stack(0x00007f3a48000000,0x00007f3a48100000)]
+=>0x00007f3a40012345 JavaThread "http-nio-8080-exec-3" daemon [_thread_in_native, id=5678, stack(0x00007f3a1c000000,0x00007f3a1c100000)]
+Total: 2
+
+Heap:
+ garbage-first heap   total 262144K, used 123456K [0x0000000700000000, 0x0000000800000000)
+  region size 1024K, 50 young (51200K), 2 survivors (2048K)
+ Metaspace       used 45678K, committed 46080K, reserved 1089536K
+
+GC Heap History (20 events):
+Event: 1.234 GC heap before
+
+Dynamic libraries:
+55d1c0000000-55d1c0001000 r--p 00000000 08:01 123 /opt/java/openjdk/bin/java
+7f3a2c000000-7f3a2d000000 r-xp 00000000 08:01 456 /tmp/librocksdbjni123.so
+7f3a30000000-7f3a31000000 r-xp 00000000 08:01 789 /opt/java/openjdk/lib/server/libjvm.so
+7f3a32000000-7f3a32100000 r-xp 00000000 08:01 790 /usr/lib/x86_64-linux-gnu/libc.so.6
+7f3a33000000-7f3a33100000 r-xp 00000000 08:01 791 /opt/java/openjdk/lib/libnio.so
+
+VM Arguments:
+jvm_args: -Xmx4g -XX:+UseG1GC
+java_command: com.acme.Application
+
+---------------  S Y S T E M  ---------------
+
+Memory: 4k page, physical 16384000k(123456k free), swap 0k(0k free)
+"#;
+
+    const NATIVE_OOM: &str = r#"#
+# There is insufficient memory for the Java Runtime Environment to continue.
+# Native memory allocation (mmap) failed to map 12288 bytes for committing reserved memory.
+# Possible reasons:
+#   The system is out of physical RAM or swap space
+# This output file may be truncated or incomplete.
+#
+#  Out of Memory Error (os_linux.cpp:2749), pid=4321, tid=4400
+#
+# JRE version: OpenJDK Runtime Environment (11.0.21+9) (build 11.0.21+9)
+# Java VM: OpenJDK 64-Bit Server VM (11.0.21+9, mixed mode, tiered, compressed oops, parallel gc, linux-amd64)
+#
+
+Memory: 4k page, physical 2048000k(10240k free), swap 0k(0k free)
+"#;
+
+    #[test]
+    fn test_parse_sigsegv_in_jni_library() {
+        let log = parse(SEGV_JNI).unwrap();
+        assert_eq!(log.kind, CrashKind::Signal);
+        assert_eq!(log.signal.as_deref(), Some("SIGSEGV"));
+        assert_eq!(log.pid, Some(1234));
+        assert_eq!(log.gc.as_deref(), Some("g1 gc"));
+        assert_eq!(log.problematic_frame.as_deref(), Some("C  [librocksdbjni123.so+0x4d5e6f]  rocksdb::DBImpl::Get+0x1f"));
+        assert_eq!(log.native_frames.len(), 4);
+        assert_eq!(log.java_threads, 2);
+        assert_eq!(log.heap.len(), 3);
+        assert_eq!(log.gc_events, Some(20));
+        assert_eq!(log.mappings, 5);
+        assert_eq!(log.third_party_libraries, vec!["/tmp/librocksdbjni123.so"]);
+        assert_eq!(log.jvm_args.as_deref(), Some("-Xmx4g -XX:+UseG1GC"));
+
+        let suspect = diagnose(&log);
+        assert_eq!(suspect.subsystem, "jni");
+        assert!(suspect.evidence.contains("第三方本地库 `librocksdbjni123.so`"));
+        assert_eq!(suspect.symptom, Some("resource"));
+        let sections: Vec<String> = checklist_sections("resource").into_iter().map(|s| s.section).collect();
+        assert_eq!(sections, vec!["4", "2"]);
+    }
+
+    #[test]
+    fn test_parse_native_oom() {
+        let log = parse(NATIVE_OOM).unwrap();
+        assert_eq!(log.kind, CrashKind::OutOfMemory);
+        assert_eq!(log.pid, Some(4321));
+        assert_eq!(log.reason, "Native memory allocation (mmap) failed to map 12288 bytes for committing reserved memory.");
+        assert_eq!(log.gc.as_deref(), Some("parallel gc"));
+        let suspect = diagnose(&log);
+        assert_eq!(suspect.subsystem, "native-memory");
+        assert!(suspect.evidence.contains("physical 2048000k"));
+    }
+
+    #[test]
+    fn test_diagnose_subsystems() {
+        let mut log = parse(SEGV_JNI).unwrap();
+        log.problematic_frame = Some("V  [libjvm.so+0x7a1b2c]  G1ParScanThreadState::trim_queue_partially()+0x2c".to_string());
+        log.current_thread = Some(r#"WorkerThread "GC Thread#3" [stack: 0x00007f3a1c000000,0x00007f3a1c100000] [id=5690]"#.to_string());
+        assert_eq!(diagnose(&log).subsystem, "gc");
+
+        log.problematic_frame = Some("J 4512 c2 com.acme.Pricing.compute(Ljava/util/List;)J (312 bytes) @ 0x00007f3a2d1e2f30".to_string());
+        assert_eq!(diagnose(&log).subsystem, "jit");
+
+        log.problematic_frame = Some("V  [libjvm.so+0x9e8f7a]  Unsafe_CopyMemory0+0x9a".to_string());
+        assert_eq!(diagnose(&log).subsystem, "unsafe");
+
+        let fatal = "#\n# A fatal error has been detected by the Java Runtime Environment:\n#\n\
+            #  Internal Error (debug.cpp:308), pid=77, tid=78\n#  fatal error: OutOfMemory encountered: Java heap space\n#\n";
+        let log = parse(fatal).unwrap();
+        assert_eq!(log.kind, CrashKind::OutOfMemory);
+        assert_eq!(log.pid, Some(77));
+        assert_eq!(diagnose(&log).subsystem, "heap");
+
+        assert!(parse("2026-10-17 INFO Started OrderApplication").is_none());
+    }
+
+    #[test]
+    fn test_analyze_crash_report_and_heap_dump_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let hs_err = dir.path().join("hs_err_pid1234.log");
+        std::fs::write(&hs_err, SEGV_JNI).unwrap();
+        let result = analyze_crash(hs_err.to_str().unwrap()).unwrap();
+        assert_eq!(result["suspect"]["subsystem"], "jni");
+        assert_eq!(result["checklist"][0]["section"], "4");
+        let report = result["report"].as_str().unwrap();
+        assert!(report.contains("### 🎯 疑似子系统: JNI 本地库 (`jni`)"));
+        assert!(report.contains("- `/tmp/librocksdbjni123.so`"));
+        assert!(report.contains("**收集器**: g1 gc | **GC 事件**: 20"));
+
+        let hprof = dir.path().join("java_pid1234.hprof");
+        let mut file = std::fs::File::create(&hprof).unwrap();
+        file.write_all(b"JAVA PROFILE 1.0.2\0").unwrap();
+        file.write_all(&8u32.to_be_bytes()).unwrap();
+        file.write_all(&1_792_195_200_000u64.to_be_bytes()).unwrap();
+        drop(file);
+        let result = analyze_crash(hprof.to_str().unwrap()).unwrap();
+        assert_eq!(result["heap_dump"]["id_size"], 8);
+        assert!(result["report"].as_str().unwrap().contains("2026-10-17 00:00:00 UTC"));
+
+        let other = dir.path().join("app.log");
+        std::fs::write(&other, "INFO ok\n").unwrap();
+        assert!(analyze_crash(other.to_str().unwrap()).is_err());
+    }
+}
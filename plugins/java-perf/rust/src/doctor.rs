@@ -0,0 +1,236 @@
+//! 环境自检 (Doctor) - v9.6
+//!
+//! `java-perf doctor` 在使用取证/扫描命令之前检查运行环境，每项给出结论与修复建议，
+//! 避免问题只在 `jstack`/`jmap` 调用深处才暴露：
+//! - JAVA_HOME 是否指向完整 JDK (而非 JRE)
+//! - JDK 工具 jstack / jmap / javap / jcmd / jfr 是否可用
+//! - 本机 JVM 进程是否可 attach (同用户、hsperfdata 可见)
+//! - 缓存目录 (`<path>/.java-perf`) 是否可写
+//! - tree-sitter 运行时与 Java 语法版本是否兼容、规则查询能否编译
+//!
+//! 存在 `fail` 项时 CLI 以退出码 1 结束。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+
+use crate::jdk_engine;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+
+/// 取证依赖的 JDK 工具
+const JDK_TOOLS: &[&str] = &["jstack", "jmap", "javap", "jcmd", "jfr"];
+
+/// 检查结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CheckStatus {
+    Ok,
+    Warn,
+    Fail,
+}
+
+/// 单项检查结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Check {
+    pub name: String,
+    pub status: CheckStatus,
+    pub detail: String,
+    /// 修复建议 (`ok` 时为空)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remedy: Option<String>,
+}
+
+impl Check {
+    fn ok(name: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Ok, detail: detail.into(), remedy: None }
+    }
+
+    fn warn(name: impl Into<String>, detail: impl Into<String>, remedy: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Warn, detail: detail.into(), remedy: Some(remedy.into()) }
+    }
+
+    fn fail(name: impl Into<String>, detail: impl Into<String>, remedy: impl Into<String>) -> Self {
+        Self { name: name.into(), status: CheckStatus::Fail, detail: detail.into(), remedy: Some(remedy.into()) }
+    }
+}
+
+/// 运行全部检查，返回 `checks`、`failed` 与 Markdown `report`
+pub fn run_doctor(path: &str) -> Value {
+    let mut checks = vec![check_java_home()];
+    checks.extend(JDK_TOOLS.iter().map(|tool| check_jdk_tool(tool)));
+    checks.push(check_attach());
+    checks.push(check_cache_dir(Path::new(path)));
+    checks.extend(check_tree_sitter());
+
+    let failed = checks.iter().filter(|c| c.status == CheckStatus::Fail).count();
+    json!({
+        "checks": checks,
+        "failed": failed,
+        "report": render_markdown(&checks),
+    })
+}
+
+fn check_java_home() -> Check {
+    const NAME: &str = "JAVA_HOME";
+    let Ok(home) = std::env::var("JAVA_HOME") else {
+        return Check::warn(NAME, "未设置，从 $PATH 查找 JDK 工具",
+            "export JAVA_HOME=<JDK 安装目录> (与目标应用使用同一 JDK，避免 attach 版本不匹配)");
+    };
+    let home = Path::new(&home);
+    if !home.is_dir() {
+        return Check::fail(NAME, format!("{} 不存在", home.display()), "将 JAVA_HOME 指向实际存在的 JDK 安装目录");
+    }
+    if !home.join("bin").join(exe("java")).exists() {
+        return Check::fail(NAME, format!("{} 下没有 bin/java", home.display()),
+            "JAVA_HOME 应为 JDK 根目录 (包含 bin/)，而不是 bin/ 本身或其上级目录");
+    }
+    let version = java_release_version(home);
+    if !home.join("bin").join(exe("jcmd")).exists() {
+        return Check::fail(NAME, format!("{} 是 JRE (缺少 jcmd)", home.display()),
+            "安装完整 JDK (如 openjdk-17-jdk / temurin-17-jdk)，JRE 不包含取证工具");
+    }
+    Check::ok(NAME, match version {
+        Some(v) => format!("{} (Java {v})", home.display()),
+        None => home.display().to_string(),
+    })
+}
+
+/// `$JAVA_HOME/release` 中的 JAVA_VERSION
+fn java_release_version(home: &Path) -> Option<String> {
+    let release = std::fs::read_to_string(home.join("release")).ok()?;
+    release.lines()
+        .find_map(|l| l.strip_prefix("JAVA_VERSION="))
+        .map(|v| v.trim_matches('"').to_string())
+}
+
+fn check_jdk_tool(tool: &str) -> Check {
+    match jdk_engine::get_jdk_tool(tool) {
+        Some(path) => Check::ok(tool, path),
+        None if tool == "jfr" => Check::warn(tool, "未找到",
+            "jfr 命令需要 JDK 11+；JDK 8 请使用 jcmd <pid> JFR.start / JFR.dump"),
+        None => Check::fail(tool, "未找到 (JAVA_HOME/bin 与 $PATH)",
+            format!("安装 JDK 并设置 JAVA_HOME，或把 {tool} 所在目录加入 $PATH")),
+    }
+}
+
+/// attach 检查: HotSpot 通过临时目录下的 `hsperfdata_<user>` 发现 JVM，只能 attach 同一用户的进程
+fn check_attach() -> Check {
+    const NAME: &str = "JVM attach";
+    let user = std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_default();
+    let mut own = 0;
+    let mut others: Vec<String> = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(std::env::temp_dir()) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let Some(owner) = name.strip_prefix("hsperfdata_") else { continue };
+            let pids = std::fs::read_dir(entry.path()).map(|d| d.count()).unwrap_or(0);
+            if owner == user {
+                own += pids;
+            } else if pids > 0 {
+                others.push(format!("{owner} ({pids})"));
+            }
+        }
+    }
+
+    if !others.is_empty() {
+        return Check::warn(NAME, format!("当前用户 {own} 个 JVM；其它用户的 JVM: {}", others.join(", ")),
+            "attach 只能作用于同一用户的 JVM：以目标进程用户运行 (sudo -u <user> java-perf ...)；容器中需共享 /tmp");
+    }
+    if own > 0 {
+        return Check::ok(NAME, format!("当前用户 {own} 个可 attach 的 JVM"));
+    }
+    Check::warn(NAME, "未发现本机 JVM (hsperfdata 为空)",
+        "目标 JVM 未运行、在其它容器中，或启用了 -XX:+PerfDisableSharedMem / -XX:-UsePerfData；可改用 jcmd <pid> 显式指定")
+}
+
+/// 缓存目录: `.java-perf/index.bin` 等写入扫描根目录下的 `.java-perf`
+fn check_cache_dir(root: &Path) -> Check {
+    const NAME: &str = "cache dir";
+    let dir = root.join(".java-perf");
+    let probe_dir = if dir.is_dir() { dir.clone() } else { root.to_path_buf() };
+    let probe = probe_dir.join(format!(".java-perf-doctor-{}", std::process::id()));
+    match std::fs::write(&probe, b"") {
+        Ok(()) => {
+            let _ = std::fs::remove_file(&probe);
+            Check::ok(NAME, format!("{} 可写", dir.display()))
+        }
+        Err(e) => Check::fail(NAME, format!("{} 不可写: {e}", probe_dir.display()),
+            "为当前用户授予写权限，或在可写的副本目录中运行 index/scan (只读挂载的 CI 工作区常见)"),
+    }
+}
+
+/// tree-sitter ABI 兼容性 + 规则查询编译
+fn check_tree_sitter() -> Vec<Check> {
+    let grammar = tree_sitter_java::language().version();
+    let runtime = format!("ABI {}..={}", tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION, tree_sitter::LANGUAGE_VERSION);
+    let grammar_check = if (tree_sitter::MIN_COMPATIBLE_LANGUAGE_VERSION..=tree_sitter::LANGUAGE_VERSION).contains(&grammar) {
+        Check::ok("tree-sitter", format!("Java 语法 ABI {grammar}，运行时 {runtime}"))
+    } else {
+        Check::fail("tree-sitter", format!("Java 语法 ABI {grammar} 不在运行时 {runtime} 范围内"),
+            "tree-sitter 与 tree-sitter-java 版本不匹配：按 Cargo.lock 重新构建 (cargo build --locked)")
+    };
+
+    let rules_check = match JavaTreeSitterAnalyzer::new() {
+        Ok(analyzer) => Check::ok("rules", format!("{} 条规则查询编译通过", analyzer.rule_ids().len())),
+        Err(e) => Check::fail("rules", format!("规则查询编译失败: {e}"),
+            "查询与语法节点不匹配，通常由升级 tree-sitter-java 引起：回退语法版本或更新 resources/queries"),
+    };
+    vec![grammar_check, rules_check]
+}
+
+fn exe(name: &str) -> String {
+    format!("{name}{}", std::env::consts::EXE_SUFFIX)
+}
+
+fn render_markdown(checks: &[Check]) -> String {
+    let mut out = String::from("## 🩺 Doctor\n\n| 检查项 | 结论 | 详情 |\n|--------|------|------|\n");
+    for check in checks {
+        let icon = match check.status {
+            CheckStatus::Ok => "✅",
+            CheckStatus::Warn => "⚠️",
+            CheckStatus::Fail => "❌",
+        };
+        out.push_str(&format!("| {} | {icon} | {} |\n", check.name, check.detail));
+    }
+
+    let remedies: Vec<&Check> = checks.iter().filter(|c| c.remedy.is_some()).collect();
+    if remedies.is_empty() {
+        out.push_str("\n✅ 环境就绪\n");
+    } else {
+        out.push_str("\n### 🔧 修复建议\n\n");
+        for check in remedies {
+            out.push_str(&format!("- **{}**: {}\n", check.name, check.remedy.as_deref().unwrap_or_default()));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tree_sitter_and_rules_pass() {
+        let checks = check_tree_sitter();
+        assert!(checks.iter().all(|c| c.status == CheckStatus::Ok), "{checks:?}");
+    }
+
+    #[test]
+    fn test_cache_dir_writable() {
+        let dir = tempfile::tempdir().unwrap();
+        assert_eq!(check_cache_dir(dir.path()).status, CheckStatus::Ok);
+        assert_eq!(check_cache_dir(&dir.path().join("missing")).status, CheckStatus::Fail);
+        // 探测文件不残留
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_report_lists_remedies() {
+        let report = render_markdown(&[
+            Check::ok("javap", "/usr/bin/javap"),
+            Check::fail("jstack", "未找到", "安装 JDK"),
+        ]);
+        assert!(report.contains("| jstack | ❌ | 未找到 |"));
+        assert!(report.contains("- **jstack**: 安装 JDK"));
+    }
+}
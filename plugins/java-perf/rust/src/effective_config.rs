@@ -0,0 +1,288 @@
+//! Spring Profile 生效配置 (Effective Config) - v9.6
+//!
+//! 按 Spring Boot 的配置加载顺序计算指定 profile 下每个配置项的生效值，并记录其来源 `文件:行`：
+//! - 同一目录的 `application*` 与其下 `config/application*` 为一组 (多模块项目各自独立计算)
+//! - 优先级从低到高: 通用文件 < profile 文件 (多个 profile 时靠后的优先)；同类文件中 `config/` 高于根目录，
+//!   同一目录中 `.properties` 高于 `.yml`；同一文件中靠后的文档覆盖靠前的
+//! - 多文档 YAML (`---`) 与 `.properties` (`#---`) 中声明 `spring.config.activate.on-profile`
+//!   (旧写法 `spring.profiles`) 的文档只在 profile 匹配时生效，支持 `prod,aws` 与 `!prod`
+//! - key 按 Spring 宽松绑定归一
+//!
+//! `scan --spring-profile prod` 时配置规则改为在生效配置上执行 (被覆盖的值不再报告)，见 [`apply`]；
+//! `effective-config --profile prod` 输出生效配置及来源。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::ast_engine::{self, AstIssue};
+use crate::i18n;
+use crate::scanner::config::{self, normalize_key, LineBasedConfigAnalyzer, PlaceholderEnv};
+
+/// 声明文档生效 profile 的配置项
+const ACTIVATION_KEYS: &[&str] = &["spring.config.activate.on-profile", "spring.profiles"];
+
+/// 一个配置项的生效值
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EffectiveValue {
+    pub value: String,
+    /// 相对扫描根目录的路径
+    pub file: String,
+    pub line: usize,
+    /// 被覆盖的定义 (`文件:行`，按加载顺序)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub overridden: Vec<String>,
+}
+
+/// 一组配置 (一个目录及其 `config/` 子目录)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ConfigSet {
+    pub dir: String,
+    pub values: BTreeMap<String, EffectiveValue>,
+}
+
+/// 参与计算的一个配置文件
+struct Source<'a> {
+    rel: &'a str,
+    content: &'a str,
+    dir: String,
+    /// (profile 序号 (通用文件为 0), `config/` 目录, `.properties`)
+    rank: (usize, bool, bool),
+}
+
+/// 计算项目中各组配置在 `profiles` 下的生效值 (按目录排序)
+pub fn effective_config(root: &Path, profiles: &[String]) -> Vec<ConfigSet> {
+    compute(&config::spring_config_contents(root), profiles)
+}
+
+fn compute(files: &[(String, String)], profiles: &[String]) -> Vec<ConfigSet> {
+    let mut sources: Vec<Source> = files.iter().filter_map(|(rel, content)| source(rel, content, profiles)).collect();
+    sources.sort_by(|a, b| (&a.dir, a.rank).cmp(&(&b.dir, b.rank)));
+
+    let mut sets: BTreeMap<String, ConfigSet> = BTreeMap::new();
+    for source in &sources {
+        let set = sets.entry(source.dir.clone()).or_insert_with(|| ConfigSet { dir: source.dir.clone(), ..Default::default() });
+        let yaml = !source.rel.ends_with(".properties");
+        for document in documents(source.content, yaml) {
+            if !document.active(profiles) {
+                continue;
+            }
+            for (key, value, line) in document.entries {
+                let overridden = match set.values.remove(&key) {
+                    Some(previous) => {
+                        let mut overridden = previous.overridden;
+                        overridden.push(format!("{}:{}", previous.file, previous.line));
+                        overridden
+                    }
+                    None => Vec::new(),
+                };
+                set.values.insert(key, EffectiveValue { value, file: source.rel.to_string(), line, overridden });
+            }
+        }
+    }
+    sets.into_values().collect()
+}
+
+/// `application[-profile].{yml,yaml,properties}` 且 profile 处于激活状态时参与计算
+fn source<'a>(rel: &'a str, content: &'a str, profiles: &[String]) -> Option<Source<'a>> {
+    let (parent, name) = rel.rsplit_once('/').unwrap_or(("", rel));
+    let (stem, ext) = name.rsplit_once('.')?;
+    let profile_rank = match stem.strip_prefix("application") {
+        Some("") => 0,
+        Some(rest) => profiles.iter().position(|p| rest.strip_prefix('-') == Some(p.as_str()))? + 1,
+        None => return None,
+    };
+    let (dir, in_config_dir) = match parent.rsplit_once('/') {
+        Some((dir, "config")) => (dir.to_string(), true),
+        _ if parent == "config" => (String::new(), true),
+        _ => (parent.to_string(), false),
+    };
+    Some(Source { rel, content, dir, rank: (profile_rank, in_config_dir, ext == "properties") })
+}
+
+/// 文件中的一个文档
+struct Document {
+    /// 归一化 key, 值, 行号
+    entries: Vec<(String, String, usize)>,
+}
+
+impl Document {
+    fn active(&self, profiles: &[String]) -> bool {
+        let Some((_, expr, _)) = self.entries.iter().find(|(key, _, _)| ACTIVATION_KEYS.contains(&key.as_str())) else {
+            return true;
+        };
+        expr.split([',', '|']).map(str::trim).filter(|term| !term.is_empty()).any(|term| match term.strip_prefix('!') {
+            Some(negated) => !profiles.iter().any(|p| p == negated.trim()),
+            None => profiles.iter().any(|p| p == term),
+        })
+    }
+}
+
+/// 按 `---` (YAML) / `#---` (properties) 拆分文档，行号相对整个文件
+fn documents(content: &str, yaml: bool) -> Vec<Document> {
+    let separator = if yaml { "---" } else { "#---" };
+    let mut documents = Vec::new();
+    let mut start = 0;
+    let lines: Vec<&str> = content.lines().collect();
+    for end in (0..lines.len()).filter(|&idx| lines[idx].trim() == separator).chain([lines.len()]) {
+        let entries = config::property_entries(&lines[start..end].join("\n"), yaml)
+            .into_iter()
+            .map(|entry| (normalize_key(&entry.key), entry.value, entry.line + start))
+            .collect();
+        documents.push(Document { entries });
+        start = end + 1;
+    }
+    documents
+}
+
+/// 配置规则改为在生效配置上执行: 移除 `application*` 文件上的逐文件配置问题，按生效值重新检查
+///
+/// 新问题定位到生效值所在的文件与行，描述注明 profile。
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path, profiles: &[String], env: PlaceholderEnv) {
+    let Ok(analyzer) = LineBasedConfigAnalyzer::with_env(env) else { return };
+    let files = config::spring_config_contents(root);
+    issues.retain(|issue| {
+        let name = issue.file.rsplit('/').next().unwrap_or(&issue.file);
+        !(name.starts_with("application") && analyzer.covers(&issue.issue_type))
+    });
+
+    let contents: HashMap<&str, &str> = files.iter().map(|(rel, content)| (rel.as_str(), content.as_str())).collect();
+    let profile_label = profiles.join(",");
+    for set in compute(&files, profiles) {
+        for (key, effective) in &set.values {
+            let context = contents
+                .get(effective.file.as_str())
+                .and_then(|content| content.lines().nth(effective.line.saturating_sub(1)))
+                .unwrap_or_default();
+            for issue in analyzer.check_property(key, &effective.value, &effective.file, effective.line, context) {
+                let mut issue = ast_engine::convert_issue(issue);
+                issue.description = format!("{} [profile: {profile_label}]", issue.description);
+                issues.push(issue);
+            }
+        }
+    }
+}
+
+/// `effective-config` 命令: 输出生效配置及来源，`key` 过滤包含该字符串的配置项
+pub fn report(root: &str, profiles: &[String], key: Option<&str>) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = Path::new(root);
+    if !path.is_dir() {
+        return Err(format!("{root}: not a directory").into());
+    }
+    let mut sets = effective_config(path, profiles);
+    if let Some(filter) = key.map(normalize_key) {
+        for set in &mut sets {
+            set.values.retain(|key, _| key.contains(&filter));
+        }
+    }
+    let report = render_markdown(&sets, profiles);
+    Ok(json!({
+        "profiles": profiles,
+        "sets": sets,
+        "report": report,
+    }))
+}
+
+fn render_markdown(sets: &[ConfigSet], profiles: &[String]) -> String {
+    let label = if profiles.is_empty() { "default".to_string() } else { profiles.join(",") };
+    let mut out = i18n::format("report.effective_config.title", "## 🧩 生效配置 (profile: {profile})", &[("profile", &label)]);
+    out.push_str("\n\n");
+    if sets.iter().all(|set| set.values.is_empty()) {
+        out.push_str(i18n::text("report.effective_config.empty", "未找到 application* 配置"));
+        out.push('\n');
+        return out;
+    }
+    for set in sets.iter().filter(|set| !set.values.is_empty()) {
+        let dir = if set.dir.is_empty() { "." } else { &set.dir };
+        out.push_str(&format!("### {dir}\n\n"));
+        out.push_str(i18n::text("report.effective_config.columns", "| 配置项 | 生效值 | 来源 | 覆盖 |"));
+        out.push_str("\n|--------|--------|------|------|\n");
+        for (key, effective) in &set.values {
+            out.push_str(&format!(
+                "| `{key}` | `{}` | {}:{} | {} |\n",
+                effective.value, effective.file, effective.line, effective.overridden.join(", ")
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn files() -> Vec<(String, String)> {
+        vec![
+            (
+                "src/main/resources/application.yml".to_string(),
+                "spring:\n  datasource:\n    hikari:\n      maximumPoolSize: 20\n  jpa:\n    open-in-view: false\n---\nspring:\n  config:\n    activate:\n      on-profile: prod\n  jpa:\n    show-sql: true\n".to_string(),
+            ),
+            ("src/main/resources/application.properties".to_string(), "spring.jpa.open-in-view=true\n".to_string()),
+            ("src/main/resources/application-prod.yml".to_string(), "spring:\n  datasource:\n    hikari:\n      maximum-pool-size: 3\n".to_string()),
+            ("src/main/resources/config/application.yml".to_string(), "server:\n  tomcat:\n    max-threads: 400\n".to_string()),
+            ("src/main/resources/application-dev.yml".to_string(), "server:\n  tomcat:\n    max-threads: 10\n".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_effective_config_precedence() {
+        let sets = compute(&files(), &["prod".to_string()]);
+        assert_eq!(sets.len(), 1);
+        assert_eq!(sets[0].dir, "src/main/resources");
+        let values = &sets[0].values;
+
+        let pool = &values["spring.datasource.hikari.maximum-pool-size"];
+        assert_eq!((pool.value.as_str(), pool.file.as_str(), pool.line), ("3", "src/main/resources/application-prod.yml", 4));
+        assert_eq!(pool.overridden, vec!["src/main/resources/application.yml:4"]);
+        // 同目录 .properties 高于 .yml
+        assert_eq!(values["spring.jpa.open-in-view"].file, "src/main/resources/application.properties");
+        // on-profile 文档只在 prod 下生效，行号相对整个文件
+        assert_eq!(values["spring.jpa.show-sql"].line, 13);
+        assert_eq!(values["server.tomcat.max-threads"].value, "400");
+
+        let dev = compute(&files(), &["dev".to_string()]);
+        assert_eq!(dev[0].values["server.tomcat.max-threads"].value, "10");
+        assert_eq!(dev[0].values["spring.datasource.hikari.maximum-pool-size"].value, "20");
+        assert!(!dev[0].values.contains_key("spring.jpa.show-sql"));
+    }
+
+    #[test]
+    fn test_apply_checks_effective_values() {
+        let dir = std::env::temp_dir().join(format!("java-perf-effective-config-{}", std::process::id()));
+        for (rel, content) in files() {
+            let path = dir.join(rel);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let stale = AstIssue {
+            severity: Severity::P1,
+            issue_type: "TOMCAT_THREADS_LOW".to_string(),
+            file: "src/main/resources/application-dev.yml".to_string(),
+            line: 0,
+            column: 0,
+            end_line: 0,
+            end_column: 0,
+            description: String::new(),
+            owner: None,
+            effort: Default::default(),
+        };
+        let mut issues = vec![stale];
+        apply(&mut issues, &dir, &["prod".to_string()], PlaceholderEnv::default());
+        let found: Vec<(&str, &str, usize)> = issues.iter().map(|i| (i.issue_type.as_str(), i.file.as_str(), i.line)).collect();
+        assert_eq!(found, vec![
+            ("DB_POOL_SMALL", "src/main/resources/application-prod.yml", 4),
+            ("JPA_OPEN_IN_VIEW", "src/main/resources/application.properties", 1),
+            ("JPA_SHOW_SQL_PROD", "src/main/resources/application.yml", 13),
+        ]);
+        assert!(issues[0].description.ends_with("[profile: prod]"));
+
+        let report = report(dir.to_str().unwrap(), &["prod".to_string()], Some("maximumPoolSize")).unwrap();
+        let text = report["report"].as_str().unwrap();
+        assert!(text.contains("| `spring.datasource.hikari.maximum-pool-size` | `3` | src/main/resources/application-prod.yml:4 | src/main/resources/application.yml:4 |"), "{text}");
+        assert!(!text.contains("max-threads"));
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -1,22 +1,63 @@
 //! Forensic 模块 - 日志指纹归类分析
 //! 
 //! 🔬 法医取证：流式处理大日志
+//!
+//! v9.6: 同一次读取中识别类加载/Metaspace 记录，见 [`class_loading`]；启动耗时记录，见 [`startup`]
+//!
+//! v9.6: [`follow_log`] 跟踪正在写入的日志，报告时间窗口内的指纹与基线的差异
+//!
+//! v9.6: 识别 Trace ID (见 [`log_timeline::extract_trace`])，报告中列出异常所在的请求，
+//! 供 `log --files ... --trace ID` 还原跨服务轨迹
 
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::Path;
 use std::time::{Duration, Instant};
 use regex::Regex;
 
+use crate::class_loading::ClassLoadStats;
+use crate::log_timeline;
+use crate::startup::StartupStats;
+
 /// 安全限制
 #[allow(dead_code)]
 const MAX_MEMORY_MB: usize = 1024;
 const MS_PER_MB: u64 = 100;
 const MIN_PROCESS_TIME_MS: u64 = 30000;
 
+const MIB: u64 = 1024 * 1024;
+
+/// 统计的不同 Trace ID 上限 (超过后只计记录数)
+const MAX_TRACE_IDS: usize = 100_000;
+/// 报告中列出的异常请求数
+const FAILING_TRACES: usize = 5;
+
+/// 分层采样: 超过阈值的日志只读取头部、尾部与均匀分布的中间段，
+/// 中间段的次数按 (中间区间大小 / 中间样本大小) 加权估算总数
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SamplePlan {
+    /// 超过该大小才采样
+    pub threshold: u64,
+    pub head: u64,
+    pub tail: u64,
+    /// 中间段数量与每段大小
+    pub blocks: u64,
+    pub block: u64,
+}
+
+impl Default for SamplePlan {
+    fn default() -> Self {
+        SamplePlan { threshold: 512 * MIB, head: 32 * MIB, tail: 32 * MIB, blocks: 64, block: 2 * MIB }
+    }
+}
+
+/// follow 模式轮询间隔与时长上限
+const FOLLOW_POLL: Duration = Duration::from_millis(500);
+pub const MAX_FOLLOW: Duration = Duration::from_secs(600);
+
 /// 静态编译的正则表达式
 static EXCEPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(\w+Exception|\w+Error)").unwrap()
@@ -36,134 +77,339 @@ struct ExceptionFingerprint {
     example: String,
 }
 
-/// 分析日志文件
-pub fn analyze_log(log_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
-    let path = Path::new(log_path);
-    if !path.exists() {
-        return Err(format!("Log file not found: {log_path}").into());
+/// 流式统计: 异常指纹 + 类加载 + 启动耗时 (全量分析与 follow 模式共用)
+#[derive(Debug, Default)]
+struct LogStats {
+    exceptions: HashMap<String, ExceptionFingerprint>,
+    lines: usize,
+    class_loading: ClassLoadStats,
+    startup: StartupStats,
+    traces: TraceStats,
+}
+
+/// Trace ID 统计: 带 Trace ID 的记录数、不同请求数、异常所在的请求
+#[derive(Debug, Default)]
+struct TraceStats {
+    records: usize,
+    ids: HashSet<String>,
+    /// 当前记录的 Trace ID (堆栈行沿用所在记录的)
+    current: Option<String>,
+    /// (Trace ID, 异常指纹)
+    failing: Vec<(String, String)>,
+}
+
+impl TraceStats {
+    fn observe(&mut self, line: &str) {
+        // 以日期/方括号开头的行是新记录的首行，其余 (堆栈、续行) 属于上一条记录
+        if !line.starts_with(|c: char| c.is_ascii_digit() || c == '[') {
+            return;
+        }
+        self.current = log_timeline::extract_trace(line).map(|t| t.trace_id);
+        if let Some(id) = &self.current {
+            self.records += 1;
+            if self.ids.len() < MAX_TRACE_IDS {
+                self.ids.insert(id.clone());
+            }
+        }
     }
-    
+
+    fn on_exception(&mut self, fingerprint: &str) {
+        let Some(id) = &self.current else { return };
+        if self.failing.len() < FAILING_TRACES && !self.failing.iter().any(|(t, _)| t == id) {
+            self.failing.push((id.clone(), fingerprint.to_string()));
+        }
+    }
+
+    fn render(&self) -> String {
+        let capped = if self.ids.len() >= MAX_TRACE_IDS { "≥ " } else { "" };
+        let mut out = format!(
+            "\n## 🧵 Trace ID\n\n识别到 {} 条带 Trace ID 的记录 ({capped}{} 个请求)\n",
+            self.records, self.ids.len()
+        );
+        if !self.failing.is_empty() {
+            out.push_str(
+                "\n异常所在的请求 (`log --files <各服务日志> --trace <ID>` 还原跨服务轨迹与最慢间隔)：\n\n\
+                | Trace ID | 异常 |\n|------|------|\n"
+            );
+            for (id, fingerprint) in &self.failing {
+                out.push_str(&format!("| `{id}` | `{fingerprint}` |\n"));
+            }
+        }
+        out
+    }
+}
+
+impl LogStats {
+    /// 处理一行，返回该行的异常指纹及是否首次出现
+    fn observe(&mut self, line: &str) -> Option<(String, bool)> {
+        self.lines += 1;
+        self.traces.observe(line);
+        if self.class_loading.observe(line) || self.startup.observe(line) {
+            return None;
+        }
+
+        // 提取异常 (使用静态编译的正则)
+        let ex_type = EXCEPTION_REGEX.find(line)?.as_str().to_string();
+
+        // 提取位置
+        let location = LOCATION_REGEX.find(line)
+            .map(|m| {
+                let parts: Vec<&str> = m.as_str().split('.').collect();
+                if parts.len() >= 2 {
+                    format!("{}.{}", parts[parts.len()-2], parts[parts.len()-1])
+                } else {
+                    m.as_str().to_string()
+                }
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let fingerprint = format!("{ex_type}@{location}");
+        let mut is_new = false;
+        let entry = self.exceptions.entry(fingerprint.clone()).or_insert_with(|| {
+            is_new = true;
+            ExceptionFingerprint {
+                exception_type: ex_type,
+                location,
+                count: 0,
+                example: line.chars().take(150).collect(),
+            }
+        });
+        entry.count += 1;
+        self.traces.on_exception(&fingerprint);
+        Some((fingerprint, is_new))
+    }
+
+    /// 按次数降序
+    fn sorted(&self) -> Vec<&ExceptionFingerprint> {
+        let mut fingerprints: Vec<_> = self.exceptions.values().collect();
+        fingerprints.sort_by_key(|f| std::cmp::Reverse(f.count));
+        fingerprints
+    }
+
+    fn count(&self, fingerprint: &ExceptionFingerprint) -> usize {
+        self.exceptions.get(&fingerprint.key()).map_or(0, |f| f.count)
+    }
+}
+
+impl ExceptionFingerprint {
+    fn key(&self) -> String {
+        format!("{}@{}", self.exception_type, self.location)
+    }
+}
+
+/// 采样结果: 全部样本的统计 + 中间段单独计数 (用于加权估算)
+#[derive(Debug, Default)]
+struct SampledLog {
+    stats: LogStats,
+    /// 中间段内各指纹次数与行数
+    middle: HashMap<String, usize>,
+    middle_lines: usize,
+    /// 中间区间总大小 / 实际读取的中间样本大小
+    middle_bytes: u64,
+    middle_sampled: u64,
+    sampled_bytes: u64,
+}
+
+impl SampledLog {
+    /// 中间段放大倍数
+    fn scale(&self) -> f64 {
+        if self.middle_sampled == 0 {
+            1.0
+        } else {
+            self.middle_bytes as f64 / self.middle_sampled as f64
+        }
+    }
+
+    /// 头尾按实际次数，中间段按放大倍数加权
+    fn estimate(&self, sampled: usize, middle: usize) -> usize {
+        sampled - middle + (middle as f64 * self.scale()).round() as usize
+    }
+
+    fn estimate_count(&self, fingerprint: &ExceptionFingerprint) -> usize {
+        self.estimate(fingerprint.count, self.middle.get(&fingerprint.key()).copied().unwrap_or(0))
+    }
+}
+
+/// 读取 `[start, end)` 区间内开始的完整行 (跳过 start 处的半行)，返回读取的字节数
+fn observe_region(
+    file: &File,
+    start: u64,
+    end: u64,
+    mut on_line: impl FnMut(&str),
+) -> std::io::Result<u64> {
+    let mut reader = BufReader::new(file);
+    reader.seek(SeekFrom::Start(start))?;
+    let mut position = start;
+    let mut buf = Vec::new();
+    if start > 0 {
+        position += reader.read_until(b'\n', &mut buf)? as u64;
+    }
+    let mut consumed = 0;
+    while position < end {
+        buf.clear();
+        let n = reader.read_until(b'\n', &mut buf)? as u64;
+        if n == 0 {
+            break;
+        }
+        position += n;
+        consumed += n;
+        on_line(String::from_utf8_lossy(&buf).trim_end_matches(['\r', '\n']));
+    }
+    Ok(consumed)
+}
+
+/// 分层采样: 头部 + 尾部 + 中间均匀分布的若干段
+fn sample_log(path: &Path, size: u64, plan: &SamplePlan) -> std::io::Result<SampledLog> {
     let file = File::open(path)?;
-    let file_size = file.metadata()?.len();
-    let reader = BufReader::new(file);
-    
-    // 动态超时
-    let file_size_mb = file_size / (1024 * 1024);
-    let timeout = Duration::from_millis(
-        std::cmp::max(MIN_PROCESS_TIME_MS, file_size_mb * MS_PER_MB)
-    );
-    
+    let mut sampled = SampledLog::default();
+    let tail_start = size.saturating_sub(plan.tail).max(plan.head);
+
+    let head = observe_region(&file, 0, plan.head, |line| {
+        sampled.stats.observe(line);
+    })?;
+
+    sampled.middle_bytes = tail_start.saturating_sub(plan.head);
+    let stride = (sampled.middle_bytes / plan.blocks.max(1)).max(1);
+    let mut middle = 0;
+    for i in 0..plan.blocks {
+        let start = plan.head + i * stride;
+        if start >= tail_start {
+            break;
+        }
+        let end = (start + plan.block.min(stride)).min(tail_start);
+        middle += observe_region(&file, start, end, |line| {
+            sampled.middle_lines += 1;
+            if let Some((fingerprint, _)) = sampled.stats.observe(line) {
+                *sampled.middle.entry(fingerprint).or_default() += 1;
+            }
+        })?;
+    }
+    sampled.middle_sampled = middle;
+
+    let tail = observe_region(&file, tail_start, size, |line| {
+        sampled.stats.observe(line);
+    })?;
+    sampled.sampled_bytes = head + middle + tail;
+    Ok(sampled)
+}
+
+/// 流式读取并统计，超时或指纹过多时熔断，返回熔断原因
+fn observe_lines(reader: impl BufRead, stats: &mut LogStats, timeout: Duration, file_size_mb: u64) -> Option<String> {
     let start_time = Instant::now();
-    let mut exception_map: HashMap<String, ExceptionFingerprint> = HashMap::new();
-    let mut lines_processed: usize = 0;
-    let mut truncated = false;
-    let mut truncate_reason = String::new();
-    
-    // 流式读取
     for line_result in reader.lines() {
         // 熔断检查：时间
         if start_time.elapsed() > timeout {
-            truncated = true;
-            truncate_reason = format!(
+            return Some(format!(
                 "⚠️ 分析超时 (>{}s for {}MB)，已自动终止",
                 timeout.as_secs(), file_size_mb
-            );
-            break;
+            ));
         }
-        
+
         // 熔断检查：行数（防止内存过大）
-        if exception_map.len() > 1000 {
-            truncated = true;
-            truncate_reason = "⚠️ 异常类型过多 (>1000 种)，已自动终止".to_string();
-            break;
+        if stats.exceptions.len() > 1000 {
+            return Some("⚠️ 异常类型过多 (>1000 种)，已自动终止".to_string());
         }
-        
+
         if let Ok(line) = line_result {
-            lines_processed += 1;
-            
-            // 提取异常 (使用静态编译的正则)
-            if let Some(ex_match) = EXCEPTION_REGEX.find(&line) {
-                let ex_type = ex_match.as_str().to_string();
-                
-                // 提取位置
-                let location = LOCATION_REGEX.find(&line)
-                    .map(|m| {
-                        let parts: Vec<&str> = m.as_str().split('.').collect();
-                        if parts.len() >= 2 {
-                            format!("{}.{}", parts[parts.len()-2], parts[parts.len()-1])
-                        } else {
-                            m.as_str().to_string()
-                        }
-                    })
-                    .unwrap_or_else(|| "Unknown".to_string());
-                
-                let fingerprint = format!("{ex_type}@{location}");
-                
-                let entry = exception_map.entry(fingerprint.clone()).or_insert_with(|| {
-                    ExceptionFingerprint {
-                        exception_type: ex_type.clone(),
-                        location: location.clone(),
-                        count: 0,
-                        example: line.chars().take(150).collect(),
-                    }
-                });
-                entry.count += 1;
-            }
+            stats.observe(&line);
         }
     }
+    None
+}
+
+/// 动态超时: 按文件大小放宽
+fn timeout_for(file_size_mb: u64) -> Duration {
+    Duration::from_millis(std::cmp::max(MIN_PROCESS_TIME_MS, file_size_mb * MS_PER_MB))
+}
+
+fn file_name(path: &Path, log_path: &str) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| log_path.to_string())
+}
+
+/// 分析日志文件 (超过 512 MiB 时分层采样，见 [`SamplePlan`])
+pub fn analyze_log(log_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    analyze_log_with(log_path, &SamplePlan::default())
+}
+
+fn analyze_log_with(log_path: &str, plan: &SamplePlan) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = Path::new(log_path);
+    if !path.exists() {
+        return Err(format!("Log file not found: {log_path}").into());
+    }
+    
+    let file = File::open(path)?;
+    let file_size = file.metadata()?.len();
     
+    let file_size_mb = file_size / MIB;
+    let start_time = Instant::now();
+    let mut truncate_reason = None;
+    let sampled = if file_size > plan.threshold {
+        sample_log(path, file_size, plan)?
+    } else {
+        let mut stats = LogStats::default();
+        truncate_reason = observe_lines(BufReader::new(file), &mut stats, timeout_for(file_size_mb), file_size_mb);
+        SampledLog { sampled_bytes: file_size, stats, ..Default::default() }
+    };
     let process_time = start_time.elapsed();
+    let stats = &sampled.stats;
+    let is_sampled = file_size > plan.threshold;
     
-    // 排序
-    let mut fingerprints: Vec<_> = exception_map.values().collect();
-    fingerprints.sort_by(|a, b| b.count.cmp(&a.count));
+    let mut fingerprints: Vec<_> = stats.sorted().into_iter().map(|fp| (fp, sampled.estimate_count(fp))).collect();
+    fingerprints.sort_by_key(|(_, estimate)| std::cmp::Reverse(*estimate));
     
     // 生成报告
-    let file_name = path.file_name()
-        .map(|n| n.to_string_lossy().to_string())
-        .unwrap_or_else(|| log_path.to_string());
-    
-    let mut report = format!(
-        "### 日志分析: {}\n\n\
-        **性能**: {} 行, {}ms\n",
-        file_name,
-        lines_processed,
-        process_time.as_millis()
-    );
+    let mut report = format!("### 日志分析: {}\n\n", file_name(path, log_path));
+    if is_sampled {
+        report.push_str(&format!(
+            "**性能**: 样本 {} 行 (估算共 {} 行), {}ms\n\n\
+            **采样**: 文件 {} 超过 {}，分层采样 头部 {} + 尾部 {} + 中间 {} 段 × {} (采样率 {:.1}%)，\
+            中间段次数按 ×{:.1} 加权估算\n",
+            stats.lines,
+            sampled.estimate(stats.lines, sampled.middle_lines),
+            process_time.as_millis(),
+            mib(file_size), mib(plan.threshold), mib(plan.head), mib(plan.tail), plan.blocks, mib(plan.block),
+            sampled.sampled_bytes as f64 * 100.0 / file_size as f64,
+            sampled.scale(),
+        ));
+    } else {
+        report.push_str(&format!("**性能**: {} 行, {}ms\n", stats.lines, process_time.as_millis()));
+    }
     
-    if truncated {
+    if let Some(truncate_reason) = truncate_reason {
         report.push_str(&format!("\n> [!CAUTION]\n> {truncate_reason}\n\n"));
     }
     
     if !fingerprints.is_empty() {
-        let total: usize = fingerprints.iter().map(|f| f.count).sum();
+        let total: usize = fingerprints.iter().map(|(_, estimate)| estimate).sum();
         
-        report.push_str(&format!(
-            "\n## 🔬 异常指纹归类 ({} 类, 共 {} 次)\n\n\
-            | # | 类型 | 位置 | 次数 | 标记 |\n\
-            |---|------|------|------|------|\n",
-            fingerprints.len(), total
-        ));
+        if is_sampled {
+            report.push_str(&format!(
+                "\n## 🔬 异常指纹归类 ({} 类, 估算共 {} 次)\n\n\
+                | # | 类型 | 位置 | 估算次数 | 样本次数 | 标记 |\n\
+                |---|------|------|------|------|------|\n",
+                fingerprints.len(), total
+            ));
+        } else {
+            report.push_str(&format!(
+                "\n## 🔬 异常指纹归类 ({} 类, 共 {} 次)\n\n\
+                | # | 类型 | 位置 | 次数 | 标记 |\n\
+                |---|------|------|------|------|\n",
+                fingerprints.len(), total
+            ));
+        }
         
-        for (i, fp) in fingerprints.iter().take(10).enumerate() {
-            let tag = if fp.count > 1000 {
-                "🔥 核心噪音"
-            } else if fp.count < 10 {
-                "⚠️ 可能根因"
-            } else if fp.count < 100 {
-                "🔍 需关注"
-            } else {
-                ""
-            };
-            
+        for (i, (fp, estimate)) in fingerprints.iter().take(10).enumerate() {
+            let sample_count = if is_sampled { format!(" {} |", fp.count) } else { String::new() };
             report.push_str(&format!(
-                "| {} | `{}` | {} | {} | {} |\n",
-                i + 1, fp.exception_type, fp.location, fp.count, tag
+                "| {} | `{}` | {} | {} |{sample_count} {} |\n",
+                i + 1, fp.exception_type, fp.location, estimate, frequency_tag(*estimate)
             ));
         }
         
         // 关键发现
-        let key_errors: Vec<_> = fingerprints.iter().filter(|f| f.count < 10).collect();
+        let key_errors: Vec<_> = fingerprints.iter().filter(|(_, estimate)| *estimate < 10).collect();
         if !key_errors.is_empty() {
             report.push_str(&format!(
                 "\n> [!IMPORTANT]\n> 发现 {} 个低频异常，可能是根因！\n",
@@ -173,6 +419,250 @@ pub fn analyze_log(log_path: &str) -> Result<Value, Box<dyn std::error::Error>>
     } else {
         report.push_str("\n✅ 未发现异常\n");
     }
+
+    if stats.traces.records > 0 {
+        report.push_str(&stats.traces.render());
+    }
+
+    if is_sampled {
+        report.push_str("\n> [!NOTE]\n> 采样可能遗漏只出现在未采样区间的低频异常；需要完整结论时按时间段切分日志后分别分析\n");
+    }
+
+    if stats.class_loading.is_relevant() {
+        report.push_str(&stats.class_loading.render());
+    }
+    if stats.startup.is_relevant() {
+        report.push_str(&stats.startup.render());
+    }
     
     Ok(json!(report))
 }
+
+fn mib(bytes: u64) -> String {
+    if bytes.is_multiple_of(MIB) {
+        format!("{} MiB", bytes / MIB)
+    } else {
+        format!("{:.2} MiB", bytes as f64 / MIB as f64)
+    }
+}
+
+fn frequency_tag(count: usize) -> &'static str {
+    if count > 1000 {
+        "🔥 核心噪音"
+    } else if count < 10 {
+        "⚠️ 可能根因"
+    } else if count < 100 {
+        "🔍 需关注"
+    } else {
+        ""
+    }
+}
+
+/// 跟踪日志 (tail -f): 先统计已有内容作为基线，再在 `duration` 内增量读取新行，
+/// 输出窗口内的异常指纹与基线对比 (新出现的指纹标记 🆕)
+///
+/// 文件变小 (截断/滚动) 时从新文件开头继续读取；时长上限 [`MAX_FOLLOW`]。
+pub fn follow_log(log_path: &str, duration: Duration) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = Path::new(log_path);
+    if !path.exists() {
+        return Err(format!("Log file not found: {log_path}").into());
+    }
+    let duration = duration.min(MAX_FOLLOW);
+
+    // 基线: 只读取开始跟踪时已有的内容
+    let mut position = std::fs::metadata(path)?.len();
+    let file_size_mb = position / MIB;
+    let mut baseline = LogStats::default();
+    let baseline_truncated = observe_lines(
+        BufReader::new(File::open(path)?.take(position)),
+        &mut baseline,
+        timeout_for(file_size_mb),
+        file_size_mb,
+    );
+
+    let started = Instant::now();
+    let deadline = started + duration;
+    let mut window = LogStats::default();
+    let mut pending: Vec<u8> = Vec::new();
+    let mut rotations = 0;
+    loop {
+        let len = std::fs::metadata(path).map(|m| m.len()).unwrap_or(position);
+        if len < position {
+            rotations += 1;
+            position = 0;
+            pending.clear();
+        }
+        if len > position {
+            let mut file = File::open(path)?;
+            file.seek(SeekFrom::Start(position))?;
+            file.take(len - position).read_to_end(&mut pending)?;
+            position = len;
+
+            let complete = pending.iter().rposition(|&b| b == b'\n').map_or(0, |i| i + 1);
+            for line in pending[..complete].split(|&b| b == b'\n').filter(|l| !l.is_empty()) {
+                let line = String::from_utf8_lossy(line);
+                if let Some((fingerprint, true)) = window.observe(line.trim_end_matches('\r')) {
+                    tracing::info!("{}: 新异常指纹 {fingerprint}", file_name(path, log_path));
+                }
+            }
+            pending.drain(..complete);
+        }
+
+        let now = Instant::now();
+        if now >= deadline {
+            break;
+        }
+        std::thread::sleep(FOLLOW_POLL.min(deadline - now));
+    }
+    if !pending.is_empty() {
+        window.observe(String::from_utf8_lossy(&pending).trim_end());
+    }
+
+    let mut report = format!(
+        "### 日志跟踪: {}\n\n**窗口**: {}s, 新增 {} 行 (基线 {} 行)\n",
+        file_name(path, log_path),
+        started.elapsed().as_secs(),
+        window.lines,
+        baseline.lines,
+    );
+    if rotations > 0 {
+        report.push_str(&format!("\n> [!NOTE]\n> 跟踪期间文件被截断/滚动 {rotations} 次，已从新文件开头继续读取\n"));
+    }
+    if let Some(reason) = baseline_truncated {
+        report.push_str(&format!("\n> [!CAUTION]\n> 基线{reason}\n"));
+    }
+
+    let fingerprints = window.sorted();
+    if fingerprints.is_empty() {
+        report.push_str("\n✅ 窗口内未发现异常\n");
+    } else {
+        let total: usize = fingerprints.iter().map(|f| f.count).sum();
+        report.push_str(&format!(
+            "\n## 📈 窗口内异常指纹 ({} 类, 共 {} 次)\n\n\
+            | # | 类型 | 位置 | 窗口内次数 | 基线次数 | 标记 |\n\
+            |---|------|------|------|------|------|\n",
+            fingerprints.len(), total
+        ));
+        for (i, fp) in fingerprints.iter().take(20).enumerate() {
+            let before = baseline.count(fp);
+            let tag = if before == 0 { "🆕 新出现" } else { frequency_tag(fp.count) };
+            report.push_str(&format!(
+                "| {} | `{}` | {} | {} | {} | {} |\n",
+                i + 1, fp.exception_type, fp.location, fp.count, before, tag
+            ));
+        }
+
+        let new: Vec<_> = fingerprints.iter().filter(|f| baseline.count(f) == 0).collect();
+        if !new.is_empty() {
+            report.push_str(&format!(
+                "\n> [!IMPORTANT]\n> 窗口内新出现 {} 类异常，优先排查 (示例: `{}`)\n",
+                new.len(), new[0].example
+            ));
+        }
+    }
+
+    if window.class_loading.is_relevant() {
+        report.push_str(&window.class_loading.render());
+    }
+
+    Ok(json!(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    #[test]
+    fn test_analyze_log_fingerprints() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("app.log");
+        std::fs::write(&log, "ERROR java.lang.IllegalStateException: boom at com.acme.OrderService\n\
+            ERROR java.lang.IllegalStateException: boom at com.acme.OrderService\n\
+            INFO started\n").unwrap();
+
+        let report = analyze_log(log.to_str().unwrap()).unwrap();
+        let report = report.as_str().unwrap();
+        assert!(report.contains("**性能**: 3 行"));
+        assert!(report.contains("异常指纹归类 (1 类, 共 2 次)"));
+        assert!(report.contains("`IllegalStateException` | lang.IllegalStateException | 2 | ⚠️ 可能根因"));
+    }
+
+    #[test]
+    fn test_analyze_log_lists_failing_traces() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("app.log");
+        std::fs::write(&log, "2026-10-17 10:00:00.000  INFO [order,6f1d2a3b4c5d6e7f,1a2b3c4d5e6f7a8b] 1 --- [exec-1] c.a.OrderController : accepted\n\
+            2026-10-17 10:00:00.100 ERROR [order,0a0b0c0d0e0f0a0b,0a0b0c0d0e0f0a0b] 1 --- [exec-2] c.a.OrderController : failed\n\
+            java.lang.IllegalStateException: boom\n\
+            \tat com.acme.OrderService.place(OrderService.java:42)\n\
+            2026-10-17 10:00:00.200  INFO 1 --- [main] c.a.Scheduler : tick\n").unwrap();
+
+        let report = analyze_log(log.to_str().unwrap()).unwrap();
+        let report = report.as_str().unwrap();
+        assert!(report.contains("识别到 2 条带 Trace ID 的记录 (2 个请求)"), "{report}");
+        // 堆栈行中的异常归属到所在记录的请求
+        assert!(report.contains("| `0a0b0c0d0e0f0a0b` | `IllegalStateException@lang.IllegalStateException` |"), "{report}");
+        assert!(!report.contains("| `6f1d2a3b4c5d6e7f` |"));
+    }
+
+    #[test]
+    fn test_sampled_log_estimates_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("huge.log");
+        let mut content = String::new();
+        // 每行 64 字节，每 4 行一个超时异常 (共 2500 次)
+        for i in 0..10_000 {
+            let line = if i % 4 == 0 {
+                "WARN java.net.SocketTimeoutException: Read timed out"
+            } else {
+                "INFO request handled"
+            };
+            content.push_str(&format!("{line:<63}\n"));
+        }
+        std::fs::write(&log, &content).unwrap();
+
+        let plan = SamplePlan { threshold: 64 * 1024, head: 16 * 1024, tail: 16 * 1024, blocks: 8, block: 8 * 1024 };
+        let report = analyze_log_with(log.to_str().unwrap(), &plan).unwrap();
+        let report = report.as_str().unwrap();
+        assert!(report.contains("**采样**: 文件 0.61 MiB 超过 0.06 MiB，分层采样 头部 0.02 MiB + 尾部 0.02 MiB + 中间 8 段 × 0.01 MiB"), "{report}");
+        assert!(report.contains("(采样率 15.3%)"), "{report}");
+
+        // 估算值与真实次数 2500 误差在 1% 以内
+        let row = report.lines().find(|l| l.contains("`SocketTimeoutException`")).unwrap();
+        let cells: Vec<&str> = row.split('|').map(str::trim).collect();
+        let estimate: f64 = cells[4].parse().unwrap();
+        assert!((estimate - 2500.0).abs() < 25.0, "{row}");
+        assert_eq!(cells[5], "379");
+        assert!(report.contains("采样可能遗漏"));
+    }
+
+    #[test]
+    fn test_follow_log_reports_window_delta() {
+        let dir = tempfile::tempdir().unwrap();
+        let log = dir.path().join("app.log");
+        std::fs::write(&log, "WARN java.net.SocketTimeoutException: Read timed out\n").unwrap();
+
+        let writer = {
+            let log = log.clone();
+            std::thread::spawn(move || {
+                std::thread::sleep(Duration::from_millis(300));
+                let mut file = std::fs::OpenOptions::new().append(true).open(&log).unwrap();
+                writeln!(file, "WARN java.net.SocketTimeoutException: Read timed out").unwrap();
+                // 半行在下一次轮询时补全
+                write!(file, "ERROR org.hibernate.LazyInitializationException: ").unwrap();
+                file.flush().unwrap();
+                std::thread::sleep(Duration::from_millis(700));
+                writeln!(file, "could not initialize proxy").unwrap();
+            })
+        };
+
+        let report = follow_log(log.to_str().unwrap(), Duration::from_millis(1500)).unwrap();
+        writer.join().unwrap();
+        let report = report.as_str().unwrap();
+        assert!(report.contains("新增 2 行 (基线 1 行)"), "{report}");
+        assert!(report.contains("`SocketTimeoutException` | net.SocketTimeoutException | 1 | 1 |"));
+        assert!(report.contains("`LazyInitializationException` | hibernate.LazyInitializationException | 1 | 0 | 🆕 新出现"));
+        assert!(report.contains("窗口内新出现 1 类异常"));
+    }
+}
@@ -0,0 +1,477 @@
+//! JMH 基准骨架生成 (genbench) - v9.6
+//!
+//! `genbench --issue RULE@file:line` 为扫描报告中的热点生成 JMH 基准骨架，修复前后各运行一次即可量化收益：
+//! - 定位问题所在的方法，基准类写入 `src/jmh/java` 下与目标类相同的包 (包可见方法可直接调用)
+//! - 方法参数按类型生成随 `@Param size` 伸缩的输入 (集合、数组、字符串、数值)，
+//!   参数/返回值类型经源文件 import 与项目符号表解析后生成 import
+//! - 按规则家族调整规模与说明: 循环内字符串拼接、嵌套循环 join、SimpleDateFormat (多线程争用)
+//!
+//! 无法推断的构造 (依赖注入、自定义元素类型) 生成 `TODO` 注释，由开发者补全。
+
+use serde_json::{json, Value};
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+
+use crate::ast_engine;
+use crate::rules::catalog;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+
+/// 基准源码目录 (Gradle me.champeau.jmh 插件约定)
+pub const JMH_SOURCE_DIR: &str = "src/jmh/java";
+
+/// 规则家族: 决定输入规模、线程数与说明
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Family {
+    StringConcat,
+    NestedLoop,
+    DateFormat,
+    Generic,
+}
+
+impl Family {
+    fn of(rule_id: &str) -> Self {
+        match rule_id {
+            "STRING_CONCAT_LOOP" | "BC_CONCAT_IN_LOOP" | "BC_STRING_CONCAT_HEAVY" => Family::StringConcat,
+            "NESTED_LOOP" | "NESTED_LOOP_MIXED" | "LIST_CONTAINS_IN_LOOP" | "MAX_LOOP_DEPTH" => Family::NestedLoop,
+            "SIMPLE_DATE_FORMAT" | "DATE_FORMATTER_IN_LOOP" | "CALENDAR_PER_CALL" => Family::DateFormat,
+            _ => Family::Generic,
+        }
+    }
+
+    /// `@Param` 规模: 覆盖到复杂度拐点 (O(N²) 在 10000 时与线性实现拉开数量级)
+    fn sizes(self) -> &'static str {
+        match self {
+            Family::StringConcat | Family::NestedLoop => r#""100", "1000", "10000""#,
+            Family::DateFormat => r#""1", "100", "10000""#,
+            Family::Generic => r#""10", "1000""#,
+        }
+    }
+
+    /// 基准类说明中的测量要点
+    fn note(self) -> &'static str {
+        match self {
+            Family::StringConcat => "循环内字符串拼接每次复制整个字符串，耗时随 size 平方增长；改用 StringBuilder 后应接近线性",
+            Family::NestedLoop => "嵌套循环 join 为 O(N×M)；内层改为 Map / Set 查找后，size 增大 10 倍耗时也应只增大约 10 倍",
+            Family::DateFormat => "SimpleDateFormat / Calendar 创建开销大且非线程安全；@Threads(4) 同时测量多线程争用，改用 DateTimeFormatter 常量后对比",
+            Family::Generic => "修复前后各运行一次，对比各 size 下的 Score",
+        }
+    }
+
+    fn threads(self) -> Option<usize> {
+        (self == Family::DateFormat).then_some(4)
+    }
+}
+
+/// 解析 `RULE@file:line`
+pub fn parse_issue(issue: &str) -> Result<(String, String, usize), String> {
+    let invalid = || format!("无效的问题标识 '{issue}': 应为 RULE_ID@file:line，如 STRING_CONCAT_LOOP@src/main/java/com/acme/Report.java:42");
+    let (rule, location) = issue.split_once('@').ok_or_else(invalid)?;
+    let (file, line) = location.rsplit_once(':').ok_or_else(invalid)?;
+    let line = line.trim().parse::<usize>().map_err(|_| invalid())?;
+    if rule.trim().is_empty() || file.trim().is_empty() || line == 0 {
+        return Err(invalid());
+    }
+    Ok((rule.trim().to_string(), file.trim().to_string(), line))
+}
+
+/// 被测方法
+#[derive(Debug)]
+struct Target {
+    package: Option<String>,
+    /// 外部类.内部类
+    class: String,
+    method: String,
+    is_static: bool,
+    is_private: bool,
+    return_type: String,
+    /// (类型, 参数名)
+    params: Vec<(String, String)>,
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+/// 包含该行的最内层方法
+fn find_target(root: Node, code: &str, line: usize) -> Option<Target> {
+    let row = line.saturating_sub(1);
+    let mut method = None;
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        if node.start_position().row > row || node.end_position().row < row {
+            continue;
+        }
+        if node.kind() == "method_declaration" {
+            method = Some(node);
+        }
+        let mut cursor = node.walk();
+        stack.extend(node.named_children(&mut cursor));
+    }
+    let method = method?;
+
+    let mut classes = Vec::new();
+    let mut parent = method.parent();
+    while let Some(node) = parent {
+        if matches!(node.kind(), "class_declaration" | "enum_declaration" | "record_declaration") {
+            classes.extend(node.child_by_field_name("name").map(|n| text(n, code).to_string()));
+        }
+        parent = node.parent();
+    }
+    classes.reverse();
+
+    let mut cursor = root.walk();
+    let package = root.named_children(&mut cursor)
+        .find(|n| n.kind() == "package_declaration")
+        .and_then(|p| p.named_child(0))
+        .map(|n| text(n, code).to_string());
+
+    let modifiers = method.child(0).filter(|m| m.kind() == "modifiers").map(|m| text(m, code)).unwrap_or("");
+    let words: Vec<&str> = modifiers.split_whitespace().collect();
+    let params = method.child_by_field_name("parameters")
+        .map(|list| {
+            let mut cursor = list.walk();
+            list.named_children(&mut cursor)
+                .filter(|p| matches!(p.kind(), "formal_parameter" | "spread_parameter"))
+                .filter_map(|p| {
+                    let name = p.child_by_field_name("name").map(|n| text(n, code).to_string())
+                        .or_else(|| p.named_child(p.named_child_count().saturating_sub(1))
+                            .and_then(|d| d.child_by_field_name("name"))
+                            .map(|n| text(n, code).to_string()))?;
+                    let type_name = p.child_by_field_name("type").map(|t| text(t, code).to_string())
+                        .or_else(|| p.named_child(usize::from(p.child(0).is_some_and(|m| m.kind() == "modifiers")))
+                            .map(|t| format!("{}[]", text(t, code))))?;
+                    Some((type_name, name))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Some(Target {
+        package,
+        class: classes.join("."),
+        method: text(method.child_by_field_name("name")?, code).to_string(),
+        is_static: words.contains(&"static"),
+        is_private: words.contains(&"private"),
+        return_type: method.child_by_field_name("type").map(|t| text(t, code).to_string()).unwrap_or_else(|| "void".to_string()),
+        params,
+    })
+}
+
+/// 类型中出现的简单类名 (`Map<String, List<Order>>` → Map, String, List, Order)
+fn type_names(type_name: &str) -> impl Iterator<Item = &str> {
+    type_name.split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.'))
+        .filter(|t| t.chars().next().is_some_and(char::is_uppercase))
+}
+
+/// 泛型参数 (`List<Long>` → ["Long"])
+fn type_args(type_name: &str) -> Vec<String> {
+    let Some(start) = type_name.find('<') else { return Vec::new() };
+    let inner = &type_name[start + 1..type_name.rfind('>').unwrap_or(type_name.len())];
+    let mut args = Vec::new();
+    let (mut depth, mut current) = (0, String::new());
+    for c in inner.chars() {
+        match c {
+            '<' => depth += 1,
+            '>' => depth -= 1,
+            ',' if depth == 0 => {
+                args.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    if !current.trim().is_empty() {
+        args.push(current.trim().to_string());
+    }
+    args
+}
+
+/// 集合元素的构造表达式 (下标变量为 `i`)
+fn element(type_name: &str) -> String {
+    match type_name.trim_start_matches("? extends ") {
+        "Long" | "long" => "(long) i".to_string(),
+        "Integer" | "int" | "Object" | "?" => "i".to_string(),
+        "Double" | "double" => "(double) i".to_string(),
+        "String" | "CharSequence" => "\"item-\" + i".to_string(),
+        other => format!("new {other}() /* TODO: 按 i 构造测试数据 */"),
+    }
+}
+
+/// 随 size 伸缩的输入: (初始化表达式, 需要的 import)
+fn fixture(type_name: &str) -> (String, Vec<&'static str>) {
+    const STREAM: &[&str] = &["java.util.stream.IntStream", "java.util.stream.Collectors"];
+    let base = type_name.split('<').next().unwrap_or(type_name).trim();
+    let args = type_args(type_name);
+    let elem = args.first().map(String::as_str).unwrap_or("Integer");
+    let stream = |collector: &str| format!("IntStream.range(0, size).mapToObj(i -> {}).collect(Collectors.{collector}())", element(elem));
+    match base {
+        "List" | "Collection" | "Iterable" => (stream("toList"), STREAM.to_vec()),
+        "ArrayList" | "LinkedList" => (format!("new {base}<>({})", stream("toList")), STREAM.to_vec()),
+        "Set" => (stream("toSet"), STREAM.to_vec()),
+        "HashSet" | "LinkedHashSet" | "TreeSet" => (format!("new {base}<>({})", stream("toSet")), STREAM.to_vec()),
+        "Map" | "HashMap" | "LinkedHashMap" | "TreeMap" => {
+            let value = args.get(1).map(String::as_str).unwrap_or("Integer");
+            let map = format!(
+                "IntStream.range(0, size).boxed().collect(Collectors.toMap(i -> {}, i -> {}))",
+                element(elem), element(value)
+            );
+            if base == "Map" { (map, STREAM.to_vec()) } else { (format!("new {base}<>({map})"), STREAM.to_vec()) }
+        }
+        "int[]" => ("IntStream.range(0, size).toArray()".to_string(), vec!["java.util.stream.IntStream"]),
+        "long[]" => ("LongStream.range(0, size).toArray()".to_string(), vec!["java.util.stream.LongStream"]),
+        _ if base.ends_with("[]") => {
+            let component = base.trim_end_matches("[]");
+            (format!("IntStream.range(0, size).mapToObj(i -> {}).toArray({component}[]::new)", element(component)), vec!["java.util.stream.IntStream"])
+        }
+        "int" | "Integer" | "short" | "byte" => ("size".to_string(), Vec::new()),
+        "long" | "Long" => ("(long) size".to_string(), Vec::new()),
+        "double" | "Double" | "float" | "Float" => ("size".to_string(), Vec::new()),
+        "boolean" | "Boolean" => ("true".to_string(), Vec::new()),
+        "String" | "CharSequence" => ("\"x\".repeat(size)".to_string(), Vec::new()),
+        "Date" => ("new Date()".to_string(), Vec::new()),
+        "LocalDate" | "LocalDateTime" | "Instant" => (format!("{base}.now()"), Vec::new()),
+        other => (format!("new {other}() /* TODO: 构造测试数据 */"), Vec::new()),
+    }
+}
+
+fn capitalize(s: &str) -> String {
+    let mut chars = s.chars();
+    chars.next().map(|c| c.to_uppercase().chain(chars).collect()).unwrap_or_default()
+}
+
+/// 生成的基准
+#[derive(Debug)]
+struct Benchmark {
+    class_name: String,
+    source: String,
+}
+
+fn render(target: &Target, rule_id: &str, location: &str, imports: &BTreeSet<String>) -> Benchmark {
+    let family = Family::of(rule_id);
+    let outer = target.class.split('.').next().unwrap_or(&target.class);
+    let class_name = format!("{}{}Benchmark", target.class.replace('.', ""), capitalize(&target.method));
+    let fix = catalog::fix(rule_id).map(|f| format!("\n * 修复建议: {f}")).unwrap_or_default();
+
+    let mut out = String::new();
+    if let Some(package) = &target.package {
+        out.push_str(&format!("package {package};\n\n"));
+    }
+    for import in imports {
+        out.push_str(&format!("import {import};\n"));
+    }
+    out.push_str(&format!(
+        "\n/**\n * {rule_id} @ {location}: {}.{}{fix}\n *\n * {}\n */\n",
+        target.class, target.method, family.note()
+    ));
+    out.push_str("@BenchmarkMode(Mode.AverageTime)\n@OutputTimeUnit(TimeUnit.MICROSECONDS)\n@Warmup(iterations = 3, time = 1)\n@Measurement(iterations = 5, time = 1)\n@Fork(1)\n");
+    if let Some(threads) = family.threads() {
+        out.push_str(&format!("@Threads({threads})\n"));
+    }
+    out.push_str(&format!("@State(Scope.Benchmark)\npublic class {class_name} {{\n\n    @Param({{{}}})\n    int size;\n\n", family.sizes()));
+
+    if !target.is_static {
+        out.push_str(&format!("    private {} target;\n", target.class));
+    }
+    for (type_name, name) in &target.params {
+        out.push_str(&format!("    private {type_name} {name};\n"));
+    }
+    out.push_str("\n    @Setup\n    public void setUp() {\n");
+    if !target.is_static {
+        out.push_str(&format!("        target = new {}(); // TODO: 注入依赖 (mock / 内存实现)\n", target.class));
+    }
+    for (type_name, name) in &target.params {
+        out.push_str(&format!("        {name} = {};\n", fixture(type_name).0));
+    }
+    out.push_str("    }\n\n");
+
+    if target.is_private {
+        out.push_str(&format!("    // TODO: {} 为 private，改为包可见 (基准与 {outer} 同包) 或改测其公共入口\n", target.method));
+    }
+    let receiver = if target.is_static { target.class.as_str() } else { "target" };
+    let args: Vec<&str> = target.params.iter().map(|(_, name)| name.as_str()).collect();
+    let call = format!("{receiver}.{}({})", target.method, args.join(", "));
+    if target.return_type == "void" {
+        out.push_str(&format!("    @Benchmark\n    public void {}() {{\n        {call};\n    }}\n}}\n", target.method));
+    } else {
+        out.push_str(&format!("    @Benchmark\n    public void {}(Blackhole bh) {{\n        bh.consume({call});\n    }}\n}}\n", target.method));
+    }
+    Benchmark { class_name, source: out }
+}
+
+/// 基准类需要的 import: JMH、输入构造、参数/返回值中项目外或其他包的类型
+fn imports_for(target: &Target, file_imports: &crate::symbol_table::ImportIndex, known: &HashMap<String, String>) -> BTreeSet<String> {
+    let mut imports: BTreeSet<String> = [
+        "java.util.concurrent.TimeUnit",
+        "org.openjdk.jmh.annotations.*",
+    ].iter().map(|s| s.to_string()).collect();
+    if target.return_type != "void" {
+        imports.insert("org.openjdk.jmh.infra.Blackhole".to_string());
+    }
+    let package = target.package.as_deref().unwrap_or("");
+    let types = target.params.iter().map(|(t, _)| t.as_str()).chain([target.return_type.as_str()]);
+    for type_name in types {
+        imports.extend(fixture(type_name).1.into_iter().map(str::to_string));
+        for name in type_names(type_name) {
+            if name.contains('.') {
+                continue;
+            }
+            let resolved = file_imports.resolve(name, known).or_else(|| {
+                // 参数里的 java.util 集合多为通配符导入
+                ["List", "Set", "Map", "Collection", "ArrayList", "LinkedList", "HashSet", "LinkedHashSet", "TreeSet", "HashMap", "LinkedHashMap", "TreeMap", "Date"]
+                    .contains(&name)
+                    .then(|| format!("java.util.{name}"))
+            });
+            if let Some(fqn) = resolved {
+                let owner = fqn.rsplit_once('.').map_or("", |(p, _)| p);
+                if owner != "java.lang" && owner != package {
+                    imports.insert(fqn);
+                }
+            }
+        }
+    }
+    imports
+}
+
+/// 为扫描报告中的问题生成 JMH 基准骨架
+///
+/// `issue` 形如 `STRING_CONCAT_LOOP@src/main/java/com/acme/Report.java:42` (文件相对项目路径，与 `scan` 输出一致)；
+/// `dry_run` 时只返回源码不写文件，目标文件已存在时报错而不覆盖。
+pub fn generate(root: &str, issue: &str, dry_run: bool) -> Result<Value, Box<dyn std::error::Error>> {
+    let (rule_id, file, line) = parse_issue(issue)?;
+    let root_path = Path::new(root);
+    let file_path = {
+        let joined = root_path.join(&file);
+        if joined.is_file() { joined } else { PathBuf::from(&file) }
+    };
+    let code = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Cannot read {}: {e}", file_path.display()))?;
+
+    let analyzer = JavaTreeSitterAnalyzer::new()?;
+    let tree = analyzer.parse(&code)?;
+    let target = find_target(tree.root_node(), &code, line)
+        .ok_or_else(|| format!("{file}:{line} 不在任何方法内"))?;
+
+    // 项目符号表: 解析通配符 import 与同包类型
+    let index = ast_engine::index_project(root)?;
+    let known: HashMap<String, String> = index.symbol_table.classes.keys().map(|fqn| (fqn.clone(), fqn.clone())).collect();
+    let (_, _, file_imports) = analyzer.extract_symbols(&code, &file_path)?;
+    let imports = imports_for(&target, &file_imports, &known);
+
+    let location = format!("{file}:{line}");
+    let bench = render(&target, &rule_id, &location, &imports);
+    let package_dir = target.package.as_deref().unwrap_or("").replace('.', "/");
+    let output = root_path.join(JMH_SOURCE_DIR).join(package_dir).join(format!("{}.java", bench.class_name));
+    if !dry_run {
+        if output.exists() {
+            return Err(format!("{} 已存在，删除后重新生成或使用 --dry-run 预览", output.display()).into());
+        }
+        std::fs::create_dir_all(output.parent().unwrap_or(root_path))?;
+        std::fs::write(&output, &bench.source)?;
+    }
+
+    let method = format!("{}.{}", target.class, target.method);
+    let report = render_report(&rule_id, &location, &method, &output, dry_run, &bench.source);
+    Ok(json!({
+        "issue": { "id": rule_id, "file": file, "line": line },
+        "package": target.package,
+        "class": target.class,
+        "method": target.method,
+        "benchmark": bench.class_name,
+        "output": output.display().to_string(),
+        "written": !dry_run,
+        "source": bench.source,
+        "report": report,
+    }))
+}
+
+fn render_report(rule_id: &str, location: &str, method: &str, output: &Path, dry_run: bool, source: &str) -> String {
+    let mut out = format!("## ⏱️ JMH 基准骨架: {rule_id} @ {location}\n\n**被测方法**: `{method}`\n\n");
+    if dry_run {
+        out.push_str(&format!("```java\n{source}```\n\n> 去掉 `--dry-run` 写入 `{}`\n", output.display()));
+    } else {
+        out.push_str(&format!("- 已写入 `{}`\n", output.display()));
+        out.push_str("- 补全 TODO 后运行: Gradle 使用 `me.champeau.jmh` 插件 `./gradlew jmh`；Maven 用 build-helper 添加 `src/jmh/java` 源目录并引入 `jmh-core` + `jmh-generator-annprocess`\n");
+        out.push_str("- 修复前后各运行一次，对比各 `size` 下的 Score\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("src/main/java/com/acme/report");
+        let model = dir.path().join("src/main/java/com/acme/model");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::create_dir_all(&model).unwrap();
+        std::fs::write(model.join("Order.java"), "package com.acme.model;\n\npublic class Order {\n}\n").unwrap();
+        std::fs::write(pkg.join("ReportService.java"), concat!(
+            "package com.acme.report;\n\n",
+            "import com.acme.model.*;\n",
+            "import java.util.List;\n\n",
+            "public class ReportService {\n",
+            "    public String render(List<Order> orders, int columns) {\n",
+            "        String out = \"\";\n",
+            "        for (Order o : orders) {\n",
+            "            out += o.toString();\n",
+            "        }\n",
+            "        return out;\n",
+            "    }\n",
+            "}\n",
+        )).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_generate_string_concat_benchmark() {
+        let dir = project();
+        let root = dir.path().to_str().unwrap();
+        let v = generate(root, "STRING_CONCAT_LOOP@src/main/java/com/acme/report/ReportService.java:9", false).unwrap();
+
+        assert_eq!(v["benchmark"], "ReportServiceRenderBenchmark");
+        let output = dir.path().join("src/jmh/java/com/acme/report/ReportServiceRenderBenchmark.java");
+        let source = std::fs::read_to_string(&output).unwrap();
+        assert!(source.starts_with("package com.acme.report;\n"));
+        assert!(source.contains("import com.acme.model.Order;\n"), "{source}");
+        assert!(source.contains("import java.util.List;\n"));
+        assert!(source.contains("import java.util.stream.IntStream;\n"));
+        assert!(source.contains("import org.openjdk.jmh.infra.Blackhole;\n"));
+        assert!(source.contains(r#"@Param({"100", "1000", "10000"})"#));
+        assert!(source.contains("orders = IntStream.range(0, size).mapToObj(i -> new Order() /* TODO: 按 i 构造测试数据 */).collect(Collectors.toList());"));
+        assert!(source.contains("columns = size;"));
+        assert!(source.contains("bh.consume(target.render(orders, columns));"));
+        assert!(source.contains("修复建议: 使用 StringBuilder"));
+
+        // 不覆盖已有基准
+        assert!(generate(root, "STRING_CONCAT_LOOP@src/main/java/com/acme/report/ReportService.java:9", false).is_err());
+    }
+
+    #[test]
+    fn test_parse_issue_and_date_format_family() {
+        assert_eq!(parse_issue("NESTED_LOOP@src/A.java:12").unwrap(), ("NESTED_LOOP".to_string(), "src/A.java".to_string(), 12));
+        assert!(parse_issue("NESTED_LOOP@src/A.java").is_err());
+        assert!(parse_issue("src/A.java:12").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Dates.java"), concat!(
+            "public class Dates {\n",
+            "    static String format(java.util.Date date) {\n",
+            "        return new java.text.SimpleDateFormat(\"yyyy-MM-dd\").format(date);\n",
+            "    }\n",
+            "}\n",
+        )).unwrap();
+        let v = generate(dir.path().to_str().unwrap(), "SIMPLE_DATE_FORMAT@Dates.java:3", true).unwrap();
+        let source = v["source"].as_str().unwrap();
+        assert_eq!(v["written"], false);
+        assert!(!dir.path().join(JMH_SOURCE_DIR).exists());
+        assert!(source.contains("@Threads(4)"));
+        assert!(source.contains("bh.consume(Dates.format(date));"));
+        assert!(!source.contains("target"));
+        assert!(!source.starts_with("package"));
+    }
+}
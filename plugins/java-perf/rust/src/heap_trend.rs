@@ -0,0 +1,198 @@
+//! 堆直方图趋势 (Heap Trend) - v9.6
+//!
+//! 单次 `jmap -histo` 只能看到静态快照：`byte[]`/`String` 永远排在前面，看不出谁在泄漏。
+//! `jmap --samples 5 --interval 30s` 间隔采集多份直方图，逐类对比实例数/字节数，
+//! 每个采样间隔都在增长且总增量最大的类即为泄漏嫌疑。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// 报告中列出的增长最快的类数量
+const TOP_GROWING: usize = 10;
+
+/// 直方图中的一行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoEntry {
+    pub class: String,
+    pub instances: u64,
+    pub bytes: u64,
+}
+
+/// 一次采样: 相对第一次采样的时间 + 直方图
+#[derive(Debug, Clone)]
+pub struct HistoSample {
+    pub elapsed: Duration,
+    pub entries: Vec<HistoEntry>,
+}
+
+/// 单个类在采样期间的增长
+#[derive(Debug, Clone, Serialize)]
+pub struct ClassGrowth {
+    pub class: String,
+    /// 每次采样的字节数 (类未出现时为 0)
+    pub bytes: Vec<u64>,
+    pub instances: Vec<u64>,
+    pub bytes_delta: i64,
+    pub instances_delta: i64,
+    /// 每分钟字节增长
+    pub bytes_per_min: f64,
+    /// 每个采样间隔都在增长 (泄漏嫌疑)
+    pub monotonic: bool,
+}
+
+/// 解析 `jmap -histo` 输出
+///
+/// ```text
+///  num     #instances         #bytes  class name (module)
+///    1:         12345         678900  [B (java.base@17.0.9)
+/// ```
+pub fn parse_histogram(output: &str) -> Vec<HistoEntry> {
+    output.lines().filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        fields.next()?.strip_suffix(':')?.parse::<u64>().ok()?;
+        let instances = fields.next()?.parse().ok()?;
+        let bytes = fields.next()?.parse().ok()?;
+        let class = fields.next()?.to_string();
+        Some(HistoEntry { class, instances, bytes })
+    }).collect()
+}
+
+/// 逐类对比采样，按字节增量降序 (单调增长的类优先)
+pub fn class_growth(samples: &[HistoSample]) -> Vec<ClassGrowth> {
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else { return Vec::new() };
+    let minutes = (last.elapsed - first.elapsed).as_secs_f64() / 60.0;
+
+    let mut series: HashMap<&str, (Vec<u64>, Vec<u64>)> = HashMap::new();
+    for (i, sample) in samples.iter().enumerate() {
+        for entry in &sample.entries {
+            let (bytes, instances) = series.entry(&entry.class)
+                .or_insert_with(|| (vec![0; samples.len()], vec![0; samples.len()]));
+            bytes[i] += entry.bytes;
+            instances[i] += entry.instances;
+        }
+    }
+
+    let mut growth: Vec<ClassGrowth> = series.into_iter().map(|(class, (bytes, instances))| {
+        let bytes_delta = bytes[bytes.len() - 1] as i64 - bytes[0] as i64;
+        let instances_delta = instances[instances.len() - 1] as i64 - instances[0] as i64;
+        ClassGrowth {
+            class: class.to_string(),
+            monotonic: bytes.len() > 1 && bytes.windows(2).all(|w| w[1] > w[0]),
+            bytes_per_min: if minutes > 0.0 { bytes_delta as f64 / minutes } else { 0.0 },
+            bytes,
+            instances,
+            bytes_delta,
+            instances_delta,
+        }
+    }).collect();
+    growth.sort_by(|a, b| b.monotonic.cmp(&a.monotonic)
+        .then(b.bytes_delta.cmp(&a.bytes_delta))
+        .then_with(|| a.class.cmp(&b.class)));
+    growth
+}
+
+/// 渲染趋势报告，返回 `suspects` (单调增长的类)、`growing` (增长最快的前 N 个) 与 Markdown `report`
+pub fn render(pid: u32, label: &str, samples: &[HistoSample]) -> Value {
+    let growth = class_growth(samples);
+    let growing: Vec<&ClassGrowth> = growth.iter().filter(|g| g.bytes_delta > 0).take(TOP_GROWING).collect();
+    let suspects: Vec<&ClassGrowth> = growing.iter().copied().filter(|g| g.monotonic).collect();
+    let total = |s: &HistoSample| s.entries.iter().map(|e| e.bytes).sum::<u64>();
+    let totals: Vec<u64> = samples.iter().map(total).collect();
+    let span = samples.last().map(|s| s.elapsed.as_secs()).unwrap_or(0);
+
+    let mut report = format!(
+        "## 📈 堆增长趋势 (PID: {pid}{label})\n\n**采样**: {} 次，历时 {span}s | **堆占用**: {}\n\n",
+        samples.len(),
+        totals.iter().map(|b| mb(*b)).collect::<Vec<_>>().join(" → "),
+    );
+    if growing.is_empty() {
+        report.push_str("✅ 采样期间没有类持续增长\n");
+    } else {
+        report.push_str("| 类 | 字节 (首 → 末) | Δ 字节 | Δ 实例 | 增速/分钟 | 持续增长 |\n");
+        report.push_str("|----|----------------|--------|--------|-----------|----------|\n");
+        for g in &growing {
+            report.push_str(&format!(
+                "| `{}` | {} → {} | +{} | {:+} | {}/min | {} |\n",
+                g.class,
+                mb(g.bytes[0]),
+                mb(g.bytes[g.bytes.len() - 1]),
+                mb(g.bytes_delta as u64),
+                g.instances_delta,
+                mb(g.bytes_per_min.max(0.0) as u64),
+                if g.monotonic { "⚠️" } else { "-" },
+            ));
+        }
+        if !suspects.is_empty() {
+            report.push_str(&format!(
+                "\n> [!WARNING]\n> {} 个类在每个采样间隔都在增长，为泄漏嫌疑：{}\n",
+                suspects.len(),
+                suspects.iter().map(|g| format!("`{}`", g.class)).collect::<Vec<_>>().join(", "),
+            ));
+        }
+    }
+
+    json!({
+        "pid": pid,
+        "samples": samples.len(),
+        "heap_bytes": totals,
+        "growing": growing,
+        "suspects": suspects.iter().map(|g| &g.class).collect::<Vec<_>>(),
+        "report": report,
+    })
+}
+
+fn mb(bytes: u64) -> String {
+    format!("{:.1} MB", bytes as f64 / 1_048_576.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample(secs: u64, entries: &[(&str, u64, u64)]) -> HistoSample {
+        HistoSample {
+            elapsed: Duration::from_secs(secs),
+            entries: entries.iter()
+                .map(|(class, instances, bytes)| HistoEntry { class: class.to_string(), instances: *instances, bytes: *bytes })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_parse_histogram() {
+        let output = "\
+ num     #instances         #bytes  class name (module)
+-------------------------------------------------------
+   1:         52014        4812736  [B (java.base@17.0.9)
+   2:         50231        1205544  java.lang.String (java.base@17.0.9)
+   3:           120           5760  com.acme.OrderCache$Entry
+Total        102365        6024040
+";
+        let entries = parse_histogram(output);
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0], HistoEntry { class: "[B".to_string(), instances: 52014, bytes: 4812736 });
+        assert_eq!(entries[2].class, "com.acme.OrderCache$Entry");
+    }
+
+    #[test]
+    fn test_monotonic_growth_is_suspect() {
+        let samples = [
+            sample(0, &[("[B", 100, 4_000_000), ("com.acme.OrderCache$Entry", 10, 1_000)]),
+            sample(30, &[("[B", 90, 3_000_000), ("com.acme.OrderCache$Entry", 2_000, 96_000)]),
+            sample(60, &[("[B", 120, 5_000_000), ("com.acme.OrderCache$Entry", 4_000, 192_000)]),
+        ];
+        let growth = class_growth(&samples);
+        // 单调增长优先于总增量更大但有回落的 byte[]
+        assert_eq!(growth[0].class, "com.acme.OrderCache$Entry");
+        assert!(growth[0].monotonic);
+        assert_eq!(growth[0].instances_delta, 3_990);
+        assert_eq!(growth[0].bytes_per_min, 191_000.0);
+        assert!(!growth[1].monotonic);
+
+        let v = render(42, "", &samples);
+        assert_eq!(v["suspects"], json!(["com.acme.OrderCache$Entry"]));
+        assert_eq!(v["growing"].as_array().unwrap().len(), 2);
+    }
+}
@@ -0,0 +1,895 @@
+//! Hprof 模块 - 离线堆转储分析
+//!
+//! 🔬 法医取证：流式解析 HPROF 二进制格式 (`jmap -dump` / `HeapDumpOnOutOfMemoryError`)
+//!
+//! 聚焦两类最常见的泄漏线索：
+//! 1. **重复字符串**: 内容相同的 String (char[]/byte[])，按可节省的 retained size 排序
+//!    (String + 只被它引用的底层数组)
+//! 2. **超大集合**: 元素数过多的 HashMap/ArrayList 等，附 GC Root 引用路径 (底层数组为浅大小)
+//!
+//! 记录中的长度/元素数来自文件，读取前先检查不越过所在段，截断或损坏的转储返回错误。
+//!
+//! 多遍流式扫描，不整体加载文件：
+//! - Pass 1: 字符串表、类定义、GC Roots
+//! - Pass 2: 实例/数组 (引用关系、String 值、集合大小)
+//! - Pass 3: 仅读取 Top 重复值对应的数组内容用于预览
+
+use serde_json::{json, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufReader, Read};
+use std::path::Path;
+
+/// 报告中展示的条目数
+const TOP_N: usize = 10;
+/// GC Root 路径最大深度
+const MAX_PATH_DEPTH: usize = 32;
+/// 重复字符串预览长度
+const PREVIEW_CHARS: usize = 60;
+/// 对象头估算 (64 位 JVM，压缩指针)
+const OBJECT_HEADER: u64 = 16;
+
+// 顶层记录
+const TAG_UTF8: u8 = 0x01;
+const TAG_LOAD_CLASS: u8 = 0x02;
+const TAG_HEAP_DUMP: u8 = 0x0C;
+const TAG_HEAP_DUMP_SEGMENT: u8 = 0x1C;
+
+// 堆子记录
+const SUB_ROOT_UNKNOWN: u8 = 0xFF;
+const SUB_ROOT_JNI_GLOBAL: u8 = 0x01;
+const SUB_ROOT_JNI_LOCAL: u8 = 0x02;
+const SUB_ROOT_JAVA_FRAME: u8 = 0x03;
+const SUB_ROOT_NATIVE_STACK: u8 = 0x04;
+const SUB_ROOT_STICKY_CLASS: u8 = 0x05;
+const SUB_ROOT_THREAD_BLOCK: u8 = 0x06;
+const SUB_ROOT_MONITOR_USED: u8 = 0x07;
+const SUB_ROOT_THREAD_OBJECT: u8 = 0x08;
+const SUB_CLASS_DUMP: u8 = 0x20;
+const SUB_INSTANCE_DUMP: u8 = 0x21;
+const SUB_OBJ_ARRAY_DUMP: u8 = 0x22;
+const SUB_PRIM_ARRAY_DUMP: u8 = 0x23;
+
+// 基本类型
+const T_OBJECT: u8 = 2;
+const T_CHAR: u8 = 5;
+const T_BYTE: u8 = 8;
+
+/// 集合类 -> (元素计数字段, 底层数组字段)
+const COLLECTION_CLASSES: &[(&str, &str, &str)] = &[
+    ("java.util.HashMap", "size", "table"),
+    ("java.util.ArrayList", "size", "elementData"),
+    ("java.util.concurrent.ConcurrentHashMap", "baseCount", "table"),
+    ("java.util.Hashtable", "count", "table"),
+    ("java.util.LinkedList", "size", ""),
+];
+
+fn type_size(t: u8, id_size: usize) -> io::Result<usize> {
+    match t {
+        T_OBJECT => Ok(id_size),
+        4 | T_BYTE => Ok(1),   // boolean, byte
+        T_CHAR | 9 => Ok(2),   // char, short
+        6 | 10 => Ok(4),       // float, int
+        7 | 11 => Ok(8),       // double, long
+        _ => Err(invalid(format!("unknown basic type: {t}"))),
+    }
+}
+
+fn invalid(msg: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+// ============================================================================
+// 二进制读取
+// ============================================================================
+
+/// 带位置计数的大端读取器
+struct HprofReader<R: Read> {
+    inner: R,
+    id_size: usize,
+    pos: u64,
+}
+
+impl<R: Read> HprofReader<R> {
+    /// 读取 n 字节；按实际读到的数据增长，不按文件中的长度预分配
+    fn bytes(&mut self, n: u64) -> io::Result<Vec<u8>> {
+        let mut buf = Vec::new();
+        (&mut self.inner).take(n).read_to_end(&mut buf)?;
+        if buf.len() as u64 != n {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.pos += n;
+        Ok(buf)
+    }
+
+    /// `count` 个 `size` 字节的元素总长，不得超出当前段 (截断/损坏的转储返回 Err)
+    fn payload(&self, count: u64, size: usize, end: u64) -> io::Result<u64> {
+        count.checked_mul(size as u64)
+            .filter(|bytes| self.pos.checked_add(*bytes).is_some_and(|e| e <= end))
+            .ok_or_else(|| invalid(format!("record of {count} x {size} bytes overruns its segment at offset {}", self.pos)))
+    }
+
+    fn skip(&mut self, n: u64) -> io::Result<()> {
+        let copied = io::copy(&mut (&mut self.inner).take(n), &mut io::sink())?;
+        if copied != n {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        self.pos += n;
+        Ok(())
+    }
+
+    fn uint(&mut self, n: usize) -> io::Result<u64> {
+        let mut buf = [0u8; 8];
+        self.inner.read_exact(&mut buf[..n])?;
+        self.pos += n as u64;
+        Ok(buf[..n].iter().fold(0u64, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    fn u1(&mut self) -> io::Result<u8> {
+        self.uint(1).map(|v| v as u8)
+    }
+
+    fn u2(&mut self) -> io::Result<u16> {
+        self.uint(2).map(|v| v as u16)
+    }
+
+    fn u4(&mut self) -> io::Result<u32> {
+        self.uint(4).map(|v| v as u32)
+    }
+
+    fn id(&mut self) -> io::Result<u64> {
+        self.uint(self.id_size)
+    }
+
+    /// 读取下一个顶层标签，文件结束返回 None
+    fn next_tag(&mut self) -> io::Result<Option<u8>> {
+        let mut buf = [0u8; 1];
+        match self.inner.read(&mut buf)? {
+            0 => Ok(None),
+            _ => {
+                self.pos += 1;
+                Ok(Some(buf[0]))
+            }
+        }
+    }
+}
+
+/// 类定义 (CLASS DUMP)
+#[derive(Debug, Clone, Default)]
+struct ClassDump {
+    super_id: u64,
+    instance_size: u32,
+    /// 静态字段: (名称 ID, 类型, 值)
+    statics: Vec<(u64, u8, u64)>,
+    /// 实例字段: (名称 ID, 类型)，仅本类声明
+    fields: Vec<(u64, u8)>,
+}
+
+/// 解析出的记录
+enum HeapRecord {
+    Utf8 { id: u64, text: String },
+    LoadClass { class_id: u64, name_id: u64 },
+    Root { id: u64 },
+    Class { id: u64, dump: ClassDump },
+    Instance { id: u64, class_id: u64, data: Vec<u8> },
+    ObjArray { id: u64, class_id: u64, elements: Vec<u64> },
+    PrimArray { id: u64, elem_type: u8, data: Vec<u8> },
+}
+
+/// HPROF 文件头
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HprofHeader {
+    /// 格式版本，如 `JAVA PROFILE 1.0.2`
+    pub version: String,
+    pub id_size: usize,
+    /// 转储时间 (Unix 毫秒)
+    pub timestamp_ms: u64,
+}
+
+/// 只读取文件头 (不遍历记录)，用于快速识别堆转储
+pub fn read_header(path: &Path) -> io::Result<HprofHeader> {
+    let file = File::open(path)?;
+    let mut r = HprofReader { inner: BufReader::new(file), id_size: 4, pos: 0 };
+    header(&mut r)
+}
+
+/// 头部: "JAVA PROFILE 1.0.x\0" + u4 id_size + u8 timestamp
+fn header<R: Read>(r: &mut HprofReader<R>) -> io::Result<HprofHeader> {
+    let mut version = Vec::new();
+    loop {
+        let b = r.u1()?;
+        if b == 0 {
+            break;
+        }
+        version.push(b);
+        if version.len() > 32 {
+            return Err(invalid("not an HPROF file".to_string()));
+        }
+    }
+    if !version.starts_with(b"JAVA PROFILE") {
+        return Err(invalid("not an HPROF file".to_string()));
+    }
+    let id_size = r.u4()? as usize;
+    if id_size != 4 && id_size != 8 {
+        return Err(invalid(format!("unsupported identifier size: {id_size}")));
+    }
+    let timestamp_ms = r.uint(8)?;
+    Ok(HprofHeader { version: String::from_utf8_lossy(&version).into_owned(), id_size, timestamp_ms })
+}
+
+/// 流式遍历 HPROF 文件
+///
+/// `with_objects=false` 时跳过实例/数组内容 (Pass 1 只需要类和 Root)
+fn walk(path: &Path, with_objects: bool, f: &mut dyn FnMut(HeapRecord)) -> io::Result<usize> {
+    let file = File::open(path)?;
+    let mut r = HprofReader { inner: BufReader::with_capacity(1 << 20, file), id_size: 4, pos: 0 };
+    let id_size = header(&mut r)?.id_size;
+    r.id_size = id_size;
+
+    while let Some(tag) = r.next_tag()? {
+        r.skip(4)?; // time offset
+        let len = r.u4()? as u64;
+        match tag {
+            TAG_UTF8 => {
+                let id = r.id()?;
+                let text = String::from_utf8_lossy(&r.bytes(len.saturating_sub(id_size as u64))?).into_owned();
+                f(HeapRecord::Utf8 { id, text });
+            }
+            TAG_LOAD_CLASS => {
+                r.skip(4)?;
+                let class_id = r.id()?;
+                r.skip(4)?;
+                let name_id = r.id()?;
+                f(HeapRecord::LoadClass { class_id, name_id });
+            }
+            TAG_HEAP_DUMP | TAG_HEAP_DUMP_SEGMENT => {
+                let end = r.pos + len;
+                while r.pos < end {
+                    walk_sub_record(&mut r, end, with_objects, f)?;
+                }
+            }
+            _ => r.skip(len)?,
+        }
+    }
+
+    Ok(id_size)
+}
+
+/// 解析一条堆子记录；`end` 为所在段的结束位置，记录中的长度/元素数都不得越过它
+fn walk_sub_record<R: Read>(r: &mut HprofReader<R>, end: u64, with_objects: bool, f: &mut dyn FnMut(HeapRecord)) -> io::Result<()> {
+    let id_size = r.id_size;
+    let sub = r.u1()?;
+    match sub {
+        SUB_ROOT_UNKNOWN | SUB_ROOT_STICKY_CLASS | SUB_ROOT_MONITOR_USED => {
+            let id = r.id()?;
+            f(HeapRecord::Root { id });
+        }
+        SUB_ROOT_JNI_GLOBAL => {
+            let id = r.id()?;
+            r.skip(id_size as u64)?;
+            f(HeapRecord::Root { id });
+        }
+        SUB_ROOT_JNI_LOCAL | SUB_ROOT_JAVA_FRAME | SUB_ROOT_THREAD_OBJECT => {
+            let id = r.id()?;
+            r.skip(8)?;
+            f(HeapRecord::Root { id });
+        }
+        SUB_ROOT_NATIVE_STACK | SUB_ROOT_THREAD_BLOCK => {
+            let id = r.id()?;
+            r.skip(4)?;
+            f(HeapRecord::Root { id });
+        }
+        SUB_CLASS_DUMP => {
+            let id = r.id()?;
+            r.skip(4)?;
+            let super_id = r.id()?;
+            r.skip(5 * id_size as u64)?; // loader, signers, protection domain, reserved x2
+            let instance_size = r.u4()?;
+            // 常量池
+            for _ in 0..r.u2()? {
+                r.skip(2)?;
+                let t = r.u1()?;
+                r.skip(type_size(t, id_size)? as u64)?;
+            }
+            let mut dump = ClassDump { super_id, instance_size, ..Default::default() };
+            for _ in 0..r.u2()? {
+                let name_id = r.id()?;
+                let t = r.u1()?;
+                let value = r.uint(type_size(t, id_size)?)?;
+                dump.statics.push((name_id, t, value));
+            }
+            for _ in 0..r.u2()? {
+                let name_id = r.id()?;
+                let t = r.u1()?;
+                dump.fields.push((name_id, t));
+            }
+            f(HeapRecord::Class { id, dump });
+        }
+        SUB_INSTANCE_DUMP => {
+            let id = r.id()?;
+            r.skip(4)?;
+            let class_id = r.id()?;
+            let n = r.u4()? as u64;
+            let n = r.payload(n, 1, end)?;
+            if with_objects {
+                let data = r.bytes(n)?;
+                f(HeapRecord::Instance { id, class_id, data });
+            } else {
+                r.skip(n)?;
+            }
+        }
+        SUB_OBJ_ARRAY_DUMP => {
+            let id = r.id()?;
+            r.skip(4)?;
+            let n = r.u4()? as u64;
+            let class_id = r.id()?;
+            let bytes = r.payload(n, id_size, end)?;
+            if with_objects {
+                let mut elements = Vec::new();
+                for _ in 0..n {
+                    elements.push(r.id()?);
+                }
+                f(HeapRecord::ObjArray { id, class_id, elements });
+            } else {
+                r.skip(bytes)?;
+            }
+        }
+        SUB_PRIM_ARRAY_DUMP => {
+            let id = r.id()?;
+            r.skip(4)?;
+            let n = r.u4()? as u64;
+            let elem_type = r.u1()?;
+            let bytes = r.payload(n, type_size(elem_type, id_size)?, end)?;
+            if with_objects {
+                let data = r.bytes(bytes)?;
+                f(HeapRecord::PrimArray { id, elem_type, data });
+            } else {
+                r.skip(bytes)?;
+            }
+        }
+        other => return Err(invalid(format!("unsupported heap sub-record: 0x{other:02X}"))),
+    }
+    Ok(())
+}
+
+// ============================================================================
+// 分析
+// ============================================================================
+
+/// 展开后的类信息
+struct ClassInfo {
+    name: String,
+    instance_size: u32,
+    /// 实例数据布局: 本类字段在前，依次为父类字段
+    layout: Vec<(String, u8)>,
+    /// 命中的集合类型 (计数字段, 底层数组字段)
+    collection: Option<(&'static str, &'static str)>,
+}
+
+/// String 实例引用
+struct StringRef {
+    value_id: u64,
+    coder: u8,
+    shallow: u64,
+}
+
+/// 集合实例
+struct CollectionRef {
+    id: u64,
+    class_id: u64,
+    entries: u64,
+    backing_id: u64,
+}
+
+/// 重复字符串分组
+struct DupGroup {
+    count: u64,
+    /// 组内所有 String 的 retained size 之和
+    retained: u64,
+    /// 保留一份时仍占用的 retained size (第一份)
+    kept: u64,
+    sample_array: u64,
+    coder: u8,
+}
+
+/// 按布局逐字段读取实例数据: (字段名, 类型, 值)
+fn read_fields<'a>(layout: &'a [(String, u8)], data: &'a [u8], id_size: usize) -> impl Iterator<Item = (&'a str, u8, u64)> + 'a {
+    let mut offset = 0usize;
+    layout.iter().map_while(move |(name, t)| {
+        let size = type_size(*t, id_size).ok()?;
+        let raw = data.get(offset..offset + size)?;
+        offset += size;
+        let value = raw.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        Some((name.as_str(), *t, value))
+    })
+}
+
+/// 分析 HPROF 堆转储
+///
+/// min_entries: 集合元素数达到该阈值才会进入"超大集合"报告
+pub fn analyze_hprof(hprof_path: &str, min_entries: u64) -> Result<Value, Box<dyn std::error::Error>> {
+    let path = Path::new(hprof_path);
+    if !path.exists() {
+        return Err(format!("Heap dump not found: {hprof_path}").into());
+    }
+
+    // === Pass 1: 字符串表、类定义、GC Roots ===
+    let mut utf8: HashMap<u64, String> = HashMap::new();
+    let mut class_names: HashMap<u64, u64> = HashMap::new();
+    let mut dumps: HashMap<u64, ClassDump> = HashMap::new();
+    let mut roots: HashSet<u64> = HashSet::new();
+
+    let id_size = walk(path, false, &mut |rec| match rec {
+        HeapRecord::Utf8 { id, text } => { utf8.insert(id, text); }
+        HeapRecord::LoadClass { class_id, name_id } => { class_names.insert(class_id, name_id); }
+        HeapRecord::Root { id } => { roots.insert(id); }
+        HeapRecord::Class { id, dump } => { dumps.insert(id, dump); }
+        _ => {}
+    })?;
+
+    let name_of = |id: u64| utf8.get(&id).cloned().unwrap_or_else(|| format!("0x{id:x}"));
+    let mut classes: HashMap<u64, ClassInfo> = HashMap::new();
+    for (&class_id, dump) in &dumps {
+        let name = class_names.get(&class_id).map(|n| name_of(*n).replace('/', ".")).unwrap_or_else(|| format!("0x{class_id:x}"));
+        let mut layout = Vec::new();
+        let mut collection = None;
+        let mut cur = Some(class_id);
+        let mut depth = 0;
+        while let Some(cid) = cur.filter(|c| *c != 0 && depth < MAX_PATH_DEPTH) {
+            let Some(d) = dumps.get(&cid) else { break };
+            if collection.is_none() {
+                let cname = class_names.get(&cid).map(|n| name_of(*n).replace('/', "."));
+                collection = COLLECTION_CLASSES.iter()
+                    .find(|(c, _, _)| cname.as_deref() == Some(*c))
+                    .map(|(_, size, backing)| (*size, *backing));
+            }
+            layout.extend(d.fields.iter().map(|(n, t)| (name_of(*n), *t)));
+            cur = Some(d.super_id);
+            depth += 1;
+        }
+        classes.insert(class_id, ClassInfo { name, instance_size: dump.instance_size, layout, collection });
+    }
+
+    // 静态字段引用: 被引用对象 -> (类 ID, 字段名)
+    let mut field_names: Vec<String> = vec!["[]".to_string()];
+    let mut field_index: HashMap<String, u32> = HashMap::new();
+    let mut intern = |name: &str| -> u32 {
+        if let Some(&i) = field_index.get(name) {
+            return i;
+        }
+        field_names.push(name.to_string());
+        let i = (field_names.len() - 1) as u32;
+        field_index.insert(name.to_string(), i);
+        i
+    };
+    let mut referrers: HashMap<u64, (u64, u32)> = HashMap::new();
+    for (&class_id, dump) in &dumps {
+        for &(name_id, t, value) in &dump.statics {
+            if t == T_OBJECT && value != 0 {
+                let idx = intern(&name_of(name_id));
+                referrers.entry(value).or_insert((class_id, idx));
+            }
+        }
+    }
+
+    // === Pass 2: 实例与数组 ===
+    let mut object_class: HashMap<u64, u64> = HashMap::new();
+    let mut obj_array_len: HashMap<u64, u64> = HashMap::new();
+    let mut prim_arrays: HashMap<u64, (u64, u64)> = HashMap::new(); // id -> (hash, bytes)
+    let mut strings: Vec<StringRef> = Vec::new();
+    let mut collections: Vec<CollectionRef> = Vec::new();
+    let (mut instance_count, mut array_count) = (0usize, 0usize);
+
+    walk(path, true, &mut |rec| match rec {
+        HeapRecord::Instance { id, class_id, data } => {
+            instance_count += 1;
+            object_class.insert(id, class_id);
+            let Some(info) = classes.get(&class_id) else { return };
+            let is_string = info.name == "java.lang.String";
+            let mut string_ref = StringRef { value_id: 0, coder: 0, shallow: info.instance_size as u64 + OBJECT_HEADER };
+            let mut coll = info.collection.map(|_| CollectionRef { id, class_id, entries: 0, backing_id: 0 });
+
+            for (name, t, value) in read_fields(&info.layout, &data, id_size) {
+                if t == T_OBJECT && value != 0 {
+                    let idx = intern(name);
+                    referrers.entry(value).or_insert((id, idx));
+                }
+                if is_string {
+                    match name {
+                        "value" => string_ref.value_id = value,
+                        "coder" => string_ref.coder = value as u8,
+                        _ => {}
+                    }
+                }
+                if let (Some(c), Some((size_field, backing_field))) = (coll.as_mut(), info.collection) {
+                    if name == size_field {
+                        c.entries = value;
+                    } else if name == backing_field {
+                        c.backing_id = value;
+                    }
+                }
+            }
+
+            if is_string && string_ref.value_id != 0 {
+                strings.push(string_ref);
+            }
+            if let Some(c) = coll.filter(|c| c.entries >= min_entries) {
+                collections.push(c);
+            }
+        }
+        HeapRecord::ObjArray { id, class_id, elements } => {
+            array_count += 1;
+            object_class.insert(id, class_id);
+            obj_array_len.insert(id, elements.len() as u64);
+            for e in elements.into_iter().filter(|e| *e != 0) {
+                referrers.entry(e).or_insert((id, 0));
+            }
+        }
+        HeapRecord::PrimArray { id, elem_type, data } => {
+            array_count += 1;
+            if elem_type == T_CHAR || elem_type == T_BYTE {
+                let mut hasher = DefaultHasher::new();
+                data.hash(&mut hasher);
+                prim_arrays.insert(id, (hasher.finish(), data.len() as u64));
+            }
+        }
+        _ => {}
+    })?;
+
+    // === 重复字符串分组 ===
+    // retained size: String 本身 + 只被它引用的底层数组 (去重后共享数组的 String 只保留自身)
+    let mut array_owners: HashMap<u64, u32> = HashMap::new();
+    for s in &strings {
+        *array_owners.entry(s.value_id).or_default() += 1;
+    }
+    let mut groups: HashMap<u64, DupGroup> = HashMap::new();
+    for s in &strings {
+        let Some(&(hash, bytes)) = prim_arrays.get(&s.value_id) else { continue };
+        let retained = s.shallow + if array_owners[&s.value_id] == 1 { bytes + OBJECT_HEADER } else { 0 };
+        let g = groups.entry(hash).or_insert(DupGroup {
+            count: 0,
+            retained: 0,
+            kept: retained,
+            sample_array: s.value_id,
+            coder: s.coder,
+        });
+        g.count += 1;
+        g.retained += retained;
+    }
+    let mut dups: Vec<&DupGroup> = groups.values().filter(|g| g.count > 1).collect();
+    dups.sort_by_key(|g| std::cmp::Reverse(g.retained - g.kept));
+    dups.truncate(TOP_N);
+
+    // === Pass 3: 读取 Top 重复值的内容 ===
+    let wanted: HashSet<u64> = dups.iter().map(|g| g.sample_array).collect();
+    let mut previews: HashMap<u64, String> = HashMap::new();
+    if !wanted.is_empty() {
+        walk(path, true, &mut |rec| {
+            if let HeapRecord::PrimArray { id, elem_type, data } = rec {
+                if wanted.contains(&id) {
+                    let coder = dups.iter().find(|g| g.sample_array == id).map(|g| g.coder).unwrap_or(0);
+                    previews.insert(id, decode_string(elem_type, coder, &data));
+                }
+            }
+        })?;
+    }
+
+    // === 超大集合 + GC Root 路径 ===
+    collections.sort_by_key(|c| std::cmp::Reverse(c.entries));
+    collections.truncate(TOP_N);
+
+    let class_label = |obj: u64| -> String {
+        if let Some(info) = object_class.get(&obj).and_then(|c| classes.get(c)) {
+            short_name(&info.name).to_string()
+        } else if let Some(info) = classes.get(&obj) {
+            format!("class {}", short_name(&info.name))
+        } else {
+            format!("0x{obj:x}")
+        }
+    };
+
+    let root_path = |start: u64| -> String {
+        let mut hops = vec![class_label(start)];
+        let mut visited = HashSet::new();
+        let mut cur = start;
+        let mut reached_root = roots.contains(&cur);
+        while !reached_root && hops.len() < MAX_PATH_DEPTH && visited.insert(cur) {
+            let Some(&(parent, field)) = referrers.get(&cur) else { break };
+            hops.push(format!("{}.{}", class_label(parent), field_names[field as usize]));
+            cur = parent;
+            reached_root = roots.contains(&cur);
+        }
+        hops.reverse();
+        let chain = hops.join(" → ");
+        if reached_root {
+            format!("GC Root → {chain}")
+        } else {
+            format!("{chain} (未找到 GC Root，可能不可达)")
+        }
+    };
+
+    // === 报告 ===
+    let mut report = format!(
+        "## 🔬 堆转储分析: {}\n\n\
+        **实例**: {} | **数组**: {} | **类**: {} | **GC Roots**: {}\n\n",
+        hprof_path, instance_count, array_count, classes.len(), roots.len()
+    );
+
+    report.push_str(&format!("### 🔁 重复字符串 Top {TOP_N}\n\n"));
+    if dups.is_empty() {
+        report.push_str("✅ 未发现重复字符串\n\n");
+    } else {
+        report.push_str("| 内容 | 重复数 | Retained | 可节省 |\n|------|--------|----------|--------|\n");
+        for g in &dups {
+            let preview = previews.get(&g.sample_array).cloned().unwrap_or_default();
+            report.push_str(&format!(
+                "| `{}` | {} | {} | {} |\n",
+                preview.replace('|', "\\|").replace('`', "'"),
+                g.count,
+                format_bytes(g.retained),
+                format_bytes(g.retained - g.kept)
+            ));
+        }
+        report.push_str("\n💡 建议: 对高频重复值使用 `String.intern()`/缓存常量，或开启 `-XX:+UseStringDeduplication` (G1)\n\n");
+    }
+
+    report.push_str(&format!("### 📦 超大集合 (元素数 >= {min_entries})\n\n"));
+    if collections.is_empty() {
+        report.push_str("✅ 未发现超大集合\n");
+    } else {
+        for c in &collections {
+            let class_name = classes.get(&c.class_id).map(|i| i.name.as_str()).unwrap_or("?");
+            let backing = obj_array_len.get(&c.backing_id)
+                .map(|len| format!(", 底层数组 shallow ~{}", format_bytes(len * id_size as u64 + OBJECT_HEADER)))
+                .unwrap_or_default();
+            report.push_str(&format!(
+                "- **{}** @0x{:x} - 元素 {}{}\n  - 路径: {}\n",
+                class_name, c.id, c.entries, backing, root_path(c.id)
+            ));
+        }
+        report.push_str("\n💡 建议: 检查路径上的静态字段/缓存是否缺少容量上限或过期策略\n");
+    }
+
+    Ok(json!(report))
+}
+
+/// 解码字符串内容 (char[] 为 UTF-16BE；JDK9+ byte[] 按 coder 区分 Latin-1 / UTF-16)
+fn decode_string(elem_type: u8, coder: u8, data: &[u8]) -> String {
+    let text: String = if elem_type == T_CHAR {
+        let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else if coder == 1 {
+        let units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_le_bytes([c[0], c[1]])).collect();
+        String::from_utf16_lossy(&units)
+    } else {
+        data.iter().map(|b| *b as char).collect()
+    };
+    let mut preview: String = text.chars().take(PREVIEW_CHARS).collect();
+    if text.chars().count() > PREVIEW_CHARS {
+        preview.push('…');
+    }
+    preview.replace('\n', "\\n")
+}
+
+fn short_name(fqn: &str) -> &str {
+    fqn.rsplit('.').next().unwrap_or(fqn)
+}
+
+fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1 << 30 => format!("{:.1} GB", b as f64 / (1u64 << 30) as f64),
+        b if b >= 1 << 20 => format!("{:.1} MB", b as f64 / (1u64 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KB", b as f64 / (1u64 << 10) as f64),
+        b => format!("{b} B"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    /// 最小 HPROF 构造器 (id_size = 8)
+    #[derive(Default)]
+    struct Builder {
+        records: Vec<u8>,
+        heap: Vec<u8>,
+    }
+
+    impl Builder {
+        fn record(&mut self, tag: u8, body: &[u8]) {
+            self.records.push(tag);
+            self.records.extend(0u32.to_be_bytes());
+            self.records.extend((body.len() as u32).to_be_bytes());
+            self.records.extend(body);
+        }
+
+        fn utf8(&mut self, id: u64, text: &str) {
+            let mut body = id.to_be_bytes().to_vec();
+            body.extend(text.as_bytes());
+            self.record(TAG_UTF8, &body);
+        }
+
+        fn class(&mut self, id: u64, name_id: u64, super_id: u64, statics: &[(u64, u64)], fields: &[(u64, u8)]) {
+            let mut body = 0u32.to_be_bytes().to_vec();
+            body.extend(id.to_be_bytes());
+            body.extend(0u32.to_be_bytes());
+            body.extend(name_id.to_be_bytes());
+            self.record(TAG_LOAD_CLASS, &body);
+
+            let h = &mut self.heap;
+            h.push(SUB_CLASS_DUMP);
+            h.extend(id.to_be_bytes());
+            h.extend(0u32.to_be_bytes());
+            h.extend(super_id.to_be_bytes());
+            h.extend([0u8; 40]);
+            h.extend(16u32.to_be_bytes());
+            h.extend(0u16.to_be_bytes());
+            h.extend((statics.len() as u16).to_be_bytes());
+            for (name, value) in statics {
+                h.extend(name.to_be_bytes());
+                h.push(T_OBJECT);
+                h.extend(value.to_be_bytes());
+            }
+            h.extend((fields.len() as u16).to_be_bytes());
+            for (name, t) in fields {
+                h.extend(name.to_be_bytes());
+                h.push(*t);
+            }
+        }
+
+        fn instance(&mut self, id: u64, class_id: u64, data: &[u8]) {
+            let h = &mut self.heap;
+            h.push(SUB_INSTANCE_DUMP);
+            h.extend(id.to_be_bytes());
+            h.extend(0u32.to_be_bytes());
+            h.extend(class_id.to_be_bytes());
+            h.extend((data.len() as u32).to_be_bytes());
+            h.extend(data);
+        }
+
+        fn bytes_array(&mut self, id: u64, data: &[u8]) {
+            let h = &mut self.heap;
+            h.push(SUB_PRIM_ARRAY_DUMP);
+            h.extend(id.to_be_bytes());
+            h.extend(0u32.to_be_bytes());
+            h.extend((data.len() as u32).to_be_bytes());
+            h.push(T_BYTE);
+            h.extend(data);
+        }
+
+        fn obj_array(&mut self, id: u64, elements: &[u64]) {
+            let h = &mut self.heap;
+            h.push(SUB_OBJ_ARRAY_DUMP);
+            h.extend(id.to_be_bytes());
+            h.extend(0u32.to_be_bytes());
+            h.extend((elements.len() as u32).to_be_bytes());
+            h.extend(0u64.to_be_bytes());
+            for e in elements {
+                h.extend(e.to_be_bytes());
+            }
+        }
+
+        fn sticky_root(&mut self, id: u64) {
+            self.heap.push(SUB_ROOT_STICKY_CLASS);
+            self.heap.extend(id.to_be_bytes());
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            let heap = std::mem::take(&mut self.heap);
+            self.record(TAG_HEAP_DUMP_SEGMENT, &heap);
+            let mut out = b"JAVA PROFILE 1.0.2\0".to_vec();
+            out.extend(8u32.to_be_bytes());
+            out.extend(0u64.to_be_bytes());
+            out.extend(self.records);
+            out
+        }
+    }
+
+    fn string_data(value_id: u64) -> Vec<u8> {
+        let mut d = value_id.to_be_bytes().to_vec();
+        d.push(0); // coder = LATIN1
+        d
+    }
+
+    #[test]
+    fn test_duplicate_strings_and_large_collection() {
+        let mut b = Builder::default();
+        for (id, name) in [
+            (1, "java/lang/Object"), (2, "java/lang/String"), (3, "java/util/ArrayList"),
+            (4, "com/example/Cache"), (10, "value"), (11, "coder"), (12, "size"),
+            (13, "elementData"), (14, "ITEMS"),
+        ] {
+            b.utf8(id, name);
+        }
+        b.class(0x100, 1, 0, &[], &[]);
+        b.class(0x101, 2, 0x100, &[], &[(10, T_OBJECT), (11, T_BYTE)]);
+        b.class(0x102, 3, 0x100, &[], &[(13, T_OBJECT), (12, 10)]);
+        b.class(0x103, 4, 0x100, &[(14, 0x500)], &[]);
+        b.sticky_root(0x103);
+
+        // 3 个内容相同的字符串 + 1 个唯一字符串
+        for i in 0..3u64 {
+            b.bytes_array(0x200 + i, b"hello world");
+            b.instance(0x300 + i, 0x101, &string_data(0x200 + i));
+        }
+        b.bytes_array(0x210, b"unique");
+        b.instance(0x310, 0x101, &string_data(0x210));
+
+        // Cache.ITEMS -> ArrayList(size=20000)
+        b.obj_array(0x501, &[0x300, 0x301, 0x302]);
+        let mut list = 0x501u64.to_be_bytes().to_vec();
+        list.extend(20_000u32.to_be_bytes());
+        b.instance(0x500, 0x102, &list);
+
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&b.finish()).unwrap();
+
+        let result = analyze_hprof(file.path().to_str().unwrap(), 10_000).unwrap();
+        let report = result.as_str().unwrap();
+
+        assert!(report.contains("| `hello world` | 3 |"), "report: {report}");
+        assert!(!report.contains("unique"));
+        assert!(report.contains("**java.util.ArrayList** @0x500 - 元素 20000"));
+        assert!(report.contains("GC Root → class Cache.ITEMS → ArrayList"), "report: {report}");
+    }
+
+    #[test]
+    fn test_retained_size_and_malformed_counts() {
+        let mut b = Builder::default();
+        for (id, name) in [(1, "java/lang/Object"), (2, "java/lang/String"), (10, "value"), (11, "coder")] {
+            b.utf8(id, name);
+        }
+        b.class(0x100, 1, 0, &[], &[]);
+        b.class(0x101, 2, 0x100, &[], &[(10, T_OBJECT), (11, T_BYTE)]);
+        // 两个独立数组 + 两个共享同一数组的 String (已去重，只多占 String 本身)
+        b.bytes_array(0x200, b"dup");
+        b.bytes_array(0x201, b"dup");
+        b.instance(0x300, 0x101, &string_data(0x200));
+        b.instance(0x301, 0x101, &string_data(0x201));
+        b.instance(0x302, 0x101, &string_data(0x201));
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&b.finish()).unwrap();
+        let report = analyze_hprof(file.path().to_str().unwrap(), 10_000).unwrap();
+        // String 16 + 头 16 = 32；独占数组 3 + 16 = 19
+        assert!(report.as_str().unwrap().contains("| `dup` | 3 | 115 B | 64 B |"), "report: {report}");
+
+        // 元素数远超段长度: 返回错误而不是按文件中的数量分配内存
+        let mut b = Builder::default();
+        b.heap.push(SUB_OBJ_ARRAY_DUMP);
+        b.heap.extend(0x400u64.to_be_bytes());
+        b.heap.extend(0u32.to_be_bytes());
+        b.heap.extend(u32::MAX.to_be_bytes());
+        b.heap.extend(0u64.to_be_bytes());
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&b.finish()).unwrap();
+        let err = analyze_hprof(file.path().to_str().unwrap(), 10_000).unwrap_err();
+        assert!(err.to_string().contains("overruns its segment"), "{err}");
+
+        // 截断的 UTF8 记录
+        let mut bytes = Builder::default().finish();
+        bytes.push(TAG_UTF8);
+        bytes.extend(0u32.to_be_bytes());
+        bytes.extend(u32::MAX.to_be_bytes());
+        bytes.extend(1u64.to_be_bytes());
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(&bytes).unwrap();
+        assert!(analyze_hprof(file.path().to_str().unwrap(), 10_000).is_err());
+    }
+
+    #[test]
+    fn test_rejects_non_hprof() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"not a heap dump").unwrap();
+        assert!(analyze_hprof(file.path().to_str().unwrap(), 10_000).is_err());
+    }
+
+    #[test]
+    fn test_decode_string() {
+        assert_eq!(decode_string(T_BYTE, 0, b"abc"), "abc");
+        assert_eq!(decode_string(T_CHAR, 0, &[0, b'h', 0, b'i']), "hi");
+        assert_eq!(decode_string(T_BYTE, 1, &[b'o', 0, b'k', 0]), "ok");
+    }
+}
@@ -0,0 +1,135 @@
+//! 报告本地化 (v9.6)
+//!
+//! 中文是源语言：规则描述、检查清单、报告标题都直接以中文写在代码里，
+//! 同时带一个稳定的消息 ID (如 `rule.N_PLUS_ONE`、`checklist.0.title`、`report.scan.title`)。
+//! 其他语言通过 `resources/i18n/<lang>.yaml` 目录提供 `ID -> 文本` 映射，
+//! 缺失的条目回退到中文原文。
+//!
+//! 新增语言: 添加 `Lang` 变体 + 对应的 yaml 目录文件即可。
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+
+/// 输出语言
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Lang {
+    /// 中文 (默认)
+    #[default]
+    Zh,
+    /// English
+    En,
+}
+
+type Catalog = HashMap<String, String>;
+
+static EN: Lazy<Catalog> = Lazy::new(|| load(include_str!("../resources/i18n/en.yaml")));
+
+static CURRENT: AtomicU8 = AtomicU8::new(Lang::Zh as u8);
+
+fn load(src: &str) -> Catalog {
+    serde_yaml::from_str(src).expect("invalid i18n catalog")
+}
+
+/// 设置全局输出语言 (CLI 启动时调用一次)
+pub fn set_lang(lang: Lang) {
+    CURRENT.store(lang as u8, Ordering::Relaxed);
+}
+
+/// 当前输出语言
+pub fn lang() -> Lang {
+    match CURRENT.load(Ordering::Relaxed) {
+        x if x == Lang::En as u8 => Lang::En,
+        _ => Lang::Zh,
+    }
+}
+
+fn catalog(lang: Lang) -> Option<&'static Catalog> {
+    match lang {
+        Lang::Zh => None,
+        Lang::En => Some(&EN),
+    }
+}
+
+/// 在指定语言目录中查找消息 (中文无目录，始终返回 None)
+pub fn lookup_in(lang: Lang, id: &str) -> Option<&'static str> {
+    catalog(lang).and_then(|c| c.get(id)).map(String::as_str)
+}
+
+/// 在当前语言目录中查找消息
+pub fn lookup(id: &str) -> Option<&'static str> {
+    lookup_in(lang(), id)
+}
+
+/// 获取本地化文本，缺失时回退到中文原文
+pub fn text(id: &str, zh: &'static str) -> &'static str {
+    lookup(id).unwrap_or(zh)
+}
+
+/// 获取本地化模板并替换 `{name}` 占位符
+pub fn format(id: &str, zh: &'static str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = text(id, zh).to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zh_has_no_catalog() {
+        assert_eq!(lookup_in(Lang::Zh, "report.scan.title"), None);
+    }
+
+    #[test]
+    fn test_en_catalog_lookup() {
+        assert_eq!(lookup_in(Lang::En, "rule.N_PLUS_ONE"), Some("Method call inside a for loop (possible N+1 problem)"));
+        assert_eq!(lookup_in(Lang::En, "no.such.id"), None);
+    }
+
+    #[test]
+    fn test_format_placeholders() {
+        // 默认语言为中文，模板取原文
+        let s = format("config.DB_POOL_SMALL.value", "数据库连接池过小: {size} (建议 >= 10)", &[("size", &3)]);
+        assert_eq!(s, "数据库连接池过小: 3 (建议 >= 10)");
+    }
+
+    #[test]
+    fn test_en_templates_keep_placeholders() {
+        // 英文模板必须保留与中文原文相同的占位符
+        let tpl = lookup_in(Lang::En, "config.DB_POOL_SMALL.value").unwrap();
+        assert!(tpl.contains("{size}"));
+    }
+
+    #[test]
+    fn test_note_templates_keep_placeholders() {
+        // 扫描后附加到描述的标注，英文中不能残留中文
+        let notes: &[(&str, &[&str])] = &[
+            ("note.layer_weight", &["layer"]),
+            ("note.jdbc_batch", &["location"]),
+            ("note.jdbc_batch.missing", &["code", "config"]),
+            ("note.jdbc_batch.unset", &["file"]),
+            ("note.jdbc_batch.no_config", &[]),
+            ("note.log_level", &["level", "location"]),
+            ("note.log_level.concat", &["count", "calls"]),
+            ("note.severity_override", &["from", "to"]),
+            ("note.generated", &["label", "origin"]),
+            ("note.generated.unmapped", &["label"]),
+            ("note.javap_confirmed", &["evidence"]),
+            ("note.call_chain", &[]),
+            ("note.requires_new.outer", &["chain"]),
+            ("note.requires_new.no_timeout", &[]),
+            ("note.separator", &[]),
+            ("note.unreachable", &["method"]),
+        ];
+        for (id, placeholders) in notes {
+            let tpl = lookup_in(Lang::En, id).unwrap_or_else(|| panic!("missing {id}"));
+            assert!(placeholders.iter().all(|p| tpl.contains(&format!("{{{p}}}"))), "{id}: {tpl}");
+            assert!(tpl.chars().all(|c| !('\u{4e00}'..='\u{9fff}').contains(&c) && c != '；'), "{id}: {tpl}");
+        }
+    }
+}
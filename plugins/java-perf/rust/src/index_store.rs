@@ -0,0 +1,111 @@
+//! Index Store - 项目索引持久化 (v9.6)
+//!
+//! `java-perf index` 把 Phase 1 产物 (符号表 + 调用图) 以 bincode 写入
+//! `.java-perf/index.bin`；`scan` / `verify_issue` / `get_call_chain`
+//! 在索引新鲜时直接加载，跳过全量重建。
+//!
+//! 新鲜度: 版本号 + 每个 `.java` 文件的 (相对路径, mtime, 大小) 必须完全一致，
+//! 任一文件新增/删除/修改都会使索引失效 (回退到内存中重建，不自动覆盖)。
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::ast_engine::ProjectIndex;
+
+/// 索引文件 (相对项目根目录)
+pub const INDEX_FILE: &str = ".java-perf/index.bin";
+
+/// 单个源文件的指纹
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct FileStamp {
+    path: String,
+    mtime_nanos: u128,
+    len: u64,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredIndex {
+    version: String,
+    files: Vec<FileStamp>,
+    index: ProjectIndex,
+}
+
+/// 项目的索引文件路径
+pub fn index_path(root: &Path) -> PathBuf {
+    root.join(INDEX_FILE)
+}
+
+/// 按当前源文件计算指纹 (排序后可直接比较)
+fn stamps(root: &Path, java_files: &[&Path]) -> Vec<FileStamp> {
+    let mut stamps: Vec<FileStamp> = java_files.iter()
+        .filter_map(|file| {
+            let meta = std::fs::metadata(file).ok()?;
+            let mtime_nanos = meta.modified().ok()?.duration_since(UNIX_EPOCH).ok()?.as_nanos();
+            let rel = file.strip_prefix(root).unwrap_or(file);
+            Some(FileStamp { path: rel.to_string_lossy().to_string(), mtime_nanos, len: meta.len() })
+        })
+        .collect();
+    stamps.sort_by(|a, b| a.path.cmp(&b.path));
+    stamps
+}
+
+/// 加载新鲜的索引；不存在、损坏、版本不同或源文件有变化时返回 None
+pub fn load_fresh(root: &Path, java_files: &[&Path]) -> Option<ProjectIndex> {
+    let bytes = std::fs::read(index_path(root)).ok()?;
+    let stored: StoredIndex = bincode::deserialize(&bytes).ok()?;
+    (stored.version == env!("CARGO_PKG_VERSION") && stored.files == stamps(root, java_files))
+        .then_some(stored.index)
+}
+
+/// 写入索引，返回索引文件路径
+pub fn save(root: &Path, java_files: &[&Path], index: ProjectIndex) -> Result<PathBuf, Box<dyn std::error::Error>> {
+    let stored = StoredIndex {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        files: stamps(root, java_files),
+        index,
+    };
+    let path = index_path(root);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    // 先写临时文件再 rename，避免并发读取到半个索引
+    let tmp = path.with_extension("bin.tmp");
+    std::fs::write(&tmp, bincode::serialize(&stored)?)?;
+    std::fs::rename(&tmp, &path)?;
+    Ok(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_table::TypeInfo;
+
+    #[test]
+    fn test_index_roundtrip_and_staleness() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("A.java");
+        std::fs::write(&file, "class A {}").unwrap();
+        let files = [file.as_path()];
+
+        let mut index = ProjectIndex::default();
+        index.symbol_table.register_class_fqn(TypeInfo::new("A", file.clone(), 1));
+        save(dir.path(), &files, index).unwrap();
+
+        let loaded = load_fresh(dir.path(), &files).expect("fresh index");
+        assert!(loaded.symbol_table.classes.contains_key("A"));
+
+        // 新增文件 → 失效
+        let other = dir.path().join("B.java");
+        std::fs::write(&other, "class B {}").unwrap();
+        assert!(load_fresh(dir.path(), &[file.as_path(), other.as_path()]).is_none());
+
+        // 修改内容 (大小变化) → 失效
+        std::fs::write(&file, "class A { int x; }").unwrap();
+        assert!(load_fresh(dir.path(), &files).is_none());
+
+        // 损坏 → 失效
+        std::fs::write(index_path(dir.path()), b"garbage").unwrap();
+        assert!(load_fresh(dir.path(), &files).is_none());
+    }
+}
@@ -0,0 +1,149 @@
+//! 问题位置深链接 (v9.6)
+//!
+//! Markdown 报告中把问题的 `file:line` 渲染为可点击链接 (`scan --link-format`)：
+//! - `idea`: `idea://open?file=<绝对路径>&line=N` (JetBrains IDE / Toolbox 协议)
+//! - `vscode`: `vscode://file/<绝对路径>:N`
+//! - `github`: `<repo-url>/blob/<commit>/<仓库内路径>#LN`；commit 依次取 `--commit`、`--meta git_sha=`、
+//!   `git rev-parse HEAD`，扫描目录是仓库子目录时自动补上仓库内前缀
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// 链接格式
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkFormat {
+    /// JetBrains IDE (`idea://open?file=...&line=...`)
+    Idea,
+    /// Visual Studio Code (`vscode://file/...:line`)
+    Vscode,
+    /// GitHub blob URL (需要 `--repo-url`)
+    Github,
+}
+
+/// 问题位置链接生成器
+#[derive(Debug, Clone)]
+pub struct IssueLinks {
+    format: LinkFormat,
+    /// 问题文件路径的基准目录 (绝对路径)
+    root: PathBuf,
+    /// GitHub 仓库地址 (不含结尾 `/`)
+    repo_url: String,
+    commit: String,
+    /// 扫描根目录在仓库内的前缀 (`services/order/`，仓库根目录时为空)
+    prefix: String,
+}
+
+impl IssueLinks {
+    /// 按扫描路径创建；`github` 格式需要 `repo_url`
+    pub fn new(format: LinkFormat, scan_path: &Path, repo_url: Option<&str>, commit: Option<&str>) -> Result<Self, String> {
+        let path = std::fs::canonicalize(scan_path).unwrap_or_else(|_| scan_path.to_path_buf());
+        // 单文件扫描时问题的 file 只有文件名
+        let root = if path.is_file() {
+            path.parent().map(Path::to_path_buf).unwrap_or_default()
+        } else {
+            path
+        };
+        let (repo_url, commit, prefix) = match format {
+            LinkFormat::Github => {
+                let repo_url = repo_url
+                    .map(|u| u.trim_end_matches('/').to_string())
+                    .filter(|u| !u.is_empty())
+                    .ok_or("--link-format github requires --repo-url")?;
+                let commit = commit
+                    .map(str::to_string)
+                    .or_else(|| git(&root, &["rev-parse", "HEAD"]))
+                    .unwrap_or_else(|| "HEAD".to_string());
+                let prefix = git(&root, &["rev-parse", "--show-prefix"]).unwrap_or_default();
+                (repo_url, commit, prefix)
+            }
+            LinkFormat::Idea | LinkFormat::Vscode => Default::default(),
+        };
+        Ok(Self { format, root, repo_url, commit, prefix })
+    }
+
+    /// 问题位置的 URL
+    pub fn url(&self, file: &str, line: usize) -> String {
+        match self.format {
+            LinkFormat::Idea => format!("idea://open?file={}&line={line}", encode(&self.absolute(file), false)),
+            LinkFormat::Vscode => format!("vscode://file/{}:{line}", encode(self.absolute(file).trim_start_matches('/'), true)),
+            LinkFormat::Github => format!(
+                "{}/blob/{}/{}#L{line}",
+                self.repo_url,
+                self.commit,
+                encode(&format!("{}{file}", self.prefix), true)
+            ),
+        }
+    }
+
+    /// Markdown 链接: [`file:line`](url)
+    pub fn markdown(&self, file: &str, line: usize) -> String {
+        format!("[`{file}:{line}`]({})", self.url(file, line))
+    }
+
+    fn absolute(&self, file: &str) -> String {
+        self.root.join(file).to_string_lossy().replace('\\', "/")
+    }
+}
+
+/// 在扫描目录执行 git 命令，取第一行输出
+fn git(root: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git").arg("-C").arg(root).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 百分号编码 (保留 unreserved 字符；`keep_slash` 时保留路径分隔符)
+fn encode(s: &str, keep_slash: bool) -> String {
+    let mut out = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => out.push(byte as char),
+            b'/' | b':' if keep_slash => out.push(byte as char),
+            _ => out.push_str(&format!("%{byte:02X}")),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn links(format: LinkFormat) -> IssueLinks {
+        IssueLinks {
+            format,
+            root: PathBuf::from("/work/my shop"),
+            repo_url: "https://github.com/acme/shop".to_string(),
+            commit: "abc123".to_string(),
+            prefix: "services/order/".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_link_formats() {
+        let file = "src/main/java/OrderService.java";
+        assert_eq!(
+            links(LinkFormat::Idea).url(file, 42),
+            "idea://open?file=%2Fwork%2Fmy%20shop%2Fsrc%2Fmain%2Fjava%2FOrderService.java&line=42"
+        );
+        assert_eq!(
+            links(LinkFormat::Vscode).url(file, 42),
+            "vscode://file/work/my%20shop/src/main/java/OrderService.java:42"
+        );
+        assert_eq!(
+            links(LinkFormat::Github).markdown(file, 42),
+            "[`src/main/java/OrderService.java:42`](https://github.com/acme/shop/blob/abc123/services/order/src/main/java/OrderService.java#L42)"
+        );
+    }
+
+    #[test]
+    fn test_github_requires_repo_url() {
+        let dir = std::env::temp_dir();
+        assert!(IssueLinks::new(LinkFormat::Github, &dir, None, None).is_err());
+        let links = IssueLinks::new(LinkFormat::Github, &dir, Some("https://github.com/acme/shop/"), Some("v1.2")).unwrap();
+        assert!(links.url("A.java", 3).starts_with("https://github.com/acme/shop/blob/v1.2/"));
+        assert!(IssueLinks::new(LinkFormat::Idea, &dir, None, None).is_ok());
+    }
+}
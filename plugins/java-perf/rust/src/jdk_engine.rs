@@ -1,11 +1,90 @@
 //! JDK Engine - JDK CLI 工具集成
 //! 
-//! 🔬 法医取证：jstack, javap, jmap
+//! 🔬 法医取证：jstack, javap, jmap；进程发现：jps / jcmd -l
+//!
+//! v9.6: 目标 JVM 可在 Docker 容器 / Kubernetes Pod 中 ([`JvmHost`])，
+//! JDK 工具通过 `docker exec` / `kubectl exec` 在容器内运行，堆转储再复制回本地。
+//!
+//! v9.6: 生产安全 ([`Safety`])：所有子进程带超时 (超时 kill)；
+//! 默认使用不触发 Full GC 的直方图，`histo:live` 与堆转储等会造成 STW 停顿的操作需要 `--force`。
 
+use serde::Serialize;
 use serde_json::{json, Value};
-use std::process::Command;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+use std::time::{Duration, Instant};
 use std::env;
 
+use crate::{bytecode, heap_trend, thread_dump};
+
+/// JDK 工具默认超时
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// 辅助探测命令 (which / stat / id / ps) 的超时
+const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// 生产环境安全选项 (v9.6)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Safety {
+    /// 允许会造成 STW 停顿的操作 (`jmap -histo:live` 触发 Full GC、堆转储)
+    pub force: bool,
+    /// 单个 JDK 工具子进程的超时，超时后 kill
+    pub timeout: Duration,
+}
+
+impl Default for Safety {
+    fn default() -> Self {
+        Self { force: false, timeout: DEFAULT_TIMEOUT }
+    }
+}
+
+impl Safety {
+    /// 停顿型操作的闸门: 未 `--force` 时返回影响说明
+    fn require_force(&self, operation: &str, impact: &str) -> Result<(), String> {
+        if self.force {
+            return Ok(());
+        }
+        Err(format!("{operation} {impact}。生产环境请在低峰期执行，确认影响后加 --force"))
+    }
+}
+
+/// 运行子进程，超过 `timeout` 时 kill 并返回 `TimedOut` (attach 卡住的 JVM 不会挂住整个会话)
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> std::io::Result<Output> {
+    let mut child = cmd.stdin(Stdio::null()).stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+    // 独立线程读取输出，避免管道写满后子进程阻塞
+    let drain = |pipe: Option<Box<dyn Read + Send>>| std::thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut buf);
+        }
+        buf
+    });
+    let stdout = drain(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = drain(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                format!("超时 (>{}s)，已终止子进程；可用 --timeout 调大", timeout.as_secs()),
+            ));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+    Ok(Output {
+        status,
+        stdout: stdout.join().unwrap_or_default(),
+        stderr: stderr.join().unwrap_or_default(),
+    })
+}
+
 /// 检查 JDK 是否可用 (旧版兼容)
 #[allow(dead_code)]
 pub fn check_jdk_available() -> bool {
@@ -22,8 +101,7 @@ pub fn check_tool_available(tool: &str) -> bool {
 fn get_java_home() -> Option<String> {
     env::var("JAVA_HOME").ok().or_else(|| {
         // macOS: 尝试 /usr/libexec/java_home
-        Command::new("/usr/libexec/java_home")
-            .output()
+        run_with_timeout(&mut Command::new("/usr/libexec/java_home"), PROBE_TIMEOUT)
             .ok()
             .and_then(|out| {
                 if out.status.success() {
@@ -37,7 +115,7 @@ fn get_java_home() -> Option<String> {
 
 /// 获取 JDK 工具路径
 /// 优先使用 JAVA_HOME，备选使用 $PATH 中的工具
-fn get_jdk_tool(tool: &str) -> Option<String> {
+pub fn get_jdk_tool(tool: &str) -> Option<String> {
     // 方案 1: 使用 JAVA_HOME
     if let Some(home) = get_java_home() {
         let path = format!("{home}/bin/{tool}");
@@ -47,9 +125,7 @@ fn get_jdk_tool(tool: &str) -> Option<String> {
     }
     
     // 方案 2: 使用 which 命令在 $PATH 中查找
-    Command::new("which")
-        .arg(tool)
-        .output()
+    run_with_timeout(Command::new("which").arg(tool), PROBE_TIMEOUT)
         .ok()
         .and_then(|out| {
             if out.status.success() {
@@ -60,89 +136,203 @@ fn get_jdk_tool(tool: &str) -> Option<String> {
         })
 }
 
-/// 分析线程 Dump
-pub fn analyze_thread_dump(pid: u32) -> Result<Value, Box<dyn std::error::Error>> {
+/// 容器内查找 JDK 工具: 优先 `$JAVA_HOME/bin/<tool>`，否则容器 $PATH (`sh -c SCRIPT <tool> <args...>`)
+const CONTAINER_TOOL_SCRIPT: &str = r#"exec "${JAVA_HOME:+$JAVA_HOME/bin/}$0" "$@""#;
+
+/// 取证目标 JVM 的运行位置 (v9.6)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum JvmHost {
+    /// 本机进程
+    #[default]
+    Local,
+    /// Docker 容器；`user` 为空时使用目标 JVM 进程的属主 (attach 要求同一用户)
+    Docker { container: String, user: Option<String> },
+    /// Kubernetes Pod；`kubectl exec` 无法切换用户，JVM 须以容器默认用户运行
+    Kubernetes { pod: String, namespace: Option<String>, container: Option<String> },
+}
+
+impl JvmHost {
+    /// 由 `--container` / `--pod` / `--namespace` / `--user` 组合出目标位置
+    ///
+    /// 有 `--pod` 时 `--container` 为 Pod 内的容器名 (与 kubectl 一致)，否则为 Docker 容器。
+    ///
+    /// 这些值原样进入 `docker exec` / `kubectl exec` 命令行，以 `-` 开头的值会被当作选项，直接拒绝。
+    pub fn new(container: Option<String>, pod: Option<String>, namespace: Option<String>, user: Option<String>) -> Result<Self, String> {
+        for (flag, value) in [("--container", &container), ("--pod", &pod), ("--namespace", &namespace), ("--user", &user)] {
+            if value.as_deref().is_some_and(|v| v.is_empty() || v.starts_with('-')) {
+                return Err(format!("{flag} 不能为空或以 '-' 开头"));
+            }
+        }
+        match (pod, container) {
+            (Some(_), _) if user.is_some() => Err("--user 仅支持 Docker: kubectl exec 无法切换用户".to_string()),
+            (Some(pod), container) => Ok(JvmHost::Kubernetes { pod, namespace, container }),
+            (None, _) if namespace.is_some() => Err("--namespace 需要与 --pod 一起使用".to_string()),
+            (None, Some(container)) => Ok(JvmHost::Docker { container, user }),
+            (None, None) if user.is_some() => Err("--user 需要与 --container 一起使用".to_string()),
+            (None, None) => Ok(JvmHost::Local),
+        }
+    }
+
+    /// 访问控制使用的目标名: Docker 容器名，Pod 为 `namespace/pod` (未指定命名空间时为 `pod`)，本机为 None
+    pub fn target(&self) -> Option<String> {
+        match self {
+            JvmHost::Local => None,
+            JvmHost::Docker { container, .. } => Some(container.clone()),
+            JvmHost::Kubernetes { pod, namespace, .. } => Some(match namespace {
+                Some(ns) => format!("{ns}/{pod}"),
+                None => pod.clone(),
+            }),
+        }
+    }
+
+    /// 报告标题中的位置标注 (本机为空)
+    fn label(&self) -> String {
+        match self {
+            JvmHost::Local => String::new(),
+            JvmHost::Docker { container, .. } => format!(" @ docker:{container}"),
+            JvmHost::Kubernetes { pod, namespace, container } => format!(
+                " @ pod:{}{pod}{}",
+                namespace.as_deref().map(|ns| format!("{ns}/")).unwrap_or_default(),
+                container.as_deref().map(|c| format!(" ({c})")).unwrap_or_default(),
+            ),
+        }
+    }
+
+    /// 在目标位置运行 JDK 工具 (超时 kill)；`pid` 用于容器内确定 attach 用户
+    fn run_tool(&self, tool: &str, args: &[String], pid: Option<u32>, timeout: Duration) -> Result<Output, Box<dyn std::error::Error>> {
+        let (program, argv) = match self {
+            JvmHost::Local => {
+                let path = get_jdk_tool(tool)
+                    .ok_or_else(|| format!("{tool} 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 {tool} 在 $PATH 中"))?;
+                (path, args.to_vec())
+            }
+            JvmHost::Docker { user, .. } => {
+                let user = user.clone().or_else(|| pid.and_then(|pid| self.jvm_owner(pid)));
+                self.exec_argv(user.as_deref(), &self.tool_argv(tool, args))
+            }
+            JvmHost::Kubernetes { .. } => {
+                if let Some(pid) = pid {
+                    self.check_exec_user(pid)?;
+                }
+                self.exec_argv(None, &self.tool_argv(tool, args))
+            }
+        };
+        run_with_timeout(Command::new(program).args(argv), timeout).map_err(|e| format!("{tool}: {e}").into())
+    }
+
+    fn tool_argv(&self, tool: &str, args: &[String]) -> Vec<String> {
+        ["sh", "-c", CONTAINER_TOOL_SCRIPT, tool].iter().map(|s| s.to_string()).chain(args.iter().cloned()).collect()
+    }
+
+    /// `docker exec` / `kubectl exec` 命令行 (本机直接运行 argv)
+    fn exec_argv(&self, user: Option<&str>, argv: &[String]) -> (String, Vec<String>) {
+        let mut args: Vec<String> = vec!["exec".to_string()];
+        let program = match self {
+            JvmHost::Local => return (argv[0].clone(), argv[1..].to_vec()),
+            JvmHost::Docker { container, .. } => {
+                if let Some(user) = user {
+                    args.extend(["-u".to_string(), user.to_string()]);
+                }
+                args.push(container.clone());
+                "docker"
+            }
+            JvmHost::Kubernetes { pod, namespace, container } => {
+                if let Some(ns) = namespace {
+                    args.extend(["-n".to_string(), ns.clone()]);
+                }
+                args.push(pod.clone());
+                if let Some(container) = container {
+                    args.extend(["-c".to_string(), container.clone()]);
+                }
+                args.push("--".to_string());
+                "kubectl"
+            }
+        };
+        args.extend(argv.iter().cloned());
+        (program.to_string(), args)
+    }
+
+    /// 在容器内运行普通命令 (非 JDK 工具)，返回去除首尾空白的 stdout
+    fn exec_shell(&self, user: Option<&str>, argv: &[&str]) -> Option<String> {
+        let argv: Vec<String> = argv.iter().map(|s| s.to_string()).collect();
+        let (program, args) = self.exec_argv(user, &argv);
+        let output = run_with_timeout(Command::new(program).args(args), PROBE_TIMEOUT).ok()?;
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    /// 容器内目标 JVM 进程的属主 uid
+    fn jvm_owner(&self, pid: u32) -> Option<String> {
+        self.exec_shell(Some("0"), &["stat", "-c", "%u", &format!("/proc/{pid}")])
+    }
+
+    /// Pod 中 exec 用户与 JVM 属主不同时，attach 必然失败，提前给出修复建议
+    fn check_exec_user(&self, pid: u32) -> Result<(), String> {
+        let exec_uid = self.exec_shell(None, &["id", "-u"]);
+        let owner = self.exec_shell(None, &["stat", "-c", "%u", &format!("/proc/{pid}")]);
+        match (exec_uid, owner) {
+            (Some(exec_uid), Some(owner)) if exec_uid != owner => Err(format!(
+                "kubectl exec 用户 (uid {exec_uid}) 与 JVM 属主 (uid {owner}) 不一致，attach 会失败: \
+                让容器以 JVM 同一用户运行 (securityContext.runAsUser)，或使用 kubectl debug --target 临时容器"
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// 把容器内的文件复制到本地，并删除容器内的副本
+    fn copy_out(&self, remote: &str, local: &str, timeout: Duration) -> Result<(), Box<dyn std::error::Error>> {
+        let mut cmd = match self {
+            JvmHost::Local => return Ok(()),
+            JvmHost::Docker { container, .. } => {
+                let mut cmd = Command::new("docker");
+                cmd.args(["cp", &format!("{container}:{remote}"), local]);
+                cmd
+            }
+            JvmHost::Kubernetes { pod, namespace, container } => {
+                let mut cmd = Command::new("kubectl");
+                if let Some(ns) = namespace {
+                    cmd.args(["-n", ns]);
+                }
+                cmd.args(["cp", &format!("{pod}:{remote}"), local]);
+                if let Some(container) = container {
+                    cmd.args(["-c", container]);
+                }
+                cmd
+            }
+        };
+        let output = run_with_timeout(&mut cmd, timeout);
+        // 复制失败也清理容器内的转储，避免占满容器磁盘
+        self.exec_shell(Some("0").filter(|_| matches!(self, JvmHost::Docker { .. })), &["rm", "-f", remote]);
+        let output = output.map_err(|e| format!("copy {remote}: {e}"))?;
+        if !output.status.success() {
+            return Err(format!("copy {remote} failed: {}", String::from_utf8_lossy(&output.stderr)).into());
+        }
+        Ok(())
+    }
+}
+
+/// 分析线程 Dump: 解析为结构化线程模型 (见 [`thread_dump`])
+pub fn analyze_thread_dump(pid: u32, host: &JvmHost, safety: &Safety) -> Result<Value, Box<dyn std::error::Error>> {
     // 输入验证
     if pid == 0 {
         return Err("Invalid PID: 0 is not a valid process ID".into());
     }
     
-    let jstack = get_jdk_tool("jstack").ok_or("jstack 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 jstack 在 $PATH 中")?;
-    
-    let output = Command::new(&jstack)
-        .arg(pid.to_string())
-        .output()?;
+    let output = host.run_tool("jstack", &[pid.to_string()], Some(pid), safety.timeout)?;
     
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("jstack failed: {stderr}").into());
     }
     
-    let dump = String::from_utf8_lossy(&output.stdout).to_string();
-    
-    // 分析线程状态
-    let mut blocked = 0;
-    let mut waiting = 0;
-    let mut runnable = 0;
-    let mut deadlock = false;
-    
-    for line in dump.lines() {
-        if line.contains("BLOCKED") {
-            blocked += 1;
-        } else if line.contains("WAITING") || line.contains("TIMED_WAITING") {
-            waiting += 1;
-        } else if line.contains("RUNNABLE") {
-            runnable += 1;
-        }
-        
-        if line.contains("Found") && line.contains("deadlock") {
-            deadlock = true;
-        }
-    }
-    
-    let mut report = format!(
-        "## 🔬 线程 Dump 分析 (PID: {pid})\n\n\
-        **线程状态**:\n\
-        - RUNNABLE: {runnable}\n\
-        - WAITING: {waiting}\n\
-        - BLOCKED: {blocked}\n\n"
-    );
-    
-    if deadlock {
-        report.push_str("> [!CAUTION]\n> ⚠️ 检测到死锁！\n\n");
-    }
-    
-    if blocked > 10 {
-        report.push_str(&format!(
-            "> [!WARNING]\n> {blocked} 个线程处于 BLOCKED 状态，可能存在锁竞争\n\n"
-        ));
-    }
-    
-    // 截取关键部分: 头部 50 行 + 尾部 50 行
-    let all_lines: Vec<&str> = dump.lines().collect();
-    let total_lines = all_lines.len();
-    
-    if total_lines <= 100 {
-        // 总行数小于 100，全部显示
-        report.push_str(&format!("### 线程摘要 (全部 {total_lines} 行)\n\n```\n"));
-        report.push_str(&all_lines.join("\n"));
-    } else {
-        // 显示头尾各 50 行
-        let head: Vec<&str> = all_lines.iter().take(50).cloned().collect();
-        let tail: Vec<&str> = all_lines.iter().rev().take(50).cloned().collect::<Vec<_>>().into_iter().rev().collect();
-        
-        report.push_str(&format!("### 线程摘要 (头 50 + 尾 50 行, 共 {total_lines} 行)\n\n```\n"));
-        report.push_str(&head.join("\n"));
-        report.push_str(&format!("\n\n... 省略 {} 行 ...\n\n", total_lines - 100));
-        report.push_str(&tail.join("\n"));
-    }
-    report.push_str("\n```\n");
-    
-    Ok(json!(report))
+    let dump = String::from_utf8_lossy(&output.stdout);
+    Ok(thread_dump::analyze(pid, &host.label(), &dump))
 }
 
 /// 分析字节码
-pub fn analyze_bytecode(class_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+///
+/// v9.6: 支持 JAR/WAR，按 `package` glob 过滤后批量 javap 并输出字节码级问题；
+/// 单个类输出方法画像 (内联档位、异常表密度、monitorenter、字符串拼接) 与排序后的发现
+pub fn analyze_bytecode(class_path: &str, package: Option<&str>) -> Result<Value, Box<dyn std::error::Error>> {
     // 输入验证
     if class_path.is_empty() {
         return Err("Invalid class path: path cannot be empty".into());
@@ -152,42 +342,78 @@ pub fn analyze_bytecode(class_path: &str) -> Result<Value, Box<dyn std::error::E
     }
     
     let javap = get_jdk_tool("javap").ok_or("javap 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 javap 在 $PATH 中")?;
+
+    if is_archive(class_path) {
+        let report = bytecode::analyze_archive(&javap, Path::new(class_path), package)?;
+        let mut value = serde_json::to_value(&report)?;
+        value["report"] = json!(report.render(class_path, package));
+        return Ok(value);
+    }
     
-    let output = Command::new(&javap)
-        .args(["-c", "-v", class_path])
-        .output()?;
+    let output = run_with_timeout(Command::new(&javap).args(["-c", "-p", class_path]), DEFAULT_TIMEOUT)?;
     
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(format!("javap failed: {stderr}").into());
     }
     
-    let bytecode = String::from_utf8_lossy(&output.stdout);
-    
-    // 截取前 200 行
-    let lines: Vec<&str> = bytecode.lines().take(200).collect();
-    
-    let report = format!(
-        "## 🔬 字节码分析: {}\n\n```\n{}\n```\n",
-        class_path,
-        lines.join("\n")
-    );
-    
-    Ok(json!(report))
+    let methods = bytecode::parse_javap(&String::from_utf8_lossy(&output.stdout));
+    let report = bytecode::ClassReport::new(&methods);
+    let mut value = serde_json::to_value(&report)?;
+    value["report"] = json!(report.render(class_path));
+    Ok(value)
+}
+
+/// 扫描第三方依赖 JAR (v9.6): 只保留 `bytecode::DEPENDENCY_RULES` 规则集
+pub fn scan_jar(jar: &str, package: Option<&str>) -> Result<bytecode::ArchiveReport, Box<dyn std::error::Error>> {
+    if !Path::new(jar).is_file() {
+        return Err(format!("JAR not found: {jar}").into());
+    }
+    let javap = get_jdk_tool("javap").ok_or("javap 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 javap 在 $PATH 中")?;
+    let mut report = bytecode::analyze_archive(&javap, Path::new(jar), package)?;
+    report.findings.retain(|f| bytecode::DEPENDENCY_RULES.contains(&f.id));
+    Ok(report)
+}
+
+/// JAR/WAR 归档 (按扩展名判断)
+fn is_archive(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    (lower.ends_with(".jar") || lower.ends_with(".war")) && Path::new(path).is_file()
+}
+
+/// 直方图参数: 默认 `-histo` (不触发 GC)；`live` 需要 `--force`
+fn histo_arg(live: bool, safety: &Safety) -> Result<String, String> {
+    if !live {
+        return Ok("-histo".to_string());
+    }
+    safety.require_force("jmap -histo:live", "会触发 Full GC (STW 停顿与堆大小成正比，可达数秒)")?;
+    Ok("-histo:live".to_string())
+}
+
+/// 直方图模式的影响说明
+fn histo_notice(live: bool) -> &'static str {
+    if live {
+        "> [!WARNING]\n> 已执行 `histo:live`，目标 JVM 触发了一次 Full GC\n\n"
+    } else {
+        "> [!NOTE]\n> 非 live 直方图 (不触发 Full GC)，包含尚未回收的垃圾对象；需要只统计存活对象时加 `--live --force`\n\n"
+    }
 }
 
 /// 分析堆内存
-pub fn analyze_heap(pid: u32) -> Result<Value, Box<dyn std::error::Error>> {
+///
+/// v9.6: `dump` 指定本地路径时写出堆转储 (.hprof) 而不是直方图；容器中先转储到容器 /tmp 再复制出来。
+/// `live` 直方图与堆转储会暂停目标 JVM，需要 `safety.force`。
+pub fn analyze_heap(pid: u32, host: &JvmHost, live: bool, dump: Option<&str>, safety: &Safety) -> Result<Value, Box<dyn std::error::Error>> {
     // 输入验证
     if pid == 0 {
         return Err("Invalid PID: 0 is not a valid process ID".into());
     }
+
+    if let Some(local) = dump {
+        return dump_heap(pid, host, local, safety);
+    }
     
-    let jmap = get_jdk_tool("jmap").ok_or("jmap 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 jmap 在 $PATH 中")?;
-    
-    let output = Command::new(&jmap)
-        .args(["-histo:live", &pid.to_string()])
-        .output()?;
+    let output = host.run_tool("jmap", &[histo_arg(live, safety)?, pid.to_string()], Some(pid), safety.timeout)?;
     
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -200,11 +426,261 @@ pub fn analyze_heap(pid: u32) -> Result<Value, Box<dyn std::error::Error>> {
     let lines: Vec<&str> = histo.lines().take(50).collect();
     
     let report = format!(
-        "## 🔬 堆内存分析 (PID: {})\n\n\
+        "## 🔬 堆内存分析 (PID: {}{})\n\n{}\
         **Top 对象**:\n\n```\n{}\n```\n",
         pid,
+        host.label(),
+        histo_notice(live),
         lines.join("\n")
     );
     
     Ok(json!(report))
 }
+
+/// 堆增长趋势 (v9.6): 间隔 `interval` 采集 `samples` 份直方图，报告增长最快的类
+///
+/// 默认非 live 直方图，垃圾对象会造成波动；只有每个间隔都增长的类才标记为泄漏嫌疑。
+pub fn analyze_heap_trend(pid: u32, host: &JvmHost, samples: u32, interval: Duration, live: bool, safety: &Safety) -> Result<Value, Box<dyn std::error::Error>> {
+    if pid == 0 {
+        return Err("Invalid PID: 0 is not a valid process ID".into());
+    }
+    if samples < 2 {
+        return Err("趋势分析至少需要 2 次采样 (--samples)".into());
+    }
+    let histo = histo_arg(live, safety)?;
+
+    let started = Instant::now();
+    let mut histograms = Vec::new();
+    for i in 0..samples {
+        if i > 0 {
+            std::thread::sleep(interval);
+        }
+        let elapsed = started.elapsed();
+        let output = host.run_tool("jmap", &[histo.clone(), pid.to_string()], Some(pid), safety.timeout)?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(format!("jmap failed (sample {}): {stderr}", i + 1).into());
+        }
+        let entries = heap_trend::parse_histogram(&String::from_utf8_lossy(&output.stdout));
+        histograms.push(heap_trend::HistoSample { elapsed, entries });
+    }
+
+    let mut trend = heap_trend::render(pid, &host.label(), &histograms);
+    if let Some(report) = trend["report"].as_str() {
+        trend["report"] = json!(report.replacen("\n\n", &format!("\n\n{}", histo_notice(live)), 1));
+    }
+    Ok(trend)
+}
+
+/// 解析采样间隔: `30s` / `2m` / `500ms`，纯数字按秒
+pub fn parse_interval(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let (value, unit) = s.split_at(split);
+    let value: u64 = value.parse().map_err(|_| format!("invalid interval: {s} (expected e.g. 30s, 2m, 500ms)"))?;
+    match unit {
+        "" | "s" => Ok(Duration::from_secs(value)),
+        "ms" => Ok(Duration::from_millis(value)),
+        "m" => Ok(Duration::from_secs(value * 60)),
+        _ => Err(format!("invalid interval unit: {unit} (expected ms, s or m)")),
+    }
+}
+
+/// 写出堆转储到本地 `local` (转储由目标 JVM 写入，本机时路径须对 JVM 进程可写)
+fn dump_heap(pid: u32, host: &JvmHost, local: &str, safety: &Safety) -> Result<Value, Box<dyn std::error::Error>> {
+    safety.require_force("jmap -dump", "会暂停目标 JVM 直到转储写完 (停顿与堆大小成正比，数 GB 堆可达数十秒)，并占用与堆等量的磁盘")?;
+    let local_abs = std::path::absolute(local)?;
+    let target = match host {
+        JvmHost::Local => local_abs.to_string_lossy().into_owned(),
+        _ => format!("/tmp/java-perf-{pid}-{}.hprof", std::process::id()),
+    };
+
+    let output = host.run_tool("jmap", &[format!("-dump:format=b,file={target}"), pid.to_string()], Some(pid), safety.timeout)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(format!("jmap failed: {stderr}").into());
+    }
+    host.copy_out(&target, local, safety.timeout)?;
+
+    let size_mb = std::fs::metadata(&local_abs).map(|m| m.len() as f64 / 1_048_576.0).unwrap_or(0.0);
+    let report = format!(
+        "## 🔬 堆转储 (PID: {pid}{})\n\n已写入 `{}` ({size_mb:.1} MB)\n\n下一步: `java-perf hprof --file {local}`\n",
+        host.label(),
+        local_abs.display(),
+    );
+    Ok(json!(report))
+}
+
+/// 本机 Java 进程 (v9.6: `ps` / MCP `list_jvms`)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JvmProcess {
+    pub pid: u32,
+    /// 主类或 JAR (`jps -l`)；进程信息不可读时为 jps 的提示文本
+    pub main_class: String,
+    /// JVM 参数 (`jps -v`；回退到 `jcmd -l` 时为空)
+    pub jvm_args: Vec<String>,
+    /// 运行时长 (`ps -o etime`，[[dd-]hh:]mm:ss)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub uptime: Option<String>,
+}
+
+/// 列出 Java 进程，省去 jstack/jmap 前手动查找 PID
+///
+/// 优先 `jps -lv` (含 JVM 参数)，不可用时回退 `jcmd -l`；不包含 jps/jcmd 自身。
+/// 容器中的进程不统计运行时长 (容器镜像常不带 ps)。
+pub fn list_jvms(host: &JvmHost, safety: &Safety) -> Result<Value, Box<dyn std::error::Error>> {
+    let jps = host.run_tool("jps", &["-lv".to_string()], None, safety.timeout).ok().filter(|o| o.status.success());
+    let (source, with_jvm_args, output) = match jps {
+        Some(output) => ("jps -lv", true, output),
+        None => {
+            let output = host.run_tool("jcmd", &["-l".to_string()], None, safety.timeout)
+                .map_err(|_| "jps/jcmd 不可用: 请确保已安装 JDK 且 JAVA_HOME 已设置或 jps 在 $PATH 中")?;
+            if !output.status.success() {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                return Err(format!("jcmd -l failed: {stderr}").into());
+            }
+            ("jcmd -l", false, output)
+        }
+    };
+
+    let mut processes = parse_jvm_list(&String::from_utf8_lossy(&output.stdout), with_jvm_args);
+    if *host == JvmHost::Local {
+        for process in &mut processes {
+            process.uptime = process_uptime(process.pid);
+        }
+    }
+
+    let title = if *host == JvmHost::Local { "本机 Java 进程".to_string() } else { format!("Java 进程{}", host.label()) };
+    let mut report = format!("## 🔍 {title} ({} 个)\n\n", processes.len());
+    if processes.is_empty() {
+        report.push_str("未发现 Java 进程 (只能看到当前用户的 JVM，可运行 `java-perf doctor` 检查 attach 环境)\n");
+    } else {
+        report.push_str("| PID | 主类 | 运行时长 | JVM 参数 |\n|-----|------|----------|----------|\n");
+        for p in &processes {
+            report.push_str(&format!(
+                "| {} | `{}` | {} | {} |\n",
+                p.pid, p.main_class, p.uptime.as_deref().unwrap_or("-"), p.jvm_args.join(" ")
+            ));
+        }
+        report.push_str("\n下一步: `java-perf jstack --pid <PID>` / `java-perf jmap --pid <PID>`\n");
+    }
+
+    Ok(json!({ "source": source, "processes": processes, "report": report }))
+}
+
+/// 解析 `jps -lv` / `jcmd -l` 输出: `<pid> [主类|JAR] [参数...]`
+fn parse_jvm_list(output: &str, with_jvm_args: bool) -> Vec<JvmProcess> {
+    const SELF_TOOLS: &[&str] = &["sun.tools.jps.Jps", "sun.tools.jcmd.JCmd"];
+
+    output.lines().filter_map(|line| {
+        let (pid, rest) = line.trim().split_once(' ').unwrap_or((line.trim(), ""));
+        let pid = pid.parse().ok()?;
+        let rest = rest.trim();
+        if rest.starts_with("--") {
+            // "-- process information unavailable" (其它用户/容器中的进程)
+            return Some(JvmProcess { pid, main_class: rest.to_string(), jvm_args: Vec::new(), uptime: None });
+        }
+        let mut tokens = rest.split_whitespace().peekable();
+        // jps 读不到主类时直接输出 JVM 参数
+        let main_class = tokens.next_if(|t| !t.starts_with('-')).unwrap_or_default().to_string();
+        if SELF_TOOLS.iter().any(|tool| main_class.ends_with(tool)) {
+            return None;
+        }
+        let jvm_args = if with_jvm_args { tokens.map(str::to_string).collect() } else { Vec::new() };
+        Some(JvmProcess { pid, main_class, jvm_args, uptime: None })
+    }).collect()
+}
+
+/// 进程运行时长 (`ps -o etime= -p <pid>`，Linux/macOS)
+fn process_uptime(pid: u32) -> Option<String> {
+    let output = run_with_timeout(Command::new("ps").args(["-o", "etime=", "-p", &pid.to_string()]), PROBE_TIMEOUT).ok()?;
+    let etime = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !etime.is_empty()).then_some(etime)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pause_operations_require_force() {
+        let safe = Safety::default();
+        let err = analyze_heap(4242, &JvmHost::Local, true, None, &safe).unwrap_err().to_string();
+        assert!(err.contains("Full GC") && err.contains("--force"), "{err}");
+        let err = analyze_heap(4242, &JvmHost::Local, false, Some("heap.hprof"), &safe).unwrap_err().to_string();
+        assert!(err.contains("jmap -dump") && err.contains("--force"), "{err}");
+        assert!(analyze_heap_trend(4242, &JvmHost::Local, 3, Duration::ZERO, true, &safe).is_err());
+
+        assert_eq!(histo_arg(false, &safe), Ok("-histo".to_string()));
+        assert_eq!(histo_arg(true, &Safety { force: true, ..safe }), Ok("-histo:live".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_run_with_timeout_kills_child() {
+        let started = Instant::now();
+        let err = run_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(200)).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::TimedOut);
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let output = run_with_timeout(Command::new("echo").arg("ok"), Duration::from_secs(5)).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "ok");
+    }
+
+    #[test]
+    fn test_parse_interval() {
+        assert_eq!(parse_interval("30s"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_interval("2m"), Ok(Duration::from_secs(120)));
+        assert_eq!(parse_interval("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_interval("45"), Ok(Duration::from_secs(45)));
+        assert!(parse_interval("1h").is_err());
+        assert!(parse_interval("s").is_err());
+    }
+
+    #[test]
+    fn test_jvm_host_exec_argv() {
+        let argv = |host: &JvmHost, user: Option<&str>| {
+            let (program, args) = host.exec_argv(user, &host.tool_argv("jstack", &["1".to_string()]));
+            format!("{program} {}", args.join(" "))
+        };
+        let docker = JvmHost::new(Some("order-api".into()), None, None, None).unwrap();
+        assert_eq!(argv(&docker, Some("1001")), format!("docker exec -u 1001 order-api sh -c {CONTAINER_TOOL_SCRIPT} jstack 1"));
+
+        let pod = JvmHost::new(Some("app".into()), Some("order-7d9f".into()), Some("prod".into()), None).unwrap();
+        assert_eq!(argv(&pod, None), format!("kubectl exec -n prod order-7d9f -c app -- sh -c {CONTAINER_TOOL_SCRIPT} jstack 1"));
+        assert_eq!(pod.label(), " @ pod:prod/order-7d9f (app)");
+
+        assert_eq!(JvmHost::new(None, None, None, None).unwrap(), JvmHost::Local);
+        assert!(JvmHost::new(None, Some("p".into()), None, Some("1001".into())).is_err());
+        assert!(JvmHost::new(None, None, Some("prod".into()), None).is_err());
+        // 选项注入: 以 '-' 开头的值不进入 exec 命令行
+        assert!(JvmHost::new(Some("--privileged".into()), None, None, None).is_err());
+        assert!(JvmHost::new(None, Some("-it".into()), Some("prod".into()), None).is_err());
+        assert!(JvmHost::new(Some("app".into()), None, None, Some("-0".into())).is_err());
+        assert_eq!(pod.target().as_deref(), Some("prod/order-7d9f"));
+        assert_eq!(docker.target().as_deref(), Some("order-api"));
+    }
+
+    #[test]
+    fn test_parse_jps_output() {
+        let output = "\
+4242 com.acme.OrderApplication -Xmx2g -XX:+UseG1GC -Dspring.profiles.active=prod
+5151 /opt/app/billing.jar -Xms512m
+6060  -Dcatalina.home=/opt/tomcat
+7070 -- process information unavailable
+8080 jdk.jcmd/sun.tools.jps.Jps -Dapplication.home=/usr/lib/jvm/java-17 -Xms8m
+";
+        let processes = parse_jvm_list(output, true);
+        assert_eq!(processes.len(), 4);
+        assert_eq!(processes[0].main_class, "com.acme.OrderApplication");
+        assert_eq!(processes[0].jvm_args, vec!["-Xmx2g", "-XX:+UseG1GC", "-Dspring.profiles.active=prod"]);
+        assert_eq!(processes[1].main_class, "/opt/app/billing.jar");
+        assert_eq!(processes[2].main_class, "");
+        assert_eq!(processes[2].jvm_args, vec!["-Dcatalina.home=/opt/tomcat"]);
+        assert_eq!(processes[3].main_class, "-- process information unavailable");
+
+        // jcmd -l 的其余字段是应用参数，不作为 JVM 参数
+        let processes = parse_jvm_list("4242 com.acme.OrderApplication --server.port=8080\n", false);
+        assert!(processes[0].jvm_args.is_empty());
+    }
+}
@@ -2,16 +2,50 @@
 // Java Performance Diagnostics Tool - Library Interface
 // ============================================================================
 //
-// This module exposes the internal modules for integration testing.
-// The main binary (main.rs) uses these modules directly.
+// This is the shared core of java-perf: scanner, rules, symbol table, taint,
+// forensic and jdk engine live here exactly once. The CLI binary (main.rs) and
+// any other front-end (e.g. an MCP server) must build on these modules rather
+// than keeping their own copies of `Issue`/`Severity`/analyzers.
+//
+// It also exposes the internal modules for integration testing.
 
 pub mod ast_engine;
+pub mod bench;
+pub mod bytecode;
+pub mod call_chain;
+pub mod class_loading;
+pub mod config_diff;
+pub mod crash;
+pub mod effective_config;
 pub mod forensic;
+pub mod genbench;
+pub mod heap_trend;
+pub mod startup;
+pub mod thread_dump;
+pub mod thread_patterns;
+pub mod hprof;
+pub mod i18n;
+pub mod index_store;
+pub mod issue_link;
 pub mod jdk_engine;
+pub mod log_json;
+pub mod log_timeline;
+pub mod mcp;
+pub mod metrics;
+pub mod native_config;
+pub mod output_budget;
+pub mod ownership;
 pub mod checklist;
+pub mod redact;
+pub mod doctor;
 pub mod scanner;
 pub mod cli;
 pub mod taint;
 pub mod symbol_table;
 pub mod project_detector;
+pub mod reachability;
+pub mod rule_pack;
 pub mod rules;
+pub mod scan_diff;
+pub mod report;
+pub mod verify;
@@ -0,0 +1,205 @@
+//! JSON 行日志 (v9.6: `--log-json FILE`)
+//!
+//! 每个 tracing 事件写成一行 JSON：`ts`/`level`/`target`/`message` + 事件字段 +
+//! 所在 span 链上的字段 (外层在前，同名字段内层覆盖)。MCP 服务的每个请求在
+//! `mcp_request` span 中执行 (request_id / method / tool / arguments)，完成时记录
+//! `duration_ms` 与问题数，运维据此审计 Agent 调用了什么、每个工具耗时多久。
+//!
+//! 文件按大小滚动: 超过上限时 `FILE` → `FILE.1` → ... → `FILE.N`，最旧的丢弃。
+
+use serde_json::{Map, Value};
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+/// 单个日志文件上限
+pub const DEFAULT_MAX_BYTES: u64 = 10 * 1024 * 1024;
+/// 保留的滚动文件数 (不含当前文件)
+pub const DEFAULT_KEEP: usize = 5;
+
+/// 按大小滚动的日志文件
+struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_bytes: u64,
+    keep: usize,
+}
+
+impl RotatingFile {
+    fn open(path: &Path, max_bytes: u64, keep: usize) -> std::io::Result<Self> {
+        if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+            std::fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        let size = file.metadata()?.len();
+        Ok(RotatingFile { path: path.to_path_buf(), file, size, max_bytes, keep })
+    }
+
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut name = self.path.clone().into_os_string();
+        name.push(format!(".{n}"));
+        PathBuf::from(name)
+    }
+
+    fn rotate(&mut self) -> std::io::Result<()> {
+        if self.keep == 0 {
+            self.file = File::create(&self.path)?;
+        } else {
+            for n in (1..self.keep).rev() {
+                let from = self.rotated(n);
+                if from.exists() {
+                    std::fs::rename(&from, self.rotated(n + 1))?;
+                }
+            }
+            std::fs::rename(&self.path, self.rotated(1))?;
+            self.file = OpenOptions::new().create(true).append(true).open(&self.path)?;
+        }
+        self.size = 0;
+        Ok(())
+    }
+
+    fn write_line(&mut self, line: &str) -> std::io::Result<()> {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.max_bytes {
+            self.rotate()?;
+        }
+        writeln!(self.file, "{line}")?;
+        self.size += len;
+        Ok(())
+    }
+}
+
+/// 把事件写成 JSON 行的 tracing Layer
+pub struct JsonLogLayer {
+    file: Mutex<RotatingFile>,
+}
+
+impl JsonLogLayer {
+    pub fn open(path: &Path) -> std::io::Result<Self> {
+        Self::with_rotation(path, DEFAULT_MAX_BYTES, DEFAULT_KEEP)
+    }
+
+    pub fn with_rotation(path: &Path, max_bytes: u64, keep: usize) -> std::io::Result<Self> {
+        Ok(JsonLogLayer { file: Mutex::new(RotatingFile::open(path, max_bytes, keep)?) })
+    }
+}
+
+/// span 上已记录的字段 (存放在 registry 的 extensions 中)
+struct SpanFields(Map<String, Value>);
+
+struct JsonVisitor<'a>(&'a mut Map<String, Value>);
+
+impl Visit for JsonVisitor<'_> {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), Value::from(value));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), Value::from(format!("{value:?}")));
+    }
+}
+
+impl<S> Layer<S> for JsonLogLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = Map::new();
+        attrs.record(&mut JsonVisitor(&mut fields));
+        span.extensions_mut().insert(SpanFields(fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            values.record(&mut JsonVisitor(fields));
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let mut line = Map::new();
+        line.insert("ts".to_string(), Value::from(chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true)));
+        line.insert("level".to_string(), Value::from(meta.level().as_str()));
+        line.insert("target".to_string(), Value::from(meta.target()));
+
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                line.insert("span".to_string(), Value::from(span.name()));
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    line.extend(fields.iter().map(|(k, v)| (k.clone(), v.clone())));
+                }
+            }
+        }
+        event.record(&mut JsonVisitor(&mut line));
+
+        let Ok(text) = serde_json::to_string(&line) else { return };
+        let mut file = self.file.lock().unwrap_or_else(|e| e.into_inner());
+        // 日志写入失败不能影响服务本身
+        let _ = file.write_line(&text);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[test]
+    fn test_json_lines_with_span_fields_and_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs/mcp.jsonl");
+        let layer = JsonLogLayer::with_rotation(&path, 400, 2).unwrap();
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            for id in 1..=6 {
+                let span = tracing::info_span!("mcp_request", request_id = id, tool = "radar_scan", p0 = tracing::field::Empty);
+                let _enter = span.enter();
+                span.record("p0", 3);
+                tracing::info!(duration_ms = 12.5, is_error = false, "mcp request");
+            }
+        });
+
+        let current = std::fs::read_to_string(&path).unwrap();
+        let last: Value = serde_json::from_str(current.lines().last().unwrap()).unwrap();
+        assert_eq!(last["span"], "mcp_request");
+        assert_eq!(last["request_id"], 6);
+        assert_eq!(last["tool"], "radar_scan");
+        assert_eq!(last["p0"], 3);
+        assert_eq!(last["duration_ms"], 12.5);
+        assert_eq!(last["message"], "mcp request");
+        assert_eq!(last["level"], "INFO");
+
+        // 超过上限时滚动，只保留 2 个旧文件
+        assert!(dir.path().join("logs/mcp.jsonl.1").exists());
+        assert!(dir.path().join("logs/mcp.jsonl.2").exists());
+        assert!(!dir.path().join("logs/mcp.jsonl.3").exists());
+        assert!(std::fs::metadata(&path).unwrap().len() <= 400);
+    }
+}
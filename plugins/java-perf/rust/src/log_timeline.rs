@@ -0,0 +1,738 @@
+//! 多日志关联 (Log Timeline) - v9.6
+//!
+//! `log --files app1.log,app2.log,gc.log` 按时间戳对齐多个服务的日志与 GC 日志：
+//! - **GC 停顿 ↔ 错误突增**: 错误突增开始前 [`CORRELATION_WINDOW`] 内结束的长 GC 停顿
+//! - **上游超时 ↔ 下游异常**: 某个日志中的超时，其前 [`TIMEOUT_WINDOW`] 内其他日志出现的异常
+//! - **合并时间线**: 长 GC 停顿、错误突增、各文件异常首次出现按时间排序
+//!
+//! `log --files ... --trace ID` 还原单个请求的跨服务轨迹 ([`trace_journey`])：按时间列出各文件中
+//! 带该 Trace ID 的记录，并指出相邻两条记录之间的最长间隔 (请求在哪一段没有任何输出)。
+//! Trace ID 的识别见 [`extract_trace`] (W3C `traceparent`、MDC `traceId=`、Sleuth `[app,trace,span]`)。
+//!
+//! 日志记录以带时间戳的行开头，之后不带时间戳的行 (堆栈) 归入同一条记录。
+//! 时间戳按各日志自身的本地时间对齐 (忽略时区后缀)，多台机器的时钟偏差需要自行校正。
+//!
+//! 支持的格式：
+//! ```text
+//! 2026-10-17 10:00:01.234 ERROR 1 --- [nio-8080-exec-3] c.a.OrderClient : call failed
+//! [2026-10-17T10:00:01.234+0800][info][gc] GC(12) Pause Young (Normal) (G1 Evacuation Pause) 512M->128M(1024M) 812.345ms
+//! 2026-10-17T10:00:01.234+0800: 12.345: [Full GC (Allocation Failure) 900M->600M(1024M), 1.2345678 secs]
+//! ```
+
+use chrono::{NaiveDate, NaiveDateTime};
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+/// 报告中列出的长 GC 停顿阈值 (毫秒)
+const GC_PAUSE_MS: f64 = 200.0;
+/// 错误突增: 同一文件相邻错误间隔不超过 1 秒、且至少 BURST_MIN 条
+const BURST_GAP_MS: i64 = 1000;
+const BURST_MIN: usize = 5;
+/// GC 停顿结束后多久内开始的错误突增视为相关 (毫秒)
+pub const CORRELATION_WINDOW: i64 = 2000;
+/// 上游超时前多久内的下游异常视为相关 (毫秒)，另允许 1 秒时钟偏差
+pub const TIMEOUT_WINDOW: i64 = 10_000;
+const CLOCK_SKEW_MS: i64 = 1000;
+/// 各日志时间范围相距超过该值 (分钟) 时提示时区/日期不一致
+const SKEW_WARN_MINUTES: i64 = 30;
+/// 单个文件保留的事件上限 (超过后不再记录，报告中提示)
+const MAX_EVENTS: usize = 200_000;
+/// 时间线行数上限
+const TIMELINE_MAX: usize = 40;
+/// 请求轨迹行数上限
+const JOURNEY_MAX: usize = 100;
+const TOP_N: usize = 10;
+
+/// `yyyy-MM-dd HH:mm:ss[.SSS]` / `yyyy-MM-ddTHH:mm:ss[,SSS]`
+static TIMESTAMP_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(\d{4})-(\d{2})-(\d{2})[T ](\d{2}):(\d{2}):(\d{2})(?:[.,](\d{1,9}))?").unwrap()
+});
+
+/// 统一日志 `Pause Young ... 12.345ms` / 传统格式 `[GC ... 0.0123456 secs]`
+static GC_PAUSE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Pause .*?(\d+(?:\.\d+)?)ms\s*$|\[(?:Full )?GC\b.*?(\d+\.\d+) secs\]").unwrap()
+});
+
+static EXCEPTION_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:[a-z]\w*\.)*([A-Z]\w*(?:Exception|Error))\b").unwrap()
+});
+
+static TIMEOUT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\w*Timeout\w*Exception|\b(?:Read|connect) timed out\b|\btimed out after\b").unwrap()
+});
+
+/// W3C Trace Context: `traceparent: 00-<trace-id>-<parent-id>-<flags>`
+static TRACEPARENT_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b00-([0-9a-f]{32})-([0-9a-f]{16})-[0-9a-f]{2}\b").unwrap()
+});
+
+/// MDC 键值: `traceId=...` / `trace_id: ...` / `"X-B3-TraceId":"..."`
+static MDC_TRACE_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:trace[_.-]?id|x-b3-traceid)["']?\s*[=:]\s*["']?([0-9a-z][0-9a-z-]{7,63})"#).unwrap()
+});
+
+static MDC_SPAN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?i)\b(?:span[_.-]?id|x-b3-spanid)["']?\s*[=:]\s*["']?([0-9a-z][0-9a-z-]{7,63})"#).unwrap()
+});
+
+/// Spring Cloud Sleuth / Micrometer Tracing 默认格式: `[app,traceId,spanId(,export)]`
+static SLEUTH_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\[[\w.-]*,([0-9a-f]{16}|[0-9a-f]{32}),([0-9a-f]{16})(?:,\w+)?\]").unwrap()
+});
+
+static ERROR_LEVEL_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(?:ERROR|FATAL|SEVERE)\b").unwrap()
+});
+
+/// 解析行内第一个时间戳 (忽略时区后缀)
+pub fn parse_timestamp(line: &str) -> Option<NaiveDateTime> {
+    let caps = TIMESTAMP_REGEX.captures(line)?;
+    let num = |i: usize| caps[i].parse::<u32>().ok();
+    let date = NaiveDate::from_ymd_opt(caps[1].parse().ok()?, num(2)?, num(3)?)?;
+    let nanos = caps.get(7).map_or(Some(0), |m| {
+        let digits = m.as_str();
+        digits.parse::<u32>().ok().map(|n| n * 10u32.pow(9 - digits.len() as u32))
+    })?;
+    date.and_hms_nano_opt(num(4)?, num(5)?, num(6)?, nanos)
+}
+
+/// 日志行中的追踪上下文
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    pub trace_id: String,
+    pub span_id: Option<String>,
+}
+
+/// 识别日志行中的 Trace ID / Span ID (依次尝试 W3C traceparent、MDC 键值、Sleuth 格式)
+pub fn extract_trace(line: &str) -> Option<TraceContext> {
+    if let Some(caps) = TRACEPARENT_REGEX.captures(line) {
+        return Some(TraceContext { trace_id: caps[1].to_string(), span_id: Some(caps[2].to_string()) });
+    }
+    if let Some(caps) = MDC_TRACE_REGEX.captures(line) {
+        let span_id = MDC_SPAN_REGEX.captures(line).map(|c| c[1].to_string());
+        return Some(TraceContext { trace_id: caps[1].to_string(), span_id });
+    }
+    SLEUTH_REGEX.captures(line).map(|caps| TraceContext { trace_id: caps[1].to_string(), span_id: Some(caps[2].to_string()) })
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum EventKind {
+    /// GC 停顿 (毫秒)
+    GcPause(f64),
+    /// 超时 (异常类型或超时描述)
+    Timeout(String),
+    /// 错误 (异常类型，ERROR 级但无异常时为 `ERROR`)
+    Error(String),
+}
+
+#[derive(Debug, Clone)]
+struct LogEvent {
+    at: NaiveDateTime,
+    file: usize,
+    line: usize,
+    kind: EventKind,
+}
+
+/// 一条日志记录: 带时间戳的首行 + 后续堆栈行中的第一个异常
+struct Record {
+    at: NaiveDateTime,
+    line: usize,
+    header: String,
+    exception: Option<String>,
+    timeout: Option<String>,
+}
+
+impl Record {
+    fn classify(self, file: usize) -> Option<LogEvent> {
+        let kind = if let Some(caps) = GC_PAUSE_REGEX.captures(&self.header) {
+            let ms = match (caps.get(1), caps.get(2)) {
+                (Some(ms), _) => ms.as_str().parse().ok()?,
+                (_, Some(secs)) => secs.as_str().parse::<f64>().ok()? * 1000.0,
+                _ => return None,
+            };
+            EventKind::GcPause(ms)
+        } else if let Some(timeout) = self.timeout {
+            EventKind::Timeout(timeout)
+        } else if let Some(exception) = self.exception {
+            EventKind::Error(exception)
+        } else if ERROR_LEVEL_REGEX.is_match(&self.header) {
+            EventKind::Error("ERROR".to_string())
+        } else {
+            return None;
+        };
+        Some(LogEvent { at: self.at, file, line: self.line, kind })
+    }
+
+    fn absorb(&mut self, text: &str) {
+        if self.timeout.is_none() {
+            self.timeout = TIMEOUT_REGEX.find(text).map(|m| m.as_str().to_string());
+        }
+        if self.exception.is_none() {
+            self.exception = EXCEPTION_REGEX.captures(text).map(|c| c[1].to_string());
+        }
+    }
+}
+
+/// 单个文件的读取结果
+#[derive(Debug, Default)]
+struct LogFile {
+    name: String,
+    lines: usize,
+    first: Option<NaiveDateTime>,
+    last: Option<NaiveDateTime>,
+    events: Vec<LogEvent>,
+    truncated: bool,
+}
+
+impl LogFile {
+    fn read(path: &Path, index: usize) -> std::io::Result<Self> {
+        let mut log = LogFile { name: display_name(path), ..Default::default() };
+        log.lines = read_records(path, |record| log.push(record, index))?;
+        Ok(log)
+    }
+
+    fn push(&mut self, record: Record, index: usize) {
+        self.first.get_or_insert(record.at);
+        self.last = Some(record.at);
+        if self.events.len() >= MAX_EVENTS {
+            self.truncated = true;
+            return;
+        }
+        if let Some(event) = record.classify(index) {
+            self.events.push(event);
+        }
+    }
+
+    fn count(&self, pred: impl Fn(&EventKind) -> bool) -> usize {
+        self.events.iter().filter(|e| pred(&e.kind)).count()
+    }
+}
+
+fn display_name(path: &Path) -> String {
+    path.file_name().map_or_else(|| path.display().to_string(), |n| n.to_string_lossy().to_string())
+}
+
+/// 逐条读取日志记录，返回总行数；第一条带时间戳的行之前的内容被忽略
+fn read_records(path: &Path, mut on_record: impl FnMut(Record)) -> std::io::Result<usize> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut record: Option<Record> = None;
+    let mut buf = Vec::new();
+    let mut lines = 0;
+    loop {
+        buf.clear();
+        if reader.read_until(b'\n', &mut buf)? == 0 {
+            break;
+        }
+        lines += 1;
+        let text = String::from_utf8_lossy(&buf);
+        match parse_timestamp(&text) {
+            Some(at) => {
+                if let Some(done) = record.take() {
+                    on_record(done);
+                }
+                let mut next = Record { at, line: lines, header: text.trim_end().to_string(), exception: None, timeout: None };
+                next.absorb(&text);
+                record = Some(next);
+            }
+            None => {
+                if let Some(record) = record.as_mut() {
+                    record.absorb(&text);
+                }
+            }
+        }
+    }
+    if let Some(done) = record.take() {
+        on_record(done);
+    }
+    Ok(lines)
+}
+
+/// 错误突增: 同一文件中密集出现的错误 (含超时)
+#[derive(Debug)]
+struct Burst {
+    file: usize,
+    start: NaiveDateTime,
+    end: NaiveDateTime,
+    count: usize,
+    /// 出现最多的异常
+    top: String,
+}
+
+fn is_failure(kind: &EventKind) -> bool {
+    matches!(kind, EventKind::Error(_) | EventKind::Timeout(_))
+}
+
+fn failure_name(kind: &EventKind) -> &str {
+    match kind {
+        EventKind::Error(name) | EventKind::Timeout(name) => name,
+        EventKind::GcPause(_) => "GC",
+    }
+}
+
+fn find_bursts(log: &LogFile, index: usize) -> Vec<Burst> {
+    let mut bursts = Vec::new();
+    let failures: Vec<&LogEvent> = log.events.iter().filter(|e| is_failure(&e.kind)).collect();
+    let mut start = 0;
+    for i in 1..=failures.len() {
+        let split = i == failures.len() || (failures[i].at - failures[i - 1].at).num_milliseconds() > BURST_GAP_MS;
+        if !split {
+            continue;
+        }
+        let group = &failures[start..i];
+        if group.len() >= BURST_MIN {
+            let mut names: HashMap<&str, usize> = HashMap::new();
+            for event in group {
+                *names.entry(failure_name(&event.kind)).or_default() += 1;
+            }
+            let top = names.into_iter().max_by_key(|(name, n)| (*n, std::cmp::Reverse(*name))).map(|(name, _)| name.to_string()).unwrap_or_default();
+            bursts.push(Burst { file: index, start: group[0].at, end: group[group.len() - 1].at, count: group.len(), top });
+        }
+        start = i;
+    }
+    bursts
+}
+
+fn fmt_time(at: NaiveDateTime) -> String {
+    at.format("%Y-%m-%d %H:%M:%S%.3f").to_string()
+}
+
+fn fmt_range(log: &LogFile) -> String {
+    match (log.first, log.last) {
+        (Some(first), Some(last)) => format!("{} ~ {}", fmt_time(first), last.format("%H:%M:%S%.3f")),
+        _ => "无时间戳".to_string(),
+    }
+}
+
+/// 关联多个日志文件
+pub fn correlate_logs(paths: &[String]) -> Result<Value, Box<dyn std::error::Error>> {
+    if paths.len() < 2 {
+        return Err("--files needs at least two log files".into());
+    }
+    let mut logs = Vec::with_capacity(paths.len());
+    for (index, path) in existing_paths(paths)?.into_iter().enumerate() {
+        logs.push(LogFile::read(path, index)?);
+    }
+    Ok(json!(render(&logs)))
+}
+
+fn existing_paths(paths: &[String]) -> Result<Vec<&Path>, Box<dyn std::error::Error>> {
+    paths.iter()
+        .map(|log_path| {
+            let path = Path::new(log_path);
+            if path.exists() { Ok(path) } else { Err(format!("Log file not found: {log_path}").into()) }
+        })
+        .collect()
+}
+
+/// 请求轨迹中的一条记录
+struct Step {
+    at: NaiveDateTime,
+    file: usize,
+    line: usize,
+    span: Option<String>,
+    text: String,
+    exception: Option<String>,
+}
+
+/// 还原单个请求的跨文件轨迹: 各文件中首行包含 `trace_id` 的记录按时间排序，报告相邻记录间的最长间隔
+pub fn trace_journey(paths: &[String], trace_id: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let trace_id = trace_id.trim();
+    if trace_id.is_empty() {
+        return Err("--trace needs a trace ID".into());
+    }
+    let paths = existing_paths(paths)?;
+    let names: Vec<String> = paths.iter().map(|p| display_name(p)).collect();
+    let mut steps = Vec::new();
+    for (file, path) in paths.iter().enumerate() {
+        read_records(path, |record| {
+            if !record.header.contains(trace_id) {
+                return;
+            }
+            steps.push(Step {
+                at: record.at,
+                file,
+                line: record.line,
+                span: extract_trace(&record.header).filter(|t| t.trace_id == trace_id).and_then(|t| t.span_id),
+                text: record.header.chars().take(160).collect::<String>().replace('|', "\\|"),
+                exception: record.timeout.or(record.exception),
+            });
+        })?;
+    }
+    steps.sort_by(|a, b| a.at.cmp(&b.at).then(a.file.cmp(&b.file)).then(a.line.cmp(&b.line)));
+    Ok(json!(render_journey(&names, trace_id, &steps)))
+}
+
+fn render_journey(names: &[String], trace_id: &str, steps: &[Step]) -> String {
+    let mut report = format!("### 请求轨迹: trace `{trace_id}`\n\n");
+    let (Some(first), Some(last)) = (steps.first(), steps.last()) else {
+        report.push_str(&format!("未在 {} 中找到该 Trace ID 的日志记录\n", names.join(", ")));
+        return report;
+    };
+
+    // 文件按首次出现的顺序排列，近似请求经过各服务的顺序
+    let mut route: Vec<usize> = Vec::new();
+    for step in steps {
+        if !route.contains(&step.file) {
+            route.push(step.file);
+        }
+    }
+    let route: Vec<&str> = route.iter().map(|&f| names[f].as_str()).collect();
+    report.push_str(&format!(
+        "**范围**: {} 条记录，{} 个文件 ({})，总耗时 {}ms\n",
+        steps.len(), route.len(), route.join(" → "), (last.at - first.at).num_milliseconds()
+    ));
+
+    let slowest = steps.windows(2)
+        .enumerate()
+        .map(|(i, w)| (i + 1, (w[1].at - w[0].at).num_milliseconds()))
+        .max_by_key(|&(i, gap)| (gap, std::cmp::Reverse(i)));
+
+    report.push_str("\n| # | 时间 | 间隔 | 文件 | Span | 日志 |\n|---|------|------|------|------|------|\n");
+    for (i, step) in steps.iter().enumerate().take(JOURNEY_MAX) {
+        let gap = match i {
+            0 => "-".to_string(),
+            _ => {
+                let ms = (step.at - steps[i - 1].at).num_milliseconds();
+                if slowest.is_some_and(|(j, max)| j == i && max > 0) { format!("**+{ms}ms** 🐢") } else { format!("+{ms}ms") }
+            }
+        };
+        let span = step.span.as_deref().map_or_else(|| "-".to_string(), |s| format!("`{s}`"));
+        let exception = step.exception.as_deref().map(|e| format!(" ❌ `{e}`")).unwrap_or_default();
+        report.push_str(&format!(
+            "| {} | {} | {gap} | {}:{} | {span} | {}{exception} |\n",
+            i + 1, step.at.format("%H:%M:%S%.3f"), names[step.file], step.line, step.text
+        ));
+    }
+    if steps.len() > JOURNEY_MAX {
+        report.push_str(&format!("\n*... 其余 {} 条省略*\n", steps.len() - JOURNEY_MAX));
+    }
+
+    if let Some((i, gap)) = slowest.filter(|&(_, gap)| gap > 0) {
+        let (from, to) = (&steps[i - 1], &steps[i]);
+        report.push_str(&format!(
+            "\n> [!IMPORTANT]\n> 最慢间隔 {gap}ms: #{} ({}:{}) → #{} ({}:{})，占总耗时 {:.0}%；\
+            这段时间内请求没有任何日志，通常在等待下游调用、锁或连接池，先查看 #{} 之后发起的调用\n",
+            i, names[from.file], from.line, i + 1, names[to.file], to.line,
+            gap as f64 * 100.0 / (last.at - first.at).num_milliseconds().max(1) as f64, i
+        ));
+    }
+    report
+}
+
+fn render(logs: &[LogFile]) -> String {
+    let names: Vec<&str> = logs.iter().map(|l| l.name.as_str()).collect();
+    let mut report = format!("### 多日志关联: {}\n\n", names.join(", "));
+
+    report.push_str("| 文件 | 行数 | 时间范围 | GC 停顿 | 超时 | 错误 |\n|------|------|------|------|------|------|\n");
+    for log in logs {
+        report.push_str(&format!(
+            "| {} | {} | {} | {} | {} | {} |\n",
+            log.name, log.lines, fmt_range(log),
+            log.count(|k| matches!(k, EventKind::GcPause(_))),
+            log.count(|k| matches!(k, EventKind::Timeout(_))),
+            log.count(|k| matches!(k, EventKind::Error(_))),
+        ));
+    }
+
+    let truncated: Vec<&str> = logs.iter().filter(|l| l.truncated).map(|l| l.name.as_str()).collect();
+    if !truncated.is_empty() {
+        report.push_str(&format!(
+            "\n> [!CAUTION]\n> {} 的事件超过 {MAX_EVENTS} 条，之后的事件未参与关联；请按时间段切分日志\n",
+            truncated.join(", ")
+        ));
+    }
+    if let Some(note) = overlap_note(logs) {
+        report.push_str(&note);
+    }
+
+    let bursts: Vec<Burst> = logs.iter().enumerate().flat_map(|(i, log)| find_bursts(log, i)).collect();
+    let pauses: Vec<&LogEvent> = logs.iter()
+        .flat_map(|l| &l.events)
+        .filter(|e| matches!(e.kind, EventKind::GcPause(ms) if ms >= GC_PAUSE_MS))
+        .collect();
+
+    render_gc_correlation(&mut report, logs, &bursts, &pauses);
+    render_timeout_correlation(&mut report, logs);
+    render_timeline(&mut report, logs, &bursts, &pauses);
+    report
+}
+
+/// 无时间戳的文件、时间范围相距过远 (多半是时区或日期不一致) 的提示
+fn overlap_note(logs: &[LogFile]) -> Option<String> {
+    let ranges: Vec<(NaiveDateTime, NaiveDateTime)> = logs.iter().filter_map(|l| Some((l.first?, l.last?))).collect();
+    let missing: Vec<&str> = logs.iter().filter(|l| l.first.is_none()).map(|l| l.name.as_str()).collect();
+    let mut note = String::new();
+    if !missing.is_empty() {
+        note.push_str(&format!(
+            "\n> [!WARNING]\n> {} 没有可识别的日期时间戳 (如 GC 日志只有 uptime)，未参与对齐；GC 日志请使用 `-Xlog:gc*:file=gc.log:time,uptime`\n",
+            missing.join(", ")
+        ));
+    }
+    let latest_start = ranges.iter().map(|r| r.0).max();
+    let earliest_end = ranges.iter().map(|r| r.1).min();
+    if let (Some(start), Some(end)) = (latest_start, earliest_end) {
+        if (start - end).num_minutes() >= SKEW_WARN_MINUTES {
+            note.push_str(&format!(
+                "\n> [!WARNING]\n> 各日志的时间范围相距 {} 分钟以上没有重叠，检查是否为同一时间段、时区是否一致\n",
+                SKEW_WARN_MINUTES
+            ));
+        }
+    }
+    (!note.is_empty()).then_some(note)
+}
+
+fn render_gc_correlation(report: &mut String, logs: &[LogFile], bursts: &[Burst], pauses: &[&LogEvent]) {
+    let mut rows = Vec::new();
+    for burst in bursts {
+        // GC 日志的时间戳为停顿结束时刻
+        let related: Vec<&&LogEvent> = pauses.iter()
+            .filter(|p| p.file != burst.file)
+            .filter(|p| {
+                let lead = (burst.start - p.at).num_milliseconds();
+                (-CLOCK_SKEW_MS..=CORRELATION_WINDOW).contains(&lead) || (p.at >= burst.start && p.at <= burst.end)
+            })
+            .collect();
+        let Some(longest) = related.iter().max_by(|a, b| pause_ms(a).total_cmp(&pause_ms(b))) else { continue };
+        rows.push((burst, *longest, related.len()));
+    }
+    if rows.is_empty() {
+        return;
+    }
+
+    report.push_str(&format!(
+        "\n## ⏱️ GC 停顿 ↔ 错误突增 ({} 处)\n\n\
+        | GC 停顿结束 | 停顿 | 错误突增 | 文件 | 错误数 | 主要异常 |\n\
+        |------|------|------|------|------|------|\n",
+        rows.len()
+    ));
+    for (burst, pause, n) in rows.iter().take(TOP_N) {
+        let extra = if *n > 1 { format!(" (共 {n} 次停顿)") } else { String::new() };
+        report.push_str(&format!(
+            "| {} | {:.0}ms{extra} | {} ~ {} | {} | {} | `{}` |\n",
+            fmt_time(pause.at), pause_ms(pause),
+            burst.start.format("%H:%M:%S%.3f"), burst.end.format("%H:%M:%S%.3f"),
+            logs[burst.file].name, burst.count, burst.top,
+        ));
+    }
+    report.push_str(
+        "\n> [!TIP]\n> 停顿期间请求在线程内堆积，恢复后集中超时/失败；先按 GC 日志分析停顿原因 (堆大小、晋升失败、大对象)，而不是排查错误本身\n"
+    );
+}
+
+fn pause_ms(event: &LogEvent) -> f64 {
+    match event.kind {
+        EventKind::GcPause(ms) => ms,
+        _ => 0.0,
+    }
+}
+
+fn render_timeout_correlation(report: &mut String, logs: &[LogFile]) {
+    // (上游文件, 超时, 下游文件, 下游异常) -> (关联的超时次数, 首次时间)
+    let mut pairs: BTreeMap<(usize, String, usize, String), (usize, NaiveDateTime)> = BTreeMap::new();
+    for upstream in logs {
+        for timeout in upstream.events.iter().filter(|e| matches!(e.kind, EventKind::Timeout(_))) {
+            let mut matched: Vec<(usize, &str)> = Vec::new();
+            for (d, downstream) in logs.iter().enumerate().filter(|(d, _)| *d != timeout.file) {
+                let lo = timeout.at - chrono::Duration::milliseconds(TIMEOUT_WINDOW);
+                let hi = timeout.at + chrono::Duration::milliseconds(CLOCK_SKEW_MS);
+                let from = downstream.events.partition_point(|e| e.at < lo);
+                for event in downstream.events[from..].iter().take_while(|e| e.at <= hi) {
+                    if let EventKind::Error(name) = &event.kind {
+                        if !matched.contains(&(d, name.as_str())) {
+                            matched.push((d, name.as_str()));
+                        }
+                    }
+                }
+            }
+            for (d, name) in matched {
+                let key = (timeout.file, failure_name(&timeout.kind).to_string(), d, name.to_string());
+                let entry = pairs.entry(key).or_insert((0, timeout.at));
+                entry.0 += 1;
+            }
+        }
+    }
+    if pairs.is_empty() {
+        return;
+    }
+
+    let mut rows: Vec<_> = pairs.into_iter().collect();
+    rows.sort_by_key(|(_, (n, first))| (std::cmp::Reverse(*n), *first));
+    report.push_str(&format!(
+        "\n## 🔗 上游超时 ↔ 下游异常 ({} 组)\n\n\
+        超时前 {} 秒内其他日志中出现的异常：\n\n\
+        | 上游 | 超时 | 下游 | 下游异常 | 关联超时数 | 首次 |\n\
+        |------|------|------|------|------|------|\n",
+        rows.len(), TIMEOUT_WINDOW / 1000
+    ));
+    for ((up, timeout, down, exception), (n, first)) in rows.iter().take(TOP_N) {
+        report.push_str(&format!(
+            "| {} | `{timeout}` | {} | `{exception}` | {n} | {} |\n",
+            logs[*up].name, logs[*down].name, fmt_time(*first)
+        ));
+    }
+}
+
+fn render_timeline(report: &mut String, logs: &[LogFile], bursts: &[Burst], pauses: &[&LogEvent]) {
+    let mut entries: Vec<(NaiveDateTime, usize, String)> = Vec::new();
+    for pause in pauses {
+        entries.push((pause.at, pause.file, format!("GC 停顿 {:.0}ms (行 {})", pause_ms(pause), pause.line)));
+    }
+    for burst in bursts {
+        let secs = (burst.end - burst.start).num_milliseconds() as f64 / 1000.0;
+        entries.push((burst.start, burst.file, format!("错误突增 {} 条 / {secs:.1}s，主要 `{}`", burst.count, burst.top)));
+    }
+    for (i, log) in logs.iter().enumerate() {
+        let mut seen: Vec<&str> = Vec::new();
+        for event in log.events.iter().filter(|e| is_failure(&e.kind)) {
+            let name = failure_name(&event.kind);
+            if name != "ERROR" && !seen.contains(&name) {
+                seen.push(name);
+                entries.push((event.at, i, format!("首次出现 `{name}` (行 {})", event.line)));
+            }
+        }
+    }
+    if entries.is_empty() {
+        report.push_str("\n✅ 未发现长 GC 停顿、错误突增或异常\n");
+        return;
+    }
+
+    entries.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.cmp(&b.1)));
+    report.push_str(&format!(
+        "\n## 🕒 合并时间线 ({} 项)\n\n| 时间 | 文件 | 事件 |\n|------|------|------|\n",
+        entries.len()
+    ));
+    for (at, file, text) in entries.iter().take(TIMELINE_MAX) {
+        report.push_str(&format!("| {} | {} | {text} |\n", fmt_time(*at), logs[*file].name));
+    }
+    if entries.len() > TIMELINE_MAX {
+        report.push_str(&format!("\n*... 其余 {} 项省略*\n", entries.len() - TIMELINE_MAX));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_log(dir: &Path, name: &str, lines: &[String]) -> String {
+        let path = dir.join(name);
+        let mut file = File::create(&path).unwrap();
+        for line in lines {
+            writeln!(file, "{line}").unwrap();
+        }
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        let expected = NaiveDate::from_ymd_opt(2026, 10, 17).unwrap().and_hms_milli_opt(10, 0, 1, 234).unwrap();
+        assert_eq!(parse_timestamp("2026-10-17 10:00:01.234 ERROR 1 --- [main] x"), Some(expected));
+        assert_eq!(parse_timestamp("2026-10-17 10:00:01,234 [main] ERROR x"), Some(expected));
+        assert_eq!(parse_timestamp("[2026-10-17T10:00:01.234+0800][info][gc] GC(1) Pause Young 5.0ms"), Some(expected));
+        assert_eq!(parse_timestamp("\tat com.acme.Foo.bar(Foo.java:12)"), None);
+    }
+
+    #[test]
+    fn test_correlate_gc_pauses_and_timeouts() {
+        let dir = tempfile::tempdir().unwrap();
+        let gc = write_log(dir.path(), "gc.log", &[
+            "[2026-10-17T10:00:00.500+0800][info][gc] GC(1) Pause Young (Normal) (G1 Evacuation Pause) 100M->20M(256M) 4.321ms".to_string(),
+            "[2026-10-17T10:00:05.000+0800][info][gc] GC(2) Pause Full (G1 Compaction Pause) 250M->200M(256M) 1830.500ms".to_string(),
+        ]);
+        // 网关: GC 后 1 秒开始集中超时
+        let mut gateway: Vec<String> = vec!["2026-10-17 10:00:00.100  INFO 1 --- [main] c.a.Gateway : started".to_string()];
+        for i in 0..8 {
+            gateway.push(format!("2026-10-17 10:00:06.{i}00 ERROR 1 --- [exec-{i}] c.a.OrderClient : call order-service failed"));
+            gateway.push("java.net.SocketTimeoutException: Read timed out".to_string());
+            gateway.push("\tat java.net.SocketInputStream.read(SocketInputStream.java:171)".to_string());
+        }
+        let gateway = write_log(dir.path(), "gateway.log", &gateway);
+        // 下游: 超时前 2 秒数据库连接池耗尽
+        let order = write_log(dir.path(), "order.log", &[
+            "2026-10-17 10:00:04.000 ERROR 1 --- [exec-1] c.a.OrderDao : query failed".to_string(),
+            "org.springframework.jdbc.CannotGetJdbcConnectionException: Failed to obtain JDBC Connection".to_string(),
+            "2026-10-17 10:00:04.200  WARN 1 --- [exec-2] c.a.OrderDao : slow query 3000ms".to_string(),
+        ]);
+
+        let report = correlate_logs(&[gateway, order, gc]).unwrap();
+        let report = report.as_str().unwrap();
+
+        assert!(report.contains("### 多日志关联: gateway.log, order.log, gc.log"), "{report}");
+        assert!(report.contains("| gateway.log | 25 | 2026-10-17 10:00:00.100 ~ 10:00:06.700 | 0 | 8 | 0 |"), "{report}");
+        assert!(report.contains("| gc.log | 2 |"), "{report}");
+
+        // 1830ms 的 Full GC 结束 1 秒后网关出现 8 次超时
+        assert!(report.contains("## ⏱️ GC 停顿 ↔ 错误突增 (1 处)"), "{report}");
+        assert!(report.contains("| 2026-10-17 10:00:05.000 | 1830ms | 10:00:06.000 ~ 10:00:06.700 | gateway.log | 8 | `SocketTimeoutException` |"), "{report}");
+
+        // 每次超时前 10 秒内下游都有连接获取失败
+        assert!(report.contains("| gateway.log | `SocketTimeoutException` | order.log | `CannotGetJdbcConnectionException` | 8 | 2026-10-17 10:00:06.000 |"), "{report}");
+
+        // 时间线按时间排序，短 GC 停顿不列出
+        let timeline = &report[report.find("## 🕒 合并时间线").unwrap()..];
+        assert!(!timeline.contains("4ms"));
+        let order_first = timeline.find("首次出现 `CannotGetJdbcConnectionException`").unwrap();
+        let gc_pause = timeline.find("GC 停顿 1830ms").unwrap();
+        let burst = timeline.find("错误突增 8 条").unwrap();
+        assert!(order_first < gc_pause && gc_pause < burst, "{timeline}");
+    }
+
+    #[test]
+    fn test_extract_trace_formats() {
+        let w3c = extract_trace("traceparent=00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01 GET /orders").unwrap();
+        assert_eq!(w3c.trace_id, "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(w3c.span_id.as_deref(), Some("00f067aa0ba902b7"));
+
+        let mdc = extract_trace("10:00:01.234 [exec-1] INFO traceId=abc123def4567890 spanId=1122334455667788 c.a.Foo - ok").unwrap();
+        assert_eq!(mdc.trace_id, "abc123def4567890");
+        assert_eq!(mdc.span_id.as_deref(), Some("1122334455667788"));
+        assert_eq!(extract_trace(r#"{"level":"INFO","trace_id":"9f8e7d6c5b4a39281706f5e4d3c2b1a0"}"#).unwrap().trace_id, "9f8e7d6c5b4a39281706f5e4d3c2b1a0");
+
+        let sleuth = extract_trace("2026-10-17 10:00:01.234  INFO [order-service,5f1e2a3b4c5d6e7f,8a9b0c1d2e3f4a5b,true] 1 --- [exec-1] c.a.Foo : ok").unwrap();
+        assert_eq!(sleuth.trace_id, "5f1e2a3b4c5d6e7f");
+        assert_eq!(sleuth.span_id.as_deref(), Some("8a9b0c1d2e3f4a5b"));
+
+        assert_eq!(extract_trace("2026-10-17 10:00:01.234  INFO [main] c.a.Foo : started"), None);
+    }
+
+    #[test]
+    fn test_trace_journey_reports_slowest_gap() {
+        let dir = tempfile::tempdir().unwrap();
+        let trace = "4bf92f3577b34da6a3ce929d0e0e4736";
+        let gateway = write_log(dir.path(), "gateway.log", &[
+            format!("2026-10-17 10:00:00.000  INFO 1 --- [exec-1] c.a.Route : traceId={trace} spanId=aaaaaaaaaaaaaaa1 GET /orders/42"),
+            "2026-10-17 10:00:00.010  INFO 1 --- [exec-2] c.a.Route : traceId=ffffffffffffffffffffffffffffffff GET /health".to_string(),
+            format!("2026-10-17 10:00:03.250 ERROR 1 --- [exec-1] c.a.Route : traceId={trace} spanId=aaaaaaaaaaaaaaa1 upstream failed"),
+            "java.net.SocketTimeoutException: Read timed out".to_string(),
+        ]);
+        let order = write_log(dir.path(), "order.log", &[
+            format!("2026-10-17 10:00:00.050  INFO 1 --- [exec-7] c.a.OrderService : traceId={trace} spanId=bbbbbbbbbbbbbbb2 load order 42"),
+            format!("2026-10-17 10:00:00.080  INFO 1 --- [exec-7] c.a.OrderDao : traceId={trace} spanId=bbbbbbbbbbbbbbb2 select order"),
+            format!("2026-10-17 10:00:03.200  WARN 1 --- [exec-7] c.a.OrderDao : traceId={trace} spanId=bbbbbbbbbbbbbbb2 query took 3120ms"),
+        ]);
+
+        let report = trace_journey(&[gateway.clone(), order], trace).unwrap();
+        let report = report.as_str().unwrap();
+        assert!(report.contains(&format!("### 请求轨迹: trace `{trace}`")), "{report}");
+        assert!(report.contains("**范围**: 5 条记录，2 个文件 (gateway.log → order.log)，总耗时 3250ms"), "{report}");
+        assert!(!report.contains("/health"));
+        // 按时间交错排列，异常来自堆栈行
+        assert!(report.contains("| 2 | 10:00:00.050 | +50ms | order.log:1 | `bbbbbbbbbbbbbbb2` |"), "{report}");
+        assert!(report.contains("| 4 | 10:00:03.200 | **+3120ms** 🐢 | order.log:3 |"), "{report}");
+        assert!(report.contains("upstream failed ❌ `SocketTimeoutException` |"), "{report}");
+        assert!(report.contains("最慢间隔 3120ms: #3 (order.log:2) → #4 (order.log:3)，占总耗时 96%"), "{report}");
+
+        let missing = trace_journey(&[gateway], "0123456789abcdef").unwrap();
+        assert!(missing.as_str().unwrap().contains("未在 gateway.log 中找到该 Trace ID 的日志记录"));
+    }
+
+    #[test]
+    fn test_correlate_requires_two_files() {
+        assert!(correlate_logs(&["app.log".to_string()]).is_err());
+    }
+}
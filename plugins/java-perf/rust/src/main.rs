@@ -1,17 +1,10 @@
-mod ast_engine;
-mod forensic;
-mod jdk_engine;
-mod checklist;
-mod scanner;
-mod cli;
-mod taint;
-mod symbol_table;
-mod project_detector;
-mod rules;
+// 二进制只是核心库 (lib.rs) 之上的薄 CLI 包装：模块全部来自 java_perf，不再重复编译一份
+use java_perf::{cli, i18n, log_json, redact};
 
 use clap::Parser;
-use tracing::Level;
-use tracing_subscriber::FmtSubscriber;
+use std::path::PathBuf;
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::layer::SubscriberExt;
 use anyhow::Result;
 
 // Re-export Command from cli module
@@ -26,10 +19,26 @@ struct Args {
     #[arg(long, default_value = "info")]
     log_level: String,
 
+    /// 同时以 JSON 行写入日志文件 (按 10 MiB 滚动，保留 5 个)，用于审计 MCP 请求
+    #[arg(long, global = true, value_name = "FILE")]
+    log_json: Option<PathBuf>,
+
     /// 输出 JSON 格式 (默认输出人类可读的 Markdown)
     #[arg(long, global = true)]
     json: bool,
 
+    /// 输出语言: zh | en (规则描述、检查清单、报告标题)
+    #[arg(long, value_enum, global = true, default_value_t = i18n::Lang::Zh)]
+    lang: i18n::Lang,
+
+    /// 追加脱敏规则 (正则，可重复)：输出前匹配内容替换为 ***，内置规则已覆盖邮箱/密码/令牌/带凭据的 JDBC URL
+    #[arg(long, global = true, value_name = "REGEX")]
+    redact_pattern: Vec<String>,
+
+    /// 关闭输出脱敏 (仅限本地排查，MCP 输出会发送给 LLM 服务商)
+    #[arg(long, global = true, conflicts_with = "redact_pattern")]
+    no_redact: bool,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -37,14 +46,22 @@ struct Args {
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    // 初始化日志
-    let subscriber = FmtSubscriber::builder()
-        .with_max_level(Level::INFO)
-        .with_writer(std::io::stderr)
-        .finish();
+    // 初始化日志 (v9.6: --log-json 时追加 JSON 行文件)
+    let json_log = args.log_json.as_deref().map(log_json::JsonLogLayer::open).transpose()?;
+    let subscriber = tracing_subscriber::registry()
+        .with(LevelFilter::INFO)
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
+        .with(json_log);
 
     tracing::subscriber::set_global_default(subscriber)
         .expect("setting default subscriber failed");
 
+    i18n::set_lang(args.lang);
+    redact::configure(if args.no_redact {
+        redact::Redactor::disabled()
+    } else {
+        redact::Redactor::with_patterns(&args.redact_pattern).map_err(anyhow::Error::msg)?
+    });
+
     cli::handle_command(args.command, args.json)
 }
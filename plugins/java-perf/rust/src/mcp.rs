@@ -0,0 +1,1220 @@
+//! MCP Server - stdio JSON-RPC 2.0 (v9.6)
+//!
+//! v6.0 起主入口是 CLI + Skill；需要 MCP 的 Agent 客户端通过 `java-perf mcp`
+//! 使用同一套核心能力 (不复制任何分析逻辑，只做参数转换)。
+//!
+//! - **tools**: radar_scan / analyze_file / get_checklist / get_antipatterns /
+//!   analyze_log / analyze_thread_dump / analyze_heap / analyze_bytecode /
+//!   verify_issue (Sniper 单点验证) / get_call_chain (调用链影响面) /
+//!   compare_scans (两次扫描对比) / list_jvms (本机 Java 进程) /
+//!   analyze_crash (hs_err 崩溃日志) / generate_native_config (GraalVM 反射配置)
+//! - **prompts**: Radar-Sniper-Forensic 诊断剧本 (如 `diagnose-high-cpu`)，
+//!   预填好参数的工具调用链，客户端直接获得完整流程
+//!
+//! `radar_scan` 缓存最近 8 次扫描结果，键为 (路径, 文件 mtime 摘要, 规则集指纹)；
+//! 源码与规则未变化时直接返回上次结果 (扫描信息带 `cached=true`)，`force` 跳过缓存。
+//! `radar_scan` / `analyze_heap` 同一时间只执行一个，相同参数的并发请求合并为一次执行。
+//!
+//! 传输: 逐条自动识别分帧，回复使用与请求相同的分帧：
+//! - 每行一条 JSON-RPC 消息 (newline-delimited)
+//! - LSP 风格 `Content-Length: N\r\n\r\n<body>`
+//!
+//! 消息可以是 JSON-RPC 批量数组，回复为对应的响应数组 (全部为通知时不回复)。
+//!
+//! 远程模式 `java-perf mcp --listen ADDR`: 每个 HTTP POST 请求体是一条 JSON-RPC 消息，
+//! 须携带 `Authorization: Bearer <token>` (非回环地址必须配置 token)。
+//! [`AccessPolicy`] 限制工具可读写的目录、可 attach 的 PID 与可 exec 的容器/Pod (stdio 模式同样生效)。
+//!
+//! 工具输出 (含错误信息) 返回前经过 [`crate::redact`] 脱敏，内容会被发送给 LLM 服务商。
+//! 每个工具都接受 `maxOutputTokens` / `maxBytes`，超出时按 [`output_budget`] 的优先级裁剪
+//! (保留全部 P0，P1 汇总，省略代码片段)。
+
+use clap::ValueEnum;
+use once_cell::sync::Lazy;
+use serde_json::{json, Map, Value};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, VecDeque};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::{Arc, Condvar, Mutex};
+use walkdir::WalkDir;
+
+use crate::ast_engine::{ScanResult, Severity};
+use crate::rules::{advisory, complexity_budget};
+use crate::rules::taxonomy::Tag;
+use crate::{ast_engine, call_chain, checklist, crash, forensic, jdk_engine, native_config, output_budget, redact, rule_pack, scan_diff, verify};
+
+/// 支持的 MCP 协议版本
+pub const PROTOCOL_VERSION: &str = "2024-11-05";
+
+const PARSE_ERROR: i64 = -32700;
+const INVALID_REQUEST: i64 = -32600;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+type ToolResult = Result<Value, Box<dyn std::error::Error>>;
+
+/// 单条消息体上限 (JSON-RPC 请求只含工具参数，远小于此)
+const MAX_BODY_BYTES: usize = 4 * 1024 * 1024;
+/// HTTP 请求行 + 头部上限 (认证之前读取，必须有界)
+const MAX_HEADER_BYTES: u64 = 16 * 1024;
+
+/// 工具访问控制: 允许读写的目录、允许 attach 的 PID 与允许 exec 的容器/Pod，列表为空表示不限制
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AccessPolicy {
+    /// 规范化后的目录 (解析符号链接)
+    paths: Vec<PathBuf>,
+    pids: Vec<u32>,
+    /// Docker 容器名，或 Pod 名 (`namespace/pod` 时只允许该命名空间)
+    hosts: Vec<String>,
+}
+
+impl AccessPolicy {
+    /// 目录必须存在 (启动时规范化，避免符号链接绕过)
+    pub fn new(paths: &[PathBuf], pids: &[u32], hosts: &[String]) -> std::io::Result<Self> {
+        let paths = paths.iter()
+            .map(|p| std::fs::canonicalize(p).map_err(|e| std::io::Error::new(e.kind(), format!("{}: {e}", p.display()))))
+            .collect::<Result<_, _>>()?;
+        Ok(AccessPolicy { paths, pids: pids.to_vec(), hosts: hosts.to_vec() })
+    }
+
+    /// 路径 (可以尚不存在，如写出目录) 必须位于某个允许的目录下
+    fn check_path(&self, path: &Path) -> Result<(), String> {
+        if self.paths.is_empty() {
+            return Ok(());
+        }
+        let denied = || format!("Access denied: {} is outside the allowed paths", path.display());
+
+        // 规范化最近的已存在祖先，其余部分原样拼接 (不允许 `..`)
+        let absolute = std::path::absolute(path).map_err(|_| denied())?;
+        let mut existing = absolute.as_path();
+        let mut rest = Vec::new();
+        let resolved = loop {
+            if let Ok(canonical) = std::fs::canonicalize(existing) {
+                break canonical;
+            }
+            let (Some(parent), Some(name)) = (existing.parent(), existing.file_name()) else {
+                return Err(denied());
+            };
+            rest.push(name);
+            existing = parent;
+        };
+        if absolute.components().any(|c| c == Component::ParentDir) && !rest.is_empty() {
+            return Err(denied());
+        }
+        let resolved = rest.iter().rev().fold(resolved, |acc, name| acc.join(name));
+
+        if self.paths.iter().any(|allowed| resolved.starts_with(allowed)) {
+            Ok(())
+        } else {
+            Err(denied())
+        }
+    }
+
+    fn check_pid(&self, pid: u32) -> Result<(), String> {
+        if self.pids.is_empty() || self.pids.contains(&pid) {
+            Ok(())
+        } else {
+            Err(format!("Access denied: pid {pid} is not in the allowed pids"))
+        }
+    }
+
+    /// 本机进程不受限制；容器/Pod 须在允许列表中 (Pod 按 `namespace/pod` 或 `pod` 匹配)
+    fn check_host(&self, host: &jdk_engine::JvmHost) -> Result<(), String> {
+        match host.target() {
+            Some(target) if !self.hosts.is_empty() && !self.hosts.contains(&target) => {
+                Err(format!("Access denied: {target} is not in the allowed hosts"))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// 运行 stdio 服务循环，直到 stdin 关闭
+pub fn serve(policy: &AccessPolicy) -> Result<(), Box<dyn std::error::Error>> {
+    serve_on(std::io::stdin().lock(), std::io::stdout(), policy)
+}
+
+/// 在任意读写端上运行服务循环 (stdio 之外便于测试)
+pub fn serve_on(mut reader: impl BufRead, mut writer: impl Write, policy: &AccessPolicy) -> Result<(), Box<dyn std::error::Error>> {
+    while let Some((framing, payload)) = read_message(&mut reader)? {
+        if let Some(response) = handle_payload(&payload, policy) {
+            write_message(&mut writer, framing, &serde_json::to_string(&response)?)?;
+        }
+    }
+    Ok(())
+}
+
+/// 消息分帧
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Framing {
+    /// 每行一条 JSON
+    Newline,
+    /// `Content-Length` 头部 + 空行 + 定长消息体
+    ContentLength,
+}
+
+/// 读取下一条消息，输入结束时返回 None
+///
+/// 以 `{`/`[` 开头的行是 newline-delimited 消息，否则按头部解析 (直到空行)。
+fn read_message(reader: &mut impl BufRead) -> std::io::Result<Option<(Framing, String)>> {
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let trimmed = line.trim();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if trimmed.starts_with('{') || trimmed.starts_with('[') {
+            return Ok(Some((Framing::Newline, trimmed.to_string())));
+        }
+        break;
+    }
+
+    let headers = read_headers(reader, line)?;
+    Ok(Some((Framing::ContentLength, read_body(reader, &headers)?)))
+}
+
+/// 从 `first` 行开始读取头部直到空行，名称转为小写
+fn read_headers(reader: &mut impl BufRead, first: String) -> std::io::Result<Vec<(String, String)>> {
+    let mut headers = Vec::new();
+    let mut line = first;
+    loop {
+        let header = line.trim();
+        if header.is_empty() {
+            return Ok(headers);
+        }
+        let (name, value) = header.split_once(':').ok_or_else(|| invalid_data(format!("Invalid header: {header}")))?;
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid_data("Unexpected end of input in headers".to_string()));
+        }
+    }
+}
+
+fn header<'a>(headers: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    headers.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str())
+}
+
+/// 按 `Content-Length` 读取消息体 (超过 [`MAX_BODY_BYTES`] 直接拒绝)
+fn read_body(reader: &mut impl BufRead, headers: &[(String, String)]) -> std::io::Result<String> {
+    let length = header(headers, "content-length")
+        .ok_or_else(|| invalid_data("Missing Content-Length header".to_string()))?
+        .parse::<usize>()
+        .map_err(|e| invalid_data(format!("Invalid Content-Length: {e}")))?;
+    if length > MAX_BODY_BYTES {
+        return Err(invalid_data(format!("Content-Length {length} exceeds the {MAX_BODY_BYTES} byte limit")));
+    }
+    let mut body = vec![0; length];
+    reader.read_exact(&mut body)?;
+    String::from_utf8(body).map_err(|e| invalid_data(format!("Invalid UTF-8 body: {e}")))
+}
+
+fn invalid_data(message: String) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, message)
+}
+
+fn write_message(writer: &mut impl Write, framing: Framing, body: &str) -> std::io::Result<()> {
+    match framing {
+        Framing::Newline => writeln!(writer, "{body}")?,
+        Framing::ContentLength => write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())?,
+    }
+    writer.flush()
+}
+
+/// 处理一条消息 (单个请求或批量数组)，无需回复时返回 None
+pub fn handle_payload(payload: &str, policy: &AccessPolicy) -> Option<Value> {
+    match serde_json::from_str::<Value>(payload) {
+        Ok(Value::Array(batch)) if batch.is_empty() => {
+            Some(error_response(Value::Null, INVALID_REQUEST, "Invalid Request: empty batch"))
+        }
+        Ok(Value::Array(batch)) => {
+            let responses: Vec<Value> = batch.iter().filter_map(|msg| handle_request(msg, policy)).collect();
+            (!responses.is_empty()).then_some(Value::Array(responses))
+        }
+        Ok(msg) => handle_request(&msg, policy),
+        Err(e) => Some(error_response(Value::Null, PARSE_ERROR, &format!("Parse error: {e}"))),
+    }
+}
+
+/// 批量数组中的元素也必须是请求对象
+fn handle_request(msg: &Value, policy: &AccessPolicy) -> Option<Value> {
+    if msg.is_object() {
+        handle_message(msg, policy)
+    } else {
+        Some(error_response(Value::Null, INVALID_REQUEST, "Invalid Request"))
+    }
+}
+
+/// 处理单条 JSON-RPC 消息，通知 (无 id) 返回 None
+pub fn handle_message(msg: &Value, policy: &AccessPolicy) -> Option<Value> {
+    let id = msg.get("id").cloned()?;
+    let method = msg.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = msg.get("params").cloned().unwrap_or(Value::Null);
+
+    // 审计: 每个请求一个 span，工具在其中记录 cached / p0 / p1
+    let span = tracing::info_span!(
+        "mcp_request",
+        request_id = %id,
+        method,
+        tool = tracing::field::Empty,
+        arguments = tracing::field::Empty,
+        cached = tracing::field::Empty,
+        coalesced = tracing::field::Empty,
+        p0 = tracing::field::Empty,
+        p1 = tracing::field::Empty,
+    );
+    if method == "tools/call" {
+        span.record("tool", params.get("name").and_then(Value::as_str).unwrap_or(""));
+        span.record("arguments", params.get("arguments").map(Value::to_string).unwrap_or_default().as_str());
+    }
+    let _enter = span.enter();
+    let started = std::time::Instant::now();
+
+    let result = match method {
+        "initialize" => Ok(json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": { "tools": {}, "prompts": {} },
+            "serverInfo": { "name": "java-perf", "version": env!("CARGO_PKG_VERSION") },
+        })),
+        "ping" => Ok(json!({})),
+        "tools/list" => Ok(json!({ "tools": tool_definitions() })),
+        "tools/call" => Ok(call_tool(&params, policy)),
+        "prompts/list" => Ok(json!({ "prompts": PROMPTS.iter().map(Prompt::definition).collect::<Vec<_>>() })),
+        "prompts/get" => get_prompt(&params),
+        _ => Err((METHOD_NOT_FOUND, format!("Method not found: {method}"))),
+    };
+
+    let is_error = result.as_ref().map_or(true, |r| r.get("isError") == Some(&Value::Bool(true)));
+    tracing::info!(duration_ms = started.elapsed().as_secs_f64() * 1000.0, is_error, "mcp request");
+
+    Some(match result {
+        Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        Err((code, message)) => error_response(id, code, &message),
+    })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+// ============================================================================
+// HTTP transport - 远程模式
+// ============================================================================
+
+/// 运行 HTTP 服务 (每个连接一个线程，一个连接一个请求)
+///
+/// 监听非回环地址时必须配置 `token`。
+pub fn serve_http(addr: &str, token: Option<String>, policy: AccessPolicy) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = TcpListener::bind(addr)?;
+    let local = listener.local_addr()?;
+    if token.is_none() && !local.ip().is_loopback() {
+        return Err(format!("refusing to serve MCP on non-loopback address {local} without --token").into());
+    }
+    eprintln!("java-perf MCP listening on http://{local}/");
+
+    let shared = Arc::new((token, policy));
+    for stream in listener.incoming() {
+        let stream = match stream {
+            Ok(stream) => stream,
+            Err(e) => {
+                tracing::warn!("MCP HTTP accept failed: {e}");
+                continue;
+            }
+        };
+        let shared = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            let (token, policy) = &*shared;
+            if let Err(e) = handle_connection(stream, token.as_deref(), policy) {
+                tracing::warn!("MCP HTTP connection failed: {e}");
+            }
+        });
+    }
+    Ok(())
+}
+
+fn handle_connection(stream: TcpStream, token: Option<&str>, policy: &AccessPolicy) -> std::io::Result<()> {
+    stream.set_read_timeout(Some(std::time::Duration::from_secs(30)))?;
+    let mut reader = BufReader::new(stream.try_clone()?);
+    handle_http(&mut reader, stream, token, policy)
+}
+
+/// 处理一个 HTTP 请求: `POST` + Bearer token + JSON-RPC 请求体
+///
+/// 头部最多读取 [`MAX_HEADER_BYTES`]，token 校验通过后才读取消息体。
+fn handle_http(reader: &mut impl BufRead, mut writer: impl Write, token: Option<&str>, policy: &AccessPolicy) -> std::io::Result<()> {
+    let mut limited = reader.by_ref().take(MAX_HEADER_BYTES);
+    let mut request_line = String::new();
+    limited.read_line(&mut request_line)?;
+    let mut first = String::new();
+    limited.read_line(&mut first)?;
+    let headers = read_headers(&mut limited, first);
+    let truncated = limited.limit() == 0;
+
+    let authorized = |headers: &[(String, String)]| token.is_none_or(|expected| {
+        header(headers, "authorization")
+            .and_then(|v| v.strip_prefix("Bearer "))
+            .is_some_and(|given| constant_time_eq(given.trim().as_bytes(), expected.as_bytes()))
+    });
+
+    let (status, body) = match headers {
+        Err(_) if truncated => ("431 Request Header Fields Too Large", None),
+        Err(e) => return Err(e),
+        Ok(_) if !request_line.starts_with("POST ") => ("405 Method Not Allowed", None),
+        Ok(headers) if !authorized(&headers) => ("401 Unauthorized", None),
+        Ok(headers) => match read_body(reader, &headers) {
+            Ok(payload) => match handle_payload(&payload, policy) {
+                Some(response) => ("200 OK", Some(serde_json::to_string(&response)?)),
+                None => ("202 Accepted", None),
+            },
+            Err(_) => ("400 Bad Request", None),
+        },
+    };
+
+    let body = body.unwrap_or_default();
+    let mut head = format!("HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n", body.len());
+    if !body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    if status.starts_with("401") {
+        head.push_str("WWW-Authenticate: Bearer\r\n");
+    }
+    write!(writer, "{head}\r\n{body}")?;
+    writer.flush()
+}
+
+/// 比较 token 时不因首个不同字节提前返回
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+// ============================================================================
+// Tools
+// ============================================================================
+
+fn tool(name: &str, description: &str, mut properties: Value, required: &[&str]) -> Value {
+    // 所有工具共用的输出预算参数 (见 call_tool)
+    if let Some(map) = properties.as_object_mut() {
+        map.insert("maxOutputTokens".to_string(), json!({ "type": "integer", "description": "输出 token 上限 (估算)；超出时保留全部 P0，P1 汇总为按规则计数，省略代码片段" }));
+        map.insert("maxBytes".to_string(), json!({ "type": "integer", "description": "输出字节上限，裁剪方式同 maxOutputTokens" }));
+    }
+    json!({
+        "name": name,
+        "description": description,
+        "inputSchema": { "type": "object", "properties": properties, "required": required },
+    })
+}
+
+fn tool_definitions() -> Vec<Value> {
+    vec![
+        tool("radar_scan", "全项目雷达扫描 (双遍 AST 语义分析)，默认只返回 P0", json!({
+            "path": { "type": "string", "description": "项目路径" },
+            "full": { "type": "boolean", "description": "包含 P1 警告" },
+            "max_p1": { "type": "integer", "description": "最多返回的 P1/P2/Info 数量 (默认 5，P0 全部返回)" },
+            "min_severity": { "type": "string", "enum": ["p0", "p1", "p2", "info"], "description": "只返回不低于该级别的问题 (默认全部)" },
+            "only_tags": { "type": "string", "description": "只返回带有任一标签的问题，逗号分隔: concurrency,memory,gc,cpu,db,io,reactive,config,logging,docker,build,spark,security-adjacent,test" },
+            "force": { "type": "boolean", "description": "忽略缓存重新扫描 (默认在源码与规则未变化时复用上次结果)" },
+        }), &["path"]),
+        tool("verify_issue", "Sniper 单点验证: 在完整语义上下文中重新分析一个问题，返回结论/置信度/证据", json!({
+            "path": { "type": "string", "description": "项目路径" },
+            "file": { "type": "string", "description": "问题所在文件 (相对项目路径或绝对路径)" },
+            "line": { "type": "integer", "description": "问题行号" },
+            "id": { "type": "string", "description": "规则 ID，如 N_PLUS_ONE" },
+        }), &["path", "file", "line", "id"]),
+        tool("get_call_chain", "调用链影响面: 给定类+方法，返回上游 Controller 入口路径与下游 Repository 调用路径", json!({
+            "path": { "type": "string", "description": "项目路径" },
+            "class": { "type": "string", "description": "类名 (FQN 或简单类名)" },
+            "method": { "type": "string", "description": "方法名" },
+            "max_depth": { "type": "integer", "description": "最大追踪深度 (默认 5)" },
+        }), &["path", "class", "method"]),
+        tool("generate_native_config", "汇总 Class.forName / Method.invoke / Proxy.newProxyInstance 调用点，生成 GraalVM reflect-config.json / proxy-config.json 骨架 (动态名称为 TODO 条目)", json!({
+            "path": { "type": "string", "description": "项目路径" },
+            "out": { "type": "string", "description": "写出目录 (与已有配置合并)，不指定时只返回预览" },
+        }), &["path"]),
+        tool("compare_scans", "对比两份 scan --format json 报告: 按稳定指纹分为新增/已解决/未变化", json!({
+            "old": { "type": "string", "description": "基线扫描报告路径" },
+            "new": { "type": "string", "description": "本次扫描报告路径" },
+        }), &["old", "new"]),
+        tool("analyze_file", "单文件 AST 分析", json!({
+            "file": { "type": "string", "description": "Java 文件路径" },
+        }), &["file"]),
+        tool("get_checklist", "按症状获取检查清单", json!({
+            "symptoms": { "type": "string", "description": "逗号分隔: memory,cpu,slow,resource,backlog,gc,p99-spike,timeout,connection-reset,disk-io,network,startup-slow，也可传用户的自由文本描述 (按关键词推断)" },
+            "full": { "type": "boolean", "description": "包含 verify/fix/why" },
+        }), &["symptoms"]),
+        tool("get_antipatterns", "列出项目实际注册的性能规则: 级别 (含 .java-perf.toml 覆盖)、分类、修复建议、当前档案下是否启用", json!({
+            "path": { "type": "string", "description": "项目路径 (默认当前目录)" },
+        }), &[]),
+        tool("analyze_log", "日志指纹归类 (异常/错误聚合)；含 -verbose:class / Metaspace 记录时追加类加载泄漏、代理类爆炸、Metaspace 增长分析；follow 时跟踪新增内容并报告窗口内指纹与基线的差异", json!({
+            "file": { "type": "string", "description": "日志文件路径" },
+            "follow": { "type": "boolean", "description": "跟踪文件新增内容 (复现问题期间使用)，结束时返回窗口内新出现/增多的指纹" },
+            "duration": { "type": "string", "description": "跟踪时长，如 30s / 2m (默认 60s，最长 10m)" },
+        }), &["file"]),
+        tool("analyze_crash", "JVM 崩溃日志 (hs_err_pid*.log) 分析: SIGSEGV/OOM 原因、栈顶帧、第三方本地库、GC 状态，给出疑似子系统 (JIT/GC/JNI/Unsafe/本地内存) 与检查清单章节", json!({
+            "file": { "type": "string", "description": "hs_err 日志路径" },
+        }), &["file"]),
+        tool("list_jvms", "列出本机 Java 进程 (PID、主类、JVM 参数、运行时长)，用于选择 jstack/jmap 的 PID", jvm_target_properties(json!({})), &[]),
+        tool("analyze_thread_dump", "jstack 线程 Dump 分析: 线程状态、热点锁 (持有者/等待者)、死锁环、已知问题模式 (连接池耗尽、commonPool 阻塞等)、相同栈分组、线程池聚合", jvm_target_properties(json!({
+            "pid": { "type": "integer", "description": "Java 进程 PID (容器内通常为 1)" },
+        })), &["pid"]),
+        tool("analyze_heap", "jmap 堆直方图分析，或写出堆转储到本地", jvm_target_properties(json!({
+            "pid": { "type": "integer", "description": "Java 进程 PID (容器内通常为 1)" },
+            "live": { "type": "boolean", "description": "只统计存活对象 (histo:live 触发 Full GC，需要 force)" },
+            "dump": { "type": "string", "description": "写出堆转储 (.hprof) 的本地路径 (暂停 JVM，需要 force)" },
+            "force": { "type": "boolean", "description": "确认执行会暂停目标 JVM 的操作；调用前须向用户说明影响并获得同意" },
+            "samples": { "type": "integer", "description": "直方图采样次数，>1 时报告增长最快的类 (默认 1)" },
+            "interval": { "type": "string", "description": "采样间隔，如 30s / 2m (默认 30s)" },
+        })), &["pid"]),
+        tool("analyze_bytecode", "javap 字节码分析 (.class 或 JAR/WAR)", json!({
+            "class": { "type": "string", "description": "类路径、.class 文件或 JAR/WAR" },
+            "package": { "type": "string", "description": "JAR/WAR 包 glob，如 com.acme.**" },
+        }), &["class"]),
+    ]
+}
+
+/// 容器中的目标 JVM (与 CLI `--container`/`--pod`/`--namespace`/`--user` 一致)
+fn jvm_target_properties(mut properties: Value) -> Value {
+    properties["container"] = json!({ "type": "string", "description": "Docker 容器 (与 pod 一起使用时为 Pod 内的容器名)" });
+    properties["pod"] = json!({ "type": "string", "description": "Kubernetes Pod" });
+    properties["namespace"] = json!({ "type": "string", "description": "Kubernetes 命名空间" });
+    properties["user"] = json!({ "type": "string", "description": "docker exec 用户 (默认为 JVM 进程属主)" });
+    properties["timeout"] = json!({ "type": "integer", "description": "JDK 工具超时秒数，超时后终止 (默认 60)" });
+    properties
+}
+
+/// 执行工具，错误以 `isError: true` 返回给客户端 (不是 JSON-RPC 错误)
+fn call_tool(params: &Value, policy: &AccessPolicy) -> Value {
+    let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+    let mut args = params.get("arguments").cloned().unwrap_or_else(|| json!({}));
+    // 预算参数不传给工具，参数相同、预算不同的并发请求仍可合并
+    let budget = match output_budget::Budget::take_from(&mut args) {
+        Ok(budget) => budget,
+        Err(e) => return json!({ "content": [{ "type": "text", "text": e }], "isError": true }),
+    };
+
+    let run = || run_tool(name, &args, policy).map_err(|e| e.to_string());
+    let result = match EXPENSIVE_TOOLS.iter().find(|(tool, _)| *tool == name) {
+        Some(&(_, limit)) => TOOL_GATE.run(name, &args, limit, run),
+        None => run(),
+    };
+
+    // 工具输出会被发送给 LLM 服务商，返回前脱敏
+    match result {
+        Ok(value) => {
+            let text = match value {
+                Value::String(s) => s,
+                other => serde_json::to_string_pretty(&other).unwrap_or_default(),
+            };
+            let text = redact::redact(&text);
+            let text = match budget {
+                Some(budget) => output_budget::fit(&text, &budget),
+                None => text,
+            };
+            json!({ "content": [{ "type": "text", "text": text }], "isError": false })
+        }
+        Err(e) => json!({ "content": [{ "type": "text", "text": redact::redact(&e) }], "isError": true }),
+    }
+}
+
+fn run_tool(name: &str, args: &Value, policy: &AccessPolicy) -> ToolResult {
+    let str_arg = |key: &str| -> Result<&str, String> {
+        args.get(key).and_then(Value::as_str).ok_or_else(|| format!("Missing argument: {key}"))
+    };
+    // 文件系统参数与 PID 按访问控制检查
+    let path_arg = |key: &str| -> Result<&str, String> {
+        let path = str_arg(key)?;
+        policy.check_path(Path::new(path))?;
+        Ok(path)
+    };
+    let pid_arg = || -> Result<u32, String> {
+        let pid = args.get("pid").and_then(Value::as_u64)
+            .and_then(|p| u32::try_from(p).ok())
+            .ok_or_else(|| "Missing argument: pid".to_string())?;
+        policy.check_pid(pid)?;
+        Ok(pid)
+    };
+    let bool_arg = |key: &str| args.get(key).and_then(Value::as_bool).unwrap_or(false);
+    let opt_arg = |key: &str| args.get(key).and_then(Value::as_str).map(str::to_string);
+    let opt_path_arg = |key: &str| -> Result<Option<String>, String> {
+        opt_arg(key).map(|p| policy.check_path(Path::new(&p)).map(|()| p)).transpose()
+    };
+    let host_arg = || -> Result<jdk_engine::JvmHost, String> {
+        let host = jdk_engine::JvmHost::new(opt_arg("container"), opt_arg("pod"), opt_arg("namespace"), opt_arg("user"))?;
+        policy.check_host(&host)?;
+        Ok(host)
+    };
+    let safety = jdk_engine::Safety {
+        force: bool_arg("force"),
+        timeout: args.get("timeout").and_then(Value::as_u64).map_or(jdk_engine::DEFAULT_TIMEOUT, std::time::Duration::from_secs),
+    };
+
+    match name {
+        "radar_scan" => {
+            let max_p1 = args.get("max_p1").and_then(Value::as_u64).unwrap_or(5) as usize;
+            let min_severity = opt_arg("min_severity")
+                .map(|s| Severity::from_str(&s, true).map_err(|_| format!("min_severity must be one of p0 | p1 | p2 | info, got '{s}'")))
+                .transpose()?;
+            let only_tags = opt_arg("only_tags").map_or(Ok(Vec::new()), |tags| {
+                tags.split(',').map(str::trim).filter(|t| !t.is_empty())
+                    .map(|t| Tag::from_str(t, true).map_err(|_| format!("unknown tag '{t}' in only_tags")))
+                    .collect::<Result<Vec<_>, _>>()
+            })?;
+            cached_scan(path_arg("path")?, bool_arg("force"), ScanView { compact: !bool_arg("full"), max_p1, min_severity, only_tags })
+        }
+        "verify_issue" => {
+            let line = args.get("line").and_then(Value::as_u64).ok_or("Missing argument: line")? as usize;
+            let (path, file) = (path_arg("path")?, str_arg("file")?);
+            policy.check_path(&Path::new(path).join(file))?;
+            verify::verify_issue(path, file, line, str_arg("id")?)
+        }
+        "get_call_chain" => {
+            let max_depth = args.get("max_depth").and_then(Value::as_u64)
+                .map_or(call_chain::DEFAULT_MAX_DEPTH, |d| d as usize);
+            call_chain::get_call_chain(path_arg("path")?, str_arg("class")?, str_arg("method")?, max_depth)
+        }
+        "generate_native_config" => {
+            native_config::generate(path_arg("path")?, opt_path_arg("out")?.as_deref().map(Path::new))
+        }
+        "compare_scans" => scan_diff::compare_files(path_arg("old")?, path_arg("new")?),
+        "analyze_file" => {
+            let file = path_arg("file")?;
+            let content = std::fs::read_to_string(file)?;
+            ast_engine::scan_source_code(&content, file)
+        }
+        "get_checklist" => {
+            let symptoms: Vec<&str> = str_arg("symptoms")?.split(',').map(str::trim).collect();
+            checklist::get_checklist(&symptoms, None, !bool_arg("full"))
+        }
+        "get_antipatterns" => {
+            let path = opt_arg("path").unwrap_or_else(|| ".".to_string());
+            policy.check_path(Path::new(&path))?;
+            checklist::get_all_antipatterns(&path, &ast_engine::ScanOptions::default()).map(|v| v["report"].clone())
+        }
+        "analyze_log" if bool_arg("follow") => {
+            let duration = jdk_engine::parse_interval(opt_arg("duration").as_deref().unwrap_or("60s"))?;
+            forensic::follow_log(path_arg("file")?, duration)
+        }
+        "analyze_log" => forensic::analyze_log(path_arg("file")?),
+        "analyze_crash" => crash::analyze_crash(path_arg("file")?),
+        "list_jvms" => jdk_engine::list_jvms(&host_arg()?, &safety),
+        "analyze_thread_dump" => jdk_engine::analyze_thread_dump(pid_arg()?, &host_arg()?, &safety),
+        "analyze_heap" => match args.get("samples").and_then(Value::as_u64).unwrap_or(1) {
+            samples if samples > 1 => {
+                let interval = jdk_engine::parse_interval(opt_arg("interval").as_deref().unwrap_or("30s"))?;
+                jdk_engine::analyze_heap_trend(pid_arg()?, &host_arg()?, u32::try_from(samples)?, interval, bool_arg("live"), &safety)
+            }
+            _ => jdk_engine::analyze_heap(pid_arg()?, &host_arg()?, bool_arg("live"), opt_path_arg("dump")?.as_deref(), &safety),
+        },
+        "analyze_bytecode" => {
+            let package = args.get("package").and_then(Value::as_str);
+            jdk_engine::analyze_bytecode(path_arg("class")?, package)
+        }
+        _ => Err(format!("Unknown tool: {name}").into()),
+    }
+}
+
+// ============================================================================
+// Scan cache - radar_scan 结果缓存
+// ============================================================================
+
+/// 缓存的扫描结果数 (最近使用的在前)
+const SCAN_CACHE_SIZE: usize = 8;
+
+/// 缓存键: 路径相同、文件未变化、规则集未变化时扫描结果相同
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ScanKey {
+    path: PathBuf,
+    /// 扫描根下所有文件的 (相对路径, mtime, 大小) 摘要
+    files: u64,
+    /// 规则集指纹: 工具版本 + 已安装规则包 + 脚本规则目录 `.java-perf/rules` + `.java-perf.toml` + [`SCAN_INPUTS`]
+    rules: u64,
+}
+
+/// 扫描后处理读取的其他 `.java-perf` 文件 (该目录下其余文件是工具自身产物，不影响扫描结果)
+const SCAN_INPUTS: &[&str] = &[advisory::PROJECT_DB];
+
+/// 扫描结果按格式参数渲染，缓存的是未渲染的结果 (`full`/`max_p1` 不影响命中)
+static SCAN_CACHE: Lazy<Mutex<VecDeque<(ScanKey, ScanResult)>>> = Lazy::new(Default::default);
+
+impl ScanKey {
+    /// 路径不存在时返回 None (不缓存)
+    fn new(path: &str) -> Option<Self> {
+        let path = std::fs::canonicalize(path).ok()?;
+        let mut files = DefaultHasher::new();
+        let mut rules = DefaultHasher::new();
+        env!("CARGO_PKG_VERSION").hash(&mut rules);
+        cfg!(feature = "script-rules").hash(&mut rules);
+        rule_pack::installed().hash(&mut rules);
+
+        let entries = WalkDir::new(&path)
+            .follow_links(true)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|e| e.file_name() != ".git")
+            .filter_map(Result::ok)
+            .filter(|e| e.file_type().is_file());
+        for entry in entries {
+            let Ok(meta) = entry.metadata() else { continue };
+            let rel = entry.path().strip_prefix(&path).unwrap_or(entry.path());
+            let stamp = (rel, meta.modified().ok(), meta.len());
+            let input = rel.starts_with(".java-perf/rules")
+                || rel == Path::new(complexity_budget::CONFIG_FILE)
+                || SCAN_INPUTS.iter().any(|f| rel == Path::new(f));
+            if input {
+                stamp.hash(&mut rules);
+            } else if !rel.starts_with(".java-perf") {
+                // index.bin 等工具自身产物不影响扫描结果
+                stamp.hash(&mut files);
+            }
+        }
+        Some(ScanKey { path, files: files.finish(), rules: rules.finish() })
+    }
+}
+
+/// radar_scan 的渲染与过滤参数 (不影响缓存命中)
+struct ScanView {
+    compact: bool,
+    max_p1: usize,
+    min_severity: Option<Severity>,
+    only_tags: Vec<Tag>,
+}
+
+/// 带缓存的双遍扫描: 命中时报告扫描信息带 `cached=true`，`force` 时重新扫描并刷新缓存
+fn cached_scan(path: &str, force: bool, view: ScanView) -> ToolResult {
+    let ScanView { compact, max_p1, min_severity, only_tags } = view;
+    let lock = || SCAN_CACHE.lock().unwrap_or_else(|e| e.into_inner());
+    let render = |result: &ScanResult| {
+        // 缓存的是未过滤的结果，按级别/标签过滤时渲染副本
+        let filtered = (min_severity.is_some() || !only_tags.is_empty()).then(|| {
+            let mut copy = result.clone();
+            if let Some(min) = min_severity {
+                copy.retain_min_severity(min);
+            }
+            if !only_tags.is_empty() {
+                copy.retain_tags(&only_tags);
+            }
+            copy
+        });
+        let result = filtered.as_ref().unwrap_or(result);
+        let span = tracing::Span::current();
+        span.record("p0", result.issues.iter().filter(|i| i.severity == Severity::P0).count());
+        span.record("p1", result.issues.iter().filter(|i| i.severity == Severity::P1).count());
+        ast_engine::render_markdown(result, compact, max_p1)
+    };
+    let Some(key) = ScanKey::new(path) else {
+        return ast_engine::radar_scan(path, compact, max_p1);
+    };
+
+    if !force {
+        let mut cache = lock();
+        if let Some(pos) = cache.iter().position(|(k, _)| *k == key) {
+            let entry = cache.remove(pos).expect("position is in range");
+            tracing::Span::current().record("cached", true);
+            let report = render(&entry.1);
+            cache.push_front(entry);
+            return Ok(report);
+        }
+    }
+
+    // 扫描期间不持有锁；键在扫描前计算，扫描中途修改的文件在下次调用时失效
+    let mut result = ast_engine::collect_issues(path)?;
+    let report = render(&result);
+    result.info.cached = true;
+
+    let mut cache = lock();
+    cache.retain(|(k, _)| *k != key);
+    cache.push_front((key, result));
+    cache.truncate(SCAN_CACHE_SIZE);
+    Ok(report)
+}
+
+// ============================================================================
+// Tool gate - 昂贵工具并发限制
+// ============================================================================
+
+/// 昂贵工具的并发上限 (全项目扫描占满 CPU，jmap 会暂停目标 JVM)
+const EXPENSIVE_TOOLS: &[(&str, usize)] = &[("radar_scan", 1), ("analyze_heap", 1)];
+
+/// 工具结果 (错误转为文本，便于在合并的请求间共享)
+type SharedResult = Result<Value, String>;
+
+/// 一次工具执行，结果由所有相同请求共享
+#[derive(Default)]
+struct Flight {
+    result: Mutex<Option<SharedResult>>,
+    done: Condvar,
+}
+
+impl Flight {
+    fn finish(&self, result: SharedResult) {
+        *self.result.lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+        self.done.notify_all();
+    }
+
+    fn wait(&self) -> SharedResult {
+        let mut result = self.result.lock().unwrap_or_else(|e| e.into_inner());
+        loop {
+            if let Some(result) = result.as_ref() {
+                return result.clone();
+            }
+            result = self.done.wait(result).unwrap_or_else(|e| e.into_inner());
+        }
+    }
+}
+
+/// 按工具计数的信号量 + 相同 (工具, 参数) 请求合并
+#[derive(Default)]
+struct ToolGate {
+    state: Mutex<GateState>,
+    released: Condvar,
+}
+
+#[derive(Default)]
+struct GateState {
+    /// 各工具正在执行的数量
+    running: HashMap<String, usize>,
+    /// (工具, 参数) → 正在执行或排队中的请求
+    flights: HashMap<String, Arc<Flight>>,
+}
+
+static TOOL_GATE: Lazy<ToolGate> = Lazy::new(ToolGate::default);
+
+impl ToolGate {
+    fn lock(&self) -> std::sync::MutexGuard<'_, GateState> {
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// 在并发上限内执行 `run`；相同请求已在执行或排队时直接等待其结果
+    fn run(&self, tool: &str, args: &Value, limit: usize, run: impl FnOnce() -> SharedResult) -> SharedResult {
+        // serde_json 对象按键排序，参数顺序不同也视为相同请求
+        let key = format!("{tool} {args}");
+        let mut state = self.lock();
+        if let Some(flight) = state.flights.get(&key).cloned() {
+            drop(state);
+            tracing::Span::current().record("coalesced", true);
+            return flight.wait();
+        }
+
+        let flight = Arc::new(Flight::default());
+        state.flights.insert(key.clone(), Arc::clone(&flight));
+        while state.running.get(tool).copied().unwrap_or(0) >= limit {
+            state = self.released.wait(state).unwrap_or_else(|e| e.into_inner());
+        }
+        *state.running.entry(tool.to_string()).or_default() += 1;
+        drop(state);
+
+        let permit = Permit { gate: self, tool, key, flight };
+        let result = run();
+        permit.flight.finish(result.clone());
+        result
+    }
+}
+
+/// 执行结束 (含 panic) 时归还名额并移除合并记录
+struct Permit<'a> {
+    gate: &'a ToolGate,
+    tool: &'a str,
+    key: String,
+    flight: Arc<Flight>,
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        let mut state = self.gate.lock();
+        state.flights.remove(&self.key);
+        if let Some(running) = state.running.get_mut(self.tool) {
+            *running -= 1;
+        }
+        drop(state);
+        self.gate.released.notify_all();
+
+        let mut result = self.flight.result.lock().unwrap_or_else(|e| e.into_inner());
+        if result.is_none() {
+            *result = Some(Err(format!("{} aborted", self.tool)));
+            self.flight.done.notify_all();
+        }
+    }
+}
+
+// ============================================================================
+// Prompts - Radar-Sniper-Forensic 诊断剧本
+// ============================================================================
+
+/// 诊断剧本: 症状 → 检查清单 → 雷达扫描 → 法医取证
+struct Prompt {
+    name: &'static str,
+    title: &'static str,
+    description: &'static str,
+    /// `get_checklist` 的症状参数
+    symptoms: &'static str,
+    /// 有 PID 时调用的取证工具
+    forensic_tool: &'static str,
+    /// 取证阶段关注点
+    forensic_focus: &'static str,
+}
+
+const PROMPTS: &[Prompt] = &[
+    Prompt {
+        name: "diagnose-high-cpu",
+        title: "诊断高 CPU",
+        description: "CPU 飙高: 检查清单 → 雷达扫描 → jstack 热点线程",
+        symptoms: "cpu",
+        forensic_tool: "analyze_thread_dump",
+        forensic_focus: "RUNNABLE 线程的栈顶方法，与扫描出的 NESTED_LOOP / 正则 / 锁自旋位置对照",
+    },
+    Prompt {
+        name: "diagnose-memory-leak",
+        title: "诊断内存泄漏",
+        description: "内存暴涨/OOM: 检查清单 → 雷达扫描 → jmap 堆直方图",
+        symptoms: "memory,gc",
+        forensic_tool: "analyze_heap",
+        forensic_focus: "直方图 Top 类是否对应 THREADLOCAL_LEAK / UNBOUNDED_CACHE / 大集合",
+    },
+    Prompt {
+        name: "diagnose-slow-response",
+        title: "诊断响应慢",
+        description: "接口慢/超时: 检查清单 → 雷达扫描 → jstack 阻塞线程",
+        symptoms: "slow,resource",
+        forensic_tool: "analyze_thread_dump",
+        forensic_focus: "BLOCKED/WAITING 线程等待的锁与连接池，与 N_PLUS_ONE / NO_TIMEOUT / 锁相关问题对照",
+    },
+    Prompt {
+        name: "diagnose-backlog",
+        title: "诊断消息积压",
+        description: "队列/消息积压: 检查清单 → 雷达扫描 → jstack 消费线程",
+        symptoms: "backlog",
+        forensic_tool: "analyze_thread_dump",
+        forensic_focus: "消费者线程是否阻塞在同步 IO、锁或无超时调用上",
+    },
+];
+
+impl Prompt {
+    fn definition(&self) -> Value {
+        json!({
+            "name": self.name,
+            "title": self.title,
+            "description": self.description,
+            "arguments": [
+                { "name": "path", "description": "项目源码路径", "required": true },
+                { "name": "pid", "description": "目标 Java 进程 PID (可选，用于取证)", "required": false },
+            ],
+        })
+    }
+
+    fn render(&self, path: &str, pid: Option<&str>) -> String {
+        let call = |tool: &str, args: Value| format!("`{tool}` {}", serde_json::to_string(&args).unwrap_or_default());
+
+        let mut steps = vec![
+            format!("**Phase 0 知识预载**: 调用 {}，了解该症状的常见根因。",
+                call("get_checklist", json!({ "symptoms": self.symptoms }))),
+            format!("**Phase 1 雷达扫描**: 调用 {}，列出可疑位置 (P0 优先)。",
+                call("radar_scan", json!({ "path": path, "full": true }))),
+            "**Phase 2 狙击验证**: 逐个读取扫描结果中的源码位置，结合上下文确认或排除，不要直接采信扫描结果。".to_string(),
+        ];
+
+        match pid.and_then(|p| p.trim().parse::<u32>().ok()) {
+            Some(pid) => steps.push(format!("**Phase 3 法医取证**: 调用 {}，关注{}。",
+                call(self.forensic_tool, json!({ "pid": pid })), self.forensic_focus)),
+            None => steps.push(format!("**Phase 3 法医取证**: 调用 `list_jvms` 找到目标进程 (或向用户索取 PID) 后调用 `{}`，关注{}。",
+                self.forensic_tool, self.forensic_focus)),
+        }
+        steps.push("**Phase 4 影响评估**: 对确认的问题给出 位置 / 根因 / 量化影响 / 修复建议。".to_string());
+
+        let mut text = format!("请按 Radar-Sniper-Forensic 流程{}，项目路径 `{path}`：\n\n", self.title);
+        for (i, step) in steps.iter().enumerate() {
+            text.push_str(&format!("{}. {step}\n", i + 1));
+        }
+        text
+    }
+}
+
+fn get_prompt(params: &Value) -> Result<Value, (i64, String)> {
+    let name = params.get("name").and_then(Value::as_str).unwrap_or("");
+    let prompt = PROMPTS.iter().find(|p| p.name == name)
+        .ok_or_else(|| (INVALID_PARAMS, format!("Unknown prompt: {name}")))?;
+
+    let empty = Map::new();
+    let args = params.get("arguments").and_then(Value::as_object).unwrap_or(&empty);
+    let path = args.get("path").and_then(Value::as_str)
+        .ok_or_else(|| (INVALID_PARAMS, "Missing required argument: path".to_string()))?;
+    let pid = args.get("pid").and_then(Value::as_str);
+
+    Ok(json!({
+        "description": prompt.description,
+        "messages": [{
+            "role": "user",
+            "content": { "type": "text", "text": prompt.render(path, pid) },
+        }],
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(method: &str, params: Value) -> Value {
+        handle_message(&json!({ "jsonrpc": "2.0", "id": 1, "method": method, "params": params }), &AccessPolicy::default()).unwrap()
+    }
+
+    #[test]
+    fn test_initialize_and_notifications() {
+        let resp = request("initialize", json!({}));
+        assert_eq!(resp["result"]["protocolVersion"], PROTOCOL_VERSION);
+        assert!(resp["result"]["capabilities"]["prompts"].is_object());
+
+        assert!(handle_message(&json!({ "jsonrpc": "2.0", "method": "notifications/initialized" }), &AccessPolicy::default()).is_none());
+        assert_eq!(request("no/such", json!({}))["error"]["code"], METHOD_NOT_FOUND);
+    }
+
+    #[test]
+    fn test_framing_and_batch() {
+        let framed = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+        let input = format!(
+            "Content-Length: {}\r\nContent-Type: application/vscode-jsonrpc; charset=utf-8\r\n\r\n{framed}\n{}\n{}\n[]\n",
+            framed.len(),
+            r#"{"jsonrpc":"2.0","id":2,"method":"ping"}"#,
+            r#"[{"jsonrpc":"2.0","id":3,"method":"ping"},{"jsonrpc":"2.0","method":"notifications/initialized"},7]"#,
+        );
+        let mut output = Vec::new();
+        serve_on(input.as_bytes(), &mut output, &AccessPolicy::default()).unwrap();
+        let output = String::from_utf8(output).unwrap();
+
+        // 回复使用与请求相同的分帧
+        let expected = r#"{"id":1,"jsonrpc":"2.0","result":{}}"#;
+        assert!(output.starts_with(&format!("Content-Length: {}\r\n\r\n{expected}", expected.len())));
+        let lines: Vec<Value> = output[output.find(expected).unwrap() + expected.len()..]
+            .lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(lines[0]["id"], 2);
+        // 批量: 通知不回复，非对象元素为 Invalid Request
+        assert_eq!(lines[1].as_array().unwrap().len(), 2);
+        assert_eq!(lines[1][0]["id"], 3);
+        assert_eq!(lines[1][1]["error"]["code"], INVALID_REQUEST);
+        assert_eq!(lines[2]["error"]["code"], INVALID_REQUEST);
+        assert_eq!(lines.len(), 3);
+
+        assert!(handle_payload(r#"[{"jsonrpc":"2.0","method":"notifications/initialized"}]"#, &AccessPolicy::default()).is_none());
+        assert!(serve_on("Content-Type: x\r\n\r\n{}".as_bytes(), Vec::new(), &AccessPolicy::default()).is_err());
+    }
+
+    #[test]
+    fn test_tool_gate_limits_and_coalesces() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Barrier;
+
+        let gate = ToolGate::default();
+        let (executions, running, max_running) = (AtomicUsize::new(0), AtomicUsize::new(0), AtomicUsize::new(0));
+        let slow = |path: &str| -> SharedResult {
+            executions.fetch_add(1, Ordering::SeqCst);
+            max_running.fetch_max(running.fetch_add(1, Ordering::SeqCst) + 1, Ordering::SeqCst);
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            running.fetch_sub(1, Ordering::SeqCst);
+            Ok(json!(path))
+        };
+
+        // 相同参数: 只执行一次，所有调用者拿到同一结果
+        let barrier = Barrier::new(4);
+        let results: Vec<SharedResult> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..4).map(|_| s.spawn(|| {
+                barrier.wait();
+                gate.run("radar_scan", &json!({ "path": "/srv/app", "full": true }), 1, || slow("/srv/app"))
+            })).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(results.iter().all(|r| r.as_ref().unwrap() == "/srv/app"));
+        assert_eq!(executions.load(Ordering::SeqCst), 1);
+
+        // 不同参数: 逐个执行
+        executions.store(0, Ordering::SeqCst);
+        std::thread::scope(|s| {
+            let (gate, slow) = (&gate, &slow);
+            for path in ["/a", "/b", "/c"] {
+                s.spawn(move || gate.run("radar_scan", &json!({ "path": path }), 1, || slow(path)));
+            }
+        });
+        assert_eq!(executions.load(Ordering::SeqCst), 3);
+        assert_eq!(max_running.load(Ordering::SeqCst), 1);
+        assert!(gate.lock().flights.is_empty());
+        assert_eq!(gate.lock().running["radar_scan"], 0);
+    }
+
+    #[test]
+    fn test_access_policy() {
+        let allowed = tempfile::tempdir().unwrap();
+        let other = tempfile::tempdir().unwrap();
+        std::fs::write(allowed.path().join("app.log"), "INFO started").unwrap();
+        std::fs::write(other.path().join("app.log"), "INFO started").unwrap();
+        let policy = AccessPolicy::new(&[allowed.path().to_path_buf()], &[4242], &["prod/order-7d9f".to_string()]).unwrap();
+
+        assert!(policy.check_path(&allowed.path().join("app.log")).is_ok());
+        // 尚不存在的写出路径按已存在的祖先判断
+        assert!(policy.check_path(&allowed.path().join("out/native")).is_ok());
+        assert!(policy.check_path(&other.path().join("app.log")).is_err());
+        assert!(policy.check_path(&allowed.path().join("../x/y")).is_err());
+        assert!(policy.check_pid(4242).is_ok());
+        assert!(policy.check_pid(1).is_err());
+
+        let call = |name: &str, args: Value| {
+            let msg = json!({ "jsonrpc": "2.0", "id": 1, "method": "tools/call", "params": { "name": name, "arguments": args } });
+            handle_message(&msg, &policy).unwrap()["result"].clone()
+        };
+        let denied = call("analyze_log", json!({ "file": other.path().join("app.log") }));
+        assert_eq!(denied["isError"], true);
+        assert!(denied["content"][0]["text"].as_str().unwrap().starts_with("Access denied"));
+        assert_eq!(call("analyze_log", json!({ "file": allowed.path().join("app.log") }))["isError"], false);
+        let denied = call("analyze_thread_dump", json!({ "pid": 1 }));
+        assert!(denied["content"][0]["text"].as_str().unwrap().starts_with("Access denied"));
+        // 允许的 PID 也不能指向允许列表之外的容器/Pod
+        let denied = call("analyze_thread_dump", json!({ "pid": 4242, "container": "billing" }));
+        assert!(denied["content"][0]["text"].as_str().unwrap().starts_with("Access denied: billing"));
+        let denied = call("analyze_thread_dump", json!({ "pid": 4242, "pod": "order-7d9f", "namespace": "staging" }));
+        assert!(denied["content"][0]["text"].as_str().unwrap().starts_with("Access denied: staging/order-7d9f"));
+    }
+
+    #[test]
+    fn test_http_bearer_auth() {
+        let post = |auth: Option<&str>| {
+            let body = r#"{"jsonrpc":"2.0","id":1,"method":"ping"}"#;
+            let auth = auth.map(|a| format!("Authorization: {a}\r\n")).unwrap_or_default();
+            let input = format!("POST / HTTP/1.1\r\nHost: localhost\r\n{auth}Content-Length: {}\r\n\r\n{body}", body.len());
+            let mut output = Vec::new();
+            handle_http(&mut input.as_bytes(), &mut output, Some("s3cret"), &AccessPolicy::default()).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        let ok = post(Some("Bearer s3cret"));
+        assert!(ok.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(ok.ends_with(r#"{"id":1,"jsonrpc":"2.0","result":{}}"#));
+        assert!(post(Some("Bearer wrong")).starts_with("HTTP/1.1 401 Unauthorized\r\n"));
+        assert!(post(None).contains("WWW-Authenticate: Bearer"));
+
+        let mut output = Vec::new();
+        handle_http(&mut "GET / HTTP/1.1\r\n\r\n".as_bytes(), &mut output, None, &AccessPolicy::default()).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("HTTP/1.1 405"));
+
+        // 头部与消息体有上限: 未认证的请求不会读取消息体，超大 Content-Length 直接拒绝
+        let mut output = Vec::new();
+        let huge = format!("POST / HTTP/1.1\r\nX-Pad: {}\r\n\r\n", "a".repeat(MAX_HEADER_BYTES as usize));
+        handle_http(&mut huge.as_bytes(), &mut output, None, &AccessPolicy::default()).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("HTTP/1.1 431"));
+        let mut output = Vec::new();
+        let oversized = format!("POST / HTTP/1.1\r\nContent-Length: {}\r\n\r\n{{}}", usize::MAX);
+        handle_http(&mut oversized.as_bytes(), &mut output, None, &AccessPolicy::default()).unwrap();
+        assert!(String::from_utf8(output).unwrap().starts_with("HTTP/1.1 400"));
+    }
+
+    #[test]
+    fn test_prompts_list_and_get() {
+        let list = request("prompts/list", json!({}));
+        let names: Vec<&str> = list["result"]["prompts"].as_array().unwrap()
+            .iter().map(|p| p["name"].as_str().unwrap()).collect();
+        assert!(names.contains(&"diagnose-high-cpu"));
+
+        let resp = request("prompts/get", json!({
+            "name": "diagnose-high-cpu",
+            "arguments": { "path": "/srv/app", "pid": "4242" },
+        }));
+        let text = resp["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains(r#"`get_checklist` {"symptoms":"cpu"}"#));
+        assert!(text.contains(r#"`radar_scan` {"full":true,"path":"/srv/app"}"#));
+        assert!(text.contains(r#"`analyze_thread_dump` {"pid":4242}"#));
+
+        // 无 PID 时先列出本机 JVM 或向用户索取
+        let resp = request("prompts/get", json!({ "name": "diagnose-memory-leak", "arguments": { "path": "." } }));
+        let text = resp["result"]["messages"][0]["content"]["text"].as_str().unwrap();
+        assert!(text.contains("调用 `list_jvms` 找到目标进程 (或向用户索取 PID)"));
+
+        let resp = request("prompts/get", json!({ "name": "diagnose-high-cpu", "arguments": {} }));
+        assert_eq!(resp["error"]["code"], INVALID_PARAMS);
+    }
+
+    #[test]
+    fn test_tools_call() {
+        let list = request("tools/list", json!({}));
+        assert!(list["result"]["tools"].as_array().unwrap().iter().any(|t| t["name"] == "radar_scan"));
+
+        let resp = request("tools/call", json!({ "name": "get_checklist", "arguments": { "symptoms": "memory" } }));
+        assert_eq!(resp["result"]["isError"], false);
+        assert!(!resp["result"]["content"][0]["text"].as_str().unwrap().is_empty());
+
+        let resp = request("tools/call", json!({ "name": "analyze_thread_dump", "arguments": {} }));
+        assert_eq!(resp["result"]["isError"], true);
+    }
+
+    #[test]
+    fn test_output_budget() {
+        let list = request("tools/list", json!({}));
+        for tool in list["result"]["tools"].as_array().unwrap() {
+            assert_eq!(tool["inputSchema"]["properties"]["maxOutputTokens"]["type"], "integer", "{}", tool["name"]);
+            assert_eq!(tool["inputSchema"]["properties"]["maxBytes"]["type"], "integer", "{}", tool["name"]);
+        }
+
+        let text = |args: Value| {
+            let resp = request("tools/call", json!({ "name": "get_checklist", "arguments": args }));
+            (resp["result"]["isError"] == true, resp["result"]["content"][0]["text"].as_str().unwrap().to_string())
+        };
+        let (_, full) = text(json!({ "symptoms": "memory" }));
+        let (is_error, fitted) = text(json!({ "symptoms": "memory", "maxBytes": full.len() / 2 }));
+        assert!(!is_error);
+        assert!(fitted.len() <= full.len() / 2);
+        assert!(fitted.contains(&format!("输出超出预算 (maxBytes={})", full.len() / 2)));
+
+        let (is_error, message) = text(json!({ "symptoms": "memory", "maxOutputTokens": -1 }));
+        assert!(is_error);
+        assert_eq!(message, "maxOutputTokens must be a positive integer");
+    }
+
+    #[test]
+    fn test_radar_scan_cache() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("Order.java");
+        std::fs::write(&file, "class Order {}").unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+
+        let scan = |args: Value| {
+            let resp = request("tools/call", json!({ "name": "radar_scan", "arguments": args }));
+            assert_eq!(resp["result"]["isError"], false);
+            resp["result"]["content"][0]["text"].as_str().unwrap().to_string()
+        };
+
+        assert!(!scan(json!({ "path": path })).contains("`cached=true`"));
+        // 格式参数不同也命中同一份扫描结果
+        assert!(scan(json!({ "path": path, "full": true })).contains("`cached=true`"));
+        assert!(!scan(json!({ "path": path, "force": true })).contains("`cached=true`"));
+
+        // 源文件变化 → 失效
+        std::fs::write(&file, "class Order { int id; }").unwrap();
+        assert!(!scan(json!({ "path": path })).contains("`cached=true`"));
+        // 规则配置变化 → 失效
+        std::fs::write(dir.path().join(complexity_budget::CONFIG_FILE), "[complexity]\n").unwrap();
+        assert!(!scan(json!({ "path": path })).contains("`cached=true`"));
+        assert!(scan(json!({ "path": path })).contains("`cached=true`"));
+    }
+
+    #[test]
+    fn test_scan_key_tracks_advisory_db() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("pom.xml"), "<project/>").unwrap();
+        let path = dir.path().to_string_lossy().to_string();
+        let key = || ScanKey::new(&path).unwrap();
+
+        let before = key();
+        std::fs::create_dir_all(dir.path().join(".java-perf")).unwrap();
+        std::fs::write(dir.path().join(advisory::PROJECT_DB), "{\"advisories\": []}").unwrap();
+        let with_db = key();
+        assert_eq!(with_db.files, before.files);
+        assert_ne!(with_db.rules, before.rules);
+
+        // 编辑公告库 → 规则集指纹变化；索引等工具产物不影响
+        std::fs::write(dir.path().join(advisory::PROJECT_DB), "{\"advisories\": [{}]}").unwrap();
+        let edited = key();
+        assert_ne!(edited.rules, with_db.rules);
+        std::fs::write(dir.path().join(crate::index_store::INDEX_FILE), "x").unwrap();
+        assert_eq!(key(), edited);
+    }
+}
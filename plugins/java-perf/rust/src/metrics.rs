@@ -0,0 +1,309 @@
+//! Metrics 模块 - 方法复杂度度量 (v9.6)
+//!
+//! 📐 Phase 2 复用已解析的语法树，逐方法计算:
+//! - **cyclomatic**: 圈复杂度 (1 + if/循环/catch/三元/case/`&&`/`||`)
+//! - **loop_depth**: 最大循环嵌套深度
+//! - **allocations**: `new` 对象/数组次数 (`loop_allocations`: 其中位于循环内的次数)
+//! - **statements**: 语句数
+//!
+//! 扫描 JSON 的 `metrics.riskiest` 按 "问题权重 × 复杂度" 对被标记的方法排序，先看最危险的方法；
+//! `java-perf metrics --file X.java` 单独输出一个文件的度量。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::Path;
+use tree_sitter::Node;
+
+use crate::ast_engine::{self, AstIssue, Severity};
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+
+/// `metrics.riskiest` 最多返回的方法数
+const MAX_RISKIEST: usize = 20;
+
+/// 单个方法 (含构造器) 的度量
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MethodMetrics {
+    pub file: String,
+    /// 所在包 (无 package 声明时为空)
+    pub package: String,
+    pub class: String,
+    pub method: String,
+    pub line: usize,
+    pub end_line: usize,
+    pub cyclomatic: usize,
+    pub loop_depth: usize,
+    pub allocations: usize,
+    pub loop_allocations: usize,
+    pub statements: usize,
+    /// 复杂度分: 圈复杂度 + 3 × 循环深度 + 分配数 + 语句数 / 10
+    pub complexity: usize,
+}
+
+/// 被标记方法的风险排序项
+#[derive(Debug, Serialize)]
+pub struct RiskyMethod {
+    #[serde(flatten)]
+    pub metrics: MethodMetrics,
+    /// (2 × P0 + P1) × 复杂度
+    pub risk: usize,
+    /// 方法内问题的规则 ID (按行排序)
+    pub issues: Vec<String>,
+}
+
+/// 计算语法树中所有方法的度量；嵌套类 / 匿名类的方法单独计算，不计入外层方法
+pub fn collect(root: Node, code: &str, file: &str) -> Vec<MethodMetrics> {
+    let mut cursor = root.walk();
+    let package = root.named_children(&mut cursor)
+        .find(|n| n.kind() == "package_declaration")
+        .and_then(|decl| {
+            let mut cursor = decl.walk();
+            let name = decl.named_children(&mut cursor).find(|n| matches!(n.kind(), "identifier" | "scoped_identifier"));
+            text(name, code)
+        })
+        .unwrap_or_default();
+    let mut methods = Vec::new();
+    let mut stack = vec![(root, String::new())];
+    while let Some((node, class)) = stack.pop() {
+        let class = match node.kind() {
+            "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration" => {
+                text(node.child_by_field_name("name"), code).unwrap_or(class)
+            }
+            _ => class,
+        };
+        if matches!(node.kind(), "method_declaration" | "constructor_declaration") {
+            if let Some(body) = node.child_by_field_name("body") {
+                methods.push(measure_method(node, body, code, (file, &package, &class)));
+            }
+        }
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev().map(|c| (c, class.clone())));
+    }
+    methods
+}
+
+fn text(node: Option<Node>, code: &str) -> Option<String> {
+    node.and_then(|n| n.utf8_text(code.as_bytes()).ok()).map(str::to_string)
+}
+
+/// `location`: (文件, 包, 类)
+fn measure_method(decl: Node, body: Node, code: &str, location: (&str, &str, &str)) -> MethodMetrics {
+    let (file, package, class) = location;
+    let mut m = MethodMetrics {
+        file: file.to_string(),
+        package: package.to_string(),
+        class: class.to_string(),
+        method: text(decl.child_by_field_name("name"), code).unwrap_or_default(),
+        line: decl.start_position().row + 1,
+        end_line: decl.end_position().row + 1,
+        cyclomatic: 1,
+        loop_depth: 0,
+        allocations: 0,
+        loop_allocations: 0,
+        statements: 0,
+        complexity: 0,
+    };
+    measure(body, 0, code, &mut m);
+    m.complexity = m.cyclomatic + 3 * m.loop_depth + m.allocations + m.statements / 10;
+    m
+}
+
+fn measure(node: Node, depth: usize, code: &str, m: &mut MethodMetrics) {
+    let kind = node.kind();
+    let is_loop = matches!(kind, "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement");
+    let depth = depth + usize::from(is_loop);
+    m.loop_depth = m.loop_depth.max(depth);
+
+    let decision = match kind {
+        "if_statement" | "catch_clause" | "ternary_expression" => true,
+        "switch_label" => node.utf8_text(code.as_bytes()).is_ok_and(|t| t.starts_with("case")),
+        "binary_expression" => node.child_by_field_name("operator").is_some_and(|op| matches!(op.kind(), "&&" | "||")),
+        _ => is_loop,
+    };
+    m.cyclomatic += usize::from(decision);
+    let allocation = matches!(kind, "object_creation_expression" | "array_creation_expression");
+    m.allocations += usize::from(allocation);
+    m.loop_allocations += usize::from(allocation && depth > 0);
+    m.statements += usize::from(kind.ends_with("_statement") || kind == "local_variable_declaration");
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        // 匿名类 / 局部类的方法由 collect 单独计算
+        if child.kind() != "class_body" {
+            measure(child, depth, code, m);
+        }
+    }
+}
+
+/// 按 "问题权重 × 复杂度" 对含问题的方法排序 (问题按文件 + 行落到最内层方法)
+pub fn rank_flagged(metrics: &[MethodMetrics], issues: &[AstIssue]) -> Vec<RiskyMethod> {
+    let mut flagged: Vec<(usize, usize, Vec<String>)> = vec![(0, 0, Vec::new()); metrics.len()];
+    for issue in issues {
+        let innermost = metrics.iter().enumerate()
+            .filter(|(_, m)| m.file == issue.file && (m.line..=m.end_line).contains(&issue.line))
+            .min_by_key(|(_, m)| m.end_line - m.line);
+        if let Some((idx, _)) = innermost {
+            let (p0, p1, ids) = &mut flagged[idx];
+            match issue.severity {
+                Severity::P0 => *p0 += 1,
+                Severity::P1 => *p1 += 1,
+                Severity::P2 | Severity::Info => {}
+            }
+            ids.push(issue.issue_type.clone());
+        }
+    }
+
+    let mut ranked: Vec<RiskyMethod> = metrics.iter().zip(flagged)
+        .filter(|(_, (_, _, ids))| !ids.is_empty())
+        .map(|(m, (p0, p1, issues))| RiskyMethod { risk: (2 * p0 + p1) * m.complexity, metrics: m.clone(), issues })
+        .collect();
+    ranked.sort_by(|a, b| b.risk.cmp(&a.risk)
+        .then_with(|| (&a.metrics.file, a.metrics.line).cmp(&(&b.metrics.file, b.metrics.line))));
+    ranked.truncate(MAX_RISKIEST);
+    ranked
+}
+
+/// 扫描 JSON 的 `metrics` 段
+pub fn to_json(metrics: &[MethodMetrics], issues: &[AstIssue]) -> Value {
+    json!({
+        "methods": metrics,
+        "riskiest": rank_flagged(metrics, issues),
+    })
+}
+
+/// `java-perf metrics --file X.java`: 单文件度量，按复杂度降序
+pub fn analyze_file(file: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let code = std::fs::read_to_string(file)?;
+    let path = Path::new(file);
+    let (issues, mut methods) = JavaTreeSitterAnalyzer::new()?.analyze_with_metrics(&code, path, None, None)?;
+    let mut issues: Vec<AstIssue> = issues.into_iter().map(ast_engine::convert_issue).collect();
+    issues.sort_by_key(|i| (i.line, i.column));
+    methods.sort_by(|a, b| b.complexity.cmp(&a.complexity).then(a.line.cmp(&b.line)));
+
+    let riskiest = rank_flagged(&methods, &issues);
+    let report = render_report(file, &methods, &riskiest);
+    Ok(json!({
+        "file": file,
+        "methods": methods,
+        "riskiest": riskiest,
+        "report": report,
+    }))
+}
+
+fn render_report(file: &str, methods: &[MethodMetrics], riskiest: &[RiskyMethod]) -> String {
+    let mut out = format!("## 📐 方法度量: {file}\n\n");
+    if methods.is_empty() {
+        out.push_str("(无方法)\n");
+        return out;
+    }
+    out.push_str("| 方法 | 行 | 圈复杂度 | 循环深度 | 分配 | 语句 | 复杂度分 |\n|---|---|---|---|---|---|---|\n");
+    for m in methods {
+        out.push_str(&format!(
+            "| `{}.{}` | {}-{} | {} | {} | {} | {} | {} |\n",
+            m.class, m.method, m.line, m.end_line, m.cyclomatic, m.loop_depth, m.allocations, m.statements, m.complexity
+        ));
+    }
+    if !riskiest.is_empty() {
+        out.push_str("\n**最危险的被标记方法** (问题权重 × 复杂度):\n");
+        for r in riskiest {
+            out.push_str(&format!("- `{}.{}` (风险 {}): {}\n", r.metrics.class, r.metrics.method, r.risk, r.issues.join(", ")));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CODE: &str = r#"package com.acme.order;
+public class OrderService {
+    public OrderService() {
+        this.cache = new HashMap<>();
+    }
+
+    public int total(List<Order> orders, boolean vip) {
+        int sum = 0;
+        for (Order o : orders) {
+            for (Item i : o.items()) {
+                if (i.price() > 0 && !i.free()) {
+                    sum += vip ? i.price() / 2 : i.price();
+                }
+                Object probe = new Object();
+            }
+        }
+        switch (sum) {
+            case 0: return 0;
+            case 1: return 1;
+            default: break;
+        }
+        Runnable r = new Runnable() {
+            public void run() {
+                while (true) { tick(); }
+            }
+        };
+        return sum;
+    }
+}
+"#;
+
+    fn parse() -> Vec<MethodMetrics> {
+        let (_, metrics) = JavaTreeSitterAnalyzer::new().unwrap()
+            .analyze_with_metrics(CODE, Path::new("OrderService.java"), None, None)
+            .unwrap();
+        metrics
+    }
+
+    #[test]
+    fn test_method_metrics() {
+        let metrics = parse();
+        let names: Vec<&str> = metrics.iter().map(|m| m.method.as_str()).collect();
+        assert_eq!(names, vec!["OrderService", "total", "run"]);
+
+        let total = &metrics[1];
+        assert_eq!((total.line, total.end_line), (7, 28));
+        // 1 + for×2 + if + && + 三元 + case×2
+        assert_eq!(total.cyclomatic, 8);
+        assert_eq!(total.loop_depth, 2);
+        // new Object() + new Runnable() (匿名类内的 while 不计入)
+        assert_eq!((total.allocations, total.loop_allocations), (2, 1));
+        assert_eq!(total.complexity, 8 + 6 + 2 + total.statements / 10);
+
+        assert_eq!(total.package, "com.acme.order");
+
+        let run = &metrics[2];
+        assert_eq!((run.class.as_str(), run.cyclomatic, run.loop_depth), ("OrderService", 2, 1));
+    }
+
+    #[test]
+    fn test_rank_flagged_methods() {
+        let metrics = parse();
+        let issue = |id: &str, severity, file: &str, line| AstIssue {
+            severity,
+            issue_type: id.to_string(),
+            file: file.to_string(),
+            line,
+            column: 0,
+            end_line: line,
+            end_column: 0,
+            description: String::new(),
+            owner: None,
+            effort: Default::default(),
+        };
+        let issues = vec![
+            issue("OBJECT_IN_LOOP", Severity::P1, "OrderService.java", 14),
+            issue("INFINITE_LOOP", Severity::P0, "OrderService.java", 24),
+            issue("OBJECT_IN_LOOP", Severity::P0, "Other.java", 14),
+        ];
+
+        let ranked = rank_flagged(&metrics, &issues);
+        assert_eq!(ranked.len(), 2);
+        // 匿名类方法按最内层方法归属
+        let total = ranked.iter().find(|r| r.metrics.method == "total").unwrap();
+        let run = ranked.iter().find(|r| r.metrics.method == "run").unwrap();
+        assert_eq!(total.issues, vec!["OBJECT_IN_LOOP"]);
+        assert_eq!(total.risk, total.metrics.complexity);
+        assert_eq!(run.risk, 2 * run.metrics.complexity);
+        assert!(ranked[0].risk >= ranked[1].risk);
+    }
+}
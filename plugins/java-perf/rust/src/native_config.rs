@@ -0,0 +1,518 @@
+//! GraalVM native-image 配置生成 (Native Config) - v9.6
+//!
+//! `native-config --path .` 汇总源码中 `GRAALVM_*` 规则对应的反射调用点，生成
+//! `reflect-config.json` / `proxy-config.json` 骨架，省去手写配置：
+//! - `Class.forName("com.acme.Plugin")` → `{"name": "com.acme.Plugin", "allDeclaredConstructors": true}`
+//! - `Foo.class.getMethod("run", String.class)` 得到的 `Method` 上的 `invoke` →
+//!   `{"name": "com.acme.Foo", "methods": [{"name": "run", "parameterTypes": ["java.lang.String"]}]}`
+//! - `Proxy.newProxyInstance(loader, new Class<?>[]{Api.class}, h)` → `{"interfaces": ["com.acme.Api"]}`
+//!
+//! 类名/方法名/接口在运行时才确定的调用点生成 `TODO:` 条目 (附源码位置)，人工补全后才能交给 native-image。
+//! 测试源码不进入镜像，不参与生成。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use tree_sitter::Node;
+use walkdir::WalkDir;
+
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+use crate::symbol_table::ImportIndex;
+
+/// 动态名称占位前缀
+const TODO: &str = "TODO";
+
+/// 原生类型 (`int.class`) 不需要解析包名
+const PRIMITIVES: &[&str] = &["boolean", "byte", "char", "short", "int", "long", "float", "double", "void"];
+
+/// 一个反射调用点
+#[derive(Debug, Clone, Serialize)]
+pub struct ReflectionSite {
+    /// 对应的扫描规则
+    pub rule: &'static str,
+    pub file: String,
+    pub line: usize,
+    /// 调用点源码 (首行)
+    pub code: String,
+    /// 是否静态解析出了目标 (否则生成 TODO 条目)
+    pub resolved: bool,
+}
+
+/// reflect-config.json 中的一个类
+#[derive(Debug, Default)]
+struct ReflectEntry {
+    constructors: bool,
+    /// (方法名, 参数类型)
+    methods: BTreeSet<(String, Vec<String>)>,
+}
+
+/// 汇总结果
+#[derive(Debug, Default)]
+pub struct NativeConfig {
+    classes: BTreeMap<String, ReflectEntry>,
+    proxies: BTreeSet<Vec<String>>,
+    pub sites: Vec<ReflectionSite>,
+}
+
+/// 单个文件的解析上下文
+struct FileContext<'a> {
+    code: &'a str,
+    file: String,
+    imports: &'a ImportIndex,
+    known: &'a HashMap<String, String>,
+}
+
+impl FileContext<'_> {
+    fn text(&self, node: Node) -> &str {
+        node.utf8_text(self.code.as_bytes()).unwrap_or("")
+    }
+
+    /// 动态目标的占位名: `TODO: <调用> @ <文件:行>`
+    fn todo(&self, node: Node) -> String {
+        format!("{TODO}: {} @ {}:{}", self.text(node).lines().next().unwrap_or("").trim(), self.file, node.start_position().row + 1)
+    }
+
+    /// 简单类名 → FQN (无法解析时假定与当前文件同包)
+    fn resolve_type(&self, type_name: &str) -> String {
+        let type_name = type_name.split('<').next().unwrap_or(type_name).trim();
+        let (base, dims) = match type_name.find('[') {
+            Some(i) => (&type_name[..i], &type_name[i..]),
+            None => (type_name, ""),
+        };
+        let fqn = if base.contains('.') || PRIMITIVES.contains(&base) {
+            base.to_string()
+        } else {
+            self.imports.resolve(base, self.known).unwrap_or_else(|| match &self.imports.package {
+                Some(package) => format!("{package}.{base}"),
+                None => base.to_string(),
+            })
+        };
+        format!("{fqn}{dims}")
+    }
+
+    /// `Foo.class` → FQN
+    fn class_literal(&self, node: Node) -> Option<String> {
+        (node.kind() == "class_literal").then(|| node.named_child(0).map(|t| self.resolve_type(self.text(t))))?
+    }
+
+    /// 字符串字面量内容
+    fn string_literal(&self, node: Node) -> Option<String> {
+        (node.kind() == "string_literal").then(|| self.text(node).trim_matches('"').to_string())
+    }
+}
+
+/// 调用的 (接收者, 方法名, 参数列表)
+fn invocation(node: Node) -> Option<(Option<Node>, Node, Vec<Node>)> {
+    if node.kind() != "method_invocation" {
+        return None;
+    }
+    let args = node.child_by_field_name("arguments")
+        .map(|a| {
+            let mut cursor = a.walk();
+            a.named_children(&mut cursor).collect()
+        })
+        .unwrap_or_default();
+    Some((node.child_by_field_name("object"), node.child_by_field_name("name")?, args))
+}
+
+/// 变量在所在方法 (其次所在类) 中的初始化/赋值表达式与声明类型
+fn find_binding<'t>(from: Node<'t>, var: &str, code: &str) -> Option<(Option<String>, Option<Node<'t>>)> {
+    let mut scopes = Vec::new();
+    let mut current = from;
+    while let Some(parent) = current.parent() {
+        if matches!(parent.kind(), "method_declaration" | "constructor_declaration" | "class_body") {
+            scopes.push(parent);
+        }
+        current = parent;
+    }
+
+    for scope in scopes {
+        let mut stack = vec![scope];
+        while let Some(node) = stack.pop() {
+            let name = |field: &str| node.child_by_field_name(field).and_then(|n| n.utf8_text(code.as_bytes()).ok());
+            match node.kind() {
+                "variable_declarator" if name("name") == Some(var) => {
+                    let declared = node.parent().and_then(|d| d.child_by_field_name("type"))
+                        .and_then(|t| t.utf8_text(code.as_bytes()).ok())
+                        .map(str::to_string);
+                    return Some((declared, node.child_by_field_name("value")));
+                }
+                "assignment_expression" if name("left") == Some(var) => {
+                    return Some((None, node.child_by_field_name("right")));
+                }
+                "formal_parameter" if name("name") == Some(var) => {
+                    return Some((name("type").map(str::to_string), None));
+                }
+                _ => {}
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+    }
+    None
+}
+
+impl NativeConfig {
+    /// 分析一个源文件
+    pub fn add_file(&mut self, analyzer: &JavaTreeSitterAnalyzer, code: &str, file: &str, known: &HashMap<String, String>) {
+        let path = Path::new(file);
+        let (Ok(tree), Ok((_, _, imports))) = (analyzer.parse(code), analyzer.extract_symbols(code, path)) else {
+            return;
+        };
+        let ctx = FileContext { code, file: file.to_string(), imports: &imports, known };
+
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if let Some((object, name, args)) = invocation(node) {
+                let receiver = object.map(|o| ctx.text(o)).unwrap_or("");
+                match (receiver, ctx.text(name)) {
+                    ("Class", "forName") => self.class_for_name(node, &args, &ctx),
+                    ("Proxy", "newProxyInstance") => self.proxy(node, &args, &ctx),
+                    (_, "invoke") => {
+                        if let Some(object) = object {
+                            self.method_invoke(node, object, &ctx);
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.named_children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+    }
+
+    fn record(&mut self, rule: &'static str, node: Node, ctx: &FileContext, resolved: bool) {
+        self.sites.push(ReflectionSite {
+            rule,
+            file: ctx.file.clone(),
+            line: node.start_position().row + 1,
+            code: ctx.text(node).lines().next().unwrap_or("").trim().to_string(),
+            resolved,
+        });
+    }
+
+    fn class_for_name(&mut self, call: Node, args: &[Node], ctx: &FileContext) {
+        let target = args.first().and_then(|a| ctx.string_literal(*a));
+        let resolved = target.is_some();
+        let name = target.unwrap_or_else(|| ctx.todo(call));
+        self.classes.entry(name).or_default().constructors = true;
+        self.record("GRAALVM_CLASS_FORNAME", call, ctx, resolved);
+    }
+
+    fn proxy(&mut self, call: Node, args: &[Node], ctx: &FileContext) {
+        // new Class<?>[]{A.class, B.class}
+        let interfaces = args.get(1)
+            .filter(|a| a.kind() == "array_creation_expression")
+            .and_then(|a| a.child_by_field_name("value"))
+            .and_then(|init| {
+                let mut cursor = init.walk();
+                let elements: Vec<Node> = init.named_children(&mut cursor).collect();
+                elements.iter().map(|e| ctx.class_literal(*e)).collect::<Option<Vec<_>>>()
+            })
+            .filter(|list| !list.is_empty());
+        let resolved = interfaces.is_some();
+        self.proxies.insert(interfaces.unwrap_or_else(|| vec![ctx.todo(call)]));
+        self.record("GRAALVM_PROXY", call, ctx, resolved);
+    }
+
+    /// `method.invoke(..)` / `X.class.getMethod("m").invoke(..)`
+    fn method_invoke(&mut self, call: Node, object: Node, ctx: &FileContext) {
+        let (declared_method, lookup) = match object.kind() {
+            "method_invocation" => (false, Some(object)),
+            "identifier" => match find_binding(object, ctx.text(object), ctx.code) {
+                Some((declared, value)) => {
+                    (declared.is_some_and(|t| t == "Method" || t == "java.lang.reflect.Method"), value)
+                }
+                None => return,
+            },
+            _ => return,
+        };
+        // 只处理 java.lang.reflect.Method (排除 MethodHandle / InvocationHandler 等同名方法)
+        let from_lookup = lookup.and_then(invocation)
+            .is_some_and(|(_, name, _)| matches!(ctx.text(name), "getMethod" | "getDeclaredMethod"));
+        if !declared_method && !from_lookup {
+            return;
+        }
+
+        match lookup.and_then(|l| self.method_target(l, ctx)) {
+            Some((class, method, params)) => {
+                self.classes.entry(class).or_default().methods.insert((method, params));
+                self.record("GRAALVM_METHOD_INVOKE", call, ctx, true);
+            }
+            None => {
+                self.classes.entry(ctx.todo(call)).or_default();
+                self.record("GRAALVM_METHOD_INVOKE", call, ctx, false);
+            }
+        }
+    }
+
+    /// `getMethod("m", String.class)` / `getDeclaredMethod(..)` → (类, 方法名, 参数类型)
+    fn method_target(&self, lookup: Node, ctx: &FileContext) -> Option<(String, String, Vec<String>)> {
+        let (owner, name, args) = invocation(lookup)?;
+        if !matches!(ctx.text(name), "getMethod" | "getDeclaredMethod") {
+            return None;
+        }
+        let class = self.class_expression(owner?, ctx)?;
+        let (method, params) = args.split_first()?;
+        let method = ctx.string_literal(*method)?;
+        let params = params.iter().map(|p| ctx.class_literal(*p)).collect::<Option<Vec<_>>>()?;
+        Some((class, method, params))
+    }
+
+    /// `Foo.class` / `Class.forName("x.Y")` / 持有二者之一的局部变量 → FQN
+    fn class_expression(&self, node: Node, ctx: &FileContext) -> Option<String> {
+        match node.kind() {
+            "class_literal" => ctx.class_literal(node),
+            "method_invocation" => {
+                let (object, name, args) = invocation(node)?;
+                (object.map(|o| ctx.text(o)) == Some("Class") && ctx.text(name) == "forName")
+                    .then(|| args.first().and_then(|a| ctx.string_literal(*a)))?
+            }
+            "identifier" => {
+                let (_, value) = find_binding(node, ctx.text(node), ctx.code)?;
+                value.filter(|v| v.kind() != "identifier").and_then(|v| self.class_expression(v, ctx))
+            }
+            _ => None,
+        }
+    }
+
+    /// TODO 条目数
+    pub fn todo_count(&self) -> usize {
+        self.sites.iter().filter(|s| !s.resolved).count()
+    }
+
+    /// reflect-config.json 条目 (按类名排序)
+    pub fn reflect_config(&self) -> Vec<Value> {
+        self.classes.iter().map(|(name, entry)| {
+            let mut value = json!({ "name": name });
+            if entry.constructors {
+                value["allDeclaredConstructors"] = json!(true);
+            }
+            if !entry.methods.is_empty() {
+                value["methods"] = entry.methods.iter()
+                    .map(|(method, params)| json!({ "name": method, "parameterTypes": params }))
+                    .collect();
+            }
+            value
+        }).collect()
+    }
+
+    /// proxy-config.json 条目
+    pub fn proxy_config(&self) -> Vec<Value> {
+        self.proxies.iter().map(|interfaces| json!({ "interfaces": interfaces })).collect()
+    }
+}
+
+/// 项目中的生产源码 (测试源码不进入镜像)
+fn source_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .follow_links(true)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "java"))
+        .map(|e| e.into_path())
+        .filter(|p| !crate::rules::test_code::is_test_source(p))
+        .collect();
+    files.sort();
+    files
+}
+
+/// 合并到已有配置: 已有条目原样保留，只追加新的类 / 代理接口组合
+fn merge_into(path: &Path, generated: &[Value], key: &str) -> Result<usize, Box<dyn std::error::Error>> {
+    let mut entries: Vec<Value> = match std::fs::read_to_string(path) {
+        Ok(content) => serde_json::from_str(&content)
+            .map_err(|e| format!("{}: 不是 JSON 数组 ({e})，未写入", path.display()))?,
+        Err(_) => Vec::new(),
+    };
+    let existing: Vec<Value> = entries.iter().map(|e| e[key].clone()).collect();
+    let added: Vec<Value> = generated.iter().filter(|g| !existing.contains(&g[key])).cloned().collect();
+    let count = added.len();
+    entries.extend(added);
+    std::fs::write(path, serde_json::to_string_pretty(&entries)? + "\n")?;
+    Ok(count)
+}
+
+/// 生成 native-image 配置；`out` 为写出目录 (如 `src/main/resources/META-INF/native-image`)
+pub fn generate(root: &str, out: Option<&Path>) -> Result<Value, Box<dyn std::error::Error>> {
+    let root_path = Path::new(root);
+    if !root_path.is_dir() {
+        return Err(format!("Project directory not found: {root}").into());
+    }
+    let analyzer = JavaTreeSitterAnalyzer::new()?;
+    let files = source_files(root_path);
+    let sources: Vec<(String, String)> = files.iter()
+        .filter_map(|f| {
+            let code = std::fs::read_to_string(f).ok()?;
+            let rel = f.strip_prefix(root_path).unwrap_or(f).display().to_string();
+            Some((rel, code))
+        })
+        .collect();
+
+    // 项目内所有类的 FQN，用于通配符 import 的解析
+    let known: HashMap<String, String> = sources.iter()
+        .filter_map(|(rel, code)| analyzer.extract_symbols(code, Path::new(rel)).ok()?.0)
+        .map(|info| (info.fqn.clone(), info.fqn))
+        .collect();
+
+    let mut config = NativeConfig::default();
+    for (rel, code) in &sources {
+        config.add_file(&analyzer, code, rel, &known);
+    }
+
+    let (reflect, proxy) = (config.reflect_config(), config.proxy_config());
+    let mut written = Vec::new();
+    if let Some(dir) = out {
+        std::fs::create_dir_all(dir)?;
+        for (name, entries, key) in [("reflect-config.json", &reflect, "name"), ("proxy-config.json", &proxy, "interfaces")] {
+            if entries.is_empty() {
+                continue;
+            }
+            let path = dir.join(name);
+            let added = merge_into(&path, entries, key)?;
+            written.push(json!({ "file": path.display().to_string(), "added": added }));
+        }
+    }
+
+    let report = render_report(&config, &reflect, &proxy, &written, files.len());
+    Ok(json!({
+        "path": root,
+        "files": files.len(),
+        "sites": config.sites,
+        "todo": config.todo_count(),
+        "reflect_config": reflect,
+        "proxy_config": proxy,
+        "written": written,
+        "report": report,
+    }))
+}
+
+fn render_report(config: &NativeConfig, reflect: &[Value], proxy: &[Value], written: &[Value], files: usize) -> String {
+    let mut out = format!(
+        "## 🧊 GraalVM native-image 配置\n\n**源文件**: {files} | **反射调用点**: {} | **待补全 (TODO)**: {}\n\n",
+        config.sites.len(), config.todo_count()
+    );
+    if config.sites.is_empty() {
+        out.push_str("✅ 未发现 Class.forName / Method.invoke / Proxy.newProxyInstance 调用\n");
+        return out;
+    }
+
+    let todo: Vec<&ReflectionSite> = config.sites.iter().filter(|s| !s.resolved).collect();
+    if !todo.is_empty() {
+        out.push_str("### ⚠️ 动态目标 (需人工补全 TODO 条目)\n\n");
+        for site in todo {
+            out.push_str(&format!("- `{}:{}` [{}] `{}`\n", site.file, site.line, site.rule, site.code));
+        }
+        out.push('\n');
+    }
+
+    if written.is_empty() {
+        for (name, entries) in [("reflect-config.json", reflect), ("proxy-config.json", proxy)] {
+            if !entries.is_empty() {
+                out.push_str(&format!(
+                    "### {name}\n\n```json\n{}\n```\n\n",
+                    serde_json::to_string_pretty(entries).unwrap_or_default()
+                ));
+            }
+        }
+        out.push_str("> 使用 `--out src/main/resources/META-INF/native-image` 写入 (与已有配置合并)\n");
+    } else {
+        for w in written {
+            out.push_str(&format!("- 已写入 `{}` (新增 {} 条)\n", w["file"].as_str().unwrap_or(""), w["added"]));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collect(code: &str) -> NativeConfig {
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let mut config = NativeConfig::default();
+        config.add_file(&analyzer, code, "src/main/java/com/acme/Loader.java", &HashMap::new());
+        config
+    }
+
+    #[test]
+    fn test_reflection_sites() {
+        let config = collect(r#"
+            package com.acme;
+
+            import java.lang.reflect.Method;
+            import java.lang.reflect.Proxy;
+            import com.acme.api.OrderApi;
+
+            public class Loader {
+                public Object load(String className, Method callback, Object target) throws Exception {
+                    Class<?> plugin = Class.forName("com.acme.plugin.CsvExporter");
+                    Object dynamic = Class.forName(className).getDeclaredConstructor().newInstance();
+
+                    Method run = Task.class.getMethod("run", String.class, int.class);
+                    run.invoke(target, "a", 1);
+                    plugin.getDeclaredMethod("export").invoke(target);
+                    callback.invoke(target);
+                    handle.invoke(target);
+
+                    OrderApi api = (OrderApi) Proxy.newProxyInstance(loader, new Class<?>[]{OrderApi.class, Auditable.class}, handler);
+                    return Proxy.newProxyInstance(loader, interfaces, handler);
+                }
+            }
+        "#);
+
+        // handle 未声明 → 不是 Method，忽略
+        assert_eq!(config.sites.len(), 7);
+        assert_eq!(config.todo_count(), 3);
+
+        let reflect = config.reflect_config();
+        let names: Vec<&str> = reflect.iter().map(|e| e["name"].as_str().unwrap()).collect();
+        assert_eq!(names[..2], ["TODO: Class.forName(className) @ src/main/java/com/acme/Loader.java:11", "TODO: callback.invoke(target) @ src/main/java/com/acme/Loader.java:16"]);
+        assert_eq!(reflect[2], json!({
+            "name": "com.acme.Task",
+            "methods": [{ "name": "run", "parameterTypes": ["java.lang.String", "int"] }],
+        }));
+        assert_eq!(reflect[3], json!({
+            "name": "com.acme.plugin.CsvExporter",
+            "allDeclaredConstructors": true,
+            "methods": [{ "name": "export", "parameterTypes": [] }],
+        }));
+
+        let proxy = config.proxy_config();
+        assert_eq!(proxy[0], json!({ "interfaces": ["TODO: Proxy.newProxyInstance(loader, interfaces, handler) @ src/main/java/com/acme/Loader.java:20"] }));
+        assert_eq!(proxy[1], json!({ "interfaces": ["com.acme.api.OrderApi", "com.acme.Auditable"] }));
+    }
+
+    #[test]
+    fn test_generate_merges_existing_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("Registry.java"), r#"
+            package com.acme;
+            public class Registry {
+                Object create() throws Exception { return Class.forName("com.acme.Codec"); }
+            }
+        "#).unwrap();
+        let test_src = dir.path().join("src/test/java/com/acme");
+        std::fs::create_dir_all(&test_src).unwrap();
+        std::fs::write(test_src.join("RegistryTest.java"), r#"
+            class RegistryTest { void t() throws Exception { Class.forName("com.acme.Fixture"); } }
+        "#).unwrap();
+
+        let out = dir.path().join("native-image");
+        std::fs::create_dir_all(&out).unwrap();
+        std::fs::write(out.join("reflect-config.json"), r#"[{"name": "com.acme.Codec", "allPublicMethods": true}]"#).unwrap();
+
+        let result = generate(dir.path().to_str().unwrap(), Some(&out)).unwrap();
+        assert_eq!(result["files"], 1);
+        assert_eq!(result["written"][0]["added"], 0);
+        let merged: Value = serde_json::from_str(&std::fs::read_to_string(out.join("reflect-config.json")).unwrap()).unwrap();
+        assert_eq!(merged, json!([{ "name": "com.acme.Codec", "allPublicMethods": true }]));
+        assert!(!out.join("proxy-config.json").exists());
+
+        let preview = generate(dir.path().to_str().unwrap(), None).unwrap();
+        assert!(preview["report"].as_str().unwrap().contains("\"name\": \"com.acme.Codec\""));
+    }
+}
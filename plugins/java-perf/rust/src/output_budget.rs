@@ -0,0 +1,359 @@
+//! 输出预算 (v9.6)
+//!
+//! MCP 工具的 `maxOutputTokens` / `maxBytes` 参数：报告超出预算时按优先级逐步裁剪，
+//! 而不是按固定行数截断，保证上下文窗口小的 Agent 仍能拿到关键结论：
+//! 1. 省略代码片段 (```` ``` ```` 代码块)
+//! 2. P1 章节的明细、以及分类章节中 🟡/🔵/⚪ 标记的明细汇总为按规则计数
+//! 3. 从后往前收缩其余章节 (只保留前几行)，仍超出时只保留章节标题
+//! 4. 第一个章节 (标题与摘要)、P0 章节和 🔴 标记的明细始终保留；仍超出时按行截断
+//!
+//! 裁剪后在末尾追加说明。Token 数按 ASCII 每 4 字节 1 个、其他字符每字符 1 个估算。
+
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+/// 收缩章节时保留的正文行数 (不含 P0 明细)
+const KEEP_LINES: usize = 5;
+
+/// 可汇总的明细级别标记 (P1 / P2 / Info)
+const MINOR_MARKS: [&str; 3] = ["🟡", "🔵", "⚪"];
+
+/// 输出预算 (两项都设置时同时满足)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Budget {
+    pub max_bytes: Option<usize>,
+    pub max_tokens: Option<usize>,
+}
+
+impl Budget {
+    /// 从工具参数中取出 `maxOutputTokens` / `maxBytes` (取出后不再传给工具)；都未设置时返回 `None`
+    pub fn take_from(args: &mut Value) -> Result<Option<Budget>, String> {
+        let Some(map) = args.as_object_mut() else { return Ok(None) };
+        let mut take = |key: &str| -> Result<Option<usize>, String> {
+            match map.remove(key) {
+                None | Some(Value::Null) => Ok(None),
+                Some(v) => v.as_u64()
+                    .filter(|&n| n > 0)
+                    .and_then(|n| usize::try_from(n).ok())
+                    .map(Some)
+                    .ok_or_else(|| format!("{key} must be a positive integer")),
+            }
+        };
+        let budget = Budget { max_tokens: take("maxOutputTokens")?, max_bytes: take("maxBytes")? };
+        Ok((budget != Budget::default()).then_some(budget))
+    }
+
+    pub fn fits(&self, text: &str) -> bool {
+        self.max_bytes.is_none_or(|max| text.len() <= max) && self.max_tokens.is_none_or(|max| estimate_tokens(text) <= max)
+    }
+
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(tokens) = self.max_tokens {
+            parts.push(format!("maxOutputTokens={tokens}"));
+        }
+        if let Some(bytes) = self.max_bytes {
+            parts.push(format!("maxBytes={bytes}"));
+        }
+        parts.join(", ")
+    }
+}
+
+/// 估算 token 数
+pub fn estimate_tokens(text: &str) -> usize {
+    let ascii = text.bytes().filter(u8::is_ascii).count();
+    let other = text.chars().filter(|c| !c.is_ascii()).count();
+    ascii.div_ceil(4) + other
+}
+
+/// Markdown 章节 (以 `#` 开头的行分隔)
+#[derive(Debug)]
+struct Section {
+    heading: Option<String>,
+    lines: Vec<String>,
+    /// 标题/摘要与 P0 章节，不收缩
+    critical: bool,
+    p1: bool,
+}
+
+fn parse(text: &str) -> Vec<Section> {
+    let mut sections: Vec<Section> = Vec::new();
+    for line in text.lines() {
+        if line.starts_with('#') || sections.is_empty() {
+            let heading = line.starts_with('#').then(|| line.to_string());
+            let title = heading.as_deref().unwrap_or("");
+            let critical = sections.is_empty() || title.contains("P0") || title.contains('🔴');
+            let p1 = !critical && (title.contains("P1") || title.contains('🟡'));
+            sections.push(Section { heading, lines: Vec::new(), critical, p1 });
+            if line.starts_with('#') {
+                continue;
+            }
+        }
+        if let Some(section) = sections.last_mut() {
+            section.lines.push(line.to_string());
+        }
+    }
+    sections
+}
+
+fn render(sections: &[Section]) -> String {
+    let mut out = String::new();
+    for section in sections {
+        for line in section.heading.iter().chain(&section.lines) {
+            out.push_str(line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// 把代码块替换为一行说明，返回是否有改动
+fn elide_code(sections: &mut [Section]) -> bool {
+    let mut changed = false;
+    for section in sections {
+        let mut lines = Vec::with_capacity(section.lines.len());
+        let mut fence: Option<usize> = None;
+        for line in section.lines.drain(..) {
+            match fence {
+                None if line.trim_start().starts_with("```") => fence = Some(0),
+                None => lines.push(line),
+                Some(n) if line.trim_start().starts_with("```") => {
+                    lines.push(format!("*(代码片段 {n} 行已省略)*"));
+                    fence = None;
+                    changed = true;
+                }
+                Some(n) => fence = Some(n + 1),
+            }
+        }
+        section.lines = lines;
+    }
+    changed
+}
+
+/// P1 章节及带次要级别标记的列表项按规则计数 (`- **RULE** ...` / ``- `RULE` ...`` / `- 🟡 **RULE** ...`)，返回是否有改动
+fn summarize_p1(sections: &mut [Section]) -> bool {
+    let mut changed = false;
+    for section in sections.iter_mut().filter(|s| !s.critical) {
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        let mut order = Vec::new();
+        let mut other = Vec::new();
+        let mut insert_at = None;
+        for line in &section.lines {
+            match list_item(line) {
+                Some((mark, key)) if (section.p1 && mark.is_empty()) || MINOR_MARKS.contains(&mark) => {
+                    let label = if mark.is_empty() { format!("**{key}**") } else { format!("{mark} **{key}**") };
+                    let count = counts.entry(label.clone()).or_default();
+                    if *count == 0 {
+                        order.push(label);
+                    }
+                    *count += 1;
+                    insert_at.get_or_insert(other.len());
+                }
+                _ => other.push(line.clone()),
+            }
+        }
+        let Some(insert_at) = insert_at else { continue };
+        // 汇总放在第一条明细原来的位置
+        let summary: Vec<String> = order.iter().map(|label| format!("- {label} ×{}", counts[label])).collect();
+        other.splice(insert_at..insert_at, summary);
+        section.lines = other;
+        changed = true;
+    }
+    changed
+}
+
+/// 列表项的 (级别标记, 规则)，无标记时标记为空
+fn list_item(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix("- ")?;
+    let (mark, rest) = ["🔴"].iter().chain(&MINOR_MARKS)
+        .find_map(|m| rest.strip_prefix(m).map(|r| (*m, r.trim_start())))
+        .unwrap_or(("", rest));
+    if let Some(rest) = rest.strip_prefix("**") {
+        return rest.split_once("**").map(|(key, _)| (mark, key));
+    }
+    rest.strip_prefix('`')?.split_once('`').map(|(key, _)| (mark, key))
+}
+
+/// P0 明细 (收缩章节时保留)
+fn is_critical_line(line: &str) -> bool {
+    line.starts_with("- 🔴")
+}
+
+fn shrink(section: &mut Section, keep: usize) -> bool {
+    let removable = section.lines.iter().filter(|l| !l.trim().is_empty() && !is_critical_line(l)).count();
+    if removable <= keep {
+        return false;
+    }
+    let mut kept = 0;
+    let mut lines = Vec::new();
+    for line in section.lines.drain(..) {
+        let blank = line.trim().is_empty();
+        if is_critical_line(&line) || kept < keep || (blank && lines.is_empty()) {
+            kept += usize::from(!blank && !is_critical_line(&line));
+            lines.push(line);
+        }
+    }
+    lines.push(if keep == 0 { format!("*(已省略 {removable} 行)*") } else { format!("*... 其余 {} 行已省略*", removable - keep) });
+    lines.push(String::new());
+    section.lines = lines;
+    true
+}
+
+/// 按预算裁剪报告；未超出时原样返回
+pub fn fit(text: &str, budget: &Budget) -> String {
+    if budget.fits(text) {
+        return text.to_string();
+    }
+    let note = |steps: &[&str]| {
+        format!(
+            "\n> [!NOTE]\n> 输出超出预算 ({})，已{}；调大预算或缩小查询范围查看完整报告\n",
+            budget.describe(), steps.join("、")
+        )
+    };
+    let finish = |sections: &[Section], steps: &[&str]| format!("{}{}", render(sections), note(steps));
+
+    let mut sections = parse(text);
+    let mut steps = Vec::new();
+    if elide_code(&mut sections) {
+        steps.push("省略代码片段");
+        if budget.fits(&finish(&sections, &steps)) {
+            return finish(&sections, &steps);
+        }
+    }
+    if summarize_p1(&mut sections) {
+        steps.push("将 P1 明细汇总为按规则计数");
+        if budget.fits(&finish(&sections, &steps)) {
+            return finish(&sections, &steps);
+        }
+    }
+    for (keep, step) in [(KEEP_LINES, "收缩次要章节"), (0, "省略次要章节正文")] {
+        for i in (0..sections.len()).rev() {
+            if sections[i].critical || !shrink(&mut sections[i], keep) {
+                continue;
+            }
+            if !steps.contains(&step) {
+                steps.push(step);
+            }
+            if budget.fits(&finish(&sections, &steps)) {
+                return finish(&sections, &steps);
+            }
+        }
+    }
+
+    // 关键章节本身超出预算: 按行截断
+    steps.push("按行截断");
+    let rendered = render(&sections);
+    let mut lines: Vec<&str> = rendered.lines().collect();
+    let tail = note(&steps);
+    while !lines.is_empty() && !budget.fits(&format!("{}\n{tail}", lines.join("\n"))) {
+        lines.pop();
+    }
+    if lines.is_empty() {
+        // 预算比说明本身还小
+        let mut out = String::new();
+        for c in tail.trim_start().chars() {
+            out.push(c);
+            if !budget.fits(&out) {
+                out.pop();
+                break;
+            }
+        }
+        return out;
+    }
+    format!("{}\n{tail}", lines.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn scan_report() -> String {
+        let mut report = String::from("## 🛰️ 雷达扫描结果\n\n**扫描**: 120 个文件\n**发现**: 43 个嫌疑点 (P0: 3, P1: 40)\n\n### 🔴 P0 严重嫌疑\n\n");
+        for i in 0..3 {
+            report.push_str(&format!("- **N_PLUS_ONE** - `src/Order{i}.java:{i}0` - 循环内执行数据库查询\n"));
+        }
+        report.push_str("\n```java\nfor (Order o : orders) {\n    repo.findItems(o.getId());\n}\n```\n\n### 🟡 P1 警告 (显示前 40)\n\n");
+        for i in 0..40 {
+            let rule = if i % 4 == 0 { "SYNC_METHOD" } else { "STRING_CONCAT_LOOP" };
+            report.push_str(&format!("- **{rule}** - `src/util/Helper{i}.java:{i}` - 描述文字描述文字描述文字\n"));
+        }
+        report.push_str("\n### 👥 各团队问题数\n\n| 团队 | P0 | P1 | 合计 |\n|------|----|----|------|\n");
+        for i in 0..20 {
+            report.push_str(&format!("| @acme/team-{i} | 0 | 2 | 2 |\n"));
+        }
+        report
+    }
+
+    #[test]
+    fn test_budget_args() {
+        let mut args = json!({ "path": ".", "maxOutputTokens": 800, "maxBytes": null });
+        assert_eq!(Budget::take_from(&mut args).unwrap(), Some(Budget { max_bytes: None, max_tokens: Some(800) }));
+        assert_eq!(args, json!({ "path": "." }));
+        assert_eq!(Budget::take_from(&mut json!({ "path": "." })).unwrap(), None);
+        assert!(Budget::take_from(&mut json!({ "maxBytes": 0 })).is_err());
+        assert!(Budget::take_from(&mut json!({ "maxBytes": "4k" })).is_err());
+        assert_eq!(estimate_tokens("abcdefgh 中文"), 5);
+    }
+
+    #[test]
+    fn test_fit_keeps_p0_and_summarizes_p1() {
+        let report = scan_report();
+        let unlimited = Budget { max_bytes: Some(report.len()), max_tokens: None };
+        assert_eq!(fit(&report, &unlimited), report);
+
+        // 省略代码片段 + P1 汇总即可满足
+        let budget = Budget { max_bytes: Some(2200), max_tokens: None };
+        let fitted = fit(&report, &budget);
+        assert!(budget.fits(&fitted), "{}", fitted.len());
+        assert_eq!(fitted.matches("- **N_PLUS_ONE** - `src/Order").count(), 3);
+        assert!(fitted.contains("*(代码片段 3 行已省略)*"));
+        assert!(fitted.contains("### 🟡 P1 警告 (显示前 40)\n\n- **SYNC_METHOD** ×10\n- **STRING_CONCAT_LOOP** ×30\n"), "{fitted}");
+        assert!(fitted.contains("| @acme/team-19 |"));
+        assert!(fitted.contains("> 输出超出预算 (maxBytes=2200)，已省略代码片段、将 P1 明细汇总为按规则计数；"));
+
+        // 更小的预算: 收缩团队表，P0 与摘要仍完整
+        let budget = Budget { max_bytes: None, max_tokens: Some(300) };
+        let fitted = fit(&report, &budget);
+        assert!(budget.fits(&fitted), "{}", estimate_tokens(&fitted));
+        assert!(fitted.contains("**发现**: 43 个嫌疑点 (P0: 3, P1: 40)"));
+        assert_eq!(fitted.matches("- **N_PLUS_ONE** - `src/Order").count(), 3);
+        assert!(fitted.contains("| 团队 | P0 | P1 | 合计 |\n|------|----|----|------|\n| @acme/team-0 | 0 | 2 | 2 |\n"), "{fitted}");
+        assert!(fitted.contains("*... 其余 17 行已省略*"), "{fitted}");
+        assert!(fitted.contains("收缩次要章节"));
+        assert!(!fitted.contains("| @acme/team-19 |"));
+
+        // 预算小于关键章节: 按行截断，仍不超出
+        let budget = Budget { max_bytes: Some(300), max_tokens: None };
+        let fitted = fit(&report, &budget);
+        assert!(fitted.len() <= 300);
+        assert!(fitted.starts_with("## 🛰️ 雷达扫描结果"));
+        assert!(fitted.contains("按行截断"));
+    }
+
+    #[test]
+    fn test_fit_category_sections() {
+        let mut report = String::from("## 🛰️ 雷达扫描结果\n\n**发现**: 32 个嫌疑点 (P0: 2, P1: 30)\n\n### 🗄️ 数据库 (32)\n\n");
+        report.push_str("- 🔴 **N_PLUS_ONE** - `src/Order.java:10` - 循环内执行数据库查询\n");
+        report.push_str("- 🔴 **SQL_STRING_CONCAT** - `src/Order.java:20` - SQL 拼接\n");
+        for i in 0..30 {
+            report.push_str(&format!("- 🟡 **SELECT_STAR** - `src/dao/Dao{i}.java:{i}` - 描述文字描述文字描述文字\n"));
+        }
+        report.push_str("\n### 🔥 CPU (12)\n\n");
+        for i in 0..12 {
+            report.push_str(&format!("- 🔵 **STREAM_REPEATED** - `src/Util{i}.java:{i}` - 描述\n"));
+        }
+
+        // 分类章节中 P0 明细保留，其余按规则汇总在原位置
+        let budget = Budget { max_bytes: Some(800), max_tokens: None };
+        let fitted = fit(&report, &budget);
+        assert!(budget.fits(&fitted), "{}", fitted.len());
+        assert!(fitted.contains("### 🗄️ 数据库 (32)\n\n- 🔴 **N_PLUS_ONE** - `src/Order.java:10` - 循环内执行数据库查询\n- 🔴 **SQL_STRING_CONCAT** - `src/Order.java:20` - SQL 拼接\n- 🟡 **SELECT_STAR** ×30\n"), "{fitted}");
+        assert!(fitted.contains("### 🔥 CPU (12)\n\n- 🔵 **STREAM_REPEATED** ×12\n"), "{fitted}");
+
+        // 收缩时 P0 明细仍保留
+        let mut section = parse("### 🗄️ 数据库\n\n- 🔴 **A** x\n- 🟡 **B** y\n- 🟡 **C** z\n").remove(0);
+        assert!(shrink(&mut section, 0));
+        assert_eq!(section.lines, ["", "- 🔴 **A** x", "*(已省略 2 行)*", ""]);
+    }
+}
@@ -0,0 +1,416 @@
+//! 问题归属 (Ownership) - v9.6
+//!
+//! `scan --owners` 对每个问题所在行执行 `git blame`，附上最后修改的作者与提交，
+//! 便于把整改工作分派到人；仓库存在 CODEOWNERS 时再按规则匹配负责团队，
+//! 并在报告中输出"每个团队的问题数"汇总。
+//!
+//! - 每个文件只调用一次 `git blame` (多个 `-L` 区间)，文件间并行
+//! - CODEOWNERS 路径相对仓库根目录，问题路径相对扫描根目录，两者通过 `git rev-parse --show-toplevel` 对齐
+//! - 不在 git 仓库中 / git 不可用时跳过，不影响扫描结果
+//!
+//! `scan --owned-paths` / `--team` 在 monorepo 中只保留某个团队负责的问题 (后置过滤，见 [`prune`])：
+//! 路径模式与 CODEOWNERS 语法相同、相对扫描根目录；团队按 CODEOWNERS 匹配 (不需要 `--owners`)。
+//! 被隐藏的问题按负责团队计数，写入扫描信息 `owned_hidden`。
+
+use rayon::prelude::*;
+use regex::Regex;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::ast_engine::{AstIssue, Severity};
+
+/// CODEOWNERS 文件的候选位置 (与 GitHub/GitLab 查找顺序一致)
+const CODEOWNERS_PATHS: &[&str] = &[".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS", ".gitlab/CODEOWNERS"];
+
+/// 没有匹配任何 CODEOWNERS 规则的问题归入该组
+pub const UNOWNED: &str = "(unowned)";
+
+/// 问题所在行的归属信息
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct IssueOwner {
+    /// 最后修改该行的作者
+    pub author: String,
+    pub email: String,
+    /// 提交 SHA (未提交的修改为全 0)
+    pub commit: String,
+    /// 作者时间 (YYYY-MM-DD)
+    pub date: String,
+    /// CODEOWNERS 中匹配的负责人/团队
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub teams: Vec<String>,
+}
+
+/// 单个团队的问题计数
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct TeamCount {
+    pub p0: usize,
+    pub p1: usize,
+    pub total: usize,
+}
+
+/// 为目录扫描的问题附加归属信息
+///
+/// 返回找到的 CODEOWNERS 文件 (相对仓库根目录)；不在 git 仓库中时返回 `None` 且不修改问题。
+pub fn attach(root: &Path, issues: &mut [AstIssue]) -> Option<String> {
+    let toplevel = git_toplevel(root)?;
+    let root_abs = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let prefix = root_abs.strip_prefix(&toplevel).map(Path::to_path_buf).unwrap_or_default();
+    let codeowners = CodeOwners::load(&toplevel);
+
+    let mut lines_by_file: BTreeMap<&str, BTreeSet<usize>> = BTreeMap::new();
+    for issue in issues.iter().filter(|i| i.line > 0) {
+        lines_by_file.entry(issue.file.as_str()).or_default().insert(issue.line);
+    }
+    let blamed: HashMap<(String, usize), IssueOwner> = lines_by_file
+        .into_par_iter()
+        .flat_map_iter(|(file, lines)| {
+            blame_lines(root, file, &lines).into_iter().map(move |(line, owner)| ((file.to_string(), line), owner))
+        })
+        .collect();
+
+    for issue in issues.iter_mut() {
+        let mut owner = blamed.get(&(issue.file.clone(), issue.line)).cloned().unwrap_or_default();
+        if let Some(codeowners) = &codeowners {
+            let repo_path = prefix.join(&issue.file).to_string_lossy().replace('\\', "/");
+            owner.teams = codeowners.owners_of(&repo_path).to_vec();
+        }
+        issue.owner = Some(owner);
+    }
+    codeowners.map(|c| c.file)
+}
+
+/// 按团队汇总问题数 (一个问题有多个负责人时分别计入)
+pub fn team_summary(issues: &[AstIssue]) -> BTreeMap<String, TeamCount> {
+    let mut summary: BTreeMap<String, TeamCount> = BTreeMap::new();
+    for issue in issues {
+        let teams = issue.owner.as_ref().map(|o| o.teams.as_slice()).unwrap_or_default();
+        let teams: Vec<&str> = if teams.is_empty() { vec![UNOWNED] } else { teams.iter().map(String::as_str).collect() };
+        for team in teams {
+            let count = summary.entry(team.to_string()).or_default();
+            match issue.severity {
+                Severity::P0 => count.p0 += 1,
+                Severity::P1 => count.p1 += 1,
+                Severity::P2 | Severity::Info => {}
+            }
+            count.total += 1;
+        }
+    }
+    summary
+}
+
+/// 归属过滤条件: 匹配任一路径模式或任一团队的问题保留
+#[derive(Debug, Clone, Default)]
+pub struct OwnershipFilter {
+    paths: Vec<Regex>,
+    teams: Vec<String>,
+}
+
+impl OwnershipFilter {
+    /// 路径模式 (CODEOWNERS 语法，相对扫描根目录) 与团队 (`@acme/orders`，`@` 可省略)
+    pub fn new(paths: &[String], teams: &[String]) -> Result<Self, String> {
+        let paths = paths
+            .iter()
+            .map(|p| pattern_regex(p.trim()).ok_or_else(|| format!("invalid owned path pattern '{p}'")))
+            .collect::<Result<_, _>>()?;
+        let teams = teams.iter().map(|t| t.trim().trim_start_matches('@').to_ascii_lowercase()).collect();
+        Ok(Self { paths, teams })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.paths.is_empty() && self.teams.is_empty()
+    }
+
+    fn keeps(&self, file: &str, owners: &[String]) -> bool {
+        self.paths.iter().any(|re| re.is_match(file))
+            || owners.iter().any(|o| self.teams.contains(&o.trim_start_matches('@').to_ascii_lowercase()))
+    }
+}
+
+/// 裁剪结果: 被隐藏的问题总数及按负责团队的计数
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Pruned {
+    pub hidden: usize,
+    pub by_team: BTreeMap<String, usize>,
+}
+
+impl Pruned {
+    /// 扫描信息中的描述: `7 (@acme/payments: 4, (unowned): 3)`
+    pub fn describe(&self) -> String {
+        let teams: Vec<String> = self.by_team.iter().map(|(team, count)| format!("{team}: {count}")).collect();
+        if teams.is_empty() {
+            self.hidden.to_string()
+        } else {
+            format!("{} ({})", self.hidden, teams.join(", "))
+        }
+    }
+}
+
+/// 只保留归属过滤条件匹配的问题，返回被隐藏问题的计数
+///
+/// 按团队过滤时需要 CODEOWNERS (仓库根目录，不在 git 仓库中时为扫描根目录)。
+pub fn prune(root: &Path, issues: &mut Vec<AstIssue>, filter: &OwnershipFilter) -> Result<Pruned, String> {
+    let root = if root.is_file() { root.parent().unwrap_or(Path::new(".")) } else { root };
+    let root_abs = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let toplevel = git_toplevel(root).unwrap_or_else(|| root_abs.clone());
+    let prefix = root_abs.strip_prefix(&toplevel).map(Path::to_path_buf).unwrap_or_default();
+    let codeowners = CodeOwners::load(&toplevel);
+    if !filter.teams.is_empty() && codeowners.is_none() {
+        return Err(format!("--team requires a CODEOWNERS file ({})", CODEOWNERS_PATHS.join(", ")));
+    }
+
+    let mut pruned = Pruned::default();
+    issues.retain(|issue| {
+        let owners = match &codeowners {
+            Some(codeowners) => codeowners.owners_of(&prefix.join(&issue.file).to_string_lossy().replace('\\', "/")).to_vec(),
+            None => Vec::new(),
+        };
+        if filter.keeps(&issue.file, &owners) {
+            return true;
+        }
+        pruned.hidden += 1;
+        if owners.is_empty() {
+            *pruned.by_team.entry(UNOWNED.to_string()).or_default() += 1;
+        }
+        for owner in owners {
+            *pruned.by_team.entry(owner).or_default() += 1;
+        }
+        false
+    });
+    Ok(pruned)
+}
+
+fn git_toplevel(root: &Path) -> Option<PathBuf> {
+    let output = Command::new("git").arg("-C").arg(root).args(["rev-parse", "--show-toplevel"]).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let toplevel = PathBuf::from(String::from_utf8_lossy(&output.stdout).trim());
+    Some(std::fs::canonicalize(&toplevel).unwrap_or(toplevel))
+}
+
+/// 对一个文件的多行执行 `git blame --line-porcelain`
+fn blame_lines(root: &Path, file: &str, lines: &BTreeSet<usize>) -> Vec<(usize, IssueOwner)> {
+    let mut cmd = Command::new("git");
+    cmd.arg("-C").arg(root).args(["blame", "--line-porcelain"]);
+    for line in lines {
+        cmd.arg("-L").arg(format!("{line},{line}"));
+    }
+    match cmd.arg("--").arg(file).output() {
+        Ok(output) if output.status.success() => parse_porcelain(&String::from_utf8_lossy(&output.stdout)),
+        _ => Vec::new(),
+    }
+}
+
+/// 解析 `git blame --line-porcelain` 输出: 每行一个 `<sha> <orig> <final> [n]` 头 + 键值行 + `\t<源码>`
+fn parse_porcelain(output: &str) -> Vec<(usize, IssueOwner)> {
+    let mut result = Vec::new();
+    let mut current: Option<(usize, IssueOwner)> = None;
+    for line in output.lines() {
+        if line.starts_with('\t') {
+            result.extend(current.take());
+            continue;
+        }
+        let Some((key, value)) = line.split_once(' ') else { continue };
+        match key {
+            "author" => if let Some((_, o)) = &mut current { o.author = value.to_string() },
+            "author-mail" => if let Some((_, o)) = &mut current {
+                o.email = value.trim_start_matches('<').trim_end_matches('>').to_string();
+            },
+            "author-time" => if let Some((_, o)) = &mut current {
+                o.date = value.parse::<i64>().ok()
+                    .and_then(|t| chrono::DateTime::from_timestamp(t, 0))
+                    .map(|d| d.format("%Y-%m-%d").to_string())
+                    .unwrap_or_default();
+            },
+            sha if sha.len() == 40 && sha.bytes().all(|b| b.is_ascii_hexdigit()) => {
+                let final_line = value.split(' ').nth(1).and_then(|n| n.parse().ok()).unwrap_or(0);
+                current = Some((final_line, IssueOwner { commit: sha.to_string(), ..Default::default() }));
+            }
+            _ => {}
+        }
+    }
+    result
+}
+
+/// 解析后的 CODEOWNERS (后出现的规则优先)
+struct CodeOwners {
+    file: String,
+    rules: Vec<(Regex, Vec<String>)>,
+}
+
+impl CodeOwners {
+    fn load(toplevel: &Path) -> Option<Self> {
+        CODEOWNERS_PATHS.iter().find_map(|rel| {
+            let content = std::fs::read_to_string(toplevel.join(rel)).ok()?;
+            Some(Self::parse(rel, &content))
+        })
+    }
+
+    fn parse(file: &str, content: &str) -> Self {
+        let rules = content
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty() && !l.starts_with('#') && !l.starts_with('['))
+            .filter_map(|l| {
+                let mut parts = l.split_whitespace();
+                let regex = pattern_regex(parts.next()?)?;
+                Some((regex, parts.take_while(|p| !p.starts_with('#')).map(str::to_string).collect()))
+            })
+            .collect();
+        Self { file: file.to_string(), rules }
+    }
+
+    fn owners_of(&self, repo_path: &str) -> &[String] {
+        self.rules.iter().rev()
+            .find(|(regex, _)| regex.is_match(repo_path))
+            .map(|(_, owners)| owners.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// CODEOWNERS (gitignore 风格) 模式转正则
+///
+/// 含 `/` 的模式相对仓库根目录锚定，否则匹配任意层级；匹配到目录时包含其下全部文件，
+/// 但 `dir/*` 只匹配直接子文件。
+fn pattern_regex(pattern: &str) -> Option<Regex> {
+    let anchored = pattern.trim_end_matches('/').contains('/');
+    let body = pattern.trim_start_matches('/').trim_end_matches('/');
+    let mut re = String::from(if anchored { "^" } else { "^(?:.*/)?" });
+    let mut chars = body.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '*' if chars.peek() == Some(&'*') => {
+                chars.next();
+                if chars.peek() == Some(&'/') {
+                    chars.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            '*' => re.push_str("[^/]*"),
+            '?' => re.push_str("[^/]"),
+            c => re.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    let direct_children = body.ends_with("/*") && !body.ends_with("**");
+    re.push_str(if direct_children { "$" } else { "(?:/.*)?$" });
+    Regex::new(&re).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codeowners_matching() {
+        let owners = CodeOwners::parse("CODEOWNERS", "\
+# default
+*                       @acme/platform
+*.yml                   @acme/ops
+/order/src/             @acme/orders
+docs/*                  @acme/docs
+**/payment/**           @acme/payments @alice
+");
+        let of = |p: &str| owners.owners_of(p).join(" ");
+        assert_eq!(of("README.md"), "@acme/platform");
+        assert_eq!(of("deploy/app.yml"), "@acme/ops");
+        assert_eq!(of("order/src/main/java/OrderService.java"), "@acme/orders");
+        assert_eq!(of("legacy/order/src/A.java"), "@acme/platform");
+        assert_eq!(of("docs/guide.md"), "@acme/docs");
+        assert_eq!(of("docs/api/guide.md"), "@acme/platform");
+        assert_eq!(of("order/src/main/java/payment/Refund.java"), "@acme/payments @alice");
+    }
+
+    #[test]
+    fn test_parse_porcelain() {
+        let output = "\
+3f2a9c1e0b7d4a6f8e2c5b1a9d0e7f6c4b3a2918 10 12 1
+author Alice Zhang
+author-mail <alice@acme.io>
+author-time 1767225600
+author-tz +0000
+summary Add order export
+filename src/Order.java
+\tfor (Order o : orders) {
+0000000000000000000000000000000000000000 20 20 1
+author Not Committed Yet
+author-mail <not.committed.yet>
+author-time 1767312000
+filename src/Order.java
+\t    repo.save(o);
+";
+        let parsed = parse_porcelain(output);
+        assert_eq!(parsed.len(), 2);
+        assert_eq!(parsed[0].0, 12);
+        assert_eq!(parsed[0].1.author, "Alice Zhang");
+        assert_eq!(parsed[0].1.email, "alice@acme.io");
+        assert_eq!(parsed[0].1.date, "2026-01-01");
+        assert_eq!(parsed[1].0, 20);
+        assert_eq!(parsed[1].1.commit, "0".repeat(40));
+    }
+
+    #[test]
+    fn test_team_summary_counts_unowned() {
+        let issue = |severity, teams: &[&str]| AstIssue {
+            severity,
+            issue_type: "N_PLUS_ONE".to_string(),
+            file: "A.java".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: String::new(),
+            owner: Some(IssueOwner { teams: teams.iter().map(|t| t.to_string()).collect(), ..Default::default() }),
+            effort: Default::default(),
+        };
+        let summary = team_summary(&[
+            issue(Severity::P0, &["@acme/orders"]),
+            issue(Severity::P1, &["@acme/orders", "@bob"]),
+            issue(Severity::P1, &[]),
+        ]);
+        assert_eq!(summary["@acme/orders"], TeamCount { p0: 1, p1: 1, total: 2 });
+        assert_eq!(summary["@bob"].total, 1);
+        assert_eq!(summary[UNOWNED].p1, 1);
+    }
+
+    #[test]
+    fn test_prune_by_owned_paths_and_team() {
+        let dir = std::env::temp_dir().join(format!("java-perf-prune-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("CODEOWNERS"), "* @acme/platform\n/order/ @acme/orders\n/payment/ @acme/payments\n").unwrap();
+        let issue = |file: &str| AstIssue {
+            severity: Severity::P1,
+            issue_type: "N_PLUS_ONE".to_string(),
+            file: file.to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: String::new(),
+            owner: None,
+            effort: Default::default(),
+        };
+        let all = || vec![issue("order/A.java"), issue("payment/B.java"), issue("payment/C.java"), issue("shared/D.java")];
+
+        let mut issues = all();
+        let filter = OwnershipFilter::new(&[], &["acme/orders".to_string()]).unwrap();
+        let pruned = prune(&dir, &mut issues, &filter).unwrap();
+        assert_eq!(issues.iter().map(|i| i.file.as_str()).collect::<Vec<_>>(), vec!["order/A.java"]);
+        assert_eq!(pruned.describe(), "3 (@acme/payments: 2, @acme/platform: 1)");
+
+        let mut issues = all();
+        let filter = OwnershipFilter::new(&["shared/".to_string()], &["@ACME/Orders".to_string()]).unwrap();
+        assert_eq!(prune(&dir, &mut issues, &filter).unwrap().hidden, 2);
+        assert_eq!(issues.len(), 2);
+
+        std::fs::remove_file(dir.join("CODEOWNERS")).unwrap();
+        let mut issues = all();
+        let filter = OwnershipFilter::new(&["payment/*.java".to_string()], &[]).unwrap();
+        assert_eq!(prune(&dir, &mut issues, &filter).unwrap().describe(), "2 ((unowned): 2)");
+        assert!(prune(&dir, &mut all(), &OwnershipFilter::new(&[], &["@acme/orders".to_string()]).unwrap()).is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
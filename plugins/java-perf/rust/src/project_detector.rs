@@ -343,11 +343,36 @@ pub fn detect_stack_from_maven_deps(deps: &[MavenDependency]) -> DetectedStack {
         if artifact == "lombok" || group == "org.projectlombok" {
             stack.has_lombok = true;
         }
+
+        detect_web_and_persistence(&mut stack, group, artifact);
     }
     
     stack
 }
 
+/// Web 栈与持久层依赖 (v9.6)
+///
+/// spring-boot-starter-web 与 webflux 同时存在时 Spring Boot 以 Servlet (MVC) 启动。
+fn detect_web_and_persistence(stack: &mut DetectedStack, group: &str, artifact: &str) {
+    if artifact == "spring-boot-starter-web" {
+        stack.web_stack = WEB_MVC.to_string();
+        stack.evidence.push(format!("web={WEB_MVC} ← dependency {artifact}"));
+    } else if artifact == "spring-boot-starter-webflux" && stack.web_stack.is_empty() {
+        stack.web_stack = WEB_FLUX.to_string();
+        stack.evidence.push(format!("web={WEB_FLUX} ← dependency {artifact}"));
+    }
+
+    if !stack.has_jpa && (artifact == "spring-boot-starter-data-jpa" || artifact == "hibernate-core"
+        || artifact.ends_with("persistence-api")) {
+        stack.has_jpa = true;
+        stack.evidence.push(format!("jpa ← dependency {artifact}"));
+    }
+    if !stack.has_mybatis && (artifact.starts_with("mybatis") || group == "org.mybatis" || group == "com.baomidou") {
+        stack.has_mybatis = true;
+        stack.evidence.push(format!("mybatis ← dependency {artifact}"));
+    }
+}
+
 // ============================================================================
 // Gradle Build File Parsing (Requirements 3.4)
 // ============================================================================
@@ -489,6 +514,8 @@ pub fn detect_stack_from_gradle_deps(deps: &[GradleDependency]) -> DetectedStack
         if name == "lombok" || group == "org.projectlombok" {
             stack.has_lombok = true;
         }
+
+        detect_web_and_persistence(&mut stack, group, name);
     }
     
     stack
@@ -499,13 +526,36 @@ pub struct DetectedStack {
     pub is_spring_boot: bool,
     pub is_spring_mvc: bool,
     pub is_reactive: bool,      // WebFlux, Vert.x, Reactor
+    #[serde(default)]
+    pub is_native_image: bool,  // GraalVM native-image 构建插件 (v9.6)
     pub is_maven: bool,
     pub is_gradle: bool,
     pub has_lombok: bool,
     pub jdk_version: String,    // "8", "11", "17", "21"
     pub build_tool: String,     // "maven" or "gradle"
+    /// Spring Boot 版本 (parent / BOM / Gradle 插件声明，v9.6)
+    #[serde(default)]
+    pub spring_boot_version: Option<String>,
+    /// Web 栈: "mvc" | "webflux" | "" (未识别)
+    #[serde(default)]
+    pub web_stack: String,
+    #[serde(default)]
+    pub has_jpa: bool,
+    #[serde(default)]
+    pub has_mybatis: bool,
+    /// 每项结论的依据 (`web=webflux ← application.yml spring.main.web-application-type`)
+    #[serde(default)]
+    pub evidence: Vec<String>,
 }
 
+/// Servlet 栈 (Spring MVC)
+pub const WEB_MVC: &str = "mvc";
+/// Reactive 栈 (WebFlux)
+pub const WEB_FLUX: &str = "webflux";
+
+/// import 侦测最多读取的源文件数
+const MAX_SOURCE_FILES: usize = 5000;
+
 
 /// 扫描项目目录，检测技术栈
 pub fn detect_stack(root: &Path) -> DetectedStack {
@@ -521,8 +571,11 @@ pub fn detect_stack(root: &Path) -> DetectedStack {
         stack.build_tool = "gradle".to_string();
         analyze_gradle(root, &mut stack);
     }
+
+    // 2. 源码 import 与 Spring 配置 (v9.6)
+    analyze_sources(root, &mut stack);
     
-    // 2. 默认值兜底
+    // 3. 默认值兜底
     if stack.jdk_version.is_empty() {
         stack.jdk_version = "1.8".to_string(); // 默认假设
     }
@@ -542,6 +595,10 @@ fn analyze_maven(root: &Path, stack: &mut DetectedStack) {
                 stack.is_spring_mvc = detected.is_spring_mvc;
                 stack.is_reactive = detected.is_reactive;
                 stack.has_lombok = detected.has_lombok;
+                stack.web_stack = detected.web_stack;
+                stack.has_jpa = detected.has_jpa;
+                stack.has_mybatis = detected.has_mybatis;
+                stack.evidence.extend(detected.evidence.into_iter().map(|e| format!("{e} (pom.xml)")));
             }
             Err(_) => {
                 // Fall back to simple string matching if XML parsing fails
@@ -558,18 +615,224 @@ fn analyze_maven(root: &Path, stack: &mut DetectedStack) {
                 if content.contains("lombok") {
                     stack.has_lombok = true;
                 }
+                if content.contains("spring-boot-starter-data-jpa") || content.contains("hibernate-core") {
+                    stack.has_jpa = true;
+                }
+                if content.contains("mybatis") {
+                    stack.has_mybatis = true;
+                }
             }
         }
+
+        if let Some(version) = maven_spring_boot_version(&content) {
+            stack.evidence.push(format!("spring-boot={version} ← pom.xml"));
+            stack.spring_boot_version = Some(version);
+            stack.is_spring_boot = true;
+        }
         
-        // Extract JDK version from properties (still use string matching as it's in <properties>)
-        if content.contains("<java.version>17") || content.contains("<target>17") {
-            stack.jdk_version = "17".to_string();
-        } else if content.contains("<java.version>21") || content.contains("<target>21") {
-            stack.jdk_version = "21".to_string();
-        } else if content.contains("<java.version>11") || content.contains("<target>11") {
-            stack.jdk_version = "11".to_string();
+        // GraalVM native-image 通过构建插件启用，不在 <dependencies> 中 (v9.6)
+        if content.contains("native-maven-plugin") || content.contains("spring-native") {
+            stack.is_native_image = true;
+        }
+
+        // JDK 目标版本: <java.version> / <maven.compiler.release> / compiler 插件 <release>/<target>
+        if let Some((tag, version)) = maven_jdk_version(&content) {
+            stack.evidence.push(format!("jdk={version} ← pom.xml <{tag}>"));
+            stack.jdk_version = version;
+        }
+    }
+}
+
+/// pom.xml 中的 Spring Boot 版本: spring-boot-starter-parent → spring-boot-dependencies BOM → `<spring-boot.version>`
+fn maven_spring_boot_version(content: &str) -> Option<String> {
+    let property = |name: &str| {
+        Regex::new(&format!(r"<{}>\s*([^<\s]+)\s*</", regex::escape(name))).ok()?
+            .captures(content)
+            .map(|c| c[1].to_string())
+    };
+    let declared = ["spring-boot-starter-parent", "spring-boot-dependencies"].iter().find_map(|artifact| {
+        Regex::new(&format!(r"(?s)<artifactId>\s*{artifact}\s*</artifactId>\s*<version>\s*([^<\s]+)\s*</version>")).ok()?
+            .captures(content)
+            .map(|c| c[1].to_string())
+    });
+    let version = declared.or_else(|| property("spring-boot.version"))?;
+    // ${spring-boot.version} 等属性引用
+    match version.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        Some(name) => property(name),
+        None => Some(version),
+    }
+}
+
+/// pom.xml 中的 JDK 目标版本 (按优先级): (标签, 版本)
+fn maven_jdk_version(content: &str) -> Option<(&'static str, String)> {
+    ["java.version", "maven.compiler.release", "release", "maven.compiler.target", "target", "maven.compiler.source"]
+        .into_iter()
+        .find_map(|tag| {
+            Regex::new(&format!(r"<{}>\s*([0-9][0-9.]*)\s*</", regex::escape(tag))).ok()?
+                .captures(content)
+                .map(|c| (tag, c[1].to_string()))
+        })
+}
+
+/// Gradle 构建文件中的 Spring Boot 插件版本
+fn gradle_spring_boot_version(content: &str) -> Option<String> {
+    [
+        r#"id\s*\(?\s*["']org\.springframework\.boot["']\s*\)?\s*version\s*\(?\s*["']([^"']+)["']"#,
+        r#"spring-boot-gradle-plugin:([0-9][^"')\s]*)"#,
+        r#"spring-boot-dependencies:([0-9][^"')\s]*)"#,
+    ]
+    .iter()
+    .find_map(|pattern| Regex::new(pattern).ok()?.captures(content).map(|c| c[1].to_string()))
+}
+
+/// Gradle 构建文件中的 JDK 目标版本 (toolchain → JavaVersion → sourceCompatibility)
+fn gradle_jdk_version(content: &str) -> Option<String> {
+    let toolchain = Regex::new(r"(?:JavaLanguageVersion\.of|jvmToolchain)\s*\(\s*(\d+)\s*\)").ok()?;
+    if let Some(c) = toolchain.captures(content) {
+        return Some(c[1].to_string());
+    }
+    // VERSION_1_8 → 1.8，VERSION_17 → 17
+    let java_version = Regex::new(r"JavaVersion\.VERSION_(\d+)(?:_(\d+))?").ok()?;
+    if let Some(c) = java_version.captures(content) {
+        return Some(match c.get(2) {
+            Some(minor) => format!("{}.{}", &c[1], minor.as_str()),
+            None => c[1].to_string(),
+        });
+    }
+    let compatibility = Regex::new(r#"(?:source|target)Compatibility\s*=\s*['"]?(\d+(?:\.\d+)?)"#).ok()?;
+    compatibility.captures(content).map(|c| c[1].to_string())
+}
+
+/// 源码 import 计数 (每个文件每类只计一次)
+#[derive(Debug, Default)]
+struct ImportCounts {
+    reactive_web: usize,
+    servlet: usize,
+    jpa: usize,
+    mybatis: usize,
+    spring_boot: usize,
+}
+
+/// 从主源码 import 与 application*.yml/properties 补充 Web 栈、持久层与 Spring Boot (v9.6)
+///
+/// 构建文件的结论优先；配置中显式的 `spring.main.web-application-type` 覆盖依赖推断。
+fn analyze_sources(root: &Path, stack: &mut DetectedStack) {
+    let mut imports = ImportCounts::default();
+    let mut configured_web: Option<(String, String)> = None;
+    let (mut config_jpa, mut config_mybatis): (Option<String>, Option<String>) = (None, None);
+    let web_type = Regex::new(r"web-application-type\s*[:=]\s*(reactive|servlet)").ok();
+
+    let mut java_files = 0;
+    let walker = walkdir::WalkDir::new(root).into_iter().filter_entry(|e| {
+        let name = e.file_name().to_string_lossy();
+        e.depth() == 0 || !(name.starts_with('.') || matches!(name.as_ref(), "target" | "build" | "node_modules" | "out"))
+    });
+    for entry in walker.filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        let path = entry.path();
+        let rel = path.strip_prefix(root).unwrap_or(path);
+        if crate::rules::test_code::is_test_source(rel) {
+            continue;
+        }
+        let name = entry.file_name().to_string_lossy();
+        if name.ends_with(".java") && java_files < MAX_SOURCE_FILES {
+            java_files += 1;
+            let Ok(content) = fs::read_to_string(path) else { continue };
+            let imported: Vec<&str> = content.lines()
+                .filter_map(|l| l.trim_start().strip_prefix("import "))
+                .map(|l| l.trim_start_matches("static ").trim())
+                .collect();
+            let any = |prefixes: &[&str]| imported.iter().any(|i| prefixes.iter().any(|p| i.starts_with(p)));
+            imports.reactive_web += usize::from(any(&["org.springframework.web.reactive.", "reactor.core.publisher.", "io.vertx."]));
+            imports.servlet += usize::from(any(&["javax.servlet.", "jakarta.servlet.", "org.springframework.web.servlet."]));
+            imports.jpa += usize::from(any(&["javax.persistence.", "jakarta.persistence.", "org.springframework.data.jpa."]));
+            imports.mybatis += usize::from(any(&["org.apache.ibatis.", "org.mybatis.", "com.baomidou.mybatisplus."]));
+            imports.spring_boot += usize::from(any(&["org.springframework.boot."]));
+        } else if (name.starts_with("application") || name.starts_with("bootstrap"))
+            && [".yml", ".yaml", ".properties"].iter().any(|ext| name.ends_with(ext)) {
+            let Ok(content) = fs::read_to_string(path) else { continue };
+            let file = rel.to_string_lossy().replace('\\', "/");
+            if let Some(c) = web_type.as_ref().and_then(|re| re.captures(&content)) {
+                let web = if &c[1] == "reactive" { WEB_FLUX } else { WEB_MVC };
+                configured_web = Some((web.to_string(), file.clone()));
+            }
+            let keys: Vec<&str> = content.lines().map(str::trim_start).collect();
+            if config_jpa.is_none() && keys.iter().any(|k| k.starts_with("spring.jpa.") || k.starts_with("jpa:")) {
+                config_jpa = Some(file.clone());
+            }
+            if config_mybatis.is_none() && keys.iter().any(|k| k.starts_with("mybatis")) {
+                config_mybatis = Some(file);
+            }
+        }
+    }
+
+    if let Some((web, file)) = configured_web {
+        stack.evidence.push(format!("web={web} ← {file} spring.main.web-application-type"));
+        stack.web_stack = web;
+    } else if stack.web_stack.is_empty() && imports.reactive_web + imports.servlet > 0 {
+        let (web, files) = if imports.reactive_web > imports.servlet {
+            (WEB_FLUX, imports.reactive_web)
+        } else {
+            (WEB_MVC, imports.servlet)
+        };
+        stack.web_stack = web.to_string();
+        stack.evidence.push(format!("web={web} ← imports ({files} files)"));
+    }
+    if stack.web_stack == WEB_FLUX {
+        stack.is_reactive = true;
+    } else if stack.web_stack == WEB_MVC && stack.is_spring_boot {
+        stack.is_spring_mvc = true;
+    }
+
+    if !stack.has_jpa {
+        if let Some(source) = config_jpa.or_else(|| (imports.jpa > 0).then(|| format!("imports ({} files)", imports.jpa))) {
+            stack.has_jpa = true;
+            stack.evidence.push(format!("jpa ← {source}"));
         }
     }
+    if !stack.has_mybatis {
+        if let Some(source) = config_mybatis.or_else(|| (imports.mybatis > 0).then(|| format!("imports ({} files)", imports.mybatis))) {
+            stack.has_mybatis = true;
+            stack.evidence.push(format!("mybatis ← {source}"));
+        }
+    }
+    if !stack.is_spring_boot && imports.spring_boot > 0 {
+        stack.is_spring_boot = true;
+        stack.evidence.push(format!("spring-boot ← imports ({} files)", imports.spring_boot));
+    }
+}
+
+/// 技术栈摘要 (`summary` / `detect` 共用)
+pub fn render_stack(stack: &DetectedStack) -> String {
+    let spring_boot = match (&stack.spring_boot_version, stack.is_spring_boot) {
+        (Some(version), _) => version.clone(),
+        (None, true) => "Yes".to_string(),
+        (None, false) => "No".to_string(),
+    };
+    let web = match stack.web_stack.as_str() {
+        WEB_MVC => "Spring MVC (servlet)",
+        WEB_FLUX => "WebFlux (reactive)",
+        _ if stack.is_reactive => "Reactor",
+        _ => "-",
+    };
+    let persistence = [(stack.has_jpa, "JPA"), (stack.has_mybatis, "MyBatis")]
+        .iter()
+        .filter(|(present, _)| *present)
+        .map(|(_, name)| *name)
+        .collect::<Vec<_>>()
+        .join(" + ");
+    let mut lines = vec![
+        format!("- Build Tool:  {}", if stack.build_tool.is_empty() { "Unknown" } else { &stack.build_tool }),
+        format!("- JDK Target:  {}", stack.jdk_version),
+        format!("- Spring Boot: {spring_boot}"),
+        format!("- Web:         {web}"),
+        format!("- Persistence: {}", if persistence.is_empty() { "-" } else { &persistence }),
+        format!("- Reactive:    {}", if stack.is_reactive { "Yes" } else { "No" }),
+        format!("- Lombok:      {}", if stack.has_lombok { "Yes" } else { "No" }),
+    ];
+    if stack.is_native_image {
+        lines.push("- Native Image: Yes".to_string());
+    }
+    lines.join("\n")
 }
 
 fn analyze_gradle(root: &Path, stack: &mut DetectedStack) {
@@ -586,6 +849,12 @@ fn analyze_gradle(root: &Path, stack: &mut DetectedStack) {
                     stack.is_spring_mvc = stack.is_spring_mvc || detected.is_spring_mvc;
                     stack.is_reactive = stack.is_reactive || detected.is_reactive;
                     stack.has_lombok = stack.has_lombok || detected.has_lombok;
+                    if stack.web_stack != WEB_MVC && !detected.web_stack.is_empty() {
+                        stack.web_stack = detected.web_stack;
+                    }
+                    stack.has_jpa = stack.has_jpa || detected.has_jpa;
+                    stack.has_mybatis = stack.has_mybatis || detected.has_mybatis;
+                    stack.evidence.extend(detected.evidence.into_iter().map(|e| format!("{e} ({file})")));
                 }
                 Err(_) => {
                     // Fall back to simple string matching if parsing fails
@@ -599,11 +868,21 @@ fn analyze_gradle(root: &Path, stack: &mut DetectedStack) {
                 }
             }
             
-            // Extract JDK version (still use string matching as it's in build config)
-            if content.contains("JavaVersion.VERSION_17") || content.contains("sourceCompatibility = '17'") {
-                stack.jdk_version = "17".to_string();
-            } else if content.contains("JavaVersion.VERSION_21") || content.contains("sourceCompatibility = '21'") {
-                stack.jdk_version = "21".to_string();
+            // GraalVM native-image 插件 (v9.6)
+            if content.contains("org.graalvm.buildtools.native") || content.contains("spring-native") {
+                stack.is_native_image = true;
+            }
+
+            if let Some(version) = gradle_spring_boot_version(&content) {
+                stack.evidence.push(format!("spring-boot={version} ← {file}"));
+                stack.spring_boot_version = Some(version);
+                stack.is_spring_boot = true;
+            }
+
+            // JDK 目标版本: toolchain / JavaVersion / sourceCompatibility
+            if let Some(version) = gradle_jdk_version(&content) {
+                stack.evidence.push(format!("jdk={version} ← {file}"));
+                stack.jdk_version = version;
             }
         }
     }
@@ -631,6 +910,13 @@ pub fn generate_strategy_hint(stack: &DetectedStack) -> String {
         }
     }
     
+    if stack.has_jpa {
+        hints.push("- **JPA**: Focus on lazy-loading N+1, missing fetch joins / batch size, and unbounded result lists.".to_string());
+    }
+    if stack.has_mybatis {
+        hints.push("- **MyBatis**: Focus on per-row mapper calls in loops, nested selects and `<foreach>` batch sizes.".to_string());
+    }
+    
     if stack.has_lombok {
         hints.push("- **Lombok**: Be aware of generated code (equals/hashCode) performance impacts.".to_string());
     }
@@ -754,6 +1040,84 @@ mod tests {
         assert!(hint.contains("JDK 17"));
     }
     
+    #[test]
+    fn test_detect_versions_web_and_persistence() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("pom.xml"), r#"
+            <project>
+                <parent>
+                    <groupId>org.springframework.boot</groupId>
+                    <artifactId>spring-boot-starter-parent</artifactId>
+                    <version>3.2.1</version>
+                </parent>
+                <properties><maven.compiler.release>21</maven.compiler.release></properties>
+                <dependencies>
+                    <dependency>
+                        <groupId>org.springframework.boot</groupId>
+                        <artifactId>spring-boot-starter-webflux</artifactId>
+                    </dependency>
+                    <dependency>
+                        <groupId>org.mybatis.spring.boot</groupId>
+                        <artifactId>mybatis-spring-boot-starter</artifactId>
+                    </dependency>
+                </dependencies>
+            </project>
+        "#).unwrap();
+        let src = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("Order.java"), "package com.acme;\nimport jakarta.persistence.Entity;\n@Entity class Order {}\n").unwrap();
+        // 测试源码中的 import 不计入
+        let test = dir.path().join("src/test/java/com/acme");
+        std::fs::create_dir_all(&test).unwrap();
+        std::fs::write(test.join("OrderTest.java"), "import javax.servlet.Filter;\nclass OrderTest {}\n").unwrap();
+
+        let stack = detect_stack(dir.path());
+        assert_eq!(stack.spring_boot_version.as_deref(), Some("3.2.1"));
+        assert_eq!(stack.jdk_version, "21");
+        assert_eq!(stack.web_stack, WEB_FLUX);
+        assert!(stack.is_reactive && stack.has_jpa && stack.has_mybatis);
+        assert!(stack.evidence.contains(&"jpa ← imports (1 files)".to_string()));
+        let rendered = render_stack(&stack);
+        assert!(rendered.contains("- Spring Boot: 3.2.1"));
+        assert!(rendered.contains("- Web:         WebFlux (reactive)"));
+        assert!(rendered.contains("- Persistence: JPA + MyBatis"));
+
+        // 配置显式指定 servlet 时覆盖依赖推断
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.yml"), "spring:\n  main:\n    web-application-type: servlet\n").unwrap();
+        let stack = detect_stack(dir.path());
+        assert_eq!(stack.web_stack, WEB_MVC);
+        assert!(stack.evidence.contains(&"web=mvc ← src/main/resources/application.yml spring.main.web-application-type".to_string()));
+    }
+
+    #[test]
+    fn test_detect_gradle_versions_and_imports() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("build.gradle.kts"), r#"
+plugins {
+    id("org.springframework.boot") version "3.3.0"
+}
+java {
+    toolchain { languageVersion = JavaLanguageVersion.of(17) }
+}
+"#).unwrap();
+        let src = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("Router.java"), "import org.springframework.web.reactive.function.server.RouterFunction;\nclass Router {}\n").unwrap();
+
+        let stack = detect_stack(dir.path());
+        assert!(stack.is_spring_boot);
+        assert_eq!(stack.spring_boot_version.as_deref(), Some("3.3.0"));
+        assert_eq!(stack.jdk_version, "17");
+        assert_eq!(stack.web_stack, WEB_FLUX);
+        assert!(stack.is_reactive);
+
+        assert_eq!(gradle_jdk_version("sourceCompatibility = JavaVersion.VERSION_1_8"), Some("1.8".to_string()));
+        assert_eq!(gradle_jdk_version("sourceCompatibility = '11'"), Some("11".to_string()));
+        assert_eq!(maven_spring_boot_version("<properties><spring-boot.version>2.7.18</spring-boot.version></properties><artifactId>spring-boot-dependencies</artifactId><version>${spring-boot.version}</version>"), Some("2.7.18".to_string()));
+    }
+
     // ========================================================================
     // Unit tests for parse_maven_pom
     // ========================================================================
@@ -0,0 +1,357 @@
+//! 不可达代码 (Reachability) - v9.6
+//!
+//! 调用图上没有调用方的 Service / Repository 方法多半是废弃代码，其中的问题不会在运行时触发，
+//! 修复应排在活跃路径之后。目录扫描后按 Phase 2 方法度量的行范围把问题归到所在方法，方法不可达时
+//! 级别降低一级 (P0 → P1 → P2 → Info)，描述末尾注明 `[不可达代码: X.y() 无调用方]`。
+//!
+//! 方法同时满足以下条件时视为不可达：
+//! - 所在类为 Service / Repository 层 (Controller 等入口由框架调用)
+//! - 调用图中没有接收者类型为该类 (或其父类/接口、子类) 的同名方法调用点；接收者类型经符号表
+//!   (调用图链接结果、调用方类的字段类型) 解析，与该类无关的同名调用不算调用方
+//! - 同名调用点的接收者类型无法解析 (如局部变量) 时可达性未知，按可达处理，不报告也不降级
+//! - 没有框架入口注解 ([`ENTRY_ANNOTATIONS`]，如 `@Scheduled`、`@EventListener`、`@Bean`)
+//!
+//! `unused` 命令列出不可达方法，以及全部方法都不可达、且没有被其他类以字段引用的候选废弃 Bean。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::ast_engine::{self, AstIssue, Severity};
+use crate::i18n;
+use crate::metrics::MethodMetrics;
+use crate::scanner::annotation;
+use crate::symbol_table::{LayerType, SymbolTable};
+use crate::taint::{CallGraph, CallSite};
+
+/// 由框架调用的方法注解 (调用图中没有调用方也是可达的)
+pub const ENTRY_ANNOTATIONS: &[&str] = &[
+    "Override", "Bean", "PostConstruct", "PreDestroy", "Scheduled", "Async",
+    "EventListener", "TransactionalEventListener", "KafkaListener", "RabbitListener", "JmsListener",
+    "SqsListener", "StreamListener", "RabbitHandler", "KafkaHandler", "ExceptionHandler",
+];
+
+/// 不可达的方法
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct UnreachableMethod {
+    pub class: String,
+    pub method: String,
+    pub file: String,
+    pub line: usize,
+}
+
+/// 候选废弃 Bean: 全部方法不可达且没有字段引用
+#[derive(Debug, Clone, Serialize)]
+pub struct UnusedBean {
+    pub class: String,
+    pub layer: String,
+    pub file: String,
+    pub line: usize,
+    pub methods: usize,
+}
+
+/// Service / Repository 层中不可达的方法 `(类名, 方法名)`
+pub fn unreachable_methods(symbol_table: &SymbolTable, call_graph: &CallGraph) -> HashSet<(String, String)> {
+    // 方法名 -> 各调用点的接收者类型 (None = 无法解析)
+    let mut receivers: HashMap<&str, Vec<Option<String>>> = HashMap::new();
+    for (callee, sites) in &call_graph.incoming {
+        let types = receivers.entry(callee.name.as_str()).or_default();
+        types.extend(sites.iter().map(|site| receiver_type(symbol_table, site)));
+    }
+    symbol_table.methods.values()
+        .filter(|m| {
+            let Some(types) = receivers.get(m.name.as_str()) else { return true };
+            let own = ancestors(symbol_table, &m.class);
+            !types.iter().any(|t| match t {
+                None => true,
+                // 接收者为该类、其父类/接口，或其子类 (调用继承的方法)
+                Some(t) => own.contains(t) || ancestors(symbol_table, t).contains(&m.class),
+            })
+        })
+        .filter(|m| !m.annotations.iter().any(|a| ENTRY_ANNOTATIONS.contains(&annotation::parse_text(a).0)))
+        .filter(|m| {
+            symbol_table.lookup_by_simple_name(&m.class).iter()
+                .any(|info| matches!(info.layer, LayerType::Service | LayerType::Repository))
+        })
+        .map(|m| (m.class.clone(), m.name.clone()))
+        .collect()
+}
+
+/// 调用点接收者的简单类名：调用图已链接的类，或调用方类中同名字段的声明类型；都没有时返回 None
+fn receiver_type(symbol_table: &SymbolTable, site: &CallSite) -> Option<String> {
+    if !site.callee.is_unresolved() {
+        return Some(site.callee.simple_class_name().to_string());
+    }
+    let key = (site.caller.simple_class_name().to_string(), site.callee.simple_class_name().to_string());
+    symbol_table.fields.get(&key).map(|binding| {
+        let base = binding.type_name.split('<').next().unwrap_or(&binding.type_name);
+        base.rsplit('.').next().unwrap_or(base).trim().to_string()
+    })
+}
+
+/// 类自身及其全部父类/接口 (按符号表中的 `supertypes` 传递展开)
+fn ancestors(symbol_table: &SymbolTable, class: &str) -> HashSet<String> {
+    let mut seen = HashSet::from([class.to_string()]);
+    let mut pending = vec![class.to_string()];
+    while let Some(name) = pending.pop() {
+        for info in symbol_table.lookup_by_simple_name(&name) {
+            for parent in &info.supertypes {
+                if seen.insert(parent.clone()) {
+                    pending.push(parent.clone());
+                }
+            }
+        }
+    }
+    seen
+}
+
+/// 降低一级
+fn demote(severity: Severity) -> Severity {
+    match severity {
+        Severity::P0 => Severity::P1,
+        Severity::P1 => Severity::P2,
+        Severity::P2 | Severity::Info => Severity::Info,
+    }
+}
+
+/// 降低不可达方法中问题的级别并注明，返回数量
+///
+/// 问题按 `metrics` 中同一文件、行范围包含问题行的最内层方法归属；不在任何方法内的问题不处理。
+pub fn apply(issues: &mut [AstIssue], metrics: &[MethodMetrics], symbol_table: &SymbolTable, call_graph: &CallGraph) -> usize {
+    let unreachable = unreachable_methods(symbol_table, call_graph);
+    if unreachable.is_empty() {
+        return 0;
+    }
+    let mut demoted = 0;
+    for issue in issues.iter_mut() {
+        let method = metrics.iter()
+            .filter(|m| m.file == issue.file && m.line <= issue.line && issue.line <= m.end_line)
+            .max_by_key(|m| m.line);
+        let Some(method) = method else { continue };
+        if !unreachable.contains(&(method.class.clone(), method.method.clone())) {
+            continue;
+        }
+        issue.severity = demote(issue.severity);
+        let note = i18n::format(
+            "note.unreachable", "[不可达代码: {method}() 无调用方]",
+            &[("method", &format!("{}.{}", method.class, method.method))],
+        );
+        issue.description = format!("{} {note}", issue.description);
+        demoted += 1;
+    }
+    demoted
+}
+
+/// `java-perf unused`: 不可达方法与候选废弃 Bean
+pub fn report(root: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let index = ast_engine::index_project(root)?;
+    let path = std::fs::canonicalize(root).unwrap_or_else(|_| root.into());
+    let rel = |file: &std::path::Path| file.strip_prefix(&path).unwrap_or(file).to_string_lossy().replace('\\', "/");
+    let st = &index.symbol_table;
+    let unreachable = unreachable_methods(st, &index.call_graph);
+
+    let mut methods: Vec<UnreachableMethod> = st.methods.values()
+        .filter(|m| unreachable.contains(&(m.class.clone(), m.name.clone())))
+        .filter_map(|m| {
+            let info = st.lookup_by_simple_name(&m.class).into_iter().next()?;
+            Some(UnreachableMethod { class: m.class.clone(), method: m.name.clone(), file: rel(&info.file), line: m.line })
+        })
+        .collect();
+    methods.sort();
+    methods.dedup();
+
+    // 类 → (方法总数, 不可达方法数)
+    let mut counts: BTreeMap<&str, (usize, usize)> = BTreeMap::new();
+    for m in st.methods.values() {
+        let entry = counts.entry(m.class.as_str()).or_default();
+        entry.0 += 1;
+        if unreachable.contains(&(m.class.clone(), m.name.clone())) {
+            entry.1 += 1;
+        }
+    }
+    let referenced: HashSet<&str> = st.fields.iter()
+        .filter(|((owner, _), binding)| *owner != binding.type_name)
+        .map(|(_, binding)| binding.type_name.split('<').next().unwrap_or(&binding.type_name))
+        .collect();
+    let beans: Vec<UnusedBean> = counts.iter()
+        .filter(|(class, (total, dead))| total == dead && !referenced.contains(**class))
+        .filter_map(|(class, (total, _))| {
+            let info = st.lookup_by_simple_name(class).into_iter().next()?;
+            Some(UnusedBean {
+                class: class.to_string(),
+                layer: format!("{:?}", info.layer),
+                file: rel(&info.file),
+                line: info.line,
+                methods: *total,
+            })
+        })
+        .collect();
+
+    let report = render_markdown(&methods, &beans);
+    Ok(json!({
+        "methods": methods,
+        "beans": beans,
+        "report": report,
+    }))
+}
+
+fn render_markdown(methods: &[UnreachableMethod], beans: &[UnusedBean]) -> String {
+    let mut out = i18n::text("report.unused.title", "## 🪦 候选废弃代码 (调用图无调用方)").to_string();
+    out.push_str("\n\n");
+    if methods.is_empty() {
+        out.push_str(i18n::text("report.unused.empty", "未发现不可达的 Service / Repository 方法"));
+        out.push('\n');
+        return out;
+    }
+    if !beans.is_empty() {
+        out.push_str(&format!("### {}\n\n", i18n::text("report.unused.beans", "候选废弃 Bean")));
+        out.push_str(i18n::text("report.unused.bean_columns", "| 类 | 层级 | 位置 | 方法数 |"));
+        out.push_str("\n|----|------|------|--------|\n");
+        for bean in beans {
+            out.push_str(&format!("| `{}` | {} | {}:{} | {} |\n", bean.class, bean.layer, bean.file, bean.line, bean.methods));
+        }
+        out.push('\n');
+    }
+    out.push_str(&format!("### {}\n\n", i18n::text("report.unused.methods", "不可达方法")));
+    out.push_str(i18n::text("report.unused.method_columns", "| 方法 | 位置 |"));
+    out.push_str("\n|------|------|\n");
+    for m in methods {
+        out.push_str(&format!("| `{}.{}()` | {}:{} |\n", m.class, m.method, m.file, m.line));
+    }
+    out.push('\n');
+    out.push_str(i18n::text(
+        "report.unused.note",
+        "> 调用图只包含项目源码中的调用：反射、其他模块或脚本调用的方法也会出现在这里，删除前请确认。",
+    ));
+    out.push('\n');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unreachable_methods_and_beans() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, code: &str| std::fs::write(dir.path().join(name), code).unwrap();
+        write("OrderController.java", r#"package com.acme;
+@RestController
+public class OrderController {
+    private OrderService orderService;
+    @GetMapping("/orders")
+    public List<Order> list() { return orderService.list(); }
+}
+"#);
+        write("OrderService.java", r#"package com.acme;
+@Service
+public class OrderService {
+    private OrderRepository orderRepository;
+    public List<Order> list() { return load(); }
+    private List<Order> load() { return orderRepository.findAll(); }
+    public void legacyExport() {
+        for (Order o : orderRepository.findAll()) { o.toString(); }
+    }
+    @Scheduled(cron = "0 0 2 * * *")
+    public void nightly() { orderRepository.findAll().forEach(this::touch); }
+    private void touch(Order o) {}
+}
+"#);
+        write("ArchiveService.java", r#"package com.acme;
+@Service
+public class ArchiveService {
+    public void archive() {}
+    public void purge() {}
+}
+"#);
+
+        let index = ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        let mut unreachable: Vec<_> = unreachable_methods(&index.symbol_table, &index.call_graph).into_iter().collect();
+        unreachable.sort();
+        assert_eq!(unreachable, vec![
+            ("ArchiveService".to_string(), "archive".to_string()),
+            ("ArchiveService".to_string(), "purge".to_string()),
+            ("OrderService".to_string(), "legacyExport".to_string()),
+        ]);
+
+        let result = report(dir.path().to_str().unwrap()).unwrap();
+        assert_eq!(result["beans"].as_array().unwrap().len(), 1);
+        assert_eq!(result["beans"][0]["class"], "ArchiveService");
+        assert_eq!(result["methods"].as_array().unwrap().len(), 3);
+        assert!(result["report"].as_str().unwrap().contains("| `OrderService.legacyExport()` | OrderService.java:7 |"));
+    }
+
+    #[test]
+    fn test_apply_demotes_issues_in_unreachable_methods() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("ReportService.java"), r#"package com.acme;
+@Service
+public class ReportService {
+    public void legacy() {}
+}
+"#).unwrap();
+        let index = ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        let metric = |method: &str, line, end_line| MethodMetrics {
+            file: "ReportService.java".to_string(),
+            package: "com.acme".to_string(),
+            class: "ReportService".to_string(),
+            method: method.to_string(),
+            line,
+            end_line,
+            cyclomatic: 1,
+            loop_depth: 0,
+            allocations: 0,
+            loop_allocations: 0,
+            statements: 0,
+            complexity: 1,
+        };
+        let issue = |line, severity| AstIssue {
+            severity,
+            issue_type: "OBJECT_IN_LOOP".to_string(),
+            file: "ReportService.java".to_string(),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        };
+        let mut issues = vec![issue(4, Severity::P0), issue(9, Severity::P1)];
+        assert_eq!(apply(&mut issues, &[metric("legacy", 4, 4)], &index.symbol_table, &index.call_graph), 1);
+        assert_eq!(issues[0].severity, Severity::P1);
+        assert_eq!(issues[0].description, "desc [不可达代码: ReportService.legacy() 无调用方]");
+        assert_eq!(issues[1].severity, Severity::P1);
+    }
+
+    #[test]
+    fn test_same_named_methods_resolved_by_receiver_type() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, code: &str| std::fs::write(dir.path().join(name), code).unwrap();
+        write("AdminController.java", r#"package com.acme;
+@RestController
+public class AdminController {
+    private CacheService cacheService;
+    private BillingService billingService;
+    @PostMapping("/purge")
+    public void purge() {
+        cacheService.purge();
+        billingService.charge();
+        ExportService exporter = lookup();
+        exporter.export();
+    }
+}
+"#);
+        write("CacheService.java", "package com.acme;\n@Service\npublic class CacheService {\n    public void purge() {}\n}\n");
+        write("ArchiveService.java", "package com.acme;\n@Service\npublic class ArchiveService {\n    public void purge() {}\n}\n");
+        write("BillingService.java", "package com.acme;\npublic interface BillingService {\n    void charge();\n}\n");
+        write("BillingServiceImpl.java", "package com.acme;\n@Service\npublic class BillingServiceImpl implements BillingService {\n    public void charge() {}\n}\n");
+        write("ExportService.java", "package com.acme;\n@Service\npublic class ExportService {\n    public void export() {}\n}\n");
+
+        let index = ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        let unreachable: Vec<_> = unreachable_methods(&index.symbol_table, &index.call_graph).into_iter().collect();
+        // CacheService.purge() 的调用不能证明 ArchiveService.purge() 可达；
+        // 经接口调用的实现类方法可达；局部变量接收者无法解析，可达性未知，不报告
+        assert_eq!(unreachable, vec![("ArchiveService".to_string(), "purge".to_string())]);
+    }
+}
@@ -0,0 +1,709 @@
+//! 报告输出格式 (v9.6)
+//!
+//! 将扫描结果渲染为机器可读格式：
+//! - `json`: 原始问题列表，包含完整范围 (line/column/end_line/end_column) 与分类标签 (`tags`)
+//! - `sarif`: SARIF 2.1.0，可直接被 GitHub Code Scanning / IDE 插件消费
+//! - `gitlab`: GitLab Code Quality JSON，fingerprint 跨次运行稳定 (MR 组件据此显示新增/已解决)
+//! - `checkstyle`: Checkstyle XML，可被 Jenkins Warnings-NG 等现有看板直接导入
+//! - `github`: GitHub Actions workflow commands (`::error file=...::msg`)，问题直接标注在 PR diff 上
+//!
+//! JSON 报告的结构由内置的 JSON Schema (`java-perf schema` 输出) 约定，报告中的 `$schema` 字段指向它、
+//! `report_version` 为布局版本 ([`REPORT_VERSION`])；同一版本内新增字段只追加，已有字段保持含义不变。
+//! 删除或改变字段时版本递增，`scan --compat N` 继续输出旧版布局 ([`render_json_version`])。
+//!
+//! `--meta` 构建元数据只写入 Markdown、`json` (`meta`) 与 `sarif` (`runs[].properties.meta`)：
+//! `github` / `gitlab` / `checkstyle` 的格式由消费方固定，没有存放元数据的位置。
+//!
+//! Markdown 报告仍由 ast_engine 生成 (面向人类阅读，支持 compact 模式)。
+//! 机器可读格式始终输出全部问题，不受 compact/max_p1 影响。
+
+use serde_json::{json, Value};
+use quick_xml::escape::escape;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use crate::ast_engine::{AstIssue, ScanResult, Severity};
+use crate::metrics;
+use crate::rules::{effort, taxonomy};
+use crate::ownership;
+
+/// 报告输出格式
+#[derive(clap::ValueEnum, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ReportFormat {
+    /// 人类可读 Markdown (默认)
+    #[default]
+    Markdown,
+    /// 原始 JSON 问题列表
+    Json,
+    /// SARIF 2.1.0
+    Sarif,
+    /// GitHub Actions 注解 (workflow commands)
+    Github,
+    /// GitLab Code Quality
+    Gitlab,
+    /// Checkstyle XML
+    Checkstyle,
+}
+
+/// 解析 `--meta key=value` 参数
+pub fn parse_meta(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((k, v)) if !k.trim().is_empty() => Ok((k.trim().to_string(), v.trim().to_string())),
+        _ => Err(format!("invalid metadata '{s}': expected key=value")),
+    }
+}
+
+/// 当前 JSON 报告布局版本 (`report_version`)
+///
+/// - 1: 最早的 `--format json` 布局: `files_scanned` / `summary {p0, p1, total}` / `issues` (级别、范围与描述)
+/// - 2: 新增 `$schema`、`report_version`、构建元数据 `meta`、扫描过程信息 `scan`，P2/Info 计数，
+///   问题的 `tags` / `effort` / `owner`，`summary.remediation`、`teams`、`metrics`
+pub const REPORT_VERSION: u32 = 2;
+
+/// 各版本的 JSON Schema (`java-perf schema` 原样输出，保留字段顺序)，下标 = 版本 - 1
+const SCHEMAS: [&str; REPORT_VERSION as usize] = [
+    include_str!("../resources/schema/scan-report.v1.json"),
+    include_str!("../resources/schema/scan-report.v2.json"),
+];
+
+/// `version` 版报告的 JSON Schema 标识
+pub fn schema_id(version: u32) -> String {
+    format!("https://github.com/ly87ing/dev-skills/raw/main/plugins/java-perf/rust/resources/schema/scan-report.v{version}.json")
+}
+
+/// `version` 版报告的 JSON Schema 源文本 (版本不存在时为 None)
+pub fn schema(version: u32) -> Option<&'static str> {
+    version.checked_sub(1).and_then(|i| SCHEMAS.get(i as usize)).copied()
+}
+
+/// 按布局版本渲染 JSON 报告 (`scan --compat N`)
+pub fn render_json_version(result: &ScanResult, version: u32) -> Value {
+    match version {
+        1 => render_json_v1(result),
+        _ => render_json(result),
+    }
+}
+
+/// 版本 1 布局: 没有 `report_version` 的最早格式
+///
+/// 问题一个不少 (P2/Info 也列出，`summary.total` 计入)，只省略版本 2 新增的字段。
+fn render_json_v1(result: &ScanResult) -> Value {
+    let count = |severity: Severity| result.issues.iter().filter(|i| i.severity == severity).count();
+    json!({
+        "files_scanned": result.file_count,
+        "summary": { "p0": count(Severity::P0), "p1": count(Severity::P1), "total": result.issues.len() },
+        "issues": result.issues.iter().map(|issue| json!({
+            "severity": issue.severity,
+            "id": issue.issue_type,
+            "file": issue.file,
+            "line": issue.line,
+            "column": issue.column,
+            "end_line": issue.end_line,
+            "end_column": issue.end_column,
+            "description": issue.description,
+        })).collect::<Vec<_>>(),
+    })
+}
+
+/// 渲染 JSON 报告
+pub fn render_json(result: &ScanResult) -> Value {
+    let count = |severity: Severity| result.issues.iter().filter(|i| i.severity == severity).count();
+    let mut report = json!({
+        "$schema": schema_id(REPORT_VERSION),
+        "report_version": REPORT_VERSION,
+        "meta": result.meta,
+        "scan": result.info,
+        "files_scanned": result.file_count,
+        "summary": {
+            "p0": count(Severity::P0),
+            "p1": count(Severity::P1),
+            "p2": count(Severity::P2),
+            "info": count(Severity::Info),
+            "total": result.issues.len(),
+            "remediation": effort::Plan::of(&result.issues),
+        },
+        "issues": result.issues.iter().map(|issue| {
+            let mut value = json!(issue);
+            value["tags"] = json!(taxonomy::tags(&issue.issue_type));
+            value
+        }).collect::<Vec<_>>(),
+    });
+    // v9.6: `scan --owners` 找到 CODEOWNERS 时按团队汇总
+    if result.info.codeowners.is_some() {
+        report["teams"] = json!(ownership::team_summary(&result.issues));
+    }
+    // v9.6: 方法复杂度度量，被标记的方法按 问题权重 × 复杂度 排序
+    if !result.metrics.is_empty() {
+        report["metrics"] = metrics::to_json(&result.metrics, &result.issues);
+    }
+    report
+}
+
+/// 渲染 SARIF 2.1.0 报告
+pub fn render_sarif(result: &ScanResult) -> Value {
+    let rule_ids: BTreeSet<&str> = result.issues.iter().map(|i| i.issue_type.as_str()).collect();
+    let rules: Vec<Value> = rule_ids.iter()
+        .map(|id| json!({ "id": id, "properties": { "tags": taxonomy::tags(id) } }))
+        .collect();
+    let results: Vec<Value> = result.issues.iter().map(sarif_result).collect();
+
+    json!({
+        "$schema": "https://json.schemastore.org/sarif-2.1.0.json",
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "java-perf",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": env!("CARGO_PKG_REPOSITORY"),
+                    "rules": rules,
+                }
+            },
+            "results": results,
+            "properties": { "meta": result.meta, "scan": result.info },
+        }]
+    })
+}
+
+/// 渲染 GitHub Actions workflow commands
+///
+/// 每个问题一行: `::error file=src/A.java,line=12,col=9,endLine=12,endColumn=40,title=N_PLUS_ONE::描述`。
+/// P0 → error, P1 → warning, P2/Info → notice。无行号的问题只标注到文件。
+pub fn render_github(result: &ScanResult) -> Value {
+    let lines: Vec<String> = result.issues.iter().map(github_annotation).collect();
+    json!(lines.join("\n"))
+}
+
+fn github_annotation(issue: &AstIssue) -> String {
+    let level = match issue.severity {
+        Severity::P0 => "error",
+        Severity::P1 => "warning",
+        Severity::P2 | Severity::Info => "notice",
+    };
+
+    let mut props = vec![format!("file={}", escape_github_property(&issue.file))];
+    if issue.line > 0 {
+        props.push(format!("line={}", issue.line));
+        if issue.column > 0 {
+            props.push(format!("col={}", issue.column));
+        }
+        if issue.end_line > 0 {
+            props.push(format!("endLine={}", issue.end_line));
+            if issue.end_column > 0 {
+                props.push(format!("endColumn={}", issue.end_column));
+            }
+        }
+    }
+    props.push(format!("title={}", escape_github_property(&issue.issue_type)));
+
+    format!("::{level} {}::{}", props.join(","), escape_github_data(&issue.description))
+}
+
+/// workflow command 消息转义
+fn escape_github_data(s: &str) -> String {
+    s.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+/// workflow command 属性值转义 (额外转义 `:` 和 `,`)
+fn escape_github_property(s: &str) -> String {
+    escape_github_data(s).replace(':', "%3A").replace(',', "%2C")
+}
+
+/// 渲染 GitLab Code Quality 报告
+///
+/// fingerprint 不包含行号 (代码上下移动不应产生"新问题")，
+/// 由 规则 ID + 文件 + 描述 + 同类问题序号 计算，跨次运行稳定 (描述随 --lang 变化，CI 中需固定语言)。
+pub fn render_gitlab(result: &ScanResult) -> Value {
+    let mut occurrences: HashMap<(&str, &str, &str), usize> = HashMap::new();
+
+    let entries: Vec<Value> = result.issues.iter().map(|issue| {
+        let key = (issue.issue_type.as_str(), issue.file.as_str(), issue.description.as_str());
+        let ordinal = occurrences.entry(key).or_insert(0);
+        let fingerprint = fingerprint(&issue.issue_type, &issue.file, &issue.description, *ordinal);
+        *ordinal += 1;
+
+        let severity = match issue.severity {
+            Severity::P0 => "critical",
+            Severity::P1 => "major",
+            Severity::P2 => "minor",
+            Severity::Info => "info",
+        };
+        json!({
+            "description": issue.description,
+            "check_name": issue.issue_type,
+            "fingerprint": fingerprint,
+            "severity": severity,
+            "location": {
+                "path": issue.file,
+                // GitLab 要求 begin >= 1；无行号的问题标注到第 1 行
+                "lines": { "begin": issue.line.max(1) },
+            },
+        })
+    }).collect();
+
+    json!(entries)
+}
+
+/// 稳定指纹: FNV-1a 64 位 (两轮不同种子拼成 32 位十六进制)
+///
+/// `ordinal` 为同一 (规则, 文件, 描述) 下的问题序号；GitLab 报告与 `diff-report` 共用。
+/// 不使用 std 的 DefaultHasher：其算法不保证跨 Rust 版本稳定。
+pub fn fingerprint(id: &str, file: &str, description: &str, ordinal: usize) -> String {
+    fn fnv1a(seed: u64, parts: &[&[u8]]) -> u64 {
+        let mut hash = seed;
+        for part in parts {
+            for b in part.iter().chain(std::iter::once(&0u8)) {
+                hash ^= u64::from(*b);
+                hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+            }
+        }
+        hash
+    }
+
+    let ordinal = ordinal.to_string();
+    let parts: [&[u8]; 4] = [
+        id.as_bytes(),
+        file.as_bytes(),
+        description.as_bytes(),
+        ordinal.as_bytes(),
+    ];
+    format!("{:016x}{:016x}", fnv1a(0xcbf2_9ce4_8422_2325, &parts), fnv1a(0x6c62_272e_07bb_0142, &parts))
+}
+
+/// 渲染 Checkstyle XML 报告
+///
+/// 按文件分组为 `<file>`，每个问题一个 `<error>`；P0 → error, P1 → warning, P2/Info → info，
+/// `source` 为 `java-perf.<规则 ID>` (Warnings-NG 以此归类)。
+pub fn render_checkstyle(result: &ScanResult) -> Value {
+    let mut by_file: BTreeMap<&str, Vec<&AstIssue>> = BTreeMap::new();
+    for issue in &result.issues {
+        by_file.entry(issue.file.as_str()).or_default().push(issue);
+    }
+
+    let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<checkstyle version=\"8.0\">\n");
+    for (file, issues) in by_file {
+        xml.push_str(&format!("  <file name=\"{}\">\n", escape(file)));
+        for issue in issues {
+            let severity = match issue.severity {
+                Severity::P0 => "error",
+                Severity::P1 => "warning",
+                Severity::P2 | Severity::Info => "info",
+            };
+            let column = if issue.column > 0 { format!(" column=\"{}\"", issue.column) } else { String::new() };
+            xml.push_str(&format!(
+                "    <error line=\"{}\"{} severity=\"{}\" message=\"{}\" source=\"java-perf.{}\"/>\n",
+                issue.line, column, severity, escape(issue.description.as_str()), escape(issue.issue_type.as_str())
+            ));
+        }
+        xml.push_str("  </file>\n");
+    }
+    xml.push_str("</checkstyle>");
+
+    json!(xml)
+}
+
+fn sarif_result(issue: &AstIssue) -> Value {
+    let level = match issue.severity {
+        Severity::P0 => "error",
+        Severity::P1 => "warning",
+        Severity::P2 | Severity::Info => "note",
+    };
+
+    let mut location = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": issue.file }
+        }
+    });
+    // SARIF 要求 startLine >= 1；结构化配置解析无行号时省略 region
+    if let Some(region) = sarif_region(issue) {
+        location["physicalLocation"]["region"] = region;
+    }
+
+    json!({
+        "ruleId": issue.issue_type,
+        "level": level,
+        "message": { "text": issue.description },
+        "locations": [location],
+    })
+}
+
+fn sarif_region(issue: &AstIssue) -> Option<Value> {
+    if issue.line == 0 {
+        return None;
+    }
+    let mut region = json!({ "startLine": issue.line });
+    if issue.column > 0 {
+        region["startColumn"] = json!(issue.column);
+    }
+    if issue.end_line > 0 {
+        region["endLine"] = json!(issue.end_line);
+        if issue.end_column > 0 {
+            region["endColumn"] = json!(issue.end_column);
+        }
+    }
+    Some(region)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::effort::Effort;
+    use crate::rules::taxonomy::Tag;
+
+    fn sample() -> ScanResult {
+        ScanResult {
+            file_count: 2,
+            issues: vec![
+                AstIssue {
+                    severity: Severity::P0,
+                    issue_type: "N_PLUS_ONE".to_string(),
+                    file: "UserService.java".to_string(),
+                    line: 12,
+                    column: 9,
+                    end_line: 12,
+                    end_column: 40,
+                    description: "循环内调用 DAO".to_string(),
+                    owner: None,
+                    effort: Effort::Structural,
+                },
+                AstIssue {
+                    severity: Severity::P1,
+                    issue_type: "DB_POOL_SMALL".to_string(),
+                    file: "application.yml".to_string(),
+                    line: 0,
+                    column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    description: "连接池过小".to_string(),
+                    owner: None,
+                    effort: Effort::Trivial,
+                },
+            ],
+            meta: [("git_sha".to_string(), "abc123".to_string())].into_iter().collect(),
+            info: Default::default(),
+            timing: None,
+            metrics: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_json_includes_ranges() {
+        let v = render_json(&sample());
+        assert_eq!(v["summary"]["p0"], 1);
+        assert_eq!(v["issues"][0]["id"], "N_PLUS_ONE");
+        assert_eq!(v["issues"][0]["column"], 9);
+        assert_eq!(v["issues"][0]["end_column"], 40);
+        assert_eq!(v["issues"][0]["tags"], json!(["db", "io"]));
+        assert_eq!(v["issues"][1]["tags"], json!(["config", "db"]));
+        assert_eq!(v["issues"][1]["effort"], "trivial");
+        assert_eq!(v["summary"]["remediation"], json!({ "trivial": 1, "local": 0, "structural": 1 }));
+        assert_eq!(v["meta"]["git_sha"], "abc123");
+        assert_eq!(v["scan"], json!({}));
+
+        // 用户元数据与扫描信息分开: `--meta codeowners=x` 不会打开团队汇总
+        let mut result = sample();
+        result.meta.insert("codeowners".to_string(), "x".to_string());
+        assert!(render_json(&result).get("teams").is_none());
+        result.info.codeowners = Some("CODEOWNERS".to_string());
+        result.info.cached = true;
+        let v = render_json(&result);
+        assert_eq!(v["scan"], json!({ "codeowners": "CODEOWNERS", "cached": true }));
+        assert!(v.get("teams").is_some());
+    }
+
+    #[test]
+    fn test_retain_tags() {
+        let mut result = sample();
+        result.retain_tags(&[Tag::Config, Tag::Docker]);
+        assert_eq!(result.issues.len(), 1);
+        assert_eq!(result.issues[0].issue_type, "DB_POOL_SMALL");
+        // 次要标签同样匹配
+        let mut result = sample();
+        result.retain_tags(&[Tag::Db]);
+        assert_eq!(result.issues.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_meta() {
+        assert_eq!(parse_meta("build=42").unwrap(), ("build".to_string(), "42".to_string()));
+        assert_eq!(parse_meta("url=a=b").unwrap().1, "a=b");
+        assert!(parse_meta("=x").is_err());
+        assert!(parse_meta("novalue").is_err());
+    }
+
+    #[test]
+    fn test_github_annotations() {
+        let v = render_github(&sample());
+        let lines: Vec<&str> = v.as_str().unwrap().lines().collect();
+        assert_eq!(
+            lines[0],
+            "::error file=UserService.java,line=12,col=9,endLine=12,endColumn=40,title=N_PLUS_ONE::循环内调用 DAO"
+        );
+        // 无行号时只标注文件
+        assert_eq!(lines[1], "::warning file=application.yml,title=DB_POOL_SMALL::连接池过小");
+    }
+
+    #[test]
+    fn test_gitlab_code_quality() {
+        let v = render_gitlab(&sample());
+        assert_eq!(v[0]["check_name"], "N_PLUS_ONE");
+        assert_eq!(v[0]["severity"], "critical");
+        assert_eq!(v[0]["location"]["path"], "UserService.java");
+        assert_eq!(v[0]["location"]["lines"]["begin"], 12);
+        assert_eq!(v[1]["location"]["lines"]["begin"], 1);
+        assert_eq!(v[0]["fingerprint"].as_str().unwrap().len(), 32);
+    }
+
+    #[test]
+    fn test_gitlab_fingerprint_stable() {
+        let mut moved = sample();
+        moved.issues[0].line = 30; // 代码下移，指纹不变
+        assert_eq!(render_gitlab(&sample())[0]["fingerprint"], render_gitlab(&moved)[0]["fingerprint"]);
+
+        // 同文件同规则的多处问题指纹互不相同
+        let mut dup = sample();
+        let mut second = sample().issues.remove(0);
+        second.line = 20;
+        dup.issues.insert(1, second);
+        let v = render_gitlab(&dup);
+        assert_ne!(v[0]["fingerprint"], v[1]["fingerprint"]);
+    }
+
+    #[test]
+    fn test_checkstyle_xml() {
+        let mut result = sample();
+        result.issues[0].description = "a < b & \"c\"".to_string();
+        let v = render_checkstyle(&result);
+        let xml = v.as_str().unwrap();
+        assert!(xml.starts_with("<?xml"));
+        assert!(xml.contains(
+            r#"<error line="12" column="9" severity="error" message="a &lt; b &amp; &quot;c&quot;" source="java-perf.N_PLUS_ONE"/>"#
+        ));
+        // 无列号时省略 column 属性
+        assert!(xml.contains(r#"<error line="0" severity="warning" message="连接池过小" source="java-perf.DB_POOL_SMALL"/>"#));
+        assert_eq!(xml.matches("<file ").count(), 2);
+    }
+
+    #[test]
+    fn test_github_escaping() {
+        assert_eq!(escape_github_data("50% done\nnext"), "50%25 done%0Anext");
+        assert_eq!(escape_github_property("a,b:c"), "a%2Cb%3Ac");
+    }
+
+    #[test]
+    fn test_sarif_region() {
+        let v = render_sarif(&sample());
+        let results = &v["runs"][0]["results"];
+        let region = &results[0]["locations"][0]["physicalLocation"]["region"];
+        assert_eq!(results[0]["level"], "error");
+        assert_eq!(region["startLine"], 12);
+        assert_eq!(region["startColumn"], 9);
+        assert_eq!(region["endColumn"], 40);
+        // 无行号的问题不输出 region
+        assert!(results[1]["locations"][0]["physicalLocation"]["region"].is_null());
+        assert_eq!(v["runs"][0]["tool"]["driver"]["rules"].as_array().unwrap().len(), 2);
+        assert_eq!(v["runs"][0]["tool"]["driver"]["rules"][1]["properties"]["tags"], json!(["db", "io"]));
+        assert_eq!(v["runs"][0]["properties"]["meta"]["git_sha"], "abc123");
+    }
+
+    #[test]
+    fn test_advisory_levels() {
+        let mut result = sample();
+        for (severity, id) in [(Severity::Info, "AUTOWIRED_FIELD"), (Severity::P2, "ATOMIC_SPIN")] {
+            let mut issue = result.issues[0].clone();
+            (issue.severity, issue.issue_type) = (severity, id.to_string());
+            result.issues.push(issue);
+        }
+
+        let summary = &render_json(&result)["summary"];
+        assert_eq!((summary["p0"].as_u64(), summary["p1"].as_u64(), summary["p2"].as_u64(), summary["info"].as_u64()), (Some(1), Some(1), Some(1), Some(1)));
+        assert_eq!(render_json(&result)["issues"][2]["severity"], "Info");
+        assert_eq!(render_sarif(&result)["runs"][0]["results"][3]["level"], "note");
+        assert_eq!(render_gitlab(&result)[2]["severity"], "info");
+        assert_eq!(render_gitlab(&result)[3]["severity"], "minor");
+        assert!(render_github(&result).as_str().unwrap().contains("::notice file=UserService.java"));
+        assert!(render_checkstyle(&result).as_str().unwrap().contains(r#"severity="info" message="循环内调用 DAO" source="java-perf.ATOMIC_SPIN""#));
+
+        // Markdown: 按主分类分组，含更高级别问题的分类在前；P0 以外的问题受 max_p1 限制
+        let markdown = crate::ast_engine::render_markdown(&result, false, 2);
+        let markdown = markdown.as_str().unwrap();
+        assert!(markdown.contains("**建议/提示**: P2: 1, Info: 1\n"), "{markdown}");
+        assert!(markdown.contains("**修复计划**: 1 个简单修复，3 个结构性重构\n\n"), "{markdown}");
+        assert!(markdown.contains(concat!(
+            "### 🗄️ 数据库 (1)\n\n- 🔴 **N_PLUS_ONE** - `UserService.java:12` - 循环内调用 DAO\n\n",
+            "### ⚙️ 配置 (1)\n\n- 🟡 **DB_POOL_SMALL** - `application.yml:0` - 连接池过小\n\n",
+            "### 🧵 并发 (1)\n\n- 🔵 **ATOMIC_SPIN** - `UserService.java:12` - 循环内调用 DAO\n\n",
+            "*（另有 1 个 P1/P2/Info 问题未显示，调大 max_p1 查看）*\n",
+        )), "{markdown}");
+        let markdown = crate::ast_engine::render_markdown(&result, false, 5);
+        assert!(markdown.as_str().unwrap().contains("### ⚙️ 配置 (2)\n\n- 🟡 **DB_POOL_SMALL** - `application.yml:0` - 连接池过小\n- ⚪ **AUTOWIRED_FIELD**"), "{markdown}");
+        let compact = crate::ast_engine::render_markdown(&result, true, 5);
+        assert!(compact.as_str().unwrap().contains("*（2 个 P2 建议 / Info 提示已省略）*"));
+
+        result.retain_min_severity(Severity::P1);
+        assert_eq!(result.issues.len(), 2);
+        result.retain_min_severity(Severity::P0);
+        assert_eq!(result.issues[0].issue_type, "N_PLUS_ONE");
+        assert_eq!(result.issues.len(), 1);
+    }
+
+    #[test]
+    fn test_markdown_issue_links() {
+        use crate::issue_link::{IssueLinks, LinkFormat};
+
+        let links = IssueLinks::new(LinkFormat::Github, std::path::Path::new("."), Some("https://github.com/acme/shop"), Some("abc123")).unwrap();
+        let full = crate::ast_engine::render_markdown_with_links(&sample(), false, 5, Some(&links));
+        let full = full.as_str().unwrap();
+        assert!(full.contains("- 🔴 **N_PLUS_ONE** - [`UserService.java:12`](https://github.com/acme/shop/blob/abc123/"), "{full}");
+        assert!(full.contains("UserService.java#L12) - 循环内调用 DAO\n"), "{full}");
+
+        let links = IssueLinks::new(LinkFormat::Idea, &std::env::temp_dir(), None, None).unwrap();
+        let compact = crate::ast_engine::render_markdown_with_links(&sample(), true, 5, Some(&links));
+        let compact = compact.as_str().unwrap();
+        assert!(compact.contains("- `N_PLUS_ONE` [`UserService.java:12`](idea://open?file="), "{compact}");
+        assert!(compact.contains("UserService.java&line=12)\n"), "{compact}");
+    }
+
+    /// `$ref` 指向的定义
+    fn resolve<'s>(schema: &'s Value, root: &'s Value) -> Option<&'s Value> {
+        let name = schema["$ref"].as_str()?.trim_start_matches("#/$defs/");
+        Some(&root["$defs"][name])
+    }
+
+    /// 本层及 `$ref` 声明的属性名 (`unevaluatedProperties` 的可见范围)
+    fn declared<'s>(schema: &'s Value, root: &'s Value) -> Vec<&'s str> {
+        let mut names: Vec<&str> = schema["properties"].as_object().into_iter().flatten().map(|(k, _)| k.as_str()).collect();
+        names.extend(resolve(schema, root).map(|target| declared(target, root)).unwrap_or_default());
+        names
+    }
+
+    /// 按 Schema 校验 (仅覆盖内置 Schema 用到的关键字)
+    fn violations(value: &Value, schema: &Value, root: &Value, path: &str, out: &mut Vec<String>) {
+        if let Some(target) = resolve(schema, root) {
+            violations(value, target, root, path, out);
+        }
+        let type_ok = match schema["type"].as_str() {
+            Some("object") => value.is_object(),
+            Some("array") => value.is_array(),
+            Some("string") => value.is_string(),
+            Some("integer") => value.is_u64() || value.is_i64(),
+            Some("number") => value.is_number(),
+            Some("boolean") => value.is_boolean(),
+            _ => true,
+        };
+        if !type_ok {
+            return out.push(format!("{path}: expected {}", schema["type"]));
+        }
+        if !schema["const"].is_null() && schema["const"] != *value {
+            out.push(format!("{path}: expected {}", schema["const"]));
+        }
+        if let Some(allowed) = schema["enum"].as_array() {
+            if !allowed.contains(value) {
+                out.push(format!("{path}: {value} not in enum"));
+            }
+        }
+        if let Some(object) = value.as_object() {
+            for key in schema["required"].as_array().into_iter().flatten() {
+                if !object.contains_key(key.as_str().unwrap()) {
+                    out.push(format!("{path}: missing {key}"));
+                }
+            }
+            let closed = schema["additionalProperties"] == false || schema["unevaluatedProperties"] == false;
+            let declared = declared(schema, root);
+            for (key, field) in object {
+                let field_path = format!("{path}.{key}");
+                match &schema["properties"][key] {
+                    Value::Null if schema["additionalProperties"].is_object() => {
+                        violations(field, &schema["additionalProperties"], root, &field_path, out)
+                    }
+                    Value::Null if closed && !declared.contains(&key.as_str()) => out.push(format!("{field_path}: not in schema")),
+                    Value::Null => {}
+                    property => violations(field, property, root, &field_path, out),
+                }
+            }
+        }
+        if let Some(items) = value.as_array() {
+            for (i, item) in items.iter().enumerate() {
+                let item_schema = schema["prefixItems"].get(i).unwrap_or(&schema["items"]);
+                violations(item, item_schema, root, &format!("{path}[{i}]"), out);
+            }
+        }
+    }
+
+    #[test]
+    fn test_json_matches_schema() {
+        let schema: Value = serde_json::from_str(super::schema(REPORT_VERSION).unwrap()).unwrap();
+        assert_eq!(schema["$id"], schema_id(REPORT_VERSION));
+
+        let mut result = sample();
+        result.issues[0].owner = Some(ownership::IssueOwner {
+            author: "Alice".to_string(),
+            email: "alice@example.com".to_string(),
+            commit: "0".repeat(40),
+            date: "2024-05-01".to_string(),
+            teams: vec!["@acme/orders".to_string()],
+        });
+        result.info.codeowners = Some("CODEOWNERS".to_string());
+        result.metrics = vec![metrics::MethodMetrics {
+            file: "UserService.java".to_string(),
+            package: "com.acme".to_string(),
+            class: "UserService".to_string(),
+            method: "load".to_string(),
+            line: 10,
+            end_line: 14,
+            cyclomatic: 2,
+            loop_depth: 1,
+            allocations: 0,
+            loop_allocations: 0,
+            statements: 3,
+            complexity: 5,
+        }];
+        let mut v = render_json(&result);
+        v["timing"] = json!(crate::ast_engine::ScanTiming {
+            slowest_files: vec![("UserService.java".to_string(), 1.5)],
+            ..Default::default()
+        });
+        assert_eq!(v["$schema"], schema["$id"]);
+        assert_eq!(v["report_version"], REPORT_VERSION);
+        assert!(!v["teams"].is_null() && !v["metrics"]["riskiest"][0].is_null());
+
+        let mut errors = Vec::new();
+        violations(&v, &schema, &schema, "$", &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        // 未在 Schema 中声明的字段视为破坏约定
+        v["issues"][0]["confidence"] = json!("high");
+        v["summary"]["p0"] = json!("1");
+        v["metrics"]["methods"][0]["risk"] = json!(3);
+        let mut errors = Vec::new();
+        violations(&v, &schema, &schema, "$", &mut errors);
+        assert_eq!(errors, vec![
+            "$.issues[0].confidence: not in schema",
+            "$.metrics.methods[0].risk: not in schema",
+            "$.summary.p0: expected \"integer\"",
+        ]);
+    }
+
+    #[test]
+    fn test_compat_v1_layout() {
+        let mut result = sample();
+        let mut advisory = result.issues[0].clone();
+        (advisory.severity, advisory.issue_type) = (Severity::P2, "ATOMIC_SPIN".to_string());
+        result.issues.push(advisory);
+
+        let v = render_json_version(&result, 1);
+        let schema: Value = serde_json::from_str(super::schema(1).unwrap()).unwrap();
+        let mut errors = Vec::new();
+        violations(&v, &schema, &schema, "$", &mut errors);
+        assert!(errors.is_empty(), "{errors:?}");
+        assert!(v.get("report_version").is_none() && v.get("$schema").is_none());
+        assert!(v.get("meta").is_none() && v.get("scan").is_none());
+        // P2/Info 不丢弃，只是没有单独计数
+        assert_eq!(v["summary"], json!({ "p0": 1, "p1": 1, "total": 3 }));
+        assert_eq!(v["issues"].as_array().unwrap().len(), 3);
+        assert_eq!(v["issues"][2]["severity"], "P2");
+        assert_eq!(v["issues"][0]["end_column"], 40);
+
+        assert_eq!(render_json_version(&result, REPORT_VERSION), render_json(&result));
+        assert!(super::schema(0).is_none() && super::schema(REPORT_VERSION + 1).is_none());
+    }
+}
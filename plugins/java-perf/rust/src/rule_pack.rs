@@ -0,0 +1,554 @@
+//! 规则包更新 (Rule Pack) - v9.6
+//!
+//! 规则包把脚本规则 (Tree-sitter 查询 + WASM 判定，格式见 `scanner/script_rules.rs`) 与检查清单章节
+//! 一起发布，`java-perf update` 独立于二进制更新它们：
+//!
+//! ```text
+//! acme-rules-2026.10.zip        未压缩 (stored) 的 zip
+//! ├── pack.yaml                 name / version / min_engine (可选，要求的最低 java-perf 版本) / description
+//! ├── rules/*.yaml, rules/*.wasm
+//! └── checklist.yaml            可选，与 `checklist --kb` 相同格式
+//! acme-rules-2026.10.zip.sig    对 zip 全部字节的 Ed25519 签名 (64 字节，十六进制)
+//! ```
+//!
+//! 安装步骤：签名须由受信任公钥 (`--key` / `JAVA_PERF_PACK_KEYS`，32 字节十六进制) 之一验证通过 →
+//! 解压到临时目录 (条目路径不能越出包目录) → 校验清单、`min_engine`、规则与检查清单 →
+//! 整体替换 `$JAVA_PERF_HOME/rule-pack` (默认 `~/.java-perf/rule-pack`)。任一步失败时已安装的包不变。
+//! 在线模式用 `curl` 下载 `<url>` 与 `<url>.sig`；隔离网络用 `--offline <zip>` (签名为同目录的 `<zip>.sig`)。
+//!
+//! 安装后，目录扫描在项目规则 (`.java-perf/rules`) 之前加载包内规则，检查清单在内置数据上合并包内章节。
+
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::checklist;
+use crate::i18n;
+
+/// 用户级目录的环境变量
+const HOME_ENV: &str = "JAVA_PERF_HOME";
+/// 安装目录名 (位于用户级目录下)
+const PACK_DIR: &str = "rule-pack";
+/// 包清单
+const MANIFEST: &str = "pack.yaml";
+/// 包内检查清单
+const CHECKLIST: &str = "checklist.yaml";
+/// 包内规则目录
+const RULES: &str = "rules";
+/// 下载超时 (秒)
+const DOWNLOAD_TIMEOUT_SECS: u32 = 120;
+/// 下载大小上限
+const MAX_PACK_BYTES: u64 = 64 * 1024 * 1024;
+
+/// 规则包清单 (`pack.yaml`)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Manifest {
+    pub name: String,
+    pub version: String,
+    /// 要求的最低 java-perf 版本，低于该版本时拒绝安装 (需先升级二进制)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_engine: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 规则包来源
+#[derive(Debug, Clone)]
+pub enum Source {
+    /// 下载 `<url>` 与 `<url>.sig`
+    Url(String),
+    /// 本地 zip，签名为 `<zip>.sig`
+    Offline(PathBuf),
+}
+
+/// 校验 (及安装) 结果
+#[derive(Debug, Clone, Serialize)]
+pub struct Installed {
+    #[serde(flatten)]
+    pub manifest: Manifest,
+    /// 脚本规则数 (`rules/*.yaml`)
+    pub rules: usize,
+    /// 是否包含检查清单
+    pub checklist: bool,
+}
+
+/// 用户级目录: `$JAVA_PERF_HOME`，否则 `~/.java-perf`
+pub fn home() -> Option<PathBuf> {
+    std::env::var_os(HOME_ENV)
+        .filter(|v| !v.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(|h| PathBuf::from(h).join(".java-perf")))
+}
+
+/// 已安装规则包的目录
+fn installed_dir() -> Option<PathBuf> {
+    home().map(|h| h.join(PACK_DIR)).filter(|d| d.join(MANIFEST).is_file())
+}
+
+/// 已安装规则包的清单
+pub fn installed() -> Option<Manifest> {
+    read_manifest(&installed_dir()?).ok()
+}
+
+/// 已安装规则包的规则目录
+pub fn installed_rules() -> Option<PathBuf> {
+    installed_dir().map(|d| d.join(RULES)).filter(|d| d.is_dir())
+}
+
+/// 已安装规则包的检查清单
+pub fn installed_checklist() -> Option<PathBuf> {
+    installed_dir().map(|d| d.join(CHECKLIST)).filter(|p| p.is_file())
+}
+
+fn read_manifest(dir: &Path) -> Result<Manifest, String> {
+    let path = dir.join(MANIFEST);
+    let content = std::fs::read_to_string(&path).map_err(|e| format!("{MANIFEST}: {e}"))?;
+    serde_yaml::from_str(&content).map_err(|e| format!("{MANIFEST}: {e}"))
+}
+
+fn decode_hex(s: &str) -> Option<Vec<u8>> {
+    let s = s.trim();
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return None;
+    }
+    (0..s.len()).step_by(2).map(|i| u8::from_str_radix(&s[i..i + 2], 16).ok()).collect()
+}
+
+/// 解析受信任公钥 (32 字节 Ed25519 公钥的十六进制)
+pub fn parse_key(hex: &str) -> Result<VerifyingKey, String> {
+    let bytes: [u8; 32] = decode_hex(hex)
+        .and_then(|b| b.try_into().ok())
+        .ok_or_else(|| format!("invalid rule pack key '{hex}': expected 64 hex characters"))?;
+    VerifyingKey::from_bytes(&bytes).map_err(|e| format!("invalid rule pack key '{hex}': {e}"))
+}
+
+/// 签名由任一受信任公钥验证通过时返回 Ok
+pub fn verify_signature(pack: &[u8], signature: &str, keys: &[VerifyingKey]) -> Result<(), String> {
+    let bytes: [u8; 64] = decode_hex(signature)
+        .and_then(|b| b.try_into().ok())
+        .ok_or("invalid rule pack signature: expected 128 hex characters")?;
+    let signature = Signature::from_bytes(&bytes);
+    if keys.iter().any(|key| key.verify(pack, &signature).is_ok()) {
+        Ok(())
+    } else {
+        Err("rule pack signature does not match any trusted key".to_string())
+    }
+}
+
+/// 版本号: 点分数字段 + 可选的预发布后缀 (`2026.10.0`、`10.0.0-rc.1`)
+///
+/// 必须完整解析: `v2.0`、`2026.10-beta!` 之类的字符串直接拒绝，不会被截断成更小的版本绕过回滚检查。
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Version {
+    /// 去掉末尾 0 的数字段 (`9.5` 与 `9.5.0` 相等)
+    parts: Vec<u64>,
+    /// 预发布标识 (`rc.1` → [`rc`, `1`])，为空表示正式版
+    pre: Vec<String>,
+}
+
+impl Version {
+    fn parse(v: &str) -> Result<Self, String> {
+        let invalid = || format!("invalid version '{v}': expected numbers separated by '.', optionally followed by -<pre-release>");
+        let (core, pre) = match v.split_once('-') {
+            Some((core, pre)) => (core, Some(pre)),
+            None => (v, None),
+        };
+        let mut parts = core.split('.')
+            .map(|p| if !p.is_empty() && p.bytes().all(|b| b.is_ascii_digit()) { p.parse().ok() } else { None })
+            .collect::<Option<Vec<u64>>>()
+            .ok_or_else(invalid)?;
+        while parts.len() > 1 && parts.last() == Some(&0) {
+            parts.pop();
+        }
+        let pre = match pre {
+            Some(pre) => pre.split('.')
+                .map(|id| (!id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric())).then(|| id.to_string()))
+                .collect::<Option<Vec<_>>>()
+                .ok_or_else(invalid)?,
+            None => Vec::new(),
+        };
+        Ok(Version { parts, pre })
+    }
+}
+
+impl Ord for Version {
+    /// 数字段逐段比较；相同时预发布版低于正式版，预发布标识按 semver 规则比较 (纯数字按数值)
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        use std::cmp::Ordering;
+        let identifier = |a: &String, b: &String| match (a.parse::<u64>(), b.parse::<u64>()) {
+            (Ok(a), Ok(b)) => a.cmp(&b),
+            (Ok(_), Err(_)) => Ordering::Less,
+            (Err(_), Ok(_)) => Ordering::Greater,
+            (Err(_), Err(_)) => a.cmp(b),
+        };
+        self.parts.cmp(&other.parts).then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => self.pre.iter().zip(&other.pre)
+                .map(|(a, b)| identifier(a, b))
+                .find(|o| o.is_ne())
+                .unwrap_or_else(|| self.pre.len().cmp(&other.pre.len())),
+        })
+    }
+}
+
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// 校验签名后解压到 `<home>/rule-pack`；`apply = false` 时只校验不安装
+///
+/// 与已安装包同名但版本更低的规则包即使签名有效也拒绝，防止重放旧包回滚规则；
+/// 名称不同的包只有 `replace = true` (`update --replace`) 时才替换已安装的包。
+pub fn install(pack: &[u8], signature: &str, keys: &[VerifyingKey], home: &Path, apply: bool, replace: bool) -> Result<Installed, String> {
+    verify_signature(pack, signature, keys)?;
+
+    let staging = home.join(format!("{PACK_DIR}.staging"));
+    if staging.exists() {
+        std::fs::remove_dir_all(&staging).map_err(|e| format!("{}: {e}", staging.display()))?;
+    }
+    let checked = extract(pack, &staging)
+        .and_then(|_| validate(&staging))
+        .and_then(|installed| reject_rollback(installed, &home.join(PACK_DIR), replace));
+    let installed = match checked {
+        Ok(installed) if apply => installed,
+        other => {
+            let _ = std::fs::remove_dir_all(&staging);
+            return other;
+        }
+    };
+
+    let target = home.join(PACK_DIR);
+    let previous = home.join(format!("{PACK_DIR}.old"));
+    let _ = std::fs::remove_dir_all(&previous);
+    if target.exists() {
+        std::fs::rename(&target, &previous).map_err(|e| format!("{}: {e}", target.display()))?;
+    }
+    if let Err(e) = std::fs::rename(&staging, &target) {
+        let _ = std::fs::rename(&previous, &target);
+        return Err(format!("{}: {e}", target.display()));
+    }
+    let _ = std::fs::remove_dir_all(&previous);
+    Ok(installed)
+}
+
+/// 解压 zip，拒绝越出目标目录的条目
+fn extract(pack: &[u8], dir: &Path) -> Result<(), String> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(pack)).map_err(|e| format!("invalid rule pack archive: {e}"))?;
+    for i in 0..archive.len() {
+        let mut entry = archive.by_index(i).map_err(|e| format!("invalid rule pack archive: {e}"))?;
+        let rel = entry.enclosed_name().ok_or_else(|| format!("unsafe path in rule pack: {}", entry.name()))?;
+        let path = dir.join(rel);
+        if entry.is_dir() {
+            std::fs::create_dir_all(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+            continue;
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| format!("{}: {e}", parent.display()))?;
+        }
+        let mut file = std::fs::File::create(&path).map_err(|e| format!("{}: {e}", path.display()))?;
+        std::io::copy(&mut entry, &mut file).map_err(|e| format!("{}: {e}", path.display()))?;
+    }
+    Ok(())
+}
+
+/// 校验解压后的规则包
+fn validate(dir: &Path) -> Result<Installed, String> {
+    let manifest = read_manifest(dir)?;
+    if manifest.name.trim().is_empty() || manifest.version.trim().is_empty() {
+        return Err(format!("{MANIFEST}: name and version are required"));
+    }
+    Version::parse(&manifest.version).map_err(|e| format!("{MANIFEST}: {e}"))?;
+    let engine = env!("CARGO_PKG_VERSION");
+    if let Some(min) = &manifest.min_engine {
+        let required = Version::parse(min).map_err(|e| format!("{MANIFEST}: min_engine: {e}"))?;
+        if Version::parse(engine).is_ok_and(|engine| engine < required) {
+            return Err(format!(
+                "rule pack {} {} requires java-perf >= {min} (current {engine}); upgrade the binary first",
+                manifest.name, manifest.version
+            ));
+        }
+    }
+
+    let checklist = dir.join(CHECKLIST);
+    let has_checklist = checklist.is_file();
+    if has_checklist {
+        checklist::validate_extension_file(&checklist)
+            .map_err(|errors| format!("{CHECKLIST}: {}", errors.join("; ")))?;
+    }
+
+    let rules_dir = dir.join(RULES);
+    let rules = if rules_dir.is_dir() { load_rules(&rules_dir)? } else { 0 };
+    Ok(Installed { manifest, rules, checklist: has_checklist })
+}
+
+/// 与 `dir` 中已安装的包比较: 同名且版本更低时返回 Err，名称不同且未确认替换时返回 Err
+///
+/// 已安装包的版本无法解析 (旧版本工具安装) 时不阻止更新。
+fn reject_rollback(installed: Installed, dir: &Path, replace: bool) -> Result<Installed, String> {
+    let Ok(current) = read_manifest(dir) else { return Ok(installed) };
+    let new = &installed.manifest;
+    if new.name != current.name {
+        if replace {
+            return Ok(installed);
+        }
+        return Err(format!(
+            "rule pack {} {} would replace the installed {} {}; pass --replace to confirm",
+            new.name, new.version, current.name, current.version
+        ));
+    }
+    match (Version::parse(&new.version), Version::parse(&current.version)) {
+        (Ok(version), Ok(installed_version)) if version < installed_version => Err(format!(
+            "rule pack {} {} is older than the installed {}; refusing to roll back",
+            new.name, new.version, current.version
+        )),
+        _ => Ok(installed),
+    }
+}
+
+/// 加载规则目录 (确认查询可编译、WASM 模块有效)，返回规则数
+#[cfg(feature = "script-rules")]
+fn load_rules(dir: &Path) -> Result<usize, String> {
+    crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer::builder()
+        .with_scripted_rules(dir)
+        .and_then(|builder| builder.build())
+        .map_err(|e| format!("{RULES}/: {e:#}"))?;
+    count_rule_files(dir)
+}
+
+/// 未启用脚本规则时只统计规则文件 (扫描时忽略包内规则)
+#[cfg(not(feature = "script-rules"))]
+fn load_rules(dir: &Path) -> Result<usize, String> {
+    count_rule_files(dir)
+}
+
+fn count_rule_files(dir: &Path) -> Result<usize, String> {
+    let entries = std::fs::read_dir(dir).map_err(|e| format!("{RULES}/: {e}"))?;
+    Ok(entries
+        .filter_map(|e| e.ok())
+        .filter(|e| matches!(e.path().extension().and_then(|x| x.to_str()), Some("yaml" | "yml")))
+        .count())
+}
+
+/// 用 curl 下载
+fn download(url: &str) -> Result<Vec<u8>, String> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location"])
+        .args(["--max-time", &DOWNLOAD_TIMEOUT_SECS.to_string(), "--max-filesize", &MAX_PACK_BYTES.to_string()])
+        .arg("--")
+        .arg(url)
+        .output()
+        .map_err(|e| format!("curl not available ({e}); download the pack and use --offline"))?;
+    if !output.status.success() {
+        return Err(format!("download {url} failed: {}", String::from_utf8_lossy(&output.stderr).trim()));
+    }
+    Ok(output.stdout)
+}
+
+/// `java-perf update`: 下载或读取规则包，校验签名后安装 (`check` 时只校验)
+pub fn update(source: &Source, keys: &[String], check: bool, replace: bool) -> Result<Value, Box<dyn std::error::Error>> {
+    if keys.is_empty() {
+        return Err("no trusted rule pack key: pass --key or set JAVA_PERF_PACK_KEYS".into());
+    }
+    let keys = keys.iter().map(|k| parse_key(k)).collect::<Result<Vec<_>, _>>()?;
+    let (pack, signature, origin) = match source {
+        Source::Url(url) => {
+            let signature = download(&format!("{url}.sig"))?;
+            (download(url)?, String::from_utf8_lossy(&signature).into_owned(), url.clone())
+        }
+        Source::Offline(path) => {
+            let mut sig_path = path.clone().into_os_string();
+            sig_path.push(".sig");
+            let sig_path = PathBuf::from(sig_path);
+            let signature = std::fs::read_to_string(&sig_path).map_err(|e| format!("{}: {e}", sig_path.display()))?;
+            let pack = std::fs::read(path).map_err(|e| format!("{}: {e}", path.display()))?;
+            (pack, signature, path.display().to_string())
+        }
+    };
+    let home = home().ok_or("cannot determine the java-perf home directory: set JAVA_PERF_HOME")?;
+    std::fs::create_dir_all(&home).map_err(|e| format!("{}: {e}", home.display()))?;
+    let previous = read_manifest(&home.join(PACK_DIR)).ok();
+    let installed = install(&pack, &signature, &keys, &home, !check, replace)?;
+
+    let report = render_report(&installed, previous.as_ref(), &origin, check);
+    Ok(json!({
+        "pack": installed,
+        "previous": previous,
+        "source": origin,
+        "installed": !check,
+        "path": home.join(PACK_DIR).display().to_string(),
+        "report": report,
+    }))
+}
+
+fn render_report(installed: &Installed, previous: Option<&Manifest>, origin: &str, check: bool) -> String {
+    let manifest = &installed.manifest;
+    let mut out = i18n::text("report.update.title", "## 📦 规则包更新").to_string();
+    out.push_str("\n\n");
+    let status = if check {
+        i18n::text("report.update.verified", "✅ 签名与内容校验通过 (未安装)")
+    } else {
+        i18n::text("report.update.installed", "✅ 已安装")
+    };
+    out.push_str(&format!("{status}: **{}** {}\n\n", manifest.name, manifest.version));
+    if let Some(description) = &manifest.description {
+        out.push_str(&format!("{description}\n\n"));
+    }
+    let previous = previous.map_or_else(|| "-".to_string(), |p| format!("{} {}", p.name, p.version));
+    let checklist = if installed.checklist { "✓" } else { "-" };
+    out.push_str(&i18n::format("report.update.details", "- 来源: {source}\n- 之前: {previous}\n- 脚本规则: {rules}\n- 检查清单: {checklist}\n", &[
+        ("source", &origin),
+        ("previous", &previous),
+        ("rules", &installed.rules),
+        ("checklist", &checklist),
+    ]));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{Signer, SigningKey};
+    use std::io::Write;
+
+    const CHECKLIST_YAML: &str = r#"version: 1
+sections:
+  - id: corp-mq
+    title: 公司消息队列
+    priority: P1
+    items:
+      - desc: 消费者并发数与分区数匹配
+symptoms:
+  mq-lag: [corp-mq]
+"#;
+
+    fn pack(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        let options = zip::write::SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in files {
+            zip.start_file(*name, options).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn sign(key: &SigningKey, bytes: &[u8]) -> String {
+        key.sign(bytes).to_bytes().iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    fn hex_key(key: &SigningKey) -> String {
+        key.verifying_key().to_bytes().iter().map(|b| format!("{b:02x}")).collect()
+    }
+
+    #[test]
+    fn test_install_signed_pack() {
+        let home = tempfile::tempdir().unwrap();
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trusted = vec![parse_key(&hex_key(&key)).unwrap()];
+
+        let v1 = pack(&[("pack.yaml", "name: acme\nversion: 2026.9.0\n"), ("checklist.yaml", CHECKLIST_YAML)]);
+        let installed = install(&v1, &sign(&key, &v1), &trusted, home.path(), true, false).unwrap();
+        assert_eq!(installed.manifest.version, "2026.9.0");
+        assert!(installed.checklist);
+        assert_eq!(installed.rules, 0);
+        assert!(home.path().join("rule-pack/checklist.yaml").is_file());
+
+        // 新版本整体替换旧目录
+        let v2 = pack(&[("pack.yaml", "name: acme\nversion: 2026.10.0\ndescription: October rules\n")]);
+        install(&v2, &sign(&key, &v2), &trusted, home.path(), true, false).unwrap();
+        assert_eq!(read_manifest(&home.path().join("rule-pack")).unwrap().version, "2026.10.0");
+        assert!(!home.path().join("rule-pack/checklist.yaml").exists());
+        assert!(!home.path().join("rule-pack.staging").exists());
+
+        // 只校验时不改变已安装的包
+        let v3 = pack(&[("pack.yaml", "name: acme\nversion: 2026.11.0\n")]);
+        assert_eq!(install(&v3, &sign(&key, &v3), &trusted, home.path(), false, false).unwrap().manifest.version, "2026.11.0");
+        assert_eq!(read_manifest(&home.path().join("rule-pack")).unwrap().version, "2026.10.0");
+    }
+
+    #[test]
+    fn test_reject_untrusted_or_invalid_pack() {
+        let home = tempfile::tempdir().unwrap();
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trusted = vec![parse_key(&hex_key(&key)).unwrap()];
+        let good = pack(&[("pack.yaml", "name: acme\nversion: 1.0\n")]);
+        install(&good, &sign(&key, &good), &trusted, home.path(), true, false).unwrap();
+
+        // 篡改内容或使用不受信任的密钥签名
+        let mut tampered = good.clone();
+        *tampered.last_mut().unwrap() ^= 1;
+        let err = install(&tampered, &sign(&key, &good), &trusted, home.path(), true, false).unwrap_err();
+        assert!(err.contains("does not match any trusted key"), "{err}");
+        let other = SigningKey::from_bytes(&[9; 32]);
+        assert!(install(&good, &sign(&other, &good), &trusted, home.path(), true, false).is_err());
+
+        // 签名有效但内容无效: 路径越界、版本要求过高、检查清单错误
+        for (files, expected) in [
+            (vec![("pack.yaml", "name: acme\nversion: 2.0\n"), ("../evil.yaml", "x")], "unsafe path"),
+            (vec![("pack.yaml", "name: acme\nversion: 2.0\nmin_engine: 99.0\n")], "requires java-perf >= 99.0"),
+            (vec![("pack.yaml", "name: acme\nversion: 2.0\n"), ("checklist.yaml", "version: 1\nsections: []\nsymptoms:\n  mq-lag: [missing]\n")], "checklist.yaml"),
+        ] {
+            let bytes = pack(&files);
+            let err = install(&bytes, &sign(&key, &bytes), &trusted, home.path(), true, false).unwrap_err();
+            assert!(err.contains(expected), "{err}");
+        }
+        assert!(!home.path().join("evil.yaml").exists());
+
+        // 签名有效的旧版本 (重放) 不能回滚已安装的包，只校验时同样拒绝
+        let old = pack(&[("pack.yaml", "name: acme\nversion: 0.9\n")]);
+        for apply in [true, false] {
+            let err = install(&old, &sign(&key, &old), &trusted, home.path(), apply, false).unwrap_err();
+            assert!(err.contains("older than the installed 1.0"), "{err}");
+        }
+
+        // 版本号必须完整解析: 前缀或非法后缀不会被截断成更低的版本
+        for version in ["v2.0", "2026.10-beta!", "2.0.", "1..0", ""] {
+            let bytes = pack(&[("pack.yaml", &format!("name: acme\nversion: \"{version}\"\n"))]);
+            let err = install(&bytes, &sign(&key, &bytes), &trusted, home.path(), true, false).unwrap_err();
+            assert!(err.contains("pack.yaml"), "{version}: {err}");
+        }
+        let bad_min = pack(&[("pack.yaml", "name: acme\nversion: 2.0\nmin_engine: latest\n")]);
+        assert!(install(&bad_min, &sign(&key, &bad_min), &trusted, home.path(), true, false).unwrap_err().contains("min_engine"));
+        // 失败不影响已安装的包
+        assert_eq!(read_manifest(&home.path().join("rule-pack")).unwrap().version, "1.0");
+    }
+
+    #[test]
+    fn test_pack_name_change_requires_replace() {
+        let home = tempfile::tempdir().unwrap();
+        let key = SigningKey::from_bytes(&[7; 32]);
+        let trusted = vec![parse_key(&hex_key(&key)).unwrap()];
+        let acme = pack(&[("pack.yaml", "name: acme\nversion: 2026.10.0\n")]);
+        install(&acme, &sign(&key, &acme), &trusted, home.path(), true, false).unwrap();
+
+        // 不同名称的包不参与版本比较，但替换需要显式确认
+        let other = pack(&[("pack.yaml", "name: globex\nversion: 1.0\n")]);
+        let err = install(&other, &sign(&key, &other), &trusted, home.path(), true, false).unwrap_err();
+        assert!(err.contains("pass --replace"), "{err}");
+        assert_eq!(read_manifest(&home.path().join("rule-pack")).unwrap().name, "acme");
+        install(&other, &sign(&key, &other), &trusted, home.path(), true, true).unwrap();
+        assert_eq!(read_manifest(&home.path().join("rule-pack")).unwrap().name, "globex");
+        // 同名包的版本比较不受 --replace 影响
+        let old = pack(&[("pack.yaml", "name: globex\nversion: 0.9\n")]);
+        assert!(install(&old, &sign(&key, &old), &trusted, home.path(), true, true).unwrap_err().contains("roll back"));
+    }
+
+    #[test]
+    fn test_keys_and_versions() {
+        assert!(parse_key("abc").is_err());
+        assert!(parse_key(&"zz".repeat(32)).is_err());
+        let v = |s: &str| Version::parse(s).unwrap();
+        assert!(v("9.5.0") < v("9.10"));
+        assert_eq!(v("9.5.0"), v("9.5"));
+        assert!(v("9.5.0") < v("10.0.0-rc1"));
+        assert!(v("10.0.0-rc.2") < v("10.0.0-rc.10"));
+        assert!(v("10.0.0-rc.10") < v("10.0.0"));
+        assert!(Version::parse("v2.0").is_err() && Version::parse("2.0-").is_err() && Version::parse("2.x").is_err());
+    }
+}
@@ -0,0 +1,413 @@
+//! 依赖公告库 (Dependency Advisories) - v9.6
+//!
+//! 内置离线公告库 `resources/advisories.json`，把已知的依赖版本映射到性能/安全公告
+//! (旧版 Jackson Afterburner、Log4j 1.x 同步 Appender、Hibernate 6.0/6.1 的 N+1 回退等)。
+//! 目录扫描时解析项目中所有 `pom.xml` / `build.gradle(.kts)` 的主依赖，命中的版本报告为：
+//! - `DEPENDENCY_PERF_ADVISORY`: 性能公告
+//! - `DEPENDENCY_VULN_ADVISORY`: 安全公告
+//!
+//! 级别取自公告。公告库可离线更新：项目中的 `.java-perf/advisories.json` (同样格式) 与内置库合并，
+//! `id` 相同的条目覆盖内置条目。
+//!
+//! 版本范围: `*`、`<2.15.0`、`>=2.0-beta9,<2.17.1` (逗号表示同时满足)；
+//! Maven `${prop}` 从 `<properties>` 解析，Gradle `$prop` 从同文件赋值与 `gradle.properties` 解析，无法解析的跳过。
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n::{self, Lang};
+use crate::project_detector;
+use crate::rules::effort;
+use crate::scanner::config::BUILD_OUTPUT_DIRS;
+
+/// 性能公告规则
+pub const PERF_RULE_ID: &str = "DEPENDENCY_PERF_ADVISORY";
+/// 安全公告规则
+pub const VULN_RULE_ID: &str = "DEPENDENCY_VULN_ADVISORY";
+
+/// 项目级公告库 (与内置库合并)
+pub const PROJECT_DB: &str = ".java-perf/advisories.json";
+
+static BUILTIN: Lazy<AdvisoryDb> = Lazy::new(|| {
+    serde_json::from_str(include_str!("../../resources/advisories.json")).expect("invalid built-in advisory database")
+});
+
+/// Maven `<properties>` 中的 `<name>value</name>`
+static RE_MAVEN_PROPERTY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"<([\w.\-]+)>\s*([^<\s]+)\s*</([\w.\-]+)>").unwrap()
+});
+
+/// Gradle `name = 'value'` / `ext.name = "value"` / `set('name', 'value')`
+static RE_GRADLE_PROPERTY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?m)^\s*(?:ext\.|val\s+|def\s+)?(\w+)\s*=\s*['"]([^'"$]+)['"]|set\(\s*['"](\w+)['"]\s*,\s*['"]([^'"$]+)['"]\s*\)"#).unwrap()
+});
+
+/// 公告类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AdvisoryKind {
+    Performance,
+    Security,
+}
+
+/// 一条公告
+#[derive(Debug, Clone, Deserialize)]
+pub struct Advisory {
+    pub id: String,
+    pub kind: AdvisoryKind,
+    pub severity: Severity,
+    pub group: String,
+    pub artifact: String,
+    /// 受影响版本范围
+    pub affected: String,
+    pub summary: String,
+    #[serde(default)]
+    pub summary_en: Option<String>,
+    pub fix: String,
+    #[serde(default)]
+    pub fix_en: Option<String>,
+}
+
+impl Advisory {
+    fn rule_id(&self) -> &'static str {
+        match self.kind {
+            AdvisoryKind::Performance => PERF_RULE_ID,
+            AdvisoryKind::Security => VULN_RULE_ID,
+        }
+    }
+
+    /// 当前输出语言的说明与修复建议
+    fn text(&self) -> String {
+        match (i18n::lang(), &self.summary_en, &self.fix_en) {
+            (Lang::En, Some(summary), Some(fix)) => format!("{summary}; {fix}"),
+            _ => format!("{}；{}", self.summary, self.fix),
+        }
+    }
+}
+
+/// 公告库
+#[derive(Debug, Clone, Deserialize)]
+pub struct AdvisoryDb {
+    pub version: String,
+    pub advisories: Vec<Advisory>,
+}
+
+impl AdvisoryDb {
+    /// 内置库
+    pub fn builtin() -> Self {
+        BUILTIN.clone()
+    }
+
+    /// 内置库合并项目级 `.java-perf/advisories.json` (解析失败时忽略项目库)
+    pub fn load(root: &Path) -> Self {
+        let mut db = Self::builtin();
+        let Some(project) = std::fs::read_to_string(root.join(PROJECT_DB)).ok()
+            .and_then(|content| serde_json::from_str::<AdvisoryDb>(&content).ok())
+        else {
+            return db;
+        };
+        for advisory in project.advisories {
+            match db.advisories.iter_mut().find(|a| a.id == advisory.id) {
+                Some(existing) => *existing = advisory,
+                None => db.advisories.push(advisory),
+            }
+        }
+        db.version = format!("{}+{}", db.version, project.version);
+        db
+    }
+
+    /// 命中某依赖版本的公告
+    pub fn matching<'a>(&'a self, group: &'a str, artifact: &'a str, version: &'a str) -> impl Iterator<Item = &'a Advisory> + 'a {
+        self.advisories.iter().filter(move |a| a.group == group && a.artifact == artifact && in_range(version, &a.affected))
+    }
+}
+
+/// 版本号的一段
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum Part {
+    /// 预发布限定符 (alpha/beta/rc/M1/SNAPSHOT)，低于同位置的正式版本
+    Pre(String),
+    Num(u64),
+}
+
+fn parts(version: &str) -> Vec<Part> {
+    let mut parts: Vec<Part> = Vec::new();
+    for token in version.split(['.', '-', '_']).filter(|t| !t.is_empty()) {
+        let digits = token.len() - token.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+        if digits > 0 {
+            parts.push(Part::Num(token[..digits].parse().unwrap_or(u64::MAX)));
+            // `0beta9` / `1rc1` 形式
+            if digits < token.len() {
+                parts.push(Part::Pre(token[digits..].to_ascii_lowercase()));
+            }
+        } else {
+            let lower = token.to_ascii_lowercase();
+            // Final / GA / RELEASE 等同正式版本
+            if !["final", "ga", "release"].contains(&lower.as_str()) {
+                parts.push(Part::Pre(lower));
+            }
+        }
+    }
+    parts
+}
+
+/// 比较两个版本号 (缺少的数字段视为 0，预发布低于正式版本)
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let (a, b) = (parts(a), parts(b));
+    for i in 0..a.len().max(b.len()) {
+        let ordering = match (a.get(i), b.get(i)) {
+            (Some(x), Some(y)) => x.cmp(y),
+            (Some(Part::Num(n)), None) => n.cmp(&0),
+            (None, Some(Part::Num(n))) => 0.cmp(n),
+            (Some(Part::Pre(_)), None) => Ordering::Less,
+            (None, Some(Part::Pre(_))) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+/// 版本是否落在范围内 (`*` / 逗号分隔的 `<`、`<=`、`>`、`>=`、`=` 条件)
+pub fn in_range(version: &str, range: &str) -> bool {
+    range.split(',').map(str::trim).filter(|c| !c.is_empty()).all(|condition| {
+        if condition == "*" {
+            return true;
+        }
+        let (op, bound) = ["<=", ">=", "<", ">", "="].iter()
+            .find_map(|op| condition.strip_prefix(op).map(|rest| (*op, rest.trim())))
+            .unwrap_or(("=", condition));
+        let ordering = compare_versions(version, bound);
+        match op {
+            "<" => ordering == Ordering::Less,
+            "<=" => ordering != Ordering::Greater,
+            ">" => ordering == Ordering::Greater,
+            ">=" => ordering != Ordering::Less,
+            _ => ordering == Ordering::Equal,
+        }
+    })
+}
+
+/// 构建文件中声明的一个依赖版本
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeclaredDependency {
+    pub group: String,
+    pub artifact: String,
+    pub version: String,
+    /// 构建文件 (相对扫描根目录) 与行号
+    pub file: String,
+    pub line: usize,
+}
+
+fn build_files(root: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !e.file_type().is_dir() || !BUILD_OUTPUT_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| ["pom.xml", "build.gradle", "build.gradle.kts"].contains(&e.file_name().to_string_lossy().as_ref()))
+        .map(|e| e.into_path())
+        .collect();
+    files.sort();
+    files
+}
+
+/// 解析 `${name}` / `$name` 引用
+fn resolve(version: &str, properties: &HashMap<String, String>) -> Option<String> {
+    let name = version.strip_prefix("${").and_then(|v| v.strip_suffix('}'))
+        .or_else(|| version.strip_prefix('$'));
+    match name {
+        Some(name) => properties.get(name).cloned(),
+        None => Some(version.to_string()),
+    }
+}
+
+/// 依赖声明所在行 (找不到时为 1)
+fn line_of(content: &str, needles: &[String]) -> usize {
+    content.lines().position(|line| needles.iter().any(|n| line.contains(n.as_str()))).map_or(1, |i| i + 1)
+}
+
+fn maven_dependencies(content: &str, file: &str) -> Vec<DeclaredDependency> {
+    let Ok(deps) = project_detector::parse_maven_pom(content) else { return Vec::new() };
+    let properties: HashMap<String, String> = content.find("<properties>")
+        .zip(content.find("</properties>"))
+        .map(|(start, end)| &content[start..end.max(start)])
+        .map(|block| RE_MAVEN_PROPERTY.captures_iter(block)
+            .filter(|caps| caps[1] == caps[3])
+            .map(|caps| (caps[1].to_string(), caps[2].to_string()))
+            .collect())
+        .unwrap_or_default();
+    project_detector::filter_main_dependencies(&deps).into_iter()
+        .filter_map(|dep| {
+            let version = resolve(dep.version.as_deref()?, &properties)?;
+            Some(DeclaredDependency {
+                line: line_of(content, &[format!("<artifactId>{}</artifactId>", dep.artifact_id)]),
+                group: dep.group_id.clone(),
+                artifact: dep.artifact_id.clone(),
+                version,
+                file: file.to_string(),
+            })
+        })
+        .collect()
+}
+
+fn gradle_dependencies(content: &str, file: &str, gradle_properties: &str) -> Vec<DeclaredDependency> {
+    let Ok(deps) = project_detector::parse_gradle_build(content) else { return Vec::new() };
+    let mut properties: HashMap<String, String> = gradle_properties.lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(k, v)| (k.trim().to_string(), v.trim().to_string()))
+        .collect();
+    for caps in RE_GRADLE_PROPERTY.captures_iter(content) {
+        let (name, value) = match (caps.get(1), caps.get(2)) {
+            (Some(name), Some(value)) => (name, value),
+            _ => (caps.get(3).unwrap(), caps.get(4).unwrap()),
+        };
+        properties.insert(name.as_str().to_string(), value.as_str().to_string());
+    }
+    project_detector::filter_main_gradle_dependencies(&deps).into_iter()
+        .filter_map(|dep| {
+            let version = resolve(dep.version.as_deref()?, &properties)?;
+            Some(DeclaredDependency {
+                line: line_of(content, &[format!("{}:{}", dep.group, dep.name)]),
+                group: dep.group.clone(),
+                artifact: dep.name.clone(),
+                version,
+                file: file.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// 项目中所有构建文件声明的主依赖 (测试范围除外，版本无法确定的跳过)
+pub fn declared_dependencies(root: &Path) -> Vec<DeclaredDependency> {
+    build_files(root).into_iter().flat_map(|path| {
+        let Ok(content) = std::fs::read_to_string(&path) else { return Vec::new() };
+        let rel = path.strip_prefix(root).unwrap_or(&path).to_string_lossy().replace('\\', "/");
+        if path.file_name().is_some_and(|n| n == "pom.xml") {
+            return maven_dependencies(&content, &rel);
+        }
+        // gradle.properties 在模块目录或根目录
+        let gradle_properties = path.parent()
+            .into_iter()
+            .chain([root])
+            .find_map(|dir| std::fs::read_to_string(dir.join("gradle.properties")).ok())
+            .unwrap_or_default();
+        gradle_dependencies(&content, &rel, &gradle_properties)
+    }).collect()
+}
+
+/// 报告命中公告的依赖，返回新增问题数
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path) -> usize {
+    let dependencies = declared_dependencies(root);
+    if dependencies.is_empty() {
+        return 0;
+    }
+    let db = AdvisoryDb::load(root);
+    let before = issues.len();
+    for dep in &dependencies {
+        for advisory in db.matching(&dep.group, &dep.artifact, &dep.version) {
+            let rule_id = advisory.rule_id();
+            issues.push(AstIssue {
+                severity: advisory.severity,
+                issue_type: rule_id.to_string(),
+                file: dep.file.clone(),
+                line: dep.line,
+                column: 1,
+                end_line: dep.line,
+                end_column: 1,
+                description: format!(
+                    "{} [公告 {}: {}:{}:{} ∈ {}]",
+                    advisory.text(), advisory.id, dep.group, dep.artifact, dep.version, advisory.affected,
+                ),
+                owner: None,
+                effort: effort::builtin(rule_id),
+            });
+        }
+    }
+    issues.len() - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_ranges() {
+        assert_eq!(compare_versions("2.9.10", "2.15.0"), Ordering::Less);
+        assert_eq!(compare_versions("2.15", "2.15.0"), Ordering::Equal);
+        assert_eq!(compare_versions("4.1.100.Final", "4.1.100"), Ordering::Equal);
+        assert_eq!(compare_versions("2.0-beta9", "2.0"), Ordering::Less);
+        assert_eq!(compare_versions("6.2.0.CR1", "6.2.0"), Ordering::Less);
+        assert!(in_range("2.14.1", "<2.15.0"));
+        assert!(!in_range("2.15.0", "<2.15.0"));
+        assert!(in_range("2.0-rc1", ">=2.0-beta9,<2.17.1"));
+        assert!(!in_range("2.0-beta8", ">=2.0-beta9,<2.17.1"));
+        assert!(in_range("1.2.17", "*"));
+        assert!(AdvisoryDb::builtin().advisories.len() >= 8);
+    }
+
+    #[test]
+    fn test_maven_and_gradle_matches() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("pom.xml"), r#"<project>
+  <properties>
+    <jackson.version>2.13.4</jackson.version>
+  </properties>
+  <dependencies>
+    <dependency>
+      <groupId>com.fasterxml.jackson.core</groupId>
+      <artifactId>jackson-core</artifactId>
+      <version>${jackson.version}</version>
+    </dependency>
+    <dependency>
+      <groupId>log4j</groupId>
+      <artifactId>log4j</artifactId>
+      <version>1.2.17</version>
+      <scope>test</scope>
+    </dependency>
+  </dependencies>
+</project>
+"#).unwrap();
+        let module = dir.path().join("orders");
+        std::fs::create_dir_all(&module).unwrap();
+        std::fs::write(module.join("build.gradle"), "def hibernateVersion = '6.1.7.Final'\n\ndependencies {\n    implementation \"org.hibernate.orm:hibernate-core:$hibernateVersion\"\n    implementation 'org.yaml:snakeyaml:2.2'\n}\n").unwrap();
+
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, dir.path()), 2);
+        let found: Vec<(&str, &str, usize, Severity)> = issues.iter()
+            .map(|i| (i.issue_type.as_str(), i.file.as_str(), i.line, i.severity))
+            .collect();
+        assert_eq!(found, vec![
+            (PERF_RULE_ID, "orders/build.gradle", 4, Severity::P1),
+            (VULN_RULE_ID, "pom.xml", 8, Severity::P1),
+        ]);
+        assert!(issues[1].description.ends_with("[公告 JACKSON_CORE_NO_READ_CONSTRAINTS: com.fasterxml.jackson.core:jackson-core:2.13.4 ∈ <2.15.0]"), "{}", issues[1].description);
+    }
+
+    #[test]
+    fn test_project_database_overrides_builtin() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join(".java-perf")).unwrap();
+        std::fs::write(dir.path().join(PROJECT_DB), r#"{
+  "version": "acme-1",
+  "advisories": [
+    { "id": "HTTPCLIENT4_SMALL_POOL_DEFAULTS", "kind": "performance", "severity": "P1", "group": "org.apache.httpcomponents",
+      "artifact": "httpclient", "affected": "<4.5.0", "summary": "内部公告", "fix": "升级" },
+    { "id": "ACME_CLIENT_SLOW", "kind": "performance", "severity": "P2", "group": "com.acme", "artifact": "client",
+      "affected": "<3.0", "summary": "旧客户端无连接复用", "fix": "升级到 3.x" }
+  ]
+}"#).unwrap();
+        let db = AdvisoryDb::load(dir.path());
+        assert!(db.version.ends_with("+acme-1"));
+        assert_eq!(db.matching("org.apache.httpcomponents", "httpclient", "4.5.13").count(), 0);
+        assert_eq!(db.matching("com.acme", "client", "2.9").next().map(|a| a.id.as_str()), Some("ACME_CLIENT_SLOW"));
+    }
+}
@@ -0,0 +1,206 @@
+//! 批量写入检测 (Batch Insert) - v9.6
+//!
+//! `N_PLUS_ONE` 命中循环内的 `save()` / `insert()` 时，再检查项目配置是否开启了 JDBC 批处理：
+//! - JPA/Hibernate: `spring.jpa.properties.hibernate.jdbc.batch_size` (> 1)
+//! - MyBatis: `mybatis.executor-type` / `mybatis.configuration.default-executor-type` 为 `BATCH`
+//!
+//! 未开启时把该 N+1 问题替换为合并问题 [`RULE_ID`]，描述中同时给出代码位置与配置位置；
+//! 已开启时保留 N+1，并在描述末尾注明 `[JDBC 批处理: 文件:行]`。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::ast_engine::AstIssue;
+use crate::i18n;
+use crate::scanner::config::{self, PropertyEntry};
+
+/// 合并后的问题 ID
+pub const RULE_ID: &str = "SAVE_IN_LOOP_NO_BATCH";
+
+const DESCRIPTION: &str = "循环内逐条 save()/insert() 且未开启 JDBC 批处理：每条记录一次数据库往返，应改为 saveAll + hibernate.jdbc.batch_size (或 MyBatis BATCH 执行器 / JdbcTemplate.batchUpdate)";
+
+/// 逐条写入方法
+const WRITE_METHODS: &[&str] = &["save", "saveAndFlush", "persist", "merge", "insert", "insertSelective"];
+
+/// 批处理配置键 (展开后 key 的末级)
+const BATCH_SIZE_KEY: &str = "batch_size";
+const EXECUTOR_TYPE_KEYS: &[&str] = &["executor-type", "default-executor-type", "executorType", "defaultExecutorType"];
+
+/// 配置中的一处批处理设置
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Setting {
+    file: String,
+    line: usize,
+    key: String,
+    value: String,
+    enabled: bool,
+}
+
+/// 项目中的 Spring 配置文件与批处理设置 (跳过测试资源与构建产物)
+#[derive(Debug, Default)]
+struct BatchConfig {
+    files: Vec<String>,
+    settings: Vec<Setting>,
+}
+
+impl BatchConfig {
+    /// 扫描 `application*` / `bootstrap*` 配置
+    fn scan(root: &Path) -> Self {
+        let mut config = BatchConfig::default();
+        for (file, entries) in config::spring_config_files(root) {
+            config.settings.extend(entries.iter().filter_map(|entry| Self::setting(entry, &file)));
+            config.files.push(file);
+        }
+        config
+    }
+
+    /// 批处理配置项: `*.batch_size` / `*.executor-type` 等
+    fn setting(entry: &PropertyEntry, file: &str) -> Option<Setting> {
+        let last = entry.key.rsplit('.').next().unwrap_or(&entry.key);
+        let enabled = if last == BATCH_SIZE_KEY {
+            entry.value.parse::<u32>().is_ok_and(|size| size > 1)
+        } else if EXECUTOR_TYPE_KEYS.contains(&last) {
+            entry.value.eq_ignore_ascii_case("batch")
+        } else {
+            return None;
+        };
+        Some(Setting { file: file.to_string(), line: entry.line, key: entry.key.clone(), value: entry.value.clone(), enabled })
+    }
+
+    /// 问题描述中的配置位置
+    fn location(&self) -> String {
+        if let Some(setting) = self.settings.first() {
+            return format!("{}:{} `{}={}`", setting.file, setting.line, setting.key, setting.value);
+        }
+        match self.files.first() {
+            Some(file) => i18n::format("note.jdbc_batch.unset", "{file} 未设置 hibernate.jdbc.batch_size", &[("file", file)]),
+            None => i18n::text("note.jdbc_batch.no_config", "未找到 application.yml / application.properties").to_string(),
+        }
+    }
+}
+
+/// 源码行中的逐条写入调用 (`repo.save(` → `save`)
+fn write_call(line: &str) -> Option<&'static str> {
+    WRITE_METHODS.iter().copied().find(|method| line.contains(&format!(".{method}(")))
+}
+
+/// 合并循环内的逐条写入与批处理配置，返回替换为 [`RULE_ID`] 的数量
+///
+/// `issue.file` 为相对 `root` 的路径 (目录扫描的显示路径)。
+pub fn apply(issues: &mut [AstIssue], root: &Path) -> usize {
+    let mut sources: HashMap<String, Option<String>> = HashMap::new();
+    let mut writes = Vec::new();
+    for (idx, issue) in issues.iter().enumerate().filter(|(_, i)| i.issue_type == "N_PLUS_ONE") {
+        let code = sources.entry(issue.file.clone())
+            .or_insert_with(|| std::fs::read_to_string(root.join(&issue.file)).ok());
+        let method = code.as_deref()
+            .and_then(|code| code.lines().nth(issue.line.saturating_sub(1)))
+            .and_then(write_call);
+        if let Some(method) = method {
+            writes.push((idx, method));
+        }
+    }
+    if writes.is_empty() {
+        return 0;
+    }
+
+    let config = BatchConfig::scan(root);
+    let enabled = config.settings.iter().find(|s| s.enabled);
+    let mut replaced = 0;
+    for (idx, method) in writes {
+        let issue = &mut issues[idx];
+        match enabled {
+            Some(setting) => {
+                let location = format!("{}:{}", setting.file, setting.line);
+                let note = i18n::format("note.jdbc_batch", "[JDBC 批处理: {location}]", &[("location", &location)]);
+                issue.description = format!("{} {note}", issue.description);
+            }
+            None => {
+                issue.issue_type = RULE_ID.to_string();
+                let code = format!("{method}() @ {}:{}", issue.file, issue.line);
+                let note = i18n::format(
+                    "note.jdbc_batch.missing", "[代码: {code}; 配置: {config}]",
+                    &[("code", &code), ("config", &config.location())],
+                );
+                issue.description = format!("{} {note}", i18n::text(&format!("rule.{RULE_ID}"), DESCRIPTION));
+                replaced += 1;
+            }
+        }
+    }
+    replaced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(file: &str, line: usize) -> AstIssue {
+        AstIssue {
+            severity: Severity::P0,
+            issue_type: "N_PLUS_ONE".to_string(),
+            file: file.to_string(),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    fn project(config: Option<&str>) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let java = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&java).unwrap();
+        std::fs::write(java.join("ImportService.java"), "class ImportService {\n  void run(List<Row> rows) {\n    for (Row r : rows) {\n      orderRepository.save(r.toOrder());\n      auditClient.send(r);\n    }\n  }\n}\n").unwrap();
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        if let Some(config) = config {
+            std::fs::write(resources.join("application.yml"), config).unwrap();
+        }
+        // 构建产物与测试资源中的配置不计入
+        std::fs::create_dir_all(dir.path().join("target/classes")).unwrap();
+        std::fs::write(dir.path().join("target/classes/application.yml"), "hibernate:\n  jdbc:\n    batch_size: 50\n").unwrap();
+        std::fs::create_dir_all(dir.path().join("src/test/resources")).unwrap();
+        std::fs::write(dir.path().join("src/test/resources/application.properties"), "spring.jpa.properties.hibernate.jdbc.batch_size=50\n").unwrap();
+        dir
+    }
+
+    const FILE: &str = "src/main/java/com/acme/ImportService.java";
+
+    #[test]
+    fn test_save_in_loop_without_batching() {
+        let dir = project(Some("spring:\n  jpa:\n    properties:\n      hibernate:\n        jdbc:\n          batch_size: 1\n"));
+        let mut issues = vec![issue(FILE, 4), issue(FILE, 5)];
+        assert_eq!(apply(&mut issues, dir.path()), 1);
+
+        assert_eq!(issues[0].issue_type, RULE_ID);
+        assert!(issues[0].description.ends_with(&format!(
+            "[代码: save() @ {FILE}:4; 配置: src/main/resources/application.yml:6 `spring.jpa.properties.hibernate.jdbc.batch_size=1`]"
+        )));
+        // 非写入调用保持 N+1
+        assert_eq!(issues[1].issue_type, "N_PLUS_ONE");
+        assert_eq!(issues[1].description, "desc");
+
+        let dir = project(None);
+        let mut issues = vec![issue(FILE, 4)];
+        apply(&mut issues, dir.path());
+        assert!(issues[0].description.ends_with("配置: 未找到 application.yml / application.properties]"));
+    }
+
+    #[test]
+    fn test_batching_configured() {
+        let dir = project(Some("mybatis:\n  configuration:\n    default-executor-type: BATCH\n"));
+        let mut issues = vec![issue(FILE, 4)];
+        assert_eq!(apply(&mut issues, dir.path()), 0);
+        assert_eq!(issues[0].issue_type, "N_PLUS_ONE");
+        assert_eq!(issues[0].description, "desc [JDBC 批处理: src/main/resources/application.yml:3]");
+
+        let entries = config::property_entries("spring.jpa.properties.hibernate.jdbc.batch_size=30\n# batch_size=1\n", false);
+        let settings: Vec<_> = entries.iter().filter_map(|e| BatchConfig::setting(e, "application.properties")).collect();
+        assert_eq!(settings.len(), 1);
+        assert!(settings[0].enabled);
+    }
+}
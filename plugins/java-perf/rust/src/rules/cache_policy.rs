@@ -0,0 +1,93 @@
+//! 缓存策略关联 (Cache Policy) - v9.6
+//!
+//! `CACHE_NO_EXPIRE` 在构建器调用链上检查过期与容量设置；本模块在扫描后检查 Spring 缓存管理器配置：
+//! `spring.cache.caffeine.spec` 设置了过期或容量上限时，Spring Boot 以该规格创建 `CaffeineCacheManager`
+//! 的缓存 (优先于 `Caffeine` Bean)。调用链末尾没有 `build()` 的 `Caffeine.newBuilder()` 是交给
+//! CacheManager 的构建器，由该规格决定策略，不再报告；自行 `build()` 的缓存与 Guava `CacheBuilder` 不受影响。
+
+use std::path::Path;
+
+use crate::ast_engine::AstIssue;
+use crate::scanner::config;
+use crate::scanner::rule_handlers::cache_chain_label;
+
+/// 受影响的规则
+pub const RULE_ID: &str = "CACHE_NO_EXPIRE";
+
+/// Spring Boot Caffeine 缓存规格
+const SPEC_KEY: &str = "spring.cache.caffeine.spec";
+
+/// 规格中的过期 / 容量项
+const SPEC_POLICIES: &[&str] = &["expireAfterWrite", "expireAfterAccess", "maximumSize", "maximumWeight"];
+
+/// 设置了过期或容量上限的 `spring.cache.caffeine.spec` 位置 (`文件:行`)
+pub fn caffeine_spec(root: &Path) -> Option<String> {
+    config::spring_config_files(root).into_iter().find_map(|(file, entries)| {
+        entries
+            .iter()
+            .find(|entry| config::normalize_key(&entry.key) == SPEC_KEY && SPEC_POLICIES.iter().any(|p| entry.value.contains(p)))
+            .map(|entry| format!("{file}:{}", entry.line))
+    })
+}
+
+/// 问题描述中的调用链是否为交给 CacheManager 的 Caffeine 构建器 (未 `build()`)
+fn managed_builder(description: &str) -> bool {
+    let Some((_, chain)) = description.rsplit_once(&format!("[{}: ", cache_chain_label())) else {
+        return false;
+    };
+    chain.starts_with("Caffeine.") && !chain.contains(".build(") && !chain.contains(".buildAsync(")
+}
+
+/// 存在缓存规格时移除交给 CacheManager 的构建器上的 [`RULE_ID`] 问题，返回移除数量
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path) -> usize {
+    let candidates = |issue: &AstIssue| issue.issue_type == RULE_ID && managed_builder(&issue.description);
+    if !issues.iter().any(candidates) || caffeine_spec(root).is_none() {
+        return 0;
+    }
+    let before = issues.len();
+    issues.retain(|issue| !candidates(issue));
+    before - issues.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(chain: &str) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: RULE_ID.to_string(),
+            file: "src/main/java/com/acme/CacheConfig.java".to_string(),
+            line: 5,
+            column: 1,
+            end_line: 5,
+            end_column: 2,
+            description: format!("desc [{}: {chain}]", cache_chain_label()),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_caffeine_spec_covers_managed_builders() {
+        let dir = tempfile::tempdir().unwrap();
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.yml"), "spring:\n  cache:\n    type: caffeine\n    caffeine:\n      spec: maximumSize=500,expireAfterAccess=600s\n").unwrap();
+        assert_eq!(caffeine_spec(dir.path()).as_deref(), Some("src/main/resources/application.yml:5"));
+
+        let mut issues = vec![
+            issue("Caffeine.newBuilder().recordStats()"),
+            issue("Caffeine.newBuilder().build()"),
+            issue("CacheBuilder.newBuilder()"),
+        ];
+        assert_eq!(apply(&mut issues, dir.path()), 1);
+        assert_eq!(issues.len(), 2);
+
+        std::fs::write(resources.join("application.yml"), "spring:\n  cache:\n    caffeine:\n      spec: recordStats\n").unwrap();
+        assert_eq!(caffeine_spec(dir.path()), None);
+        let mut issues = vec![issue("Caffeine.newBuilder()")];
+        assert_eq!(apply(&mut issues, dir.path()), 0);
+    }
+}
@@ -0,0 +1,250 @@
+//! 规则目录 (Rule Catalog) - v9.6
+//!
+//! `antipatterns` 命令与 MCP `get_antipatterns` 的数据来源：从分析器实际注册的规则
+//! (内置 + 项目的 WASM 脚本规则) 生成，每条规则给出描述、默认/生效级别、分类标签、修复建议，
+//! 以及在当前项目下是否启用：
+//! - 档案 (`--profile`，未指定时从 pom.xml / build.gradle 推断) 关闭的规则组标记为未启用
+//! - 档案升级 (如 reactive 下的阻塞调用) 与 `.java-perf.toml [severity]` 覆盖体现在生效级别上
+//! - 修复工作量含 `.java-perf.toml [effort]` 覆盖 (见 [`crate::rules::effort`])
+//!
+//! 修复建议登记在 [`FIXES`]，新增内置规则时须同时登记，`test_every_builtin_rule_has_fix` 会检查遗漏。
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::ast_engine::{self, ScanOptions, Severity};
+use crate::i18n;
+use crate::rules::effort::{Effort, EffortOverrides};
+use crate::rules::profile::{self, RuleProfile};
+use crate::rules::severity_override::SeverityOverrides;
+use crate::rules::taxonomy::{self, Tag};
+
+/// 规则 → 修复建议 (消息 ID: `fix.<RULE_ID>`)
+pub const FIXES: &[(&str, &str)] = &[
+    // 数据库
+    ("N_PLUS_ONE", "循环外批量查询 (IN 查询 / 批量接口)，再按 Map 取值"),
+    ("N_PLUS_ONE_WHILE", "循环外批量查询 (IN 查询 / 批量接口)，再按 Map 取值"),
+    ("N_PLUS_ONE_FOREACH", "循环外批量查询 (IN 查询 / 批量接口)，再按 Map 取值"),
+    ("SELECT_STAR", "明确列出需要的字段"),
+    ("LIKE_LEADING_WILDCARD", "改为前缀匹配，或使用全文索引 / 搜索引擎"),
+    ("UNBOUNDED_QUERY_RESULT", "接收 Pageable 或加 LIMIT，限制单次返回条数"),
+    ("CONNECTION_NOT_CLOSED", "try-with-resources 获取连接"),
+    ("DATASOURCE_NO_POOL", "使用 HikariCP 等连接池的 DataSource"),
+    ("TRANSACTIONAL_REQUIRES_NEW", "确认确实需要独立事务；嵌套调用会额外占用一个连接"),
+    ("TRANSACTION_SELF_CALL", "把事务方法移到另一个 Bean，或通过注入的代理调用"),
+    ("JPA_BIDIRECTIONAL_JSON_CYCLE", "一端加 @JsonIgnore / @JsonManagedReference，或返回 DTO"),
+    ("FILTER_DB_CALL", "缓存查询结果 (带过期)，或移出请求路径"),
+    ("SQL_STRING_CONCAT", "使用 ? / :name 绑定参数"),
+    ("REDIS_SETNX_NO_EXPIRE", "SET key value NX EX / setIfAbsent(key, value, timeout)"),
+    ("REDIS_JEDIS_SHARED", "每次调用从 JedisPool 借出，或改用 JedisPooled / Lettuce"),
+    ("REDIS_FULL_SCAN_IN_REQUEST", "SCAN / SSCAN 游标分批，或维护有界索引"),
+    ("REDIS_OP_IN_LOOP", "multiGet / MSET，或 executePipelined 批量提交"),
+    ("RETRY_NON_IDEMPOTENT", "只重试幂等操作，或按业务键去重"),
+    // 并发
+    ("UNBOUNDED_POOL", "ThreadPoolExecutor + 有界队列 + 拒绝策略"),
+    ("ASYNC_DEFAULT_POOL", "@Async(\"executorName\") 指定自定义有界线程池"),
+    ("SCHEDULED_FIXED_RATE", "改用 fixedDelay，或加分布式锁防止重叠执行"),
+    ("SCHEDULED_POOL_ZERO_CORE", "核心线程数至少为 1"),
+    ("THREAD_IN_REQUEST_SCOPE", "提交到受管理的线程池 (Spring TaskExecutor)"),
+    ("THREAD_NO_NAME", "设置线程名，或使用带命名的 ThreadFactory"),
+    ("SYNC_METHOD", "缩小到同步代码块，或使用并发容器"),
+    ("SYNC_BLOCK", "缩小锁范围；虚拟线程下改用 ReentrantLock"),
+    ("SLEEP_IN_LOCK", "把 sleep 移出锁，或用 Condition.await 等待"),
+    ("LOCK_METHOD_CALL", "lock() 后紧跟 try，finally 中 unlock()"),
+    ("SEMAPHORE_NO_RELEASE", "acquire() 后紧跟 try，finally 中 release()"),
+    ("LATCH_COUNTDOWN_NOT_IN_FINALLY", "finally 中 countDown()，await 带超时"),
+    ("DOUBLE_CHECKED_LOCKING", "字段声明为 volatile，或使用 Holder 惰性初始化"),
+    ("ATOMIC_SPIN", "高竞争计数改用 LongAdder"),
+    ("VOLATILE_ARRAY", "使用 AtomicReferenceArray / AtomicIntegerArray"),
+    ("SYNCHRONIZED_MAP", "改用 ConcurrentHashMap"),
+    ("SIMPLE_DATE_FORMAT", "改用 DateTimeFormatter (线程安全，可作常量)"),
+    ("RANDOM_SHARED", "改用 ThreadLocalRandom.current()"),
+    ("FUTURE_GET_NO_TIMEOUT", "get(timeout, unit) 并处理 TimeoutException"),
+    ("AWAIT_NO_TIMEOUT", "await / tryAcquire 带超时"),
+    ("COMPLETABLE_JOIN", "orTimeout / completeOnTimeout 后再 join"),
+    ("COMPLETABLE_GET_NO_TIMEOUT", "get(timeout, unit)，或 orTimeout"),
+    ("LISTENER_BLOCKING_CALL", "阻塞调用带超时或异步化，慢处理交给独立线程池"),
+    ("LISTENER_AUTO_ACK_SLOW", "手动 ack，调小单次拉取条数 (max.poll.records / prefetch)"),
+    ("LISTENER_TRANSACTIONAL", "只把写库部分放进事务，缩短连接占用"),
+    // 内存
+    ("STATIC_COLLECTION", "改用带容量上限与过期的 Caffeine / Guava Cache"),
+    ("THREADLOCAL_LEAK", "finally 中 remove()"),
+    ("MDC_LEAK", "finally 中 MDC.remove() / MDC.clear()，或使用 MDC.putCloseable"),
+    ("CACHE_NO_EXPIRE", "配置 maximumSize 与 expireAfterWrite"),
+    ("CACHEABLE_NO_KEY", "显式指定 key (如 key = \"#id\")"),
+    ("STRING_INTERN", "确认必要性，改用应用内的有界去重 Map"),
+    ("LISTENER_UNBOUNDED_BUFFER", "按条数/字节数限制缓冲并及时刷出"),
+    ("LARGE_ARRAY", "分块处理或复用缓冲 (对象池)"),
+    // GC / 分配
+    ("OBJECT_IN_LOOP", "复用对象或移出循环"),
+    ("SOFT_REFERENCE", "改用带容量上限的缓存"),
+    ("FINALIZE_OVERRIDE", "改用 Cleaner 或 try-with-resources"),
+    ("COLLECTION_NO_CAPACITY", "按源集合大小指定初始容量"),
+    ("DATE_IN_LOOP", "循环外读取一次时间"),
+    ("CALENDAR_PER_CALL", "改用 java.time (LocalDate / Instant)"),
+    ("DATE_FORMATTER_IN_LOOP", "提取为 static final 常量"),
+    ("STRING_CONCAT_LOOP", "使用 StringBuilder"),
+    ("SERIALIZER_PER_CALL", "共享单个 ObjectMapper / Gson 实例"),
+    ("STREAM_COLLECT_SIZE", "使用 count()"),
+    // CPU
+    ("NESTED_LOOP", "内层改为 Map / Set 查找"),
+    ("NESTED_LOOP_MIXED", "内层改为 Map / Set 查找"),
+    ("PATTERN_COMPILE_HOT_PATH", "提取为 static final Pattern 常量"),
+    ("LIST_CONTAINS_IN_LOOP", "循环前转为 HashSet"),
+    ("LINKEDLIST_RANDOM_ACCESS", "改用 ArrayList，或用迭代器遍历"),
+    ("SERIALIZE_IN_LOOP", "循环外序列化一次，或只序列化增量"),
+    ("STREAM_COUNT_FOR_EXISTENCE", "使用 anyMatch / noneMatch"),
+    ("STREAM_REPEATED", "合并为一次遍历 (groupingBy / partitioningBy)"),
+    ("RECORD_MUTABLE_COMPONENT", "紧凑构造器中 List.copyOf / Map.copyOf"),
+    ("SWITCH_PATTERN_NO_DEFAULT", "补 default 分支，热点类型放在前面"),
+    ("OPTIONAL_GET_UNCHECKED", "使用 orElse / orElseThrow / ifPresent"),
+    ("REGEX_REDOS", "消除嵌套量词与重叠分支，或限制输入长度"),
+    // IO / 远程调用
+    ("BLOCKING_IO", "使用 NIO (Files / FileChannel)，阻塞 IO 放到独立线程池"),
+    ("STREAM_RESOURCE_LEAK", "try-with-resources"),
+    ("HTTP_CLIENT_TIMEOUT", "设置 connectTimeout 与 readTimeout / responseTimeout (通常 3-5s)"),
+    ("FILTER_HTTP_CALL", "缓存结果 (带过期)，或异步化"),
+    ("POSTCONSTRUCT_REMOTE_CALL", "异步预热，或在 ApplicationReadyEvent 中执行"),
+    ("RETRY_NO_BACKOFF", "配置指数退避 (@Backoff(delay, multiplier) / enable-exponential-backoff)"),
+    ("RETRY_EXCESSIVE_ATTEMPTS", "maxAttempts ≤ 3，并配合熔断器"),
+    // 响应式
+    ("FLUX_BLOCK", "保持异步链路，必须阻塞时 subscribeOn(Schedulers.boundedElastic())"),
+    ("SUBSCRIBE_NO_ERROR", "传入 error consumer，或 onErrorResume 处理"),
+    ("FLUX_COLLECT_LIST", "buffer(n) / window(n) 分批处理"),
+    ("PARALLEL_NO_RUN_ON", "parallel() 后接 runOn(Schedulers.parallel())"),
+    ("EMITTER_UNBOUNDED", "改用 Sinks.many() 并指定背压策略"),
+    ("SINKS_MANY", "使用 onBackpressureBuffer(有界) / multicast().directBestEffort()"),
+    // 配置 / 框架
+    ("AUTOWIRED_FIELD", "改用构造器注入"),
+    ("COMPONENT_SCAN_BROAD", "扫描范围缩小到应用自己的包"),
+    ("EAGER_HEAVY_BEAN", "@Lazy，或开启 spring.main.lazy-initialization"),
+    ("DEFAULT_TIMEZONE", "显式传入 ZoneId"),
+    ("GRAALVM_CLASS_FORNAME", "在 reflect-config.json 登记，或使用 RuntimeHints"),
+    ("GRAALVM_METHOD_INVOKE", "在 reflect-config.json 登记，或使用 RuntimeHints"),
+    ("GRAALVM_PROXY", "在 proxy-config.json 登记，或使用 RuntimeHints"),
+    // 日志
+    ("LOG_STRING_CONCAT", "使用占位符 log.info(\"x={}\", x)"),
+    ("EMPTY_CATCH", "记录日志并处理或重新抛出"),
+    // 安全相关
+    ("SYSTEM_EXIT", "抛出异常，由容器 / 框架决定进程退出"),
+    ("RUNTIME_EXEC", "ProcessBuilder 传参数列表，不拼接命令字符串"),
+    // 测试源码
+    ("TEST_THREAD_SLEEP", "使用 Awaitility 等条件等待"),
+    ("TEST_FIXED_PORT", "RANDOM_PORT / @LocalServerPort 或端口 0"),
+    ("TEST_SHARED_STATIC_STATE", "改为实例字段，在 @BeforeEach 中初始化"),
+    ("TEST_CONTAINER_LEAK", "@Container + @Testcontainers、try-with-resources 或 @AfterEach 中 stop()"),
+];
+
+static FIX_INDEX: Lazy<HashMap<&'static str, &'static str>> = Lazy::new(|| FIXES.iter().copied().collect());
+
+/// 规则的修复建议；未登记的规则 (如 WASM 脚本规则) 返回 None
+pub fn fix(rule_id: &str) -> Option<&'static str> {
+    FIX_INDEX.get(rule_id).map(|fix| i18n::text(&format!("fix.{rule_id}"), fix))
+}
+
+/// 规则目录条目
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleEntry {
+    pub id: &'static str,
+    pub description: &'static str,
+    /// 规则注册时的级别
+    pub default_severity: Severity,
+    /// 经档案升级与 `.java-perf.toml [severity]` 覆盖后的级别
+    pub severity: Severity,
+    pub tags: &'static [Tag],
+    pub fix: Option<&'static str>,
+    /// 预估修复工作量 (含 `.java-perf.toml [effort]` 覆盖)
+    pub effort: Effort,
+    /// 当前档案下是否启用
+    pub enabled: bool,
+}
+
+/// 项目的规则目录
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub profiles: Vec<RuleProfile>,
+    pub rules: Vec<RuleEntry>,
+}
+
+/// 生成项目的规则目录
+///
+/// `options.profiles` 为空时从项目推断；脚本规则目录与扫描相同 (`options.rules_dir` 或 `<root>/.java-perf/rules`)。
+pub fn build(root: &Path, options: &ScanOptions) -> Result<Catalog, String> {
+    let analyzer = ast_engine::build_java_analyzer(root, root.is_dir(), options).map_err(|e| e.to_string())?;
+    let profiles = if options.profiles.is_empty() {
+        RuleProfile::infer_from_path(root)
+    } else {
+        options.profiles.clone()
+    };
+    let overrides = SeverityOverrides::load(root).unwrap_or_else(|e| {
+        tracing::warn!("忽略级别覆盖配置 {e}");
+        SeverityOverrides::default()
+    });
+    let efforts = EffortOverrides::load(root).unwrap_or_else(|e| {
+        tracing::warn!("忽略工作量覆盖配置 {e}");
+        EffortOverrides::default()
+    });
+
+    let rules = analyzer.rule_definitions().into_iter()
+        .map(|(id, severity, description)| {
+            let default_severity = ast_engine::convert_severity(severity);
+            let escalated = if profile::is_escalated(&profiles, id) { Severity::P0 } else { default_severity };
+            RuleEntry {
+                id,
+                description: i18n::text(&format!("rule.{id}"), description),
+                default_severity,
+                severity: overrides.get(id).unwrap_or(escalated),
+                tags: taxonomy::tags(id),
+                fix: fix(id),
+                effort: efforts.effort(id),
+                enabled: profile::is_enabled(&profiles, id),
+            }
+        })
+        .collect();
+    Ok(Catalog { profiles, rules })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::complexity_budget::CONFIG_FILE;
+    use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+
+    #[test]
+    fn test_every_builtin_rule_has_fix() {
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let ids = analyzer.rule_ids();
+        let missing: Vec<&str> = ids.iter().copied().filter(|id| fix(id).is_none()).collect();
+        assert!(missing.is_empty(), "rules missing fix guidance: {missing:?}");
+        let stale: Vec<&str> = FIXES.iter().map(|(id, _)| *id).filter(|id| !ids.contains(id)).collect();
+        assert!(stale.is_empty(), "fix guidance for unknown rules: {stale:?}");
+    }
+
+    #[test]
+    fn test_catalog_reflects_project_config() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("pom.xml"), "<project><artifactId>demo</artifactId></project>").unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE), "[severity]\nATOMIC_SPIN = \"p1\"\n\n[effort]\nSELECT_STAR = \"structural\"\n").unwrap();
+
+        let catalog = build(dir.path(), &ScanOptions::default()).unwrap();
+        assert_eq!(catalog.profiles, [RuleProfile::Standard]);
+        let rule = |id: &str| catalog.rules.iter().find(|r| r.id == id).unwrap();
+        assert!(!rule("FLUX_BLOCK").enabled);
+        assert!(rule("N_PLUS_ONE").enabled);
+        assert_eq!(rule("ATOMIC_SPIN").default_severity, Severity::P2);
+        assert_eq!(rule("ATOMIC_SPIN").severity, Severity::P1);
+        assert_eq!(rule("SQL_STRING_CONCAT").tags, &[Tag::Db, Tag::SecurityAdjacent]);
+        assert_eq!(rule("SELECT_STAR").fix, Some("明确列出需要的字段"));
+        assert_eq!(rule("SELECT_STAR").effort, Effort::Structural);
+        assert_eq!(rule("ATOMIC_SPIN").effort, Effort::Trivial);
+
+        let reactive = ScanOptions { profiles: vec![RuleProfile::Reactive], ..Default::default() };
+        let catalog = build(dir.path(), &reactive).unwrap();
+        let rule = catalog.rules.iter().find(|r| r.id == "FLUX_BLOCK").unwrap();
+        assert!(rule.enabled);
+        let blocking = catalog.rules.iter().find(|r| r.id == "BLOCKING_IO").unwrap();
+        assert_eq!((blocking.default_severity, blocking.severity), (Severity::P1, Severity::P0));
+    }
+}
@@ -0,0 +1,228 @@
+//! 复杂度预算 (Complexity Budget) - v9.6
+//!
+//! 按 Phase 2 的方法度量 ([`crate::metrics::MethodMetrics`]) 报告超出预算的方法，均为 P1：
+//! - `MAX_METHOD_LENGTH`: 方法行数 > `max_method_length` (默认 100)
+//! - `MAX_LOOP_DEPTH`: 循环嵌套深度 > `max_loop_depth` (默认 2，即 ≥ 3 层)
+//! - `MAX_ALLOCATIONS_IN_LOOP`: 循环内 `new` 次数 > `max_allocations_in_loop` (默认 5)
+//!
+//! 阈值在扫描根目录的 `.java-perf.toml` 中配置，`packages` 下的包覆盖该包及其子包 (最长前缀优先)，
+//! 未写的键沿用顶层值：
+//!
+//! ```toml
+//! [complexity]
+//! max_method_length = 80
+//!
+//! [complexity.packages."com.acme.legacy"]
+//! max_method_length = 300
+//! max_loop_depth = 3
+//! ```
+
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::rules::effort;
+use crate::metrics::MethodMetrics;
+
+/// 项目配置文件 (扫描根目录)
+pub const CONFIG_FILE: &str = ".java-perf.toml";
+
+const METHOD_LENGTH_DESCRIPTION: &str = "方法过长：难以阅读与优化，JIT 对超大方法 (字节码 > 8000) 不做编译，应拆分职责";
+const LOOP_DEPTH_DESCRIPTION: &str = "循环嵌套过深：执行次数随数据量呈多项式增长，应改用 Map 索引 / 批量查询或拆分方法";
+const LOOP_ALLOCATIONS_DESCRIPTION: &str = "循环内对象分配过多：每次迭代产生多份短命对象，加重 Young GC，应复用对象或移出循环";
+
+/// 一组阈值 (超过即报告)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ComplexityBudget {
+    pub max_method_length: usize,
+    pub max_loop_depth: usize,
+    pub max_allocations_in_loop: usize,
+}
+
+impl Default for ComplexityBudget {
+    fn default() -> Self {
+        ComplexityBudget { max_method_length: 100, max_loop_depth: 2, max_allocations_in_loop: 5 }
+    }
+}
+
+impl ComplexityBudget {
+    /// 用表中出现的键覆盖
+    fn with_overrides(mut self, table: &dyn toml_edit::TableLike) -> Result<Self, String> {
+        for (key, slot) in [
+            ("max_method_length", &mut self.max_method_length),
+            ("max_loop_depth", &mut self.max_loop_depth),
+            ("max_allocations_in_loop", &mut self.max_allocations_in_loop),
+        ] {
+            if let Some(item) = table.get(key) {
+                *slot = item.as_integer()
+                    .and_then(|v| usize::try_from(v).ok())
+                    .ok_or_else(|| format!("{key} must be a non-negative integer"))?;
+            }
+        }
+        Ok(self)
+    }
+}
+
+/// 全局预算 + 按包覆盖
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BudgetConfig {
+    pub default: ComplexityBudget,
+    /// (包, 预算)，已合并顶层值
+    pub packages: Vec<(String, ComplexityBudget)>,
+}
+
+impl BudgetConfig {
+    /// 读取 `<root>/.java-perf.toml` 的 `[complexity]`；文件不存在时使用默认值
+    pub fn load(root: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(root.join(CONFIG_FILE)) {
+            Ok(content) => Self::parse(&content),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let doc: toml_edit::DocumentMut = content.parse().map_err(|e| format!("{CONFIG_FILE}: {e}"))?;
+        let Some(section) = doc.get("complexity").and_then(|item| item.as_table_like()) else {
+            return Ok(Self::default());
+        };
+        let default = ComplexityBudget::default().with_overrides(section).map_err(|e| format!("{CONFIG_FILE} [complexity]: {e}"))?;
+
+        let mut packages = Vec::new();
+        if let Some(table) = section.get("packages").and_then(|item| item.as_table_like()) {
+            for (package, item) in table.iter() {
+                let overrides = item.as_table_like().ok_or_else(|| format!("{CONFIG_FILE} [complexity.packages.\"{package}\"]: expected a table"))?;
+                let budget = default.with_overrides(overrides)
+                    .map_err(|e| format!("{CONFIG_FILE} [complexity.packages.\"{package}\"]: {e}"))?;
+                packages.push((package.to_string(), budget));
+            }
+        }
+        Ok(BudgetConfig { default, packages })
+    }
+
+    /// 包的预算: 最长匹配的包前缀，否则顶层值；返回 (预算, 匹配的包)
+    pub fn budget_for(&self, package: &str) -> (ComplexityBudget, Option<&str>) {
+        self.packages.iter()
+            .filter(|(prefix, _)| package == prefix || package.strip_prefix(prefix.as_str()).is_some_and(|rest| rest.starts_with('.')))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(prefix, budget)| (*budget, Some(prefix.as_str())))
+            .unwrap_or((self.default, None))
+    }
+}
+
+/// 按预算检查方法度量，追加超出预算的问题，返回追加数量
+///
+/// `config_root` 为 `.java-perf.toml` 所在目录；配置无效时忽略并使用默认预算。
+pub fn apply(issues: &mut Vec<AstIssue>, metrics: &[MethodMetrics], config_root: &Path) -> usize {
+    let config = BudgetConfig::load(config_root).unwrap_or_else(|e| {
+        tracing::warn!("忽略复杂度预算配置 {e}");
+        BudgetConfig::default()
+    });
+
+    let before = issues.len();
+    for m in metrics {
+        let (budget, package) = config.budget_for(&m.package);
+        let length = m.end_line + 1 - m.line;
+        for (rule_id, description, value, limit, unit) in [
+            ("MAX_METHOD_LENGTH", METHOD_LENGTH_DESCRIPTION, length, budget.max_method_length, "行"),
+            ("MAX_LOOP_DEPTH", LOOP_DEPTH_DESCRIPTION, m.loop_depth, budget.max_loop_depth, "层循环嵌套"),
+            ("MAX_ALLOCATIONS_IN_LOOP", LOOP_ALLOCATIONS_DESCRIPTION, m.loop_allocations, budget.max_allocations_in_loop, "处循环内 new"),
+        ] {
+            if value <= limit {
+                continue;
+            }
+            let source = package.map(|p| format!(", {CONFIG_FILE} 包 {p}")).unwrap_or_default();
+            issues.push(AstIssue {
+                severity: Severity::P1,
+                issue_type: rule_id.to_string(),
+                file: m.file.clone(),
+                line: m.line,
+                column: 1,
+                end_line: m.line,
+                end_column: 1,
+                description: format!(
+                    "{} [{}.{}(): {value} {unit} > 预算 {limit}{source}]",
+                    i18n::text(&format!("rule.{rule_id}"), description), m.class, m.method,
+                ),
+                owner: None,
+                effort: effort::builtin(rule_id),
+            });
+        }
+    }
+    issues.len() - before
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn method(package: &str, lines: usize, loop_depth: usize, loop_allocations: usize) -> MethodMetrics {
+        MethodMetrics {
+            file: "src/main/java/Batch.java".to_string(),
+            package: package.to_string(),
+            class: "Batch".to_string(),
+            method: "run".to_string(),
+            line: 10,
+            end_line: 9 + lines,
+            cyclomatic: 1,
+            loop_depth,
+            allocations: loop_allocations,
+            loop_allocations,
+            statements: 0,
+            complexity: 0,
+        }
+    }
+
+    #[test]
+    fn test_budget_config_package_overrides() {
+        let config = BudgetConfig::parse(r#"
+[complexity]
+max_method_length = 80
+
+[complexity.packages."com.acme.legacy"]
+max_method_length = 300
+max_loop_depth = 3
+
+[complexity.packages."com.acme.legacy.report"]
+max_allocations_in_loop = 20
+"#).unwrap();
+
+        assert_eq!(config.budget_for("com.acme.order"), (ComplexityBudget { max_method_length: 80, ..Default::default() }, None));
+        // 子包按最长前缀匹配，未写的键沿用顶层值而不是父包的值
+        let (budget, package) = config.budget_for("com.acme.legacy.report.pdf");
+        assert_eq!(package, Some("com.acme.legacy.report"));
+        assert_eq!(budget, ComplexityBudget { max_method_length: 80, max_loop_depth: 2, max_allocations_in_loop: 20 });
+        assert_eq!(config.budget_for("com.acme.legacy").0.max_loop_depth, 3);
+        // 仅名字前缀相同的包不匹配
+        assert_eq!(config.budget_for("com.acme.legacyx").1, None);
+
+        assert_eq!(BudgetConfig::parse("").unwrap(), BudgetConfig::default());
+        assert!(BudgetConfig::parse("[complexity]\nmax_loop_depth = \"deep\"\n").is_err());
+    }
+
+    #[test]
+    fn test_apply_reports_methods_over_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join(CONFIG_FILE), "[complexity.packages.\"com.acme.legacy\"]\nmax_method_length = 300\n").unwrap();
+
+        let metrics = vec![
+            method("com.acme.order", 120, 3, 6),
+            method("com.acme.legacy", 120, 2, 5),
+        ];
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, &metrics, dir.path()), 3);
+
+        let found: Vec<_> = issues.iter().map(|i| (i.issue_type.as_str(), i.severity, i.line)).collect();
+        assert_eq!(found, vec![
+            ("MAX_METHOD_LENGTH", Severity::P1, 10),
+            ("MAX_LOOP_DEPTH", Severity::P1, 10),
+            ("MAX_ALLOCATIONS_IN_LOOP", Severity::P1, 10),
+        ]);
+        assert!(issues[1].description.ends_with("[Batch.run(): 3 层循环嵌套 > 预算 2]"));
+
+        // 配置无效时使用默认预算
+        std::fs::write(dir.path().join(CONFIG_FILE), "[complexity\n").unwrap();
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, &metrics[1..], dir.path()), 1);
+        assert!(issues[0].description.ends_with("[Batch.run(): 120 行 > 预算 100]"));
+    }
+}
@@ -0,0 +1,265 @@
+//! 修复工作量估算 (Remediation Effort) - v9.6
+//!
+//! 每条规则登记一个预估的修复工作量，扫描报告据此汇总修复计划 ("12 个简单修复，3 个结构性重构")：
+//! - `trivial`: 一两行的机械修改 (加超时参数、换用 LongAdder、提取常量、改配置值)
+//! - `local`: 限于一个方法/类内的修改 (try-finally 清理、换用有界线程池)，未登记的规则默认为此
+//! - `structural`: 跨类/跨层的重构 (批量查询替代 N+1、分页改造、拆分事务、缓存请求路径)
+//!
+//! 团队可在 `.java-perf.toml` 中按规则覆盖：
+//!
+//! ```toml
+//! [effort]
+//! SQL_STRING_CONCAT = "structural"
+//! ```
+
+use clap::ValueEnum;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::ast_engine::AstIssue;
+use crate::i18n;
+use crate::rules::complexity_budget::CONFIG_FILE;
+
+/// 修复工作量
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Effort {
+    /// 一两行的机械修改
+    Trivial,
+    /// 限于一个方法/类内的修改
+    #[default]
+    Local,
+    /// 跨类/跨层的重构
+    Structural,
+}
+
+/// 一两行即可修复的规则
+const TRIVIAL_RULES: &[&str] = &[
+    "ATOMIC_SPIN",
+    "AWAIT_NO_TIMEOUT",
+    "CACHEABLE_NO_KEY",
+    "CACHE_NO_EXPIRE",
+    "CIRCUIT_BREAKER_TINY_WINDOW",
+    "COLLECTION_NO_CAPACITY",
+    "COMPLETABLE_GET_NO_TIMEOUT",
+    "COMPLETABLE_JOIN",
+    "COMPONENT_SCAN_BROAD",
+    "DATE_FORMATTER_IN_LOOP",
+    "DATE_IN_LOOP",
+    "DOUBLE_CHECKED_LOCKING",
+    "EAGER_HEAVY_BEAN",
+    "FUTURE_GET_NO_TIMEOUT",
+    "LINKEDLIST_RANDOM_ACCESS",
+    "LIST_CONTAINS_IN_LOOP",
+    "LOG_STRING_CONCAT",
+    "OPTIONAL_GET_UNCHECKED",
+    "PATTERN_COMPILE_HOT_PATH",
+    "RANDOM_SHARED",
+    "REDIS_SETNX_NO_EXPIRE",
+    "RETRY_EXCESSIVE_ATTEMPTS",
+    "RETRY_NO_BACKOFF",
+    "SCHEDULED_FIXED_RATE",
+    "SCHEDULED_POOL_ZERO_CORE",
+    "SERIALIZER_PER_CALL",
+    "STREAM_COLLECT_SIZE",
+    "STREAM_COUNT_FOR_EXISTENCE",
+    "SYNCHRONIZED_MAP",
+    "TEST_FIXED_PORT",
+    "THREAD_NO_NAME",
+    // 配置文件
+    "DB_CONNECTION_TIMEOUT_LONG",
+    "DB_CONNECTION_TIMEOUT_MISSING",
+    "DB_POOL_SMALL",
+    "DEBUG_LOG_IN_PROD",
+    "JPA_SHOW_SQL_PROD",
+    "REDIS_TIMEOUT_MISSING",
+    "TOMCAT_THREADS_LOW",
+    "LOG_CALLER_DATA_PATTERN",
+    "LOG_ROOT_DEBUG",
+    // Dockerfile
+    "DOCKER_APT_NO_CLEAN",
+    "DOCKER_JDK_RUNTIME",
+    "DOCKER_LATEST_TAG",
+    "DOCKER_NO_TAG",
+    "DOCKER_NO_USER",
+    // docker-compose
+    "COMPOSE_DB_DEFAULT_BUFFERS",
+    "COMPOSE_DEPENDS_NO_CONDITION",
+    "COMPOSE_NO_MEM_LIMIT",
+    // CI 流水线
+    "CI_MVN_CLEAN_INSTALL",
+    "CI_NO_BUILD_TIMING",
+    "CI_NO_DEPENDENCY_CACHE",
+    "CI_TESTS_NO_PARALLEL",
+    // Gradle 构建脚本
+    "GRADLE_DYNAMIC_VERSION",
+    "GRADLE_SLOW_REPO_FIRST",
+    "GRADLE_TEST_NO_PARALLEL_FORKS",
+    // 依赖公告 (升级版本)
+    "DEPENDENCY_PERF_ADVISORY",
+    "DEPENDENCY_VULN_ADVISORY",
+    // Spark 作业
+    "SPARK_JOIN_NO_BROADCAST",
+];
+
+/// 需要跨类/跨层重构的规则
+const STRUCTURAL_RULES: &[&str] = &[
+    "N_PLUS_ONE",
+    "N_PLUS_ONE_WHILE",
+    "N_PLUS_ONE_FOREACH",
+    "NESTED_LOOP",
+    "NESTED_LOOP_MIXED",
+    "UNBOUNDED_QUERY_RESULT",
+    "LIKE_LEADING_WILDCARD",
+    "TRANSACTION_SELF_CALL",
+    "FILTER_DB_CALL",
+    "FILTER_HTTP_CALL",
+    "FLUX_BLOCK",
+    "LISTENER_AUTO_ACK_SLOW",
+    "LISTENER_BLOCKING_CALL",
+    "LISTENER_TRANSACTIONAL",
+    "REDIS_FULL_SCAN_IN_REQUEST",
+    "RETRY_NON_IDEMPOTENT",
+    "JPA_OPEN_IN_VIEW",
+    "MAX_LOOP_DEPTH",
+    "MAX_METHOD_LENGTH",
+    "BC_HUGE_METHOD",
+    "BC_NOT_INLINABLE",
+    "DOCKER_JDK8_IMAGE",
+];
+
+/// 规则的内置修复工作量
+pub fn builtin(rule_id: &str) -> Effort {
+    if TRIVIAL_RULES.contains(&rule_id) {
+        Effort::Trivial
+    } else if STRUCTURAL_RULES.contains(&rule_id) {
+        Effort::Structural
+    } else {
+        Effort::Local
+    }
+}
+
+/// 规则 → 覆盖后的工作量 (`.java-perf.toml [effort]`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EffortOverrides {
+    efforts: BTreeMap<String, Effort>,
+}
+
+impl EffortOverrides {
+    /// 读取 `<root>/.java-perf.toml` 的 `[effort]`；文件不存在时为空
+    pub fn load(root: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(root.join(CONFIG_FILE)) {
+            Ok(content) => Self::parse(&content),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let doc: toml_edit::DocumentMut = content.parse().map_err(|e| format!("{CONFIG_FILE}: {e}"))?;
+        let Some(section) = doc.get("effort").and_then(|item| item.as_table_like()) else {
+            return Ok(Self::default());
+        };
+        let efforts = section.iter()
+            .map(|(rule, item)| {
+                item.as_str()
+                    .ok_or_else(|| "expected a string".to_string())
+                    .and_then(|s| Effort::from_str(s.trim(), true)
+                        .map_err(|_| format!("unknown effort '{s}': expected trivial | local | structural")))
+                    .map(|effort| (rule.to_string(), effort))
+                    .map_err(|e| format!("{CONFIG_FILE} [effort] {rule}: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(EffortOverrides { efforts })
+    }
+
+    /// 规则的工作量 (覆盖优先，否则为内置值)
+    pub fn effort(&self, rule_id: &str) -> Effort {
+        self.efforts.get(rule_id).copied().unwrap_or_else(|| builtin(rule_id))
+    }
+}
+
+/// 按 `.java-perf.toml [effort]` 调整问题的工作量；配置无效时保留内置值
+pub fn apply(issues: &mut [AstIssue], config_root: &Path) {
+    let overrides = EffortOverrides::load(config_root).unwrap_or_else(|e| {
+        tracing::warn!("忽略工作量覆盖配置 {e}");
+        EffortOverrides::default()
+    });
+    for issue in issues.iter_mut() {
+        issue.effort = overrides.effort(&issue.issue_type);
+    }
+}
+
+/// 修复计划: 各工作量的问题数
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct Plan {
+    pub trivial: usize,
+    pub local: usize,
+    pub structural: usize,
+}
+
+impl Plan {
+    pub fn of(issues: &[AstIssue]) -> Self {
+        let mut plan = Plan::default();
+        for issue in issues {
+            match issue.effort {
+                Effort::Trivial => plan.trivial += 1,
+                Effort::Local => plan.local += 1,
+                Effort::Structural => plan.structural += 1,
+            }
+        }
+        plan
+    }
+
+    /// 如 "12 个简单修复，5 个局部修改，3 个结构性重构" (省略为 0 的项)
+    pub fn describe(&self) -> String {
+        [
+            (self.trivial, "report.effort.trivial", "{count} 个简单修复"),
+            (self.local, "report.effort.local", "{count} 个局部修改"),
+            (self.structural, "report.effort.structural", "{count} 个结构性重构"),
+        ]
+        .into_iter()
+        .filter(|(count, _, _)| *count > 0)
+        .map(|(count, id, zh)| i18n::format(id, zh, &[("count", &count)]))
+        .collect::<Vec<_>>()
+        .join(i18n::text("report.effort.separator", "，"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(id: &str) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: id.to_string(),
+            file: "A.java".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: String::new(),
+            owner: None,
+            effort: builtin(id),
+        }
+    }
+
+    #[test]
+    fn test_overrides_and_plan() {
+        let overrides = EffortOverrides::parse("[effort]\nSQL_STRING_CONCAT = \"Structural\"\n").unwrap();
+        assert_eq!(overrides.effort("SQL_STRING_CONCAT"), Effort::Structural);
+        assert_eq!(overrides.effort("N_PLUS_ONE"), Effort::Structural);
+        assert_eq!(overrides.effort("FUTURE_GET_NO_TIMEOUT"), Effort::Trivial);
+        assert_eq!(overrides.effort("CORP_DAO_IN_LOOP"), Effort::Local);
+        assert!(EffortOverrides::parse("[effort]\nN_PLUS_ONE = \"huge\"\n").unwrap_err().contains("N_PLUS_ONE"));
+
+        let issues: Vec<AstIssue> = ["FUTURE_GET_NO_TIMEOUT", "ATOMIC_SPIN", "N_PLUS_ONE", "THREADLOCAL_LEAK"]
+            .into_iter().map(issue).collect();
+        let plan = Plan::of(&issues);
+        assert_eq!(plan, Plan { trivial: 2, local: 1, structural: 1 });
+        assert_eq!(plan.describe(), "2 个简单修复，1 个局部修改，1 个结构性重构");
+        assert_eq!(Plan { trivial: 0, local: 0, structural: 3 }.describe(), "3 个结构性重构");
+    }
+}
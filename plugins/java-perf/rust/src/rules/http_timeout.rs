@@ -0,0 +1,132 @@
+//! HTTP 客户端全局超时 (HTTP Timeout) - v9.6
+//!
+//! `HTTP_CLIENT_TIMEOUT` 在客户端创建处检查方法内的超时设置；本模块在扫描后检查项目级超时配置，
+//! 找到任意一处时丢弃全部 `HTTP_CLIENT_TIMEOUT` 问题：
+//! - 配置: `spring.http.client.*timeout*` / `spring.http.reactiveclient.*timeout*` (Spring Boot 3.4+)
+//! - 代码: `RestTemplateCustomizer` / `WebClientCustomizer` / `RestClientCustomizer` /
+//!   `ClientHttpRequestFactorySettings` 与超时设置出现在同一文件
+
+use std::path::Path;
+
+use crate::ast_engine::AstIssue;
+use crate::rules::test_code;
+use crate::scanner::config;
+
+/// 受影响的规则
+pub const RULE_ID: &str = "HTTP_CLIENT_TIMEOUT";
+
+/// 全局 HTTP 客户端配置前缀
+const CONFIG_PREFIXES: &[&str] = &["spring.http.client.", "spring.http.clients.", "spring.http.reactiveclient."];
+
+/// 对所有客户端生效的定制器类型
+const CUSTOMIZER_TYPES: &[&str] = &[
+    "RestTemplateCustomizer",
+    "WebClientCustomizer",
+    "RestClientCustomizer",
+    "ClientHttpRequestFactorySettings",
+    "ClientHttpConnectorSettings",
+];
+
+/// 项目级超时配置的位置 (`文件:行`)
+pub fn global_timeout(root: &Path) -> Option<String> {
+    for (file, entries) in config::spring_config_files(root) {
+        let found = entries.iter().find(|entry| {
+            CONFIG_PREFIXES.iter().any(|prefix| entry.key.starts_with(prefix))
+                && entry.key.to_ascii_lowercase().contains("timeout")
+        });
+        if let Some(entry) = found {
+            return Some(format!("{file}:{}", entry.line));
+        }
+    }
+
+    let mut sources: Vec<_> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !e.file_type().is_dir() || !config::BUILD_OUTPUT_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file() && e.path().extension().is_some_and(|ext| ext == "java"))
+        .filter(|e| !test_code::is_test_source(e.path().strip_prefix(root).unwrap_or(e.path())))
+        .map(|e| e.into_path())
+        .collect();
+    sources.sort();
+    sources.into_iter().find_map(|path| {
+        let code = std::fs::read_to_string(&path).ok()?;
+        if !code.to_ascii_lowercase().contains("timeout") {
+            return None;
+        }
+        let line = code.lines().position(|line| CUSTOMIZER_TYPES.iter().any(|t| line.contains(t)))?;
+        let rel = path.strip_prefix(root).unwrap_or(&path);
+        Some(format!("{}:{}", rel.to_string_lossy().replace('\\', "/"), line + 1))
+    })
+}
+
+/// 存在项目级超时配置时移除 [`RULE_ID`] 问题，返回移除数量
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path) -> usize {
+    if !issues.iter().any(|issue| issue.issue_type == RULE_ID) || global_timeout(root).is_none() {
+        return 0;
+    }
+    let before = issues.len();
+    issues.retain(|issue| issue.issue_type != RULE_ID);
+    before - issues.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(issue_type: &str) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: issue_type.to_string(),
+            file: "src/main/java/com/acme/GatewayClient.java".to_string(),
+            line: 3,
+            column: 1,
+            end_line: 3,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    fn project(files: &[(&str, &str)]) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        for (path, content) in files {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_global_timeout_config() {
+        let dir = project(&[
+            ("src/main/resources/application.yml", "spring:\n  http:\n    client:\n      connect-timeout: 2s\n      read-timeout: 5s\n"),
+        ]);
+        assert_eq!(global_timeout(dir.path()).as_deref(), Some("src/main/resources/application.yml:4"));
+        let mut issues = vec![issue(RULE_ID), issue("N_PLUS_ONE"), issue(RULE_ID)];
+        assert_eq!(apply(&mut issues, dir.path()), 2);
+        assert_eq!(issues.len(), 1);
+
+        let dir = project(&[
+            ("src/main/java/com/acme/HttpConfig.java", "class HttpConfig {\n  @Bean\n  RestTemplateCustomizer timeouts() {\n    return t -> t.setRequestFactory(factory(Duration.ofSeconds(2)));\n  }\n  Factory factory(Duration connectTimeout) { return null; }\n}\n"),
+        ]);
+        assert_eq!(global_timeout(dir.path()).as_deref(), Some("src/main/java/com/acme/HttpConfig.java:3"));
+    }
+
+    #[test]
+    fn test_no_global_timeout() {
+        // 无关配置、测试源码与构建产物不计入
+        let dir = project(&[
+            ("src/main/resources/application.properties", "spring.datasource.hikari.connection-timeout=3000\n"),
+            ("src/test/resources/application.yml", "spring:\n  http:\n    client:\n      read-timeout: 5s\n"),
+            ("src/test/java/com/acme/StubConfig.java", "class StubConfig { WebClientCustomizer c = b -> b.responseTimeout(null); }\n"),
+            ("target/classes/application.yml", "spring:\n  http:\n    client:\n      read-timeout: 5s\n"),
+        ]);
+        assert_eq!(global_timeout(dir.path()), None);
+        let mut issues = vec![issue(RULE_ID)];
+        assert_eq!(apply(&mut issues, dir.path()), 0);
+        assert_eq!(issues.len(), 1);
+    }
+}
@@ -0,0 +1,505 @@
+//! javap 接收者类型校验 (Javap Verify) - v9.6
+//!
+//! N+1 与无界线程池是影响最大的两类问题，但 AST 阶段只能按变量名/方法名启发式判断接收者类型。
+//! `scan --verify-with-javap` 对这些问题在编译产物 (`target/classes`、`build/classes/java/main`
+//! 与 `--classpath`) 上用 `javap -p -v` 解析接收者类：
+//! - N+1: 接收者或其父类型是 Spring Data Repository / EntityManager / JdbcTemplate / MyBatis 等
+//!   (含 `@Mapper`、`@Repository` 注解) → 确认并升级为 P0；类型层级完整可解析且都不是 → 丢弃
+//! - 无界线程池: 接收者确为 `java.util.concurrent.Executors` → 确认；解析到同名的其他类 → 丢弃
+//!
+//! 无法定位接收者 (链式调用、`var`、类未编译或父类型不在 classpath) 时保持原样。
+
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use tree_sitter::Node;
+use walkdir::WalkDir;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::jdk_engine;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+use crate::symbol_table::ImportIndex;
+
+/// 父类型层级最大深度
+const MAX_DEPTH: usize = 6;
+
+/// JDK 无界线程池工厂
+const EXECUTORS: &str = "java.util.concurrent.Executors";
+
+/// 数据访问类型 (类型本身或父类型命中即确认 N+1)
+const DATA_ACCESS_TYPES: &[&str] = &[
+    "javax.persistence.EntityManager",
+    "jakarta.persistence.EntityManager",
+    "org.hibernate.Session",
+    "org.springframework.jdbc.core.JdbcOperations",
+    "org.springframework.jdbc.core.namedparam.NamedParameterJdbcOperations",
+    "org.apache.ibatis.session.SqlSession",
+    "com.baomidou.mybatisplus.core.mapper.BaseMapper",
+    "java.sql.Connection",
+    "java.sql.Statement",
+];
+
+/// 标记数据访问类的注解 (常量池中的类型描述符)
+const DATA_ACCESS_ANNOTATIONS: &[&str] = &[
+    "Lorg/apache/ibatis/annotations/Mapper;",
+    "Lorg/springframework/stereotype/Repository;",
+];
+
+/// 需要校验的问题类别
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Check {
+    DataAccess,
+    ThreadPool,
+}
+
+fn check_for(rule_id: &str) -> Option<Check> {
+    match rule_id {
+        "N_PLUS_ONE" | "N_PLUS_ONE_WHILE" | "N_PLUS_ONE_FOREACH" => Some(Check::DataAccess),
+        "UNBOUNDED_POOL" => Some(Check::ThreadPool),
+        _ => None,
+    }
+}
+
+fn is_data_access_type(fqn: &str) -> bool {
+    DATA_ACCESS_TYPES.contains(&fqn) || (fqn.starts_with("org.springframework.data.") && fqn.ends_with("Repository"))
+}
+
+/// `javap -p -v` 解析出的类信息
+#[derive(Debug, Clone, PartialEq)]
+struct ClassInfo {
+    name: String,
+    supertypes: Vec<String>,
+    /// 方法声明 (javap 原样输出)
+    methods: Vec<String>,
+    /// 带有数据访问注解
+    data_access_annotation: bool,
+}
+
+/// 去掉泛型参数 (`Repo<T extends java.lang.Object>` → `Repo`)
+fn strip_generics(text: &str) -> String {
+    let mut depth = 0;
+    text.chars()
+        .filter(|&c| {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => return depth == 0,
+            }
+            false
+        })
+        .collect()
+}
+
+/// 解析 `javap -p -v` 输出 (也兼容 `javap -p`)
+fn parse_class(output: &str) -> Option<ClassInfo> {
+    let header = output.lines()
+        .filter(|line| !line.starts_with(' ') && !line.starts_with("Classfile") && !line.starts_with("Compiled from"))
+        .map(strip_generics)
+        .find(|line| line.split_whitespace().any(|t| matches!(t, "class" | "interface" | "enum" | "record")))?;
+    let tokens: Vec<&str> = header.trim_end_matches('{').split_whitespace().collect();
+    let kind = tokens.iter().position(|t| matches!(*t, "class" | "interface" | "enum" | "record"))?;
+    let name = tokens.get(kind + 1)?.to_string();
+    let supertypes = tokens[kind + 2..].iter()
+        .filter(|t| !matches!(**t, "extends" | "implements"))
+        .map(|t| t.trim_end_matches(',').to_string())
+        .filter(|t| !t.is_empty() && t != "java.lang.Object")
+        .collect();
+    let methods = output.lines()
+        .filter(|line| line.starts_with("  ") && !line.starts_with("   ") && !line.trim_start().starts_with('#'))
+        .filter(|line| line.contains('(') && line.trim_end().ends_with(';'))
+        .map(|line| line.trim().trim_end_matches(';').to_string())
+        .collect();
+    Some(ClassInfo {
+        name,
+        supertypes,
+        methods,
+        data_access_annotation: DATA_ACCESS_ANNOTATIONS.iter().any(|a| output.contains(a)),
+    })
+}
+
+/// 按类全名查询 javap 输出 (找不到时为 None)
+type Javap<'a> = Box<dyn Fn(&str) -> Option<String> + 'a>;
+
+/// 带缓存的编译类查询
+struct Classes<'a> {
+    javap: Javap<'a>,
+    cache: HashMap<String, Option<ClassInfo>>,
+}
+
+impl<'a> Classes<'a> {
+    fn new(javap: Javap<'a>) -> Self {
+        Self { javap, cache: HashMap::new() }
+    }
+
+    fn get(&mut self, fqn: &str) -> Option<ClassInfo> {
+        if !self.cache.contains_key(fqn) {
+            let info = (self.javap)(fqn).and_then(|output| parse_class(&output));
+            self.cache.insert(fqn.to_string(), info);
+        }
+        self.cache[fqn].clone()
+    }
+}
+
+/// 校验结论
+#[derive(Debug, PartialEq)]
+enum Outcome {
+    /// 确认，附证据 (类型层级)
+    Confirmed(String),
+    /// 接收者类型已解析且不符合，丢弃
+    Rejected,
+    /// 无法判断
+    Unresolved,
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+fn descendants(root: Node) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        nodes.push(node);
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    nodes
+}
+
+/// 问题位置上的调用: (接收者的声明类型, 方法名)
+fn receiver_type(root: Node, code: &str, line: usize, column: usize) -> Option<(String, String)> {
+    let row = line.saturating_sub(1);
+    let nodes = descendants(root);
+    let calls: Vec<Node> = nodes.iter()
+        .filter(|n| n.kind() == "method_invocation" && n.start_position().row == row)
+        .copied()
+        .collect();
+    let call = calls.iter().find(|n| n.start_position().column + 1 == column).or(calls.first())?;
+    let method = text(call.child_by_field_name("name")?, code).to_string();
+    let object = call.child_by_field_name("object")?;
+    let variable = match object.kind() {
+        "identifier" => text(object, code),
+        // this.orderRepository
+        "field_access" if object.child_by_field_name("object").is_some_and(|o| o.kind() == "this") => {
+            text(object.child_by_field_name("field")?, code)
+        }
+        _ => return None,
+    };
+
+    // 问题行之前最近的声明 (局部变量 / 参数 / 字段)
+    let declared = nodes.iter()
+        .rev()
+        .filter(|n| n.start_position().row <= row)
+        .find_map(|n| match n.kind() {
+            "variable_declarator" => n.child_by_field_name("name")
+                .filter(|name| text(*name, code) == variable)
+                .and_then(|_| n.parent())
+                .and_then(|decl| decl.child_by_field_name("type")),
+            "formal_parameter" | "catch_formal_parameter" | "enhanced_for_statement" => n.child_by_field_name("name")
+                .filter(|name| text(*name, code) == variable)
+                .and_then(|_| n.child_by_field_name("type")),
+            _ => None,
+        })
+        .map(|t| strip_generics(text(t, code)));
+    let type_name = match declared {
+        Some(t) if t == "var" => return None,
+        Some(t) => t,
+        // 未声明的大写标识符为静态调用 (Executors.newCachedThreadPool)
+        None if variable.starts_with(|c: char| c.is_uppercase()) => variable.to_string(),
+        None => return None,
+    };
+    Some((type_name, method))
+}
+
+/// 简单类名的候选全名 (显式 import → 同包 → 通配符 import → java.lang)
+fn candidates(type_name: &str, imports: &ImportIndex) -> Vec<String> {
+    if let Some(fqn) = imports.explicit.get(type_name) {
+        return vec![fqn.clone()];
+    }
+    let mut candidates = Vec::new();
+    if type_name.contains('.') {
+        candidates.push(type_name.to_string());
+    }
+    candidates.extend(imports.package.iter().map(|p| format!("{p}.{type_name}")));
+    candidates.extend(imports.wildcards.iter().map(|w| format!("{w}.{type_name}")));
+    if !type_name.contains('.') {
+        candidates.push(format!("java.lang.{type_name}"));
+    }
+    candidates
+}
+
+/// 沿父类型层级查找数据访问类型: Ok(命中路径) / Err(层级是否完整解析)
+fn data_access_path(classes: &mut Classes, fqn: &str, depth: usize) -> Result<Vec<String>, bool> {
+    if is_data_access_type(fqn) {
+        return Ok(vec![fqn.to_string()]);
+    }
+    let Some(info) = classes.get(fqn) else { return Err(false) };
+    if info.data_access_annotation {
+        return Ok(vec![fqn.to_string()]);
+    }
+    if depth >= MAX_DEPTH {
+        return Err(false);
+    }
+    let mut complete = true;
+    for supertype in &info.supertypes {
+        match data_access_path(classes, supertype, depth + 1) {
+            Ok(mut path) => {
+                path.insert(0, fqn.to_string());
+                return Ok(path);
+            }
+            Err(resolved) => complete &= resolved,
+        }
+    }
+    Err(complete)
+}
+
+fn simple_name(fqn: &str) -> &str {
+    fqn.rsplit('.').next().unwrap_or(fqn)
+}
+
+fn verify(classes: &mut Classes, check: Check, type_name: &str, method: &str, imports: &ImportIndex) -> Outcome {
+    let Some((fqn, info)) = candidates(type_name, imports).into_iter()
+        .find_map(|fqn| classes.get(&fqn).map(|info| (fqn, info))) else {
+        return Outcome::Unresolved;
+    };
+    match check {
+        Check::DataAccess => match data_access_path(classes, &fqn, 0) {
+            Ok(path) => Outcome::Confirmed(path.iter().enumerate()
+                .map(|(i, t)| if i == 0 { simple_name(t).to_string() } else { t.clone() })
+                .collect::<Vec<_>>()
+                .join(" → ")),
+            Err(true) => Outcome::Rejected,
+            Err(false) => Outcome::Unresolved,
+        },
+        Check::ThreadPool if info.name == EXECUTORS => {
+            let signature = info.methods.iter()
+                .find(|m| m.contains(&format!(" {method}(")))
+                .map(|m| format!(": {m}"))
+                .unwrap_or_default();
+            Outcome::Confirmed(format!("{EXECUTORS}{signature}"))
+        }
+        Check::ThreadPool => Outcome::Rejected,
+    }
+}
+
+/// 校验统计
+#[derive(Debug, Default, PartialEq)]
+pub struct VerifySummary {
+    pub confirmed: usize,
+    pub discarded: usize,
+    pub unresolved: usize,
+}
+
+impl VerifySummary {
+    /// 写入 `scan.javap_verify` 的摘要
+    pub fn display(&self) -> String {
+        format!("{} confirmed, {} discarded, {} unresolved", self.confirmed, self.discarded, self.unresolved)
+    }
+}
+
+/// 项目编译输出目录 (Maven `target/classes`、Gradle `build/classes/java/main`，含多模块)
+pub fn class_dirs(root: &Path) -> Vec<PathBuf> {
+    WalkDir::new(root)
+        .max_depth(6)
+        .into_iter()
+        .filter_entry(|e| !e.file_name().to_str().is_some_and(|n| (n.starts_with('.') && e.depth() > 0) || n == "node_modules" || n == "src"))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_dir())
+        .map(|e| e.into_path())
+        .filter(|p| p.ends_with("target/classes") || p.ends_with("build/classes/java/main"))
+        .collect()
+}
+
+/// 校验高影响问题的接收者类型，确认的升级为 P0 并注明类型层级，排除的从列表中移除
+///
+/// `issue.file` 为相对 `root` 的路径 (目录扫描的显示路径)；javap 不可用时返回 None。
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path, extra_classpath: &[PathBuf]) -> Option<VerifySummary> {
+    let javap = jdk_engine::get_jdk_tool("javap")?;
+    let mut dirs = class_dirs(root);
+    dirs.extend(extra_classpath.iter().cloned());
+    let classpath = std::env::join_paths(&dirs).unwrap_or_default();
+    let runner: Javap = Box::new(move |fqn: &str| run_javap(&javap, &classpath, fqn));
+    Some(apply_with(issues, root, &mut Classes::new(runner)))
+}
+
+fn run_javap(javap: &str, classpath: &OsString, fqn: &str) -> Option<String> {
+    let mut cmd = Command::new(javap);
+    cmd.args(["-p", "-v"]);
+    if !classpath.is_empty() {
+        cmd.arg("-cp").arg(classpath);
+    }
+    let output = jdk_engine::run_with_timeout(cmd.arg(fqn), jdk_engine::DEFAULT_TIMEOUT).ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn apply_with(issues: &mut Vec<AstIssue>, root: &Path, classes: &mut Classes) -> VerifySummary {
+    let mut summary = VerifySummary::default();
+    let targets: Vec<(usize, Check)> = issues.iter()
+        .enumerate()
+        .filter_map(|(idx, issue)| check_for(&issue.issue_type).map(|check| (idx, check)))
+        .collect();
+    if targets.is_empty() {
+        return summary;
+    }
+    let Ok(analyzer) = JavaTreeSitterAnalyzer::new() else { return summary };
+
+    let mut sources: HashMap<String, Option<(String, ImportIndex)>> = HashMap::new();
+    let mut rejected = Vec::new();
+    for (idx, check) in targets {
+        let issue = &mut issues[idx];
+        let source = sources.entry(issue.file.clone()).or_insert_with(|| {
+            let path = root.join(&issue.file);
+            let code = std::fs::read_to_string(&path).ok()?;
+            let (_, _, imports) = analyzer.extract_symbols(&code, &path).ok()?;
+            Some((code, imports))
+        });
+        let outcome = source.as_ref()
+            .and_then(|(code, imports)| {
+                let tree = analyzer.parse(code).ok()?;
+                let (type_name, method) = receiver_type(tree.root_node(), code, issue.line, issue.column)?;
+                Some(verify(classes, check, &type_name, &method, imports))
+            })
+            .unwrap_or(Outcome::Unresolved);
+        match outcome {
+            Outcome::Confirmed(evidence) => {
+                issue.severity = Severity::P0;
+                let note = i18n::format("note.javap_confirmed", "[javap 确认: {evidence}]", &[("evidence", &evidence)]);
+                issue.description = format!("{} {note}", issue.description);
+                summary.confirmed += 1;
+            }
+            Outcome::Rejected => {
+                rejected.push(idx);
+                summary.discarded += 1;
+            }
+            Outcome::Unresolved => summary.unresolved += 1,
+        }
+    }
+
+    let mut idx = 0;
+    issues.retain(|_| {
+        idx += 1;
+        !rejected.contains(&(idx - 1))
+    });
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REPOSITORY: &str = "Classfile /app/target/classes/com/acme/OrderRepository.class\n  Compiled from \"OrderRepository.java\"\npublic interface com.acme.OrderRepository extends com.acme.BaseRepository<com.acme.Order, java.lang.Long>\n  minor version: 0\n{\n  public abstract java.util.List<com.acme.Order> findByCustomerId(java.lang.Long);\n    descriptor: (Ljava/lang/Long;)Ljava/util/List;\n}\n";
+    const BASE: &str = "public interface com.acme.BaseRepository<T extends java.lang.Object, ID extends java.lang.Object> extends org.springframework.data.jpa.repository.JpaRepository<T, ID>\n{\n}\n";
+    const PRICING: &str = "public class com.acme.PricingService implements com.acme.Pricing {\n  public java.math.BigDecimal price(com.acme.Order);\n}\n";
+    const PRICING_API: &str = "public interface com.acme.Pricing\n{\n}\n";
+    const MAPPER: &str = "public interface com.acme.OrderMapper\n  #9 = Utf8               Lorg/apache/ibatis/annotations/Mapper;\n{\n}\n";
+    const EXECUTORS_CLASS: &str = "public class java.util.concurrent.Executors\n{\n  public static java.util.concurrent.ExecutorService newCachedThreadPool();\n    descriptor: ()Ljava/util/concurrent/ExecutorService;\n}\n";
+
+    fn classes() -> Classes<'static> {
+        Classes::new(Box::new(|fqn: &str| match fqn {
+            "com.acme.OrderRepository" => Some(REPOSITORY.to_string()),
+            "com.acme.BaseRepository" => Some(BASE.to_string()),
+            "com.acme.PricingService" => Some(PRICING.to_string()),
+            "com.acme.Pricing" => Some(PRICING_API.to_string()),
+            "com.acme.OrderMapper" => Some(MAPPER.to_string()),
+            "java.util.concurrent.Executors" => Some(EXECUTORS_CLASS.to_string()),
+            _ => None,
+        }))
+    }
+
+    fn issue(issue_type: &str, file: &str, line: usize) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: issue_type.to_string(),
+            file: file.to_string(),
+            line,
+            column: 13,
+            end_line: line,
+            end_column: 40,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_parse_class() {
+        let info = parse_class(REPOSITORY).unwrap();
+        assert_eq!(info.name, "com.acme.OrderRepository");
+        assert_eq!(info.supertypes, vec!["com.acme.BaseRepository"]);
+        assert_eq!(info.methods, vec!["public abstract java.util.List<com.acme.Order> findByCustomerId(java.lang.Long)"]);
+        assert!(!info.data_access_annotation);
+        assert_eq!(parse_class(BASE).unwrap().supertypes, vec!["org.springframework.data.jpa.repository.JpaRepository"]);
+        assert!(parse_class(MAPPER).unwrap().data_access_annotation);
+        // javap -p (无 -v) 的头部以 `{` 结尾
+        assert_eq!(parse_class(PRICING).unwrap().supertypes, vec!["com.acme.Pricing"]);
+    }
+
+    #[test]
+    fn test_confirm_and_discard() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = "src/main/java/com/acme/OrderService.java";
+        std::fs::create_dir_all(dir.path().join("src/main/java/com/acme")).unwrap();
+        std::fs::write(dir.path().join(file), "package com.acme;\n\nimport java.util.concurrent.*;\n\nclass OrderService {\n    private OrderRepository orderRepository;\n    private PricingService pricing;\n\n    void run(List<Long> ids, OrderMapper mapper, Cursor cursor) {\n        for (Long id : ids) {\n            orderRepository.findByCustomerId(id);\n            this.pricing.price(id);\n            mapper.selectById(id);\n            cursor.fetch(id);\n        }\n        ExecutorService pool = Executors.newCachedThreadPool();\n    }\n}\n").unwrap();
+
+        let mut issues = vec![
+            issue("N_PLUS_ONE_FOREACH", file, 11),
+            issue("N_PLUS_ONE_FOREACH", file, 12),
+            issue("N_PLUS_ONE_FOREACH", file, 13),
+            issue("N_PLUS_ONE_FOREACH", file, 14),
+            issue("UNBOUNDED_POOL", file, 16),
+            issue("NESTED_LOOP", file, 10),
+        ];
+        let summary = apply_with(&mut issues, dir.path(), &mut classes());
+        assert_eq!(summary, VerifySummary { confirmed: 3, discarded: 1, unresolved: 1 });
+        assert_eq!(summary.display(), "3 confirmed, 1 discarded, 1 unresolved");
+
+        let remaining: Vec<(usize, Severity)> = issues.iter().map(|i| (i.line, i.severity)).collect();
+        assert_eq!(remaining, vec![(11, Severity::P0), (13, Severity::P0), (14, Severity::P1), (16, Severity::P0), (10, Severity::P1)]);
+        assert_eq!(issues[0].description, "desc [javap 确认: OrderRepository → com.acme.BaseRepository → org.springframework.data.jpa.repository.JpaRepository]");
+        assert_eq!(issues[1].description, "desc [javap 确认: OrderMapper]");
+        assert_eq!(issues[3].description, "desc [javap 确认: java.util.concurrent.Executors: public static java.util.concurrent.ExecutorService newCachedThreadPool()]");
+    }
+
+    #[test]
+    fn test_custom_executors_discarded() {
+        let imports = ImportIndex {
+            explicit: HashMap::from([("Executors".to_string(), "com.acme.concurrent.Executors".to_string())]),
+            ..Default::default()
+        };
+        let mut classes = Classes::new(Box::new(|fqn: &str| {
+            (fqn == "com.acme.concurrent.Executors").then(|| "public final class com.acme.concurrent.Executors {\n}\n".to_string())
+        }));
+        assert_eq!(verify(&mut classes, Check::ThreadPool, "Executors", "newCachedThreadPool", &imports), Outcome::Rejected);
+        assert_eq!(verify(&mut classes, Check::DataAccess, "Missing", "find", &imports), Outcome::Unresolved);
+    }
+
+    #[test]
+    fn test_with_compiled_classes() {
+        let (Some(javac), Some(javap)) = (jdk_engine::get_jdk_tool("javac"), jdk_engine::get_jdk_tool("javap")) else {
+            eprintln!("Skipping test: javac/javap not available");
+            return;
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&src).unwrap();
+        std::fs::write(src.join("Pricing.java"), "package com.acme;\n\npublic class Pricing {\n    public int price(Long id) { return 1; }\n}\n").unwrap();
+        std::fs::write(src.join("Orders.java"), "package com.acme;\n\nimport java.util.List;\n\npublic class Orders {\n    private Pricing pricing = new Pricing();\n\n    void run(List<Long> ids) {\n        for (Long id : ids) {\n            pricing.price(id);\n        }\n    }\n}\n").unwrap();
+        let classes_dir = dir.path().join("target/classes");
+        let status = Command::new(javac).arg("-d").arg(&classes_dir)
+            .args([src.join("Pricing.java"), src.join("Orders.java")])
+            .status().unwrap();
+        assert!(status.success());
+        assert_eq!(class_dirs(dir.path()), vec![classes_dir.clone()]);
+
+        let classpath = std::env::join_paths([&classes_dir]).unwrap();
+        let mut classes = Classes::new(Box::new(move |fqn: &str| run_javap(&javap, &classpath, fqn)));
+        let mut issues = vec![issue("N_PLUS_ONE_FOREACH", "src/main/java/com/acme/Orders.java", 10)];
+        let summary = apply_with(&mut issues, dir.path(), &mut classes);
+        assert_eq!(summary, VerifySummary { confirmed: 0, discarded: 1, unresolved: 0 });
+        assert!(issues.is_empty());
+    }
+}
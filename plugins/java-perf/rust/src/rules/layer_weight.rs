@@ -0,0 +1,173 @@
+//! 层级加权 (Layer Weighting) - v9.6
+//!
+//! 检测完成后，按问题所在类的层级 (Phase 1 符号表) 调整严重级别：
+//! 同样的 `OBJECT_IN_LOOP`，在 `@RestController` 处理方法里会随 QPS 放大，
+//! 在批处理工具类里则影响有限。
+//!
+//! 默认表见 [`DEFAULT_WEIGHTS`]；CLI 可用 `--layer-weight RULE=LAYER[+LAYER]`
+//! 覆盖单条规则 (`RULE=none` 关闭)，`--no-layer-weight` 关闭整个步骤。
+//! 升级的问题会在描述末尾注明 `[层级加权: ...]`。
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::symbol_table::{LayerType, SymbolTable};
+
+/// 默认加权表: 规则 → 升级为 P0 的层级
+pub const DEFAULT_WEIGHTS: &[(&str, &[LayerType])] = &[
+    ("OBJECT_IN_LOOP", &[LayerType::Controller]),
+    ("SIMPLE_DATE_FORMAT", &[LayerType::Controller]),
+    ("BLOCKING_IO", &[LayerType::Controller]),
+    ("STRING_CONCAT_LOOP", &[LayerType::Controller]),
+    ("LIST_CONTAINS_IN_LOOP", &[LayerType::Controller]),
+    ("SERIALIZER_PER_CALL", &[LayerType::Controller]),
+    ("CALENDAR_PER_CALL", &[LayerType::Controller]),
+];
+
+/// 层级加权配置
+#[derive(Debug, Clone)]
+pub struct LayerWeighting {
+    weights: BTreeMap<String, Vec<LayerType>>,
+}
+
+impl Default for LayerWeighting {
+    fn default() -> Self {
+        Self {
+            weights: DEFAULT_WEIGHTS.iter().map(|(id, layers)| (id.to_string(), layers.to_vec())).collect(),
+        }
+    }
+}
+
+impl LayerWeighting {
+    /// 关闭层级加权
+    pub fn disabled() -> Self {
+        Self { weights: BTreeMap::new() }
+    }
+
+    /// 覆盖单条规则的加权层级；层级为空表示关闭该规则的加权
+    pub fn with_overrides(mut self, overrides: impl IntoIterator<Item = (String, Vec<LayerType>)>) -> Self {
+        for (rule, layers) in overrides {
+            if layers.is_empty() {
+                self.weights.remove(&rule);
+            } else {
+                self.weights.insert(rule, layers);
+            }
+        }
+        self
+    }
+
+    /// 按所在类层级升级问题，返回升级数量
+    ///
+    /// `issue.file` 为相对 `root` 的路径 (目录扫描的显示路径)；
+    /// 同一文件有多个类时取起始行不晚于问题行的最近一个类。
+    pub fn apply(&self, issues: &mut [AstIssue], root: &Path, symbol_table: &SymbolTable) -> usize {
+        if self.weights.is_empty() {
+            return 0;
+        }
+        let root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+        let mut classes: HashMap<String, Vec<(usize, LayerType)>> = HashMap::new();
+        for info in symbol_table.classes.values() {
+            let rel = info.file.strip_prefix(&root).unwrap_or(&info.file);
+            classes.entry(rel.to_string_lossy().replace('\\', "/")).or_default().push((info.line, info.layer));
+        }
+
+        let mut escalated = 0;
+        for issue in issues.iter_mut().filter(|i| i.severity == Severity::P1) {
+            let Some(layers) = self.weights.get(&issue.issue_type) else { continue };
+            let layer = classes.get(&issue.file).and_then(|in_file| {
+                in_file.iter().filter(|(line, _)| *line <= issue.line).max_by_key(|(line, _)| *line).map(|(_, l)| *l)
+            });
+            if let Some(layer) = layer.filter(|l| layers.contains(l)) {
+                issue.severity = Severity::P0;
+                let note = i18n::format("note.layer_weight", "[层级加权: {layer} 层 P1→P0]", &[("layer", &format!("{layer:?}"))]);
+                issue.description = format!("{} {note}", issue.description);
+                escalated += 1;
+            }
+        }
+        escalated
+    }
+}
+
+/// 解析 `--layer-weight RULE=LAYER[+LAYER]` (`RULE=none` 关闭该规则的加权)
+pub fn parse_weight(s: &str) -> Result<(String, Vec<LayerType>), String> {
+    let (rule, layers) = s.split_once('=')
+        .filter(|(rule, _)| !rule.trim().is_empty())
+        .ok_or_else(|| format!("invalid layer weight '{s}': expected RULE=LAYER[+LAYER]"))?;
+    let layers = layers.split('+')
+        .map(str::trim)
+        .filter(|l| !l.is_empty() && !l.eq_ignore_ascii_case("none"))
+        .map(|l| match l.to_ascii_lowercase().as_str() {
+            "controller" => Ok(LayerType::Controller),
+            "service" => Ok(LayerType::Service),
+            "repository" => Ok(LayerType::Repository),
+            "component" => Ok(LayerType::Component),
+            _ => Err(format!("unknown layer '{l}': expected controller | service | repository | component | none")),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok((rule.trim().to_string(), layers))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_table::TypeInfo;
+
+    fn issue(id: &str, file: &str, line: usize) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: id.to_string(),
+            file: file.to_string(),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_escalates_by_class_layer() {
+        let root = Path::new("/proj");
+        let mut st = SymbolTable::new();
+        let mut controller = TypeInfo::new("OrderController", root.join("src/OrderController.java"), 3);
+        controller.add_annotation("RestController");
+        st.register_class_fqn(controller);
+        st.register_class_fqn(TypeInfo::new("BatchUtil", root.join("src/BatchUtil.java"), 1));
+
+        let mut issues = vec![
+            issue("OBJECT_IN_LOOP", "src/OrderController.java", 10),
+            issue("OBJECT_IN_LOOP", "src/BatchUtil.java", 10),
+            issue("EMPTY_CATCH", "src/OrderController.java", 12),
+        ];
+        let escalated = LayerWeighting::default().apply(&mut issues, root, &st);
+        assert_eq!(escalated, 1);
+        assert_eq!(issues[0].severity, Severity::P0);
+        assert_eq!(issues[0].description, "desc [层级加权: Controller 层 P1→P0]");
+        assert_eq!(issues[1].severity, Severity::P1);
+        assert_eq!(issues[2].severity, Severity::P1);
+
+        // 覆盖: 关闭 OBJECT_IN_LOOP，开启 EMPTY_CATCH
+        let mut issues = vec![issue("OBJECT_IN_LOOP", "src/OrderController.java", 10), issue("EMPTY_CATCH", "src/OrderController.java", 12)];
+        let weighting = LayerWeighting::default().with_overrides([
+            parse_weight("OBJECT_IN_LOOP=none").unwrap(),
+            parse_weight("EMPTY_CATCH=controller+service").unwrap(),
+        ]);
+        weighting.apply(&mut issues, root, &st);
+        assert_eq!(issues[0].severity, Severity::P1);
+        assert_eq!(issues[1].severity, Severity::P0);
+
+        assert_eq!(LayerWeighting::disabled().apply(&mut issues, root, &st), 0);
+    }
+
+    #[test]
+    fn test_parse_weight() {
+        assert_eq!(parse_weight("A=Controller+service").unwrap(), ("A".to_string(), vec![LayerType::Controller, LayerType::Service]));
+        assert_eq!(parse_weight("A=none").unwrap().1, vec![]);
+        assert!(parse_weight("A=batch").is_err());
+        assert!(parse_weight("=controller").is_err());
+    }
+}
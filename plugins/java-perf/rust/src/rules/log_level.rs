@@ -0,0 +1,147 @@
+//! 日志级别关联 (Log Level) - v9.6
+//!
+//! `LOG_ROOT_DEBUG` 表示生产环境 root 级别为 DEBUG/TRACE，此时 `log.debug("..." + x)` 不仅拼接字符串，
+//! 还会真正格式化并写盘。本模块在扫描后把两类问题关联起来：
+//! - 级别覆盖到的 `LOG_STRING_CONCAT` 末尾注明 `[日志级别: root=DEBUG @ 文件:行]`
+//! - `LOG_ROOT_DEBUG` 末尾注明受影响的拼接数量
+//!
+//! `AstIssue` 不携带上下文，root 级别从问题所在的配置文件重新解析。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use crate::ast_engine::AstIssue;
+use crate::i18n;
+use crate::scanner::logging;
+
+/// 生产 root 级别问题
+pub const ROOT_RULE_ID: &str = "LOG_ROOT_DEBUG";
+/// 日志字符串拼接问题
+pub const CONCAT_RULE_ID: &str = "LOG_STRING_CONCAT";
+
+/// 级别开启的日志方法 (TRACE/ALL 同时开启 trace())
+fn enabled_methods(level: &str) -> &'static [&'static str] {
+    match level {
+        "TRACE" | "ALL" => &["debug", "trace"],
+        _ => &["debug"],
+    }
+}
+
+/// 源码行中被级别开启的日志调用 (`log.debug(` → `debug`)
+fn verbose_call<'a>(line: &str, methods: &[&'a str]) -> Option<&'a str> {
+    methods.iter().copied().find(|method| line.contains(&format!(".{method}(")))
+}
+
+/// 关联生产 root 级别与日志拼接问题，返回被注明的拼接数量
+///
+/// `issue.file` 为相对 `root` 的路径 (目录扫描的显示路径)。
+pub fn apply(issues: &mut [AstIssue], root: &Path) -> usize {
+    // 最详细的生产 root 级别: (级别, 文件:行)
+    let mut level: Option<(String, String)> = None;
+    for issue in issues.iter().filter(|i| i.issue_type == ROOT_RULE_ID) {
+        let found = std::fs::read_to_string(root.join(&issue.file)).ok()
+            .and_then(|content| logging::parse(&content))
+            .and_then(|config| logging::prod_root_levels(&config).into_iter().find(|(line, _)| *line == issue.line));
+        if let Some((line, value)) = found {
+            if level.as_ref().is_none_or(|(current, _)| enabled_methods(current).len() < enabled_methods(&value).len()) {
+                level = Some((value, format!("{}:{line}", issue.file)));
+            }
+        }
+    }
+    let Some((level, location)) = level else { return 0 };
+    let methods = enabled_methods(&level);
+
+    let mut sources: HashMap<String, Option<String>> = HashMap::new();
+    let mut annotated = 0;
+    for issue in issues.iter_mut().filter(|i| i.issue_type == CONCAT_RULE_ID) {
+        let code = sources.entry(issue.file.clone())
+            .or_insert_with(|| std::fs::read_to_string(root.join(&issue.file)).ok());
+        let method = code.as_deref()
+            .and_then(|code| code.lines().nth(issue.line.saturating_sub(1)))
+            .and_then(|line| verbose_call(line, methods));
+        if method.is_some() {
+            let note = i18n::format("note.log_level", "[日志级别: root={level} @ {location}]", &[("level", &level), ("location", &location)]);
+            issue.description = format!("{} {note}", issue.description);
+            annotated += 1;
+        }
+    }
+
+    if annotated > 0 {
+        let calls = methods.iter().map(|m| format!("{m}()")).collect::<Vec<_>>().join("/");
+        for issue in issues.iter_mut().filter(|i| i.issue_type == ROOT_RULE_ID) {
+            let note = i18n::format(
+                "note.log_level.concat", "[{count} 处 {calls} 字符串拼接会在生产输出]",
+                &[("count", &annotated), ("calls", &calls)],
+            );
+            issue.description = format!("{} {note}", issue.description);
+        }
+    }
+    annotated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(issue_type: &str, file: &str, line: usize) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: issue_type.to_string(),
+            file: file.to_string(),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    const CONFIG: &str = "src/main/resources/logback-spring.xml";
+    const SOURCE: &str = "src/main/java/com/acme/OrderService.java";
+
+    fn project(level: &str) -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("logback-spring.xml"), format!(
+            "<configuration>\n  <springProfile name=\"dev\">\n    <root level=\"TRACE\"/>\n  </springProfile>\n  <root level=\"{level}\"/>\n</configuration>\n"
+        )).unwrap();
+        let java = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&java).unwrap();
+        std::fs::write(java.join("OrderService.java"), "class OrderService {\n  void place(Order o) {\n    log.debug(\"placing \" + o);\n    log.trace(\"items \" + o.items());\n    log.info(\"placed \" + o.id());\n  }\n}\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_root_debug_annotates_concat() {
+        let dir = project("DEBUG");
+        let mut issues = vec![
+            issue(ROOT_RULE_ID, CONFIG, 5),
+            issue(CONCAT_RULE_ID, SOURCE, 3),
+            issue(CONCAT_RULE_ID, SOURCE, 4),
+            issue(CONCAT_RULE_ID, SOURCE, 5),
+        ];
+        assert_eq!(apply(&mut issues, dir.path()), 1);
+        assert_eq!(issues[0].description, "desc [1 处 debug() 字符串拼接会在生产输出]");
+        assert_eq!(issues[1].description, format!("desc [日志级别: root=DEBUG @ {CONFIG}:5]"));
+        assert_eq!(issues[2].description, "desc");
+        assert_eq!(issues[3].description, "desc");
+
+        let dir = project("TRACE");
+        let mut issues = vec![issue(ROOT_RULE_ID, CONFIG, 5), issue(CONCAT_RULE_ID, SOURCE, 4)];
+        assert_eq!(apply(&mut issues, dir.path()), 1);
+        assert_eq!(issues[0].description, "desc [1 处 debug()/trace() 字符串拼接会在生产输出]");
+    }
+
+    #[test]
+    fn test_no_root_debug() {
+        // 无 LOG_ROOT_DEBUG 问题 (如被档案过滤) 时不关联
+        let dir = project("DEBUG");
+        let mut issues = vec![issue(CONCAT_RULE_ID, SOURCE, 3)];
+        assert_eq!(apply(&mut issues, dir.path()), 0);
+        assert_eq!(issues[0].description, "desc");
+    }
+}
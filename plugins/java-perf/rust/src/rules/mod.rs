@@ -1,5 +1,23 @@
 //! 规则模块
 //!
-//! 包含规则抑制机制
+//! 包含规则抑制机制、规则档案、层级加权、测试代码分类、批量写入配置关联、HTTP 客户端全局超时、缓存策略关联、resilience4j 配置关联、定时任务重叠、日志级别关联、复杂度预算、级别覆盖、修复工作量、生成代码源映射、javap 接收者类型校验、依赖公告库、分类标签、规则目录与 ReDoS 正则分析
 
+pub mod advisory;
+pub mod batch_insert;
+pub mod cache_policy;
+pub mod catalog;
+pub mod complexity_budget;
+pub mod effort;
+pub mod http_timeout;
+pub mod javap_verify;
+pub mod layer_weight;
+pub mod log_level;
+pub mod profile;
+pub mod redos;
+pub mod resilience;
+pub mod schedule;
+pub mod severity_override;
+pub mod source_map;
 pub mod suppression;
+pub mod taxonomy;
+pub mod test_code;
@@ -0,0 +1,213 @@
+//! 规则档案 (Rule Profiles) - v9.6
+//!
+//! 按项目类型启用/禁用规则组，并调整规则组的严重级别：
+//! - `standard`: 普通 Servlet/Java 项目，关闭 GraalVM 与 Reactor 规则组
+//! - `reactive`: 开启 Reactor 规则组，EventLoop 上的阻塞调用升级为 P0
+//! - `native-image`: 开启 GraalVM 规则组并升级为 P0
+//! - `all`: 全部规则，不调整级别 (无法识别构建文件时的默认值)
+//!
+//! 可同时指定多个档案 (`--profile reactive --profile native-image`)，
+//! 规则组只要被任一档案开启即生效。未指定时从技术栈侦测 (构建文件、主源码 import、Spring 配置) 推断。
+
+use clap::ValueEnum;
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::project_detector::{self, DetectedStack};
+
+/// Reactor/WebFlux 规则组 (仅 reactive 档案启用)
+const REACTIVE_RULES: &[&str] = &[
+    "FLUX_BLOCK",
+    "SUBSCRIBE_NO_ERROR",
+    "FLUX_COLLECT_LIST",
+    "PARALLEL_NO_RUN_ON",
+    "EMITTER_UNBOUNDED",
+    "SINKS_MANY",
+];
+
+/// GraalVM 规则组 (仅 native-image 档案启用)
+const GRAALVM_RULES: &[&str] = &[
+    "GRAALVM_CLASS_FORNAME",
+    "GRAALVM_METHOD_INVOKE",
+    "GRAALVM_PROXY",
+];
+
+/// reactive 档案下升级为 P0 的规则: 阻塞 EventLoop 会拖垮该线程上的所有连接
+const REACTIVE_BLOCKING_RULES: &[&str] = &[
+    "FLUX_BLOCK",
+    "BLOCKING_IO",
+    "SLEEP_IN_LOCK",
+    "DATASOURCE_NO_POOL",
+];
+
+/// 规则档案
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleProfile {
+    /// 全部规则
+    All,
+    /// 普通 Servlet/Java 项目
+    Standard,
+    /// Reactor / WebFlux 项目
+    Reactive,
+    /// GraalVM native-image 项目
+    NativeImage,
+}
+
+impl RuleProfile {
+    pub fn name(&self) -> &'static str {
+        match self {
+            RuleProfile::All => "all",
+            RuleProfile::Standard => "standard",
+            RuleProfile::Reactive => "reactive",
+            RuleProfile::NativeImage => "native-image",
+        }
+    }
+
+    /// 从检测到的技术栈推断档案
+    pub fn infer(stack: &DetectedStack) -> Vec<RuleProfile> {
+        // 没有构建文件时无法判断，保持全部规则
+        if stack.build_tool.is_empty() {
+            return vec![RuleProfile::All];
+        }
+
+        let mut profiles = Vec::new();
+        if stack.is_reactive {
+            profiles.push(RuleProfile::Reactive);
+        }
+        if stack.is_native_image {
+            profiles.push(RuleProfile::NativeImage);
+        }
+        if profiles.is_empty() {
+            profiles.push(RuleProfile::Standard);
+        }
+        profiles
+    }
+
+    /// 从项目目录推断档案 (单文件扫描返回 all)
+    pub fn infer_from_path(path: &Path) -> Vec<RuleProfile> {
+        if path.is_dir() {
+            Self::infer(&project_detector::detect_stack(path))
+        } else {
+            vec![RuleProfile::All]
+        }
+    }
+
+    fn opens(&self, rule_id: &str) -> bool {
+        match self {
+            RuleProfile::All => true,
+            RuleProfile::Standard => false,
+            RuleProfile::Reactive => REACTIVE_RULES.contains(&rule_id),
+            RuleProfile::NativeImage => GRAALVM_RULES.contains(&rule_id),
+        }
+    }
+
+    fn escalates(&self, rule_id: &str) -> bool {
+        match self {
+            RuleProfile::Reactive => REACTIVE_BLOCKING_RULES.contains(&rule_id),
+            RuleProfile::NativeImage => GRAALVM_RULES.contains(&rule_id),
+            _ => false,
+        }
+    }
+}
+
+/// 规则是否在给定档案组合下启用
+pub fn is_enabled(profiles: &[RuleProfile], rule_id: &str) -> bool {
+    let gated = REACTIVE_RULES.contains(&rule_id) || GRAALVM_RULES.contains(&rule_id);
+    !gated || profiles.iter().any(|p| p.opens(rule_id))
+}
+
+/// 规则是否被给定档案组合升级为 P0
+pub fn is_escalated(profiles: &[RuleProfile], rule_id: &str) -> bool {
+    profiles.iter().any(|p| p.escalates(rule_id))
+}
+
+/// 按档案过滤问题并调整严重级别
+pub fn apply(profiles: &[RuleProfile], issues: &mut Vec<AstIssue>) {
+    issues.retain(|issue| is_enabled(profiles, &issue.issue_type));
+    for issue in issues.iter_mut() {
+        if is_escalated(profiles, &issue.issue_type) {
+            issue.severity = Severity::P0;
+        }
+    }
+}
+
+/// 档案组合的显示名 (如 `reactive+native-image`)
+pub fn display(profiles: &[RuleProfile]) -> String {
+    profiles.iter().map(|p| p.name()).collect::<Vec<_>>().join("+")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: &str, severity: Severity) -> AstIssue {
+        AstIssue {
+            severity,
+            issue_type: id.to_string(),
+            file: "A.java".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: String::new(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_standard_disables_gated_groups() {
+        let profiles = [RuleProfile::Standard];
+        assert!(!is_enabled(&profiles, "FLUX_BLOCK"));
+        assert!(!is_enabled(&profiles, "GRAALVM_PROXY"));
+        assert!(is_enabled(&profiles, "N_PLUS_ONE"));
+        assert!(is_enabled(&[RuleProfile::All], "GRAALVM_PROXY"));
+    }
+
+    #[test]
+    fn test_reactive_escalates_blocking() {
+        let mut issues = vec![
+            issue("BLOCKING_IO", Severity::P1),
+            issue("GRAALVM_PROXY", Severity::P1),
+            issue("SUBSCRIBE_NO_ERROR", Severity::P1),
+        ];
+        apply(&[RuleProfile::Reactive], &mut issues);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::P0);
+        assert_eq!(issues[1].issue_type, "SUBSCRIBE_NO_ERROR");
+        assert_eq!(issues[1].severity, Severity::P1);
+    }
+
+    #[test]
+    fn test_combined_profiles() {
+        let profiles = [RuleProfile::Reactive, RuleProfile::NativeImage];
+        assert!(is_enabled(&profiles, "FLUX_BLOCK"));
+        assert!(is_enabled(&profiles, "GRAALVM_METHOD_INVOKE"));
+        assert_eq!(display(&profiles), "reactive+native-image");
+    }
+
+    #[test]
+    fn test_infer_from_stack() {
+        assert_eq!(RuleProfile::infer(&DetectedStack::default()), vec![RuleProfile::All]);
+
+        let mut stack = DetectedStack { build_tool: "maven".to_string(), ..Default::default() };
+        assert_eq!(RuleProfile::infer(&stack), vec![RuleProfile::Standard]);
+
+        stack.is_reactive = true;
+        stack.is_native_image = true;
+        assert_eq!(RuleProfile::infer(&stack), vec![RuleProfile::Reactive, RuleProfile::NativeImage]);
+    }
+
+    #[test]
+    fn test_infer_from_detected_config() {
+        // 依赖未声明 WebFlux，但配置以 reactive 方式启动
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("pom.xml"), "<project><dependencies></dependencies></project>").unwrap();
+        assert_eq!(RuleProfile::infer_from_path(dir.path()), vec![RuleProfile::Standard]);
+
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.properties"), "spring.main.web-application-type=reactive\n").unwrap();
+        assert_eq!(RuleProfile::infer_from_path(dir.path()), vec![RuleProfile::Reactive]);
+    }
+}
@@ -0,0 +1,557 @@
+//! ReDoS 检测 - 正则灾难性回溯分析 (v9.6)
+//!
+//! 把 Java 正则解析为简化语法树，查找两类指数级回溯结构:
+//!
+//! 1. **嵌套量词**: 无界重复内部还有可变长度的无界重复，如 `(a+)+`、`(\w+\s?)*`
+//! 2. **重叠分支**: 无界重复内的分支可匹配同一输入，如 `(a|aa)*`、`(\d|\w)+`
+//!
+//! 占有量词 (`a*+`) 与原子组 (`(?>...)`) 不回溯，视为安全。
+//! 只做保守近似：无法解析的正则不报告。
+
+use std::ops::Range;
+
+/// 检测到的回溯结构
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedosKind {
+    NestedQuantifier,
+    OverlappingAlternation,
+}
+
+impl RedosKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            RedosKind::NestedQuantifier => "嵌套量词",
+            RedosKind::OverlappingAlternation => "重叠分支",
+        }
+    }
+}
+
+/// 分析结果: 类型 + 触发的正则片段
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedosFinding {
+    pub kind: RedosKind,
+    pub fragment: String,
+}
+
+/// 预定义字符类
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Class {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Class {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Class::Digit => c.is_ascii_digit(),
+            Class::Word => c.is_alphanumeric() || c == '_',
+            Class::Space => c.is_whitespace(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SetItem {
+    Range(char, char),
+    Class(Class, bool),
+}
+
+/// 单个字符匹配单元
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Atom {
+    Any,
+    Literal(char),
+    /// (字符类, 是否取反)
+    Class(Class, bool),
+    Set(Vec<SetItem>, bool),
+}
+
+impl Atom {
+    fn matches(&self, c: char) -> bool {
+        match self {
+            Atom::Any => c != '\n',
+            Atom::Literal(l) => *l == c,
+            Atom::Class(class, negated) => class.matches(c) != *negated,
+            Atom::Set(items, negated) => {
+                let hit = items.iter().any(|item| match item {
+                    SetItem::Range(lo, hi) => (*lo..=*hi).contains(&c),
+                    SetItem::Class(class, neg) => class.matches(c) != *neg,
+                });
+                hit != *negated
+            }
+        }
+    }
+
+    fn literals(&self, out: &mut Vec<char>) {
+        match self {
+            Atom::Literal(c) => out.push(*c),
+            Atom::Set(items, _) => {
+                for item in items {
+                    if let SetItem::Range(lo, hi) = item {
+                        out.push(*lo);
+                        out.push(*hi);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    /// 零宽断言 / 空
+    Empty,
+    Atom(Atom),
+    Seq(Vec<Node>),
+    Alt(Vec<Node>, Range<usize>),
+    /// 原子组 / 占有量词，内部不回溯
+    Atomic(Box<Node>),
+    Repeat { node: Box<Node>, min: u32, unbounded: bool, span: Range<usize> },
+}
+
+/// 判断两个首字符集合是否可能匹配同一字符的探针字符
+const PROBES: &str = "aAzZ09_ -.\t\n@/:,;!#é";
+
+impl Node {
+    fn nullable(&self) -> bool {
+        match self {
+            Node::Empty => true,
+            Node::Atom(_) => false,
+            Node::Seq(nodes) => nodes.iter().all(Node::nullable),
+            Node::Alt(branches, _) => branches.iter().any(Node::nullable),
+            Node::Atomic(node) => node.nullable(),
+            Node::Repeat { node, min, .. } => *min == 0 || node.nullable(),
+        }
+    }
+
+    /// 可能的首字符
+    fn first(&self, out: &mut Vec<Atom>) {
+        match self {
+            Node::Empty => {}
+            Node::Atom(atom) => out.push(atom.clone()),
+            Node::Seq(nodes) => {
+                for node in nodes {
+                    node.first(out);
+                    if !node.nullable() {
+                        break;
+                    }
+                }
+            }
+            Node::Alt(branches, _) => branches.iter().for_each(|b| b.first(out)),
+            Node::Atomic(node) | Node::Repeat { node, .. } => node.first(out),
+        }
+    }
+
+    /// 是否包含可变长度的无界重复 (可以 "吃" 任意多字符)
+    fn has_unbounded_repeat(&self) -> bool {
+        match self {
+            Node::Repeat { node, unbounded, .. } => (*unbounded && !node.nullable()) || node.has_unbounded_repeat(),
+            Node::Seq(nodes) => nodes.iter().any(Node::has_unbounded_repeat),
+            Node::Alt(branches, _) => branches.iter().any(Node::has_unbounded_repeat),
+            Node::Atomic(_) | Node::Empty | Node::Atom(_) => false,
+        }
+    }
+
+    /// 不含重复的定长分支展开为字符单元序列
+    fn fixed_atoms(&self) -> Option<Vec<Atom>> {
+        match self {
+            Node::Atom(atom) => Some(vec![atom.clone()]),
+            Node::Empty => Some(Vec::new()),
+            Node::Seq(nodes) => {
+                let mut out = Vec::new();
+                for node in nodes {
+                    out.extend(node.fixed_atoms()?);
+                }
+                Some(out)
+            }
+            _ => None,
+        }
+    }
+
+    /// 两个分支能否匹配同一段输入
+    ///
+    /// 定长分支逐位比较 (`POST|PUT` 不重叠，`a|aa`、`\d|\w` 重叠)；
+    /// 含重复的分支退化为首字符比较。
+    fn ambiguous_with(&self, other: &Node) -> bool {
+        match (self.fixed_atoms(), other.fixed_atoms()) {
+            (Some(a), Some(b)) if a.is_empty() || b.is_empty() => false,
+            (Some(a), Some(b)) => {
+                let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+                if short.len() == long.len() {
+                    short.iter().zip(&long).all(|(x, y)| overlaps(std::slice::from_ref(x), std::slice::from_ref(y)))
+                } else {
+                    // a|aa: 长分支可由短分支重复拼出
+                    short.len() == 1 && long.iter().all(|y| overlaps(&short, std::slice::from_ref(y)))
+                }
+            }
+            _ => {
+                let (mut a, mut b) = (Vec::new(), Vec::new());
+                self.first(&mut a);
+                other.first(&mut b);
+                overlaps(&a, &b)
+            }
+        }
+    }
+
+    /// 在无界重复内查找重叠分支
+    fn overlapping_alt(&self) -> Option<Range<usize>> {
+        match self {
+            Node::Alt(branches, span) => {
+                for (i, a) in branches.iter().enumerate() {
+                    if branches[i + 1..].iter().any(|b| a.ambiguous_with(b)) {
+                        return Some(span.clone());
+                    }
+                }
+                branches.iter().find_map(Node::overlapping_alt)
+            }
+            Node::Seq(nodes) => nodes.iter().find_map(Node::overlapping_alt),
+            Node::Repeat { node, .. } => node.overlapping_alt(),
+            Node::Atomic(_) | Node::Empty | Node::Atom(_) => None,
+        }
+    }
+
+    fn find(&self, pattern: &str) -> Option<RedosFinding> {
+        match self {
+            Node::Repeat { node, unbounded: true, span, .. } => {
+                let fragment = || pattern.get(span.clone()).unwrap_or(pattern).to_string();
+                if node.has_unbounded_repeat() {
+                    return Some(RedosFinding { kind: RedosKind::NestedQuantifier, fragment: fragment() });
+                }
+                if node.overlapping_alt().is_some() {
+                    return Some(RedosFinding { kind: RedosKind::OverlappingAlternation, fragment: fragment() });
+                }
+                node.find(pattern)
+            }
+            Node::Repeat { node, .. } => node.find(pattern),
+            Node::Seq(nodes) => nodes.iter().find_map(|n| n.find(pattern)),
+            Node::Alt(branches, _) => branches.iter().find_map(|n| n.find(pattern)),
+            Node::Atomic(_) | Node::Empty | Node::Atom(_) => None,
+        }
+    }
+}
+
+fn overlaps(a: &[Atom], b: &[Atom]) -> bool {
+    let mut probes: Vec<char> = PROBES.chars().collect();
+    a.iter().chain(b).for_each(|atom| atom.literals(&mut probes));
+    probes.iter().any(|&c| a.iter().any(|x| x.matches(c)) && b.iter().any(|y| y.matches(c)))
+}
+
+/// 分析正则 (已去掉 Java 字符串转义)，返回第一个灾难性回溯结构
+pub fn analyze(pattern: &str) -> Option<RedosFinding> {
+    let mut parser = Parser { chars: pattern.char_indices().collect(), pos: 0, len: pattern.len() };
+    let node = parser.alternation()?;
+    if parser.pos < parser.chars.len() {
+        return None; // 未闭合的 ')' 等，放弃
+    }
+    node.find(pattern)
+}
+
+/// 去掉 Java 字符串字面量的引号并处理转义: `"\\d+"` → `\d+`
+pub fn unescape_java(literal: &str) -> Option<String> {
+    let inner = if let Some(block) = literal.strip_prefix("\"\"\"") {
+        block.strip_suffix("\"\"\"")?.trim_start_matches(['\r', '\n'])
+    } else {
+        literal.strip_prefix('"')?.strip_suffix('"')?
+    };
+    let mut out = String::with_capacity(inner.len());
+    let mut chars = inner.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+        match chars.next()? {
+            'n' => out.push('\n'),
+            't' => out.push('\t'),
+            'r' => out.push('\r'),
+            'b' => out.push('\u{8}'),
+            'f' => out.push('\u{c}'),
+            's' => out.push(' '),
+            other => out.push(other),
+        }
+    }
+    Some(out)
+}
+
+struct Parser {
+    chars: Vec<(usize, char)>,
+    pos: usize,
+    len: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).map(|(_, c)| *c)
+    }
+
+    fn offset(&self) -> usize {
+        self.chars.get(self.pos).map_or(self.len, |(i, _)| *i)
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += 1;
+        Some(c)
+    }
+
+    fn eat(&mut self, c: char) -> bool {
+        if self.peek() == Some(c) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn alternation(&mut self) -> Option<Node> {
+        let start = self.offset();
+        let mut branches = vec![self.sequence()?];
+        while self.eat('|') {
+            branches.push(self.sequence()?);
+        }
+        Some(if branches.len() == 1 { branches.pop()? } else { Node::Alt(branches, start..self.offset()) })
+    }
+
+    fn sequence(&mut self) -> Option<Node> {
+        let mut nodes = Vec::new();
+        while let Some(c) = self.peek() {
+            if c == '|' || c == ')' {
+                break;
+            }
+            let start = self.offset();
+            let atom = self.atom()?;
+            nodes.push(self.quantifier(atom, start)?);
+        }
+        Some(Node::Seq(nodes))
+    }
+
+    fn atom(&mut self) -> Option<Node> {
+        match self.next()? {
+            '.' => Some(Node::Atom(Atom::Any)),
+            '^' | '$' => Some(Node::Empty),
+            '[' => self.set().map(Node::Atom),
+            '(' => self.group(),
+            '\\' => self.escape(),
+            c => Some(Node::Atom(Atom::Literal(c))),
+        }
+    }
+
+    fn group(&mut self) -> Option<Node> {
+        let mut atomic = false;
+        let mut zero_width = false;
+        if self.eat('?') {
+            match self.next()? {
+                ':' => {}
+                '>' => atomic = true,
+                '=' | '!' => zero_width = true,
+                '<' => {
+                    if self.eat('=') || self.eat('!') {
+                        zero_width = true;
+                    } else {
+                        // 命名组 (?<name>...)
+                        while self.next()? != '>' {}
+                    }
+                }
+                _ => {
+                    // 内联标志 (?i) / (?i:...)
+                    loop {
+                        match self.next()? {
+                            ')' => return Some(Node::Empty),
+                            ':' => break,
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+        let inner = self.alternation()?;
+        if !self.eat(')') {
+            return None;
+        }
+        Some(match (atomic, zero_width) {
+            (_, true) => Node::Empty,
+            (true, _) => Node::Atomic(Box::new(inner)),
+            _ => inner,
+        })
+    }
+
+    fn escape(&mut self) -> Option<Node> {
+        let c = self.next()?;
+        Some(match c {
+            'd' => Node::Atom(Atom::Class(Class::Digit, false)),
+            'D' => Node::Atom(Atom::Class(Class::Digit, true)),
+            'w' => Node::Atom(Atom::Class(Class::Word, false)),
+            'W' => Node::Atom(Atom::Class(Class::Word, true)),
+            's' => Node::Atom(Atom::Class(Class::Space, false)),
+            'S' => Node::Atom(Atom::Class(Class::Space, true)),
+            'b' | 'B' | 'A' | 'z' | 'Z' | 'G' => Node::Empty,
+            'p' | 'P' => {
+                // \p{Alpha} 等 Unicode 类，近似为单词字符
+                if self.eat('{') {
+                    while self.next()? != '}' {}
+                }
+                Node::Atom(Atom::Class(Class::Word, c == 'P'))
+            }
+            'Q' => {
+                let mut lits = Vec::new();
+                while let Some(ch) = self.next() {
+                    if ch == '\\' && self.eat('E') {
+                        break;
+                    }
+                    lits.push(Node::Atom(Atom::Literal(ch)));
+                }
+                Node::Seq(lits)
+            }
+            '1'..='9' | 'k' => {
+                // 反向引用，内容未知
+                if c == 'k' && self.eat('<') {
+                    while self.next()? != '>' {}
+                }
+                Node::Atom(Atom::Any)
+            }
+            'n' => Node::Atom(Atom::Literal('\n')),
+            't' => Node::Atom(Atom::Literal('\t')),
+            'r' => Node::Atom(Atom::Literal('\r')),
+            other => Node::Atom(Atom::Literal(other)),
+        })
+    }
+
+    fn set(&mut self) -> Option<Atom> {
+        let negated = self.eat('^');
+        let mut items = Vec::new();
+        let mut first = true;
+        loop {
+            let c = self.next()?;
+            match c {
+                ']' if !first => break,
+                '[' => {
+                    // 嵌套/交集类 [a-z&&[^b]]，近似为并集
+                    if let Atom::Set(inner, _) = self.set()? {
+                        items.extend(inner);
+                    }
+                }
+                '&' if self.eat('&') => {}
+                '\\' => match self.next()? {
+                    'd' => items.push(SetItem::Class(Class::Digit, false)),
+                    'D' => items.push(SetItem::Class(Class::Digit, true)),
+                    'w' => items.push(SetItem::Class(Class::Word, false)),
+                    'W' => items.push(SetItem::Class(Class::Word, true)),
+                    's' => items.push(SetItem::Class(Class::Space, false)),
+                    'S' => items.push(SetItem::Class(Class::Space, true)),
+                    'n' => items.push(SetItem::Range('\n', '\n')),
+                    't' => items.push(SetItem::Range('\t', '\t')),
+                    'p' | 'P' => {
+                        if self.eat('{') {
+                            while self.next()? != '}' {}
+                        }
+                        items.push(SetItem::Class(Class::Word, false));
+                    }
+                    other => items.push(SetItem::Range(other, other)),
+                },
+                lo => {
+                    if self.peek() == Some('-') && self.chars.get(self.pos + 1).is_some_and(|(_, c)| *c != ']') {
+                        self.pos += 1;
+                        let hi = match self.next()? {
+                            '\\' => self.next()?,
+                            hi => hi,
+                        };
+                        items.push(SetItem::Range(lo, hi));
+                    } else {
+                        items.push(SetItem::Range(lo, lo));
+                    }
+                }
+            }
+            first = false;
+        }
+        Some(Atom::Set(items, negated))
+    }
+
+    fn quantifier(&mut self, node: Node, start: usize) -> Option<Node> {
+        let (min, unbounded) = match self.peek() {
+            Some('*') => { self.pos += 1; (0, true) }
+            Some('+') => { self.pos += 1; (1, true) }
+            Some('?') => { self.pos += 1; (0, false) }
+            Some('{') => {
+                let save = self.pos;
+                self.pos += 1;
+                let mut spec = String::new();
+                loop {
+                    match self.next() {
+                        Some('}') => break,
+                        Some(c) if c.is_ascii_digit() || c == ',' => spec.push(c),
+                        _ => {
+                            // 不是量词，按字面量 '{' 处理
+                            self.pos = save;
+                            return Some(node);
+                        }
+                    }
+                }
+                let mut parts = spec.splitn(2, ',');
+                let min = parts.next()?.parse().ok()?;
+                let unbounded = parts.next().is_some_and(str::is_empty);
+                (min, unbounded)
+            }
+            _ => return Some(node),
+        };
+        let possessive = self.eat('+');
+        if !possessive {
+            self.eat('?'); // 惰性量词同样回溯
+        }
+        let span = start..self.offset();
+        let repeat = Node::Repeat { node: Box::new(node), min, unbounded, span };
+        Some(if possessive { Node::Atomic(Box::new(repeat)) } else { repeat })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kind(pattern: &str) -> Option<RedosKind> {
+        analyze(pattern).map(|f| f.kind)
+    }
+
+    #[test]
+    fn test_nested_quantifiers() {
+        assert_eq!(kind("(a+)+"), Some(RedosKind::NestedQuantifier));
+        assert_eq!(kind("^(\\w+\\s?)*$"), Some(RedosKind::NestedQuantifier));
+        assert_eq!(kind("([a-zA-Z0-9]+)*@example\\.com"), Some(RedosKind::NestedQuantifier));
+        assert_eq!(kind("(?:\\d{1,3}+)*"), None); // 占有量词
+        assert_eq!(analyze("x(a*)*y").unwrap().fragment, "(a*)*");
+    }
+
+    #[test]
+    fn test_overlapping_alternation() {
+        assert_eq!(kind("(a|aa)*c"), Some(RedosKind::OverlappingAlternation));
+        assert_eq!(kind("(\\d|\\w)+$"), Some(RedosKind::OverlappingAlternation));
+        assert_eq!(kind("(.|\\s)*"), Some(RedosKind::OverlappingAlternation));
+        assert_eq!(kind("(GET|POST|PUT)+"), None);
+        assert_eq!(kind("(\\d|[a-f])+"), None);
+        assert_eq!(kind("(a|ab)*c"), None);
+    }
+
+    #[test]
+    fn test_safe_patterns() {
+        for safe in [
+            "^[a-zA-Z0-9._%+-]+@[a-zA-Z0-9.-]+\\.[a-zA-Z]{2,}$",
+            "\\d{4}-\\d{2}-\\d{2}",
+            "(?>a+)+",
+            "(?i)^select\\s+\\*",
+            "[(]+",
+            "a{2,5}b",
+            "(?<year>\\d{4})-(?<month>\\d{2})",
+            "unclosed(",
+        ] {
+            assert_eq!(analyze(safe), None, "{safe}");
+        }
+    }
+
+    #[test]
+    fn test_unescape_java() {
+        assert_eq!(unescape_java(r#""\\d+\\.\\d+""#).as_deref(), Some(r"\d+\.\d+"));
+        assert_eq!(unescape_java(r#""a\"b""#).as_deref(), Some("a\"b"));
+        assert_eq!(unescape_java("not a literal"), None);
+    }
+}
@@ -0,0 +1,251 @@
+//! resilience4j 配置关联 (Resilience) - v9.6
+//!
+//! `@Retry(name = "x")` / `@CircuitBreaker(name = "x")` 的参数在配置文件中。目录扫描后按
+//! 符号表中的方法注解 (Phase 1 `extract_methods`) 查找 `resilience4j.{retry,circuitbreaker}` 配置，
+//! 按 `instances.x` → `base-config` 指向的 `configs.y` → `configs.default` 的顺序解析：
+//! - `RETRY_NO_BACKOFF`: `wait-duration` 为 0
+//! - `RETRY_EXCESSIVE_ATTEMPTS`: `max-attempts` > 5 (resilience4j 重试实例均视为远程调用)
+//! - [`CIRCUIT_BREAKER_RULE_ID`]: `sliding-window-size` ≤ 1
+//!
+//! `@Retryable` 的参数直接写在注解中，由 AST 规则检查。
+
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::rules::effort;
+use crate::rules::test_code;
+use crate::scanner::config::{self, PropertyEntry};
+use crate::scanner::rule_handlers::MAX_REMOTE_ATTEMPTS;
+use crate::symbol_table::{MethodInfo, SymbolTable};
+
+/// 熔断窗口过小
+pub const CIRCUIT_BREAKER_RULE_ID: &str = "CIRCUIT_BREAKER_TINY_WINDOW";
+
+const NO_BACKOFF_DESCRIPTION: &str = "重试未配置退避 (或退避为 0)：下游故障时各实例同步重试，放大故障期流量形成重试风暴，应配置指数退避 (@Backoff(delay, multiplier) / resilience4j enable-exponential-backoff)";
+const EXCESSIVE_ATTEMPTS_DESCRIPTION: &str = "远程调用重试次数超过 5 次：下游故障时单个请求放大为多次调用，请求线程长时间占用，应降低 maxAttempts 并配合熔断";
+const TINY_WINDOW_DESCRIPTION: &str = "熔断器 slidingWindowSize 为 1：单次调用结果即决定熔断状态，熔断器在打开与关闭之间抖动，应使用足够大的统计窗口 (≥ 10) 并设置 minimumNumberOfCalls";
+
+/// 配置中的一项: (文件, 配置项)
+type Setting<'a> = (&'a str, &'a PropertyEntry);
+
+/// 全部 Spring 配置项 (键已归一化: 小写、去掉 `-` / `_`)
+struct Resilience4jConfig {
+    entries: Vec<(String, String, PropertyEntry)>,
+}
+
+impl Resilience4jConfig {
+    fn scan(root: &Path) -> Self {
+        let entries = config::spring_config_files(root).into_iter()
+            .flat_map(|(file, entries)| entries.into_iter().map(move |entry| (normalize(&entry.key), file.clone(), entry)))
+            .filter(|(key, _, _)| key.starts_with("resilience4j."))
+            .collect();
+        Resilience4jConfig { entries }
+    }
+
+    fn get(&self, key: &str) -> Option<Setting<'_>> {
+        let key = normalize(key);
+        self.entries.iter().find(|(k, _, _)| *k == key).map(|(_, file, entry)| (file.as_str(), entry))
+    }
+
+    /// 实例属性: `instances.x` (旧版 `backends.x`) → `base-config` → `configs.default`
+    fn resolve(&self, module: &str, instance: &str, property: &str) -> Option<Setting<'_>> {
+        for group in ["instances", "backends"] {
+            if let Some(setting) = self.get(&format!("resilience4j.{module}.{group}.{instance}.{property}")) {
+                return Some(setting);
+            }
+        }
+        let base = ["instances", "backends"].iter()
+            .find_map(|group| self.get(&format!("resilience4j.{module}.{group}.{instance}.base-config")))
+            .map(|(_, entry)| entry.value.clone());
+        let inherited = base.iter().map(String::as_str).chain(["default"])
+            .find_map(|config| self.get(&format!("resilience4j.{module}.configs.{config}.{property}")));
+        inherited
+    }
+}
+
+fn normalize(key: &str) -> String {
+    key.chars().filter(|c| *c != '-' && *c != '_').collect::<String>().to_ascii_lowercase()
+}
+
+/// `0` / `0s` / `0ms` / `PT0S` 均为零时长
+fn is_zero_duration(value: &str) -> bool {
+    let value = value.trim().trim_start_matches("PT").trim_start_matches("pt");
+    let number: String = value.chars().take_while(|c| c.is_ascii_digit() || *c == '.').collect();
+    number.parse::<f64>().is_ok_and(|n| n == 0.0)
+}
+
+/// `Retry(name = "inventory", fallbackMethod = "x")` → (`Retry`, `inventory`)
+fn named_annotation(annotation: &str) -> Option<(&str, &str)> {
+    let (kind, args) = annotation.split_once('(')?;
+    let kind = kind.trim().rsplit('.').next()?;
+    let name = args.split(',').find_map(|arg| {
+        let (key, value) = arg.split_once('=')?;
+        (key.trim() == "name").then(|| value.trim().trim_end_matches(')').trim())
+    })?;
+    let name = name.strip_prefix('"')?.strip_suffix('"')?;
+    Some((kind, name))
+}
+
+/// 方法上 resilience4j 注解命中的问题: (规则 ID, 注解, 配置)
+fn check_method<'a>(method: &'a MethodInfo, config: &'a Resilience4jConfig) -> Vec<(&'static str, &'a str, Setting<'a>)> {
+    let mut found = Vec::new();
+    for annotation in &method.annotations {
+        match named_annotation(annotation) {
+            Some(("Retry", name)) => {
+                if let Some(setting) = config.resolve("retry", name, "wait-duration").filter(|(_, e)| is_zero_duration(&e.value)) {
+                    found.push(("RETRY_NO_BACKOFF", annotation.as_str(), setting));
+                }
+                let attempts = config.resolve("retry", name, "max-attempts")
+                    .filter(|(_, e)| e.value.parse::<u32>().is_ok_and(|n| n > MAX_REMOTE_ATTEMPTS));
+                if let Some(setting) = attempts {
+                    found.push(("RETRY_EXCESSIVE_ATTEMPTS", annotation.as_str(), setting));
+                }
+            }
+            Some(("CircuitBreaker", name)) => {
+                let window = config.resolve("circuitbreaker", name, "sliding-window-size")
+                    .filter(|(_, e)| e.value.parse::<u32>().is_ok_and(|n| n <= 1));
+                if let Some(setting) = window {
+                    found.push((CIRCUIT_BREAKER_RULE_ID, annotation.as_str(), setting));
+                }
+            }
+            _ => {}
+        }
+    }
+    found
+}
+
+/// 关联 resilience4j 注解与配置，追加问题并返回数量
+///
+/// 方法所在文件取符号表中同名类的文件，测试源码中的方法不检查。
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path, symbol_table: &SymbolTable) -> usize {
+    let annotated = symbol_table.methods.values()
+        .any(|m| m.annotations.iter().any(|a| named_annotation(a).is_some()));
+    if !annotated {
+        return 0;
+    }
+    let config = Resilience4jConfig::scan(root);
+    if config.entries.is_empty() {
+        return 0;
+    }
+
+    let canonical_root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let mut added: Vec<AstIssue> = Vec::new();
+    for method in symbol_table.methods.values() {
+        let found = check_method(method, &config);
+        if found.is_empty() {
+            continue;
+        }
+        let Some(info) = symbol_table.lookup_by_simple_name(&method.class).into_iter().next() else { continue };
+        let rel = info.file.strip_prefix(&canonical_root).or_else(|_| info.file.strip_prefix(root)).unwrap_or(&info.file);
+        if test_code::is_test_source(rel) {
+            continue;
+        }
+        let file = rel.to_string_lossy().replace('\\', "/");
+        for (rule_id, annotation, (config_file, entry)) in found {
+            let description = match rule_id {
+                "RETRY_NO_BACKOFF" => NO_BACKOFF_DESCRIPTION,
+                "RETRY_EXCESSIVE_ATTEMPTS" => EXCESSIVE_ATTEMPTS_DESCRIPTION,
+                _ => TINY_WINDOW_DESCRIPTION,
+            };
+            added.push(AstIssue {
+                severity: Severity::P1,
+                issue_type: rule_id.to_string(),
+                file: file.clone(),
+                line: method.line,
+                column: 1,
+                end_line: method.line,
+                end_column: 1,
+                description: format!(
+                    "{} [注解: @{annotation} @ {}.{}(); 配置: {config_file}:{} `{}={}`]",
+                    i18n::text(&format!("rule.{rule_id}"), description), method.class, method.name, entry.line, entry.key, entry.value,
+                ),
+                owner: None,
+                effort: effort::builtin(rule_id),
+            });
+        }
+    }
+    added.sort_by(|a, b| (&a.file, a.line, &a.issue_type).cmp(&(&b.file, b.line, &b.issue_type)));
+    let count = added.len();
+    issues.extend(added);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_table::TypeInfo;
+
+    const CONFIG: &str = "\
+resilience4j:
+  retry:
+    configs:
+      default:
+        max-attempts: 3
+      aggressive:
+        wait-duration: 0ms
+    instances:
+      inventory:
+        base-config: aggressive
+        max-attempts: 8
+      pricing:
+        maxAttempts: 2
+  circuitbreaker:
+    instances:
+      inventory:
+        sliding-window-size: 1
+      pricing:
+        slidingWindowSize: 50
+";
+
+    fn project(config: &str) -> (tempfile::TempDir, SymbolTable) {
+        let dir = tempfile::tempdir().unwrap();
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.yml"), config).unwrap();
+
+        let mut table = SymbolTable::new();
+        let file = dir.path().join("src/main/java/com/acme/InventoryClient.java");
+        table.register_class(TypeInfo::new("InventoryClient", file, 5));
+        for (name, line, annotations) in [
+            ("reserve", 10, vec!["Retry(name = \"inventory\", fallbackMethod = \"fallback\")", "CircuitBreaker(name = \"inventory\")"]),
+            ("price", 20, vec!["Retry(name = \"pricing\")", "CircuitBreaker(name = \"pricing\")"]),
+            ("stock", 30, vec!["Retry(name = INSTANCE)"]),
+        ] {
+            let mut method = MethodInfo::new(name, "InventoryClient", line);
+            method.annotations = annotations.into_iter().map(String::from).collect();
+            table.register_method("InventoryClient", method);
+        }
+        (dir, table)
+    }
+
+    #[test]
+    fn test_resilience4j_config_correlation() {
+        let (dir, table) = project(CONFIG);
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, dir.path(), &table), 3);
+
+        let found: Vec<_> = issues.iter().map(|i| (i.issue_type.as_str(), i.line)).collect();
+        assert_eq!(found, vec![(CIRCUIT_BREAKER_RULE_ID, 10), ("RETRY_EXCESSIVE_ATTEMPTS", 10), ("RETRY_NO_BACKOFF", 10)]);
+        assert_eq!(issues[0].file, "src/main/java/com/acme/InventoryClient.java");
+        assert!(issues[0].description.ends_with(
+            "[注解: @CircuitBreaker(name = \"inventory\") @ InventoryClient.reserve(); 配置: src/main/resources/application.yml:17 `resilience4j.circuitbreaker.instances.inventory.sliding-window-size=1`]"
+        ));
+        // wait-duration 来自 base-config 指向的 configs.aggressive
+        assert!(issues[2].description.ends_with("application.yml:7 `resilience4j.retry.configs.aggressive.wait-duration=0ms`]"));
+    }
+
+    #[test]
+    fn test_resilience4j_defaults() {
+        let (dir, table) = project("resilience4j:\n  retry:\n    configs:\n      default:\n        max-attempts: 10\n        wait-duration: 500ms\n");
+        let mut issues = Vec::new();
+        apply(&mut issues, dir.path(), &table);
+        // configs.default 对未单独配置的实例生效
+        let found: Vec<_> = issues.iter().map(|i| (i.issue_type.as_str(), i.line)).collect();
+        assert_eq!(found, vec![("RETRY_EXCESSIVE_ATTEMPTS", 10), ("RETRY_EXCESSIVE_ATTEMPTS", 20)]);
+
+        assert!(is_zero_duration("PT0S") && is_zero_duration("0") && !is_zero_duration("100ms"));
+        assert_eq!(named_annotation("Retry(name = \"a\")"), Some(("Retry", "a")));
+        assert_eq!(named_annotation("Retryable(maxAttempts = 3)"), None);
+    }
+}
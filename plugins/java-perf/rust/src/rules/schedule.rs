@@ -0,0 +1,274 @@
+//! 定时任务重叠 (Scheduled Overlap) - v9.6
+//!
+//! Spring Boot 默认的 `TaskScheduler` 只有一个线程：同一时刻触发的 `@Scheduled(cron = ...)` 任务只能依次执行，
+//! 后触发的任务被推迟，耗时长的任务会拖住其他所有任务。目录扫描后按符号表中的方法注解 (Phase 1 `extract_methods`)
+//! 解析 cron 表达式 (Spring 六段格式与 `@hourly` 等宏)，同一调度器、同一时区上触发时刻有交集的任务报告 [`RULE_ID`]。
+//!
+//! 调度器不是单线程时不检查：`spring.task.scheduling.pool.size` > 1、`spring.threads.virtual.enabled=true`、
+//! 项目自定义了 `TaskScheduler` Bean 或 `SchedulingConfigurer`。指定了 `scheduler` 的任务线程数未知，同样跳过；
+//! 含 `L` / `W` / `#` 或 `${...}` 占位符的表达式无法展开，不参与比较。
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::rules::effort;
+use crate::rules::test_code;
+use crate::scanner::annotation;
+use crate::scanner::config;
+use crate::symbol_table::{MethodInfo, SymbolTable};
+
+/// 同一单线程调度器上触发时刻重叠的 cron 任务
+pub const RULE_ID: &str = "SCHEDULED_CRON_OVERLAP";
+
+const DESCRIPTION: &str = "多个 @Scheduled(cron) 任务在同一时刻触发，默认调度器只有一个线程：任务依次执行，后触发的任务被推迟，耗时长的任务会拖住其他任务；应错开触发时间或配置 spring.task.scheduling.pool.size";
+
+/// 自定义调度器 Bean 的返回类型
+const SCHEDULER_TYPES: &[&str] = &["TaskScheduler", "ThreadPoolTaskScheduler", "ScheduledExecutorService", "SimpleAsyncTaskScheduler"];
+
+/// 秒、分、时、日、月、周 的取值范围
+const FIELD_RANGES: [(u32, u32); 6] = [(0, 59), (0, 59), (0, 23), (1, 31), (1, 12), (0, 7)];
+
+const MONTH_NAMES: &[&str] = &["JAN", "FEB", "MAR", "APR", "MAY", "JUN", "JUL", "AUG", "SEP", "OCT", "NOV", "DEC"];
+const DAY_NAMES: &[&str] = &["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// 展开后的 cron 表达式：每段为取值位图
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cron {
+    fields: [u64; 6],
+}
+
+impl Cron {
+    /// 解析 Spring cron 表达式 (`秒 分 时 日 月 周` 或 `@daily` 等宏)
+    pub fn parse(expression: &str) -> Option<Self> {
+        let expression = match expression.trim() {
+            "@yearly" | "@annually" => "0 0 0 1 1 *",
+            "@monthly" => "0 0 0 1 * *",
+            "@weekly" => "0 0 0 * * 0",
+            "@daily" | "@midnight" => "0 0 0 * * *",
+            "@hourly" => "0 0 * * * *",
+            other => other,
+        };
+        let parts: Vec<&str> = expression.split_whitespace().collect();
+        if parts.len() != 6 {
+            return None;
+        }
+        let mut fields = [0u64; 6];
+        for (i, part) in parts.iter().enumerate() {
+            let names = match i {
+                4 => MONTH_NAMES,
+                5 => DAY_NAMES,
+                _ => &[],
+            };
+            fields[i] = parse_field(part, FIELD_RANGES[i], names, i == 4)?;
+        }
+        // 周日可写作 0 或 7
+        if fields[5] & (1 << 7) != 0 {
+            fields[5] = (fields[5] & !(1 << 7)) | 1;
+        }
+        Some(Self { fields })
+    }
+
+    /// 两个表达式是否存在共同的触发时刻 (Spring 的日与周同时满足才触发)
+    pub fn overlaps(&self, other: &Cron) -> bool {
+        self.fields.iter().zip(&other.fields).all(|(a, b)| a & b != 0)
+    }
+
+    /// 与 `other` 的第一个共同触发时刻 (`HH:MM:SS`)
+    fn first_common_time(&self, other: &Cron) -> String {
+        let first = |i: usize| (self.fields[i] & other.fields[i]).trailing_zeros();
+        format!("{:02}:{:02}:{:02}", first(2), first(1), first(0))
+    }
+}
+
+/// 单段取值位图：`*` / `?` / `5` / `1-5` / `*/15` / `10/5` / `1,3,5` / `MON-FRI`
+fn parse_field(field: &str, (min, max): (u32, u32), names: &[&str], one_based_names: bool) -> Option<u64> {
+    let value = |text: &str| -> Option<u32> {
+        let upper = text.to_ascii_uppercase();
+        let n = match names.iter().position(|name| *name == upper) {
+            Some(index) => index as u32 + u32::from(one_based_names),
+            None => text.parse().ok()?,
+        };
+        (min..=max).contains(&n).then_some(n)
+    };
+    let mut bits = 0u64;
+    for part in field.split(',') {
+        let (range, step) = match part.split_once('/') {
+            Some((range, step)) => (range, Some(step.parse::<u32>().ok().filter(|s| *s > 0)?)),
+            None => (part, None),
+        };
+        let (start, end) = match range {
+            "*" | "?" => (min, max),
+            _ => match range.split_once('-') {
+                Some((a, b)) => (value(a)?, value(b)?),
+                None if step.is_some() => (value(range)?, max),
+                None => (value(range)?, value(range)?),
+            },
+        };
+        if start > end {
+            return None;
+        }
+        for n in (start..=end).step_by(step.unwrap_or(1) as usize) {
+            bits |= 1 << n;
+        }
+    }
+    Some(bits)
+}
+
+/// 一个 cron 定时任务
+struct CronJob<'a> {
+    method: &'a MethodInfo,
+    expression: String,
+    cron: Cron,
+}
+
+/// 默认调度器是否为单线程
+fn default_scheduler_single_threaded(root: &Path, symbol_table: &SymbolTable) -> bool {
+    let custom_scheduler = symbol_table.methods.values().any(|m| {
+        m.name == "configureTasks"
+            || (m.annotations.iter().any(|a| annotation::parse_text(a).0 == "Bean")
+                && m.return_type.as_deref().is_some_and(|t| SCHEDULER_TYPES.contains(&t)))
+    });
+    if custom_scheduler {
+        return false;
+    }
+    let multi_threaded = config::spring_config_files(root).into_iter().flat_map(|(_, entries)| entries).any(|entry| {
+        match config::normalize_key(&entry.key).as_str() {
+            "spring.task.scheduling.pool.size" => entry.value.trim().parse::<u32>().map_or(true, |n| n > 1),
+            "spring.threads.virtual.enabled" => entry.value.trim() == "true",
+            _ => false,
+        }
+    });
+    !multi_threaded
+}
+
+/// 关联同一单线程调度器上的 cron 任务，追加问题并返回数量
+///
+/// 每个任务只报告一次，列出与它重叠且声明在它之前 (按类名、行号) 的任务。测试源码中的方法不检查。
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path, symbol_table: &SymbolTable) -> usize {
+    // (时区) → 任务
+    let mut groups: BTreeMap<String, Vec<CronJob>> = BTreeMap::new();
+    for method in symbol_table.methods.values() {
+        for text in &method.annotations {
+            let (name, args) = annotation::parse_text(text);
+            if name != "Scheduled" {
+                continue;
+            }
+            let arg = |key: &str| args.iter().find(|(k, _)| k == key).map(|(_, v)| v.as_str());
+            if arg("scheduler").is_some() {
+                continue;
+            }
+            let Some((expression, cron)) = arg("cron").and_then(|e| Cron::parse(e).map(|c| (e, c))) else { continue };
+            groups.entry(arg("zone").unwrap_or_default().to_string()).or_default()
+                .push(CronJob { method, expression: expression.to_string(), cron });
+        }
+    }
+    if groups.values().all(|jobs| jobs.len() < 2) || !default_scheduler_single_threaded(root, symbol_table) {
+        return 0;
+    }
+
+    let canonical_root = std::fs::canonicalize(root).unwrap_or_else(|_| root.to_path_buf());
+    let mut added: Vec<AstIssue> = Vec::new();
+    for jobs in groups.values_mut() {
+        jobs.sort_by(|a, b| (&a.method.class, a.method.line).cmp(&(&b.method.class, b.method.line)));
+        for (i, job) in jobs.iter().enumerate() {
+            let partners: Vec<String> = jobs[..i].iter()
+                .filter(|other| other.cron.overlaps(&job.cron))
+                .map(|other| format!(
+                    "{}.{}() `{}` 于 {}", other.method.class, other.method.name, other.expression, other.cron.first_common_time(&job.cron),
+                ))
+                .collect();
+            if partners.is_empty() {
+                continue;
+            }
+            let Some(info) = symbol_table.lookup_by_simple_name(&job.method.class).into_iter().next() else { continue };
+            let rel = info.file.strip_prefix(&canonical_root).or_else(|_| info.file.strip_prefix(root)).unwrap_or(&info.file);
+            if test_code::is_test_source(rel) {
+                continue;
+            }
+            added.push(AstIssue {
+                severity: Severity::P1,
+                issue_type: RULE_ID.to_string(),
+                file: rel.to_string_lossy().replace('\\', "/"),
+                line: job.method.line,
+                column: 1,
+                end_line: job.method.line,
+                end_column: 1,
+                description: format!(
+                    "{} [任务: {}.{}() `{}`; 重叠: {}]",
+                    i18n::text(&format!("rule.{RULE_ID}"), DESCRIPTION), job.method.class, job.method.name, job.expression, partners.join("; "),
+                ),
+                owner: None,
+                effort: effort::builtin(RULE_ID),
+            });
+        }
+    }
+    added.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    let count = added.len();
+    issues.extend(added);
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::symbol_table::TypeInfo;
+
+    #[test]
+    fn test_cron_parse_and_overlap() {
+        let nightly = Cron::parse("0 0 2 * * *").unwrap();
+        let weekdays = Cron::parse("0 0 2 ? * MON-FRI").unwrap();
+        let quarter = Cron::parse("0 */15 * * * *").unwrap();
+        let sunday = Cron::parse("0 30 2 * * 7").unwrap();
+        assert!(nightly.overlaps(&weekdays));
+        assert!(nightly.overlaps(&quarter));
+        assert_eq!(nightly.first_common_time(&quarter), "02:00:00");
+        assert!(!nightly.overlaps(&sunday));
+        assert!(sunday.overlaps(&Cron::parse("0 30 2 ? * SUN").unwrap()));
+        assert_eq!(Cron::parse("@hourly"), Cron::parse("0 0 * * * *"));
+        assert!(!Cron::parse("0 0 3 1 * *").unwrap().overlaps(&Cron::parse("0 0 3 2 * *").unwrap()));
+
+        for invalid in ["0 0 2 L * *", "0 0 2 * * MON#1", "${job.cron}", "0 0 25 * * *", "0 0 2 * *", "-"] {
+            assert_eq!(Cron::parse(invalid), None, "{invalid}");
+        }
+    }
+
+    fn project(jobs: &[(&str, usize, &str)]) -> (tempfile::TempDir, SymbolTable) {
+        let dir = tempfile::tempdir().unwrap();
+        let mut table = SymbolTable::new();
+        let file = dir.path().join("src/main/java/com/acme/ReportJobs.java");
+        table.register_class(TypeInfo::new("ReportJobs", file, 3));
+        for (name, line, annotation) in jobs {
+            let mut method = MethodInfo::new(name, "ReportJobs", *line);
+            method.annotations = vec![annotation.to_string()];
+            table.register_method("ReportJobs", method);
+        }
+        (dir, table)
+    }
+
+    #[test]
+    fn test_overlapping_cron_jobs() {
+        let (dir, table) = project(&[
+            ("settle", 5, r#"Scheduled(cron = "0 0 2 * * *")"#),
+            ("export", 10, r#"Scheduled(cron = "0 0 1,2 * * MON-FRI")"#),
+            ("cleanup", 15, r#"Scheduled(cron = "0 30 3 * * *")"#),
+            ("remote", 20, r#"Scheduled(cron = "0 0 2 * * *", scheduler = "batchScheduler")"#),
+            ("utc", 25, r#"Scheduled(cron = "0 0 2 * * *", zone = "UTC")"#),
+        ]);
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, dir.path(), &table), 1);
+        assert_eq!(issues[0].file, "src/main/java/com/acme/ReportJobs.java");
+        assert_eq!(issues[0].line, 10);
+        assert!(issues[0].description.ends_with(
+            "[任务: ReportJobs.export() `0 0 1,2 * * MON-FRI`; 重叠: ReportJobs.settle() `0 0 2 * * *` 于 02:00:00]"
+        ));
+
+        // 多线程调度器不检查
+        let resources = dir.path().join("src/main/resources");
+        std::fs::create_dir_all(&resources).unwrap();
+        std::fs::write(resources.join("application.yml"), "spring:\n  task:\n    scheduling:\n      pool:\n        size: 4\n").unwrap();
+        let mut issues = Vec::new();
+        assert_eq!(apply(&mut issues, dir.path(), &table), 0);
+    }
+}
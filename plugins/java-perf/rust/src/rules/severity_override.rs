@@ -0,0 +1,160 @@
+//! 规则级别覆盖 (Severity Override) - v9.6
+//!
+//! 团队对同一规则的容忍度不同：有的项目要把 `AUTOWIRED_FIELD` 当作 P1 修掉，
+//! 有的项目只想把 `ATOMIC_SPIN` 当作提示。覆盖在档案、层级加权之后执行，优先级最高：
+//!
+//! ```toml
+//! [severity]
+//! AUTOWIRED_FIELD = "p1"
+//! LOG_STRING_CONCAT = "info"
+//! ```
+//!
+//! CLI `--severity RULE=LEVEL` (可重复) 覆盖 `.java-perf.toml` 中的同一规则。
+//! 级别取值 `p0 | p1 | p2 | info`；被调整的问题在描述末尾注明 `[级别覆盖: P1→Info]`。
+
+use clap::ValueEnum;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use crate::ast_engine::{AstIssue, Severity};
+use crate::i18n;
+use crate::rules::complexity_budget::CONFIG_FILE;
+
+/// 规则 → 覆盖后的级别
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SeverityOverrides {
+    levels: BTreeMap<String, Severity>,
+}
+
+impl SeverityOverrides {
+    /// 读取 `<root>/.java-perf.toml` 的 `[severity]`；文件不存在时为空
+    pub fn load(root: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(root.join(CONFIG_FILE)) {
+            Ok(content) => Self::parse(&content),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let doc: toml_edit::DocumentMut = content.parse().map_err(|e| format!("{CONFIG_FILE}: {e}"))?;
+        let Some(section) = doc.get("severity").and_then(|item| item.as_table_like()) else {
+            return Ok(Self::default());
+        };
+        let levels = section.iter()
+            .map(|(rule, item)| {
+                item.as_str()
+                    .ok_or_else(|| "expected a string".to_string())
+                    .and_then(parse_level)
+                    .map(|level| (rule.to_string(), level))
+                    .map_err(|e| format!("{CONFIG_FILE} [severity] {rule}: {e}"))
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(SeverityOverrides { levels })
+    }
+
+    /// 追加覆盖 (同一规则以后者为准)
+    pub fn with_overrides(mut self, overrides: impl IntoIterator<Item = (String, Severity)>) -> Self {
+        self.levels.extend(overrides);
+        self
+    }
+
+    /// 规则的覆盖级别
+    pub fn get(&self, rule_id: &str) -> Option<Severity> {
+        self.levels.get(rule_id).copied()
+    }
+
+    /// 调整问题级别，返回调整数量
+    pub fn apply(&self, issues: &mut [AstIssue]) -> usize {
+        let mut changed = 0;
+        for issue in issues.iter_mut() {
+            let Some(&level) = self.levels.get(&issue.issue_type) else { continue };
+            if level != issue.severity {
+                let note = i18n::format(
+                    "note.severity_override", "[级别覆盖: {from}→{to}]",
+                    &[("from", &format!("{:?}", issue.severity)), ("to", &format!("{level:?}"))],
+                );
+                issue.description = format!("{} {note}", issue.description);
+                issue.severity = level;
+                changed += 1;
+            }
+        }
+        changed
+    }
+}
+
+/// 读取 `.java-perf.toml [severity]` 并叠加 CLI 覆盖后调整问题级别，返回调整数量
+///
+/// `config_root` 为 `.java-perf.toml` 所在目录；配置无效时忽略配置文件，只应用 CLI 覆盖。
+pub fn apply(issues: &mut [AstIssue], config_root: &Path, cli: &[(String, Severity)]) -> usize {
+    let config = SeverityOverrides::load(config_root).unwrap_or_else(|e| {
+        tracing::warn!("忽略级别覆盖配置 {e}");
+        SeverityOverrides::default()
+    });
+    config.with_overrides(cli.iter().cloned()).apply(issues)
+}
+
+fn parse_level(level: &str) -> Result<Severity, String> {
+    Severity::from_str(level.trim(), true).map_err(|_| format!("unknown severity '{level}': expected p0 | p1 | p2 | info"))
+}
+
+/// 解析 `--severity RULE=LEVEL`
+pub fn parse_override(s: &str) -> Result<(String, Severity), String> {
+    let (rule, level) = s.split_once('=')
+        .filter(|(rule, _)| !rule.trim().is_empty())
+        .ok_or_else(|| format!("invalid severity override '{s}': expected RULE=LEVEL"))?;
+    Ok((rule.trim().to_string(), parse_level(level)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: &str, severity: Severity) -> AstIssue {
+        AstIssue {
+            severity,
+            issue_type: id.to_string(),
+            file: "A.java".to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_config_and_cli_overrides() {
+        let config = SeverityOverrides::parse("[severity]\nAUTOWIRED_FIELD = \"p1\"\nLOG_STRING_CONCAT = \"Info\"\n").unwrap();
+        let overrides = config.with_overrides([("AUTOWIRED_FIELD".to_string(), Severity::P2)]);
+
+        let mut issues = vec![
+            issue("AUTOWIRED_FIELD", Severity::Info),
+            issue("LOG_STRING_CONCAT", Severity::P1),
+            issue("N_PLUS_ONE", Severity::P0),
+            issue("LOG_STRING_CONCAT", Severity::Info),
+        ];
+        assert_eq!(overrides.apply(&mut issues), 2);
+        // CLI 覆盖优先于配置文件
+        assert_eq!(issues[0].severity, Severity::P2);
+        assert_eq!(issues[0].description, "desc [级别覆盖: Info→P2]");
+        assert_eq!(issues[1].severity, Severity::Info);
+        assert_eq!(issues[2].severity, Severity::P0);
+        // 级别未变化时不追加说明
+        assert_eq!(issues[3].description, "desc");
+
+        assert_eq!(SeverityOverrides::parse("").unwrap(), SeverityOverrides::default());
+        assert!(SeverityOverrides::parse("[severity]\nATOMIC_SPIN = \"p3\"\n").unwrap_err().contains("ATOMIC_SPIN"));
+        assert!(SeverityOverrides::parse("[severity]\nATOMIC_SPIN = 2\n").is_err());
+    }
+
+    #[test]
+    fn test_parse_override() {
+        assert_eq!(parse_override("ATOMIC_SPIN=info"), Ok(("ATOMIC_SPIN".to_string(), Severity::Info)));
+        assert_eq!(parse_override(" CACHEABLE_NO_KEY = P1 "), Ok(("CACHEABLE_NO_KEY".to_string(), Severity::P1)));
+        assert!(parse_override("ATOMIC_SPIN").is_err());
+        assert!(parse_override("=p1").is_err());
+        assert!(parse_override("ATOMIC_SPIN=low").unwrap_err().contains("expected p0 | p1 | p2 | info"));
+    }
+}
@@ -0,0 +1,496 @@
+//! 生成代码源映射 (Source Map) - v9.6
+//!
+//! 注解处理器 (MapStruct / Immutables / JPA 元模型) 与 delombok 的输出位于 `target/generated-sources`、
+//! `build/generated/sources` 下，每次构建都会重新生成，直接修改没有意义。本模块在扫描后按启发式
+//! 把这些问题映射回开发者可以编辑的源文件：
+//! - 生成类 → 源类: 按处理器命名约定 (`OrderMapperImpl` → `OrderMapper`，`ImmutableOrder` → `Order`，
+//!   `Order_` → `Order`，delombok 同名)，在同一模块的 `src/main/java` (测试输出为 `src/test/java`) 下查找
+//! - 行号: 源类中同名方法 (MapStruct 映射方法、delombok 保留的手写方法) → Lombok 访问器对应的字段 →
+//!   触发生成的注解 (`@Mapper`、`@Data`、`@Value.Immutable` 等) → 类声明
+//!
+//! 映射后的问题末尾注明 `[生成代码 (MapStruct @Mapper): target/.../OrderMapperImpl.java:42]`；
+//! 找不到源文件时保留生成文件位置并注明。同一源位置的重复问题只保留一个。
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use tree_sitter::Node;
+
+use crate::ast_engine::AstIssue;
+use crate::i18n;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+
+/// 注解处理器的命名约定
+struct Processor {
+    name: &'static str,
+    /// `@Generated` 注解或生成目录中出现的标记
+    markers: &'static [&'static str],
+    /// 生成类名 → 源类名
+    origin: fn(&str) -> Option<&str>,
+    /// 源类上触发生成的注解 (简单名或 `Outer.Inner`)
+    annotations: &'static [&'static str],
+}
+
+/// Lombok 类级注解
+const LOMBOK_ANNOTATIONS: &[&str] = &[
+    "Data", "Value", "Builder", "SuperBuilder", "Getter", "Setter", "With", "ToString", "EqualsAndHashCode",
+    "AllArgsConstructor", "NoArgsConstructor", "RequiredArgsConstructor",
+];
+
+const PROCESSORS: &[Processor] = &[
+    Processor {
+        name: "MapStruct",
+        markers: &["org.mapstruct"],
+        origin: |class| class.strip_suffix("Impl").filter(|name| !name.is_empty()),
+        annotations: &["Mapper"],
+    },
+    Processor {
+        name: "Immutables",
+        markers: &["org.immutables"],
+        origin: |class| class.strip_prefix("Immutable").or_else(|| class.strip_prefix("Modifiable")).filter(|name| !name.is_empty()),
+        annotations: &["Value.Immutable", "Value.Modifiable"],
+    },
+    Processor {
+        name: "JPA Metamodel",
+        markers: &["jpamodelgen", "org.hibernate.processor"],
+        origin: |class| class.strip_suffix('_').filter(|name| !name.is_empty()),
+        annotations: &["Entity", "Embeddable", "MappedSuperclass"],
+    },
+    Processor {
+        name: "Lombok",
+        markers: &["lombok"],
+        origin: |class| Some(class),
+        annotations: LOMBOK_ANNOTATIONS,
+    },
+];
+
+/// 生成代码目录中的位置
+#[derive(Debug, PartialEq)]
+struct GeneratedPath {
+    /// 所在模块目录 (相对扫描根目录，根模块为空)
+    module: String,
+    /// 测试源码集的生成输出
+    test: bool,
+    /// 生成工具目录 (`annotations` / `delombok` / `annotationProcessor` 等)
+    tool: String,
+}
+
+/// 识别 Maven `target/generated-(test-)sources/<tool>/` 与 Gradle `build/generated/...` 下的文件
+fn generated_path(file: &str) -> Option<GeneratedPath> {
+    let parts: Vec<&str> = file.split('/').collect();
+    (0..parts.len().saturating_sub(2)).find_map(|i| {
+        let module = parts[..i].join("/");
+        match (parts[i], parts[i + 1]) {
+            ("target", set @ ("generated-sources" | "generated-test-sources")) => Some(GeneratedPath {
+                module,
+                test: set == "generated-test-sources",
+                tool: parts.get(i + 2).copied().unwrap_or_default().to_string(),
+            }),
+            // build/generated/sources/<tool>/java/<sourceSet>/ 或旧版 build/generated/source/<tool>/<sourceSet>/
+            ("build", "generated") => {
+                let layout = &parts[i + 2..parts.len() - 1];
+                Some(GeneratedPath {
+                    module,
+                    test: layout.iter().take(4).any(|&p| p == "test"),
+                    tool: layout.get(1).copied().unwrap_or_default().to_string(),
+                })
+            }
+            _ => None,
+        }
+    })
+}
+
+/// 源码中的位置 (1-based，end_column 开区间)
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Span {
+    line: usize,
+    column: usize,
+    end_line: usize,
+    end_column: usize,
+}
+
+impl Span {
+    fn of(node: Node) -> Self {
+        Self {
+            line: node.start_position().row + 1,
+            column: node.start_position().column + 1,
+            end_line: node.end_position().row + 1,
+            end_column: node.end_position().column + 1,
+        }
+    }
+}
+
+fn text<'a>(node: Node, code: &'a str) -> &'a str {
+    node.utf8_text(code.as_bytes()).unwrap_or("")
+}
+
+/// 深度优先遍历所有具名节点
+fn descendants(root: Node) -> Vec<Node> {
+    let mut nodes = Vec::new();
+    let mut stack = vec![root];
+    while let Some(node) = stack.pop() {
+        nodes.push(node);
+        let mut cursor = node.walk();
+        let children: Vec<Node> = node.named_children(&mut cursor).collect();
+        stack.extend(children.into_iter().rev());
+    }
+    nodes
+}
+
+fn is_type_declaration(kind: &str) -> bool {
+    matches!(kind, "class_declaration" | "interface_declaration" | "enum_declaration" | "record_declaration")
+}
+
+/// 生成文件的结构信息
+struct GeneratedSource {
+    package: Option<String>,
+    /// 顶层类名
+    class: String,
+    /// `@Generated` 等注解文本 (识别处理器)
+    generated_by: String,
+    /// 问题所在的方法: (方法名, 参数个数, 所在类名)
+    method: Option<(String, usize, String)>,
+}
+
+fn param_count(method: Node) -> usize {
+    method.child_by_field_name("parameters")
+        .map(|list| {
+            let mut cursor = list.walk();
+            list.named_children(&mut cursor)
+                .filter(|p| matches!(p.kind(), "formal_parameter" | "spread_parameter"))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn annotation_name<'a>(node: Node, code: &'a str) -> Option<&'a str> {
+    matches!(node.kind(), "annotation" | "marker_annotation")
+        .then(|| node.child_by_field_name("name"))
+        .flatten()
+        .map(|name| text(name, code))
+}
+
+fn inspect_generated(root: Node, code: &str, line: usize) -> Option<GeneratedSource> {
+    let row = line.saturating_sub(1);
+    let nodes = descendants(root);
+    let package = nodes.iter()
+        .find(|n| n.kind() == "package_declaration")
+        .and_then(|p| p.named_child(0))
+        .map(|n| text(n, code).to_string());
+    let class = nodes.iter()
+        .find(|n| is_type_declaration(n.kind()))
+        .and_then(|n| n.child_by_field_name("name"))
+        .map(|n| text(n, code).to_string())?;
+    let generated_by = nodes.iter()
+        .filter(|n| annotation_name(**n, code).is_some_and(|name| name.ends_with("Generated")))
+        .map(|n| text(*n, code))
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    let method = nodes.iter()
+        .filter(|n| matches!(n.kind(), "method_declaration" | "constructor_declaration"))
+        .rfind(|n| n.start_position().row <= row && row <= n.end_position().row)
+        .and_then(|method| {
+            let name = text(method.child_by_field_name("name")?, code).to_string();
+            let mut parent = method.parent();
+            while let Some(node) = parent.filter(|n| !is_type_declaration(n.kind())) {
+                parent = node.parent();
+            }
+            let owner = parent.and_then(|n| n.child_by_field_name("name")).map(|n| text(n, code).to_string()).unwrap_or_default();
+            Some((name, param_count(*method), owner))
+        });
+
+    Some(GeneratedSource { package, class, generated_by, method })
+}
+
+/// 注解名是否匹配 (`lombok.Data` 匹配 `Data`，`Value.Immutable` 需完整匹配后缀)
+fn annotation_matches(name: &str, expected: &str) -> bool {
+    name == expected || name.ends_with(&format!(".{expected}"))
+}
+
+/// Lombok 生成方法对应的注解 (按优先级)
+fn lombok_annotations_for(method: &str, owner: &str) -> &'static [&'static str] {
+    match method {
+        _ if owner.ends_with("Builder") || method == "builder" || method == "toBuilder" => &["Builder", "SuperBuilder"],
+        "equals" | "hashCode" | "canEqual" => &["EqualsAndHashCode", "Data", "Value"],
+        "toString" => &["ToString", "Data", "Value"],
+        _ => &[],
+    }
+}
+
+/// Lombok 访问器对应的字段名 (`getTotalAmount` → `totalAmount`)
+fn accessor_field(method: &str) -> Option<String> {
+    let rest = ["get", "set", "is", "with"].iter().find_map(|prefix| method.strip_prefix(prefix))?;
+    let mut chars = rest.chars();
+    let first = chars.next().filter(|c| c.is_uppercase())?;
+    Some(first.to_lowercase().chain(chars).collect())
+}
+
+/// 源文件中对应生成位置的节点: (位置, 说明)
+fn locate_origin(root: Node, code: &str, generated: &GeneratedSource, processor: Option<&Processor>) -> Option<(Span, Option<String>)> {
+    let nodes = descendants(root);
+    let annotations: Vec<(Node, &str)> = nodes.iter()
+        .filter_map(|n| annotation_name(*n, code).map(|name| (*n, name)))
+        .collect();
+    let find_annotation = |expected: &[&str]| {
+        expected.iter().find_map(|e| annotations.iter().find(|(_, name)| annotation_matches(name, e)))
+            .map(|(node, name)| (Span::of(*node), Some(format!("@{name}"))))
+    };
+
+    if let Some((method, params, owner)) = &generated.method {
+        let same_name: Vec<Node> = nodes.iter()
+            .filter(|n| matches!(n.kind(), "method_declaration" | "constructor_declaration"))
+            .filter(|n| n.child_by_field_name("name").is_some_and(|name| text(name, code) == method))
+            .copied()
+            .collect();
+        if let Some(found) = same_name.iter().find(|n| param_count(**n) == *params).or(same_name.first()) {
+            let name = found.child_by_field_name("name")?;
+            return Some((Span::of(name), None));
+        }
+
+        if processor.is_some_and(|p| p.name == "Lombok") {
+            if let Some(field) = accessor_field(method) {
+                let declarator = nodes.iter()
+                    .filter(|n| n.kind() == "field_declaration")
+                    .flat_map(|n| {
+                        let mut cursor = n.walk();
+                        n.children_by_field_name("declarator", &mut cursor).collect::<Vec<_>>()
+                    })
+                    .find_map(|d| d.child_by_field_name("name").filter(|name| text(*name, code) == field));
+                if let Some(name) = declarator {
+                    return Some((Span::of(name), None));
+                }
+            }
+            if let Some(found) = find_annotation(lombok_annotations_for(method, owner)) {
+                return Some(found);
+            }
+        }
+    }
+
+    if let Some(found) = processor.and_then(|p| find_annotation(p.annotations)) {
+        return Some(found);
+    }
+    let class = nodes.iter().find(|n| is_type_declaration(n.kind()))?.child_by_field_name("name")?;
+    Some((Span::of(class), None))
+}
+
+/// 源码目录候选 (delombok 插件默认读取 `src/main/lombok`)
+fn source_dirs(test: bool) -> &'static [&'static str] {
+    if test {
+        &["src/test/java", "src/test/lombok"]
+    } else {
+        &["src/main/java", "src/main/lombok"]
+    }
+}
+
+/// 映射结果 (失败时返回识别出的处理器名)
+struct Mapping {
+    file: String,
+    span: Span,
+    label: String,
+}
+
+fn map_issue(root: &Path, analyzer: &JavaTreeSitterAnalyzer, issue: &AstIssue, location: &GeneratedPath) -> Result<Mapping, String> {
+    let code = std::fs::read_to_string(root.join(&issue.file)).map_err(|_| String::new())?;
+    let tree = analyzer.parse(&code).map_err(|_| String::new())?;
+    let generated = inspect_generated(tree.root_node(), &code, issue.line).ok_or_else(String::new)?;
+
+    let identified = PROCESSORS.iter().find(|p| {
+        p.markers.iter().any(|m| generated.generated_by.contains(m) || location.tool.to_lowercase().contains(m))
+    });
+    // 未识别处理器时依次尝试各命名约定
+    let candidates: Vec<&Processor> = match identified {
+        Some(p) => vec![p],
+        None => PROCESSORS.iter().collect(),
+    };
+    let package_dir = generated.package.as_deref().map(|p| p.replace('.', "/")).unwrap_or_default();
+
+    for processor in candidates {
+        let Some(origin_class) = (processor.origin)(&generated.class) else { continue };
+        for dir in source_dirs(location.test) {
+            let file = [location.module.as_str(), dir, package_dir.as_str(), &format!("{origin_class}.java")]
+                .iter()
+                .filter(|part| !part.is_empty())
+                .copied()
+                .collect::<Vec<_>>()
+                .join("/");
+            let Ok(origin_code) = std::fs::read_to_string(root.join(&file)) else { continue };
+            let Ok(origin_tree) = analyzer.parse(&origin_code) else { continue };
+            let Some((span, annotation)) = locate_origin(origin_tree.root_node(), &origin_code, &generated, identified) else { continue };
+            let label = match (identified, annotation) {
+                (Some(p), Some(annotation)) => format!("{} {annotation}", p.name),
+                (Some(p), None) => p.name.to_string(),
+                (None, Some(annotation)) => annotation,
+                (None, None) => String::new(),
+            };
+            return Ok(Mapping { file, span, label });
+        }
+    }
+    Err(identified.map(|p| p.name.to_string()).unwrap_or_default())
+}
+
+/// 把生成代码上的问题映射回源文件，返回成功映射的问题数量
+///
+/// `issue.file` 为相对 `root` 的路径 (目录扫描的显示路径)。
+pub fn apply(issues: &mut Vec<AstIssue>, root: &Path) -> usize {
+    let generated: Vec<(usize, GeneratedPath)> = issues.iter()
+        .enumerate()
+        .filter(|(_, issue)| issue.file.ends_with(".java"))
+        .filter_map(|(idx, issue)| generated_path(&issue.file).map(|location| (idx, location)))
+        .collect();
+    if generated.is_empty() {
+        return 0;
+    }
+    let Ok(analyzer) = JavaTreeSitterAnalyzer::new() else { return 0 };
+
+    let mut mapped = 0;
+    let mut seen: HashSet<(String, String, usize)> = HashSet::new();
+    let mut duplicates: HashSet<usize> = HashSet::new();
+    let mut failures: HashMap<String, String> = HashMap::new();
+    for (idx, location) in generated {
+        let issue = &mut issues[idx];
+        let origin = format!("{}:{}", issue.file, issue.line);
+        let result = match failures.get(&issue.file) {
+            Some(processor) => Err(processor.clone()),
+            None => map_issue(root, &analyzer, issue, &location),
+        };
+        match result {
+            Ok(mapping) => {
+                let label = if mapping.label.is_empty() { String::new() } else { format!(" ({})", mapping.label) };
+                let note = i18n::format("note.generated", "[生成代码{label}: {origin}]", &[("label", &label), ("origin", &origin)]);
+                issue.description = format!("{} {note}", issue.description);
+                issue.file = mapping.file;
+                issue.line = mapping.span.line;
+                issue.column = mapping.span.column;
+                issue.end_line = mapping.span.end_line;
+                issue.end_column = mapping.span.end_column;
+                if !seen.insert((issue.issue_type.clone(), issue.file.clone(), issue.line)) {
+                    duplicates.insert(idx);
+                }
+                mapped += 1;
+            }
+            Err(processor) => {
+                let label = if processor.is_empty() { String::new() } else { format!(" ({processor})") };
+                let note = i18n::format("note.generated.unmapped", "[生成代码{label}: 未找到对应源文件]", &[("label", &label)]);
+                issue.description = format!("{} {note}", issue.description);
+                failures.insert(issue.file.clone(), processor);
+            }
+        }
+    }
+
+    if !duplicates.is_empty() {
+        let mut idx = 0;
+        issues.retain(|_| {
+            idx += 1;
+            !duplicates.contains(&(idx - 1))
+        });
+    }
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(issue_type: &str, file: &str, line: usize) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: issue_type.to_string(),
+            file: file.to_string(),
+            line,
+            column: 1,
+            end_line: line,
+            end_column: 2,
+            description: "desc".to_string(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    fn write(root: &Path, file: &str, content: &str) {
+        let path = root.join(file);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_generated_path() {
+        assert_eq!(generated_path("order/target/generated-sources/annotations/com/acme/OrderMapperImpl.java"), Some(GeneratedPath {
+            module: "order".to_string(),
+            test: false,
+            tool: "annotations".to_string(),
+        }));
+        assert_eq!(generated_path("build/generated/sources/annotationProcessor/java/test/com/acme/Order_.java"), Some(GeneratedPath {
+            module: String::new(),
+            test: true,
+            tool: "annotationProcessor".to_string(),
+        }));
+        assert!(generated_path("target/generated-test-sources/delombok/com/acme/Order.java").unwrap().test);
+        assert_eq!(generated_path("src/main/java/com/acme/target/Order.java"), None);
+    }
+
+    #[test]
+    fn test_mapstruct_maps_to_mapper_method() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "src/main/java/com/acme/OrderMapper.java",
+            "package com.acme;\n\n@Mapper\npublic interface OrderMapper {\n    OrderDto toDto(Order order);\n}\n");
+        let generated = "target/generated-sources/annotations/com/acme/OrderMapperImpl.java";
+        write(dir.path(), generated,
+            "package com.acme;\n\n@Generated(value = \"org.mapstruct.ap.MappingProcessor\")\npublic class OrderMapperImpl implements OrderMapper {\n    @Override\n    public OrderDto toDto(Order order) {\n        String tags = \"\";\n        for (String t : order.getTags()) { tags += t; }\n        return new OrderDto(tags);\n    }\n\n    protected String helper(Order order) {\n        return order.toString();\n    }\n}\n");
+
+        let mut issues = vec![
+            issue("STRING_CONCAT_LOOP", generated, 8),
+            issue("OTHER", generated, 13),
+            issue("OTHER", "src/main/java/com/acme/OrderMapper.java", 5),
+        ];
+        assert_eq!(apply(&mut issues, dir.path()), 2);
+        assert_eq!(issues[0].file, "src/main/java/com/acme/OrderMapper.java");
+        assert_eq!((issues[0].line, issues[0].column, issues[0].end_column), (5, 14, 19));
+        assert_eq!(issues[0].description, format!("desc [生成代码 (MapStruct): {generated}:8]"));
+        // 手写 Mapper 中不存在的方法映射到 @Mapper
+        assert_eq!(issues[1].line, 3);
+        assert_eq!(issues[1].description, format!("desc [生成代码 (MapStruct @Mapper): {generated}:13]"));
+        assert_eq!(issues[2].description, "desc");
+    }
+
+    #[test]
+    fn test_delombok_maps_accessors_and_annotations() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "billing/src/main/java/com/acme/Invoice.java",
+            "package com.acme;\n\n@Data\n@Builder\npublic class Invoice {\n    private List<Line> lines;\n}\n");
+        let generated = "billing/target/generated-sources/delombok/com/acme/Invoice.java";
+        write(dir.path(), generated,
+            "package com.acme;\n\npublic class Invoice {\n    private List<Line> lines;\n\n    @java.lang.SuppressWarnings(\"all\")\n    public List<Line> getLines() {\n        return new ArrayList<>(this.lines);\n    }\n\n    @java.lang.SuppressWarnings(\"all\")\n    public int hashCode() {\n        return lines.stream().mapToInt(Line::hashCode).sum();\n    }\n\n    public static class InvoiceBuilder {\n        public Invoice build() {\n            return new Invoice();\n        }\n    }\n}\n");
+
+        let mut issues = vec![
+            issue("A", generated, 8),
+            issue("B", generated, 13),
+            issue("C", generated, 18),
+            issue("C", generated, 17),
+        ];
+        assert_eq!(apply(&mut issues, dir.path()), 4);
+        let mapped: Vec<(&str, &str, usize)> = issues.iter().map(|i| (i.issue_type.as_str(), i.file.as_str(), i.line)).collect();
+        let origin = "billing/src/main/java/com/acme/Invoice.java";
+        // 同一源位置的重复问题只保留一个
+        assert_eq!(mapped, vec![("A", origin, 6), ("B", origin, 3), ("C", origin, 4)]);
+        assert_eq!(issues[1].description, format!("desc [生成代码 (Lombok @Data): {generated}:13]"));
+    }
+
+    #[test]
+    fn test_unmapped_generated_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let generated = "build/generated/sources/annotationProcessor/java/main/com/acme/Order_.java";
+        write(dir.path(), generated,
+            "package com.acme;\n\n@Generated(\"org.hibernate.jpamodelgen.JPAMetaModelEntityProcessor\")\npublic abstract class Order_ {\n}\n");
+        let mut issues = vec![issue("A", generated, 4)];
+        assert_eq!(apply(&mut issues, dir.path()), 0);
+        assert_eq!(issues[0].file, generated);
+        assert_eq!(issues[0].description, "desc [生成代码 (JPA Metamodel): 未找到对应源文件]");
+
+        write(dir.path(), "src/main/java/com/acme/Order.java", "package com.acme;\n\n@Entity\npublic class Order {\n}\n");
+        let mut issues = vec![issue("A", generated, 4)];
+        assert_eq!(apply(&mut issues, dir.path()), 1);
+        assert_eq!((issues[0].file.as_str(), issues[0].line), ("src/main/java/com/acme/Order.java", 3));
+    }
+}
@@ -0,0 +1,323 @@
+//! 规则分类标签 (Rule Taxonomy) - v9.6
+//!
+//! 每条规则在 [`REGISTRY`] 中登记一个或多个标签，第一个标签为主分类：
+//! - JSON 报告的每个问题带 `tags` 字段，SARIF 规则带 `properties.tags`
+//! - `scan --only-tags db,concurrency` 只保留带有任一标签的问题
+//! - Markdown 完整报告按主分类分组 (未登记的规则，如 WASM 脚本规则，归入"其他")
+//!
+//! 新增规则时须在此登记，`test_every_ast_rule_is_tagged` 会检查遗漏。
+
+use clap::ValueEnum;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::i18n;
+
+/// 规则标签 (声明顺序即 Markdown 报告中同等严重程度分类的排列顺序)
+#[derive(ValueEnum, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Tag {
+    /// 线程池、锁、并发容器、异步等待
+    Concurrency,
+    /// 内存泄漏、无界集合/缓存
+    Memory,
+    /// 分配压力、GC 负担
+    Gc,
+    /// 算法复杂度、热路径上的重复计算
+    Cpu,
+    /// 数据库 / Redis 访问与连接池
+    Db,
+    /// 阻塞 IO、远程调用、超时与重试
+    Io,
+    /// Reactor / WebFlux
+    Reactive,
+    /// 应用配置、框架与构建配置
+    Config,
+    /// 日志代码与日志框架配置
+    Logging,
+    /// Dockerfile / docker-compose
+    Docker,
+    /// CI 流水线构建性能
+    Build,
+    /// Spark 作业 (Scala)
+    Spark,
+    /// 与安全相关 (注入、ReDoS、敏感信息、进程控制)
+    SecurityAdjacent,
+    /// 测试源码
+    Test,
+}
+
+impl Tag {
+    pub fn name(self) -> &'static str {
+        match self {
+            Tag::Concurrency => "concurrency",
+            Tag::Memory => "memory",
+            Tag::Gc => "gc",
+            Tag::Cpu => "cpu",
+            Tag::Db => "db",
+            Tag::Io => "io",
+            Tag::Reactive => "reactive",
+            Tag::Config => "config",
+            Tag::Logging => "logging",
+            Tag::Docker => "docker",
+            Tag::Build => "build",
+            Tag::Spark => "spark",
+            Tag::SecurityAdjacent => "security-adjacent",
+            Tag::Test => "test",
+        }
+    }
+
+    /// Markdown 分组标题
+    pub fn title(self) -> String {
+        let default = match self {
+            Tag::Concurrency => "🧵 并发",
+            Tag::Memory => "💾 内存",
+            Tag::Gc => "♻️ GC / 分配",
+            Tag::Cpu => "🔥 CPU",
+            Tag::Db => "🗄️ 数据库",
+            Tag::Io => "🌐 IO / 远程调用",
+            Tag::Reactive => "⚡ 响应式",
+            Tag::Config => "⚙️ 配置",
+            Tag::Logging => "📝 日志",
+            Tag::Docker => "🐳 Docker",
+            Tag::Build => "🏗️ 构建",
+            Tag::Spark => "✨ Spark",
+            Tag::SecurityAdjacent => "🛡️ 安全相关",
+            Tag::Test => "🧪 测试",
+        };
+        i18n::text(&format!("report.category.{}", self.name()), default).to_string()
+    }
+}
+
+use Tag::*;
+
+/// 规则 → 标签 (第一个为主分类)
+pub const REGISTRY: &[(&str, &[Tag])] = &[
+    // AST: 数据库
+    ("N_PLUS_ONE", &[Db, Io]),
+    ("N_PLUS_ONE_WHILE", &[Db, Io]),
+    ("N_PLUS_ONE_FOREACH", &[Db, Io]),
+    ("SAVE_IN_LOOP_NO_BATCH", &[Db, Io]),
+    ("SELECT_STAR", &[Db]),
+    ("LIKE_LEADING_WILDCARD", &[Db]),
+    ("UNBOUNDED_QUERY_RESULT", &[Db, Memory]),
+    ("CONNECTION_NOT_CLOSED", &[Db]),
+    ("DATASOURCE_NO_POOL", &[Db, Config]),
+    ("TRANSACTIONAL_REQUIRES_NEW", &[Db]),
+    ("TRANSACTION_SELF_CALL", &[Db]),
+    ("JPA_BIDIRECTIONAL_JSON_CYCLE", &[Db, Memory]),
+    ("FILTER_DB_CALL", &[Db, Io]),
+    ("SQL_STRING_CONCAT", &[Db, SecurityAdjacent]),
+    ("REDIS_SETNX_NO_EXPIRE", &[Db, Concurrency]),
+    ("REDIS_JEDIS_SHARED", &[Db, Concurrency]),
+    ("REDIS_FULL_SCAN_IN_REQUEST", &[Db, Cpu]),
+    ("REDIS_OP_IN_LOOP", &[Db, Io]),
+    ("RETRY_NON_IDEMPOTENT", &[Db, Io]),
+    // AST: 并发
+    ("UNBOUNDED_POOL", &[Concurrency, Memory]),
+    ("ASYNC_DEFAULT_POOL", &[Concurrency]),
+    ("SCHEDULED_FIXED_RATE", &[Concurrency]),
+    ("SCHEDULED_CRON_OVERLAP", &[Concurrency, Config]),
+    ("SCHEDULED_POOL_ZERO_CORE", &[Concurrency, Cpu]),
+    ("THREAD_IN_REQUEST_SCOPE", &[Concurrency]),
+    ("THREAD_NO_NAME", &[Concurrency]),
+    ("SYNC_METHOD", &[Concurrency]),
+    ("SYNC_BLOCK", &[Concurrency]),
+    ("SLEEP_IN_LOCK", &[Concurrency]),
+    ("LOCK_METHOD_CALL", &[Concurrency]),
+    ("SEMAPHORE_NO_RELEASE", &[Concurrency]),
+    ("LATCH_COUNTDOWN_NOT_IN_FINALLY", &[Concurrency]),
+    ("DOUBLE_CHECKED_LOCKING", &[Concurrency]),
+    ("ATOMIC_SPIN", &[Concurrency, Cpu]),
+    ("VOLATILE_ARRAY", &[Concurrency]),
+    ("SYNCHRONIZED_MAP", &[Concurrency]),
+    ("SIMPLE_DATE_FORMAT", &[Concurrency, Gc]),
+    ("RANDOM_SHARED", &[Concurrency]),
+    ("FUTURE_GET_NO_TIMEOUT", &[Concurrency, Io]),
+    ("AWAIT_NO_TIMEOUT", &[Concurrency]),
+    ("COMPLETABLE_JOIN", &[Concurrency, Io]),
+    ("COMPLETABLE_GET_NO_TIMEOUT", &[Concurrency, Io]),
+    ("LISTENER_BLOCKING_CALL", &[Concurrency, Io]),
+    ("LISTENER_AUTO_ACK_SLOW", &[Concurrency, Io]),
+    ("LISTENER_TRANSACTIONAL", &[Concurrency, Db]),
+    // AST: 内存
+    ("STATIC_COLLECTION", &[Memory]),
+    ("THREADLOCAL_LEAK", &[Memory, Concurrency]),
+    ("MDC_LEAK", &[Memory, Logging]),
+    ("CACHE_NO_EXPIRE", &[Memory]),
+    ("CACHEABLE_NO_KEY", &[Memory, Config]),
+    ("STRING_INTERN", &[Memory]),
+    ("LISTENER_UNBOUNDED_BUFFER", &[Memory, Concurrency]),
+    ("LARGE_ARRAY", &[Memory, Gc]),
+    // AST: GC / 分配
+    ("OBJECT_IN_LOOP", &[Gc]),
+    ("SOFT_REFERENCE", &[Gc, Memory]),
+    ("FINALIZE_OVERRIDE", &[Gc]),
+    ("COLLECTION_NO_CAPACITY", &[Gc]),
+    ("DATE_IN_LOOP", &[Gc]),
+    ("CALENDAR_PER_CALL", &[Gc]),
+    ("DATE_FORMATTER_IN_LOOP", &[Gc, Cpu]),
+    ("STRING_CONCAT_LOOP", &[Gc, Cpu]),
+    ("SERIALIZER_PER_CALL", &[Gc, Cpu]),
+    ("STREAM_COLLECT_SIZE", &[Gc]),
+    ("MAX_ALLOCATIONS_IN_LOOP", &[Gc]),
+    // AST: CPU
+    ("NESTED_LOOP", &[Cpu]),
+    ("NESTED_LOOP_MIXED", &[Cpu]),
+    ("PATTERN_COMPILE_HOT_PATH", &[Cpu]),
+    ("LIST_CONTAINS_IN_LOOP", &[Cpu]),
+    ("LINKEDLIST_RANDOM_ACCESS", &[Cpu]),
+    ("SERIALIZE_IN_LOOP", &[Cpu, Gc]),
+    ("STREAM_COUNT_FOR_EXISTENCE", &[Cpu]),
+    ("STREAM_REPEATED", &[Cpu]),
+    ("RECORD_MUTABLE_COMPONENT", &[Cpu]),
+    ("SWITCH_PATTERN_NO_DEFAULT", &[Cpu]),
+    ("OPTIONAL_GET_UNCHECKED", &[Cpu]),
+    ("REGEX_REDOS", &[Cpu, SecurityAdjacent]),
+    ("MAX_LOOP_DEPTH", &[Cpu]),
+    ("MAX_METHOD_LENGTH", &[Cpu]),
+    // AST: IO / 远程调用
+    ("BLOCKING_IO", &[Io]),
+    ("STREAM_RESOURCE_LEAK", &[Io]),
+    ("HTTP_CLIENT_TIMEOUT", &[Io, Config]),
+    ("FILTER_HTTP_CALL", &[Io]),
+    ("POSTCONSTRUCT_REMOTE_CALL", &[Io]),
+    ("RETRY_NO_BACKOFF", &[Io, Config]),
+    ("RETRY_EXCESSIVE_ATTEMPTS", &[Io, Config]),
+    ("CIRCUIT_BREAKER_TINY_WINDOW", &[Io, Config]),
+    // AST: 响应式
+    ("FLUX_BLOCK", &[Reactive, Concurrency]),
+    ("SUBSCRIBE_NO_ERROR", &[Reactive]),
+    ("FLUX_COLLECT_LIST", &[Reactive, Memory]),
+    ("PARALLEL_NO_RUN_ON", &[Reactive, Concurrency]),
+    ("EMITTER_UNBOUNDED", &[Reactive, Memory]),
+    ("SINKS_MANY", &[Reactive, Memory]),
+    // AST: 配置 / 框架
+    ("AUTOWIRED_FIELD", &[Config]),
+    ("COMPONENT_SCAN_BROAD", &[Config]),
+    ("EAGER_HEAVY_BEAN", &[Config, Io]),
+    ("DEFAULT_TIMEZONE", &[Config]),
+    ("GRAALVM_CLASS_FORNAME", &[Config]),
+    ("GRAALVM_METHOD_INVOKE", &[Config]),
+    ("GRAALVM_PROXY", &[Config]),
+    // AST: 日志
+    ("LOG_STRING_CONCAT", &[Logging, Gc]),
+    ("EMPTY_CATCH", &[Logging]),
+    // AST: 安全相关
+    ("SYSTEM_EXIT", &[SecurityAdjacent]),
+    ("RUNTIME_EXEC", &[SecurityAdjacent]),
+    // AST: 测试源码
+    ("TEST_THREAD_SLEEP", &[Test]),
+    ("TEST_FIXED_PORT", &[Test]),
+    ("TEST_SHARED_STATIC_STATE", &[Test, Concurrency]),
+    ("TEST_CONTAINER_LEAK", &[Test, Memory]),
+    // 配置文件 (yml / properties)
+    ("DB_POOL_SMALL", &[Config, Db]),
+    ("DB_CONNECTION_TIMEOUT_MISSING", &[Config, Db]),
+    ("DB_CONNECTION_TIMEOUT_LONG", &[Config, Db]),
+    ("JPA_OPEN_IN_VIEW", &[Config, Db]),
+    ("JPA_SHOW_SQL_PROD", &[Config, Db, Logging]),
+    ("REDIS_TIMEOUT_MISSING", &[Config, Db]),
+    ("TOMCAT_THREADS_LOW", &[Config, Concurrency]),
+    ("DEBUG_LOG_IN_PROD", &[Config, Logging]),
+    // 日志配置 (logback / log4j2)
+    ("LOG_ROOT_DEBUG", &[Logging, Config]),
+    ("LOG_NO_ROLLING_POLICY", &[Logging, Io]),
+    ("LOG_SYNC_FILE_APPENDER", &[Logging, Io]),
+    ("LOG_CALLER_DATA_PATTERN", &[Logging, Cpu]),
+    // Dockerfile
+    ("DOCKER_LATEST_TAG", &[Docker]),
+    ("DOCKER_NO_TAG", &[Docker]),
+    ("DOCKER_APT_NO_CLEAN", &[Docker]),
+    ("DOCKER_MANY_LAYERS", &[Docker]),
+    ("DOCKER_ADD_URL", &[Docker, SecurityAdjacent]),
+    ("DOCKER_SENSITIVE_ENV", &[Docker, SecurityAdjacent]),
+    ("DOCKER_JDK_RUNTIME", &[Docker]),
+    ("DOCKER_JDK8_IMAGE", &[Docker, Gc]),
+    ("DOCKER_FAT_IMAGE", &[Docker]),
+    ("DOCKER_NO_MULTI_STAGE", &[Docker]),
+    ("DOCKER_NO_USER", &[Docker, SecurityAdjacent]),
+    // docker-compose
+    ("COMPOSE_NO_MEM_LIMIT", &[Docker, Memory]),
+    ("COMPOSE_DB_DEFAULT_BUFFERS", &[Docker, Db]),
+    ("COMPOSE_NO_HEALTHCHECK", &[Docker]),
+    ("COMPOSE_DEPENDS_NO_CONDITION", &[Docker, Db, Io]),
+    // CI 流水线 (GitHub Actions / GitLab CI / Jenkinsfile)
+    ("CI_NO_DEPENDENCY_CACHE", &[Build, Io]),
+    ("CI_TESTS_NO_PARALLEL", &[Build]),
+    ("CI_MVN_CLEAN_INSTALL", &[Build]),
+    ("CI_NO_BUILD_TIMING", &[Build]),
+    // Gradle 构建脚本
+    ("GRADLE_DYNAMIC_VERSION", &[Build, Io]),
+    ("GRADLE_SLOW_REPO_FIRST", &[Build, Io]),
+    ("GRADLE_TEST_NO_PARALLEL_FORKS", &[Build]),
+    ("GRADLE_MONOLITHIC_ALLPROJECTS", &[Build]),
+    // 依赖公告库 (pom.xml / build.gradle)
+    ("DEPENDENCY_PERF_ADVISORY", &[Config]),
+    ("DEPENDENCY_VULN_ADVISORY", &[Config, SecurityAdjacent]),
+    // Spark 作业 (Scala)
+    ("SPARK_COLLECT", &[Spark, Memory]),
+    ("SPARK_GROUP_BY_KEY", &[Spark, Memory, Io]),
+    ("SPARK_JOIN_NO_BROADCAST", &[Spark, Io]),
+    ("SPARK_UDF_HEAVY", &[Spark, Cpu]),
+    // 字节码 (javap / scan-jar)
+    ("BC_SYNC_METHOD", &[Concurrency]),
+    ("BC_MONITOR_IN_LOOP", &[Concurrency]),
+    ("BC_MULTIPLE_MONITORS", &[Concurrency]),
+    ("BC_UNBOUNDED_POOL", &[Concurrency, Memory]),
+    ("BC_THREADLOCAL_LEAK", &[Memory, Concurrency]),
+    ("BC_CONCAT_IN_LOOP", &[Gc, Cpu]),
+    ("BC_STRING_CONCAT_HEAVY", &[Gc]),
+    ("BC_HUGE_METHOD", &[Cpu]),
+    ("BC_NOT_INLINABLE", &[Cpu]),
+    ("BC_EXCEPTION_DENSE", &[Cpu]),
+];
+
+static INDEX: Lazy<HashMap<&'static str, &'static [Tag]>> = Lazy::new(|| REGISTRY.iter().copied().collect());
+
+/// 规则的标签；未登记的规则 (如 WASM 脚本规则) 返回空
+pub fn tags(rule_id: &str) -> &'static [Tag] {
+    INDEX.get(rule_id).copied().unwrap_or_default()
+}
+
+/// 规则的主分类
+pub fn category(rule_id: &str) -> Option<Tag> {
+    tags(rule_id).first().copied()
+}
+
+/// 规则是否带有任一给定标签
+pub fn has_any(rule_id: &str, wanted: &[Tag]) -> bool {
+    tags(rule_id).iter().any(|tag| wanted.contains(tag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_every_ast_rule_is_tagged() {
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let untagged: Vec<&str> = analyzer.rule_ids().into_iter().filter(|id| tags(id).is_empty()).collect();
+        assert!(untagged.is_empty(), "rules missing from the taxonomy registry: {untagged:?}");
+
+        let mut seen = HashSet::new();
+        for (id, tags) in REGISTRY {
+            assert!(seen.insert(id), "{id} registered twice");
+            assert!(!tags.is_empty(), "{id} has no tags");
+        }
+    }
+
+    #[test]
+    fn test_lookup() {
+        assert_eq!(tags("SQL_STRING_CONCAT"), &[Db, SecurityAdjacent]);
+        assert_eq!(category("DOCKER_SENSITIVE_ENV"), Some(Docker));
+        assert_eq!(category("CORP_DAO_IN_LOOP"), None);
+        assert!(has_any("THREADLOCAL_LEAK", &[Db, Concurrency]));
+        assert!(!has_any("THREADLOCAL_LEAK", &[Db]));
+        assert_eq!(serde_json::to_value(SecurityAdjacent).unwrap(), "security-adjacent");
+        assert_eq!(Tag::from_str("security-adjacent", true), Ok(SecurityAdjacent));
+    }
+}
@@ -0,0 +1,145 @@
+//! 测试代码分类 (Test Sources) - v9.6
+//!
+//! `src/test/java` 里的 `Thread.sleep`、循环内创建对象并不影响线上性能，
+//! 按生产代码扫描只会制造噪音。目录扫描时先按路径识别测试源码，再按 `--test-code` 处理：
+//! - `exclude` (默认): 跳过测试源码
+//! - `test-rules`: 测试源码只保留测试专用规则 ([`TEST_RULES`]，不稳定测试模式)
+//! - `include`: 测试源码按生产代码扫描 (测试专用规则同样生效)
+//!
+//! 测试专用规则的处理器自身只在测试源码上触发，生产代码永远不会报告 `TEST_*`。
+
+use clap::ValueEnum;
+use std::path::{Component, Path};
+
+use crate::ast_engine::AstIssue;
+
+/// 测试专用规则 (只在测试源码上触发)
+pub const TEST_RULES: &[&str] = &[
+    "TEST_THREAD_SLEEP",
+    "TEST_FIXED_PORT",
+    "TEST_SHARED_STATIC_STATE",
+    "TEST_CONTAINER_LEAK",
+];
+
+/// 测试类文件名后缀 (标准目录之外的测试类，如单文件分析)
+const TEST_CLASS_SUFFIXES: &[&str] = &["Test", "Tests", "IT", "TestCase"];
+
+/// 测试源码处理方式
+#[derive(ValueEnum, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TestCodeMode {
+    /// 跳过测试源码
+    #[default]
+    Exclude,
+    /// 测试源码只应用测试专用规则
+    TestRules,
+    /// 测试源码按生产代码扫描
+    Include,
+}
+
+impl TestCodeMode {
+    pub fn name(&self) -> &'static str {
+        match self {
+            TestCodeMode::Exclude => "exclude",
+            TestCodeMode::TestRules => "test-rules",
+            TestCodeMode::Include => "include",
+        }
+    }
+}
+
+/// 是否为测试源码
+///
+/// `src/` 下的 `test`、`test*` (testFixtures)、`*Test`/`*Tests` (integrationTest) 与 `it` 源码集，
+/// 或类名以 `Test`/`Tests`/`IT`/`TestCase` 结尾的文件。
+pub fn is_test_source(path: &Path) -> bool {
+    let dirs: Vec<&str> = path.parent()
+        .map(|parent| parent.components()
+            .filter_map(|c| match c {
+                Component::Normal(name) => name.to_str(),
+                _ => None,
+            })
+            .collect())
+        .unwrap_or_default();
+    let in_test_source_set = dirs.windows(2).any(|pair| {
+        let set = pair[1];
+        pair[0] == "src" && (set == "it" || set.starts_with("test") || set.ends_with("Test") || set.ends_with("Tests"))
+    });
+    if in_test_source_set {
+        return true;
+    }
+
+    path.extension().is_some_and(|ext| ext == "java")
+        && path.file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| TEST_CLASS_SUFFIXES.iter().any(|suffix| stem.len() > suffix.len() && stem.ends_with(suffix)))
+}
+
+/// 按模式过滤测试源码上的问题 (`issue.file` 为相对扫描根目录的路径)
+pub fn apply(mode: TestCodeMode, issues: &mut Vec<AstIssue>) {
+    match mode {
+        TestCodeMode::Include => {}
+        TestCodeMode::TestRules => issues.retain(|issue| {
+            TEST_RULES.contains(&issue.issue_type.as_str()) || !is_test_source(Path::new(&issue.file))
+        }),
+        TestCodeMode::Exclude => issues.retain(|issue| !is_test_source(Path::new(&issue.file))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast_engine::Severity;
+
+    fn issue(id: &str, file: &str) -> AstIssue {
+        AstIssue {
+            severity: Severity::P1,
+            issue_type: id.to_string(),
+            file: file.to_string(),
+            line: 1,
+            column: 1,
+            end_line: 1,
+            end_column: 2,
+            description: String::new(),
+            owner: None,
+            effort: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_classify_test_sources() {
+        assert!(is_test_source(Path::new("src/test/java/com/acme/OrderService.java")));
+        assert!(is_test_source(Path::new("order/src/integrationTest/java/com/acme/Flow.java")));
+        assert!(is_test_source(Path::new("src/testFixtures/java/com/acme/Fixtures.java")));
+        assert!(is_test_source(Path::new("src/it/java/com/acme/Flow.java")));
+        assert!(is_test_source(Path::new("legacy/OrderServiceTest.java")));
+        assert!(is_test_source(Path::new("legacy/OrderServiceIT.java")));
+
+        assert!(!is_test_source(Path::new("src/main/java/com/acme/OrderService.java")));
+        assert!(!is_test_source(Path::new("test/src/main/java/com/acme/Latest.java")));
+        assert!(!is_test_source(Path::new("src/main/java/com/acme/Test.java")));
+        assert!(!is_test_source(Path::new("src/main/resources/application-test.yml")));
+    }
+
+    #[test]
+    fn test_apply_modes() {
+        let all = || vec![
+            issue("OBJECT_IN_LOOP", "src/main/java/A.java"),
+            issue("OBJECT_IN_LOOP", "src/test/java/ATest.java"),
+            issue("TEST_THREAD_SLEEP", "src/test/java/ATest.java"),
+        ];
+
+        let mut issues = all();
+        apply(TestCodeMode::Exclude, &mut issues);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].file, "src/main/java/A.java");
+
+        let mut issues = all();
+        apply(TestCodeMode::TestRules, &mut issues);
+        let kept: Vec<_> = issues.iter().map(|i| i.issue_type.as_str()).collect();
+        assert_eq!(kept, vec!["OBJECT_IN_LOOP", "TEST_THREAD_SLEEP"]);
+        assert_eq!(issues[0].file, "src/main/java/A.java");
+
+        let mut issues = all();
+        apply(TestCodeMode::Include, &mut issues);
+        assert_eq!(issues.len(), 3);
+    }
+}
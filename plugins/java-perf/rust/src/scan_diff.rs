@@ -0,0 +1,148 @@
+//! 扫描结果对比 (Scan Delta) - v9.6
+//!
+//! `diff-report old.json new.json` / MCP `compare_scans`: 对比两份 `scan --format json` 报告，
+//! 按稳定指纹把问题分为 新增 / 已解决 / 未变化。
+//!
+//! - 指纹与 GitLab Code Quality 报告相同 ([`report::fingerprint`])，不含行号，代码上下移动不算新问题
+//! - 同一 (规则, 文件, 描述) 的多个问题按位置顺序编号区分
+//! - 新增 P0 时 CLI 以退出码 1 结束，可直接作为 CI 门禁
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+
+use crate::ast_engine::Severity;
+use crate::{i18n, report};
+
+/// 对比两份扫描报告文件
+pub fn compare_files(old_path: &str, new_path: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let load = |path: &str| -> Result<Value, Box<dyn std::error::Error>> {
+        let content = std::fs::read_to_string(path).map_err(|e| format!("{path}: {e}"))?;
+        Ok(serde_json::from_str(&content).map_err(|e| format!("{path}: {e}"))?)
+    };
+    compare_reports(&load(old_path)?, &load(new_path)?)
+}
+
+/// 对比两份扫描报告 (`scan --format json` 输出，或 `--json` 包装后的 `data`)
+///
+/// 返回 `summary` (new/resolved/unchanged/new_p0)、`new`、`resolved` 问题列表与 Markdown `report`。
+pub fn compare_reports(old: &Value, new: &Value) -> Result<Value, Box<dyn std::error::Error>> {
+    let old_issues = fingerprinted(old)?;
+    let new_issues = fingerprinted(new)?;
+
+    let old_fps: HashSet<&str> = old_issues.iter().map(|(fp, _)| fp.as_str()).collect();
+    let new_fps: HashSet<&str> = new_issues.iter().map(|(fp, _)| fp.as_str()).collect();
+
+    let added: Vec<&Value> = new_issues.iter().filter(|(fp, _)| !old_fps.contains(fp.as_str())).map(|(_, i)| *i).collect();
+    let resolved: Vec<&Value> = old_issues.iter().filter(|(fp, _)| !new_fps.contains(fp.as_str())).map(|(_, i)| *i).collect();
+    let unchanged = new_issues.len() - added.len();
+    let new_p0 = added.iter().filter(|i| i["severity"] == "P0").count();
+
+    Ok(json!({
+        "summary": {
+            "new": added.len(),
+            "resolved": resolved.len(),
+            "unchanged": unchanged,
+            "new_p0": new_p0,
+        },
+        "new": added,
+        "resolved": resolved,
+        "report": render_markdown(&added, &resolved, unchanged, new_p0),
+    }))
+}
+
+/// 计算报告中每个问题的指纹 (先按位置排序，序号与报告中的顺序无关)
+fn fingerprinted(report: &Value) -> Result<Vec<(String, &Value)>, String> {
+    let issues = report["issues"].as_array()
+        .or_else(|| report["data"]["issues"].as_array())
+        .ok_or("scan report has no 'issues' array (expected `scan --format json` output)")?;
+
+    let field = |issue: &Value, key: &str| issue[key].as_str().unwrap_or("").to_string();
+    let mut sorted: Vec<&Value> = issues.iter().collect();
+    sorted.sort_by_key(|i| (field(i, "file"), i["line"].as_u64().unwrap_or(0), i["column"].as_u64().unwrap_or(0), field(i, "id")));
+
+    let mut occurrences: HashMap<(String, String, String), usize> = HashMap::new();
+    Ok(sorted.into_iter().map(|issue| {
+        let key = (field(issue, "id"), field(issue, "file"), field(issue, "description"));
+        let ordinal = occurrences.entry(key.clone()).or_insert(0);
+        let fingerprint = report::fingerprint(&key.0, &key.1, &key.2, *ordinal);
+        *ordinal += 1;
+        (fingerprint, issue)
+    }).collect())
+}
+
+fn render_markdown(added: &[&Value], resolved: &[&Value], unchanged: usize, new_p0: usize) -> String {
+    let mut out = i18n::text("report.diff.title", "## 📊 扫描对比").to_string();
+    out.push_str("\n\n");
+    out.push_str(&i18n::format(
+        "report.diff.summary",
+        "**新增**: {new} (P0: {new_p0}) | **已解决**: {resolved} | **未变化**: {unchanged}",
+        &[("new", &added.len()), ("new_p0", &new_p0), ("resolved", &resolved.len()), ("unchanged", &unchanged)],
+    ));
+    out.push_str("\n\n");
+
+    let line = |issue: &Value| {
+        let emoji = Severity::deserialize(&issue["severity"]).map_or("🟡", Severity::emoji);
+        format!(
+            "- {emoji} **{}** - `{}:{}` - {}\n",
+            issue["id"].as_str().unwrap_or(""),
+            issue["file"].as_str().unwrap_or(""),
+            issue["line"].as_u64().unwrap_or(0),
+            issue["description"].as_str().unwrap_or(""),
+        )
+    };
+    if !added.is_empty() {
+        out.push_str(i18n::text("report.diff.new_header", "### 🆕 新增问题"));
+        out.push_str("\n\n");
+        out.extend(added.iter().map(|i| line(i)));
+        out.push('\n');
+    }
+    if !resolved.is_empty() {
+        out.push_str(i18n::text("report.diff.resolved_header", "### ✅ 已解决"));
+        out.push_str("\n\n");
+        out.extend(resolved.iter().map(|i| line(i)));
+        out.push('\n');
+    }
+    if new_p0 == 0 {
+        out.push_str(i18n::text("report.diff.no_new_p0", "✅ 无新增 P0 问题"));
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(id: &str, severity: &str, file: &str, line: u64) -> Value {
+        json!({ "id": id, "severity": severity, "file": file, "line": line, "column": 1, "description": format!("{id} desc") })
+    }
+
+    #[test]
+    fn test_compare_classifies_by_fingerprint() {
+        let old = json!({ "issues": [
+            issue("N_PLUS_ONE", "P0", "A.java", 10),
+            issue("OBJECT_IN_LOOP", "P1", "A.java", 20),
+            issue("OBJECT_IN_LOOP", "P1", "A.java", 30),
+        ]});
+        // 代码整体下移 5 行: 行号变化不算新问题；第二个 OBJECT_IN_LOOP 被修复，新增一个 P0
+        let new = json!({ "data": { "issues": [
+            issue("N_PLUS_ONE", "P0", "A.java", 15),
+            issue("OBJECT_IN_LOOP", "P1", "A.java", 25),
+            issue("SLEEP_IN_LOCK", "P0", "B.java", 3),
+        ]}});
+
+        let delta = compare_reports(&old, &new).unwrap();
+        assert_eq!(delta["summary"], json!({ "new": 1, "resolved": 1, "unchanged": 2, "new_p0": 1 }));
+        assert_eq!(delta["new"][0]["id"], "SLEEP_IN_LOCK");
+        assert_eq!(delta["resolved"][0]["line"], 30);
+        assert!(delta["report"].as_str().unwrap().contains("`B.java:3`"));
+    }
+
+    #[test]
+    fn test_compare_requires_issues() {
+        assert!(compare_reports(&json!({}), &json!({ "issues": [] })).is_err());
+        let delta = compare_reports(&json!({ "issues": [] }), &json!({ "issues": [] })).unwrap();
+        assert_eq!(delta["summary"]["new_p0"], 0);
+    }
+}
@@ -0,0 +1,249 @@
+//! 注解参数提取 (Annotation Arguments) - v9.6
+//!
+//! 注解类规则不只需要知道 `@Scheduled(fixedRate = ...)` 出现了 `fixedRate`，还需要它的值：
+//! [`AnnotationArgs`] 读取注解的 `key = value` 参数 (单值 `@Scheduled(1000)` 记为 `value`)，
+//! 并提供常量整数求值 (`5_000L`、`60 * 1000`、`"1000"`)、按 `timeUnit` 换算毫秒、枚举常量名等取值方法。
+//! 引用常量或 `${...}` 占位符的值无法求值，返回 None，由规则决定如何报告。
+//! 符号表中的注解是源码文本 (`Scheduled(cron = "0 0 1,13 * * *")`)，由 [`parse_text`] 拆分参数。
+
+use tree_sitter::Node;
+
+/// 一个注解及其参数
+#[derive(Debug, Clone)]
+pub struct AnnotationArgs<'t> {
+    /// 简单名 (`org.springframework.transaction.annotation.Transactional` → `Transactional`)
+    pub name: String,
+    /// 按源码顺序的 `(key, 值节点)`
+    pub args: Vec<(String, Node<'t>)>,
+    code: &'t str,
+}
+
+impl<'t> AnnotationArgs<'t> {
+    /// 解析 `annotation` / `marker_annotation` 节点
+    pub fn parse(node: Node<'t>, code: &'t str) -> Option<Self> {
+        if !matches!(node.kind(), "annotation" | "marker_annotation") {
+            return None;
+        }
+        let name = node.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+        let name = name.rsplit('.').next().unwrap_or(name).to_string();
+        let mut args = Vec::new();
+        if let Some(list) = node.child_by_field_name("arguments") {
+            let mut cursor = list.walk();
+            for arg in list.named_children(&mut cursor) {
+                match arg.kind() {
+                    "element_value_pair" => {
+                        let key = arg.child_by_field_name("key").and_then(|k| k.utf8_text(code.as_bytes()).ok());
+                        if let (Some(key), Some(value)) = (key, arg.child_by_field_name("value")) {
+                            args.push((key.to_string(), value));
+                        }
+                    }
+                    "comment" | "block_comment" | "line_comment" => {}
+                    _ => args.push(("value".to_string(), arg)),
+                }
+            }
+        }
+        Some(Self { name, args, code })
+    }
+
+    /// 声明 (方法 / 类 / 字段) 上名为 `name` 的注解
+    pub fn find(declaration: Node<'t>, name: &str, code: &'t str) -> Option<Self> {
+        let mut cursor = declaration.walk();
+        let modifiers = declaration.children(&mut cursor).find(|c| c.kind() == "modifiers")?;
+        let mut cursor = modifiers.walk();
+        let found = modifiers.named_children(&mut cursor)
+            .filter_map(|node| Self::parse(node, code))
+            .find(|annotation| annotation.name == name);
+        found
+    }
+
+    fn value(&self, key: &str) -> Option<Node<'t>> {
+        self.args.iter().find(|(k, _)| k == key).map(|(_, v)| *v)
+    }
+
+    /// 参数源码文本
+    pub fn get(&self, key: &str) -> Option<&'t str> {
+        self.value(key)?.utf8_text(self.code.as_bytes()).ok()
+    }
+
+    /// 枚举常量名 (`Propagation.REQUIRES_NEW` → `REQUIRES_NEW`)
+    pub fn enum_constant(&self, key: &str) -> Option<&'t str> {
+        let text = self.get(key)?;
+        Some(text.rsplit('.').next().unwrap_or(text).trim())
+    }
+
+    /// 常量整数值 (字面量、字面量的四则运算、纯数字字符串)
+    pub fn int(&self, key: &str) -> Option<i64> {
+        eval(self.value(key)?, self.code)
+    }
+
+    /// 时长参数的毫秒数：`key` 或 `keyString` (Spring 的字符串形式)，按 `timeUnit` 换算 (默认毫秒)
+    pub fn duration_ms(&self, key: &str) -> Option<i64> {
+        let amount = self.int(key).or_else(|| self.int(&format!("{key}String")))?;
+        let factor = match self.enum_constant("timeUnit").unwrap_or("MILLISECONDS") {
+            "NANOSECONDS" => return Some(amount / 1_000_000),
+            "MICROSECONDS" => return Some(amount / 1_000),
+            "MILLISECONDS" => 1,
+            "SECONDS" => 1_000,
+            "MINUTES" => 60_000,
+            "HOURS" => 3_600_000,
+            "DAYS" => 86_400_000,
+            _ => return None,
+        };
+        amount.checked_mul(factor)
+    }
+}
+
+/// 常量表达式求值
+fn eval(node: Node, code: &str) -> Option<i64> {
+    let text = node.utf8_text(code.as_bytes()).ok()?;
+    match node.kind() {
+        "decimal_integer_literal" => text.trim_end_matches(['L', 'l']).replace('_', "").parse().ok(),
+        "hex_integer_literal" => i64::from_str_radix(&text[2..].trim_end_matches(['L', 'l']).replace('_', ""), 16).ok(),
+        "string_literal" => text.trim_matches('"').trim().replace('_', "").parse().ok(),
+        "parenthesized_expression" => eval(node.named_child(0)?, code),
+        "unary_expression" => {
+            let operand = eval(node.child_by_field_name("operand")?, code)?;
+            match node.child_by_field_name("operator")?.kind() {
+                "-" => operand.checked_neg(),
+                "+" => Some(operand),
+                _ => None,
+            }
+        }
+        "binary_expression" => {
+            let left = eval(node.child_by_field_name("left")?, code)?;
+            let right = eval(node.child_by_field_name("right")?, code)?;
+            match node.child_by_field_name("operator")?.kind() {
+                "+" => left.checked_add(right),
+                "-" => left.checked_sub(right),
+                "*" => left.checked_mul(right),
+                "/" => left.checked_div(right),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+/// 注解源码文本 (可带 `@`) → (简单名, `[(key, 值)]`)
+///
+/// 按顶层逗号拆分参数 (忽略字符串、括号与数组内的逗号)，单值参数记为 `value`，字符串值去掉引号。
+pub fn parse_text(annotation: &str) -> (&str, Vec<(String, String)>) {
+    let annotation = annotation.trim().trim_start_matches('@');
+    let (name, rest) = annotation.split_once('(').unwrap_or((annotation, ""));
+    let name = name.trim();
+    let name = name.rsplit('.').next().unwrap_or(name);
+    let inner = rest.trim_end().strip_suffix(')').unwrap_or(rest);
+
+    let mut parts = Vec::new();
+    let (mut start, mut depth, mut in_string, mut escaped) = (0, 0i32, false, false);
+    for (i, c) in inner.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            '(' | '{' if !in_string => depth += 1,
+            ')' | '}' if !in_string => depth -= 1,
+            ',' if !in_string && depth == 0 => {
+                parts.push(&inner[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(&inner[start..]);
+
+    let args = parts.into_iter()
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let pair = part.split_once('=')
+                .filter(|(key, _)| !key.is_empty() && key.trim().chars().all(|c| c.is_alphanumeric() || c == '_'));
+            let (key, value) = pair.map(|(k, v)| (k.trim(), v.trim())).unwrap_or(("value", part));
+            let value = value.strip_prefix('"').and_then(|v| v.strip_suffix('"')).unwrap_or(value);
+            (key.to_string(), value.to_string())
+        })
+        .collect();
+    (name, args)
+}
+
+/// 毫秒数的可读形式 (`500ms`、`5s`、`2min`)
+pub fn format_ms(ms: i64) -> String {
+    match ms {
+        ms if ms >= 60_000 && ms % 60_000 == 0 => format!("{}min", ms / 60_000),
+        ms if ms >= 1_000 && ms % 1_000 == 0 => format!("{}s", ms / 1_000),
+        ms => format!("{ms}ms"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn annotations(code: &str) -> Vec<(String, Vec<String>, Option<i64>)> {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_java::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let mut found = Vec::new();
+        let mut stack = vec![tree.root_node()];
+        while let Some(node) = stack.pop() {
+            if let Some(annotation) = AnnotationArgs::parse(node, code) {
+                let keys = annotation.args.iter().map(|(k, _)| k.clone()).collect();
+                found.push((annotation.name.clone(), keys, annotation.duration_ms("fixedRate").or_else(|| annotation.int("value"))));
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn test_annotation_values() {
+        let code = r#"class A {
+            @Scheduled(fixedRate = 5_000L) void a() {}
+            @Scheduled(fixedRate = 2 * (60 + 0), timeUnit = TimeUnit.SECONDS) void b() {}
+            @org.springframework.scheduling.annotation.Scheduled(fixedRateString = "500") void c() {}
+            @Scheduled(fixedRateString = "${job.rate:1000}") void d() {}
+            @Timeout(30) void e() {}
+            @Deprecated void f() {}
+        }"#;
+        let found = annotations(code);
+        assert!(found.contains(&("Scheduled".into(), vec!["fixedRate".into()], Some(5_000))));
+        assert!(found.contains(&("Scheduled".into(), vec!["fixedRate".into(), "timeUnit".into()], Some(120_000))));
+        assert!(found.contains(&("Scheduled".into(), vec!["fixedRateString".into()], Some(500))));
+        assert!(found.contains(&("Scheduled".into(), vec!["fixedRateString".into()], None)));
+        assert!(found.contains(&("Timeout".into(), vec!["value".into()], Some(30))));
+        assert!(found.contains(&("Deprecated".into(), vec![], None)));
+        assert_eq!(format_ms(120_000), "2min");
+        assert_eq!(format_ms(1_500), "1500ms");
+    }
+
+    #[test]
+    fn test_find_on_declaration() {
+        let code = "class A { @Transactional(propagation = Propagation.REQUIRES_NEW, timeout = 5) void f() {} }";
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_java::language()).unwrap();
+        let tree = parser.parse(code, None).unwrap();
+        let class_body = tree.root_node().named_child(0).unwrap().child_by_field_name("body").unwrap();
+        let method = class_body.named_child(0).unwrap();
+        let transactional = AnnotationArgs::find(method, "Transactional", code).unwrap();
+        assert_eq!(transactional.enum_constant("propagation"), Some("REQUIRES_NEW"));
+        assert_eq!(transactional.int("timeout"), Some(5));
+        assert_eq!(transactional.get("readOnly"), None);
+        assert!(AnnotationArgs::find(method, "Scheduled", code).is_none());
+    }
+
+    #[test]
+    fn test_parse_text() {
+        let (name, args) = parse_text(r#"org.springframework.scheduling.annotation.Scheduled(cron = "0 0 1,13 * * *", zone = "UTC")"#);
+        assert_eq!(name, "Scheduled");
+        assert_eq!(args, vec![("cron".to_string(), "0 0 1,13 * * *".to_string()), ("zone".to_string(), "UTC".to_string())]);
+
+        let (name, args) = parse_text(r#"@Transactional(propagation = Propagation.REQUIRES_NEW, rollbackFor = {A.class, B.class})"#);
+        assert_eq!(name, "Transactional");
+        assert_eq!(args[1], ("rollbackFor".to_string(), "{A.class, B.class}".to_string()));
+
+        assert_eq!(parse_text("Timeout(30)").1, vec![("value".to_string(), "30".to_string())]);
+        assert_eq!(parse_text("Transactional"), ("Transactional", vec![]));
+    }
+}
@@ -0,0 +1,198 @@
+//! 链式调用分析 (Fluent Chain) - v9.6
+//!
+//! 构建器类规则 (缓存构建器、HTTP 客户端、线程、测试容器) 需要知道整条调用链上调用了哪些方法、参数是什么，
+//! 而不是在源码文本中查找 `"maximumSize("`。[`ChainInspector`] 从链上任一节点还原整条链：
+//! `Caffeine.newBuilder().maximumSize(100).build()` → 接收者 `Caffeine` + 调用 `newBuilder` / `maximumSize` / `build`。
+//! 构建器先赋给变量时，[`ChainInspector::variable_chains`] 找出作用域内以该变量为接收者的调用链。
+
+use tree_sitter::Node;
+
+/// 链上的一次方法调用
+#[derive(Debug, Clone)]
+pub struct ChainCall<'t> {
+    #[allow(dead_code)]
+    pub node: Node<'t>,
+    pub name: String,
+    /// 参数源码文本
+    pub args: Vec<String>,
+}
+
+impl ChainCall<'_> {
+    /// `name(arg, ...)`
+    pub fn render(&self) -> String {
+        format!("{}({})", self.name, self.args.join(", "))
+    }
+}
+
+/// 一条调用链
+#[derive(Debug, Clone)]
+pub struct Chain<'t> {
+    /// 链头接收者 (`Caffeine`、`new OkHttpClient.Builder()`、变量)；`foo().bar()` 这类无接收者的链为 None
+    pub receiver: Option<Node<'t>>,
+    /// 按调用顺序
+    pub calls: Vec<ChainCall<'t>>,
+    /// 最外层节点 (整条链表达式)
+    pub top: Node<'t>,
+}
+
+impl<'t> Chain<'t> {
+    /// 链上第一次调用 `name`
+    pub fn call(&self, name: &str) -> Option<&ChainCall<'t>> {
+        self.calls.iter().find(|call| call.name == name)
+    }
+
+    pub fn has_call(&self, name: &str) -> bool {
+        self.call(name).is_some()
+    }
+
+    /// 是否调用了 `names` 中的任一方法
+    pub fn has_any(&self, names: &[&str]) -> bool {
+        self.calls.iter().any(|call| names.contains(&call.name.as_str()))
+    }
+
+    #[allow(dead_code)]
+    pub fn names(&self) -> Vec<&str> {
+        self.calls.iter().map(|call| call.name.as_str()).collect()
+    }
+}
+
+/// 调用链查看器
+pub struct ChainInspector<'a> {
+    code: &'a str,
+}
+
+impl<'a> ChainInspector<'a> {
+    pub fn new(code: &'a str) -> Self {
+        Self { code }
+    }
+
+    fn text(&self, node: Node) -> &'a str {
+        node.utf8_text(self.code.as_bytes()).unwrap_or("")
+    }
+
+    /// 包含 `node` 的整条调用链 (`node` 可以是链上任一调用或链头接收者)
+    pub fn chain<'t>(&self, node: Node<'t>) -> Chain<'t> {
+        let mut top = node;
+        while let Some(parent) = top.parent() {
+            if parent.kind() != "method_invocation" || parent.child_by_field_name("object") != Some(top) {
+                break;
+            }
+            top = parent;
+        }
+
+        let mut calls = Vec::new();
+        let mut current = Some(top);
+        let mut receiver = None;
+        while let Some(node) = current {
+            if node.kind() != "method_invocation" {
+                receiver = Some(node);
+                break;
+            }
+            let args = node.child_by_field_name("arguments")
+                .map(|args| {
+                    let mut cursor = args.walk();
+                    args.named_children(&mut cursor).map(|arg| self.text(arg).to_string()).collect()
+                })
+                .unwrap_or_default();
+            calls.push(ChainCall {
+                node,
+                name: node.child_by_field_name("name").map(|n| self.text(n)).unwrap_or_default().to_string(),
+                args,
+            });
+            current = node.child_by_field_name("object");
+        }
+        calls.reverse();
+        Chain { receiver, calls, top }
+    }
+
+    /// 链赋给的变量 (`Foo x = <chain>` / `x = <chain>` / `this.x = <chain>`)
+    pub fn assigned_variable(&self, chain: &Chain) -> Option<String> {
+        let parent = chain.top.parent()?;
+        let name = match parent.kind() {
+            "variable_declarator" => parent.child_by_field_name("name")?,
+            "assignment_expression" => parent.child_by_field_name("left")?,
+            _ => return None,
+        };
+        let name = self.text(name).trim_start_matches("this.");
+        (!name.is_empty() && name.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$')).then(|| name.to_string())
+    }
+
+    /// `scope` 内以 `variable` (或 `this.variable`) 为接收者的调用链
+    pub fn variable_chains<'t>(&self, scope: Node<'t>, variable: &str) -> Vec<Chain<'t>> {
+        let mut chains = Vec::new();
+        let mut stack = vec![scope];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "identifier" && self.text(node) == variable {
+                let receiver = match node.parent() {
+                    Some(p) if p.kind() == "field_access" && p.child_by_field_name("field") == Some(node) => {
+                        p.child_by_field_name("object").filter(|o| o.kind() == "this").map(|_| p)
+                    }
+                    _ => Some(node),
+                };
+                let chain = receiver.map(|receiver| self.chain(receiver)).filter(|chain| !chain.calls.is_empty());
+                chains.extend(chain);
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        chains.sort_by_key(|chain| chain.top.start_byte());
+        chains
+    }
+
+    /// `receiver.call(args)...` 形式的源码 (参数按原文)
+    pub fn render(&self, chain: &Chain) -> String {
+        let calls = chain.calls.iter().map(ChainCall::render);
+        match chain.receiver {
+            Some(receiver) => std::iter::once(self.text(receiver).to_string()).chain(calls).collect::<Vec<_>>().join("."),
+            None => calls.collect::<Vec<_>>().join("."),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(code: &str) -> tree_sitter::Tree {
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&tree_sitter_java::language()).unwrap();
+        parser.parse(code, None).unwrap()
+    }
+
+    fn find<'t>(node: Node<'t>, code: &str, kind: &str, text: &str) -> Option<Node<'t>> {
+        if node.kind() == kind && node.utf8_text(code.as_bytes()) == Ok(text) {
+            return Some(node);
+        }
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+        children.into_iter().find_map(|child| find(child, code, kind, text))
+    }
+
+    #[test]
+    fn test_chain_from_any_node() {
+        let code = "class A { void f() { Cache c = Caffeine.newBuilder().expireAfterWrite(10, TimeUnit.MINUTES).build(); } }";
+        let tree = parse(code);
+        let inspector = ChainInspector::new(code);
+        let receiver = find(tree.root_node(), code, "identifier", "Caffeine").unwrap();
+        let chain = inspector.chain(receiver);
+        assert_eq!(chain.names(), vec!["newBuilder", "expireAfterWrite", "build"]);
+        assert_eq!(chain.call("expireAfterWrite").unwrap().args, vec!["10", "TimeUnit.MINUTES"]);
+        assert_eq!(inspector.render(&chain), "Caffeine.newBuilder().expireAfterWrite(10, TimeUnit.MINUTES).build()");
+        assert_eq!(inspector.assigned_variable(&chain).as_deref(), Some("c"));
+
+        let inner = chain.call("newBuilder").unwrap().node;
+        assert_eq!(inspector.chain(inner).top, chain.top);
+    }
+
+    #[test]
+    fn test_variable_chains() {
+        let code = "class A { void f() { Thread t = new Thread(r); t.setDaemon(true); this.t.setName(\"worker\").start(); other.t(); } }";
+        let tree = parse(code);
+        let inspector = ChainInspector::new(code);
+        let chains = inspector.variable_chains(tree.root_node(), "t");
+        let rendered: Vec<String> = chains.iter().map(|c| inspector.render(c)).collect();
+        assert_eq!(rendered, vec!["t.setDaemon(true)", "this.t.setName(\"worker\").start()"]);
+        assert!(chains[1].has_any(&["setName", "setNameFormat"]));
+        assert!(!chains[0].has_call("setName"));
+    }
+}
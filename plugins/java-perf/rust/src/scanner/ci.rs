@@ -0,0 +1,271 @@
+//! CI 流水线构建性能分析 - v9.6
+//!
+//! 扫描 `.github/workflows/*.yml`、`.gitlab-ci.yml` 与 `Jenkinsfile` 中的 Maven / Gradle 命令：
+//! - `CI_NO_DEPENDENCY_CACHE`: 没有缓存依赖仓库，每次构建重新下载全部依赖
+//!   (GitHub: actions/cache / setup-java `cache:` / setup-gradle；GitLab: `cache:`；Jenkins: 仅 docker agent 且未挂载 .m2/.gradle)
+//! - `CI_TESTS_NO_PARALLEL`: 运行测试的命令未开启并行 (Maven `-T` / `forkCount`，Gradle `--parallel` / `maxParallelForks`)
+//! - `CI_MVN_CLEAN_INSTALL`: `mvn install` 只为校验构建时多余地写入本地仓库，`verify` 即可；全新检出的工作区 `clean` 也无必要
+//! - `CI_NO_BUILD_TIMING`: 流水线没有任何构建耗时信息 (Gradle `--scan` / `--profile` / Develocity，Maven profiler / 时间戳日志)
+//!
+//! 命令按行匹配，YAML `run:` / `script:` 与 Jenkins `sh '...'` 同样处理。
+
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// Maven / Gradle 命令: (工具, 参数)
+static RE_BUILD_COMMAND: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?:^|[\s'"(;&|])(?:\./)?(mvnw?|gradlew?)(?:\.bat|\.cmd)?\s+([^'"\n]+)"#).unwrap()
+});
+
+/// GitHub Actions 依赖缓存
+static RE_GITHUB_CACHE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"actions/cache|setup-gradle|gradle-build-action|cache:\s*['"]?(maven|gradle)"#).unwrap()
+});
+
+/// GitLab CI `cache:` 段 (全局或作业级)
+static RE_GITLAB_CACHE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*cache:").unwrap()
+});
+
+/// 构建耗时信息
+static RE_BUILD_TIMING: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"--scan\b|--profile\b|develocity|gradle-enterprise|-Dprofile\b|showDateTime|buildtime").unwrap()
+});
+
+/// 会运行测试的 Maven 生命周期阶段
+const MAVEN_TEST_PHASES: &[&str] = &["test", "integration-test", "verify", "install", "deploy", "package"];
+
+/// 会运行测试的 Gradle 任务
+const GRADLE_TEST_TASKS: &[&str] = &["test", "check", "build", "integrationTest"];
+
+/// CI 流水线类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pipeline {
+    GitHubActions,
+    GitLab,
+    Jenkins,
+}
+
+/// 按路径识别 CI 流水线文件
+pub fn pipeline(path: &Path) -> Option<Pipeline> {
+    let file_name = path.file_name()?.to_string_lossy();
+    if file_name == "Jenkinsfile" || file_name.starts_with("Jenkinsfile.") {
+        return Some(Pipeline::Jenkins);
+    }
+    if file_name == ".gitlab-ci.yml" || file_name == ".gitlab-ci.yaml" {
+        return Some(Pipeline::GitLab);
+    }
+    let yaml = file_name.ends_with(".yml") || file_name.ends_with(".yaml");
+    let workflows = path.parent().filter(|p| p.file_name().is_some_and(|n| n == "workflows"));
+    let github = workflows.and_then(Path::parent).and_then(Path::file_name).is_some_and(|n| n == ".github");
+    (yaml && github).then_some(Pipeline::GitHubActions)
+}
+
+/// 一条 Maven / Gradle 命令
+struct BuildCommand<'a> {
+    line: usize,
+    maven: bool,
+    args: Vec<&'a str>,
+}
+
+impl BuildCommand<'_> {
+    /// 目标/任务 (不以 `-` 开头的参数)
+    fn goals(&self) -> impl Iterator<Item = &str> {
+        self.args.iter().copied().filter(|a| !a.starts_with('-'))
+    }
+
+    /// `mvn clean verify` / `gradle check` 形式的命令文本
+    fn text(&self) -> String {
+        format!("{} {}", if self.maven { "mvn" } else { "gradle" }, self.args.join(" "))
+    }
+
+    fn has_flag(&self, flags: &[&str]) -> bool {
+        self.args.iter().any(|a| flags.iter().any(|f| a == f || a.starts_with(&format!("{f}="))))
+    }
+
+    fn runs_tests(&self) -> bool {
+        let phases = if self.maven { MAVEN_TEST_PHASES } else { GRADLE_TEST_TASKS };
+        let skipped = self.args.iter().any(|a| a.contains("skipTests") || a.contains("maven.test.skip"))
+            || self.args.windows(2).any(|w| w[0] == "-x" && w[1] == "test");
+        !skipped && self.goals().any(|g| phases.contains(&g.rsplit(':').next().unwrap_or(g)))
+    }
+
+    fn parallel(&self) -> bool {
+        let joined = self.args.join(" ");
+        if self.maven {
+            self.args.iter().any(|a| *a == "-T" || (a.starts_with("-T") && a[2..].chars().next().is_some_and(|c| c.is_ascii_digit())))
+                || self.has_flag(&["--threads"])
+                || joined.contains("forkCount")
+        } else {
+            self.has_flag(&["--parallel"]) || joined.contains("org.gradle.parallel") || joined.contains("maxParallelForks")
+        }
+    }
+}
+
+fn build_commands(code: &str) -> Vec<BuildCommand<'_>> {
+    code.lines().enumerate()
+        .filter(|(_, line)| {
+            let trimmed = line.trim_start();
+            !trimmed.starts_with('#') && !trimmed.starts_with("//")
+        })
+        .filter_map(|(idx, line)| {
+            let caps = RE_BUILD_COMMAND.captures(line)?;
+            let args: Vec<&str> = caps.get(2)?.as_str().split_whitespace()
+                .take_while(|a| !["&&", "||", "|", ";"].contains(a))
+                .collect();
+            // `mvn -v` / `gradle --version` 等不是构建
+            args.iter().any(|a| !a.starts_with('-')).then(|| BuildCommand {
+                line: idx + 1,
+                maven: caps[1].starts_with("mvn"),
+                args,
+            })
+        })
+        .collect()
+}
+
+/// CI 流水线分析器
+pub struct CiPipelineAnalyzer;
+
+/// 检测到的问题: (规则 ID, 级别, 行号, 上下文)
+type Finding = (&'static str, Severity, usize, String);
+
+impl CiPipelineAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn description(id: &str) -> &'static str {
+        match id {
+            "CI_NO_DEPENDENCY_CACHE" => "CI 流水线未缓存 Maven/Gradle 依赖：每次构建重新下载全部依赖，耗时数分钟且受远程仓库限流影响，应使用 actions/cache / setup-java cache / setup-gradle (GitLab: cache: paths .m2/repository)",
+            "CI_TESTS_NO_PARALLEL" => "CI 中运行测试未开启并行：多核 runner 上测试串行执行，应使用 mvn -T 1C 与 surefire forkCount=1C，或 Gradle --parallel 与 maxParallelForks",
+            "CI_MVN_CLEAN_INSTALL" => "CI 中执行 mvn install：只为校验构建时多余地把产物写入本地仓库，全新检出的工作区 clean 也无必要；使用 mvn verify",
+            _ => "CI 流水线没有构建耗时信息：无法定位慢任务与慢测试，构建变慢后难以回溯，应开启 Gradle --scan / --profile 或 Maven profiler (-Dprofile)、时间戳日志",
+        }
+    }
+
+    /// 流水线中的全部问题，按行排序
+    pub fn findings(code: &str, pipeline: Pipeline) -> Vec<Finding> {
+        let commands = build_commands(code);
+        let Some(first) = commands.first() else { return Vec::new() };
+        let mut findings = Vec::new();
+
+        let cached = match pipeline {
+            Pipeline::GitHubActions => RE_GITHUB_CACHE.is_match(code),
+            Pipeline::GitLab => RE_GITLAB_CACHE.is_match(code),
+            // 固定 agent 上 ~/.m2 会保留，只有 docker agent 每次都是空仓库
+            Pipeline::Jenkins => !code.contains("docker") || code.contains(".m2") || code.contains(".gradle"),
+        };
+        if !cached {
+            findings.push(("CI_NO_DEPENDENCY_CACHE", Severity::P1, first.line, format!("{} 无依赖缓存", first.text())));
+        }
+
+        for command in &commands {
+            if command.runs_tests() && !command.parallel() {
+                let hint = if command.maven { "-T 1C -DforkCount=1C" } else { "--parallel" };
+                findings.push(("CI_TESTS_NO_PARALLEL", Severity::P2, command.line, format!("{} → {hint}", command.text())));
+            }
+            if command.maven && command.goals().any(|g| g == "install") {
+                let verify: Vec<&str> = command.args.iter().copied()
+                    .filter(|a| *a != "clean")
+                    .map(|a| if a == "install" { "verify" } else { a })
+                    .collect();
+                findings.push(("CI_MVN_CLEAN_INSTALL", Severity::P2, command.line, format!("{} → mvn {}", command.text(), verify.join(" "))));
+            }
+        }
+
+        if !RE_BUILD_TIMING.is_match(code) {
+            findings.push(("CI_NO_BUILD_TIMING", Severity::Info, first.line, format!("{} 无耗时分析", first.text())));
+        }
+        findings.sort_by_key(|(_, _, line, _)| *line);
+        findings
+    }
+}
+
+impl CodeAnalyzer for CiPipelineAnalyzer {
+    fn supported_extension(&self) -> &str {
+        "yml"
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let Some(pipeline) = pipeline(file_path) else { return Ok(Vec::new()) };
+        let file_name = file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let lines: Vec<&str> = code.lines().collect();
+        Ok(Self::findings(code, pipeline).into_iter().map(|(id, severity, line, context)| {
+            let span = Span::whole_line(line, lines.get(line.saturating_sub(1)).copied().unwrap_or(""));
+            Issue {
+                id: id.to_string(),
+                severity,
+                file: file_name.clone(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description: i18n::text(&format!("rule.{id}"), Self::description(id)).to_string(),
+                context: Some(context),
+                confidence: None,
+            }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(code: &str, pipeline: Pipeline) -> Vec<(&'static str, usize)> {
+        CiPipelineAnalyzer::findings(code, pipeline).into_iter().map(|(id, _, line, _)| (id, line)).collect()
+    }
+
+    #[test]
+    fn test_slow_github_workflow() {
+        let code = r#"
+jobs:
+  build:
+    runs-on: ubuntu-latest
+    steps:
+      - uses: actions/setup-java@v4
+        with:
+          distribution: temurin
+          java-version: 21
+      - run: mvn -B clean install
+"#;
+        assert_eq!(ids(code, Pipeline::GitHubActions), vec![
+            ("CI_NO_DEPENDENCY_CACHE", 10),
+            ("CI_TESTS_NO_PARALLEL", 10),
+            ("CI_MVN_CLEAN_INSTALL", 10),
+            ("CI_NO_BUILD_TIMING", 10),
+        ]);
+        let context = &CiPipelineAnalyzer::findings(code, Pipeline::GitHubActions)[2].3;
+        assert_eq!(context, "mvn -B clean install → mvn -B verify");
+    }
+
+    #[test]
+    fn test_tuned_pipelines() {
+        let github = r#"
+steps:
+  - uses: actions/setup-java@v4
+    with:
+      cache: maven
+  - run: ./mvnw -B -T 1C verify -Dprofile
+  - run: mvn -v
+"#;
+        assert!(ids(github, Pipeline::GitHubActions).is_empty());
+
+        let gitlab = "cache:\n  paths: [.gradle/caches]\ntest:\n  script:\n    - ./gradlew check --parallel --scan\n";
+        assert!(ids(gitlab, Pipeline::GitLab).is_empty());
+
+        let jenkins = "pipeline {\n  agent { docker { image 'maven:3.9' } }\n  stages { stage('b') { steps { sh 'mvn -B package -DskipTests' } } }\n}\n";
+        assert_eq!(ids(jenkins, Pipeline::Jenkins), vec![("CI_NO_DEPENDENCY_CACHE", 3), ("CI_NO_BUILD_TIMING", 3)]);
+
+        assert_eq!(pipeline(Path::new("repo/.github/workflows/ci.yml")), Some(Pipeline::GitHubActions));
+        assert_eq!(pipeline(Path::new("repo/.gitlab-ci.yml")), Some(Pipeline::GitLab));
+        assert_eq!(pipeline(Path::new("repo/Jenkinsfile")), Some(Pipeline::Jenkins));
+        assert_eq!(pipeline(Path::new("repo/config/ci.yml")), None);
+    }
+}
@@ -0,0 +1,325 @@
+//! docker-compose 资源分析 - v9.6
+//!
+//! 解析 `docker-compose*.yml` / `compose*.yaml` 的 `services` 后检查：
+//! - `COMPOSE_NO_MEM_LIMIT`: Java 服务未设置 `mem_limit` / `deploy.resources.limits.memory`，JVM 按宿主机内存计算堆大小
+//! - `COMPOSE_DB_DEFAULT_BUFFERS`: PostgreSQL / MySQL 未调整 `shared_buffers` / `innodb_buffer_pool_size` (默认 128MB)
+//! - `COMPOSE_NO_HEALTHCHECK`: 被依赖的服务或 Java 服务没有 healthcheck，无法判断何时真正就绪
+//! - `COMPOSE_DEPENDS_NO_CONDITION`: `depends_on` 只保证启动顺序，未等待 `service_healthy`，应用在数据库就绪前建连，启动时连接风暴
+//!
+//! 测试代码中 Testcontainers 容器的泄漏由 AST 规则 `TEST_CONTAINER_LEAK` 检查。
+
+use super::dockerfile::is_java_image;
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use anyhow::Result;
+use serde_yaml::Value;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// 表明服务运行 JVM 的环境变量前缀
+const JVM_ENV_PREFIXES: &[&str] = &["JAVA_OPTS", "JAVA_TOOL_OPTIONS", "JDK_JAVA_OPTIONS", "SPRING_", "CATALINA_OPTS"];
+
+/// 等待依赖就绪的 `depends_on.condition`
+const READY_CONDITIONS: &[&str] = &["service_healthy", "service_completed_successfully"];
+
+/// 是否为 compose 文件
+pub fn is_compose_file(file_name: &str) -> bool {
+    let lower = file_name.to_ascii_lowercase();
+    (lower.starts_with("docker-compose") || lower.starts_with("compose"))
+        && (lower.ends_with(".yml") || lower.ends_with(".yaml"))
+}
+
+/// 服务及其顶层键的行号 (serde_yaml 不保留位置，按缩进从原文定位)
+#[derive(Debug, Default)]
+struct Layout {
+    services: HashMap<String, (usize, HashMap<String, usize>)>,
+}
+
+impl Layout {
+    fn parse(code: &str) -> Self {
+        let mut layout = Layout::default();
+        let mut in_services = false;
+        let mut service_indent = None;
+        let mut key_indent = None;
+        let mut current: Option<String> = None;
+        for (idx, line) in code.lines().enumerate() {
+            let trimmed = line.trim_end();
+            let content = trimmed.trim_start();
+            if content.is_empty() || content.starts_with('#') {
+                continue;
+            }
+            let indent = trimmed.len() - content.len();
+            if indent == 0 {
+                in_services = content.starts_with("services:");
+                current = None;
+                continue;
+            }
+            let Some(key) = in_services.then(|| content.split_once(':')).flatten().map(|(k, _)| k.trim().trim_matches(['"', '\''])) else {
+                continue;
+            };
+            let service_indent = *service_indent.get_or_insert(indent);
+            if indent == service_indent {
+                layout.services.insert(key.to_string(), (idx + 1, HashMap::new()));
+                current = Some(key.to_string());
+                key_indent = None;
+            } else if let Some(service) = &current {
+                let key_indent = *key_indent.get_or_insert(indent);
+                if indent == key_indent {
+                    if let Some((_, keys)) = layout.services.get_mut(service) {
+                        keys.entry(key.to_string()).or_insert(idx + 1);
+                    }
+                }
+            }
+        }
+        layout
+    }
+
+    /// 服务某个键所在行，键不存在时为服务所在行
+    fn line(&self, service: &str, key: Option<&str>) -> usize {
+        let Some((line, keys)) = self.services.get(service) else { return 0 };
+        key.and_then(|k| keys.get(k)).copied().unwrap_or(*line)
+    }
+}
+
+/// 字符串 / 列表 / 映射展平为空格分隔的文本 (`command`、`environment` 两种写法统一处理)
+fn flatten(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Sequence(items) => items.iter().map(flatten).collect::<Vec<_>>().join(" "),
+        Value::Mapping(map) => map.iter()
+            .map(|(k, v)| format!("{}={}", flatten(k), flatten(v)))
+            .collect::<Vec<_>>()
+            .join(" "),
+        _ => String::new(),
+    }
+}
+
+fn field<'a>(service: &'a Value, path: &[&str]) -> Option<&'a Value> {
+    path.iter().try_fold(service, |v, key| v.get(*key))
+}
+
+/// 镜像名 (去掉仓库路径与标签)，小写
+fn image_name(service: &Value) -> String {
+    let image = service.get("image").and_then(Value::as_str).unwrap_or("");
+    let image = image.split('@').next().unwrap_or(image);
+    let last = image.rsplit('/').next().unwrap_or(image);
+    last.split(':').next().unwrap_or(last).to_ascii_lowercase()
+}
+
+/// Java 服务: Java 镜像、自行构建的应用镜像、JVM 环境变量或 `java` 启动命令
+fn is_java_service(service: &Value) -> bool {
+    let image = service.get("image").and_then(Value::as_str);
+    if image.is_some_and(is_java_image) || service.get("build").is_some() {
+        return true;
+    }
+    let env = service.get("environment").map(flatten).unwrap_or_default();
+    let command = ["command", "entrypoint"].iter()
+        .filter_map(|k| service.get(*k))
+        .map(flatten)
+        .collect::<Vec<_>>()
+        .join(" ");
+    env.split_whitespace().any(|var| JVM_ENV_PREFIXES.iter().any(|p| var.starts_with(p)))
+        || command.split_whitespace().any(|word| word == "java" || word.ends_with("/java"))
+}
+
+/// 未调整缓冲区的数据库: (数据库, 参数名)
+fn default_buffers(service: &Value) -> Option<(&'static str, &'static str)> {
+    let image = image_name(service);
+    let (db, param, config_files): (_, _, &[&str]) = if image.starts_with("postgres") || image.starts_with("postgis") {
+        ("PostgreSQL", "shared_buffers", &["postgresql.conf"])
+    } else if ["mysql", "mariadb", "percona"].iter().any(|p| image.starts_with(p)) {
+        ("MySQL", "innodb_buffer_pool_size", &[".cnf", "conf.d"])
+    } else {
+        return None;
+    };
+    let settings = ["command", "environment"].iter()
+        .filter_map(|k| service.get(*k))
+        .map(flatten)
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('-', "_");
+    let volumes = service.get("volumes").map(flatten).unwrap_or_default();
+    let tuned = settings.contains(param) || config_files.iter().any(|f| volumes.contains(f));
+    (!tuned).then_some((db, param))
+}
+
+/// `depends_on` 中的依赖: (服务名, 是否等待其就绪)
+fn dependencies(service: &Value) -> Vec<(String, bool)> {
+    match service.get("depends_on") {
+        Some(Value::Sequence(items)) => items.iter().filter_map(Value::as_str).map(|name| (name.to_string(), false)).collect(),
+        Some(Value::Mapping(map)) => map.iter()
+            .filter_map(|(name, spec)| {
+                let condition = spec.get("condition").and_then(Value::as_str).unwrap_or("service_started");
+                Some((name.as_str()?.to_string(), READY_CONDITIONS.contains(&condition)))
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// docker-compose 分析器
+pub struct ComposeAnalyzer;
+
+/// 检测到的问题: (规则 ID, 级别, 服务, 定位的键, 描述)
+type Finding = (&'static str, Severity, String, Option<&'static str>, String);
+
+impl ComposeAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    /// compose 文件中的全部问题，按服务声明顺序
+    fn findings(code: &str) -> Vec<Finding> {
+        let Ok(doc) = serde_yaml::from_str::<Value>(code) else { return Vec::new() };
+        let Some(services) = doc.get("services").and_then(Value::as_mapping) else { return Vec::new() };
+        let depended: Vec<String> = services.values().flat_map(dependencies).map(|(name, _)| name).collect();
+        let mut findings = Vec::new();
+
+        for (name, service) in services {
+            let Some(name) = name.as_str() else { continue };
+            let java = is_java_service(service);
+
+            let mem_limit = service.get("mem_limit").is_some()
+                || field(service, &["deploy", "resources", "limits", "memory"]).is_some();
+            if java && !mem_limit {
+                findings.push(("COMPOSE_NO_MEM_LIMIT", Severity::P1, name.to_string(), None, i18n::format(
+                    "compose.COMPOSE_NO_MEM_LIMIT",
+                    "Java 服务 {service} 未设置 mem_limit / deploy.resources.limits.memory：JVM 按宿主机内存计算默认堆 (1/4)，多个服务同机时总和超出物理内存被 OOM Killer 杀掉；设置内存上限并配合 -XX:MaxRAMPercentage=75",
+                    &[("service", &name)],
+                )));
+            }
+
+            if let Some((db, param)) = default_buffers(service) {
+                findings.push(("COMPOSE_DB_DEFAULT_BUFFERS", Severity::P2, name.to_string(), Some("image"), i18n::format(
+                    "compose.COMPOSE_DB_DEFAULT_BUFFERS",
+                    "{db} 服务 {service} 使用默认 {param} (128MB)：热数据放不进缓冲池，压测与集成测试结果远差于生产；通过 command 设置为容器内存的 25% (PostgreSQL) / 50~70% (MySQL)",
+                    &[("db", &db), ("service", &name), ("param", &param)],
+                )));
+            }
+
+            let healthcheck = service.get("healthcheck").is_some();
+            if !healthcheck && (java || depended.iter().any(|d| d == name)) {
+                findings.push(("COMPOSE_NO_HEALTHCHECK", Severity::P2, name.to_string(), None, i18n::format(
+                    "compose.COMPOSE_NO_HEALTHCHECK",
+                    "服务 {service} 没有 healthcheck：依赖方无法等待其真正就绪，进程启动后尚未可用就被视为正常；添加 healthcheck (如 pg_isready / mysqladmin ping / actuator/health)",
+                    &[("service", &name)],
+                )));
+            }
+
+            let unready: Vec<String> = dependencies(service).into_iter()
+                .filter(|(_, ready)| !ready)
+                .map(|(name, _)| name)
+                .collect();
+            if !unready.is_empty() {
+                let deps = unready.join(", ");
+                findings.push(("COMPOSE_DEPENDS_NO_CONDITION", Severity::P1, name.to_string(), Some("depends_on"), i18n::format(
+                    "compose.COMPOSE_DEPENDS_NO_CONDITION",
+                    "服务 {service} 的 depends_on ({deps}) 只保证启动顺序：应用在依赖就绪前初始化连接池，失败重试叠加形成启动时连接风暴；使用 condition: service_healthy 并为依赖配置 healthcheck",
+                    &[("service", &name), ("deps", &deps)],
+                )));
+            }
+        }
+        findings
+    }
+}
+
+impl CodeAnalyzer for ComposeAnalyzer {
+    fn supported_extension(&self) -> &str {
+        "yml"
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let file_name = file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let layout = Layout::parse(code);
+        let lines: Vec<&str> = code.lines().collect();
+        Ok(Self::findings(code).into_iter().map(|(id, severity, service, key, description)| {
+            let line = layout.line(&service, key);
+            let span = Span::whole_line(line, lines.get(line.saturating_sub(1)).copied().unwrap_or(""));
+            Issue {
+                id: id.to_string(),
+                severity,
+                file: file_name.clone(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description,
+                context: Some(format!("services.{service}")),
+                confidence: None,
+            }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn analyze(code: &str) -> Vec<(String, usize)> {
+        ComposeAnalyzer::new().unwrap()
+            .analyze(code, &PathBuf::from("docker-compose.yml")).unwrap()
+            .into_iter().map(|i| (i.id, i.line)).collect()
+    }
+
+    #[test]
+    fn test_compose_resources_and_startup_order() {
+        let issues = analyze(r#"
+services:
+  app:
+    build: .
+    environment:
+      - JAVA_OPTS=-Xss512k
+    depends_on:
+      - db
+      - redis
+  db:
+    image: postgres:16
+  redis:
+    image: redis:7
+    healthcheck:
+      test: ["CMD", "redis-cli", "ping"]
+"#);
+        assert_eq!(issues, vec![
+            ("COMPOSE_NO_MEM_LIMIT".to_string(), 3),
+            ("COMPOSE_NO_HEALTHCHECK".to_string(), 3),
+            ("COMPOSE_DEPENDS_NO_CONDITION".to_string(), 7),
+            ("COMPOSE_DB_DEFAULT_BUFFERS".to_string(), 11),
+            ("COMPOSE_NO_HEALTHCHECK".to_string(), 10),
+        ]);
+    }
+
+    #[test]
+    fn test_compose_tuned_services() {
+        let issues = analyze(r#"
+services:
+  app:
+    image: eclipse-temurin:21-jre
+    mem_limit: 1g
+    healthcheck:
+      test: ["CMD", "curl", "-f", "http://localhost:8080/actuator/health"]
+    depends_on:
+      db:
+        condition: service_healthy
+  db:
+    image: mysql:8.4
+    command: --innodb-buffer-pool-size=512M
+    deploy:
+      resources:
+        limits:
+          memory: 1g
+    healthcheck:
+      test: ["CMD", "mysqladmin", "ping"]
+  web:
+    image: nginx:1.27
+"#);
+        assert!(issues.is_empty(), "{issues:?}");
+        assert!(is_compose_file("docker-compose.prod.yml"));
+        assert!(is_compose_file("compose.yaml"));
+        assert!(!is_compose_file("application.yml"));
+    }
+}
@@ -1,4 +1,6 @@
-use super::{CodeAnalyzer, Issue, Severity};
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use std::collections::HashMap;
 use std::path::Path;
 use anyhow::Result;
 use serde::Deserialize;
@@ -70,6 +72,7 @@ struct TomcatConfig {
 /// 这足以处理 key=value (properties) 和 key: value (yaml) 的简单情况
 pub struct LineBasedConfigAnalyzer {
     rules: Vec<ConfigRule>,
+    env: PlaceholderEnv,
 }
 
 struct ConfigRule {
@@ -201,6 +204,58 @@ impl LineBasedConfigAnalyzer {
                     description: "Redis 超时配置过长 (建议 <= 10s)",
                 },
             ],
+            env: PlaceholderEnv::default(),
+        })
+    }
+
+    /// 使用 `--env-file` 中的变量解析 `${VAR:default}` 占位符
+    pub fn with_env(env: PlaceholderEnv) -> Result<Self> {
+        Ok(Self { env, ..Self::new()? })
+    }
+
+    /// 是否为配置文件规则 (行匹配规则，以及结构化解析独有的 `DB_CONNECTION_TIMEOUT_LONG`)
+    pub fn covers(&self, rule_id: &str) -> bool {
+        rule_id == "DB_CONNECTION_TIMEOUT_LONG" || self.rules.iter().any(|rule| rule.id == rule_id)
+    }
+
+    /// 按完整 key 检查一个配置项 (key 为 Spring 宽松绑定的规范形式，见 [`normalize_key`])
+    pub fn check_property(&self, key: &str, raw_value: &str, file_name: &str, line: usize, context: &str) -> Vec<Issue> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.full_key == key)
+            .filter_map(|rule| self.check(rule, raw_value, file_name, line, context))
+            .collect()
+    }
+
+    fn check(&self, rule: &ConfigRule, raw_value: &str, file_name: &str, line: usize, context: &str) -> Option<Issue> {
+        // v9.6: `${DB_POOL:2}` 按生效值校验，报告中保留原始写法
+        let resolved = self.env.resolve(raw_value);
+        if (rule.validator)(resolved.as_deref().unwrap_or(raw_value)) {
+            return None;
+        }
+        let shown = match &resolved {
+            Some(value) => format!("{} ← {}", value.split('#').next().unwrap_or("").trim(), raw_value),
+            None => raw_value.to_string(),
+        };
+        let span = Span::whole_line(line, context);
+        Some(Issue {
+            id: rule.id.to_string(),
+            severity: rule.severity,
+            file: file_name.to_string(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: i18n::format(
+                "config.value_suffix",
+                "{description} (Value: {value})",
+                &[
+                    ("description", &i18n::text(&format!("rule.{}", rule.id), rule.description)),
+                    ("value", &shown),
+                ],
+            ),
+            context: Some(context.to_string()),
+            confidence: None, // Config rules don't use confidence
         })
     }
 }
@@ -243,22 +298,10 @@ impl CodeAnalyzer for LineBasedConfigAnalyzer {
                     };
 
                     if parts.len() == 2 {
-                        let key_part = parts[0].trim();
-                        let value_part = parts[1].trim();
-
                         // 确保 key 匹配 (Key 必须以 pattern 结尾)
-                        if key_part.ends_with(pattern)
-                             && !(rule.validator)(value_part) {
-                                 issues.push(Issue {
-                                    id: rule.id.to_string(),
-                                    severity: rule.severity,
-                                    file: file_name.clone(),
-                                    line: line_num + 1,
-                                    description: format!("{} (Value: {})", rule.description, value_part),
-                                    context: Some(line.to_string()),
-                                    confidence: None, // Config rules don't use confidence
-                                });
-                             }
+                        if parts[0].trim().ends_with(pattern) {
+                            issues.extend(self.check(rule, parts[1].trim(), &file_name, line_num + 1, line));
+                        }
                     }
                 }
             }
@@ -291,7 +334,10 @@ impl LineBasedConfigAnalyzer {
                     severity: Severity::P1,
                     file: file_name.to_string(),
                     line: 0, // 结构化解析无法获取行号
-                    description: format!("数据库连接池过小: {} (建议 >= 10)", pool_size),
+                    column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    description: i18n::format("config.DB_POOL_SMALL.value", "数据库连接池过小: {size} (建议 >= 10)", &[("size", &pool_size)]),
                     context: Some(format!("maximum-pool-size: {}", pool_size)),
                     confidence: None, // Config rules don't use confidence
                 });
@@ -305,7 +351,10 @@ impl LineBasedConfigAnalyzer {
                     severity: Severity::P1,
                     file: file_name.to_string(),
                     line: 0,
-                    description: format!("连接超时过长: {}ms (建议 <= 30000)", timeout),
+                    column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    description: i18n::format("config.DB_CONNECTION_TIMEOUT_LONG.value", "连接超时过长: {timeout}ms (建议 <= 30000)", &[("timeout", &timeout)]),
                     context: Some(format!("connection-timeout: {}", timeout)),
                     confidence: None, // Config rules don't use confidence
                 });
@@ -319,7 +368,10 @@ impl LineBasedConfigAnalyzer {
                 severity: Severity::P0,
                 file: file_name.to_string(),
                 line: 0,
-                description: "JPA open-in-view=true 会导致延迟加载问题".to_string(),
+                column: 0,
+                end_line: 0,
+                end_column: 0,
+                description: i18n::text("config.JPA_OPEN_IN_VIEW.value", "JPA open-in-view=true 会导致延迟加载问题").to_string(),
                 context: Some("open-in-view: true".to_string()),
                 confidence: None, // Config rules don't use confidence
             });
@@ -331,7 +383,10 @@ impl LineBasedConfigAnalyzer {
                 severity: Severity::P1,
                 file: file_name.to_string(),
                 line: 0,
-                description: "JPA show-sql=true 影响性能".to_string(),
+                column: 0,
+                end_line: 0,
+                end_column: 0,
+                description: i18n::text("config.JPA_SHOW_SQL_PROD.value", "JPA show-sql=true 影响性能").to_string(),
                 context: Some("show-sql: true".to_string()),
                 confidence: None, // Config rules don't use confidence
             });
@@ -345,7 +400,10 @@ impl LineBasedConfigAnalyzer {
                     severity: Severity::P1,
                     file: file_name.to_string(),
                     line: 0,
-                    description: format!("Tomcat 最大线程数过低: {} (默认 200)", threads),
+                    column: 0,
+                    end_line: 0,
+                    end_column: 0,
+                    description: i18n::format("config.TOMCAT_THREADS_LOW.value", "Tomcat 最大线程数过低: {threads} (默认 200)", &[("threads", &threads)]),
                     context: Some(format!("max-threads: {}", threads)),
                     confidence: None, // Config rules don't use confidence
                 });
@@ -356,6 +414,226 @@ impl LineBasedConfigAnalyzer {
     }
 }
 
+/// 配置文件分析器 (v9.6)
+///
+/// YAML 优先结构化解析，无结果时退回行匹配；Properties 使用行匹配。
+pub struct ConfigFileAnalyzer {
+    inner: LineBasedConfigAnalyzer,
+}
+
+impl ConfigFileAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self { inner: LineBasedConfigAnalyzer::new()? })
+    }
+
+    pub fn with_env(env: PlaceholderEnv) -> Result<Self> {
+        Ok(Self { inner: LineBasedConfigAnalyzer::with_env(env)? })
+    }
+}
+
+impl CodeAnalyzer for ConfigFileAnalyzer {
+    fn supported_extension(&self) -> &str {
+        self.inner.supported_extension()
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let ext = file_path.extension().and_then(|e| e.to_str()).unwrap_or("");
+        if ["yml", "yaml"].contains(&ext) {
+            let file_name = file_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            let resolved = self.inner.env.resolve(code);
+            let structured = self.inner.analyze_yaml_structured(resolved.as_deref().unwrap_or(code), &file_name);
+            if !structured.is_empty() {
+                return Ok(structured);
+            }
+        }
+        self.inner.analyze(code, file_path)
+    }
+}
+
+// ============================================================================
+// v9.6: 占位符解析 (`${VAR:default}`)
+// ============================================================================
+
+/// 占位符变量: 来自 `scan --env-file` (`KEY=VALUE` 每行一个，支持 `#` 注释、`export ` 前缀与引号)
+///
+/// 不读取当前进程的环境变量，扫描结果不随执行环境变化；未提供的变量使用占位符中的默认值，
+/// 既无变量也无默认值的占位符保持原样 (规则视为无法判断)。
+#[derive(Debug, Clone, Default)]
+pub struct PlaceholderEnv {
+    vars: HashMap<String, String>,
+}
+
+impl PlaceholderEnv {
+    /// 读取 env 文件
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| anyhow::anyhow!("--env-file {}: {e}", path.display()))?;
+        Ok(Self::parse(&content))
+    }
+
+    fn parse(content: &str) -> Self {
+        let vars = content.lines().filter_map(|line| {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                return None;
+            }
+            let (key, value) = line.strip_prefix("export ").unwrap_or(line).split_once('=')?;
+            let value = value.trim();
+            let value = match value.chars().next() {
+                Some(q @ ('"' | '\'')) if value.len() >= 2 && value.ends_with(q) => &value[1..value.len() - 1],
+                _ => value.split(" #").next().unwrap_or("").trim(),
+            };
+            Some((key.trim().to_string(), value.to_string()))
+        });
+        Self { vars: vars.collect() }
+    }
+
+    /// 解析文本中的 `${VAR}` / `${VAR:default}` (默认值可嵌套占位符)，不含占位符时返回 None
+    pub fn resolve(&self, text: &str) -> Option<String> {
+        if !text.contains("${") {
+            return None;
+        }
+        let mut out = String::with_capacity(text.len());
+        let mut rest = text;
+        while let Some(start) = rest.find("${") {
+            out.push_str(&rest[..start]);
+            let body = &rest[start + 2..];
+            let Some(end) = closing_brace(body) else {
+                out.push_str(&rest[start..]);
+                return Some(out);
+            };
+            let inner = &body[..end];
+            let (name, default) = match inner.split_once(':') {
+                Some((name, default)) => (name, Some(default)),
+                None => (inner, None),
+            };
+            match (self.vars.get(name.trim()), default) {
+                (Some(value), _) => out.push_str(value),
+                (None, Some(default)) => out.push_str(&self.resolve(default).unwrap_or_else(|| default.to_string())),
+                (None, None) => out.push_str(&rest[start..start + 2 + end + 1]),
+            }
+            rest = &body[end + 1..];
+        }
+        out.push_str(rest);
+        Some(out)
+    }
+}
+
+/// `${` 之后与之匹配的 `}` 位置
+fn closing_brace(body: &str) -> Option<usize> {
+    let mut depth = 0;
+    for (idx, c) in body.char_indices() {
+        match c {
+            '{' => depth += 1,
+            '}' if depth == 0 => return Some(idx),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+// ============================================================================
+// v9.6: 配置项展开 (跨文件规则定位配置位置)
+// ============================================================================
+
+/// 一条配置项: 行号 (1-based)、完整 key (YAML 按缩进展开为 `a.b.c`)、值
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PropertyEntry {
+    pub line: usize,
+    pub key: String,
+    pub value: String,
+}
+
+/// 构建产物目录 (其中的 application.yml 是复制出来的)
+pub const BUILD_OUTPUT_DIRS: &[&str] = &["target", "build", "out", ".git", "node_modules"];
+
+/// 按行展开配置文件；YAML 列表项与多文档分隔符不产生配置项
+pub fn property_entries(content: &str, yaml: bool) -> Vec<PropertyEntry> {
+    let mut entries = Vec::new();
+    let mut parents: Vec<(usize, String)> = Vec::new();
+    for (idx, raw) in content.lines().enumerate() {
+        let line = raw.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with('!') || line == "---" {
+            continue;
+        }
+        let split = if yaml { line.split_once(':') } else { line.split_once(['=', ':']) };
+        let Some((key, value)) = split else { continue };
+        let value = value.split(" #").next().unwrap_or("").trim().trim_matches(['"', '\'']).to_string();
+        let key = key.trim();
+        if !yaml {
+            entries.push(PropertyEntry { line: idx + 1, key: key.to_string(), value });
+            continue;
+        }
+        if line.starts_with("- ") {
+            continue;
+        }
+        let indent = raw.len() - raw.trim_start().len();
+        while parents.last().is_some_and(|(level, _)| *level >= indent) {
+            parents.pop();
+        }
+        let full = parents.iter().map(|(_, k)| k.as_str()).chain([key]).collect::<Vec<_>>().join(".");
+        if value.is_empty() {
+            parents.push((indent, key.to_string()));
+        } else {
+            entries.push(PropertyEntry { line: idx + 1, key: full, value });
+        }
+    }
+    entries
+}
+
+/// Spring 宽松绑定的规范形式: 小写、`-` 分隔 (`maximumPoolSize` / `maximum_pool_size` → `maximum-pool-size`)
+pub fn normalize_key(key: &str) -> String {
+    let mut out = String::with_capacity(key.len() + 4);
+    let mut prev_lower = false;
+    for c in key.trim().chars() {
+        if c.is_ascii_uppercase() && prev_lower {
+            out.push('-');
+        }
+        prev_lower = c.is_ascii_lowercase() || c.is_ascii_digit();
+        out.push(if c == '_' { '-' } else { c.to_ascii_lowercase() });
+    }
+    out
+}
+
+/// 项目中的 Spring 配置文件 `application*` / `bootstrap*` (跳过测试资源与构建产物)
+///
+/// 返回 (相对 `root` 的路径, 配置项)，按路径排序
+pub fn spring_config_files(root: &Path) -> Vec<(String, Vec<PropertyEntry>)> {
+    spring_config_contents(root)
+        .into_iter()
+        .map(|(rel, content)| {
+            let yaml = !rel.ends_with(".properties");
+            let entries = property_entries(&content, yaml);
+            (rel, entries)
+        })
+        .collect()
+}
+
+/// 项目中的 Spring 配置文件及其内容 (相对 `root` 的路径，按路径排序)
+pub fn spring_config_contents(root: &Path) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = walkdir::WalkDir::new(root)
+        .into_iter()
+        .filter_entry(|e| !e.file_type().is_dir() || !BUILD_OUTPUT_DIRS.contains(&e.file_name().to_string_lossy().as_ref()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy();
+            let yaml = name.ends_with(".yml") || name.ends_with(".yaml");
+            let is_config = (name.starts_with("application") || name.starts_with("bootstrap"))
+                && (yaml || name.ends_with(".properties"));
+            let rel = entry.path().strip_prefix(root).unwrap_or(entry.path());
+            if !is_config || crate::rules::test_code::is_test_source(rel) {
+                return None;
+            }
+            let content = std::fs::read_to_string(entry.path()).ok()?;
+            Some((rel.to_string_lossy().replace('\\', "/"), content))
+        })
+        .collect();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -397,6 +675,30 @@ server.tomcat.max-threads=250
         assert_eq!(issues[0].id, "DB_POOL_SMALL");
     }
 
+    #[test]
+    fn test_placeholder_resolution() {
+        let env = PlaceholderEnv::parse("# db\nexport DB_POOL=20\nREDIS_TIMEOUT=\"2s\"\n");
+        assert_eq!(env.resolve("${DB_POOL:2}").as_deref(), Some("20"));
+        assert_eq!(env.resolve("${DB_MAX:${DB_POOL:3}}").as_deref(), Some("20"));
+        assert_eq!(env.resolve("${MISSING:${OTHER:4}}ms").as_deref(), Some("4ms"));
+        assert_eq!(env.resolve("${MISSING}").as_deref(), Some("${MISSING}"));
+        assert_eq!(env.resolve("${REDIS_TIMEOUT}").as_deref(), Some("2s"));
+        assert_eq!(env.resolve("10"), None);
+
+        let yaml = "spring:\n  datasource:\n    hikari:\n      maximum-pool-size: ${DB_POOL:2}\n";
+        let analyzer = ConfigFileAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(yaml, &PathBuf::from("application.yml")).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].id, "DB_POOL_SMALL");
+        let analyzer = ConfigFileAnalyzer::with_env(env).unwrap();
+        assert!(analyzer.analyze(yaml, &PathBuf::from("application.yml")).unwrap().is_empty());
+
+        let properties = "spring.datasource.hikari.maximum-pool-size=${DB_POOL:2}\n";
+        let issues = LineBasedConfigAnalyzer::new().unwrap().analyze(properties, &PathBuf::from("application.properties")).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].description.contains("2 ← ${DB_POOL:2}"), "{}", issues[0].description);
+    }
+
     #[test]
     fn test_structured_yaml_analysis() {
         let code = r#"
@@ -1,4 +1,5 @@
-use super::{CodeAnalyzer, Issue, Severity};
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
 use std::path::Path;
 use anyhow::Result;
 use once_cell::sync::Lazy;
@@ -7,6 +8,9 @@ use regex::Regex;
 /// Dockerfile 分析器
 /// 
 /// 检测常见的 Dockerfile 性能和安全问题
+///
+/// v9.6: 另检查 Java 基础镜像 (见 [`check_base_images`])：运行阶段使用完整 JDK、JDK 8 镜像、
+/// 未用 jlink 裁剪的非精简镜像、Maven/Gradle 构建未分阶段、运行阶段未切换非 root 用户。
 pub struct DockerfileAnalyzer {
     rules: Vec<DockerfileRule>,
 }
@@ -39,6 +43,32 @@ static RE_RUN_APT_NO_CLEAN: Lazy<Regex> = Lazy::new(|| {
     Regex::new(r"(?i)^RUN\s+apt(-get)?\s+install").unwrap()
 });
 
+/// `FROM [--platform=...] image[:tag][@digest] [AS name]`
+static RE_FROM: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^FROM\s+(?:--\S+\s+)*(\S+)(?:\s+AS\s+(\S+))?").unwrap()
+});
+
+/// RUN 中的 Maven / Gradle 构建
+static RE_RUN_BUILD_TOOL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^RUN\s+.*(\bmvn\b|mvnw\b|\bgradle\b|gradlew\b)").unwrap()
+});
+
+/// JDK 8 标签: `8`、`8-jre`、`8u372-...`、`1.8`、`3.8-openjdk-8`
+static RE_JAVA8_TAG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)^(8|1\.8)(\D|$)|(jdk|jre|java|openjdk)-?8(\D|$)|\b8u\d+").unwrap()
+});
+
+/// Java 镜像仓库名 (取最后一段路径)
+const JAVA_IMAGES: &[&str] = &[
+    "openjdk", "eclipse-temurin", "amazoncorretto", "adoptopenjdk", "ibm-semeru-runtimes", "sapmachine", "java",
+];
+
+/// 自带 JDK 的构建工具镜像
+const BUILD_TOOL_IMAGES: &[&str] = &["maven", "gradle"];
+
+/// 推荐的运行阶段镜像 (写入修复建议)
+const SUGGESTED_RUNTIME: &str = "eclipse-temurin:21-jre-alpine / gcr.io/distroless/java21-debian12";
+
 impl DockerfileAnalyzer {
     pub fn new() -> Result<Self> {
         Ok(Self {
@@ -106,12 +136,16 @@ impl CodeAnalyzer for DockerfileAnalyzer {
                         }
                     }
 
+                    let span = Span::whole_line(line_num + 1, line);
                     issues.push(Issue {
                         id: rule.id.to_string(),
                         severity: rule.severity,
                         file: file_name.clone(),
-                        line: line_num + 1,
-                        description: rule.description.to_string(),
+                        line: span.line,
+                        column: span.column,
+                        end_line: span.end_line,
+                        end_column: span.end_column,
+                        description: i18n::text(&format!("rule.{}", rule.id), rule.description).to_string(),
                         context: Some(trimmed.chars().take(60).collect()),
                         confidence: None, // Dockerfile rules don't use confidence
                     });
@@ -130,6 +164,8 @@ impl CodeAnalyzer for DockerfileAnalyzer {
                 }
         }
 
+        check_base_images(code, &file_name, &mut issues);
+
         // 检查多个 RUN 命令 (建议合并)
         if run_count > 5 {
             issues.push(Issue {
@@ -137,7 +173,10 @@ impl CodeAnalyzer for DockerfileAnalyzer {
                 severity: Severity::P1,
                 file: file_name.clone(),
                 line: 1,
-                description: format!("有 {run_count} 个 RUN 命令，建议使用 && 合并减少层数"),
+                column: 0, // 文件级问题，无精确位置
+                end_line: 0,
+                end_column: 0,
+                description: i18n::format("docker.DOCKER_MANY_LAYERS", "有 {count} 个 RUN 命令，建议使用 && 合并减少层数", &[("count", &run_count)]),
                 context: None,
                 confidence: None, // Dockerfile rules don't use confidence
             });
@@ -150,7 +189,10 @@ impl CodeAnalyzer for DockerfileAnalyzer {
                 severity: Severity::P1,
                 file: file_name.clone(),
                 line: 1,
-                description: "apt-get install 后未清理缓存，镜像体积增大".to_string(),
+                column: 0, // 文件级问题，无精确位置
+                end_line: 0,
+                end_column: 0,
+                description: i18n::text("docker.DOCKER_APT_NO_CLEAN", "apt-get install 后未清理缓存，镜像体积增大").to_string(),
                 context: None,
                 confidence: None, // Dockerfile rules don't use confidence
             });
@@ -160,6 +202,156 @@ impl CodeAnalyzer for DockerfileAnalyzer {
     }
 }
 
+/// 构建阶段 (一条 FROM 及其后的指令)
+struct Stage<'a> {
+    /// FROM 所在行 (0-based) 与原文
+    line: usize,
+    text: &'a str,
+    image: &'a str,
+    /// 引用前面阶段 (`FROM build`) 时为 true
+    from_stage: bool,
+    /// 本阶段最后一个 USER 指令的用户
+    user: Option<&'a str>,
+}
+
+/// 镜像 `registry/ns/name:tag@digest` 的 (name, tag)
+fn split_image(image: &str) -> (&str, &str) {
+    let image = image.split('@').next().unwrap_or(image);
+    let last = image.rsplit('/').next().unwrap_or(image);
+    match last.split_once(':') {
+        Some((name, tag)) => (name, tag),
+        None => (last, ""),
+    }
+}
+
+pub(crate) fn is_java_image(image: &str) -> bool {
+    let (name, _) = split_image(image);
+    let name = name.to_ascii_lowercase();
+    JAVA_IMAGES.contains(&name.as_str())
+        || BUILD_TOOL_IMAGES.contains(&name.as_str())
+        || name.starts_with("zulu-openjdk")
+        || name.starts_with("liberica-openjdk")
+        || (image.contains("distroless") && name.starts_with("java"))
+}
+
+/// 完整 JDK 镜像: Maven/Gradle 镜像，或标签未标明 jre 的 JDK 发行版 (openjdk/temurin 等默认即 JDK)
+fn is_full_jdk(image: &str) -> bool {
+    let (name, tag) = split_image(image);
+    let (name, tag) = (name.to_ascii_lowercase(), tag.to_ascii_lowercase());
+    if BUILD_TOOL_IMAGES.contains(&name.as_str()) {
+        return true;
+    }
+    is_java_image(image) && !image.contains("distroless") && !tag.contains("jre")
+}
+
+/// 精简镜像: alpine / slim / distroless / 最小化变体
+fn is_slim(image: &str) -> bool {
+    let lower = image.to_ascii_lowercase();
+    ["alpine", "slim", "distroless", "minimal", "chiseled"].iter().any(|v| lower.contains(v))
+}
+
+/// Java 基础镜像检查 (v9.6)
+///
+/// - `DOCKER_NO_MULTI_STAGE`: 只有一个阶段却在镜像内执行 Maven/Gradle 构建，构建工具与源码进入最终镜像
+/// - `DOCKER_JDK_RUNTIME`: 多阶段构建的运行阶段仍是完整 JDK (编译器、jshell 等)，应换成 JRE
+/// - `DOCKER_JDK8_IMAGE`: 任一阶段使用 JDK 8 镜像 (容器感知不完整、GC 落后)
+/// - `DOCKER_FAT_IMAGE`: 运行阶段是非精简的 JRE 镜像且未用 jlink 裁剪运行时
+/// - `DOCKER_NO_USER`: 运行阶段没有 USER 或仍为 root (distroless `nonroot` 标签除外)
+fn check_base_images(code: &str, file_name: &str, issues: &mut Vec<Issue>) {
+    let mut stages: Vec<Stage> = Vec::new();
+    let mut stage_names: Vec<String> = Vec::new();
+    let mut build_line: Option<(usize, &str)> = None;
+    for (line_num, line) in code.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(caps) = RE_FROM.captures(trimmed) {
+            let image = caps.get(1).map_or("", |m| m.as_str());
+            stages.push(Stage {
+                line: line_num,
+                text: line,
+                image,
+                from_stage: stage_names.iter().any(|name| name.eq_ignore_ascii_case(image)),
+                user: None,
+            });
+            if let Some(name) = caps.get(2) {
+                stage_names.push(name.as_str().to_string());
+            }
+        } else if let Some(user) = trimmed.strip_prefix("USER ").or_else(|| trimmed.strip_prefix("user ")) {
+            if let Some(stage) = stages.last_mut() {
+                stage.user = Some(user.trim());
+            }
+        } else if build_line.is_none() && RE_RUN_BUILD_TOOL.is_match(trimmed) {
+            build_line = Some((line_num, line));
+        }
+    }
+    let Some(runtime) = stages.last() else {
+        return;
+    };
+
+    let mut push = |id: &str, severity: Severity, line: usize, text: &str, description: String| {
+        let span = Span::whole_line(line + 1, text);
+        issues.push(Issue {
+            id: id.to_string(),
+            severity,
+            file: file_name.to_string(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description,
+            context: Some(text.trim().chars().take(60).collect()),
+            confidence: None,
+        });
+    };
+
+    for stage in stages.iter().filter(|s| !s.from_stage && is_java_image(s.image)) {
+        if RE_JAVA8_TAG.is_match(split_image(stage.image).1) {
+            push("DOCKER_JDK8_IMAGE", Severity::P1, stage.line, stage.text, i18n::format(
+                "docker.DOCKER_JDK8_IMAGE",
+                "{image} 为 JDK 8 镜像：容器内存/CPU 感知不完整 (8u191 前完全不支持)，缺少 G1/ZGC 的后续改进；升级到 JDK 17/21，如 {suggested}",
+                &[("image", &stage.image), ("suggested", &SUGGESTED_RUNTIME)],
+            ));
+        }
+    }
+
+    let single_stage = stages.len() == 1;
+    if let (true, Some((line, text))) = (single_stage, build_line) {
+        push("DOCKER_NO_MULTI_STAGE", Severity::P1, line, text, i18n::format(
+            "docker.DOCKER_NO_MULTI_STAGE",
+            "镜像内执行 Maven/Gradle 构建但没有分阶段：构建工具、依赖缓存与源码都进入最终镜像；改为多阶段构建，如 FROM maven:3.9-eclipse-temurin-21 AS build ... FROM {suggested} 只复制 jar",
+            &[("suggested", &"eclipse-temurin:21-jre-alpine")],
+        ));
+    }
+
+    if !runtime.from_stage && is_java_image(runtime.image) {
+        let jlink = code.contains("jlink");
+        if is_full_jdk(runtime.image) && !(single_stage && build_line.is_some()) && !jlink {
+            push("DOCKER_JDK_RUNTIME", Severity::P1, runtime.line, runtime.text, i18n::format(
+                "docker.DOCKER_JDK_RUNTIME",
+                "运行阶段使用完整 JDK 镜像 {image}：编译器、调试工具等多出数百 MB 且扩大攻击面；运行阶段改用 JRE，如 {suggested}",
+                &[("image", &runtime.image), ("suggested", &SUGGESTED_RUNTIME)],
+            ));
+        } else if !is_full_jdk(runtime.image) && !is_slim(runtime.image) && !jlink {
+            push("DOCKER_FAT_IMAGE", Severity::P2, runtime.line, runtime.text, i18n::format(
+                "docker.DOCKER_FAT_IMAGE",
+                "运行阶段镜像 {image} 基于完整发行版且未用 jlink 裁剪：拉取与启动更慢；改用 {suggested}，或 jlink --add-modules 生成最小运行时后放入 debian:12-slim",
+                &[("image", &runtime.image), ("suggested", &SUGGESTED_RUNTIME)],
+            ));
+        }
+    }
+
+    let nonroot_base = runtime.image.contains("nonroot");
+    let root_user = runtime.user.is_none_or(|user| {
+        let user = user.split(':').next().unwrap_or(user);
+        user == "root" || user == "0"
+    });
+    if root_user && !nonroot_base && !runtime.image.eq_ignore_ascii_case("scratch") {
+        push("DOCKER_NO_USER", Severity::P1, runtime.line, runtime.text, i18n::text(
+            "docker.DOCKER_NO_USER",
+            "运行阶段未通过 USER 切换为非 root 用户：容器逃逸或 RCE 时直接获得 root；添加 RUN adduser -D app + USER app (或 USER 10001)，distroless 可用 :nonroot 标签",
+        ).to_string());
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,4 +416,48 @@ RUN apk add gcc
 
         assert!(issues.iter().any(|i| i.id == "DOCKER_MANY_LAYERS"));
     }
+
+    fn ids(code: &str) -> Vec<String> {
+        let analyzer = DockerfileAnalyzer::new().unwrap();
+        analyzer.analyze(code, &PathBuf::from("Dockerfile")).unwrap().into_iter().map(|i| i.id).collect()
+    }
+
+    #[test]
+    fn test_dockerfile_single_stage_build() {
+        let ids = ids(r#"
+FROM maven:3.8-openjdk-8
+COPY . /src
+RUN mvn -q package -DskipTests
+CMD ["java", "-jar", "/src/target/app.jar"]
+        "#);
+        assert!(ids.contains(&"DOCKER_NO_MULTI_STAGE".to_string()));
+        assert!(ids.contains(&"DOCKER_JDK8_IMAGE".to_string()));
+        assert!(ids.contains(&"DOCKER_NO_USER".to_string()));
+        // 分阶段后自然换掉 JDK，不重复报告
+        assert!(!ids.contains(&"DOCKER_JDK_RUNTIME".to_string()));
+    }
+
+    #[test]
+    fn test_dockerfile_runtime_stage() {
+        let jdk_runtime = ids(r#"
+FROM maven:3.9-eclipse-temurin-21 AS build
+RUN mvn package
+FROM eclipse-temurin:21-jdk
+COPY --from=build /app.jar /app.jar
+USER 10001
+        "#);
+        assert_eq!(jdk_runtime, ["DOCKER_JDK_RUNTIME"]);
+
+        let analyzer = DockerfileAnalyzer::new().unwrap();
+        let issues = analyzer.analyze("FROM eclipse-temurin:21-jre\nUSER app\n", &PathBuf::from("Dockerfile")).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!((issues[0].id.as_str(), issues[0].severity, issues[0].line), ("DOCKER_FAT_IMAGE", Severity::P2, 1));
+        assert!(issues[0].description.contains("eclipse-temurin:21-jre-alpine"));
+
+        // 精简镜像、jlink、distroless nonroot 均不报告
+        assert!(ids("FROM eclipse-temurin:21-jre-alpine\nUSER app:app\n").is_empty());
+        assert!(ids("FROM eclipse-temurin:21 AS jre\nRUN jlink --add-modules java.base --output /jre\nFROM debian:12-slim\nUSER 10001\n").is_empty());
+        assert!(ids("FROM gcr.io/distroless/java21-debian12:nonroot\n").is_empty());
+        assert_eq!(ids("FROM eclipse-temurin:21-jre-alpine\nUSER root\n"), ["DOCKER_NO_USER"]);
+    }
 }
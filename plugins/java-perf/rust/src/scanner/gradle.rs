@@ -0,0 +1,373 @@
+//! Gradle 构建脚本分析 (Groovy DSL) - v9.6
+//!
+//! 扫描 `build.gradle` / `settings.gradle` 等 `*.gradle` 文件：
+//! - `GRADLE_DYNAMIC_VERSION`: 依赖或插件使用动态版本 (`1.+`、`+`、`latest.release`)，
+//!   缓存过期后每次构建都要向远程仓库查询最新版本，构建也不可复现
+//! - `GRADLE_SLOW_REPO_FIRST`: `repositories` 中自定义仓库排在 `mavenCentral()` 之前或缺少 `mavenCentral()`，
+//!   每个依赖先到慢速仓库查询 (未命中时还要等待超时)
+//! - `GRADLE_TEST_NO_PARALLEL_FORKS`: 配置了测试任务但全文件没有 `maxParallelForks`，测试在单个 JVM 中串行执行
+//! - `GRADLE_MONOLITHIC_ALLPROJECTS`: 超大的 `allprojects` / `subprojects` 块，跨项目配置让每个子项目的配置阶段都变慢，
+//!   也无法使用按需配置 / 配置缓存
+//!
+//! 本构建未内置 Groovy 语法，按词法处理：先把注释 (和字符串) 抹成空格保持偏移不变，再匹配花括号得到块结构。
+
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// 依赖坐标中的动态版本: `group:name:version[:classifier][@ext]`
+static RE_DYNAMIC_COORDINATE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"['"]([\w.\-]+:[\w.\-]+:(?:[^'"\s:@]*\+|latest\.\w+)(?::[^'"]*)?)['"]"#).unwrap()
+});
+
+/// Map 写法 / 插件块中的动态版本: `version: '1.+'`、`version '2.+'`
+static RE_DYNAMIC_VERSION_ARG: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"\bversion\s*[:=(]?\s*['"](?:[^'"\s]*\+|latest\.\w+)['"]"#).unwrap()
+});
+
+/// 测试任务配置块的块头
+static RE_TEST_TASK: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"^(?:tasks\.)?test$|^tasks\.named\(\s*['"]test['"]\s*\)$|^tasks\.withType\(\s*Test\s*\)(?:\.configureEach)?$|^(?:tasks\.)?register\(\s*['"]\w+['"]\s*,\s*Test\s*\)$|^task\s+\w+\s*\(\s*type\s*:\s*Test\s*\)$"#).unwrap()
+});
+
+/// 仓库声明
+static RE_REPOSITORY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(mavenCentral|google|gradlePluginPortal|mavenLocal|jcenter)\s*\(|\b(maven|ivy)\s*[{(]").unwrap()
+});
+
+/// 自定义仓库地址
+static RE_REPOSITORY_URL: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"url\s*(?:=\s*)?\(?\s*(?:uri\s*\(?\s*)?['"]([^'"]+)['"]"#).unwrap()
+});
+
+/// `allprojects` / `subprojects` 块超过该行数 (非空行) 视为整体式配置
+pub const MAX_CROSS_PROJECT_LINES: usize = 40;
+
+/// 允许包含测试任务块的外层块 (其它外层如 `sourceSets { test { } }` 不是任务)
+const TASK_SCOPES: &[&str] = &["allprojects", "subprojects", "tasks"];
+
+/// 一个 `{ ... }` 块
+#[derive(Debug)]
+struct Block {
+    /// 块头 (`{` 前同一语句的文本)
+    head: String,
+    /// `{` / `}` 的字节偏移
+    open: usize,
+    close: usize,
+    /// 外层块下标
+    parent: Option<usize>,
+}
+
+/// 抹掉注释 (`keep_strings = false` 时连字符串内容一起) 为空格，保留换行与字节偏移
+///
+/// 适用于 C 风格注释、单/双/三引号字符串的语言 (Groovy、Scala)。
+pub(super) fn mask(code: &str, keep_strings: bool) -> String {
+    let bytes = code.as_bytes();
+    let mut out = bytes.to_vec();
+    let blank = |out: &mut Vec<u8>, from: usize, to: usize| {
+        for b in &mut out[from..to] {
+            if *b != b'\n' {
+                *b = b' ';
+            }
+        }
+    };
+    let mut i = 0;
+    while i < bytes.len() {
+        let rest = &bytes[i..];
+        if rest.starts_with(b"//") {
+            let end = rest.iter().position(|&b| b == b'\n').map_or(bytes.len(), |p| i + p);
+            blank(&mut out, i, end);
+            i = end;
+        } else if rest.starts_with(b"/*") {
+            let end = code[i + 2..].find("*/").map_or(bytes.len(), |p| i + 2 + p + 2);
+            blank(&mut out, i, end);
+            i = end;
+        } else if rest.starts_with(b"'''") || rest.starts_with(b"\"\"\"") {
+            let quote = &code[i..i + 3];
+            let end = code[i + 3..].find(quote).map_or(bytes.len(), |p| i + 3 + p + 3);
+            if !keep_strings {
+                blank(&mut out, i + 3, end.saturating_sub(3).max(i + 3));
+            }
+            i = end;
+        } else if rest[0] == b'\'' || rest[0] == b'"' {
+            let quote = rest[0];
+            let mut j = i + 1;
+            while j < bytes.len() && bytes[j] != quote && bytes[j] != b'\n' {
+                j += if bytes[j] == b'\\' { 2 } else { 1 };
+            }
+            let end = j.min(bytes.len());
+            if !keep_strings {
+                blank(&mut out, i + 1, end);
+            }
+            i = end + 1;
+        } else {
+            i += 1;
+        }
+    }
+    // 只替换了 ASCII 字节与完整的多字节字符，结果仍是合法 UTF-8
+    String::from_utf8(out).unwrap_or_default()
+}
+
+/// 按花括号切分块 (输入为抹掉注释与字符串后的文本)
+fn blocks(masked: &str, code: &str) -> Vec<Block> {
+    let mut blocks: Vec<Block> = Vec::new();
+    let mut stack: Vec<usize> = Vec::new();
+    for (offset, byte) in masked.bytes().enumerate() {
+        match byte {
+            b'{' => {
+                let start = masked[..offset].rfind([';', '{', '}', '\n']).map_or(0, |p| p + 1);
+                // 块头可能换行书写: `test\n{`
+                let mut head = code[start..offset].trim().to_string();
+                if head.is_empty() {
+                    let before = masked[..start].trim_end_matches(['\n', ' ', '\t', '\r']);
+                    let line_start = before.rfind([';', '{', '}', '\n']).map_or(0, |p| p + 1);
+                    head = code[line_start..before.len()].trim().to_string();
+                }
+                stack.push(blocks.len());
+                blocks.push(Block { head, open: offset, close: masked.len(), parent: None });
+            }
+            b'}' => {
+                if let Some(idx) = stack.pop() {
+                    blocks[idx].close = offset;
+                    blocks[idx].parent = stack.last().copied();
+                }
+            }
+            _ => {}
+        }
+    }
+    // 未闭合的块
+    for window in stack.windows(2) {
+        blocks[window[1]].parent = Some(window[0]);
+    }
+    blocks
+}
+
+fn line_of(code: &str, offset: usize) -> usize {
+    code[..offset.min(code.len())].matches('\n').count() + 1
+}
+
+fn ancestors(blocks: &[Block], idx: usize) -> impl Iterator<Item = &Block> {
+    std::iter::successors(blocks[idx].parent, |&p| blocks[p].parent).map(move |p| &blocks[p])
+}
+
+/// Gradle 构建脚本分析器
+pub struct GradleBuildAnalyzer;
+
+/// 检测到的问题: (规则 ID, 级别, 行号, 上下文)
+type Finding = (&'static str, Severity, usize, String);
+
+impl GradleBuildAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn description(id: &str) -> &'static str {
+        match id {
+            "GRADLE_DYNAMIC_VERSION" => "Gradle 依赖使用动态版本：缓存过期 (默认 24 小时) 后每次构建都要向所有仓库查询最新版本，拖慢依赖解析且构建不可复现；固定版本号，需要范围时配合依赖锁定 (dependency locking)",
+            "GRADLE_SLOW_REPO_FIRST" => "自定义仓库排在 mavenCentral() 之前 (或缺少 mavenCentral())：每个依赖都先到该仓库查询，未命中时还要等待超时；把 mavenCentral() 放在前面，并用 content { includeGroup } 限定自定义仓库的范围",
+            "GRADLE_TEST_NO_PARALLEL_FORKS" => "测试任务未设置 maxParallelForks：所有测试在单个 JVM 中串行执行，多核机器上测试耗时成倍增加；设置 maxParallelForks = Runtime.runtime.availableProcessors().intdiv(2) ?: 1",
+            _ => "超大的 allprojects / subprojects 块：跨项目配置让每个子项目的配置阶段都执行全部逻辑，也无法使用按需配置与配置缓存；拆分为 buildSrc / build-logic 中的约定插件 (convention plugin)，由子项目按需应用",
+        }
+    }
+
+    /// 构建脚本中的全部问题，按行排序
+    pub fn findings(code: &str) -> Vec<Finding> {
+        let masked = mask(code, false);
+        let without_comments = mask(code, true);
+        let blocks = blocks(&masked, code);
+        let mut findings = Vec::new();
+
+        // 1. 动态版本
+        for (idx, line) in without_comments.lines().enumerate() {
+            let context = match RE_DYNAMIC_COORDINATE.captures(line) {
+                Some(caps) => caps[1].to_string(),
+                None if RE_DYNAMIC_VERSION_ARG.is_match(line) => line.trim().to_string(),
+                None => continue,
+            };
+            findings.push(("GRADLE_DYNAMIC_VERSION", Severity::P1, idx + 1, context));
+        }
+
+        // 2. 仓库顺序 (publishing 中的仓库是发布目标，不参与依赖解析)
+        for (idx, block) in blocks.iter().enumerate() {
+            if block.head != "repositories" || ancestors(&blocks, idx).any(|b| b.head == "publishing") {
+                continue;
+            }
+            let body = &masked[block.open + 1..block.close];
+            let mut central = None;
+            let mut custom = None;
+            for caps in RE_REPOSITORY.captures_iter(body) {
+                let offset = block.open + 1 + caps.get(0).map_or(0, |m| m.start());
+                match caps.get(1).map(|m| m.as_str()) {
+                    Some("mavenCentral") => { central.get_or_insert(offset); }
+                    Some(_) => {}
+                    None => { custom.get_or_insert(offset); }
+                }
+            }
+            let Some(custom) = custom else { continue };
+            if central.is_some_and(|central| central < custom) {
+                continue;
+            }
+            let repo_block = blocks.iter().find(|b| b.open > custom && b.parent == Some(idx));
+            let url = repo_block
+                .and_then(|b| RE_REPOSITORY_URL.captures(&code[b.open..b.close]))
+                .map(|caps| caps[1].to_string())
+                .unwrap_or_else(|| code[custom..].lines().next().unwrap_or("").trim().to_string());
+            let context = if central.is_some() { format!("{url} 在 mavenCentral() 之前") } else { format!("{url}，无 mavenCentral()") };
+            findings.push(("GRADLE_SLOW_REPO_FIRST", Severity::P2, line_of(code, custom), context));
+        }
+
+        // 3. 测试任务未并行
+        if !masked.contains("maxParallelForks") {
+            for (idx, block) in blocks.iter().enumerate() {
+                if !RE_TEST_TASK.is_match(&block.head) {
+                    continue;
+                }
+                // `sourceSets { test { } }`、`testing { suites { test { } } }` 等不是测试任务
+                if block.head.ends_with("test") && ancestors(&blocks, idx).any(|b| !is_task_scope(&b.head)) {
+                    continue;
+                }
+                findings.push(("GRADLE_TEST_NO_PARALLEL_FORKS", Severity::P2, line_of(code, block.open), format!("{} {{ }} 无 maxParallelForks", block.head)));
+            }
+        }
+
+        // 4. 整体式跨项目配置 (嵌套的只报最外层)
+        for (idx, block) in blocks.iter().enumerate() {
+            if !["allprojects", "subprojects"].contains(&block.head.as_str())
+                || ancestors(&blocks, idx).any(|b| ["allprojects", "subprojects"].contains(&b.head.as_str()))
+            {
+                continue;
+            }
+            let lines = masked[block.open..block.close].lines().skip(1).filter(|l| !l.trim().is_empty()).count();
+            if lines > MAX_CROSS_PROJECT_LINES {
+                findings.push(("GRADLE_MONOLITHIC_ALLPROJECTS", Severity::P2, line_of(code, block.open), format!("{} {{ }} {lines} 行", block.head)));
+            }
+        }
+
+        findings.sort_by_key(|(_, _, line, _)| *line);
+        findings
+    }
+}
+
+fn is_task_scope(head: &str) -> bool {
+    TASK_SCOPES.contains(&head) || head.starts_with("project(") || head.starts_with("configure(")
+}
+
+impl CodeAnalyzer for GradleBuildAnalyzer {
+    fn supported_extension(&self) -> &str {
+        "gradle"
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let file_name = file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let lines: Vec<&str> = code.lines().collect();
+        Ok(Self::findings(code).into_iter().map(|(id, severity, line, context)| {
+            let span = Span::whole_line(line, lines.get(line.saturating_sub(1)).copied().unwrap_or(""));
+            Issue {
+                id: id.to_string(),
+                severity,
+                file: file_name.clone(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description: i18n::text(&format!("rule.{id}"), Self::description(id)).to_string(),
+                context: Some(context),
+                confidence: None,
+            }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(code: &str) -> Vec<(&'static str, usize)> {
+        GradleBuildAnalyzer::findings(code).into_iter().map(|(id, _, line, _)| (id, line)).collect()
+    }
+
+    #[test]
+    fn test_slow_build_script() {
+        let code = r#"
+plugins {
+    id 'java'
+    id 'com.github.ben-manes.versions' version '0.+'
+}
+
+repositories {
+    maven { url 'https://nexus.acme.com/repository/releases' }
+    mavenCentral()
+}
+
+dependencies {
+    implementation 'com.google.guava:guava:33.+'
+    implementation group: 'org.slf4j', name: 'slf4j-api', version: 'latest.release'
+    // implementation 'commons-io:commons-io:2.+'
+    testImplementation "org.junit.jupiter:junit-jupiter:5.10.2"
+}
+
+test {
+    useJUnitPlatform()
+}
+
+publishing {
+    repositories {
+        maven { url "https://nexus.acme.com/repository/snapshots" }
+    }
+}
+"#;
+        assert_eq!(ids(code), vec![
+            ("GRADLE_DYNAMIC_VERSION", 4),
+            ("GRADLE_SLOW_REPO_FIRST", 8),
+            ("GRADLE_DYNAMIC_VERSION", 13),
+            ("GRADLE_DYNAMIC_VERSION", 14),
+            ("GRADLE_TEST_NO_PARALLEL_FORKS", 19),
+        ]);
+        let findings = GradleBuildAnalyzer::findings(code);
+        assert_eq!(findings[1].3, "https://nexus.acme.com/repository/releases 在 mavenCentral() 之前");
+        assert_eq!(findings[2].3, "com.google.guava:guava:33.+");
+    }
+
+    #[test]
+    fn test_tuned_build_script() {
+        let code = r#"
+repositories {
+    mavenCentral()
+    maven {
+        url = uri("https://repo.spring.io/milestone") // '1.+' in a comment
+        content { includeGroup 'org.springframework' }
+    }
+}
+
+sourceSets {
+    test {
+        java.srcDirs = ['src/test/java']
+    }
+}
+
+tasks.withType(Test).configureEach {
+    maxParallelForks = Runtime.runtime.availableProcessors().intdiv(2) ?: 1
+}
+"#;
+        assert!(ids(code).is_empty());
+
+        let custom_only = "repositories {\n    maven { url 'https://nexus.acme.com/maven' }\n}\n";
+        assert_eq!(GradleBuildAnalyzer::findings(custom_only)[0].3, "https://nexus.acme.com/maven，无 mavenCentral()");
+
+        let source_sets = "sourceSets {\n    test {\n        java.srcDirs = ['src/it/java']\n    }\n}\n";
+        assert!(ids(source_sets).is_empty());
+    }
+
+    #[test]
+    fn test_monolithic_allprojects() {
+        let body: String = (0..45).map(|i| format!("    ext.flag{i} = true\n")).collect();
+        let code = format!("allprojects {{\n{body}    subprojects {{\n        apply plugin: 'java'\n    }}\n}}\n\nsubprojects {{\n    apply plugin: 'java'\n}}\n");
+        assert_eq!(ids(&code), vec![("GRADLE_MONOLITHIC_ALLPROJECTS", 1)]);
+        assert_eq!(GradleBuildAnalyzer::findings(&code)[0].3, "allprojects { } 48 行");
+    }
+}
@@ -0,0 +1,315 @@
+//! 语言注册表 (v9.6)
+//!
+//! 按文件名/扩展名识别文件语言，每种语言登记 Tree-sitter 语法与负责它的分析器。
+//! 扫描时先识别出项目中出现的语言，只实例化这些语言对应的分析器。
+//!
+//! 本构建只内置 Java 语法；Kotlin / Scala / Groovy / XML / YAML 已登记但没有语法，
+//! 其文件交给文本/结构化分析器处理，可通过 [`LanguageRegistry::register`] 补充语法。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Result;
+
+use super::ci::{self, CiPipelineAnalyzer};
+use super::compose::{self, ComposeAnalyzer};
+use super::config::ConfigFileAnalyzer;
+use super::dockerfile::DockerfileAnalyzer;
+use super::gradle::GradleBuildAnalyzer;
+use super::logging::{self, LoggingConfigAnalyzer};
+use super::spark::SparkJobAnalyzer;
+use super::tree_sitter_java::JavaTreeSitterAnalyzer;
+use super::{CodeAnalyzer, Issue};
+
+/// Tree-sitter 语法加载函数
+pub type Grammar = fn() -> tree_sitter::Language;
+
+/// 线程间共享的分析器
+pub type SharedAnalyzer = Box<dyn CodeAnalyzer + Send + Sync>;
+
+/// 分析器类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AnalyzerKind {
+    Java,
+    CiPipeline,
+    Compose,
+    Config,
+    Dockerfile,
+    LoggingConfig,
+    GradleBuild,
+    SparkJob,
+}
+
+impl AnalyzerKind {
+    /// 是否接管该文件 (同一语言按登记顺序取第一个接管的分析器)
+    fn accepts(self, path: &Path) -> bool {
+        let file_name = path.file_name().map(|n| n.to_string_lossy()).unwrap_or_default();
+        match self {
+            AnalyzerKind::CiPipeline => ci::pipeline(path).is_some(),
+            AnalyzerKind::Compose => compose::is_compose_file(&file_name),
+            AnalyzerKind::LoggingConfig => logging::is_logging_config(&file_name),
+            AnalyzerKind::GradleBuild => file_name.ends_with(".gradle"),
+            AnalyzerKind::Java | AnalyzerKind::Config | AnalyzerKind::Dockerfile | AnalyzerKind::SparkJob => true,
+        }
+    }
+
+    /// 实例化分析器 (Java 使用默认规则集；目录扫描的 Java 分析器由调用方按扫描选项构建)
+    pub fn instantiate(self) -> Result<SharedAnalyzer> {
+        Ok(match self {
+            AnalyzerKind::Java => Box::new(JavaTreeSitterAnalyzer::new()?),
+            AnalyzerKind::CiPipeline => Box::new(CiPipelineAnalyzer::new()?),
+            AnalyzerKind::Compose => Box::new(ComposeAnalyzer::new()?),
+            AnalyzerKind::Config => Box::new(ConfigFileAnalyzer::new()?),
+            AnalyzerKind::Dockerfile => Box::new(DockerfileAnalyzer::new()?),
+            AnalyzerKind::LoggingConfig => Box::new(LoggingConfigAnalyzer::new()?),
+            AnalyzerKind::GradleBuild => Box::new(GradleBuildAnalyzer::new()?),
+            AnalyzerKind::SparkJob => Box::new(SparkJobAnalyzer::new()?),
+        })
+    }
+}
+
+/// 一种源文件语言
+#[derive(Clone)]
+pub struct Language {
+    pub name: &'static str,
+    /// 扩展名 (不含点，小写)
+    pub extensions: &'static [&'static str],
+    /// 无扩展名约定的文件名 (Dockerfile、Jenkinsfile 等)，优先于扩展名匹配
+    pub file_name: Option<fn(&str) -> bool>,
+    /// Tree-sitter 语法 (None: 本构建未内置)
+    pub grammar: Option<Grammar>,
+    /// 负责该语言的分析器 (按顺序取第一个接管文件的)
+    pub analyzers: &'static [AnalyzerKind],
+}
+
+#[allow(dead_code)]
+impl Language {
+    /// 创建加载了该语言语法的解析器
+    pub fn parser(&self) -> Option<tree_sitter::Parser> {
+        let grammar = self.grammar?;
+        let mut parser = tree_sitter::Parser::new();
+        parser.set_language(&grammar()).ok()?;
+        Some(parser)
+    }
+}
+
+impl std::fmt::Debug for Language {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Language")
+            .field("name", &self.name)
+            .field("extensions", &self.extensions)
+            .field("grammar", &self.grammar.is_some())
+            .field("analyzers", &self.analyzers)
+            .finish()
+    }
+}
+
+fn is_dockerfile(file_name: &str) -> bool {
+    file_name == "Dockerfile" || file_name.starts_with("Dockerfile.")
+}
+
+fn is_jenkinsfile(file_name: &str) -> bool {
+    file_name == "Jenkinsfile" || file_name.starts_with("Jenkinsfile.")
+}
+
+/// 语言注册表
+#[derive(Debug, Clone)]
+pub struct LanguageRegistry {
+    languages: Vec<Language>,
+}
+
+impl Default for LanguageRegistry {
+    fn default() -> Self {
+        Self::builtin()
+    }
+}
+
+impl LanguageRegistry {
+    /// 内置语言
+    pub fn builtin() -> Self {
+        let languages = vec![
+            Language {
+                name: "java",
+                extensions: &["java"],
+                file_name: None,
+                grammar: Some(tree_sitter_java::language),
+                analyzers: &[AnalyzerKind::Java],
+            },
+            Language {
+                name: "kotlin",
+                extensions: &["kt", "kts"],
+                file_name: None,
+                grammar: None,
+                analyzers: &[],
+            },
+            Language {
+                name: "scala",
+                extensions: &["scala", "sc"],
+                file_name: None,
+                grammar: None,
+                analyzers: &[AnalyzerKind::SparkJob],
+            },
+            Language {
+                name: "groovy",
+                extensions: &["groovy", "gradle"],
+                file_name: Some(is_jenkinsfile),
+                grammar: None,
+                analyzers: &[AnalyzerKind::CiPipeline, AnalyzerKind::GradleBuild],
+            },
+            Language {
+                name: "xml",
+                extensions: &["xml"],
+                file_name: None,
+                grammar: None,
+                analyzers: &[AnalyzerKind::LoggingConfig],
+            },
+            Language {
+                name: "yaml",
+                extensions: &["yml", "yaml"],
+                file_name: None,
+                grammar: None,
+                analyzers: &[AnalyzerKind::CiPipeline, AnalyzerKind::Compose, AnalyzerKind::Config],
+            },
+            Language {
+                name: "properties",
+                extensions: &["properties"],
+                file_name: None,
+                grammar: None,
+                analyzers: &[AnalyzerKind::Config],
+            },
+            Language {
+                name: "dockerfile",
+                extensions: &[],
+                file_name: Some(is_dockerfile),
+                grammar: None,
+                analyzers: &[AnalyzerKind::Dockerfile],
+            },
+        ];
+        Self { languages }
+    }
+
+    /// 登记语言 (同名语言被替换，用于补充语法或分析器)
+    #[allow(dead_code)]
+    pub fn register(&mut self, language: Language) {
+        match self.languages.iter_mut().find(|l| l.name == language.name) {
+            Some(existing) => *existing = language,
+            None => self.languages.push(language),
+        }
+    }
+
+    /// 按名称查找
+    #[allow(dead_code)]
+    pub fn get(&self, name: &str) -> Option<&Language> {
+        self.languages.iter().find(|l| l.name == name)
+    }
+
+    /// 识别文件语言
+    pub fn detect(&self, path: &Path) -> Option<&Language> {
+        let file_name = path.file_name()?.to_string_lossy();
+        if let Some(language) = self.languages.iter().find(|l| l.file_name.is_some_and(|m| m(&file_name))) {
+            return Some(language);
+        }
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        self.languages.iter().find(|l| l.extensions.contains(&ext.as_str()))
+    }
+
+    /// 负责该文件的分析器
+    pub fn analyzer_for(&self, path: &Path) -> Option<AnalyzerKind> {
+        self.detect(path)?.analyzers.iter().copied().find(|kind| kind.accepts(path))
+    }
+}
+
+/// 按需实例化的分析器集合
+#[derive(Default)]
+pub struct Analyzers {
+    analyzers: HashMap<AnalyzerKind, SharedAnalyzer>,
+}
+
+impl Analyzers {
+    /// 为出现的分析器类型各实例化一次 (初始化失败的跳过)
+    pub fn for_kinds(kinds: impl IntoIterator<Item = AnalyzerKind>) -> Self {
+        let mut analyzers = HashMap::new();
+        for kind in kinds {
+            if analyzers.contains_key(&kind) {
+                continue;
+            }
+            if let Ok(analyzer) = kind.instantiate() {
+                analyzers.insert(kind, analyzer);
+            }
+        }
+        Self { analyzers }
+    }
+
+    /// 替换已实例化的分析器 (如按扫描选项配置的分析器)，项目中没有该类型文件时忽略
+    pub fn replace(&mut self, kind: AnalyzerKind, analyzer: SharedAnalyzer) {
+        if let Some(slot) = self.analyzers.get_mut(&kind) {
+            *slot = analyzer;
+        }
+    }
+
+    /// 用对应分析器分析文件 (未实例化或分析失败时返回空)
+    pub fn analyze(&self, kind: AnalyzerKind, code: &str, path: &Path) -> Vec<Issue> {
+        self.analyzers
+            .get(&kind)
+            .and_then(|analyzer| analyzer.analyze(code, path).ok())
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_languages_and_analyzers() {
+        let registry = LanguageRegistry::builtin();
+        let name = |p: &str| registry.detect(Path::new(p)).map(|l| l.name);
+        assert_eq!(name("src/main/java/A.java"), Some("java"));
+        assert_eq!(name("build.gradle.kts"), Some("kotlin"));
+        assert_eq!(name("Jenkinsfile"), Some("groovy"));
+        assert_eq!(name("Dockerfile.native"), Some("dockerfile"));
+        assert_eq!(name("README.md"), None);
+
+        let kind = |p: &str| registry.analyzer_for(Path::new(p));
+        assert_eq!(kind("A.java"), Some(AnalyzerKind::Java));
+        assert_eq!(kind(".github/workflows/build.yml"), Some(AnalyzerKind::CiPipeline));
+        assert_eq!(kind("Jenkinsfile.release"), Some(AnalyzerKind::CiPipeline));
+        assert_eq!(kind("docker-compose.yml"), Some(AnalyzerKind::Compose));
+        assert_eq!(kind("application.yml"), Some(AnalyzerKind::Config));
+        assert_eq!(kind("logback-spring.xml"), Some(AnalyzerKind::LoggingConfig));
+        assert_eq!(kind("pom.xml"), None);
+        assert_eq!(kind("Main.kt"), None);
+        assert_eq!(kind("src/main/scala/Job.scala"), Some(AnalyzerKind::SparkJob));
+        assert_eq!(kind("build.gradle"), Some(AnalyzerKind::GradleBuild));
+        assert_eq!(kind("scripts/release.groovy"), None);
+    }
+
+    #[test]
+    fn test_register_replaces_language() {
+        let mut registry = LanguageRegistry::builtin();
+        assert!(registry.get("java").unwrap().parser().is_some());
+        assert!(registry.get("kotlin").unwrap().parser().is_none());
+
+        // 用 Java 语法代替 Kotlin 语法，仅验证替换与解析器创建
+        registry.register(Language {
+            name: "kotlin",
+            extensions: &["kt"],
+            file_name: None,
+            grammar: Some(tree_sitter_java::language),
+            analyzers: &[AnalyzerKind::Java],
+        });
+        let kotlin = registry.get("kotlin").unwrap();
+        assert!(kotlin.parser().is_some());
+        assert_eq!(registry.analyzer_for(Path::new("Main.kt")), Some(AnalyzerKind::Java));
+        assert!(registry.detect(Path::new("build.gradle.kts")).is_none());
+    }
+
+    #[test]
+    fn test_analyzers_instantiated_per_kind() {
+        let analyzers = Analyzers::for_kinds([AnalyzerKind::Config, AnalyzerKind::Config]);
+        let yaml = "spring:\n  datasource:\n    hikari:\n      maximum-pool-size: 2\n";
+        assert!(!analyzers.analyze(AnalyzerKind::Config, yaml, Path::new("application.yml")).is_empty());
+        // 未实例化的分析器不产出问题
+        assert!(analyzers.analyze(AnalyzerKind::Dockerfile, "FROM openjdk:8", Path::new("Dockerfile")).is_empty());
+    }
+}
@@ -0,0 +1,408 @@
+//! 日志配置分析 (logback / log4j2 XML) - v9.6
+//!
+//! 解析 `logback*.xml` / `log4j2*.xml` 为元素树后检查：
+//! - `LOG_SYNC_FILE_APPENDER`: 文件 Appender 被 root/logger 直接引用，未经 AsyncAppender / `<Async>` / AsyncLogger
+//! - `LOG_ROOT_DEBUG`: root 级别为 DEBUG/TRACE，且作用于生产 (不在 springProfile 中，或 profile 名含 prod)
+//! - `LOG_NO_ROLLING_POLICY`: 不滚动的 FileAppender，或 RollingFileAppender 未设置 maxHistory
+//! - `LOG_CALLER_DATA_PATTERN`: 输出格式含 `%caller` / `%L` / `%M` / `%C` / `%F` 等需要抓取调用栈的转换符
+//!
+//! 元素名与属性名按小写比较 (log4j2 不区分大小写)。
+
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use std::path::Path;
+
+/// 需要抓取调用栈的转换符 (`%c`/`%m`/`%level` 等不受影响，按大小写区分)
+static RE_CALLER_DATA: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"%-?\d*(?:\.-?\d+)?(caller|class|method|line|file|location|C|M|L|F|l)\b").unwrap()
+});
+
+/// 开启后生产环境输出调试日志的级别
+const VERBOSE_LEVELS: &[&str] = &["DEBUG", "TRACE", "ALL"];
+
+/// log4j2 写文件的 Appender
+const LOG4J2_FILE_APPENDERS: &[&str] = &["file", "randomaccessfile", "rollingfile", "rollingrandomaccessfile"];
+
+/// XML 元素 (名称小写)
+#[derive(Debug, Default)]
+pub struct Element {
+    pub name: String,
+    pub attrs: Vec<(String, String)>,
+    pub text: String,
+    pub line: usize,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn attr(&self, name: &str) -> Option<&str> {
+        self.attrs.iter().find(|(k, _)| k == name).map(|(_, v)| v.as_str())
+    }
+
+    /// 属性或同名子元素的值 (`<root level="x">` / `<root><level value="x"/></root>` / `<maxHistory>30</maxHistory>`)
+    pub fn value(&self, name: &str) -> Option<String> {
+        if let Some(value) = self.attr(name) {
+            return Some(value.to_string());
+        }
+        let child = self.children.iter().find(|c| c.name == name)?;
+        Some(child.attr("value").map(str::to_string).unwrap_or_else(|| child.text.trim().to_string()))
+    }
+
+    /// 深度优先遍历，回调参数为 (元素, 祖先链)
+    fn walk<'a>(&'a self, ancestors: &mut Vec<&'a Element>, f: &mut impl FnMut(&'a Element, &[&'a Element])) {
+        f(self, ancestors);
+        ancestors.push(self);
+        for child in &self.children {
+            child.walk(ancestors, f);
+        }
+        ancestors.pop();
+    }
+}
+
+/// 字节偏移处 (跳过空白后) 的行号
+fn line_at(content: &str, pos: usize) -> usize {
+    let pos = pos.min(content.len());
+    let rest = &content[pos..];
+    let start = pos + (rest.len() - rest.trim_start().len());
+    content[..start].matches('\n').count() + 1
+}
+
+fn element(e: &BytesStart, line: usize) -> Element {
+    Element {
+        name: String::from_utf8_lossy(e.local_name().as_ref()).to_ascii_lowercase(),
+        attrs: e.attributes().flatten()
+            .map(|a| (
+                String::from_utf8_lossy(a.key.local_name().as_ref()).to_ascii_lowercase(),
+                a.unescape_value().map(|v| v.to_string()).unwrap_or_default(),
+            ))
+            .collect(),
+        line,
+        ..Element::default()
+    }
+}
+
+/// 解析为元素树，XML 不完整时返回已解析的部分
+pub fn parse(content: &str) -> Option<Element> {
+    let mut reader = Reader::from_str(content);
+    let mut buf = Vec::new();
+    let mut stack: Vec<Element> = Vec::new();
+    let mut root = None;
+    loop {
+        let pos = reader.buffer_position();
+        let event = match reader.read_event_into(&mut buf) {
+            Ok(event) => event,
+            Err(_) => break,
+        };
+        match event {
+            Event::Start(ref e) => stack.push(element(e, line_at(content, pos))),
+            Event::Empty(ref e) => {
+                let el = element(e, line_at(content, pos));
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(el),
+                    None => root = Some(el),
+                }
+            }
+            Event::Text(ref t) => {
+                if let (Some(current), Ok(text)) = (stack.last_mut(), t.unescape()) {
+                    current.text.push_str(&text);
+                }
+            }
+            Event::CData(ref t) => {
+                if let Some(current) = stack.last_mut() {
+                    current.text.push_str(&String::from_utf8_lossy(t.as_ref()));
+                }
+            }
+            Event::End(_) => {
+                let Some(done) = stack.pop() else { continue };
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(done),
+                    None => root = Some(done),
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    // 未闭合的元素挂到父元素上
+    while let Some(done) = stack.pop() {
+        match stack.last_mut() {
+            Some(parent) => parent.children.push(done),
+            None => root = Some(done),
+        }
+    }
+    root
+}
+
+/// 生效于生产环境: 不在 springProfile 中，或 profile 表达式含 prod / 取反其他环境
+fn applies_to_prod(ancestors: &[&Element]) -> bool {
+    ancestors.iter().filter(|a| a.name == "springprofile").all(|profile| {
+        let name = profile.attr("name").unwrap_or("").to_ascii_lowercase();
+        let prod = name.contains("prod") || name.contains("prd");
+        if name.starts_with('!') { !prod } else { prod }
+    })
+}
+
+/// 生效于生产环境的 root 级别: (行号, 级别)
+pub fn prod_root_levels(config: &Element) -> Vec<(usize, String)> {
+    let mut levels = Vec::new();
+    config.walk(&mut Vec::new(), &mut |el, ancestors| {
+        if !matches!(el.name.as_str(), "root" | "asyncroot") || !applies_to_prod(ancestors) {
+            return;
+        }
+        if let Some(level) = el.value("level").filter(|l| !l.contains("${")) {
+            levels.push((el.line, level.to_ascii_uppercase()));
+        }
+    });
+    levels
+}
+
+/// 是否为日志配置文件 (测试配置除外)
+pub fn is_logging_config(file_name: &str) -> bool {
+    let lower = file_name.to_ascii_lowercase();
+    (lower.starts_with("logback") || lower.starts_with("log4j2"))
+        && lower.ends_with(".xml")
+        && !lower.contains("-test")
+}
+
+/// logback / log4j2 配置分析器
+pub struct LoggingConfigAnalyzer;
+
+/// 检测到的问题: (规则 ID, 行号, 上下文)
+type Finding = (&'static str, usize, String);
+
+impl LoggingConfigAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn description(id: &str) -> &'static str {
+        match id {
+            "LOG_SYNC_FILE_APPENDER" => "文件 Appender 同步写入：业务线程在每条日志上等待磁盘 IO，磁盘抖动时请求延迟随之上升，应包一层 AsyncAppender (log4j2: <Async> / AsyncLogger)",
+            "LOG_ROOT_DEBUG" => "生产环境 root 日志级别为 DEBUG/TRACE：日志量放大数十倍，格式化与磁盘 IO 抢占 CPU，应在 prod profile 中使用 INFO 及以上",
+            "LOG_NO_ROLLING_POLICY" => "日志文件不滚动或未限制历史文件数：单文件持续增长直至磁盘写满，应使用 RollingFileAppender 并设置 maxHistory / totalSizeCap",
+            _ => "日志格式含 %caller / %L / %M / %C / %F 等位置信息：每条日志都要构造异常抓取调用栈，吞吐显著下降，生产格式应去掉位置转换符",
+        }
+    }
+
+    /// logback: `<appender class="...FileAppender">` 与 AsyncAppender 的 `<appender-ref>`
+    fn logback_findings(config: &Element, findings: &mut Vec<Finding>) {
+        let mut file_appenders = Vec::new();
+        let mut wrapped = Vec::new();
+        let mut referenced = Vec::new();
+        config.walk(&mut Vec::new(), &mut |el, ancestors| {
+            match el.name.as_str() {
+                "appender" => {
+                    let class = el.attr("class").unwrap_or("");
+                    let simple = class.rsplit('.').next().unwrap_or(class);
+                    let name = el.attr("name").unwrap_or("").to_string();
+                    if simple.contains("Async") {
+                        wrapped.extend(el.children.iter().filter(|c| c.name == "appender-ref").filter_map(|c| c.attr("ref")).map(str::to_string));
+                    } else if simple.ends_with("FileAppender") {
+                        file_appenders.push((name.clone(), el.line));
+                        if simple == "FileAppender" {
+                            findings.push(("LOG_NO_ROLLING_POLICY", el.line, format!("<appender name=\"{name}\"> FileAppender 不滚动")));
+                        } else {
+                            match el.children.iter().find(|c| c.name == "rollingpolicy") {
+                                None => findings.push(("LOG_NO_ROLLING_POLICY", el.line, format!("<appender name=\"{name}\"> 未配置 rollingPolicy"))),
+                                Some(policy) => {
+                                    let fixed_window = policy.attr("class").is_some_and(|c| c.contains("FixedWindow"));
+                                    if !fixed_window && policy.value("maxhistory").is_none() {
+                                        findings.push(("LOG_NO_ROLLING_POLICY", policy.line, format!("<appender name=\"{name}\"> rollingPolicy 未设置 maxHistory")));
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                "appender-ref" if ancestors.last().is_some_and(|p| matches!(p.name.as_str(), "root" | "logger")) => {
+                    referenced.extend(el.attr("ref").map(str::to_string));
+                }
+                _ => {}
+            }
+        });
+        for (name, line) in file_appenders {
+            if referenced.contains(&name) && !wrapped.contains(&name) {
+                findings.push(("LOG_SYNC_FILE_APPENDER", line, format!("<appender name=\"{name}\"> 被 root/logger 直接引用，未经 AsyncAppender")));
+            }
+        }
+    }
+
+    fn log4j2_findings(config: &Element, findings: &mut Vec<Finding>) {
+        let mut file_appenders = Vec::new();
+        let mut wrapped = Vec::new();
+        let mut referenced = Vec::new();
+        config.walk(&mut Vec::new(), &mut |el, ancestors| {
+            let name = el.attr("name").unwrap_or("").to_string();
+            if LOG4J2_FILE_APPENDERS.contains(&el.name.as_str()) && ancestors.last().is_some_and(|p| p.name == "appenders") {
+                file_appenders.push((name.clone(), el.line));
+                if !el.name.starts_with("rolling") {
+                    findings.push(("LOG_NO_ROLLING_POLICY", el.line, format!("<{}> \"{name}\" 不滚动", el.name)));
+                } else if !el.children.iter().any(|c| c.name == "policies" || c.name.ends_with("triggeringpolicy")) {
+                    findings.push(("LOG_NO_ROLLING_POLICY", el.line, format!("<{}> \"{name}\" 未配置触发策略 (Policies)", el.name)));
+                }
+            }
+            if el.name == "appenderref" {
+                match ancestors.last().map(|p| p.name.as_str()) {
+                    Some("async") => wrapped.extend(el.attr("ref").map(str::to_string)),
+                    Some("root" | "logger") => referenced.extend(el.attr("ref").map(str::to_string)),
+                    _ => {}
+                }
+            }
+        });
+        for (name, line) in file_appenders {
+            if referenced.contains(&name) && !wrapped.contains(&name) {
+                findings.push(("LOG_SYNC_FILE_APPENDER", line, format!("\"{name}\" 被 Root/Logger 直接引用，未经 <Async> / AsyncLogger")));
+            }
+        }
+    }
+
+    /// 输出格式: logback `<pattern>`，log4j2 `<PatternLayout pattern=..>` / `<Pattern>`
+    fn pattern_findings(config: &Element, findings: &mut Vec<Finding>) {
+        config.walk(&mut Vec::new(), &mut |el, _| {
+            let pattern = match el.name.as_str() {
+                "pattern" => el.text.trim().to_string(),
+                "patternlayout" => el.attr("pattern").unwrap_or("").to_string(),
+                _ => return,
+            };
+            let mut words: Vec<&str> = RE_CALLER_DATA.captures_iter(&pattern)
+                .filter_map(|c| c.get(1).map(|m| m.as_str()))
+                .collect();
+            words.dedup();
+            if !words.is_empty() {
+                let words: Vec<String> = words.iter().map(|w| format!("%{w}")).collect();
+                findings.push(("LOG_CALLER_DATA_PATTERN", el.line, format!("格式含 {}", words.join(" "))));
+            }
+        });
+    }
+
+    /// 配置中的全部问题，按行排序
+    pub fn findings(content: &str) -> Vec<Finding> {
+        let Some(config) = parse(content) else { return Vec::new() };
+        let mut findings = Vec::new();
+        match config.name.as_str() {
+            "configuration" if config.children.iter().any(|c| c.name == "appenders" || c.name == "loggers") => {
+                Self::log4j2_findings(&config, &mut findings);
+            }
+            "configuration" => Self::logback_findings(&config, &mut findings),
+            _ => return Vec::new(),
+        }
+        for (line, level) in prod_root_levels(&config) {
+            if VERBOSE_LEVELS.contains(&level.as_str()) {
+                findings.push(("LOG_ROOT_DEBUG", line, format!("root level={level}")));
+            }
+        }
+        Self::pattern_findings(&config, &mut findings);
+        findings.sort_by_key(|(_, line, _)| *line);
+        findings
+    }
+}
+
+impl CodeAnalyzer for LoggingConfigAnalyzer {
+    fn supported_extension(&self) -> &str {
+        "xml"
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let file_name = file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let lines: Vec<&str> = code.lines().collect();
+        Ok(Self::findings(code).into_iter().map(|(id, line, context)| {
+            let span = Span::whole_line(line, lines.get(line.saturating_sub(1)).copied().unwrap_or(""));
+            Issue {
+                id: id.to_string(),
+                severity: Severity::P1,
+                file: file_name.clone(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description: i18n::text(&format!("rule.{id}"), Self::description(id)).to_string(),
+                context: Some(context),
+                confidence: None,
+            }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(content: &str) -> Vec<(&'static str, usize)> {
+        LoggingConfigAnalyzer::findings(content).into_iter().map(|(id, line, _)| (id, line)).collect()
+    }
+
+    #[test]
+    fn test_logback_config() {
+        let xml = r#"<configuration>
+  <appender name="FILE" class="ch.qos.logback.core.rolling.RollingFileAppender">
+    <file>app.log</file>
+    <rollingPolicy class="ch.qos.logback.core.rolling.TimeBasedRollingPolicy">
+      <fileNamePattern>app.%d.log</fileNamePattern>
+    </rollingPolicy>
+    <encoder><pattern>%d %-5level [%thread] %logger{36}.%M:%L - %msg%n</pattern></encoder>
+  </appender>
+  <appender name="AUDIT" class="ch.qos.logback.core.FileAppender">
+    <file>audit.log</file>
+  </appender>
+  <appender name="ASYNC_AUDIT" class="ch.qos.logback.classic.AsyncAppender">
+    <appender-ref ref="AUDIT"/>
+  </appender>
+  <springProfile name="!prod">
+    <root level="TRACE"><appender-ref ref="FILE"/></root>
+  </springProfile>
+  <springProfile name="prod">
+    <root level="DEBUG">
+      <appender-ref ref="FILE"/>
+      <appender-ref ref="ASYNC_AUDIT"/>
+    </root>
+  </springProfile>
+</configuration>
+"#;
+        assert_eq!(ids(xml), vec![
+            ("LOG_SYNC_FILE_APPENDER", 2),
+            ("LOG_NO_ROLLING_POLICY", 4),
+            ("LOG_CALLER_DATA_PATTERN", 7),
+            ("LOG_NO_ROLLING_POLICY", 9),
+            ("LOG_ROOT_DEBUG", 19),
+        ]);
+        let findings = LoggingConfigAnalyzer::findings(xml);
+        assert_eq!(findings[2].2, "格式含 %M %L");
+        assert_eq!(findings[4].2, "root level=DEBUG");
+    }
+
+    #[test]
+    fn test_log4j2_config() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Configuration status="WARN">
+  <Appenders>
+    <RollingFile name="App" fileName="app.log" filePattern="app-%d{yyyy-MM-dd}.log">
+      <PatternLayout pattern="%d %p %c{1.} [%t] %m%n"/>
+      <Policies><TimeBasedTriggeringPolicy/></Policies>
+    </RollingFile>
+    <File name="Trace" fileName="trace.log">
+      <PatternLayout pattern="%d %l %m%n"/>
+    </File>
+    <Async name="AsyncApp"><AppenderRef ref="App"/></Async>
+  </Appenders>
+  <Loggers>
+    <Root level="${env:LOG_LEVEL:-info}"><AppenderRef ref="AsyncApp"/></Root>
+    <Logger name="com.acme.trace" level="info"><AppenderRef ref="Trace"/></Logger>
+  </Loggers>
+</Configuration>
+"#;
+        assert_eq!(ids(xml), vec![
+            ("LOG_NO_ROLLING_POLICY", 8),
+            ("LOG_SYNC_FILE_APPENDER", 8),
+            ("LOG_CALLER_DATA_PATTERN", 9),
+        ]);
+        assert!(is_logging_config("logback-spring.xml") && is_logging_config("log4j2.xml"));
+        assert!(!is_logging_config("logback-test.xml") && !is_logging_config("pom.xml"));
+    }
+}
@@ -5,14 +5,26 @@ use anyhow::Result;
 pub mod tree_sitter_java;
 pub mod config;
 pub mod dockerfile;
+pub mod logging;        // v9.6: logback / log4j2 配置
+pub mod compose;        // v9.6: docker-compose 资源配置
+pub mod ci;             // v9.6: CI 流水线构建性能
+pub mod gradle;         // v9.6: Gradle 构建脚本 (Groovy DSL)
+pub mod spark;          // v9.6: Spark 作业 (Scala)
+pub mod language;       // v9.6: 语言注册表 (语法 + 分析器)
 pub mod rule_handlers;  // v9.2: RuleHandler trait 解耦规则处理
+pub mod chain;          // v9.6: 链式调用分析 (ChainInspector)
+pub mod annotation;     // v9.6: 注解参数提取 (AnnotationArgs)
 pub mod queries;        // v9.4: 外部化 Query 加载
+#[cfg(feature = "script-rules")]
+pub mod script_rules;   // v9.6: WASM 脚本规则
 
-/// 严重级别
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+/// 严重级别 (声明顺序即优先级，P0 最高)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Severity {
-    P0, // 严重
-    P1, // 警告
+    P0,   // 严重
+    P1,   // 警告
+    P2,   // v9.6: 建议
+    Info, // v9.6: 提示 (代码风格/可维护性，不直接影响性能)
 }
 
 /// Confidence level for issue detection
@@ -37,9 +49,15 @@ pub struct Issue {
     pub severity: Severity,
     pub file: String,
     pub line: usize,
-    /// 精确列位置，用于 JetBrains MCP get_symbol_at_location 调用
+    /// 精确列位置 (1-based)，用于 JetBrains MCP get_symbol_at_location 调用
     #[serde(default)]
     pub column: usize,
+    /// 结束行 (1-based)，与 line/column 一起构成完整范围，供编辑器高亮
+    #[serde(default)]
+    pub end_line: usize,
+    /// 结束列 (1-based，开区间)
+    #[serde(default)]
+    pub end_column: usize,
     pub description: String,
     pub context: Option<String>,
     /// Confidence level for this issue detection
@@ -52,6 +70,45 @@ pub struct Issue {
     pub confidence: Option<Confidence>,
 }
 
+/// 源码范围 (v9.6)
+///
+/// 所有位置均为 1-based，`end_column` 为开区间 (与 SARIF region 语义一致)。
+/// 列按字节计算，与 Tree-sitter 保持一致。
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub end_column: usize,
+}
+
+impl Span {
+    /// 从 Tree-sitter 节点提取范围
+    pub fn from_node(node: &tree_sitter::Node) -> Self {
+        let start = node.start_position();
+        let end = node.end_position();
+        Self {
+            line: start.row + 1,
+            column: start.column + 1,
+            end_line: end.row + 1,
+            end_column: end.column + 1,
+        }
+    }
+
+    /// 整行范围 (用于行匹配类分析器：配置文件、Dockerfile)
+    ///
+    /// 起始列跳过行首缩进，结束列为行尾。
+    pub fn whole_line(line: usize, text: &str) -> Self {
+        let indent = text.len() - text.trim_start().len();
+        Self {
+            line,
+            column: indent + 1,
+            end_line: line,
+            end_column: text.trim_end().len() + 1,
+        }
+    }
+}
+
 /// 代码分析器 Trait
 #[allow(dead_code)]
 pub trait CodeAnalyzer {
@@ -15,8 +15,11 @@
 // ============================================================================
 
 use tree_sitter::{Query, QueryMatch};
-use super::{Issue, Severity, Confidence};
+use super::{Issue, Severity, Confidence, Span};
+use super::chain::ChainInspector;
+use super::annotation::{format_ms, parse_text, AnnotationArgs};
 use crate::symbol_table::SymbolTable;
+use crate::i18n;
 use std::path::Path;
 use crate::taint::CallGraph;  // v9.4: CallGraph 支持
 
@@ -67,14 +70,17 @@ impl RuleHandler for SimpleMatchHandler {
 
         for capture in m.captures {
             if capture.index == capture_idx {
-                let line = capture.node.start_position().row + 1;
+                let span = Span::from_node(&capture.node);
                 return Some(Issue {
                     id: rule_id.to_string(),
                     severity,
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: None,
                     confidence: None, // Simple match handlers don't use confidence
@@ -105,12 +111,14 @@ impl RuleHandler for StringContentHandler {
 
         for capture in m.captures {
             if capture.index == str_idx {
-                let line = capture.node.start_position().row + 1;
+                let span = Span::from_node(&capture.node);
                 let str_content = capture.node.utf8_text(ctx.code.as_bytes()).unwrap_or("");
-                let context = if str_content.len() > self.max_context_len {
-                    format!("{}...", &str_content[..self.max_context_len])
+                // v9.6: 文本块 (""" ... """) 中的多行 SQL 折叠为单行
+                let flat = str_content.split_whitespace().collect::<Vec<_>>().join(" ");
+                let context = if flat.chars().count() > self.max_context_len {
+                    format!("{}...", flat.chars().take(self.max_context_len).collect::<String>())
                 } else {
-                    str_content.to_string()
+                    flat
                 };
 
                 return Some(Issue {
@@ -119,7 +127,10 @@ impl RuleHandler for StringContentHandler {
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: Some(context),
                     confidence: None, // String content handlers don't use confidence
@@ -151,7 +162,7 @@ impl RuleHandler for ModifierCheckHandler {
         let target_idx = query.capture_index_for_name(self.target_capture)?;
 
         let mut has_modifier = false;
-        let mut line = 0;
+        let mut span = Span::default();
 
         for capture in m.captures {
             if capture.index == mods_idx {
@@ -159,18 +170,21 @@ impl RuleHandler for ModifierCheckHandler {
                 has_modifier = mods_text.contains(self.required_modifier);
             }
             if capture.index == target_idx {
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
             }
         }
 
-        if has_modifier && line > 0 {
+        if has_modifier && span.line > 0 {
             Some(Issue {
                 id: rule_id.to_string(),
                 severity,
                 file: ctx.file_path.file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default(),
-                line,
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
                 description: description.to_string(),
                 context: None,
                 confidence: None, // Modifier check handlers don't use confidence
@@ -202,7 +216,7 @@ impl RuleHandler for NPlusOneHandler {
         let call_idx = query.capture_index_for_name("call")?;
 
         let mut method_name_text = String::new();
-        let mut line = 0;
+        let mut span = Span::default();
         let mut call_node = None;
 
         for capture in m.captures {
@@ -211,7 +225,7 @@ impl RuleHandler for NPlusOneHandler {
                     .unwrap_or("").to_string();
             }
             if capture.index == call_idx {
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
                 call_node = Some(capture.node);
             }
         }
@@ -307,7 +321,10 @@ impl RuleHandler for NPlusOneHandler {
                 file: ctx.file_path.file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default(),
-                line,
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
                 description: description.to_string(),
                 context: Some(context_str),
                 confidence,
@@ -360,14 +377,17 @@ impl RuleHandler for NestedLoopHandler {
         let inner_loop_idx = query.capture_index_for_name("inner_loop")?;
         for capture in m.captures {
             if capture.index == inner_loop_idx {
-                let line = capture.node.start_position().row + 1;
+                let span = Span::from_node(&capture.node);
                 return Some(Issue {
                     id: "NESTED_LOOP".to_string(), // 统一 ID
                     severity,
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: None,
                     confidence: None, // Nested loop detection doesn't use confidence
@@ -378,180 +398,369 @@ impl RuleHandler for NestedLoopHandler {
     }
 }
 
-/// ThreadLocal 泄漏检测处理器
-/// 
-/// v9.9: Enhanced to detect remove() calls in finally blocks for accurate leak detection.
-/// 
-/// Severity gradation:
-/// - No issue: remove() is called in a finally block for the same ThreadLocal variable
-/// - P1: remove() exists but not in a finally block (potential leak on exception)
-/// - P0: No remove() call at all (definite leak)
-pub struct ThreadLocalLeakHandler;
-
-impl ThreadLocalLeakHandler {
-    /// Check if remove() is called in a finally block for the given variable
-    /// 
-    /// Traverses the AST to find try_statement nodes within the method,
-    /// then checks if any finally_clause contains a matching remove() call.
-    /// 
-    /// # Arguments
-    /// * `method_node` - The method_declaration AST node
-    /// * `var_name` - The ThreadLocal variable name to check
-    /// * `code` - The source code bytes
-    /// 
-    /// # Returns
-    /// `true` if remove() is called in a finally block for the variable
-    fn has_remove_in_finally(method_node: tree_sitter::Node, var_name: &str, code: &[u8]) -> bool {
-        // Find all try_statement nodes in the method
-        let mut cursor = method_node.walk();
-        Self::find_remove_in_finally_recursive(&mut cursor, var_name, code)
-    }
-
-    /// Recursively search for remove() calls in finally blocks
-    fn find_remove_in_finally_recursive(
-        cursor: &mut tree_sitter::TreeCursor,
-        var_name: &str,
-        code: &[u8],
-    ) -> bool {
-        loop {
-            let node = cursor.node();
-            
-            // Check if this is a try_statement
-            if node.kind() == "try_statement" {
-                // Look for finally_clause child
-                for i in 0..node.child_count() {
-                    if let Some(child) = node.child(i) {
-                        if child.kind() == "finally_clause" {
-                            // Check if finally block contains var_name.remove()
-                            if Self::finally_contains_remove(child, var_name, code) {
-                                return true;
-                            }
-                        }
-                    }
-                }
-            }
-            
-            // Recurse into children
-            if cursor.goto_first_child() {
-                if Self::find_remove_in_finally_recursive(cursor, var_name, code) {
-                    return true;
-                }
-                cursor.goto_parent();
+/// 成对调用的释放要求
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReleaseScope {
+    /// 按释放位置定级: finally 中 → 安全，其他位置 → P1，没有释放 → P0 (ThreadLocal / MDC)
+    Graded,
+    /// 必须在 finally 中释放，否则按规则级别报告 (Lock / Semaphore / Connection)
+    Finally,
+}
+
+/// 成对调用定义 (v9.6): acquire 之后必须在同一方法内释放
+///
+/// 查询捕获 `@acquire` (acquire 调用) 与 `@target` (配对目标)，`keyed` 时另捕获 `@key`：
+/// - 目标通常是接收者 (`context.set()` → `context.remove()`)，
+///   也可以是接收结果的局部变量 (`Connection c = ds.getConnection()` → `c.close()`)
+/// - `keyed` 时释放调用的第一个参数须与 `@key` 相同 (`MDC.put("traceId", ..)` → `MDC.remove("traceId")`)
+/// - `release_all` 中的方法不比较参数 (`MDC.clear()`)
+/// - `release_helpers` 为以目标为第一个参数的工具方法 (`DataSourceUtils.releaseConnection(c, ds)`)
+///
+/// 新的资源类型只需在 [`PAIRED_CALLS`] 中增加一行并在 `rule_defs` 中给出查询；
+/// 下游可直接 `with_rule(id, .., Box::new(PairedCallHandler { pair }))` 注册。
+#[derive(Debug, Clone, Copy)]
+pub struct PairedCall {
+    /// acquire 方法
+    pub acquire: &'static [&'static str],
+    /// acquire 接收者的类型 (声明类型或静态调用的类名)，为空时不检查
+    pub receiver_types: &'static [&'static str],
+    /// 释放方法
+    pub release: &'static [&'static str],
+    /// 释放全部的方法 (不比较参数)
+    pub release_all: &'static [&'static str],
+    /// 以目标为第一个参数的释放工具方法
+    pub release_helpers: &'static [&'static str],
+    /// 按第一个参数配对
+    pub keyed: bool,
+    /// 同类其他方法中的释放也视为配对 (Interceptor `preHandle` → `afterCompletion`，或互相调用的方法)
+    pub class_scope: bool,
+    pub scope: ReleaseScope,
+}
+
+impl PairedCall {
+    const fn new(acquire: &'static [&'static str], release: &'static [&'static str], scope: ReleaseScope) -> Self {
+        PairedCall {
+            acquire,
+            receiver_types: &[],
+            release,
+            release_all: &[],
+            release_helpers: &[],
+            keyed: false,
+            class_scope: false,
+            scope,
+        }
+    }
+}
+
+/// `ThreadLocal.set()` → `remove()`
+pub const THREADLOCAL_PAIR: PairedCall = PairedCall::new(&["set"], &["remove"], ReleaseScope::Graded);
+
+/// `MDC.put(key, ..)` → `MDC.remove(key)` / `MDC.clear()`
+pub const MDC_PAIR: PairedCall = PairedCall {
+    receiver_types: &["MDC"],
+    release_all: &["clear"],
+    keyed: true,
+    class_scope: true,
+    ..PairedCall::new(&["put"], &["remove"], ReleaseScope::Graded)
+};
+
+/// `lock.lock()` → finally `lock.unlock()`
+pub const LOCK_PAIR: PairedCall = PairedCall::new(&["lock", "lockInterruptibly"], &["unlock"], ReleaseScope::Finally);
+
+/// `semaphore.acquire()` → finally `semaphore.release()`
+pub const SEMAPHORE_PAIR: PairedCall = PairedCall {
+    receiver_types: &["Semaphore"],
+    ..PairedCall::new(&["acquire", "acquireUninterruptibly", "tryAcquire"], &["release"], ReleaseScope::Finally)
+};
+
+/// `Connection c = dataSource.getConnection()` → finally `c.close()` (try-with-resources 不匹配查询)
+pub const CONNECTION_PAIR: PairedCall = PairedCall {
+    receiver_types: &["DataSource", "HikariDataSource", "DruidDataSource", "BasicDataSource", "DriverManager"],
+    release_helpers: &["closeConnection", "releaseConnection", "closeQuietly"],
+    ..PairedCall::new(&["getConnection"], &["close"], ReleaseScope::Finally)
+};
+
+/// `latch.countDown()` 须在 finally 中：任务抛异常时 `await()` 永远等不到计数归零
+pub const LATCH_PAIR: PairedCall = PairedCall {
+    receiver_types: &["CountDownLatch"],
+    ..PairedCall::new(&["countDown"], &["countDown"], ReleaseScope::Finally)
+};
+
+/// 成对调用规则表
+pub const PAIRED_CALLS: &[(&str, PairedCall)] = &[
+    ("THREADLOCAL_LEAK", THREADLOCAL_PAIR),
+    ("MDC_LEAK", MDC_PAIR),
+    ("LOCK_METHOD_CALL", LOCK_PAIR),
+    ("SEMAPHORE_NO_RELEASE", SEMAPHORE_PAIR),
+    ("CONNECTION_NOT_CLOSED", CONNECTION_PAIR),
+    ("LATCH_COUNTDOWN_NOT_IN_FINALLY", LATCH_PAIR),
+];
+
+/// 规则 ID 对应的成对调用定义
+pub fn paired_call(rule_id: &str) -> Option<PairedCall> {
+    PAIRED_CALLS.iter().find(|(id, _)| *id == rule_id).map(|(_, pair)| *pair)
+}
+
+impl PairedCall {
+    /// `node` 是否为与 (target, key) 配对的释放调用
+    fn is_release(&self, node: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        if node.kind() != "method_invocation" {
+            return false;
+        }
+        let Some(method) = node.child_by_field_name("name") else { return false };
+        let method = method.utf8_text(code).unwrap_or("");
+        let first_arg = || node.child_by_field_name("arguments")
+            .and_then(|args| args.named_child(0))
+            .map(|arg| arg.utf8_text(code).unwrap_or(""));
+        if self.release_helpers.contains(&method) {
+            return first_arg() == Some(target);
+        }
+        let Some(obj) = node.child_by_field_name("object") else { return false };
+        if obj.utf8_text(code).unwrap_or("") != target {
+            return false;
+        }
+        if self.release_all.contains(&method) {
+            return true;
+        }
+        if !self.release.contains(&method) {
+            return false;
+        }
+        match key {
+            Some(key) if self.keyed => first_arg() == Some(key),
+            _ => true,
+        }
+    }
+
+    /// 子树内是否存在释放调用
+    fn contains_release(&self, root: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if self.is_release(node, target, key, code) {
+                return true;
             }
-            
-            // Move to next sibling
-            if !cursor.goto_next_sibling() {
-                break;
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        false
+    }
+
+    /// 作用域内是否在 finally 块中释放
+    fn has_release_in_finally(&self, scope: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        let mut stack = vec![scope];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "finally_clause" && self.contains_release(node, target, key, code) {
+                return true;
             }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
         }
         false
     }
 
-    /// Check if a finally_clause contains a remove() call for the given variable
-    fn finally_contains_remove(finally_node: tree_sitter::Node, var_name: &str, code: &[u8]) -> bool {
-        let mut cursor = finally_node.walk();
-        Self::find_remove_call_recursive(&mut cursor, var_name, code)
+    /// 作用域内任意位置是否释放
+    fn has_release_anywhere(&self, scope: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        self.contains_release(scope, target, key, code)
     }
 
-    /// Recursively search for var_name.remove() method invocation
-    fn find_remove_call_recursive(
-        cursor: &mut tree_sitter::TreeCursor,
-        var_name: &str,
-        code: &[u8],
-    ) -> bool {
-        loop {
-            let node = cursor.node();
-            
-            // Check if this is a method_invocation
-            if node.kind() == "method_invocation" {
-                // Check if it's var_name.remove()
-                if let (Some(obj), Some(method)) = (
-                    node.child_by_field_name("object"),
-                    node.child_by_field_name("name"),
-                ) {
-                    let obj_text = obj.utf8_text(code).unwrap_or("");
-                    let method_text = method.utf8_text(code).unwrap_or("");
-                    
-                    if obj_text == var_name && method_text == "remove" {
-                        return true;
-                    }
+    /// 按释放位置定级 (`Finally` 规则由调用方换成规则级别)
+    ///
+    /// - `None`: finally 中释放 (安全)
+    /// - `Some(P1)`: 有释放但不在 finally 中 (异常路径泄漏)
+    /// - `Some(P0)`: 没有释放
+    fn determine_severity(&self, scope: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> Option<Severity> {
+        let has_finally_release = self.has_release_in_finally(scope, target, key, code);
+        let has_any_release = self.has_release_anywhere(scope, target, key, code);
+
+        match (has_finally_release, has_any_release) {
+            (true, _) => None,
+            (false, true) => Some(Severity::P1),
+            (false, false) => Some(Severity::P0),
+        }
+    }
+
+    /// try 语句自身的 finally 块是否释放
+    fn finally_releases(&self, try_node: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        let mut cursor = try_node.walk();
+        let released = try_node.children(&mut cursor)
+            .any(|c| c.kind() == "finally_clause" && self.contains_release(c, target, key, code));
+        released
+    }
+
+    /// acquire 是否受 finally 保护 (`Finally` 规则)
+    ///
+    /// 只认两种结构，方法内其他 try 的 finally 不算:
+    /// - acquire 位于 try 块内 (含外层 try)，该 try 的 finally 释放；try-with-resources 的资源声明中 acquire 自动释放
+    /// - `lock.lock(); try { .. } finally { lock.unlock(); }`: acquire 语句之后同一块中的 try 释放，
+    ///   且两者之间没有 `return`/`throw` (否则提前退出时 finally 不会执行)
+    fn finally_guard(&self, acquire: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> FinallyGuard {
+        let mut statement = None;
+        let mut current = acquire;
+        while let Some(parent) = current.parent() {
+            match parent.kind() {
+                "method_declaration" | "constructor_declaration" | "lambda_expression" | "class_body" => break,
+                "resource" => return FinallyGuard::Guarded,
+                // countDown() 之类 acquire 即 release 的调用本身在 finally 中
+                "finally_clause" if self.is_release(acquire, target, key, code) => return FinallyGuard::Guarded,
+                "try_statement" | "try_with_resources_statement"
+                    if parent.child_by_field_name("body").is_some_and(|b| b.id() == current.id())
+                        && self.finally_releases(parent, target, key, code) => return FinallyGuard::Guarded,
+                "block" | "constructor_body" | "switch_block_statement_group" if statement.is_none() => {
+                    statement = Some(current);
                 }
+                _ => {}
             }
-            
-            // Recurse into children
-            if cursor.goto_first_child() {
-                if Self::find_remove_call_recursive(cursor, var_name, code) {
-                    return true;
-                }
-                cursor.goto_parent();
+            current = parent;
+        }
+
+        let mut early_exit = false;
+        let mut next = statement.and_then(|s| s.next_named_sibling());
+        while let Some(sibling) = next {
+            if matches!(sibling.kind(), "try_statement" | "try_with_resources_statement")
+                && self.finally_releases(sibling, target, key, code) {
+                return if early_exit { FinallyGuard::EarlyExit } else { FinallyGuard::Guarded };
             }
-            
-            // Move to next sibling
-            if !cursor.goto_next_sibling() {
-                break;
+            early_exit |= Self::may_exit(sibling);
+            next = sibling.next_named_sibling();
+        }
+        FinallyGuard::Unguarded
+    }
+
+    /// 语句内是否有 `return`/`throw` (不含 lambda、匿名类)
+    fn may_exit(statement: tree_sitter::Node) -> bool {
+        let mut stack = vec![statement];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "return_statement" | "throw_statement" => return true,
+                "lambda_expression" | "class_body" => continue,
+                _ => {}
             }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
         }
         false
     }
 
-    /// Check if remove() is called anywhere in the method (not necessarily in finally)
-    fn has_remove_anywhere(method_node: tree_sitter::Node, var_name: &str, code: &[u8]) -> bool {
-        let mut cursor = method_node.walk();
-        Self::find_remove_call_recursive(&mut cursor, var_name, code)
+    /// acquire 接收者的类型是否匹配 (`Semaphore permits` / `DriverManager.getConnection()`)
+    fn receiver_matches(&self, acquire: tree_sitter::Node, code: &str) -> bool {
+        if self.receiver_types.is_empty() {
+            return true;
+        }
+        let Some(receiver) = acquire.child_by_field_name("object").filter(|o| o.kind() == "identifier") else {
+            return false;
+        };
+        let name = receiver.utf8_text(code.as_bytes()).unwrap_or("");
+        self.receiver_types.contains(&name)
+            || CollectionHandler::declared_type(acquire, name, code)
+                .is_some_and(|(type_name, _)| self.receiver_types.contains(&CollectionHandler::base_type(&type_name)))
+    }
+}
+
+/// `Finally` 规则中 acquire 与 finally 释放的位置关系
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FinallyGuard {
+    /// 释放位于保护 acquire 的 finally 中
+    Guarded,
+    /// acquire 与其后的 try/finally 之间有 `return`/`throw`
+    EarlyExit,
+    /// 没有保护 acquire 的 finally
+    Unguarded,
+}
+
+/// 成对调用泄漏检测处理器 (定义见 [`PAIRED_CALLS`])
+pub struct PairedCallHandler {
+    pub pair: PairedCall,
+}
+
+impl PairedCallHandler {
+    /// 同类的其他方法是否释放: 只认生命周期回调配对 (`preHandle` → `afterCompletion`)
+    /// 与互相调用的方法，无关方法中的释放属于另一个请求/任务
+    fn released_by_sibling(&self, method: tree_sitter::Node, target: &str, key: Option<&str>, code: &[u8]) -> bool {
+        let Some(body) = method.parent().filter(|p| p.kind() == "class_body") else { return false };
+        let name_of = |m: tree_sitter::Node| m.child_by_field_name("name").and_then(|n| n.utf8_text(code).ok()).unwrap_or("");
+        let name = name_of(method);
+        let mut cursor = body.walk();
+        let released = body.named_children(&mut cursor)
+            .filter(|m| m.kind() == "method_declaration" && m.id() != method.id())
+            .filter(|m| {
+                let sibling = name_of(*m);
+                LIFECYCLE_RELEASES.contains(&(name, sibling))
+                    || Self::calls_own_method(method, sibling, code)
+                    || Self::calls_own_method(*m, name, code)
+            })
+            .any(|m| self.pair.contains_release(m, target, key, code));
+        released
     }
 
-    /// Determine severity based on remove() placement
-    /// 
-    /// # Returns
-    /// - `None` if remove() is in finally (safe, no issue)
-    /// - `Some(Severity::P1)` if remove() exists but not in finally
-    /// - `Some(Severity::P0)` if no remove() at all
-    fn determine_severity(
-        method_node: tree_sitter::Node,
-        var_name: &str,
-        code: &[u8],
-    ) -> Option<Severity> {
-        let has_finally_remove = Self::has_remove_in_finally(method_node, var_name, code);
-        let has_any_remove = Self::has_remove_anywhere(method_node, var_name, code);
+    /// 方法体内是否调用同类的 `name()` (无接收者或 `this.`)
+    fn calls_own_method(method: tree_sitter::Node, name: &str, code: &[u8]) -> bool {
+        let mut stack = vec![method];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "method_invocation"
+                && node.child_by_field_name("name").and_then(|n| n.utf8_text(code).ok()) == Some(name)
+                && node.child_by_field_name("object").is_none_or(|o| o.kind() == "this") {
+                return true;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        false
+    }
 
-        match (has_finally_remove, has_any_remove) {
-            (true, _) => None,                // Safe: remove in finally
-            (false, true) => Some(Severity::P1), // Remove exists but not in finally
-            (false, false) => Some(Severity::P0), // No remove at all
+    /// 目标被 `return` 返回 (所有权交给调用方)
+    fn returned(method: tree_sitter::Node, target: &str, code: &[u8]) -> bool {
+        let mut stack = vec![method];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "return_statement"
+                && node.named_child(0).is_some_and(|value| value.utf8_text(code).unwrap_or("") == target) {
+                return true;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
         }
+        false
     }
 }
 
-impl RuleHandler for ThreadLocalLeakHandler {
+impl RuleHandler for PairedCallHandler {
     fn handle(
         &self,
         query: &Query,
         m: &QueryMatch,
         rule_id: &str,
-        _severity: Severity, // Ignored - we determine severity dynamically
+        severity: Severity,
         description: &str,
         ctx: &RuleContext,
     ) -> Option<Issue> {
-        let set_call_idx = query.capture_index_for_name("set_call")?;
-        let var_name_idx = query.capture_index_for_name("var_name")?;
+        let acquire_idx = query.capture_index_for_name("acquire")?;
+        let target_idx = query.capture_index_for_name("target")?;
+        let key_idx = query.capture_index_for_name("key");
 
-        let mut var_name = String::new();
-        let mut set_node = None;
+        let code = ctx.code.as_bytes();
+        let mut target = String::new();
+        let mut key = None;
+        let mut acquire = None;
 
         for capture in m.captures {
-            if capture.index == var_name_idx {
-                var_name = capture.node.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+            if capture.index == target_idx {
+                target = capture.node.utf8_text(code).unwrap_or("").to_string();
+            }
+            if Some(capture.index) == key_idx {
+                key = capture.node.utf8_text(code).ok();
             }
-            if capture.index == set_call_idx {
-                set_node = Some(capture.node);
+            if capture.index == acquire_idx {
+                acquire = Some(capture.node);
             }
         }
 
-        if var_name.is_empty() {
+        if target.is_empty() {
             return None;
         }
 
-        let node = set_node?;
+        let node = acquire?;
+        let acquire_name = node.child_by_field_name("name").and_then(|n| n.utf8_text(code).ok())?;
+        if !self.pair.acquire.contains(&acquire_name) || !self.pair.receiver_matches(node, ctx.code) {
+            return None;
+        }
 
         // 向上查找 method_declaration
         let mut current = node.parent();
@@ -566,25 +775,61 @@ impl RuleHandler for ThreadLocalLeakHandler {
         }
 
         let method = method_node?;
-        
-        // Use AST-based detection with severity gradation
-        let determined_severity = Self::determine_severity(method, &var_name, ctx.code.as_bytes())?;
-        
-        let severity_desc = match determined_severity {
-            Severity::P0 => "no remove() call found",
-            Severity::P1 => "remove() not in finally block",
+        let key = key.filter(|_| self.pair.keyed);
+
+        let guard = match self.pair.scope {
+            ReleaseScope::Graded => None,
+            ReleaseScope::Finally => Some(self.pair.finally_guard(node, &target, key, code)),
+        };
+        let graded = match guard {
+            None => self.pair.determine_severity(method, &target, key, code)?,
+            Some(FinallyGuard::Guarded) => return None,
+            Some(_) if self.pair.has_release_anywhere(method, &target, key, code) => Severity::P1,
+            Some(_) => Severity::P0,
+        };
+        if self.pair.class_scope && self.released_by_sibling(method, &target, key, code) {
+            return None;
+        }
+        if Self::returned(method, &target, code) {
+            return None;
+        }
+        let determined_severity = match self.pair.scope {
+            ReleaseScope::Graded => graded,
+            ReleaseScope::Finally => severity,
+        };
+
+        let release = self.pair.release.first().copied().unwrap_or("release");
+        let mut severity_desc = match (guard, graded) {
+            (Some(FinallyGuard::EarlyExit), _) => format!("return/throw before try, {release}() in finally skipped"),
+            (_, Severity::P0) => format!("no {release}() call found"),
+            (Some(_), _) => format!("{release}() not in finally of the try guarding {acquire_name}()"),
+            (None, _) => format!("{release}() not in finally block"),
+        };
+        let per_request = method.child_by_field_name("name")
+            .and_then(|name| name.utf8_text(code).ok())
+            .is_some_and(|name| REQUEST_FILTER_METHODS.contains(&name));
+        let class = method.parent().and_then(|body| body.parent()).filter(|c| c.kind() == "class_declaration");
+        if per_request && class.is_some_and(|c| PerRequestSinkHandler::is_request_filter(c, ctx.code)) {
+            severity_desc.push_str(", Filter/Interceptor: 请求线程复用，后续请求继承旧值");
+        }
+        let target = match key {
+            Some(key) => format!("{target}[{key}]"),
+            None => target,
         };
 
-        let line = node.start_position().row + 1;
+        let span = Span::from_node(&node);
         Some(Issue {
             id: rule_id.to_string(),
             severity: determined_severity,
             file: ctx.file_path.file_name()
                 .map(|n| n.to_string_lossy().to_string())
                 .unwrap_or_default(),
-            line,
-            description: format!("{} (Variable: {}, {})", description, var_name, severity_desc),
-            context: Some(var_name),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: format!("{} (Variable: {}, {})", description, target, severity_desc),
+            context: Some(target),
             confidence: Some(Confidence::High), // AST-based detection is high confidence
         })
     }
@@ -608,7 +853,7 @@ impl RuleHandler for StreamResourceLeakHandler {
 
         let mut type_name = String::new();
         let mut var_name = String::new();
-        let mut line = 0;
+        let mut span = Span::default();
 
         for capture in m.captures {
             if capture.index == type_idx {
@@ -616,7 +861,7 @@ impl RuleHandler for StreamResourceLeakHandler {
             }
             if capture.index == var_idx {
                 var_name = capture.node.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
             }
         }
 
@@ -630,7 +875,10 @@ impl RuleHandler for StreamResourceLeakHandler {
                 file: ctx.file_path.file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default(),
-                line,
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
                 description: format!("{} (Type: {}, Var: {})", description, type_name, var_name),
                 context: Some(var_name),
                 confidence: None, // Stream resource leak detection doesn't use confidence
@@ -661,14 +909,14 @@ impl RuleHandler for EmptyArgsHandler {
         let args_idx = query.capture_index_for_name(self.args_capture)?;
 
         let mut args_node = None;
-        let mut line = 0;
+        let mut span = Span::default();
 
         for capture in m.captures {
             if capture.index == args_idx {
                 args_node = Some(capture.node);
             }
             if capture.index == call_idx {
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
             }
         }
 
@@ -681,7 +929,10 @@ impl RuleHandler for EmptyArgsHandler {
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: None,
                     confidence: None, // Empty args detection doesn't use confidence
@@ -692,6 +943,110 @@ impl RuleHandler for EmptyArgsHandler {
     }
 }
 
+/// `Future` 接口及其实现 (不含 CompletableFuture，由 COMPLETABLE_GET_NO_TIMEOUT 负责)
+const FUTURE_TYPES: &[&str] = &[
+    "Future", "FutureTask", "RunnableFuture", "ScheduledFuture", "RunnableScheduledFuture", "ForkJoinTask",
+    "ListenableFuture", "ListenableFutureTask", "SettableFuture",
+];
+
+/// 返回 Future 的方法 (`executor.submit(task).get()`)
+const FUTURE_PRODUCERS: &[&str] = &["submit", "schedule", "scheduleAtFixedRate", "scheduleWithFixedDelay", "fork"];
+
+/// 返回 CompletableFuture 的方法 (`CompletableFuture.supplyAsync(...).get()`)
+const COMPLETABLE_PRODUCERS: &[&str] = &[
+    "supplyAsync", "runAsync", "completedFuture", "allOf", "anyOf", "toCompletableFuture",
+    "thenApply", "thenApplyAsync", "thenCompose", "thenComposeAsync", "thenCombine", "thenCombineAsync",
+    "thenAccept", "thenAcceptAsync", "thenRun", "thenRunAsync", "whenComplete", "whenCompleteAsync", "exceptionally",
+];
+
+/// `Future.get()` / `CompletableFuture.get()` 无超时参数
+///
+/// 只有接收者类型确定为对应的 Future 时才报告：类型依次取自局部变量/参数/字段声明、符号表中的字段
+/// 与本类方法返回类型、`submit()` / `supplyAsync()` 等已知工厂方法。`Optional.get()`、`Map.get()`、
+/// `Supplier.get()` 等同名调用以及类型未知的接收者不报告。
+pub struct FutureGetHandler {
+    /// true: CompletableFuture；false: 其余 Future
+    pub completable: bool,
+}
+
+impl FutureGetHandler {
+    /// 接收者的类型名 (未去泛型)
+    fn receiver_type(call: tree_sitter::Node, ctx: &RuleContext) -> Option<String> {
+        let object = call.child_by_field_name("object")?;
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+        match object.kind() {
+            "identifier" | "field_access" => {
+                let name = CollectionHandler::receiver_name(call, ctx.code)?;
+                CollectionHandler::declared_type(call, name, ctx.code)
+                    .map(|(type_name, _)| type_name)
+                    .or_else(|| {
+                        let binding = ctx.symbol_table?.fields.get(&(ctx.current_class.to_string(), name.to_string()))?;
+                        Some(binding.type_name.clone())
+                    })
+            }
+            "method_invocation" => {
+                let name = object.child_by_field_name("name").map(text)?;
+                if COMPLETABLE_PRODUCERS.contains(&name.as_str()) {
+                    return Some("CompletableFuture".to_string());
+                }
+                if FUTURE_PRODUCERS.contains(&name.as_str()) {
+                    return Some("Future".to_string());
+                }
+                // 本类方法: 取符号表中的返回类型
+                if object.child_by_field_name("object").is_some_and(|o| o.kind() != "this") {
+                    return None;
+                }
+                ctx.symbol_table?.lookup_methods(ctx.current_class, &name).first()?.return_type.clone()
+            }
+            "object_creation_expression" => object.child_by_field_name("type").map(text),
+            _ => None,
+        }
+    }
+}
+
+impl RuleHandler for FutureGetHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let (call, args) = (capture("call")?, capture("args")?);
+        if args.named_child_count() > 0 {
+            return None;
+        }
+        let type_name = Self::receiver_type(call, ctx)?;
+        let base = CollectionHandler::base_type(&type_name);
+        let matches = if self.completable { base == "CompletableFuture" } else { FUTURE_TYPES.contains(&base) };
+        if !matches {
+            return None;
+        }
+
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(format!("{base}.get()")),
+            confidence: Some(Confidence::High),
+        })
+    }
+}
+
 /// 方法调用带上下文处理器 - 用于 Flux.block() 等
 pub struct MethodCallWithContextHandler {
     pub call_capture: &'static str,
@@ -711,7 +1066,7 @@ impl RuleHandler for MethodCallWithContextHandler {
 
         for capture in m.captures {
             if capture.index == call_idx {
-                let line = capture.node.start_position().row + 1;
+                let span = Span::from_node(&capture.node);
                 let method_text = capture.node.utf8_text(ctx.code.as_bytes())
                     .unwrap_or("").to_string();
                 return Some(Issue {
@@ -720,7 +1075,10 @@ impl RuleHandler for MethodCallWithContextHandler {
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: Some(method_text),
                     confidence: None, // Method call with context doesn't use confidence
@@ -764,7 +1122,7 @@ impl RuleHandler for SubscribeArgCountHandler {
 
                 // 只有当参数数量 < 2 时才报告
                 if arg_count < 2 {
-                    let line = node.start_position().row + 1;
+                    let span = Span::from_node(&node);
                     let method_text = node.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
                     return Some(Issue {
                         id: rule_id.to_string(),
@@ -772,7 +1130,10 @@ impl RuleHandler for SubscribeArgCountHandler {
                         file: ctx.file_path.file_name()
                             .map(|n| n.to_string_lossy().to_string())
                             .unwrap_or_default(),
-                        line,
+                        line: span.line,
+                        column: span.column,
+                        end_line: span.end_line,
+                        end_column: span.end_column,
                         description: format!("{} (参数数量: {})", description, arg_count),
                         context: Some(method_text),
                         confidence: None, // Subscribe arg count doesn't use confidence
@@ -801,14 +1162,14 @@ impl RuleHandler for EmptyCatchHandler {
         let body_idx = query.capture_index_for_name("body")?;
 
         let mut body_node = None;
-        let mut line = 0;
+        let mut span = Span::default();
 
         for capture in m.captures {
             if capture.index == body_idx {
                 body_node = Some(capture.node);
             }
             if capture.index == catch_idx {
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
             }
         }
 
@@ -824,7 +1185,10 @@ impl RuleHandler for EmptyCatchHandler {
                     file: ctx.file_path.file_name()
                         .map(|n| n.to_string_lossy().to_string())
                         .unwrap_or_default(),
-                    line,
+                    line: span.line,
+                    column: span.column,
+                    end_line: span.end_line,
+                    end_column: span.end_column,
                     description: description.to_string(),
                     context: None,
                     confidence: None, // Empty catch detection doesn't use confidence
@@ -835,73 +1199,6 @@ impl RuleHandler for EmptyCatchHandler {
     }
 }
 
-/// Lock 不在 finally 中释放检测处理器
-pub struct LockNoFinallyHandler;
-
-impl RuleHandler for LockNoFinallyHandler {
-    fn handle(
-        &self,
-        query: &Query,
-        m: &QueryMatch,
-        rule_id: &str,
-        severity: Severity,
-        description: &str,
-        ctx: &RuleContext,
-    ) -> Option<Issue> {
-        let lock_idx = query.capture_index_for_name("lock_call")?;
-        let var_idx = query.capture_index_for_name("lock_var")?;
-
-        let mut lock_var = String::new();
-        let mut line = 0;
-        let mut lock_node = None;
-
-        for capture in m.captures {
-            if capture.index == var_idx {
-                lock_var = capture.node.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
-            }
-            if capture.index == lock_idx {
-                line = capture.node.start_position().row + 1;
-                lock_node = Some(capture.node);
-            }
-        }
-
-        if let Some(node) = lock_node {
-            // 向上查找 method_declaration
-            let mut current = node.parent();
-            let mut method_node = None;
-
-            while let Some(n) = current {
-                if n.kind() == "method_declaration" {
-                    method_node = Some(n);
-                    break;
-                }
-                current = n.parent();
-            }
-
-            if let Some(method) = method_node {
-                let method_text = method.utf8_text(ctx.code.as_bytes()).unwrap_or("");
-                let unlock_in_finally = format!("{lock_var}.unlock()");
-                let has_finally = method_text.contains("finally");
-
-                if !has_finally || !method_text.contains(&unlock_in_finally) {
-                    return Some(Issue {
-                        id: rule_id.to_string(),
-                        severity,
-                        file: ctx.file_path.file_name()
-                            .map(|n| n.to_string_lossy().to_string())
-                            .unwrap_or_default(),
-                        line,
-                        description: format!("{} (Lock: {})", description, lock_var),
-                        context: Some(lock_var),
-                        confidence: None, // Lock detection doesn't use confidence
-                    });
-                }
-            }
-        }
-        None
-    }
-}
-
 /// 大数组分配检测处理器
 pub struct LargeArrayHandler {
     pub threshold: i64,
@@ -921,7 +1218,7 @@ impl RuleHandler for LargeArrayHandler {
         let size_idx = query.capture_index_for_name("size")?;
 
         let mut size_value: i64 = 0;
-        let mut line = 0;
+        let mut span = Span::default();
 
         for capture in m.captures {
             if capture.index == size_idx {
@@ -929,7 +1226,7 @@ impl RuleHandler for LargeArrayHandler {
                 size_value = size_text.parse().unwrap_or(0);
             }
             if capture.index == creation_idx {
-                line = capture.node.start_position().row + 1;
+                span = Span::from_node(&capture.node);
             }
         }
 
@@ -940,7 +1237,10 @@ impl RuleHandler for LargeArrayHandler {
                 file: ctx.file_path.file_name()
                     .map(|n| n.to_string_lossy().to_string())
                     .unwrap_or_default(),
-                line,
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
                 description: format!("{} (size: {})", description, size_value),
                 context: None,
                 confidence: None, // Large array detection doesn't use confidence
@@ -952,82 +1252,2963 @@ impl RuleHandler for LargeArrayHandler {
 }
 
 // ============================================================================
-// 处理器工厂
+// v9.6: Filter / Interceptor 每请求开销
 // ============================================================================
 
-/// 根据规则 ID 创建对应的处理器
-pub fn create_handler(rule_id: &str) -> Box<dyn RuleHandler> {
-    match rule_id {
-        // ====== N+1 检测 ======
-        "N_PLUS_ONE" | "N_PLUS_ONE_WHILE" | "N_PLUS_ONE_FOREACH" => {
-            Box::new(NPlusOneHandler)
-        }
-
-        // ====== 嵌套循环检测 ======
-        "NESTED_LOOP" | "NESTED_LOOP_MIXED" => {
-            Box::new(NestedLoopHandler)
-        }
+/// 每请求执行的方法中要查找的外部调用类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RequestSink {
+    /// Repository / DAO / JDBC
+    Database,
+    /// RestTemplate / WebClient / HttpClient / Feign
+    Http,
+}
 
-        // ====== 修饰符检查 ======
-        "SYNC_METHOD" => {
-            Box::new(ModifierCheckHandler {
-                mods_capture: "mods",
-                target_capture: "mods", // SYNC_METHOD query only has @mods
-                required_modifier: "synchronized",
-            })
-        }
-        "VOLATILE_ARRAY" => {
-            Box::new(ModifierCheckHandler {
-                mods_capture: "mods",
-                target_capture: "field",
-                required_modifier: "volatile",
-            })
-        }
-        "STATIC_COLLECTION" | "RANDOM_SHARED" => {
-            Box::new(ModifierCheckHandler {
-                mods_capture: "mods",
-                target_capture: "field",
-                required_modifier: "static",
-            })
-        }
+/// Servlet Filter / Spring Interceptor / WebFilter 的父类型
+const REQUEST_FILTER_TYPES: &[&str] = &[
+    "Filter", "OncePerRequestFilter", "GenericFilterBean", "HttpFilter",
+    "HandlerInterceptor", "AsyncHandlerInterceptor", "HandlerInterceptorAdapter",
+    "WebFilter", "HandlerFilterFunction",
+];
+
+/// Filter / Interceptor 中每请求执行的方法
+/// 请求生命周期回调: (acquire 所在方法, 释放所在方法)，同一请求内先后调用
+const LIFECYCLE_RELEASES: &[(&str, &str)] = &[("preHandle", "afterCompletion"), ("preHandle", "postHandle")];
+
+const REQUEST_FILTER_METHODS: &[&str] = &["doFilter", "doFilterInternal", "preHandle", "postHandle", "afterCompletion", "filter"];
+
+/// 调用图上追踪 sink 的最大深度
+const REQUEST_SINK_DEPTH: usize = 5;
+
+/// Filter / Interceptor 每请求 DB / HTTP 调用检测处理器
+///
+/// 1. 确认所在类继承/实现了 Filter 或 Interceptor 类型
+/// 2. 方法体内直接调用 DAO / HTTP 客户端字段 → 报告
+/// 3. 否则沿调用图查找到达 Repository 层 / HTTP 客户端的调用链 (需 Phase 1 上下文)
+pub struct PerRequestSinkHandler {
+    pub sink: RequestSink,
+}
 
-        // ====== ThreadLocal 泄漏 ======
-        "THREADLOCAL_LEAK" => {
-            Box::new(ThreadLocalLeakHandler)
+impl PerRequestSinkHandler {
+    /// 按类型名判断 sink (项目内类型优先用符号表的层级/注解)
+    fn classify_type(type_name: &str, symbol_table: Option<&SymbolTable>) -> Option<RequestSink> {
+        let simple = type_name.split('<').next().unwrap_or(type_name).rsplit('.').next().unwrap_or(type_name);
+        if let Some(info) = symbol_table.and_then(|st| st.lookup_by_simple_name(simple).into_iter().next()) {
+            if info.is_dao() {
+                return Some(RequestSink::Database);
+            }
+            if info.annotations.iter().any(|a| a == "FeignClient") {
+                return Some(RequestSink::Http);
+            }
         }
-
-        // ====== 流资源泄漏 ======
-        "STREAM_RESOURCE_LEAK" => {
-            Box::new(StreamResourceLeakHandler)
+        const DB_TYPES: &[&str] = &["JdbcTemplate", "NamedParameterJdbcTemplate", "JdbcClient", "EntityManager"];
+        const HTTP_TYPES: &[&str] = &["RestTemplate", "RestClient", "WebClient", "HttpClient", "OkHttpClient",
+            "CloseableHttpClient", "AsyncHttpClient"];
+        if DB_TYPES.contains(&simple) || ["Repository", "Dao", "DAO", "Mapper"].iter().any(|s| simple.ends_with(s)) {
+            Some(RequestSink::Database)
+        } else if HTTP_TYPES.contains(&simple) || simple.ends_with("FeignClient") {
+            Some(RequestSink::Http)
+        } else {
+            None
         }
+    }
 
-        // ====== 无超时阻塞调用 ======
-        "FUTURE_GET_NO_TIMEOUT" | "AWAIT_NO_TIMEOUT" | "COMPLETABLE_JOIN"
-        | "EMITTER_UNBOUNDED" | "COMPLETABLE_GET_NO_TIMEOUT" => {
-            Box::new(EmptyArgsHandler {
-                call_capture: "call",
-                args_capture: "args",
+    /// 类声明是否继承/实现了请求过滤器类型
+    fn is_request_filter(class: tree_sitter::Node, code: &str) -> bool {
+        ["superclass", "interfaces"].iter()
+            .filter_map(|field| class.child_by_field_name(field))
+            .any(|node| {
+                let mut stack = vec![node];
+                while let Some(n) = stack.pop() {
+                    if n.kind() == "type_identifier"
+                        && n.utf8_text(code.as_bytes()).is_ok_and(|t| REQUEST_FILTER_TYPES.contains(&t)) {
+                        return true;
+                    }
+                    let mut cursor = n.walk();
+                    stack.extend(n.children(&mut cursor));
+                }
+                false
             })
-        }
+    }
 
-        // ====== SQL 字符串检测 ======
-        "SELECT_STAR" | "LIKE_LEADING_WILDCARD" => {
-            Box::new(StringContentHandler {
-                string_capture: "str",
-                max_context_len: 50,
+    /// 类体内声明的字段: 名称 → 类型
+    fn class_fields(class: tree_sitter::Node, code: &str) -> Vec<(String, String)> {
+        let Some(body) = class.child_by_field_name("body") else { return Vec::new() };
+        let mut cursor = body.walk();
+        body.children(&mut cursor)
+            .filter(|n| n.kind() == "field_declaration")
+            .filter_map(|field| {
+                let type_name = field.child_by_field_name("type")?.utf8_text(code.as_bytes()).ok()?;
+                let name = field.child_by_field_name("declarator")?.child_by_field_name("name")?
+                    .utf8_text(code.as_bytes()).ok()?;
+                Some((name.to_string(), type_name.to_string()))
             })
+            .collect()
+    }
+
+    /// 方法体内第一个直接命中 sink 的 `field.call()`
+    fn direct_sink_call(&self, body: tree_sitter::Node, fields: &[(String, String)], ctx: &RuleContext) -> Option<String> {
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "method_invocation" {
+                let receiver = node.child_by_field_name("object").and_then(|o| match o.kind() {
+                    "identifier" => Some(o),
+                    "field_access" => o.child_by_field_name("field"),
+                    _ => None,
+                });
+                if let (Some(receiver), Some(name)) = (receiver, node.child_by_field_name("name")) {
+                    let receiver = receiver.utf8_text(ctx.code.as_bytes()).unwrap_or("");
+                    let hit = fields.iter()
+                        .find(|(field, _)| field == receiver)
+                        .and_then(|(_, type_name)| Self::classify_type(type_name, ctx.symbol_table));
+                    if hit == Some(self.sink) {
+                        return Some(format!("{receiver}.{}()", name.utf8_text(ctx.code.as_bytes()).unwrap_or("")));
+                    }
+                }
+            }
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
         }
+        None
+    }
 
-        // ====== 响应式编程规则 (带上下文) ======
-        "FLUX_BLOCK" | "FLUX_COLLECT_LIST" | "PARALLEL_NO_RUN_ON" => {
-            Box::new(MethodCallWithContextHandler {
-                call_capture: "call",
+    /// 沿调用图查找到达 sink 的调用链
+    fn indirect_sink_chain(&self, method: &str, ctx: &RuleContext) -> Option<String> {
+        let cg = ctx.call_graph?;
+        let is_sink = |site: &crate::taint::CallSite| match self.sink {
+            RequestSink::Database if cg.layer_of(&site.callee) == crate::taint::LayerType::Repository => true,
+            _ => {
+                // 未解析到项目类的接收者: 按调用方类的字段类型判断 (如 RestTemplate)
+                let receiver = site.callee.simple_class_name();
+                let type_name = ctx.symbol_table
+                    .and_then(|st| st.fields.get(&(site.caller.simple_class_name().to_string(), receiver.to_string())))
+                    .map(|b| b.type_name.as_str())
+                    .unwrap_or(receiver);
+                Self::classify_type(type_name, ctx.symbol_table) == Some(self.sink)
+            }
+        };
+
+        cg.find_methods(ctx.current_class, method).iter()
+            .find_map(|start| cg.find_path(start, REQUEST_SINK_DEPTH, is_sink).map(|path| (start, path)))
+            .map(|(start, path)| {
+                let mut hops = vec![format!("{}.{}", start.simple_class_name(), start.name)];
+                hops.extend(path.iter().map(|s| format!("{}.{}", s.callee.simple_class_name(), s.callee.name)));
+                hops.join(" → ")
             })
-        }
+    }
+}
 
-        // ====== subscribe 参数检查 ======
-        "SUBSCRIBE_NO_ERROR" => {
-            Box::new(SubscribeArgCountHandler)
+impl RuleHandler for PerRequestSinkHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let (class, method_name, decl) = (capture("class")?, capture("method")?, capture("decl")?);
+        if !Self::is_request_filter(class, ctx.code) {
+            return None;
+        }
+
+        let method = method_name.utf8_text(ctx.code.as_bytes()).ok()?;
+        let body = decl.child_by_field_name("body")?;
+        let fields = Self::class_fields(class, ctx.code);
+        let (context, confidence) = if let Some(call) = self.direct_sink_call(body, &fields, ctx) {
+            (format!("{method}(): {call}"), Confidence::High)
+        } else {
+            (format!("{method}(): [调用链: {}]", self.indirect_sink_chain(method, ctx)?), Confidence::Medium)
+        };
+
+        let span = Span::from_node(&method_name);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: Kafka / RabbitMQ 监听器
+// ============================================================================
+
+/// 监听器规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListenerCheck {
+    /// 监听方法内的长阻塞调用 (sleep / join / block / 无超时 get / HTTP)
+    BlockingCall,
+    /// 慢处理但没有手动 ack
+    AutoAckSlow,
+    /// 把消息追加到无界的字段集合中
+    UnboundedBuffer,
+    /// 监听方法上的 @Transactional
+    Transactional,
+}
+
+/// 无参调用即可能无限阻塞的方法
+const BLOCKING_NO_ARG_METHODS: &[&str] = &["join", "block", "blockFirst", "blockLast", "await"];
+
+/// 无界集合类型 (ArrayBlockingQueue 等有界队列不在此列)
+const UNBOUNDED_COLLECTIONS: &[&str] = &[
+    "List", "ArrayList", "LinkedList", "Collection", "Set", "HashSet", "Queue", "Deque", "ArrayDeque",
+    "ConcurrentLinkedQueue", "ConcurrentLinkedDeque", "CopyOnWriteArrayList", "Map", "HashMap",
+    "ConcurrentHashMap", "LinkedBlockingQueue", "LinkedBlockingDeque", "BlockingQueue",
+];
+
+/// `@KafkaListener` / `@RabbitListener` 方法检测处理器
+///
+/// 消费者处理慢是消息积压 (consumer lag) 的常见根因
+pub struct ListenerHandler {
+    pub check: ListenerCheck,
+}
+
+impl ListenerHandler {
+    /// 方法上的注解名 (含 marker 注解)
+    fn annotations(decl: tree_sitter::Node, code: &str) -> Vec<(String, String)> {
+        let Some(mods) = decl.children(&mut decl.walk()).find(|n| n.kind() == "modifiers") else {
+            return Vec::new();
+        };
+        let mut cursor = mods.walk();
+        mods.children(&mut cursor)
+            .filter(|n| n.kind() == "annotation" || n.kind() == "marker_annotation")
+            .filter_map(|n| {
+                let name = n.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                Some((name.to_string(), n.utf8_text(code.as_bytes()).unwrap_or("").to_string()))
+            })
+            .collect()
+    }
+
+    /// 所在类的字段: 名称 → (类型, 初始化表达式)
+    fn class_fields(decl: tree_sitter::Node, code: &str) -> Vec<(String, String, String)> {
+        let Some(body) = decl.parent().filter(|p| p.kind() == "class_body") else { return Vec::new() };
+        let mut cursor = body.walk();
+        body.children(&mut cursor)
+            .filter(|n| n.kind() == "field_declaration")
+            .filter_map(|field| {
+                let type_name = field.child_by_field_name("type")?.utf8_text(code.as_bytes()).ok()?;
+                let declarator = field.child_by_field_name("declarator")?;
+                let name = declarator.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                let value = declarator.child_by_field_name("value")
+                    .and_then(|v| v.utf8_text(code.as_bytes()).ok())
+                    .unwrap_or("");
+                Some((name.to_string(), type_name.to_string(), value.to_string()))
+            })
+            .collect()
+    }
+
+    /// 方法体内所有调用: (接收者文本, 方法名, 参数个数, 节点)
+    fn invocations<'t>(body: tree_sitter::Node<'t>, code: &str) -> Vec<(String, String, usize, tree_sitter::Node<'t>)> {
+        let mut calls = Vec::new();
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "method_invocation" {
+                let receiver = node.child_by_field_name("object").map(|o| match o.kind() {
+                    // this.buffer → buffer
+                    "field_access" => o.child_by_field_name("field").unwrap_or(o),
+                    _ => o,
+                });
+                let receiver = receiver.and_then(|r| r.utf8_text(code.as_bytes()).ok()).unwrap_or("");
+                let name = node.child_by_field_name("name").and_then(|n| n.utf8_text(code.as_bytes()).ok()).unwrap_or("");
+                let argc = node.child_by_field_name("arguments").map(|a| a.named_child_count()).unwrap_or(0);
+                calls.push((receiver.to_string(), name.to_string(), argc, node));
+            }
+            let mut cursor = node.walk();
+            let children: Vec<_> = node.children(&mut cursor).collect();
+            stack.extend(children.into_iter().rev());
+        }
+        calls
+    }
+
+    /// 第一个阻塞调用: (描述, 节点)
+    fn blocking_call<'t>(
+        calls: &[(String, String, usize, tree_sitter::Node<'t>)],
+        fields: &[(String, String, String)],
+        ctx: &RuleContext,
+    ) -> Option<(String, tree_sitter::Node<'t>)> {
+        calls.iter().find_map(|(receiver, name, argc, node)| {
+            let field_type = fields.iter().find(|(f, _, _)| f == receiver).map(|(_, t, _)| t.as_str());
+            let blocking = (receiver == "Thread" && name == "sleep")
+                || (*argc == 0 && BLOCKING_NO_ARG_METHODS.contains(&name.as_str()))
+                || (*argc == 0 && name == "get" && receiver.to_lowercase().contains("future"))
+                || field_type.and_then(|t| PerRequestSinkHandler::classify_type(t, ctx.symbol_table)) == Some(RequestSink::Http);
+            blocking.then(|| (format!("{receiver}.{name}()"), *node))
+        })
+    }
+
+    /// 慢处理: 阻塞调用或 DB 调用
+    fn slow_call(
+        calls: &[(String, String, usize, tree_sitter::Node)],
+        fields: &[(String, String, String)],
+        ctx: &RuleContext,
+    ) -> Option<String> {
+        Self::blocking_call(calls, fields, ctx).map(|(call, _)| call).or_else(|| {
+            calls.iter().find_map(|(receiver, name, _, _)| {
+                let (_, type_name, _) = fields.iter().find(|(f, _, _)| f == receiver)?;
+                (PerRequestSinkHandler::classify_type(type_name, ctx.symbol_table) == Some(RequestSink::Database))
+                    .then(|| format!("{receiver}.{name}()"))
+            })
+        })
+    }
+
+    /// 追加到无界字段集合且方法内没有 size() 检查: (描述, 节点)
+    fn unbounded_buffer<'t>(
+        calls: &[(String, String, usize, tree_sitter::Node<'t>)],
+        fields: &[(String, String, String)],
+    ) -> Option<(String, tree_sitter::Node<'t>)> {
+        let is_unbounded = |(_, type_name, value): &(String, String, String)| {
+            let base = type_name.split('<').next().unwrap_or(type_name).trim();
+            // new LinkedBlockingQueue<>(1000) 带容量 → 有界
+            let bounded_init = value.contains("BlockingQueue") && !value.contains("<>()") && !value.ends_with("()");
+            UNBOUNDED_COLLECTIONS.contains(&base) && !bounded_init
+        };
+        calls.iter().find_map(|(receiver, name, _, node)| {
+            let field = fields.iter().find(|(f, _, _)| f == receiver)?;
+            let appends = matches!(name.as_str(), "add" | "addAll" | "put" | "putAll" | "offer" | "push" | "addLast");
+            let checked = calls.iter().any(|(r, n, _, _)| r == receiver && n == "size");
+            (appends && is_unbounded(field) && !checked).then(|| (format!("{receiver}.{name}()"), *node))
+        })
+    }
+}
+
+impl RuleHandler for ListenerHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let (decl, method_name) = (capture("decl")?, capture("method")?);
+        let method = method_name.utf8_text(ctx.code.as_bytes()).ok()?;
+        let annotations = Self::annotations(decl, ctx.code);
+        let fields = Self::class_fields(decl, ctx.code);
+        let calls = decl.child_by_field_name("body")
+            .map(|body| Self::invocations(body, ctx.code))
+            .unwrap_or_default();
+
+        let (node, context) = match self.check {
+            ListenerCheck::BlockingCall => {
+                let (call, node) = Self::blocking_call(&calls, &fields, ctx)?;
+                (node, format!("{method}(): {call}"))
+            }
+            ListenerCheck::AutoAckSlow => {
+                let params = decl.child_by_field_name("parameters")
+                    .and_then(|p| p.utf8_text(ctx.code.as_bytes()).ok())
+                    .unwrap_or("");
+                let manual_ack = params.contains("Acknowledgment") || params.contains("Channel")
+                    || annotations.iter().any(|(_, text)| text.contains("ackMode"));
+                if manual_ack {
+                    return None;
+                }
+                (method_name, format!("{method}(): {} (无 Acknowledgment/Channel 参数)", Self::slow_call(&calls, &fields, ctx)?))
+            }
+            ListenerCheck::UnboundedBuffer => {
+                let (call, node) = Self::unbounded_buffer(&calls, &fields)?;
+                (node, format!("{method}(): {call}"))
+            }
+            ListenerCheck::Transactional => {
+                annotations.iter().find(|(name, _)| name == "Transactional")?;
+                (method_name, format!("{method}(): @Transactional"))
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: None,
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 正则 (ReDoS / 热路径编译)
+// ============================================================================
+
+/// 正则规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexCheck {
+    /// 正则字面量含灾难性回溯结构
+    Redos,
+    /// 循环内 / 每请求方法内的 Pattern.compile
+    CompileHotPath,
+}
+
+/// Controller 请求处理注解
+const REQUEST_MAPPING_ANNOTATIONS: &[&str] = &[
+    "RequestMapping", "GetMapping", "PostMapping", "PutMapping", "DeleteMapping", "PatchMapping",
+];
+
+/// 正则检测处理器
+///
+/// - Redos: 取 `Pattern.compile` / `matches` / `replaceAll` 等的正则参数
+///   (字面量或同文件 `static final String` 常量) 交给 [`crate::rules::redos`] 分析
+/// - CompileHotPath: `Pattern.compile` 位于循环或每请求方法 (Controller / Filter) 内
+pub struct RegexHandler {
+    pub check: RegexCheck,
+}
+
+impl RegexHandler {
+    /// 正则参数的字面量文本；标识符按同文件字段常量解析
+    fn regex_literal<'t>(arg: tree_sitter::Node<'t>, code: &str) -> Option<tree_sitter::Node<'t>> {
+        if arg.kind() == "string_literal" {
+            return Some(arg);
+        }
+        let name = arg.utf8_text(code.as_bytes()).ok()?;
+        let mut root = arg;
+        while let Some(parent) = root.parent() {
+            root = parent;
+        }
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            if node.kind() == "field_declaration" {
+                let mut cursor = node.walk();
+                let found = node.children_by_field_name("declarator", &mut cursor).find_map(|d| {
+                    let value = d.child_by_field_name("value").filter(|v| v.kind() == "string_literal")?;
+                    (d.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()? == name).then_some(value)
+                });
+                if found.is_some() {
+                    return found;
+                }
+                continue;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.children(&mut cursor));
+        }
+        None
+    }
+
+    /// `Pattern.compile` 所在的热路径描述
+    fn hot_path(call: tree_sitter::Node, code: &str) -> Option<String> {
+        let mut node = call;
+        while let Some(parent) = node.parent() {
+            match parent.kind() {
+                "for_statement" | "enhanced_for_statement" | "while_statement" | "do_statement" => {
+                    return Some("循环内".to_string());
+                }
+                "method_declaration" => {
+                    let method = parent.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                    if ListenerHandler::annotations(parent, code).iter()
+                        .any(|(name, _)| REQUEST_MAPPING_ANNOTATIONS.contains(&name.as_str())) {
+                        return Some(format!("{method}(): 请求处理方法"));
+                    }
+                    let class = parent.parent().and_then(|body| body.parent())
+                        .filter(|c| c.kind() == "class_declaration")?;
+                    return (REQUEST_FILTER_METHODS.contains(&method) && PerRequestSinkHandler::is_request_filter(class, code))
+                        .then(|| format!("{method}(): Filter/Interceptor 每请求执行"));
+                }
+                // 字段初始化 / 构造器 / 静态块只执行一次
+                "constructor_declaration" | "static_initializer" | "field_declaration" | "class_body" => return None,
+                _ => {}
+            }
+            node = parent;
+        }
+        None
+    }
+}
+
+impl RuleHandler for RegexHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let call = capture("call")?;
+
+        let (context, confidence) = match self.check {
+            RegexCheck::Redos => {
+                let literal = Self::regex_literal(capture("regex")?, ctx.code)?;
+                let pattern = crate::rules::redos::unescape_java(literal.utf8_text(ctx.code.as_bytes()).ok()?)?;
+                let finding = crate::rules::redos::analyze(&pattern)?;
+                let confidence = match finding.kind {
+                    crate::rules::redos::RedosKind::NestedQuantifier => Confidence::High,
+                    crate::rules::redos::RedosKind::OverlappingAlternation => Confidence::Medium,
+                };
+                (format!("{}: {} (正则: {})", finding.kind.label(), finding.fragment, pattern), Some(confidence))
+            }
+            RegexCheck::CompileHotPath => (Self::hot_path(call, ctx.code)?, None),
+        };
+
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence,
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 集合误用
+// ============================================================================
+
+/// 集合规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CollectionCheck {
+    /// 循环内 `list.contains()` (O(N²))
+    ListContainsInLoop,
+    /// `new ArrayList<>()` 后立即装入已知大小的数据
+    NoInitialCapacity,
+    /// LinkedList 按下标访问
+    LinkedListRandomAccess,
+    /// 高并发类中的 Collections.synchronizedMap
+    SynchronizedMap,
+}
+
+/// `contains()` 为线性查找的集合类型
+const LINEAR_LIST_TYPES: &[&str] = &["List", "ArrayList", "LinkedList", "Vector", "CopyOnWriteArrayList"];
+
+/// 单例 Bean (被所有请求线程共享) 的注解
+const SHARED_BEAN_ANNOTATIONS: &[&str] = &["RestController", "Controller", "Service", "Component", "Repository"];
+
+/// 表明类内有多线程并发访问的标识
+const CONCURRENCY_MARKERS: &[&str] = &[
+    "ExecutorService", "Executors.", "CompletableFuture", "@Async", "parallelStream", "new Thread(",
+];
+
+/// 集合误用检测处理器
+pub struct CollectionHandler {
+    pub check: CollectionCheck,
+}
+
+impl CollectionHandler {
+    /// 去掉泛型与包名: `java.util.List<String>` → `List`
+    fn base_type(type_name: &str) -> &str {
+        let base = type_name.split('<').next().unwrap_or(type_name).trim();
+        base.rsplit('.').next().unwrap_or(base)
+    }
+
+    /// 变量声明的 (类型, 初始化表达式): 依次查找所在方法的局部变量/参数与所在类的字段
+    fn declared_type(node: tree_sitter::Node, name: &str, code: &str) -> Option<(String, String)> {
+        let text = |n: tree_sitter::Node| n.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+        let find_in = |scope: tree_sitter::Node, kinds: &[&str]| -> Option<(String, String)> {
+            let mut stack = vec![scope];
+            while let Some(n) = stack.pop() {
+                if kinds.contains(&n.kind()) {
+                    let type_name = n.child_by_field_name("type").map(text).unwrap_or_default();
+                    if n.kind() == "formal_parameter" {
+                        if n.child_by_field_name("name").is_some_and(|id| text(id) == name) {
+                            return Some((type_name, String::new()));
+                        }
+                    } else {
+                        let mut cursor = n.walk();
+                        let found = n.children_by_field_name("declarator", &mut cursor)
+                            .find(|d| d.child_by_field_name("name").is_some_and(|id| text(id) == name))
+                            .map(|d| (type_name.clone(), d.child_by_field_name("value").map(text).unwrap_or_default()));
+                        if found.is_some() {
+                            return found;
+                        }
+                    }
+                    continue;
+                }
+                // 类字段只看当前类体，不进入方法
+                if n.kind() == "method_declaration" && kinds.contains(&"field_declaration") {
+                    continue;
+                }
+                let mut cursor = n.walk();
+                stack.extend(n.children(&mut cursor));
+            }
+            None
+        };
+
+        let mut scope = node;
+        while let Some(parent) = scope.parent() {
+            scope = parent;
+            match scope.kind() {
+                "method_declaration" | "constructor_declaration" => {
+                    if let Some(found) = find_in(scope, &["local_variable_declaration", "formal_parameter"]) {
+                        return Some(found);
+                    }
+                }
+                "class_body" => return find_in(scope, &["field_declaration"]),
+                _ => {}
+            }
+        }
+        None
+    }
+
+    /// 接收者变量名 (`list` / `this.list`)
+    fn receiver_name<'c>(call: tree_sitter::Node, code: &'c str) -> Option<&'c str> {
+        let object = call.child_by_field_name("object")?;
+        let id = match object.kind() {
+            "identifier" => object,
+            "field_access" => object.child_by_field_name("field")?,
+            _ => return None,
+        };
+        id.utf8_text(code.as_bytes()).ok()
+    }
+
+    /// 调用位于循环 (含 Stream 等 lambda 回调) 内时返回循环类型
+    fn enclosing_loop(node: tree_sitter::Node) -> Option<&'static str> {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            match parent.kind() {
+                "for_statement" | "enhanced_for_statement" => return Some("for"),
+                "while_statement" | "do_statement" => return Some("while"),
+                "lambda_expression" => return Some("lambda"),
+                "method_declaration" | "constructor_declaration" | "class_body" => return None,
+                _ => {}
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// `List<T> x = new ArrayList<>();` 之后紧跟 `x.addAll(src)` 或 `for (.. : src) x.add(..)`
+    fn known_size_fill(decl: tree_sitter::Node, var: &str, code: &str) -> Option<String> {
+        let next = decl.next_named_sibling()?;
+        let text = next.utf8_text(code.as_bytes()).ok()?;
+        match next.kind() {
+            "expression_statement" => {
+                let call = next.named_child(0).filter(|c| c.kind() == "method_invocation")?;
+                let method = call.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                let args = call.child_by_field_name("arguments")?;
+                let source = args.named_child(0)?.utf8_text(code.as_bytes()).ok()?;
+                (Self::receiver_name(call, code) == Some(var) && matches!(method, "addAll" | "putAll") && args.named_child_count() == 1)
+                    .then(|| format!("{var}.{method}({source}) → 改为 new ...({source})"))
+            }
+            "enhanced_for_statement" => {
+                let source = next.child_by_field_name("value")?.utf8_text(code.as_bytes()).ok()?;
+                let adds = [format!("{var}.add("), format!("{var}.put(")];
+                (adds.iter().any(|a| text.contains(a.as_str())) && !text.contains("if ") && !text.contains("if("))
+                    .then(|| format!("for (... : {source}) {var}.add(...) → 指定初始容量 {source}.size()"))
+            }
+            _ => None,
+        }
+    }
+
+    /// 类是否会被多线程高并发访问: 单例 Bean 或类内使用了线程池/异步
+    fn is_concurrent_class(node: tree_sitter::Node, code: &str) -> bool {
+        let mut class = node;
+        while let Some(parent) = class.parent() {
+            class = parent;
+            if class.kind() == "class_declaration" {
+                break;
+            }
+        }
+        if class.kind() != "class_declaration" {
+            return false;
+        }
+        let shared_bean = ListenerHandler::annotations(class, code).iter()
+            .any(|(name, _)| SHARED_BEAN_ANNOTATIONS.contains(&name.as_str()));
+        let text = class.utf8_text(code.as_bytes()).unwrap_or("");
+        shared_bean || CONCURRENCY_MARKERS.iter().any(|m| text.contains(m))
+    }
+}
+
+impl RuleHandler for CollectionHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+
+        let (node, context, confidence) = match self.check {
+            CollectionCheck::ListContainsInLoop => {
+                let call = capture("call")?;
+                let var = Self::receiver_name(call, ctx.code)?;
+                let loop_kind = Self::enclosing_loop(call)?;
+                let (type_name, init) = Self::declared_type(call, var, ctx.code)?;
+                let is_list = LINEAR_LIST_TYPES.contains(&Self::base_type(&type_name))
+                    || (type_name == "var" && (init.contains("ArrayList") || init.contains("toList()")));
+                if !is_list {
+                    return None;
+                }
+                (call, format!("{var}.contains() 位于 {loop_kind} 内 ({type_name}) → 改用 HashSet"), Confidence::High)
+            }
+            CollectionCheck::NoInitialCapacity => {
+                let (decl, var) = (capture("decl")?, capture("var")?);
+                if capture("args")?.named_child_count() > 0 {
+                    return None;
+                }
+                let var = var.utf8_text(ctx.code.as_bytes()).ok()?;
+                (decl, Self::known_size_fill(decl, var, ctx.code)?, Confidence::High)
+            }
+            CollectionCheck::LinkedListRandomAccess => {
+                let call = capture("call")?;
+                let var = Self::receiver_name(call, ctx.code)?;
+                let (type_name, init) = Self::declared_type(call, var, ctx.code)?;
+                let base = Self::base_type(&type_name);
+                let linked = base == "LinkedList"
+                    || (matches!(base, "List" | "var") && init.trim_start().starts_with("new LinkedList"));
+                if !linked {
+                    return None;
+                }
+                let method = capture("method")?.utf8_text(ctx.code.as_bytes()).ok()?;
+                match Self::enclosing_loop(call) {
+                    Some(loop_kind) => (call, format!("{var}.{method}(index) 位于 {loop_kind} 内 (LinkedList, O(N²))"), Confidence::High),
+                    None => (call, format!("{var}.{method}(index) (LinkedList, O(N))"), Confidence::Medium),
+                }
+            }
+            CollectionCheck::SynchronizedMap => {
+                let call = capture("call")?;
+                if !Self::is_concurrent_class(call, ctx.code) {
+                    return None;
+                }
+                let method = capture("method")?.utf8_text(ctx.code.as_bytes()).ok()?;
+                (call, format!("Collections.{method}() → ConcurrentHashMap"), Confidence::Medium)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 序列化
+// ============================================================================
+
+/// 序列化规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SerializationCheck {
+    /// 方法/循环内新建 ObjectMapper / Gson
+    SerializerPerCall,
+    /// 循环内序列化整个集合
+    SerializeInLoop,
+    /// 双向 JPA 关联缺少 Jackson 循环引用处理
+    BidirectionalJsonCycle,
+}
+
+/// 会被整体序列化的集合类型
+const COLLECTION_TYPES: &[&str] = &[
+    "Collection", "Iterable", "List", "ArrayList", "LinkedList", "Set", "HashSet", "LinkedHashSet", "TreeSet",
+    "Map", "HashMap", "LinkedHashMap", "TreeMap", "ConcurrentHashMap", "Page",
+];
+
+/// 打断 Jackson 循环引用的注解
+const JSON_CYCLE_ANNOTATIONS: &[&str] = &[
+    "JsonIgnore", "JsonManagedReference", "JsonBackReference", "JsonIgnoreProperties", "JsonIdentityInfo",
+];
+
+/// 序列化检测处理器
+pub struct SerializationHandler {
+    pub check: SerializationCheck,
+}
+
+/// 每次调用都会执行的创建点 (序列化器 / Calendar 等)
+///
+/// 字段初始化、构造器、`@Bean`/`@PostConstruct` 方法与赋值给字段的情况只执行一次，返回 None
+fn per_call_site(creation: tree_sitter::Node, code: &str) -> Option<(String, Confidence)> {
+    if creation.parent().is_some_and(|p| p.kind() == "assignment_expression"
+        && p.child_by_field_name("left").is_some_and(|l| l.kind() == "field_access")) {
+        return None;
+    }
+    let mut node = creation;
+    while let Some(parent) = node.parent() {
+        match parent.kind() {
+            "method_declaration" => {
+                let once = ListenerHandler::annotations(parent, code).iter()
+                    .any(|(name, _)| name == "Bean" || name == "PostConstruct");
+                if once {
+                    return None;
+                }
+                let method = parent.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                return Some(match CollectionHandler::enclosing_loop(creation) {
+                    Some(loop_kind) => (format!("{method}(): 位于 {loop_kind} 内"), Confidence::High),
+                    None => (format!("{method}(): 每次调用新建"), Confidence::Medium),
+                });
+            }
+            "constructor_declaration" | "static_initializer" | "field_declaration" | "class_body" => return None,
+            _ => {}
+        }
+        node = parent;
+    }
+    None
+}
+
+impl RuleHandler for SerializationHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            SerializationCheck::SerializerPerCall => {
+                let (creation, type_node) = (capture("creation")?, capture("type")?);
+                let (site, confidence) = per_call_site(creation, ctx.code)?;
+                (creation, format!("new {}() {site}", CollectionHandler::base_type(&text(type_node))), confidence)
+            }
+            SerializationCheck::SerializeInLoop => {
+                let (call, arg) = (capture("call")?, capture("arg")?);
+                let loop_kind = CollectionHandler::enclosing_loop(call)?;
+                let var = match arg.kind() {
+                    "identifier" => text(arg),
+                    "field_access" => arg.child_by_field_name("field").map(text)?,
+                    _ => return None,
+                };
+                let (type_name, _) = CollectionHandler::declared_type(call, &var, ctx.code)?;
+                let is_collection = type_name.ends_with("[]")
+                    || COLLECTION_TYPES.contains(&CollectionHandler::base_type(&type_name));
+                if !is_collection {
+                    return None;
+                }
+                let method = capture("method").map(text)?;
+                (call, format!("{method}({var}) 位于 {loop_kind} 内 ({type_name})"), Confidence::High)
+            }
+            SerializationCheck::BidirectionalJsonCycle => {
+                let (decl, field, relation) = (capture("decl")?, capture("field")?, capture("relation")?);
+                let field_name = text(field);
+                if ListenerHandler::annotations(decl, ctx.code).iter()
+                    .any(|(name, _)| JSON_CYCLE_ANNOTATIONS.contains(&name.as_str())) {
+                    return None;
+                }
+                // 类级 @JsonIgnoreProperties({"field"}) / @JsonIdentityInfo
+                let class = decl.parent().and_then(|body| body.parent()).filter(|c| c.kind() == "class_declaration")?;
+                let quoted = format!("\"{field_name}\"");
+                let class_handled = ListenerHandler::annotations(class, ctx.code).iter().any(|(name, ann)| {
+                    name == "JsonIdentityInfo" || (name == "JsonIgnoreProperties" && ann.contains(&quoted))
+                });
+                if class_handled {
+                    return None;
+                }
+                (field, format!("{field_name}: {}", text(relation)), Confidence::Medium)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 日期时间
+// ============================================================================
+
+/// 日期时间规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DateTimeCheck {
+    /// 业务逻辑依赖 JVM 默认时区
+    DefaultTimeZone,
+    /// 紧凑循环内 new Date()
+    DateInLoop,
+    /// 每次调用 Calendar.getInstance()
+    CalendarPerCall,
+    /// 循环内 DateTimeFormatter.ofPattern
+    FormatterInLoop,
+}
+
+/// 日期时间检测处理器
+pub struct DateTimeHandler {
+    pub check: DateTimeCheck,
+}
+
+impl DateTimeHandler {
+    /// 所在方法是否为业务逻辑 (排除 main、`@Bean` 方法与 `@Configuration` 类)
+    fn business_method<'c>(node: tree_sitter::Node, code: &'c str) -> Option<&'c str> {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            match parent.kind() {
+                "method_declaration" => {
+                    let method = parent.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok()?;
+                    let bean = ListenerHandler::annotations(parent, code).iter().any(|(name, _)| name == "Bean");
+                    let config = parent.parent().and_then(|body| body.parent())
+                        .is_some_and(|class| ListenerHandler::annotations(class, code).iter()
+                            .any(|(name, _)| name == "Configuration" || name == "SpringBootApplication"));
+                    return (method != "main" && !bean && !config).then_some(method);
+                }
+                "constructor_declaration" | "static_initializer" | "field_declaration" | "class_body" => return None,
+                _ => {}
+            }
+            current = parent;
+        }
+        None
+    }
+}
+
+impl RuleHandler for DateTimeHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            DateTimeCheck::DefaultTimeZone => {
+                let call = capture("call")?;
+                let (obj, method) = (capture("obj").map(text)?, capture("method").map(text)?);
+                let paired = matches!((obj.as_str(), method.as_str()), ("TimeZone", "getDefault") | ("ZoneId", "systemDefault"));
+                if !paired {
+                    return None;
+                }
+                let business = Self::business_method(call, ctx.code)?;
+                (call, format!("{business}(): {obj}.{method}()"), Confidence::Medium)
+            }
+            DateTimeCheck::DateInLoop => {
+                let creation = capture("creation")?;
+                if capture("args")?.named_child_count() > 0 {
+                    return None;
+                }
+                // lambda 回调不一定是紧凑循环，只看 for / while
+                let loop_kind = CollectionHandler::enclosing_loop(creation).filter(|k| *k != "lambda")?;
+                (creation, format!("new Date() 位于 {loop_kind} 内 → 循环外取一次或用 System.currentTimeMillis()"), Confidence::High)
+            }
+            DateTimeCheck::CalendarPerCall => {
+                let call = capture("call")?;
+                let (site, confidence) = per_call_site(call, ctx.code)?;
+                (call, format!("Calendar.getInstance() {site}"), confidence)
+            }
+            DateTimeCheck::FormatterInLoop => {
+                let call = capture("call")?;
+                let loop_kind = CollectionHandler::enclosing_loop(call)?;
+                (call, format!("{} 位于 {loop_kind} 内 → 提取为 static final 常量", text(call)), Confidence::High)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 线程创建
+// ============================================================================
+
+/// 线程创建规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThreadCheck {
+    /// Controller / Service 方法内 new Thread / new Timer
+    RequestScoped,
+    /// 未命名的线程
+    Unnamed,
+    /// 核心线程数为 0 的 ScheduledThreadPoolExecutor
+    ScheduledZeroCore,
+}
+
+/// 线程创建检测处理器
+pub struct ThreadHandler {
+    pub check: ThreadCheck,
+}
+
+impl ThreadHandler {
+    /// 所在类的层级: 优先符号表 (Phase 1)，否则读类注解
+    fn class_layer(node: tree_sitter::Node, ctx: &RuleContext) -> crate::symbol_table::LayerType {
+        use crate::symbol_table::LayerType;
+        let mut class = node;
+        while let Some(parent) = class.parent() {
+            class = parent;
+            if class.kind() == "class_declaration" {
+                break;
+            }
+        }
+        if class.kind() != "class_declaration" {
+            return LayerType::Unknown;
+        }
+        let name = class.child_by_field_name("name")
+            .and_then(|n| n.utf8_text(ctx.code.as_bytes()).ok())
+            .unwrap_or(ctx.current_class);
+        let indexed = ctx.symbol_table
+            .and_then(|st| st.lookup_by_simple_name(name).into_iter().map(|info| info.layer).next());
+        match indexed {
+            Some(layer) if layer != LayerType::Unknown => layer,
+            _ => ListenerHandler::annotations(class, ctx.code).iter()
+                .map(|(ann, _)| LayerType::from_annotation(ann))
+                .find(|layer| *layer != LayerType::Unknown)
+                .unwrap_or(LayerType::Unknown),
+        }
+    }
+
+    /// `new Thread(...)` 是否指定了线程名 (构造参数或随后 `t.setName()`)
+    fn is_named(creation: tree_sitter::Node, args: tree_sitter::Node, code: &str) -> bool {
+        let has_name_arg = args.named_child_count() >= 2
+            || args.named_child(0).is_some_and(|a| a.kind() == "string_literal");
+        if has_name_arg {
+            return true;
+        }
+        let Some(var) = creation.parent()
+            .filter(|p| p.kind() == "variable_declarator")
+            .and_then(|d| d.child_by_field_name("name"))
+            .and_then(|n| n.utf8_text(code.as_bytes()).ok()) else {
+            return false;
+        };
+        let mut scope = creation;
+        while let Some(parent) = scope.parent() {
+            scope = parent;
+            if matches!(scope.kind(), "method_declaration" | "constructor_declaration" | "lambda_expression") {
+                break;
+            }
+        }
+        ChainInspector::new(code).variable_chains(scope, var).iter().any(|chain| chain.has_call("setName"))
+    }
+}
+
+impl RuleHandler for ThreadHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        use crate::symbol_table::LayerType;
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            ThreadCheck::RequestScoped => {
+                let creation = capture("creation")?;
+                let layer = Self::class_layer(creation, ctx);
+                if !matches!(layer, LayerType::Controller | LayerType::Service) {
+                    return None;
+                }
+                let (site, _) = per_call_site(creation, ctx.code)?;
+                let type_name = capture("type").map(text)?;
+                let confidence = if ctx.symbol_table.is_some() { Confidence::High } else { Confidence::Medium };
+                (creation, format!("new {type_name}() {site} ({layer:?})"), confidence)
+            }
+            ThreadCheck::Unnamed => {
+                let (creation, args) = (capture("creation")?, capture("args")?);
+                if Self::is_named(creation, args, ctx.code) {
+                    return None;
+                }
+                (creation, format!("{} → 指定线程名或使用命名 ThreadFactory", text(creation).lines().next().unwrap_or("")), Confidence::Medium)
+            }
+            ThreadCheck::ScheduledZeroCore => {
+                let (call, core) = (capture("call")?, capture("core")?);
+                if text(core) != "0" {
+                    return None;
+                }
+                (call, format!("{} → corePoolSize 至少为 1", text(call)), Confidence::High)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: Optional / Stream
+// ============================================================================
+
+/// Optional / Stream 规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamCheck {
+    /// Optional.get() 前没有 isPresent() 检查
+    OptionalGetUnchecked,
+    /// filter().count() > 0 判断存在性
+    CountForExistence,
+    /// collect(toList()).size()
+    CollectThenSize,
+    /// 同一方法内反复对同一集合调用 stream()
+    RepeatedStream,
+}
+
+/// 返回 Optional 的常见方法
+const OPTIONAL_RETURNING_METHODS: &[&str] = &[
+    "findById", "findOne", "findFirst", "findAny", "max", "min", "ofNullable",
+];
+
+/// 同一集合 stream() 次数达到该值才报告 (两次常见且通常可读性更好)
+const REPEATED_STREAM_THRESHOLD: usize = 3;
+
+/// Optional / Stream 误用检测处理器
+pub struct StreamHandler {
+    pub check: StreamCheck,
+}
+
+impl StreamHandler {
+    /// 条件对 `var` 是否存在的保证: Some(true) 条件为真时必然存在，Some(false) 条件为假时必然存在
+    ///
+    /// 识别 `var.isPresent()` / `var.isEmpty()`、`!`、括号，以及 `&&` (任一侧为真即保证) 与 `||` (任一侧为假即保证)。
+    fn presence(cond: tree_sitter::Node, var: &str, code: &str) -> Option<bool> {
+        let text = |n: tree_sitter::Node| n.utf8_text(code.as_bytes()).unwrap_or("");
+        match cond.kind() {
+            "parenthesized_expression" => Self::presence(cond.named_child(0)?, var, code),
+            "unary_expression" if cond.child_by_field_name("operator").is_some_and(|op| text(op) == "!") => {
+                Self::presence(cond.child_by_field_name("operand")?, var, code).map(|p| !p)
+            }
+            "method_invocation" => {
+                cond.child_by_field_name("object").filter(|o| o.kind() == "identifier" && text(*o) == var)?;
+                if cond.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() > 0) {
+                    return None;
+                }
+                match cond.child_by_field_name("name").map(text)? {
+                    "isPresent" => Some(true),
+                    "isEmpty" => Some(false),
+                    _ => None,
+                }
+            }
+            "binary_expression" => {
+                let op = cond.child_by_field_name("operator").map(text)?;
+                let sides = [cond.child_by_field_name("left")?, cond.child_by_field_name("right")?];
+                let wanted = match op {
+                    "&&" => true,
+                    "||" => false,
+                    _ => return None,
+                };
+                sides.iter().any(|side| Self::presence(*side, var, code) == Some(wanted)).then_some(wanted)
+            }
+            _ => None,
+        }
+    }
+
+    /// 语句执行后必然离开当前方法 (`return` / `throw`，或以其结尾的块)
+    fn exits(statement: tree_sitter::Node) -> bool {
+        match statement.kind() {
+            "return_statement" | "throw_statement" => true,
+            "block" => statement.named_child(statement.named_child_count().saturating_sub(1)).is_some_and(Self::exits),
+            _ => false,
+        }
+    }
+
+    /// `var.get()` 是否被存在性检查支配: 所在 if / 三元表达式 / `&&` `||` 的条件，或之前的
+    /// `if (!var.isPresent()) return/throw;` 提前退出。方法内其他位置的检查不算。
+    fn guarded(call: tree_sitter::Node, var: &str, code: &str) -> bool {
+        let mut child = call;
+        while let Some(parent) = child.parent() {
+            let field = |name: &str| parent.child_by_field_name(name);
+            let is = |n: Option<tree_sitter::Node>| n.is_some_and(|n| n.id() == child.id());
+            let presence = |name: &str| field(name).and_then(|cond| Self::presence(cond, var, code));
+            let guarded = match parent.kind() {
+                "if_statement" | "ternary_expression" => {
+                    (is(field("consequence")) && presence("condition") == Some(true))
+                        || (is(field("alternative")) && presence("condition") == Some(false))
+                }
+                "binary_expression" if is(field("right")) => {
+                    match field("operator").and_then(|op| op.utf8_text(code.as_bytes()).ok()) {
+                        Some("&&") => presence("left") == Some(true),
+                        Some("||") => presence("left") == Some(false),
+                        _ => false,
+                    }
+                }
+                "block" | "constructor_body" => {
+                    let mut cursor = parent.walk();
+                    let earlier: Vec<_> = parent.named_children(&mut cursor)
+                        .take_while(|s| s.id() != child.id())
+                        .collect();
+                    earlier.iter().any(|s| {
+                        s.kind() == "if_statement"
+                            && s.child_by_field_name("alternative").is_none()
+                            && s.child_by_field_name("consequence").is_some_and(Self::exits)
+                            && s.child_by_field_name("condition").and_then(|c| Self::presence(c, var, code)) == Some(false)
+                    })
+                }
+                "method_declaration" | "constructor_declaration" | "class_body" => return false,
+                _ => false,
+            };
+            if guarded {
+                return true;
+            }
+            child = parent;
+        }
+        false
+    }
+}
+
+impl RuleHandler for StreamHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            StreamCheck::OptionalGetUnchecked => {
+                let (call, obj) = (capture("call")?, capture("obj")?);
+                if capture("args")?.named_child_count() > 0 {
+                    return None;
+                }
+                match obj.kind() {
+                    "method_invocation" => {
+                        let producer = obj.child_by_field_name("name").map(text)?;
+                        if !OPTIONAL_RETURNING_METHODS.contains(&producer.as_str()) {
+                            return None;
+                        }
+                        (call, format!("{producer}(...).get() → orElseThrow()/orElse()"), Confidence::High)
+                    }
+                    "identifier" => {
+                        let var = text(obj);
+                        let (type_name, _) = CollectionHandler::declared_type(call, &var, ctx.code)?;
+                        if CollectionHandler::base_type(&type_name) != "Optional" {
+                            return None;
+                        }
+                        if Self::guarded(call, &var, ctx.code) {
+                            return None;
+                        }
+                        (call, format!("{var}.get() 前无 isPresent() 检查"), Confidence::Medium)
+                    }
+                    _ => return None,
+                }
+            }
+            StreamCheck::CountForExistence => {
+                let (expr, n) = (capture("expr")?, capture("n")?);
+                let op = expr.child_by_field_name("operator").map(text)?;
+                let replacement = match (op.as_str(), text(n).as_str()) {
+                    (">", "0") | ("!=", "0") | (">=", "1") => "anyMatch",
+                    ("==", "0") | ("<", "1") => "noneMatch",
+                    _ => return None,
+                };
+                (expr, format!("filter(...).count() {op} {} → {replacement}(...)", text(n)), Confidence::High)
+            }
+            StreamCheck::CollectThenSize => {
+                let (call, inner) = (capture("call")?, capture("inner")?);
+                // toSet()/toMap() 会去重，size() 与 count() 不等价
+                let collector = inner.parent().and_then(|p| p.child_by_field_name("arguments")).map(text).unwrap_or_default();
+                if text(inner) == "collect" && !collector.contains("toList") {
+                    return None;
+                }
+                (call, format!("{}(...).size() → count()", text(inner)), Confidence::High)
+            }
+            StreamCheck::RepeatedStream => {
+                let (decl, body) = (capture("decl")?, capture("body")?);
+                // 收集 `recv.stream()` 的接收者 (按出现顺序)
+                let mut receivers: Vec<(String, tree_sitter::Node)> = Vec::new();
+                let mut stack = vec![body];
+                while let Some(n) = stack.pop() {
+                    if n.kind() == "method_invocation"
+                        && n.child_by_field_name("name").is_some_and(|name| text(name) == "stream")
+                        && n.child_by_field_name("arguments").is_some_and(|a| a.named_child_count() == 0) {
+                        if let Some(obj) = n.child_by_field_name("object").filter(|o| matches!(o.kind(), "identifier" | "field_access")) {
+                            receivers.push((text(obj), n));
+                        }
+                    }
+                    let mut cursor = n.walk();
+                    let children: Vec<_> = n.children(&mut cursor).collect();
+                    stack.extend(children.into_iter().rev());
+                }
+                let (receiver, count, first) = receivers.iter().find_map(|(recv, first)| {
+                    let count = receivers.iter().filter(|(r, _)| r == recv).count();
+                    (count >= REPEATED_STREAM_THRESHOLD).then(|| (recv.clone(), count, *first))
+                })?;
+                let method = decl.child_by_field_name("name").map(text)?;
+                (first, format!("{method}(): {receiver}.stream() × {count} → 合并为一次遍历"), Confidence::Medium)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: Java 17/21 (record / sealed / 模式匹配 switch)
+// ============================================================================
+
+/// Java 17/21 语法规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModernJavaCheck {
+    /// record 组件为可变集合/数组且未做防御性复制
+    RecordMutableComponent,
+    /// 循环/lambda 中的模式匹配 switch 无 default 分支
+    PatternSwitchNoDefault,
+}
+
+/// 可变集合类型 (record 组件)
+const MUTABLE_COLLECTION_TYPES: &[&str] = &[
+    "List", "ArrayList", "LinkedList", "Collection", "Set", "HashSet", "LinkedHashSet", "TreeSet",
+    "Map", "HashMap", "LinkedHashMap", "TreeMap", "Queue", "Deque", "ArrayDeque",
+];
+
+/// Java 17/21 语法规则处理器
+pub struct ModernJavaHandler {
+    pub check: ModernJavaCheck,
+}
+
+impl ModernJavaHandler {
+    /// record 体内是否对组件做了防御性复制 (`items = List.copyOf(items)` / `this.items = ...unmodifiable...`)
+    fn defensively_copied(body: &str, component: &str) -> bool {
+        regex::Regex::new(&format!(r"\b{}\s*=[^;]*(copyOf|unmodifiable|clone\(\))", regex::escape(component)))
+            .is_ok_and(|re| re.is_match(body))
+    }
+
+    /// switch 的所有 case 标签 (switch_rule / switch_block_statement_group 下的 switch_label)
+    fn labels(body: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+        let mut cursor = body.walk();
+        let groups: Vec<_> = body.named_children(&mut cursor).collect();
+        groups.into_iter()
+            .flat_map(|group| {
+                let mut cursor = group.walk();
+                group.named_children(&mut cursor).filter(|c| c.kind() == "switch_label").collect::<Vec<_>>()
+            })
+            .collect()
+    }
+}
+
+impl RuleHandler for ModernJavaHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            ModernJavaCheck::RecordMutableComponent => {
+                let (param, type_node) = (capture("param")?, capture("type")?);
+                let (record, component) = (capture("record").map(text)?, capture("component").map(text)?);
+                let type_name = text(type_node);
+                let mutable = type_node.kind() == "array_type"
+                    || MUTABLE_COLLECTION_TYPES.contains(&CollectionHandler::base_type(&type_name));
+                if !mutable || Self::defensively_copied(&capture("body").map(text)?, &component) {
+                    return None;
+                }
+                let fix = if type_node.kind() == "array_type" {
+                    format!("{component}.clone() 或改用 List")
+                } else {
+                    let base = CollectionHandler::base_type(&type_name);
+                    let factory = if base.ends_with("Map") { "Map" } else if base.ends_with("Set") { "Set" } else { "List" };
+                    format!("{component} = {factory}.copyOf({component})")
+                };
+                (param, format!("record {record}({type_name} {component}) → 紧凑构造器中 {fix}"), Confidence::High)
+            }
+            ModernJavaCheck::PatternSwitchNoDefault => {
+                let (switch, body) = (capture("switch")?, capture("body")?);
+                let labels = Self::labels(body);
+                let patterns = labels.iter()
+                    .filter(|l| (0..l.named_child_count()).filter_map(|i| l.named_child(i)).any(|c| c.kind() == "pattern"))
+                    .count();
+                let has_default = labels.iter()
+                    .any(|l| text(*l).split(|c: char| !c.is_alphanumeric()).any(|w| w == "default"));
+                if patterns == 0 || has_default {
+                    return None;
+                }
+                let loop_kind = CollectionHandler::enclosing_loop(switch)?;
+                let condition = switch.child_by_field_name("condition").map(text).unwrap_or_default();
+                (switch, format!("switch {condition} 位于 {loop_kind} 内: {patterns} 个类型模式, 无 default"), Confidence::Medium)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 测试专用规则 (不稳定测试模式)
+// ============================================================================
+
+/// 测试专用规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestCodeCheck {
+    /// Thread.sleep / TimeUnit.X.sleep 等待异步结果
+    ThreadSleep,
+    /// 写死的端口 (localhost:8080 / server.port=8080 / DEFINED_PORT)
+    FixedPort,
+    /// 测试类中的可变静态字段
+    SharedStaticState,
+    /// 手动创建且未停止的 Testcontainers 容器
+    ContainerLeak,
+}
+
+/// 测试专用规则处理器，只在测试源码 (`rules::test_code::is_test_source`) 上触发
+pub struct TestCodeHandler {
+    pub check: TestCodeCheck,
+}
+
+impl RuleHandler for TestCodeHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        if !crate::rules::test_code::is_test_source(ctx.file_path) {
+            return None;
+        }
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            TestCodeCheck::ThreadSleep => {
+                let (call, obj) = (capture("call")?, capture("obj").map(text)?);
+                if obj != "Thread" && !obj.contains("TimeUnit") {
+                    return None;
+                }
+                (call, format!("{} → await().atMost(..).until(..)", text(call)), Confidence::High)
+            }
+            TestCodeCheck::FixedPort => {
+                let site = capture("call")?;
+                // `WebEnvironment.DEFINED_PORT` 报告整个字段访问
+                let node = site.parent().filter(|p| p.kind() == "field_access").unwrap_or(site);
+                (node, format!("{} → RANDOM_PORT / 端口 0", text(node)), Confidence::High)
+            }
+            TestCodeCheck::SharedStaticState => {
+                let (field, mods) = (capture("field")?, capture("mods").map(text)?);
+                let words: Vec<&str> = mods.split_whitespace().collect();
+                // @Container / @ClassRule 等框架要求的静态字段不报告
+                if !words.contains(&"static") || mods.contains('@') {
+                    return None;
+                }
+                let (type_name, var) = (capture("type").map(text)?, capture("var").map(text)?);
+                if words.contains(&"final") {
+                    let mutable_value = field.child_by_field_name("declarator")
+                        .and_then(|d| d.child_by_field_name("value"))
+                        .filter(|v| v.kind() == "object_creation_expression")
+                        .and_then(|v| v.child_by_field_name("type"))
+                        .is_some_and(|t| MUTABLE_COLLECTION_TYPES.contains(&CollectionHandler::base_type(&text(t))));
+                    if !mutable_value {
+                        return None;
+                    }
+                }
+                (field, format!("static {type_name} {var} → 实例字段 + @BeforeEach 初始化"), Confidence::Medium)
+            }
+            TestCodeCheck::ContainerLeak => {
+                let (call, type_name) = (capture("call")?, capture("type").map(text)?);
+                if !CollectionHandler::base_type(&type_name).ends_with("Container") || !ctx.code.contains("org.testcontainers") {
+                    return None;
+                }
+                // 向上找到容器赋给的变量与声明所在的字段/方法
+                let inspector = ChainInspector::new(ctx.code);
+                let mut var = None;
+                let mut node = call;
+                let owner = loop {
+                    let parent = node.parent()?;
+                    match parent.kind() {
+                        // try-with-resources / 工厂方法返回 / Supplier 由调用方管理生命周期
+                        "resource" | "return_statement" | "lambda_expression" => return None,
+                        "variable_declarator" if var.is_none() => {
+                            var = parent.child_by_field_name("name").map(text);
+                        }
+                        "assignment_expression" if var.is_none() => {
+                            var = parent.child_by_field_name("left").map(|l| text(l).trim_start_matches("this.").to_string());
+                        }
+                        "field_declaration" | "method_declaration" | "constructor_declaration" => break parent,
+                        _ => {}
+                    }
+                    node = parent;
+                };
+                let mods = owner.child(0).filter(|m| m.kind() == "modifiers").map(text).unwrap_or_default();
+                // 可复用容器、JUnit 管理的 @Container/@Rule 字段、单例容器 (static 字段，JVM 退出时由 Ryuk 回收)、Spring @Bean (容器关闭时 close)
+                let reused = inspector.chain(call).call("withReuse").is_some_and(|c| c.args == ["true"]);
+                if reused
+                    || ["@Container", "@ClassRule", "@Rule", "@Bean"].iter().any(|a| mods.contains(a))
+                    || (owner.kind() == "field_declaration" && mods.split_whitespace().any(|w| w == "static"))
+                {
+                    return None;
+                }
+                if let Some(var) = &var {
+                    let mut scope = owner;
+                    while let Some(parent) = scope.parent() {
+                        scope = parent;
+                        if scope.kind() == "class_body" {
+                            break;
+                        }
+                    }
+                    let stopped = inspector.variable_chains(scope, var).iter()
+                        .any(|chain| chain.calls.first().is_some_and(|c| ["stop", "close"].contains(&c.name.as_str()) && c.args.is_empty()));
+                    if stopped {
+                        return None;
+                    }
+                }
+                let name = var.unwrap_or_else(|| CollectionHandler::base_type(&type_name).to_string());
+                (call, format!("new {type_name}(..) {name} 未 stop() → @Container / try-with-resources / @AfterEach stop()"), Confidence::Medium)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 启动耗时
+// ============================================================================
+
+/// 启动耗时规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StartupCheck {
+    /// @ComponentScan / scanBasePackages 根包过宽
+    BroadComponentScan,
+    /// @PostConstruct / afterPropertiesSet 中的远程调用
+    PostConstructRemoteCall,
+    /// 未 @Lazy 的重量级客户端 @Bean
+    EagerHeavyBean,
+}
+
+/// 扫描包属性 (其余属性如 excludeFilters 中的字符串不是包名)
+const SCAN_PACKAGE_KEYS: &[&str] = &["value", "basePackages", "scanBasePackages"];
+
+/// 常见的组织域名顶级段: `com.acme` 即扫描整个组织的所有 jar
+const GENERIC_PACKAGE_ROOTS: &[&str] = &["com", "org", "net", "io", "cn"];
+
+/// 创建时即建连 / 拉取元数据 / 编译规则的重量级客户端
+const HEAVY_CLIENT_TYPES: &[&str] = &[
+    "RestHighLevelClient", "ElasticsearchClient", "TransportClient", "KafkaProducer", "KafkaConsumer",
+    "MongoClient", "CqlSession", "AmazonS3", "S3Client", "DynamoDbClient", "RedissonClient", "JedisCluster",
+    "CuratorFramework", "ZooKeeper", "ManagedChannel", "HazelcastInstance", "SparkSession", "KieContainer",
+];
+
+/// 启动耗时检测处理器
+pub struct StartupHandler {
+    pub check: StartupCheck,
+}
+
+impl StartupHandler {
+    /// 扫描注解中过宽的包名: (包名, 置信度)
+    fn broad_package(args: tree_sitter::Node, code: &str) -> Option<(String, Confidence)> {
+        let mut values = Vec::new();
+        let mut cursor = args.walk();
+        for arg in args.named_children(&mut cursor) {
+            if arg.kind() != "element_value_pair" {
+                values.push(arg);
+                continue;
+            }
+            let key = arg.child_by_field_name("key").and_then(|k| k.utf8_text(code.as_bytes()).ok());
+            if key.is_some_and(|k| SCAN_PACKAGE_KEYS.contains(&k)) {
+                values.extend(arg.child_by_field_name("value"));
+            }
+        }
+
+        let mut stack = values;
+        while let Some(node) = stack.pop() {
+            if node.kind() == "string_literal" {
+                let package = node.utf8_text(code.as_bytes()).unwrap_or("").trim_matches('"').to_string();
+                let segments: Vec<&str> = package.split('.').filter(|s| !s.is_empty()).collect();
+                match segments.as_slice() {
+                    [] | [_] => return Some((package, Confidence::High)),
+                    [root, _] if GENERIC_PACKAGE_ROOTS.contains(root) => return Some((package, Confidence::Medium)),
+                    _ => {}
+                }
+                continue;
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        None
+    }
+
+    /// 在 lambda 中的调用 (如 `CompletableFuture.runAsync(() -> ...)`) 不阻塞初始化线程
+    fn in_lambda(node: tree_sitter::Node, body: tree_sitter::Node) -> bool {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            if parent.id() == body.id() {
+                return false;
+            }
+            if parent.kind() == "lambda_expression" {
+                return true;
+            }
+            current = parent;
+        }
+        false
+    }
+
+    /// 所在类声明
+    fn enclosing_class(decl: tree_sitter::Node) -> Option<tree_sitter::Node> {
+        decl.parent().filter(|b| b.kind() == "class_body").and_then(|b| b.parent())
+    }
+}
+
+impl RuleHandler for StartupHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            StartupCheck::BroadComponentScan => {
+                let (call, ann, args) = (capture("call")?, capture("ann")?, capture("args")?);
+                let (package, confidence) = Self::broad_package(args, ctx.code)?;
+                let scope = if package.is_empty() { "整个 classpath".to_string() } else { format!("{package}.*") };
+                (call, format!("@{}(\"{package}\") → 扫描 {scope}，收窄到应用自身的包", text(ann)), confidence)
+            }
+            StartupCheck::PostConstructRemoteCall => {
+                let (decl, method_name) = (capture("decl")?, capture("method")?);
+                let method = text(method_name);
+                let body = decl.child_by_field_name("body")?;
+                let fields = ListenerHandler::class_fields(decl, ctx.code);
+                let direct = ListenerHandler::invocations(body, ctx.code).into_iter().find(|(receiver, _, _, node)| {
+                    !Self::in_lambda(*node, body) && fields.iter()
+                        .find(|(f, _, _)| f == receiver)
+                        .and_then(|(_, type_name, _)| PerRequestSinkHandler::classify_type(type_name, ctx.symbol_table))
+                        .is_some()
+                });
+                if let Some((receiver, name, _, node)) = direct {
+                    (node, format!("{method}(): {receiver}.{name}()"), Confidence::High)
+                } else {
+                    let chain = [RequestSink::Http, RequestSink::Database].into_iter()
+                        .find_map(|sink| PerRequestSinkHandler { sink }.indirect_sink_chain(&method, ctx))?;
+                    (method_name, format!("{method}(): [调用链: {chain}]"), Confidence::Medium)
+                }
+            }
+            StartupCheck::EagerHeavyBean => {
+                let (decl, type_node, method_name) = (capture("decl")?, capture("type")?, capture("method")?);
+                let type_name = text(type_node);
+                let simple = type_name.split('<').next().unwrap_or(&type_name).rsplit('.').next().unwrap_or(&type_name);
+                if !HEAVY_CLIENT_TYPES.contains(&simple) {
+                    return None;
+                }
+                // 方法或整个配置类上的 @Lazy
+                let lazy = std::iter::once(decl).chain(Self::enclosing_class(decl))
+                    .any(|n| ListenerHandler::annotations(n, ctx.code).iter().any(|(name, _)| name == "Lazy"));
+                if lazy {
+                    return None;
+                }
+                (method_name, format!("@Bean {simple} {}() → @Lazy", text(method_name)), Confidence::High)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: SQL 字符串拼接
+// ============================================================================
+
+/// 判定字面量为 SQL 片段的关键字
+const SQL_KEYWORDS: &[&str] = &["SELECT", "INSERT", "UPDATE", "DELETE", "MERGE", "FROM", "WHERE", "VALUES", "SET", "ORDER", "GROUP", "HAVING", "JOIN"];
+
+/// 变量拼接进 SQL 后传给 `createQuery` / `executeQuery` / `JdbcTemplate.query` 等
+///
+/// 直接实参为 `"..." + x` / `String.format("... %s", x)` 时为 High；
+/// 实参是同一方法内以拼接赋值 (`=` / `+=`) 的局部变量时为 Medium。
+/// 全大写常量与字面量之间的拼接在编译期确定，不报告。
+pub struct SqlConcatHandler;
+
+impl SqlConcatHandler {
+    /// 字面量中是否含 SQL 关键字 (按单词匹配，忽略大小写)
+    fn is_sql_literal(literal: &str) -> bool {
+        literal.split(|c: char| !c.is_ascii_alphabetic())
+            .any(|word| SQL_KEYWORDS.iter().any(|k| k.eq_ignore_ascii_case(word)))
+    }
+
+    /// `ORDER_TABLE` / `Tables.ORDER_TABLE` 视为编译期常量
+    fn is_constant(node: tree_sitter::Node, code: &str) -> bool {
+        let name = match node.kind() {
+            "identifier" => node,
+            "field_access" => match node.child_by_field_name("field") {
+                Some(field) => field,
+                None => return false,
+            },
+            "string_literal" | "decimal_integer_literal" | "character_literal" => return true,
+            "parenthesized_expression" => return node.named_child(0).is_some_and(|n| Self::is_constant(n, code)),
+            _ => return false,
+        };
+        let text = name.utf8_text(code.as_bytes()).unwrap_or("");
+        text.chars().any(|c| c.is_ascii_uppercase())
+            && text.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+    }
+
+    /// `+` 拼接展开为操作数
+    fn operands<'t>(node: tree_sitter::Node<'t>, code: &str, out: &mut Vec<tree_sitter::Node<'t>>) {
+        match node.kind() {
+            "binary_expression" if node.child_by_field_name("operator")
+                .and_then(|o| o.utf8_text(code.as_bytes()).ok()) == Some("+") => {
+                node.child_by_field_name("left").into_iter()
+                    .chain(node.child_by_field_name("right"))
+                    .for_each(|n| Self::operands(n, code, out));
+            }
+            "parenthesized_expression" => {
+                if let Some(inner) = node.named_child(0) {
+                    Self::operands(inner, code, out);
+                }
+            }
+            _ => out.push(node),
+        }
+    }
+
+    /// 表达式中拼接进 SQL 的第一个变量 (文本)
+    fn dynamic_part(expr: tree_sitter::Node, code: &str) -> Option<String> {
+        let text = |n: tree_sitter::Node| n.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+        let operands: Vec<tree_sitter::Node> = match expr.kind() {
+            "binary_expression" => {
+                let mut out = Vec::new();
+                Self::operands(expr, code, &mut out);
+                out
+            }
+            // String.format("... %s", x) / "...".formatted(x)
+            "method_invocation" => {
+                let name = text(expr.child_by_field_name("name")?);
+                let args = expr.child_by_field_name("arguments")?;
+                let mut cursor = args.walk();
+                let args: Vec<_> = args.named_children(&mut cursor).collect();
+                match name.as_str() {
+                    "format" => args,
+                    "formatted" => expr.child_by_field_name("object").into_iter().chain(args).collect(),
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+        let sql = operands.iter()
+            .any(|n| n.kind() == "string_literal" && Self::is_sql_literal(&text(*n)));
+        if !sql {
+            return None;
+        }
+        operands.into_iter().find(|n| !Self::is_constant(*n, code)).map(text)
+    }
+
+    /// 调用前同一方法内对局部变量的拼接赋值
+    fn local_concat(var: &str, call: tree_sitter::Node, code: &str) -> Option<String> {
+        let mut body = call;
+        while !matches!(body.kind(), "method_declaration" | "constructor_declaration" | "lambda_expression") {
+            body = body.parent()?;
+        }
+        let mut found = None;
+        let mut stack = vec![body];
+        while let Some(node) = stack.pop() {
+            if node.start_byte() >= call.start_byte() {
+                continue;
+            }
+            let name_is_var = |field: &str| node.child_by_field_name(field)
+                .and_then(|n| n.utf8_text(code.as_bytes()).ok()) == Some(var);
+            let value = match node.kind() {
+                "variable_declarator" if name_is_var("name") => node.child_by_field_name("value"),
+                "assignment_expression" if name_is_var("left") => node.child_by_field_name("right"),
+                _ => None,
+            };
+            if let Some(dynamic) = value.and_then(|v| Self::dynamic_part(v, code)) {
+                found = Some(dynamic);
+            }
+            // `sql += " AND name = '" + name + "'"`: 右侧本身可能不含关键字
+            if node.kind() == "assignment_expression" && name_is_var("left") && found.is_none() {
+                let op = node.child_by_field_name("operator").and_then(|o| o.utf8_text(code.as_bytes()).ok());
+                let right = node.child_by_field_name("right");
+                if op == Some("+=") {
+                    if let Some(right) = right.filter(|r| !Self::is_constant(*r, code)) {
+                        let mut out = Vec::new();
+                        Self::operands(right, code, &mut out);
+                        found = out.into_iter().find(|n| !Self::is_constant(*n, code))
+                            .map(|n| n.utf8_text(code.as_bytes()).unwrap_or("").to_string());
+                    }
+                }
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        found
+    }
+}
+
+impl RuleHandler for SqlConcatHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let (call, method, sql) = (capture("call")?, capture("method")?, capture("sql")?);
+        let method = method.utf8_text(ctx.code.as_bytes()).unwrap_or("");
+
+        let (dynamic, confidence) = match sql.kind() {
+            "identifier" => {
+                let var = sql.utf8_text(ctx.code.as_bytes()).unwrap_or("");
+                (Self::local_concat(var, call, ctx.code)?, Confidence::Medium)
+            }
+            _ => (Self::dynamic_part(sql, ctx.code)?, Confidence::High),
+        };
+        let dynamic = dynamic.lines().next().unwrap_or("").trim().to_string();
+
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(format!("{method}(): SQL 拼接 `{dynamic}` → 改用 ? / :name 参数绑定")),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: HTTP 客户端超时
+// ============================================================================
+
+/// 把连接器 / 请求工厂交给外部 Bean 时，超时在别处配置
+const HTTP_DELEGATE_METHODS: &[&str] = &["clientConnector", "requestFactory", "setRequestFactory", "httpClient"];
+
+/// HTTP 客户端创建处的超时校验
+///
+/// 在客户端创建所在的方法 (或字段初始化、构造器) 内查找超时配置：
+/// 名称含 `timeout` 的调用、常量或类型 (`responseTimeout`、`setConnectTimeout`、
+/// `CONNECT_TIMEOUT_MILLIS`、`ReadTimeoutHandler` 等)，或把注入的连接器 / 请求工厂交给客户端；
+/// `new RestTemplate(factory)` 的超时由工厂决定，不报告；带参数的 `RestClient.builder(hosts)` 是 Elasticsearch 低级客户端，不报告。
+/// 项目级全局配置 (`spring.http.client.*`、`RestTemplateCustomizer` 等) 由 [`crate::rules::http_timeout`] 在扫描后处理。
+pub struct HttpClientTimeoutHandler;
+
+impl HttpClientTimeoutHandler {
+    /// 创建语句所在的配置范围
+    fn scope(node: tree_sitter::Node) -> tree_sitter::Node {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            if matches!(parent.kind(), "method_declaration" | "constructor_declaration" | "field_declaration" | "static_initializer") {
+                return parent;
+            }
+            current = parent;
+        }
+        current
+    }
+
+    /// 范围内是否配置了超时 (或交给了外部配置的连接器 / 请求工厂)
+    fn configured(scope: tree_sitter::Node, code: &str) -> bool {
+        let text = |n: tree_sitter::Node| n.utf8_text(code.as_bytes()).unwrap_or("");
+        let mut stack = vec![scope];
+        while let Some(node) = stack.pop() {
+            match node.kind() {
+                "identifier" | "type_identifier" if text(node).to_ascii_lowercase().contains("timeout") => return true,
+                "method_invocation" => {
+                    let delegated = node.child_by_field_name("name").is_some_and(|n| HTTP_DELEGATE_METHODS.contains(&text(n)))
+                        && node.child_by_field_name("arguments")
+                            .and_then(|args| args.named_child(0))
+                            .is_some_and(|arg| arg.kind() == "identifier");
+                    if delegated {
+                        return true;
+                    }
+                }
+                _ => {}
+            }
+            let mut cursor = node.walk();
+            stack.extend(node.named_children(&mut cursor));
+        }
+        false
+    }
+}
+
+impl RuleHandler for HttpClientTimeoutHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+        let call = capture("call")?;
+        let type_name = text(capture("type")?);
+
+        let has_args = capture("args").is_some_and(|args| args.named_child_count() > 0);
+        let client = match capture("method") {
+            Some(_) if type_name == "RestClient" && has_args => return None,
+            Some(method) => format!("{type_name}.{}()", text(method)),
+            None if type_name == "RestTemplate" && has_args => return None,
+            None => format!("new {type_name}()"),
+        };
+        let scope = Self::scope(call);
+        if Self::configured(scope, ctx.code) {
+            return None;
+        }
+        let location = match scope.kind() {
+            "method_declaration" | "constructor_declaration" => scope.child_by_field_name("name")
+                .map(|n| format!("{}()", text(n)))
+                .unwrap_or_default(),
+            _ => "字段初始化".to_string(),
+        };
+
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(format!("{client} @ {location}: 未设置 connect/read/response 超时")),
+            confidence: Some(Confidence::Medium),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 缓存过期 / 容量策略
+// ============================================================================
+
+/// 设置过期或容量上限的构建器方法
+const CACHE_POLICY_METHODS: &[&str] = &[
+    "expireAfterWrite", "expireAfterAccess", "expireAfter", "maximumSize", "maximumWeight",
+];
+
+/// 描述中调用链的标签 (扫描后按调用链判断构建器是否交给 CacheManager)
+pub fn cache_chain_label() -> &'static str {
+    i18n::text("note.call_chain", "调用链")
+}
+
+/// `Caffeine.newBuilder()` / `CacheBuilder.newBuilder()` 未设置过期时间与容量上限
+///
+/// 用 [`ChainInspector`] 还原整条调用链 (`Caffeine.newBuilder().recordStats().build()`)；构建器先赋给变量时，
+/// 同一方法内以该变量为接收者的调用链也计入。链上任意一处设置了过期或容量上限即不报告，
+/// 否则在描述中附上调用链。`spring.cache.caffeine.spec` 对交给 CacheManager 的构建器的覆盖
+/// 由 [`crate::rules::cache_policy`] 在扫描后处理。
+pub struct CacheExpiryHandler;
+
+impl RuleHandler for CacheExpiryHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let idx = query.capture_index_for_name("call")?;
+        let call = m.captures.iter().find(|c| c.index == idx)?.node;
+        let inspector = ChainInspector::new(ctx.code);
+
+        let chain = inspector.chain(call);
+        let mut chains = Vec::new();
+        if let Some(variable) = inspector.assigned_variable(&chain) {
+            chains = inspector.variable_chains(HttpClientTimeoutHandler::scope(chain.top), &variable);
+        }
+        chains.insert(0, chain);
+        if chains.iter().any(|chain| chain.has_any(CACHE_POLICY_METHODS)) {
+            return None;
+        }
+
+        let chain = chains.iter().map(|chain| inspector.render(chain)).collect::<Vec<_>>().join("; ");
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: format!("{description} [{}: {chain}]", cache_chain_label()),
+            context: Some(chain),
+            confidence: Some(Confidence::High),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 注解参数规则
+// ============================================================================
+
+/// 小于该周期 (毫秒) 的 fixedRate 几乎必然在上一次执行结束前再次触发
+const FIXED_RATE_FLOOR_MS: i64 = 1_000;
+
+/// 向上查找外层事务方法的调用深度
+const OUTER_TRANSACTION_DEPTH: usize = 4;
+
+/// `@Scheduled(fixedRate = ...)` 按周期值报告
+///
+/// 周期 < 1s 为 High，可求值的其他周期为 Medium，引用常量或 `${...}` 占位符的为 Low；描述中附带周期值。
+/// 方法体直接或经调用图 (需 Phase 1 上下文) 调用 DB / HTTP 时，执行耗时取决于下游，可能超过周期而排队：
+/// 升级为 P0 / High 并附带调用链。
+pub struct ScheduledRateHandler;
+
+impl ScheduledRateHandler {
+    /// 定时方法到达的 DB / HTTP 调用 (直接调用字段或调用图上的调用链)
+    fn sink_call(method: tree_sitter::Node, name: &str, ctx: &RuleContext) -> Option<String> {
+        let mut class = method.parent();
+        while let Some(node) = class.filter(|n| n.kind() != "class_declaration") {
+            class = node.parent();
+        }
+        let fields = class.map(|c| PerRequestSinkHandler::class_fields(c, ctx.code)).unwrap_or_default();
+        let body = method.child_by_field_name("body")?;
+        [RequestSink::Database, RequestSink::Http].into_iter().find_map(|sink| {
+            let handler = PerRequestSinkHandler { sink };
+            handler.direct_sink_call(body, &fields, ctx)
+                .or_else(|| handler.indirect_sink_chain(name, ctx).map(|chain| format!("[调用链: {chain}]")))
+        })
+    }
+}
+
+impl RuleHandler for ScheduledRateHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let idx = query.capture_index_for_name("method")?;
+        let method = m.captures.iter().find(|c| c.index == idx)?.node;
+        let scheduled = AnnotationArgs::find(method, "Scheduled", ctx.code)?;
+        let raw = scheduled.get("fixedRate").or_else(|| scheduled.get("fixedRateString"))?;
+
+        let (detail, confidence) = match scheduled.duration_ms("fixedRate") {
+            Some(ms) if ms < FIXED_RATE_FLOOR_MS => (
+                format!("fixedRate = {} < {}，上一次执行未结束即再次触发", format_ms(ms), format_ms(FIXED_RATE_FLOOR_MS)),
+                Confidence::High,
+            ),
+            Some(ms) => (format!("fixedRate = {}", format_ms(ms)), Confidence::Medium),
+            None => (format!("fixedRate = {raw}"), Confidence::Low),
+        };
+        let name = method.child_by_field_name("name")?.utf8_text(ctx.code.as_bytes()).ok()?;
+        let (detail, severity, confidence) = match Self::sink_call(method, name, ctx) {
+            Some(call) => (
+                format!("{detail}；{name}() 调用 {call}，执行耗时取决于下游，可能超过周期而排队"),
+                Severity::P0,
+                Confidence::High,
+            ),
+            None => (detail, severity, confidence),
+        };
+
+        let span = Span::from_node(&method);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: format!("{description} ({detail})"),
+            context: Some(detail),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+/// `@Transactional(propagation = REQUIRES_NEW)`
+///
+/// 其他传播行为不报告。经调用图找到外层事务方法时为 High (外层事务挂起期间同时占用两个连接)，
+/// 否则为 Medium；描述中附带外层调用链与 timeout (未设置时提示)。
+pub struct TransactionalPropagationHandler;
+
+impl TransactionalPropagationHandler {
+    /// 方法或其所在类声明了 `@Transactional`
+    fn is_transactional(sig: &crate::taint::MethodSig, st: &SymbolTable) -> bool {
+        let class = sig.simple_class_name();
+        st.lookup_methods(class, &sig.name).iter()
+            .any(|m| m.annotations.iter().any(|a| parse_text(a).0 == "Transactional"))
+            || st.lookup_by_simple_name(class).iter().any(|t| t.annotations.iter().any(|a| a == "Transactional"))
+    }
+
+    /// 从外层事务方法到 `method` 的调用链 (首跳忽略同类自调用：不经代理，REQUIRES_NEW 不生效)
+    fn outer_transaction(method: &str, ctx: &RuleContext) -> Option<String> {
+        let cg = ctx.call_graph?;
+        let st = ctx.symbol_table?;
+        let mut queue: std::collections::VecDeque<Vec<crate::taint::MethodSig>> =
+            cg.find_methods(ctx.current_class, method).into_iter().map(|start| vec![start]).collect();
+        let mut visited: std::collections::HashSet<_> = queue.iter().flatten().cloned().collect();
+        while let Some(path) = queue.pop_front() {
+            if path.len() > OUTER_TRANSACTION_DEPTH {
+                continue;
+            }
+            let current = path.last()?;
+            for site in cg.incoming.get(current).into_iter().flatten() {
+                let caller = &site.caller;
+                if path.len() == 1 && caller.simple_class_name() == current.simple_class_name() {
+                    continue;
+                }
+                if !visited.insert(caller.clone()) {
+                    continue;
+                }
+                let mut next = path.clone();
+                next.push(caller.clone());
+                if Self::is_transactional(caller, st) {
+                    return Some(next.iter().rev()
+                        .map(|sig| format!("{}.{}", sig.simple_class_name(), sig.name))
+                        .collect::<Vec<_>>()
+                        .join(" → "));
+                }
+                queue.push_back(next);
+            }
+        }
+        None
+    }
+}
+
+impl RuleHandler for TransactionalPropagationHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let idx = query.capture_index_for_name("method")?;
+        let method = m.captures.iter().find(|c| c.index == idx)?.node;
+        let transactional = AnnotationArgs::find(method, "Transactional", ctx.code)?;
+        if transactional.enum_constant("propagation") != Some("REQUIRES_NEW") {
+            return None;
+        }
+        let name = method.child_by_field_name("name")?.utf8_text(ctx.code.as_bytes()).ok()?;
+
+        let mut details = vec!["propagation = REQUIRES_NEW".to_string()];
+        let outer = Self::outer_transaction(name, ctx);
+        if let Some(chain) = &outer {
+            details.push(i18n::format(
+                "note.requires_new.outer", "在外层事务内调用，挂起期间同时占用两个连接 [调用链: {chain}]", &[("chain", chain)],
+            ));
+        }
+        details.push(match (transactional.int("timeout"), transactional.get("timeout")) {
+            (Some(seconds), _) => format!("timeout = {seconds}s"),
+            (None, Some(raw)) => format!("timeout = {raw}"),
+            (None, None) => i18n::text("note.requires_new.no_timeout", "未设置 timeout").to_string(),
+        });
+
+        let detail = details.join(i18n::text("note.separator", "；"));
+        let span = Span::from_node(&method);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: format!("{description} ({detail})"),
+            context: Some(detail),
+            confidence: Some(if outer.is_some() { Confidence::High } else { Confidence::Medium }),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 列表接口未分页
+// ============================================================================
+
+/// 无上限的集合返回类型 (Page / Slice 自带分页)
+const UNBOUNDED_RETURN_TYPES: &[&str] = &["List", "Set", "Collection", "Iterable", "Stream"];
+
+/// 分页 / 限量参数类型
+const PAGING_PARAM_TYPES: &[&str] = &["Pageable", "PageRequest", "Limit", "ScrollPosition", "RowBounds", "IPage", "Page"];
+
+/// 查询注解中的限量子句
+const LIMIT_CLAUSES: &[&str] = &["LIMIT", "TOP ", "FETCH FIRST", "FETCH NEXT", "ROWNUM"];
+
+/// Controller → Repository 反向追踪深度
+const UNBOUNDED_QUERY_DEPTH: usize = 6;
+
+/// Controller 调用链上的 Repository / Mapper 查询返回无上限集合
+///
+/// 返回类型与参数取自 Phase 1 的方法注册 ([`SymbolTable::lookup_methods`])：
+/// `List`/`Set`/`Stream` 等返回值、无 `Pageable`/`RowBounds` 参数、方法名不是 `findTop10By`/`findFirstBy`、
+/// 查询注解中没有 LIMIT 时视为无上限；Spring Data 继承的无参 `findAll()` 为 Medium。
+/// 调用方本身是 Controller，或经调用图可从 Controller 到达时报告 (后者需要调用图)。
+pub struct UnboundedQueryHandler;
+
+impl UnboundedQueryHandler {
+    /// `findTop10By..` / `findFirstBy..` / `streamTop5By..` 自带限量
+    fn is_limited_name(method: &str) -> bool {
+        ["find", "read", "get", "query", "search", "stream"].iter()
+            .filter_map(|prefix| method.strip_prefix(prefix))
+            .any(|rest| rest.starts_with("First") || rest.starts_with("Top"))
+    }
+
+    /// 已注册方法的无上限返回类型 (如 `List<Order>`)
+    fn unbounded_return(method: &crate::symbol_table::MethodInfo) -> Option<String> {
+        let return_type = method.return_type.as_deref()?;
+        let raw = return_type.split('<').next().unwrap_or(return_type).trim();
+        let simple = raw.rsplit('.').next().unwrap_or(raw);
+        if !UNBOUNDED_RETURN_TYPES.contains(&simple) || Self::is_limited_name(&method.name) {
+            return None;
+        }
+        let paged = method.params.iter().any(|p| {
+            let raw = p.type_name.split('<').next().unwrap_or(&p.type_name);
+            PAGING_PARAM_TYPES.contains(&raw.rsplit('.').next().unwrap_or(raw))
+        });
+        let limited = method.annotations.iter()
+            .any(|a| LIMIT_CLAUSES.iter().any(|clause| a.to_ascii_uppercase().contains(clause)));
+        (!paged && !limited).then(|| return_type.to_string())
+    }
+
+    /// 所在方法名
+    fn enclosing_method(node: tree_sitter::Node, code: &str) -> Option<String> {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            if parent.kind() == "method_declaration" {
+                return parent.child_by_field_name("name")?.utf8_text(code.as_bytes()).ok().map(str::to_string);
+            }
+            current = parent;
+        }
+        None
+    }
+}
+
+impl RuleHandler for UnboundedQueryHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+        let (call, recv, method, args) = (capture("call")?, capture("recv")?, capture("method")?, capture("args")?);
+        let st = ctx.symbol_table?;
+        let (receiver, method) = (text(recv), text(method));
+
+        // 接收者字段 → Repository / Mapper 类型 (不在项目中时按类型名后缀判断)
+        let binding = st.fields.get(&(ctx.current_class.to_string(), receiver.clone()))?;
+        let type_name = binding.type_name.split('<').next().unwrap_or(&binding.type_name).trim();
+        let indexed = st.lookup_by_simple_name(type_name).into_iter().any(|info| info.is_dao());
+        if !indexed && !["Repository", "Mapper", "Dao"].iter().any(|suffix| type_name.ends_with(suffix)) {
+            return None;
+        }
+
+        let overloads = st.lookup_methods(type_name, &method);
+        let (return_type, confidence) = if overloads.is_empty() {
+            // Spring Data 继承的 findAll()
+            if method != "findAll" || args.named_child_count() > 0 {
+                return None;
+            }
+            ("List".to_string(), Confidence::Medium)
+        } else {
+            let arity = args.named_child_count();
+            let target = overloads.iter().find(|m| m.params.len() == arity).unwrap_or(&overloads[0]);
+            (Self::unbounded_return(target)?, Confidence::High)
+        };
+
+        // 调用方即 Controller，或经调用图可从 Controller 到达
+        let caller = Self::enclosing_method(call, ctx.code)?;
+        let chain = if ThreadHandler::class_layer(call, ctx) == crate::symbol_table::LayerType::Controller {
+            vec![format!("{}.{caller}", ctx.current_class)]
+        } else {
+            let cg = ctx.call_graph?;
+            let path = cg.find_methods(ctx.current_class, &caller).iter()
+                .find_map(|start| cg.trace_from_layer(start, crate::taint::LayerType::Controller, UNBOUNDED_QUERY_DEPTH)
+                    .into_iter().min_by_key(|path| path.len()))?;
+            path.iter().map(|sig| format!("{}.{}", sig.simple_class_name(), sig.name)).collect()
+        };
+
+        let span = Span::from_node(&call);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(format!(
+                "{receiver}.{method}() 返回 {return_type}，无 Pageable/LIMIT [调用链: {} → {}.{method}]",
+                chain.join(" → "), type_name,
+            )),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: 重试 / 熔断
+// ============================================================================
+
+/// 重试规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryCheck {
+    /// @Retryable 未配置 backoff 或 @Backoff(0)
+    NoBackoff,
+    /// 远程调用上 maxAttempts > 5
+    ExcessiveAttempts,
+    /// @Retryable / @Retry 包裹 save / insert
+    NonIdempotent,
+}
+
+/// 远程调用可接受的最大重试次数 (含首次调用)
+pub const MAX_REMOTE_ATTEMPTS: u32 = 5;
+
+/// 非幂等写入方法
+const NON_IDEMPOTENT_METHODS: &[&str] = &["save", "saveAll", "saveAndFlush", "persist", "insert", "insertSelective", "insertBatch", "batchInsert"];
+
+/// 远程调用客户端的类型后缀 (Feign / gRPC stub / SDK 客户端)
+const REMOTE_TYPE_SUFFIXES: &[&str] = &["Client", "Stub", "Api"];
+
+/// 重试配置检测处理器
+///
+/// 解析 `@Retryable(maxAttempts = .., backoff = @Backoff(..))` 注解参数，并结合方法体内的调用判断
+/// 是否为远程调用 / 非幂等写入。resilience4j `@Retry(name)` / `@CircuitBreaker(name)` 的参数在
+/// 配置文件中，由 [`crate::rules::resilience`] 在目录扫描后关联。
+pub struct RetryHandler {
+    pub check: RetryCheck,
+}
+
+impl RetryHandler {
+    /// 注解参数: 名称 → 值节点 (单值简写记为 `value`)
+    fn arguments<'t>(annotation: tree_sitter::Node<'t>, code: &str) -> Vec<(String, tree_sitter::Node<'t>)> {
+        let Some(args) = annotation.child_by_field_name("arguments") else { return Vec::new() };
+        let mut cursor = args.walk();
+        args.named_children(&mut cursor)
+            .filter_map(|arg| {
+                if arg.kind() != "element_value_pair" {
+                    return Some(("value".to_string(), arg));
+                }
+                let key = arg.child_by_field_name("key")?.utf8_text(code.as_bytes()).ok()?;
+                Some((key.to_string(), arg.child_by_field_name("value")?))
+            })
+            .collect()
+    }
+
+    fn argument<'t>(annotation: tree_sitter::Node<'t>, name: &str, code: &str) -> Option<tree_sitter::Node<'t>> {
+        Self::arguments(annotation, code).into_iter().find(|(key, _)| key == name).map(|(_, value)| value)
+    }
+
+    fn int_value(node: tree_sitter::Node, code: &str) -> Option<u64> {
+        node.utf8_text(code.as_bytes()).ok()?.trim_end_matches(['L', 'l']).replace('_', "").parse().ok()
+    }
+
+    /// 未退避的原因; 显式配置了非零 delay 或 multiplier 时为 None
+    fn missing_backoff(annotation: tree_sitter::Node, code: &str) -> Option<String> {
+        let Some(backoff) = Self::argument(annotation, "backoff", code) else {
+            return Some("未配置 backoff (默认固定 1s 间隔，无指数退避)".to_string());
+        };
+        if backoff.kind() != "annotation" && backoff.kind() != "marker_annotation" {
+            return None;
+        }
+        let args = Self::arguments(backoff, code);
+        if args.iter().any(|(key, _)| key == "multiplier" || key == "delayExpression" || key == "random") {
+            return None;
+        }
+        let delay = args.iter().find(|(key, _)| key == "delay" || key == "value");
+        match delay {
+            Some((_, value)) if Self::int_value(*value, code) == Some(0) => Some("@Backoff 延迟为 0 (失败后立即重试)".to_string()),
+            Some(_) => None,
+            None if args.is_empty() => Some("@Backoff 未设置 delay/multiplier (默认固定 1s 间隔，无指数退避)".to_string()),
+            None => None,
+        }
+    }
+
+    /// 方法体内对某类字段的第一个调用: (接收者, 方法名, 类型, 节点)
+    fn field_call<'t>(
+        decl: tree_sitter::Node<'t>,
+        ctx: &RuleContext,
+        accept: impl Fn(&str, &str) -> bool,
+    ) -> Option<(String, String, String, tree_sitter::Node<'t>)> {
+        let body = decl.child_by_field_name("body")?;
+        let fields = ListenerHandler::class_fields(decl, ctx.code);
+        ListenerHandler::invocations(body, ctx.code).into_iter().find_map(|(receiver, name, _, node)| {
+            let (_, type_name, _) = fields.iter().find(|(f, _, _)| *f == receiver)?;
+            accept(type_name, &name).then(|| (receiver, name, type_name.clone(), node))
+        })
+    }
+}
+
+impl RuleHandler for RetryHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+        let (decl, method_name) = (capture("decl")?, capture("method")?);
+        let annotation = capture("ann")?.parent()?;
+        let ann = text(capture("ann")?);
+        let method = text(method_name);
+
+        let (node, context, confidence) = match self.check {
+            RetryCheck::NoBackoff => {
+                let reason = Self::missing_backoff(annotation, ctx.code)?;
+                (annotation, format!("@{ann} {method}(): {reason}"), Confidence::High)
+            }
+            RetryCheck::ExcessiveAttempts => {
+                let attempts = Self::argument(annotation, "maxAttempts", ctx.code)
+                    .and_then(|value| Self::int_value(value, ctx.code))
+                    .filter(|attempts| *attempts > u64::from(MAX_REMOTE_ATTEMPTS))?;
+                let http = Self::field_call(decl, ctx, |type_name, _| {
+                    PerRequestSinkHandler::classify_type(type_name, ctx.symbol_table) == Some(RequestSink::Http)
+                });
+                let (call, confidence) = match http {
+                    Some(call) => (call, Confidence::High),
+                    None => {
+                        let call = Self::field_call(decl, ctx, |type_name, _| {
+                            let simple = type_name.split('<').next().unwrap_or(type_name);
+                            REMOTE_TYPE_SUFFIXES.iter().any(|suffix| simple.ends_with(suffix))
+                        })?;
+                        (call, Confidence::Medium)
+                    }
+                };
+                let (receiver, name, _, _) = call;
+                (annotation, format!("@{ann}(maxAttempts = {attempts}) {method}(): 远程调用 {receiver}.{name}()"), confidence)
+            }
+            RetryCheck::NonIdempotent => {
+                let (receiver, name, _, node) = Self::field_call(decl, ctx, |type_name, name| {
+                    NON_IDEMPOTENT_METHODS.contains(&name)
+                        && PerRequestSinkHandler::classify_type(type_name, ctx.symbol_table) == Some(RequestSink::Database)
+                })?;
+                (node, format!("@{ann} {method}(): {receiver}.{name}() 非幂等写入，重试可能重复插入"), Confidence::High)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// v9.6: Redis
+// ============================================================================
+
+/// Redis 规则组
+pub const REDIS_RULES: &[&str] = &["REDIS_SETNX_NO_EXPIRE", "REDIS_JEDIS_SHARED", "REDIS_FULL_SCAN_IN_REQUEST", "REDIS_OP_IN_LOOP"];
+
+/// Redis 规则检查项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedisCheck {
+    /// setnx / setIfAbsent 未带过期时间
+    SetnxNoExpire,
+    /// Jedis 字段被多线程共享
+    SharedJedis,
+    /// 请求路径中的 KEYS / SMEMBERS
+    FullScanInRequest,
+    /// 循环内逐条命令
+    OpInLoop,
+}
+
+/// 直接执行命令的 Redis 客户端 / 操作接口
+const REDIS_CLIENT_TYPES: &[&str] = &[
+    "RedisTemplate", "StringRedisTemplate", "RedisOperations", "ReactiveRedisTemplate", "ReactiveStringRedisTemplate",
+    "ValueOperations", "SetOperations", "HashOperations", "ListOperations", "ZSetOperations",
+    "Jedis", "JedisCluster", "JedisPooled", "UnifiedJedis", "ShardedJedis", "BinaryJedis",
+    "RedisCommands", "RedisAdvancedClusterCommands",
+];
+
+/// 表明项目使用 Redis 的其他类型 (连接池 / 连接工厂)
+const REDIS_POOL_TYPES: &[&str] = &["JedisPool", "JedisSentinelPool", "RedisConnectionFactory", "LettuceConnectionFactory", "JedisConnectionFactory"];
+
+/// 过期设置命令
+const REDIS_EXPIRE_METHODS: &[&str] = &["expire", "pexpire", "expireAt", "pexpireAt", "expireAsync"];
+
+/// 批量提交回调: 其中的命令在同一次往返中发送
+const REDIS_PIPELINE_METHODS: &[&str] = &["executePipelined", "pipelined", "execute", "multi"];
+
+/// 调用链上追踪 Controller 的最大深度
+const REDIS_REQUEST_DEPTH: usize = 6;
+
+/// Redis 反模式检测处理器
+///
+/// 命令调用沿 `a.opsForValue().get()` 链找到根接收者，按所在方法的局部变量/参数与类字段
+/// (或符号表字段) 的声明类型确认是 Redis 客户端。
+pub struct RedisHandler {
+    pub check: RedisCheck,
+}
+
+impl RedisHandler {
+    /// 符号表中是否出现 Redis 客户端 / 连接池类型 (Redis 规则组的启用条件)
+    pub fn client_present(st: &SymbolTable) -> bool {
+        st.fields.values().any(|binding| {
+            let base = CollectionHandler::base_type(&binding.type_name);
+            REDIS_CLIENT_TYPES.contains(&base) || REDIS_POOL_TYPES.contains(&base)
+        })
+    }
+
+    /// 调用链的根接收者及其 Redis 类型: `redisTemplate.opsForSet().members(k)` → (`redisTemplate`, `RedisTemplate`)
+    fn redis_receiver(call: tree_sitter::Node, ctx: &RuleContext) -> Option<(String, String)> {
+        let mut object = call.child_by_field_name("object")?;
+        while object.kind() == "method_invocation" {
+            object = object.child_by_field_name("object")?;
+        }
+        let root = match object.kind() {
+            "identifier" => object,
+            "field_access" => object.child_by_field_name("field")?,
+            _ => return None,
+        };
+        let name = root.utf8_text(ctx.code.as_bytes()).ok()?.to_string();
+        let type_name = CollectionHandler::declared_type(call, &name, ctx.code)
+            .map(|(type_name, _)| type_name)
+            .or_else(|| ctx.symbol_table?.fields.get(&(ctx.current_class.to_string(), name.clone())).map(|b| b.type_name.clone()))?;
+        let base = CollectionHandler::base_type(&type_name);
+        REDIS_CLIENT_TYPES.contains(&base).then(|| (name, base.to_string()))
+    }
+
+    /// 所在方法 (或构造器)
+    fn enclosing_method(node: tree_sitter::Node) -> Option<tree_sitter::Node> {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            if matches!(parent.kind(), "method_declaration" | "constructor_declaration") {
+                return Some(parent);
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// 调用位于批量提交回调 (`executePipelined(cb -> ...)`) 内
+    fn in_pipeline(node: tree_sitter::Node, code: &str) -> bool {
+        let mut current = node;
+        while let Some(parent) = current.parent() {
+            if matches!(parent.kind(), "method_declaration" | "constructor_declaration") {
+                return false;
+            }
+            if parent.kind() == "method_invocation" {
+                let name = parent.child_by_field_name("name").and_then(|n| n.utf8_text(code.as_bytes()).ok());
+                if name.is_some_and(|n| REDIS_PIPELINE_METHODS.contains(&n)) {
+                    return true;
+                }
+            }
+            current = parent;
+        }
+        false
+    }
+
+    /// 首个实参 (key) 的源码文本
+    fn key_arg(call: tree_sitter::Node, code: &str) -> Option<String> {
+        let key = call.child_by_field_name("arguments")?.named_child(0)?;
+        key.utf8_text(code.as_bytes()).ok().map(str::to_string)
+    }
+
+    /// 同一方法内对同一 key 的过期设置
+    fn expire_in_method(call: tree_sitter::Node, ctx: &RuleContext) -> bool {
+        let Some(body) = Self::enclosing_method(call).and_then(|m| m.child_by_field_name("body")) else { return false };
+        let key = Self::key_arg(call, ctx.code);
+        ListenerHandler::invocations(body, ctx.code).into_iter().any(|(_, name, _, node)| {
+            REDIS_EXPIRE_METHODS.contains(&name.as_str())
+                && Self::key_arg(node, ctx.code) == key
+                && Self::redis_receiver(node, ctx).is_some()
+        })
+    }
+
+    /// 调用方法是 Controller 方法，或经调用图可从 Controller 到达: 调用链
+    fn request_chain(call: tree_sitter::Node, ctx: &RuleContext) -> Option<Vec<String>> {
+        let caller = Self::enclosing_method(call)?.child_by_field_name("name")?.utf8_text(ctx.code.as_bytes()).ok()?;
+        if ThreadHandler::class_layer(call, ctx) == crate::symbol_table::LayerType::Controller {
+            return Some(vec![format!("{}.{caller}", ctx.current_class)]);
+        }
+        let cg = ctx.call_graph?;
+        let path = cg.find_methods(ctx.current_class, caller).iter()
+            .find_map(|start| cg.trace_from_layer(start, crate::taint::LayerType::Controller, REDIS_REQUEST_DEPTH)
+                .into_iter().min_by_key(|path| path.len()))?;
+        Some(path.iter().map(|sig| format!("{}.{}", sig.simple_class_name(), sig.name)).collect())
+    }
+}
+
+impl RuleHandler for RedisHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let capture = |name: &str| {
+            let idx = query.capture_index_for_name(name)?;
+            m.captures.iter().find(|c| c.index == idx).map(|c| c.node)
+        };
+        let text = |n: tree_sitter::Node| n.utf8_text(ctx.code.as_bytes()).unwrap_or("").to_string();
+
+        let (node, context, confidence) = match self.check {
+            RedisCheck::SetnxNoExpire => {
+                let (call, method, args) = (capture("call")?, text(capture("method")?), capture("args")?);
+                // setIfAbsent(key, value, timeout[, unit]) 原子设置过期
+                if method == "setIfAbsent" && args.named_child_count() >= 3 {
+                    return None;
+                }
+                let (receiver, _) = Self::redis_receiver(call, ctx)?;
+                if Self::expire_in_method(call, ctx) {
+                    (call, format!("{receiver}.{method}() 后单独 expire()：两条命令非原子，进程在中间崩溃时锁永不过期"), Confidence::Medium)
+                } else {
+                    (call, format!("{receiver}.{method}() 未设置过期时间 → SET NX EX / setIfAbsent(key, value, timeout)"), Confidence::High)
+                }
+            }
+            RedisCheck::SharedJedis => {
+                let (decl, name) = (capture("decl")?, text(capture("name")?));
+                let is_static = decl.children(&mut decl.walk())
+                    .find(|n| n.kind() == "modifiers")
+                    .is_some_and(|mods| text(mods).split_whitespace().any(|w| w == "static"));
+                let class = decl.parent().filter(|b| b.kind() == "class_body").and_then(|b| b.parent());
+                let shared_bean = class.is_some_and(|c| ListenerHandler::annotations(c, ctx.code).iter()
+                    .any(|(ann, _)| SHARED_BEAN_ANNOTATIONS.contains(&ann.as_str())));
+                let concurrent = CONCURRENCY_MARKERS.iter().any(|marker| ctx.code.contains(marker));
+                let (owner, confidence) = if is_static {
+                    ("static 字段", Confidence::High)
+                } else if shared_bean {
+                    ("单例 Bean 字段", Confidence::High)
+                } else if concurrent {
+                    ("多线程访问的类字段", Confidence::High)
+                } else {
+                    ("字段", Confidence::Medium)
+                };
+                (decl, format!("Jedis {name} ({owner}) 被并发调用共享 → JedisPool.getResource() / JedisPooled"), confidence)
+            }
+            RedisCheck::FullScanInRequest => {
+                let (call, method) = (capture("call")?, text(capture("method")?));
+                let (_, type_name) = Self::redis_receiver(call, ctx)?;
+                let object = text(call.child_by_field_name("object")?);
+                // opsForHash().keys(key) / HashOperations.keys 是 HKEYS，只遍历单个 hash
+                let hash_ops = ChainInspector::new(ctx.code).chain(call).has_any(&["opsForHash", "boundHashOps"]);
+                if method == "keys" && (type_name == "HashOperations" || hash_ops) {
+                    return None;
+                }
+                let chain = Self::request_chain(call, ctx)?;
+                let confidence = if method == "keys" { Confidence::High } else { Confidence::Medium };
+                let command = if method == "keys" { "KEYS" } else { "SMEMBERS" };
+                (call, format!("{object}.{method}() ({command}) [调用链: {}] → SCAN / SSCAN", chain.join(" → ")), confidence)
+            }
+            RedisCheck::OpInLoop => {
+                let (call, method) = (capture("call")?, text(capture("method")?));
+                // opsForValue() / boundHashOps(key) 只是取操作接口
+                if method.starts_with("opsFor") || method.starts_with("bound") || REDIS_PIPELINE_METHODS.contains(&method.as_str()) {
+                    return None;
+                }
+                let loop_kind = CollectionHandler::enclosing_loop(call)?;
+                if Self::in_pipeline(call, ctx.code) {
+                    return None;
+                }
+                Self::redis_receiver(call, ctx)?;
+                let object = text(call.child_by_field_name("object")?);
+                (call, format!("{loop_kind} 循环内 {object}.{method}() → multiGet / executePipelined"), Confidence::High)
+            }
+        };
+
+        let span = Span::from_node(&node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file: ctx.file_path.file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: Some(context),
+            confidence: Some(confidence),
+        })
+    }
+}
+
+// ============================================================================
+// 处理器工厂
+// ============================================================================
+
+/// 根据规则 ID 创建对应的处理器
+pub fn create_handler(rule_id: &str) -> Box<dyn RuleHandler> {
+    // ====== 成对调用 (ThreadLocal / MDC / Lock / Semaphore / Connection / CountDownLatch) ======
+    if let Some(pair) = paired_call(rule_id) {
+        return Box::new(PairedCallHandler { pair });
+    }
+
+    match rule_id {
+        // ====== N+1 检测 ======
+        "N_PLUS_ONE" | "N_PLUS_ONE_WHILE" | "N_PLUS_ONE_FOREACH" => {
+            Box::new(NPlusOneHandler)
+        }
+
+        // ====== 嵌套循环检测 ======
+        "NESTED_LOOP" | "NESTED_LOOP_MIXED" => {
+            Box::new(NestedLoopHandler)
+        }
+
+        // ====== 修饰符检查 ======
+        "SYNC_METHOD" => {
+            Box::new(ModifierCheckHandler {
+                mods_capture: "mods",
+                target_capture: "mods", // SYNC_METHOD query only has @mods
+                required_modifier: "synchronized",
+            })
+        }
+        "VOLATILE_ARRAY" => {
+            Box::new(ModifierCheckHandler {
+                mods_capture: "mods",
+                target_capture: "field",
+                required_modifier: "volatile",
+            })
+        }
+        "STATIC_COLLECTION" | "RANDOM_SHARED" => {
+            Box::new(ModifierCheckHandler {
+                mods_capture: "mods",
+                target_capture: "field",
+                required_modifier: "static",
+            })
+        }
+
+        // ====== 流资源泄漏 ======
+        "STREAM_RESOURCE_LEAK" => {
+            Box::new(StreamResourceLeakHandler)
+        }
+
+        // ====== 无超时阻塞调用 ======
+        "FUTURE_GET_NO_TIMEOUT" => {
+            Box::new(FutureGetHandler { completable: false })
+        }
+        "COMPLETABLE_GET_NO_TIMEOUT" => {
+            Box::new(FutureGetHandler { completable: true })
+        }
+        "AWAIT_NO_TIMEOUT" | "COMPLETABLE_JOIN" | "EMITTER_UNBOUNDED" => {
+            Box::new(EmptyArgsHandler {
+                call_capture: "call",
+                args_capture: "args",
+            })
+        }
+
+        // ====== SQL 字符串检测 ======
+        "SELECT_STAR" | "LIKE_LEADING_WILDCARD" => {
+            Box::new(StringContentHandler {
+                string_capture: "str",
+                max_context_len: 50,
+            })
+        }
+
+        // ====== 响应式编程规则 (带上下文) ======
+        "FLUX_BLOCK" | "FLUX_COLLECT_LIST" | "PARALLEL_NO_RUN_ON" => {
+            Box::new(MethodCallWithContextHandler {
+                call_capture: "call",
+            })
+        }
+
+        // ====== subscribe 参数检查 ======
+        "SUBSCRIBE_NO_ERROR" => {
+            Box::new(SubscribeArgCountHandler)
         }
 
         // ====== 空 catch 块 ======
@@ -1035,9 +4216,176 @@ pub fn create_handler(rule_id: &str) -> Box<dyn RuleHandler> {
             Box::new(EmptyCatchHandler)
         }
 
-        // ====== Lock 不在 finally ======
-        "LOCK_METHOD_CALL" => {
-            Box::new(LockNoFinallyHandler)
+        // ====== Filter / Interceptor 每请求开销 ======
+        "FILTER_DB_CALL" => {
+            Box::new(PerRequestSinkHandler { sink: RequestSink::Database })
+        }
+        "FILTER_HTTP_CALL" => {
+            Box::new(PerRequestSinkHandler { sink: RequestSink::Http })
+        }
+
+        // ====== Kafka / RabbitMQ 监听器 ======
+        "LISTENER_BLOCKING_CALL" => {
+            Box::new(ListenerHandler { check: ListenerCheck::BlockingCall })
+        }
+        "LISTENER_AUTO_ACK_SLOW" => {
+            Box::new(ListenerHandler { check: ListenerCheck::AutoAckSlow })
+        }
+        "LISTENER_UNBOUNDED_BUFFER" => {
+            Box::new(ListenerHandler { check: ListenerCheck::UnboundedBuffer })
+        }
+        "LISTENER_TRANSACTIONAL" => {
+            Box::new(ListenerHandler { check: ListenerCheck::Transactional })
+        }
+
+        // ====== 正则 ======
+        "REGEX_REDOS" => {
+            Box::new(RegexHandler { check: RegexCheck::Redos })
+        }
+        "PATTERN_COMPILE_HOT_PATH" => {
+            Box::new(RegexHandler { check: RegexCheck::CompileHotPath })
+        }
+
+        // ====== 集合误用 ======
+        "LIST_CONTAINS_IN_LOOP" => {
+            Box::new(CollectionHandler { check: CollectionCheck::ListContainsInLoop })
+        }
+        "COLLECTION_NO_CAPACITY" => {
+            Box::new(CollectionHandler { check: CollectionCheck::NoInitialCapacity })
+        }
+        "LINKEDLIST_RANDOM_ACCESS" => {
+            Box::new(CollectionHandler { check: CollectionCheck::LinkedListRandomAccess })
+        }
+        "SYNCHRONIZED_MAP" => {
+            Box::new(CollectionHandler { check: CollectionCheck::SynchronizedMap })
+        }
+
+        // ====== 序列化 ======
+        "SERIALIZER_PER_CALL" => {
+            Box::new(SerializationHandler { check: SerializationCheck::SerializerPerCall })
+        }
+        "SERIALIZE_IN_LOOP" => {
+            Box::new(SerializationHandler { check: SerializationCheck::SerializeInLoop })
+        }
+        "JPA_BIDIRECTIONAL_JSON_CYCLE" => {
+            Box::new(SerializationHandler { check: SerializationCheck::BidirectionalJsonCycle })
+        }
+
+        // ====== 日期时间 ======
+        "DEFAULT_TIMEZONE" => {
+            Box::new(DateTimeHandler { check: DateTimeCheck::DefaultTimeZone })
+        }
+        "DATE_IN_LOOP" => {
+            Box::new(DateTimeHandler { check: DateTimeCheck::DateInLoop })
+        }
+        "CALENDAR_PER_CALL" => {
+            Box::new(DateTimeHandler { check: DateTimeCheck::CalendarPerCall })
+        }
+        "DATE_FORMATTER_IN_LOOP" => {
+            Box::new(DateTimeHandler { check: DateTimeCheck::FormatterInLoop })
+        }
+
+        // ====== 线程创建 ======
+        "THREAD_IN_REQUEST_SCOPE" => {
+            Box::new(ThreadHandler { check: ThreadCheck::RequestScoped })
+        }
+        "THREAD_NO_NAME" => {
+            Box::new(ThreadHandler { check: ThreadCheck::Unnamed })
+        }
+        "SCHEDULED_POOL_ZERO_CORE" => {
+            Box::new(ThreadHandler { check: ThreadCheck::ScheduledZeroCore })
+        }
+
+        // ====== 启动耗时 ======
+        "COMPONENT_SCAN_BROAD" => {
+            Box::new(StartupHandler { check: StartupCheck::BroadComponentScan })
+        }
+        "POSTCONSTRUCT_REMOTE_CALL" => {
+            Box::new(StartupHandler { check: StartupCheck::PostConstructRemoteCall })
+        }
+        "EAGER_HEAVY_BEAN" => {
+            Box::new(StartupHandler { check: StartupCheck::EagerHeavyBean })
+        }
+
+        // ====== SQL 字符串拼接 ======
+        "SQL_STRING_CONCAT" => {
+            Box::new(SqlConcatHandler)
+        }
+
+        // ====== HTTP 客户端超时 ======
+        "HTTP_CLIENT_TIMEOUT" => {
+            Box::new(HttpClientTimeoutHandler)
+        }
+
+        // ====== 缓存过期 / 容量策略 ======
+        "CACHE_NO_EXPIRE" => {
+            Box::new(CacheExpiryHandler)
+        }
+
+        // ====== 列表接口未分页 ======
+        "UNBOUNDED_QUERY_RESULT" => {
+            Box::new(UnboundedQueryHandler)
+        }
+
+        // ====== Redis ======
+        "REDIS_SETNX_NO_EXPIRE" => {
+            Box::new(RedisHandler { check: RedisCheck::SetnxNoExpire })
+        }
+        "REDIS_JEDIS_SHARED" => {
+            Box::new(RedisHandler { check: RedisCheck::SharedJedis })
+        }
+        "REDIS_FULL_SCAN_IN_REQUEST" => {
+            Box::new(RedisHandler { check: RedisCheck::FullScanInRequest })
+        }
+        "REDIS_OP_IN_LOOP" => {
+            Box::new(RedisHandler { check: RedisCheck::OpInLoop })
+        }
+
+        // ====== 重试 / 熔断 ======
+        "RETRY_NO_BACKOFF" => {
+            Box::new(RetryHandler { check: RetryCheck::NoBackoff })
+        }
+        "RETRY_EXCESSIVE_ATTEMPTS" => {
+            Box::new(RetryHandler { check: RetryCheck::ExcessiveAttempts })
+        }
+        "RETRY_NON_IDEMPOTENT" => {
+            Box::new(RetryHandler { check: RetryCheck::NonIdempotent })
+        }
+
+        // ====== Optional / Stream ======
+        "OPTIONAL_GET_UNCHECKED" => {
+            Box::new(StreamHandler { check: StreamCheck::OptionalGetUnchecked })
+        }
+        "STREAM_COUNT_FOR_EXISTENCE" => {
+            Box::new(StreamHandler { check: StreamCheck::CountForExistence })
+        }
+        "STREAM_COLLECT_SIZE" => {
+            Box::new(StreamHandler { check: StreamCheck::CollectThenSize })
+        }
+        "STREAM_REPEATED" => {
+            Box::new(StreamHandler { check: StreamCheck::RepeatedStream })
+        }
+
+        // ====== Java 17/21 ======
+        "RECORD_MUTABLE_COMPONENT" => {
+            Box::new(ModernJavaHandler { check: ModernJavaCheck::RecordMutableComponent })
+        }
+        "SWITCH_PATTERN_NO_DEFAULT" => {
+            Box::new(ModernJavaHandler { check: ModernJavaCheck::PatternSwitchNoDefault })
+        }
+
+        // ====== 测试专用规则 ======
+        "TEST_THREAD_SLEEP" => {
+            Box::new(TestCodeHandler { check: TestCodeCheck::ThreadSleep })
+        }
+        "TEST_FIXED_PORT" => {
+            Box::new(TestCodeHandler { check: TestCodeCheck::FixedPort })
+        }
+        "TEST_SHARED_STATIC_STATE" => {
+            Box::new(TestCodeHandler { check: TestCodeCheck::SharedStaticState })
+        }
+        "TEST_CONTAINER_LEAK" => {
+            Box::new(TestCodeHandler { check: TestCodeCheck::ContainerLeak })
         }
 
         // ====== 大数组分配 ======
@@ -1048,13 +4396,20 @@ pub fn create_handler(rule_id: &str) -> Box<dyn RuleHandler> {
         }
 
         // ====== 简单方法级规则 (匹配 @method) ======
-        "FINALIZE_OVERRIDE" | "CACHEABLE_NO_KEY" | "TRANSACTIONAL_REQUIRES_NEW"
-        | "TRANSACTION_SELF_CALL" | "ASYNC_DEFAULT_POOL" | "SCHEDULED_FIXED_RATE" => {
+        "FINALIZE_OVERRIDE" | "CACHEABLE_NO_KEY" | "TRANSACTION_SELF_CALL" | "ASYNC_DEFAULT_POOL" => {
             Box::new(SimpleMatchHandler {
                 line_capture: "method",
             })
         }
 
+        // ====== 注解参数取值 (v9.6) ======
+        "SCHEDULED_FIXED_RATE" => {
+            Box::new(ScheduledRateHandler)
+        }
+        "TRANSACTIONAL_REQUIRES_NEW" => {
+            Box::new(TransactionalPropagationHandler)
+        }
+
         // ====== @Autowired 字段注入 (匹配 @field) ======
         "AUTOWIRED_FIELD" => {
             Box::new(SimpleMatchHandler {
@@ -1071,10 +4426,9 @@ pub fn create_handler(rule_id: &str) -> Box<dyn RuleHandler> {
         }
 
         // ====== 简单方法调用规则 (匹配 @call) ======
-        "STRING_INTERN" | "UNBOUNDED_POOL" | "SINKS_MANY" | "CACHE_NO_EXPIRE"
+        "STRING_INTERN" | "UNBOUNDED_POOL" | "SINKS_MANY"
         | "DATASOURCE_NO_POOL" | "LOG_STRING_CONCAT" | "GRAALVM_CLASS_FORNAME"
-        | "GRAALVM_METHOD_INVOKE" | "GRAALVM_PROXY" | "SYSTEM_EXIT" | "RUNTIME_EXEC"
-        | "HTTP_CLIENT_TIMEOUT" => {
+        | "GRAALVM_METHOD_INVOKE" | "GRAALVM_PROXY" | "SYSTEM_EXIT" | "RUNTIME_EXEC" => {
             Box::new(SimpleMatchHandler {
                 line_capture: "call",
             })
@@ -1135,14 +4489,17 @@ impl RuleHandler for FallbackHandler {
             if let Some(idx) = query.capture_index_for_name(name) {
                 for capture in m.captures {
                     if capture.index == idx {
-                        let line = capture.node.start_position().row + 1;
+                        let span = Span::from_node(&capture.node);
                         return Some(Issue {
                             id: rule_id.to_string(),
                             severity,
                             file: ctx.file_path.file_name()
                                 .map(|n| n.to_string_lossy().to_string())
                                 .unwrap_or_default(),
-                            line,
+                            line: span.line,
+                            column: span.column,
+                            end_line: span.end_line,
+                            end_column: span.end_column,
                             description: description.to_string(),
                             context: None,
                             confidence: None, // Fallback handler doesn't use confidence
@@ -1195,7 +4552,7 @@ mod tests {
     }
 
     // ========================================================================
-    // Unit Tests for ThreadLocalLeakHandler
+    // Unit Tests for PairedCallHandler (THREADLOCAL_PAIR)
     // ========================================================================
 
     #[test]
@@ -1219,10 +4576,10 @@ mod tests {
         let method = find_method_node(&tree).unwrap();
         
         // Should detect remove in finally
-        assert!(ThreadLocalLeakHandler::has_remove_in_finally(method, "context", code.as_bytes()));
+        assert!(THREADLOCAL_PAIR.has_release_in_finally(method, "context", None, code.as_bytes()));
         
         // Severity should be None (safe)
-        assert!(ThreadLocalLeakHandler::determine_severity(method, "context", code.as_bytes()).is_none());
+        assert!(THREADLOCAL_PAIR.determine_severity(method, "context", None, code.as_bytes()).is_none());
     }
 
     #[test]
@@ -1243,14 +4600,14 @@ mod tests {
         let method = find_method_node(&tree).unwrap();
         
         // Should NOT detect remove in finally
-        assert!(!ThreadLocalLeakHandler::has_remove_in_finally(method, "context", code.as_bytes()));
+        assert!(!THREADLOCAL_PAIR.has_release_in_finally(method, "context", None, code.as_bytes()));
         
         // Should detect remove anywhere
-        assert!(ThreadLocalLeakHandler::has_remove_anywhere(method, "context", code.as_bytes()));
+        assert!(THREADLOCAL_PAIR.has_release_anywhere(method, "context", None, code.as_bytes()));
         
         // Severity should be P1
         assert_eq!(
-            ThreadLocalLeakHandler::determine_severity(method, "context", code.as_bytes()),
+            THREADLOCAL_PAIR.determine_severity(method, "context", None, code.as_bytes()),
             Some(Severity::P1)
         );
     }
@@ -1272,14 +4629,14 @@ mod tests {
         let method = find_method_node(&tree).unwrap();
         
         // Should NOT detect remove in finally
-        assert!(!ThreadLocalLeakHandler::has_remove_in_finally(method, "context", code.as_bytes()));
+        assert!(!THREADLOCAL_PAIR.has_release_in_finally(method, "context", None, code.as_bytes()));
         
         // Should NOT detect remove anywhere
-        assert!(!ThreadLocalLeakHandler::has_remove_anywhere(method, "context", code.as_bytes()));
+        assert!(!THREADLOCAL_PAIR.has_release_anywhere(method, "context", None, code.as_bytes()));
         
         // Severity should be P0
         assert_eq!(
-            ThreadLocalLeakHandler::determine_severity(method, "context", code.as_bytes()),
+            THREADLOCAL_PAIR.determine_severity(method, "context", None, code.as_bytes()),
             Some(Severity::P0)
         );
     }
@@ -1309,10 +4666,10 @@ mod tests {
         let method = find_method_node(&tree).unwrap();
         
         // Should detect remove in finally (outer)
-        assert!(ThreadLocalLeakHandler::has_remove_in_finally(method, "context", code.as_bytes()));
+        assert!(THREADLOCAL_PAIR.has_release_in_finally(method, "context", None, code.as_bytes()));
         
         // Severity should be None (safe)
-        assert!(ThreadLocalLeakHandler::determine_severity(method, "context", code.as_bytes()).is_none());
+        assert!(THREADLOCAL_PAIR.determine_severity(method, "context", None, code.as_bytes()).is_none());
     }
 
     #[test]
@@ -1337,10 +4694,10 @@ mod tests {
         let method = find_method_node(&tree).unwrap();
         
         // Should NOT detect remove for "context" (only "other" is removed)
-        assert!(!ThreadLocalLeakHandler::has_remove_in_finally(method, "context", code.as_bytes()));
+        assert!(!THREADLOCAL_PAIR.has_release_in_finally(method, "context", None, code.as_bytes()));
         
         // Should detect remove for "other"
-        assert!(ThreadLocalLeakHandler::has_remove_in_finally(method, "other", code.as_bytes()));
+        assert!(THREADLOCAL_PAIR.has_release_in_finally(method, "other", None, code.as_bytes()));
     }
 
     // ========================================================================
@@ -1403,14 +4760,14 @@ mod tests {
             
             // Property 1: has_remove_in_finally should return true
             prop_assert!(
-                ThreadLocalLeakHandler::has_remove_in_finally(method, &var_name, code.as_bytes()),
+                THREADLOCAL_PAIR.has_release_in_finally(method, &var_name, None, code.as_bytes()),
                 "Should detect remove() in finally for variable '{}'",
                 var_name
             );
             
             // Property 2: determine_severity should return None (safe)
             prop_assert!(
-                ThreadLocalLeakHandler::determine_severity(method, &var_name, code.as_bytes()).is_none(),
+                THREADLOCAL_PAIR.determine_severity(method, &var_name, None, code.as_bytes()).is_none(),
                 "Should return None severity (safe) when remove() is in finally for variable '{}'",
                 var_name
             );
@@ -1455,21 +4812,21 @@ mod tests {
             
             // Property 1: has_remove_in_finally for var1 should return false
             prop_assert!(
-                !ThreadLocalLeakHandler::has_remove_in_finally(method, &var1, code.as_bytes()),
+                !THREADLOCAL_PAIR.has_release_in_finally(method, &var1, None, code.as_bytes()),
                 "Should NOT detect remove() in finally for variable '{}' when only '{}' is removed",
                 var1, var2
             );
             
             // Property 2: has_remove_in_finally for var2 should return true
             prop_assert!(
-                ThreadLocalLeakHandler::has_remove_in_finally(method, &var2, code.as_bytes()),
+                THREADLOCAL_PAIR.has_release_in_finally(method, &var2, None, code.as_bytes()),
                 "Should detect remove() in finally for variable '{}'",
                 var2
             );
             
             // Property 3: determine_severity for var1 should return P0 (no remove at all)
             prop_assert_eq!(
-                ThreadLocalLeakHandler::determine_severity(method, &var1, code.as_bytes()),
+                THREADLOCAL_PAIR.determine_severity(method, &var1, None, code.as_bytes()),
                 Some(Severity::P0),
                 "Should return P0 severity for variable '{}' with no remove()",
                 var1
@@ -1506,21 +4863,21 @@ mod tests {
             
             // Property 1: has_remove_in_finally should return false
             prop_assert!(
-                !ThreadLocalLeakHandler::has_remove_in_finally(method, &var_name, code.as_bytes()),
+                !THREADLOCAL_PAIR.has_release_in_finally(method, &var_name, None, code.as_bytes()),
                 "Should NOT detect remove() in finally for variable '{}'",
                 var_name
             );
             
             // Property 2: has_remove_anywhere should return false
             prop_assert!(
-                !ThreadLocalLeakHandler::has_remove_anywhere(method, &var_name, code.as_bytes()),
+                !THREADLOCAL_PAIR.has_release_anywhere(method, &var_name, None, code.as_bytes()),
                 "Should NOT detect remove() anywhere for variable '{}'",
                 var_name
             );
             
             // Property 3: determine_severity should return P0
             prop_assert_eq!(
-                ThreadLocalLeakHandler::determine_severity(method, &var_name, code.as_bytes()),
+                THREADLOCAL_PAIR.determine_severity(method, &var_name, None, code.as_bytes()),
                 Some(Severity::P0),
                 "Should return P0 severity when no remove() exists for variable '{}'",
                 var_name
@@ -1557,21 +4914,21 @@ mod tests {
             
             // Property 1: has_remove_in_finally should return false
             prop_assert!(
-                !ThreadLocalLeakHandler::has_remove_in_finally(method, &var_name, code.as_bytes()),
+                !THREADLOCAL_PAIR.has_release_in_finally(method, &var_name, None, code.as_bytes()),
                 "Should NOT detect remove() in finally for variable '{}'",
                 var_name
             );
             
             // Property 2: has_remove_anywhere should return true
             prop_assert!(
-                ThreadLocalLeakHandler::has_remove_anywhere(method, &var_name, code.as_bytes()),
+                THREADLOCAL_PAIR.has_release_anywhere(method, &var_name, None, code.as_bytes()),
                 "Should detect remove() somewhere for variable '{}'",
                 var_name
             );
             
             // Property 3: determine_severity should return P1
             prop_assert_eq!(
-                ThreadLocalLeakHandler::determine_severity(method, &var_name, code.as_bytes()),
+                THREADLOCAL_PAIR.determine_severity(method, &var_name, None, code.as_bytes()),
                 Some(Severity::P1),
                 "Should return P1 severity when remove() exists but not in finally for variable '{}'",
                 var_name
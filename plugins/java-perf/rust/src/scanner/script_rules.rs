@@ -0,0 +1,388 @@
+// ============================================================================
+// 脚本规则 (v9.6) - WASM 规则处理器
+// ============================================================================
+//
+// 无法重新编译二进制的团队可以用 WASM 模块发布规则：
+// Tree-sitter 查询负责匹配，WASM 模块负责判定是否报告。
+//
+// 规则目录 (默认 `<项目>/.java-perf/rules/`) 中每个 `*.yaml` 定义一条规则:
+//
+//   id: CORP_DAO_IN_LOOP
+//   severity: P0
+//   description: 循环内调用 CorpDao
+//   query: |
+//     (for_statement body: (block (expression_statement (method_invocation) @call)))
+//   wasm: corp_dao.wasm        # 相对规则文件，必须位于规则目录内
+//   fuel: 1000000              # 可选，每次调用的指令预算 (上限 MAX_FUEL)
+//   report_capture: call       # 可选，报告位置使用的 capture (默认第一个)
+//
+// WASM ABI:
+//   导出 `memory`、`alloc(len: i32) -> i32`、`check(ptr: i32, len: i32) -> i32`
+//   输入: UTF-8 JSON `{"rule_id": "...", "file": "...", "captures": {"call": "..."}}`
+//   返回: 0 = 不报告, 1 = 按声明级别报告, 2 = 升级为 P0 报告
+//
+// 沙箱:
+//   - 不提供任何 import (模块声明 import 时加载失败)，脚本无法访问文件/网络
+//   - fuel 计量限制每次调用的执行量，超限视为不报告
+//   - 线性内存上限 16 MiB
+//
+// ============================================================================
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use serde_json::json;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use tree_sitter::{Query, QueryMatch};
+use wasmi::{Config, Engine, Linker, Module, Store, StoreLimits, StoreLimitsBuilder};
+
+use super::rule_handlers::{RuleContext, RuleHandler};
+use super::{Issue, Severity, Span};
+
+/// 默认每次调用的 fuel 预算
+const DEFAULT_FUEL: u64 = 1_000_000;
+
+/// 规则文件可声明的 fuel 上限，超出时截断
+const MAX_FUEL: u64 = 100_000_000;
+
+/// 线性内存上限
+const MAX_MEMORY_BYTES: usize = 16 * 1024 * 1024;
+
+/// 规则文件定义
+#[derive(Debug, Deserialize)]
+struct ScriptRuleFile {
+    id: String,
+    severity: Severity,
+    description: String,
+    query: String,
+    wasm: PathBuf,
+    #[serde(default)]
+    fuel: Option<u64>,
+    #[serde(default)]
+    report_capture: Option<String>,
+}
+
+/// 加载后的脚本规则
+pub struct ScriptRule {
+    pub id: String,
+    pub severity: Severity,
+    pub description: String,
+    pub query: String,
+    pub handler: ScriptedHandler,
+}
+
+/// 加载目录中的所有脚本规则 (按文件名排序)
+pub fn load_dir(dir: &Path) -> Result<Vec<ScriptRule>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to read rules dir {}", dir.display()))?
+        .filter_map(|e| e.ok().map(|e| e.path()))
+        .filter(|p| matches!(p.extension().and_then(|e| e.to_str()), Some("yaml" | "yml")))
+        .collect();
+    files.sort();
+
+    files.iter().map(|f| load_rule(f)).collect()
+}
+
+fn load_rule(rule_file: &Path) -> Result<ScriptRule> {
+    let content = std::fs::read_to_string(rule_file)
+        .with_context(|| format!("Failed to read {}", rule_file.display()))?;
+    let def: ScriptRuleFile = serde_yaml::from_str(&content)
+        .with_context(|| format!("Invalid rule file {}", rule_file.display()))?;
+
+    let wasm_path = resolve_wasm(rule_file, &def.wasm)?;
+    let wasm = std::fs::read(&wasm_path)
+        .with_context(|| format!("Failed to read {}", wasm_path.display()))?;
+    let fuel = def.fuel.unwrap_or(DEFAULT_FUEL).min(MAX_FUEL);
+    let handler = ScriptedHandler::new(&wasm, fuel, def.report_capture)
+        .with_context(|| format!("Invalid WASM module for rule '{}'", def.id))?;
+
+    Ok(ScriptRule {
+        id: def.id,
+        severity: def.severity,
+        description: def.description,
+        query: def.query,
+        handler,
+    })
+}
+
+/// 解析规则声明的 WASM 路径：规范化后必须仍位于规则目录内
+fn resolve_wasm(rule_file: &Path, wasm: &Path) -> Result<PathBuf> {
+    if wasm.is_absolute() {
+        return Err(anyhow!("WASM path {} must be relative to the rules dir", wasm.display()));
+    }
+    let dir = rule_file.parent().unwrap_or(Path::new("."));
+    let root = dir
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve rules dir {}", dir.display()))?;
+    let joined = dir.join(wasm);
+    let path = joined
+        .canonicalize()
+        .with_context(|| format!("Failed to read {}", joined.display()))?;
+    if !path.starts_with(&root) {
+        return Err(anyhow!("WASM path {} escapes the rules dir", wasm.display()));
+    }
+    Ok(path)
+}
+
+/// WASM 规则处理器
+///
+/// 每次匹配实例化一个新的 Store，调用之间不共享状态。
+pub struct ScriptedHandler {
+    engine: Engine,
+    module: Module,
+    fuel: u64,
+    report_capture: Option<String>,
+}
+
+impl ScriptedHandler {
+    pub fn new(wasm: &[u8], fuel: u64, report_capture: Option<String>) -> Result<Self> {
+        let mut config = Config::default();
+        config.consume_fuel(true);
+        let engine = Engine::new(&config);
+        let module = Module::new(&engine, wasm).map_err(|e| anyhow!("{e}"))?;
+
+        // 沙箱: 不允许任何 import
+        if let Some(import) = module.imports().next() {
+            return Err(anyhow!(
+                "scripted rules must not import host functions (found {}::{})",
+                import.module(),
+                import.name()
+            ));
+        }
+
+        Ok(Self { engine, module, fuel, report_capture })
+    }
+
+    /// 执行 check，返回判定结果 (0/1/2)
+    fn check(&self, input: &[u8]) -> Result<i32> {
+        let limits = StoreLimitsBuilder::new().memory_size(MAX_MEMORY_BYTES).build();
+        let mut store: Store<StoreLimits> = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        store.set_fuel(self.fuel).map_err(|e| anyhow!("{e}"))?;
+
+        let linker = Linker::<StoreLimits>::new(&self.engine);
+        let instance = linker
+            .instantiate(&mut store, &self.module)
+            .and_then(|pre| pre.start(&mut store))
+            .map_err(|e| anyhow!("{e}"))?;
+
+        let memory = instance.get_memory(&store, "memory").ok_or_else(|| anyhow!("missing export 'memory'"))?;
+        let alloc = instance.get_typed_func::<i32, i32>(&store, "alloc").map_err(|e| anyhow!("{e}"))?;
+        let check = instance.get_typed_func::<(i32, i32), i32>(&store, "check").map_err(|e| anyhow!("{e}"))?;
+
+        let len = i32::try_from(input.len())?;
+        let ptr = alloc.call(&mut store, len).map_err(|e| anyhow!("{e}"))?;
+        memory
+            .write(&mut store, usize::try_from(ptr)?, input)
+            .map_err(|e| anyhow!("{e}"))?;
+        check.call(&mut store, (ptr, len)).map_err(|e| anyhow!("{e}"))
+    }
+}
+
+impl RuleHandler for ScriptedHandler {
+    fn handle(
+        &self,
+        query: &Query,
+        m: &QueryMatch,
+        rule_id: &str,
+        severity: Severity,
+        description: &str,
+        ctx: &RuleContext,
+    ) -> Option<Issue> {
+        let names = query.capture_names();
+        let mut captures: BTreeMap<&str, &str> = BTreeMap::new();
+        for capture in m.captures {
+            let text = capture.node.utf8_text(ctx.code.as_bytes()).unwrap_or("");
+            captures.entry(names[capture.index as usize]).or_insert(text);
+        }
+
+        let report_node = match &self.report_capture {
+            Some(name) => {
+                let idx = query.capture_index_for_name(name)?;
+                m.captures.iter().find(|c| c.index == idx)?.node
+            }
+            None => m.captures.first()?.node,
+        };
+
+        let file = ctx.file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let input = json!({ "rule_id": rule_id, "file": file, "captures": captures }).to_string();
+
+        let severity = match self.check(input.as_bytes()) {
+            Ok(0) => return None,
+            Ok(2) => Severity::P0,
+            Ok(_) => severity,
+            Err(e) => {
+                // 脚本错误 (含 fuel 耗尽) 不中断扫描
+                tracing::warn!("scripted rule '{}' failed on {}: {}", rule_id, file, e);
+                return None;
+            }
+        };
+
+        let span = Span::from_node(&report_node);
+        Some(Issue {
+            id: rule_id.to_string(),
+            severity,
+            file,
+            line: span.line,
+            column: span.column,
+            end_line: span.end_line,
+            end_column: span.end_column,
+            description: description.to_string(),
+            context: captures.values().next().map(|s| s.chars().take(60).collect()),
+            confidence: None, // Scripted rules decide on their own
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+    use crate::scanner::CodeAnalyzer;
+
+    /// alloc 固定返回 1024；check 返回 `result`
+    fn module_returning(result: i32) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 1024)
+                (func (export "check") (param i32 i32) (result i32) i32.const {result}))"#
+        ))
+        .unwrap()
+    }
+
+    /// check 只在输入长度超过阈值时报告
+    fn module_len_over(threshold: i32) -> Vec<u8> {
+        wat::parse_str(format!(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 1024)
+                (func (export "check") (param i32 i32) (result i32)
+                    local.get 1
+                    i32.const {threshold}
+                    i32.gt_s))"#
+        ))
+        .unwrap()
+    }
+
+    const CODE: &str = r#"
+        public class Test {
+            void f() {
+                corpDao.load(1);
+            }
+        }
+    "#;
+
+    fn analyze(handler: ScriptedHandler) -> Vec<Issue> {
+        let analyzer = JavaTreeSitterAnalyzer::builder()
+            .with_rule(
+                "CORP_DAO_CALL",
+                Severity::P1,
+                "(method_invocation object: (identifier) @obj) @call",
+                "CorpDao 调用",
+                Box::new(handler),
+            )
+            .build()
+            .unwrap();
+        analyzer
+            .analyze(CODE, Path::new("Test.java"))
+            .unwrap()
+            .into_iter()
+            .filter(|i| i.id == "CORP_DAO_CALL")
+            .collect()
+    }
+
+    #[test]
+    fn test_script_decides_report() {
+        assert!(analyze(ScriptedHandler::new(&module_returning(0), DEFAULT_FUEL, None).unwrap()).is_empty());
+
+        let issues = analyze(ScriptedHandler::new(&module_returning(1), DEFAULT_FUEL, Some("call".into())).unwrap());
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::P1);
+        assert_eq!(issues[0].line, 4);
+
+        let escalated = analyze(ScriptedHandler::new(&module_returning(2), DEFAULT_FUEL, None).unwrap());
+        assert_eq!(escalated[0].severity, Severity::P0);
+    }
+
+    #[test]
+    fn test_script_receives_input() {
+        // 输入 JSON 含 rule_id/file/captures，长度远大于 10
+        assert_eq!(analyze(ScriptedHandler::new(&module_len_over(10), DEFAULT_FUEL, None).unwrap()).len(), 1);
+        assert!(analyze(ScriptedHandler::new(&module_len_over(100_000), DEFAULT_FUEL, None).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_fuel_limit_stops_infinite_loop() {
+        let wasm = wat::parse_str(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "alloc") (param i32) (result i32) i32.const 1024)
+                (func (export "check") (param i32 i32) (result i32)
+                    (loop $l (br $l))
+                    i32.const 1))"#,
+        )
+        .unwrap();
+        assert!(analyze(ScriptedHandler::new(&wasm, 10_000, None).unwrap()).is_empty());
+    }
+
+    #[test]
+    fn test_imports_rejected() {
+        let wasm = wat::parse_str(r#"(module (import "env" "read_file" (func (param i32))))"#).unwrap();
+        assert!(ScriptedHandler::new(&wasm, DEFAULT_FUEL, None).is_err());
+    }
+
+    #[test]
+    fn test_load_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("corp.wasm"), module_returning(1)).unwrap();
+        std::fs::write(
+            dir.path().join("corp.yaml"),
+            "id: CORP_DAO_CALL\nseverity: P0\ndescription: CorpDao\nquery: \"(method_invocation) @call\"\nwasm: corp.wasm\nfuel: 5000\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("README.md"), "ignored").unwrap();
+
+        let rules = load_dir(dir.path()).unwrap();
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].id, "CORP_DAO_CALL");
+        assert_eq!(rules[0].severity, Severity::P0);
+        assert_eq!(rules[0].handler.fuel, 5000);
+    }
+
+    #[test]
+    fn test_load_rule_clamps_fuel() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("corp.wasm"), module_returning(1)).unwrap();
+        std::fs::write(
+            dir.path().join("corp.yaml"),
+            "id: CORP\nseverity: P1\ndescription: d\nquery: \"(method_invocation) @call\"\nwasm: corp.wasm\nfuel: 18446744073709551615\n",
+        )
+        .unwrap();
+
+        let rules = load_dir(dir.path()).unwrap();
+        assert_eq!(rules[0].handler.fuel, MAX_FUEL);
+    }
+
+    #[test]
+    fn test_load_rule_rejects_wasm_outside_rules_dir() {
+        let root = tempfile::tempdir().unwrap();
+        let rules = root.path().join("rules");
+        std::fs::create_dir(&rules).unwrap();
+        let outside = root.path().join("outside.wasm");
+        std::fs::write(&outside, module_returning(1)).unwrap();
+
+        let rule = |wasm: &str| {
+            format!("id: CORP\nseverity: P1\ndescription: d\nquery: \"(method_invocation) @call\"\nwasm: '{wasm}'\n")
+        };
+        std::fs::write(rules.join("up.yaml"), rule("../outside.wasm")).unwrap();
+        let err = load_rule(&rules.join("up.yaml")).err().unwrap();
+        assert!(format!("{err:#}").contains("escapes the rules dir"));
+
+        std::fs::write(rules.join("abs.yaml"), rule(&outside.display().to_string())).unwrap();
+        let err = load_rule(&rules.join("abs.yaml")).err().unwrap();
+        assert!(format!("{err:#}").contains("must be relative"));
+    }
+}
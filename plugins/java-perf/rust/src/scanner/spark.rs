@@ -0,0 +1,273 @@
+//! Spark 作业分析 (Scala) - v9.6
+//!
+//! 只在 `.scala` 文件导入了 `org.apache.spark` 时启用：
+//! - `SPARK_COLLECT`: `collect()` / `collectAsList()` 把整个 RDD/DataFrame 拉回 Driver (前面有 `limit`/`sample` 的除外；
+//!   `collect { case ... }` 是转换算子，不报)
+//! - `SPARK_GROUP_BY_KEY`: RDD `groupByKey` 把每个 key 的全部值 shuffle 到一个分区 (Dataset 的 `groupByKey(_.k)` 不报)
+//! - `SPARK_JOIN_NO_BROADCAST`: 与维表/小表 (名称含 dim/lookup/ref/mapping/small/country/currency) 的 join 未使用
+//!   `broadcast()` / `hint("broadcast")`；文件关闭了 `autoBroadcastJoinThreshold` (`-1`) 时所有 join 都检查
+//! - `SPARK_UDF_HEAVY`: 一个文件定义/注册 3 个及以上 UDF，Catalyst 无法优化 UDF (无谓词下推、无代码生成)
+//!
+//! 本构建未内置 Scala 语法，按词法处理：抹掉注释与字符串后逐行匹配，方法链按 `.` 开头的续行合并为一条语句。
+
+use super::gradle::mask;
+use super::{CodeAnalyzer, Issue, Severity, Span};
+use crate::i18n;
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use std::path::Path;
+
+/// Spark 导入 (启用条件)
+static RE_SPARK_IMPORT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?m)^\s*import\s+org\.apache\.spark\b").unwrap()
+});
+
+/// `collect()` / `collectAsList()` / 无括号的 `.collect`
+static RE_COLLECT: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.(collect|collectAsList)\b\s*(\(\s*\))?").unwrap()
+});
+
+/// `groupByKey` 及其参数
+static RE_GROUP_BY_KEY: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.groupByKey\b\s*(?:\(([^()]*)\))?").unwrap()
+});
+
+/// `x.join(other, ...)`: 第一个参数为标识符
+static RE_JOIN: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\.join\s*\(\s*([A-Za-z_][\w.]*)").unwrap()
+});
+
+/// `udf(...)` / `udf { ... }` / `spark.udf.register(...)`
+static RE_UDF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\budf(?:\.register)?\s*[({]").unwrap()
+});
+
+/// 关闭自动广播: `spark.sql.autoBroadcastJoinThreshold` 设为 -1
+static RE_AUTO_BROADCAST_OFF: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"autoBroadcastJoinThreshold['"]?\s*[,=]\s*['"]?-1"#).unwrap()
+});
+
+/// 维表/小表名称中的词
+const SMALL_TABLE_WORDS: &[&str] = &[
+    "dim", "dimension", "lookup", "ref", "reference", "mapping", "small",
+    "country", "countries", "currency", "currencies",
+];
+
+/// 一个文件中 UDF 达到该数量视为 UDF 密集
+pub const MAX_UDFS: usize = 3;
+
+/// 检测到的问题: (规则 ID, 级别, 行号, 上下文)
+type Finding = (&'static str, Severity, usize, String);
+
+/// 名称拆词: `dimCountryDf` / `dim_country` → [dim, country, df]
+fn words(name: &str) -> Vec<String> {
+    let mut words = Vec::new();
+    let mut current = String::new();
+    for c in name.chars() {
+        if c == '_' || c == '.' {
+            words.push(std::mem::take(&mut current));
+        } else if c.is_uppercase() && !current.is_empty() {
+            words.push(std::mem::take(&mut current));
+            current.push(c.to_ascii_lowercase());
+        } else {
+            current.push(c.to_ascii_lowercase());
+        }
+    }
+    words.push(current);
+    words.retain(|w| !w.is_empty());
+    words
+}
+
+/// 第 `idx` 行所在的方法链语句 (前后以 `.` 开头的续行)
+fn statement(lines: &[&str], idx: usize) -> String {
+    let continues = |i: usize| lines.get(i).is_some_and(|l| l.trim_start().starts_with('.'));
+    let mut start = idx;
+    while start > 0 && continues(start) {
+        start -= 1;
+    }
+    let mut end = idx;
+    while continues(end + 1) {
+        end += 1;
+    }
+    lines[start..=end].iter().map(|l| l.trim()).collect::<Vec<_>>().join(" ")
+}
+
+/// Spark 作业分析器
+pub struct SparkJobAnalyzer;
+
+impl SparkJobAnalyzer {
+    pub fn new() -> Result<Self> {
+        Ok(Self)
+    }
+
+    fn description(id: &str) -> &'static str {
+        match id {
+            "SPARK_COLLECT" => "collect() 把整个数据集拉回 Driver：数据量大时 Driver OOM，且所有数据经单个进程串行传输；用 take(n) / limit(n) 取样，或直接在集群上 write / foreachPartition",
+            "SPARK_GROUP_BY_KEY" => "RDD groupByKey 把每个 key 的全部值 shuffle 到同一分区后才聚合：shuffle 数据量大，热点 key 导致 Executor OOM；聚合场景改用 reduceByKey / aggregateByKey (map 端预聚合)",
+            "SPARK_JOIN_NO_BROADCAST" => "与小表/维表的 join 未广播：小表超过自动广播阈值 (或阈值被关闭) 时退化为 SortMergeJoin，大表整体 shuffle；用 broadcast(small) 或 hint(\"broadcast\")",
+            _ => "UDF 密集的作业：UDF 对 Catalyst 是黑盒，无法谓词下推与全阶段代码生成，每行还要在内部格式与 JVM 对象间转换；优先使用 org.apache.spark.sql.functions 内置函数",
+        }
+    }
+
+    /// Spark 作业中的全部问题，按行排序 (未导入 Spark 时为空)
+    pub fn findings(code: &str) -> Vec<Finding> {
+        let without_comments = mask(code, true);
+        if !RE_SPARK_IMPORT.is_match(&without_comments) {
+            return Vec::new();
+        }
+        let masked = mask(code, false);
+        let lines: Vec<&str> = masked.lines().collect();
+        let source: Vec<&str> = code.lines().collect();
+        let context = |idx: usize| source.get(idx).map_or(String::new(), |l| l.trim().to_string());
+        let auto_broadcast_off = RE_AUTO_BROADCAST_OFF.is_match(&without_comments);
+        let mut findings = Vec::new();
+        let mut udfs: Vec<usize> = Vec::new();
+
+        for (idx, line) in lines.iter().enumerate() {
+            // 1. collect 拉回 Driver
+            for caps in RE_COLLECT.captures_iter(line) {
+                let Some(m) = caps.get(0) else { continue };
+                let next = line[m.end()..].trim_start().chars().next();
+                // `collect { case ... }` / `collect(pf)` 是转换算子
+                if caps.get(2).is_none() && matches!(next, Some('{') | Some('(')) {
+                    continue;
+                }
+                let stmt = statement(&lines, idx);
+                if stmt.contains(".limit(") || stmt.contains(".sample(") {
+                    continue;
+                }
+                findings.push(("SPARK_COLLECT", Severity::P1, idx + 1, context(idx)));
+            }
+
+            // 2. RDD groupByKey (Dataset 版本传入函数)
+            for caps in RE_GROUP_BY_KEY.captures_iter(line) {
+                let args = caps.get(1).map_or("", |m| m.as_str());
+                if args.contains("=>") || args.contains('_') {
+                    continue;
+                }
+                findings.push(("SPARK_GROUP_BY_KEY", Severity::P1, idx + 1, context(idx)));
+            }
+
+            // 3. 小表 join 未广播
+            for caps in RE_JOIN.captures_iter(line) {
+                let other = &caps[1];
+                if other == "broadcast" {
+                    continue;
+                }
+                let stmt = statement(&lines, idx);
+                if stmt.contains(".hint(") || stmt.contains("broadcast(") {
+                    continue;
+                }
+                let small = words(other).iter().any(|w| SMALL_TABLE_WORDS.contains(&w.as_str()));
+                if small || auto_broadcast_off {
+                    findings.push(("SPARK_JOIN_NO_BROADCAST", Severity::P2, idx + 1, format!("join({other})")));
+                }
+            }
+
+            // 4. UDF 计数
+            udfs.extend(RE_UDF.find_iter(line).map(|_| idx + 1));
+        }
+
+        if udfs.len() >= MAX_UDFS {
+            findings.push(("SPARK_UDF_HEAVY", Severity::P2, udfs[0], format!("{} 个 UDF", udfs.len())));
+        }
+
+        findings.sort_by_key(|(_, _, line, _)| *line);
+        findings
+    }
+}
+
+impl CodeAnalyzer for SparkJobAnalyzer {
+    fn supported_extension(&self) -> &str {
+        "scala"
+    }
+
+    fn analyze(&self, code: &str, file_path: &Path) -> Result<Vec<Issue>> {
+        let file_name = file_path.file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let lines: Vec<&str> = code.lines().collect();
+        Ok(Self::findings(code).into_iter().map(|(id, severity, line, context)| {
+            let span = Span::whole_line(line, lines.get(line.saturating_sub(1)).copied().unwrap_or(""));
+            Issue {
+                id: id.to_string(),
+                severity,
+                file: file_name.clone(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description: i18n::text(&format!("rule.{id}"), Self::description(id)).to_string(),
+                context: Some(context),
+                confidence: None,
+            }
+        }).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids(code: &str) -> Vec<(&'static str, usize)> {
+        SparkJobAnalyzer::findings(code).into_iter().map(|(id, _, line, _)| (id, line)).collect()
+    }
+
+    #[test]
+    fn test_slow_spark_job() {
+        let code = r#"
+import org.apache.spark.sql.SparkSession
+import org.apache.spark.sql.functions._
+
+object DailyReport {
+  def run(spark: SparkSession): Unit = {
+    val orders = spark.read.parquet("/data/orders")
+    val dimCountry = spark.read.parquet("/data/countries")
+    val rows = orders
+      .join(dimCountry, Seq("country_id"))
+      .collect()
+    val totals = orders.rdd.map(r => (r.getString(0), r.getLong(1))).groupByKey().mapValues(_.sum)
+    val clean = udf((s: String) => s.trim)
+    val upper = udf((s: String) => s.toUpperCase)
+    spark.udf.register("mask", (s: String) => "***")
+  }
+}
+"#;
+        assert_eq!(ids(code), vec![
+            ("SPARK_JOIN_NO_BROADCAST", 10),
+            ("SPARK_COLLECT", 11),
+            ("SPARK_GROUP_BY_KEY", 12),
+            ("SPARK_UDF_HEAVY", 13),
+        ]);
+        let findings = SparkJobAnalyzer::findings(code);
+        assert_eq!(findings[0].3, "join(dimCountry)");
+        assert_eq!(findings[3].3, "3 个 UDF");
+    }
+
+    #[test]
+    fn test_tuned_spark_job() {
+        let code = r#"
+import org.apache.spark.sql.functions.broadcast
+
+object Tuned {
+  // rdd.collect() in a comment
+  val sample = events.limit(100).collect()
+  val codes = pairs.collect { case (k, v) if v > 0 => k }
+  val joined = events.join(broadcast(dimUser), "user_id")
+  val hinted = events.join(lookupTable.hint("broadcast"), "id")
+  val facts = events.join(payments, "order_id")
+  val byUser = ds.groupByKey(_.userId)
+  val sums = pairs.reduceByKey(_ + _)
+}
+"#;
+        assert!(ids(code).is_empty());
+
+        // 没有 Spark 导入的 Scala 文件不分析
+        assert!(ids("object Plain { val all = rdd.collect() }").is_empty());
+
+        // 关闭自动广播后所有 join 都需要显式广播
+        let off = "import org.apache.spark.sql.SparkSession\nobject J {\n  spark.conf.set(\"spark.sql.autoBroadcastJoinThreshold\", \"-1\")\n  val j = events.join(payments, \"id\")\n}\n";
+        assert_eq!(ids(off), vec![("SPARK_JOIN_NO_BROADCAST", 4)]);
+    }
+}
@@ -1,11 +1,15 @@
 use super::{CodeAnalyzer, Issue, Severity};
-use super::rule_handlers::RuleContext;  // v9.3: 导入 RuleContext
+use super::rule_handlers::{RedisHandler, RuleContext, RuleHandler, REDIS_RULES};  // v9.3: 导入 RuleContext (v9.6: RuleHandler 用于构建器注册)
+use std::collections::HashMap;
 use std::path::Path;
 use std::cell::RefCell;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use anyhow::{Result, anyhow};
-use tree_sitter::{Parser, Query, QueryCursor, Tree};
-use crate::symbol_table::{TypeInfo, VarBinding, ImportIndex}; // Import TypeInfo and ImportIndex
+use tree_sitter::{Node, Parser, Query, QueryCursor, Tree};
+use crate::symbol_table::{TypeInfo, VarBinding, ImportIndex, MethodInfo, ParamInfo}; // Import TypeInfo and ImportIndex
 use crate::symbol_table::SymbolTable;
+use crate::metrics::{self, MethodMetrics};
 use crate::rules::suppression::SuppressionContext;
 
 // ============================================================================
@@ -18,6 +22,37 @@ use crate::rules::suppression::SuppressionContext;
 //
 // ============================================================================
 
+/// 调用点接收者: 调用本类方法 (`m()` / `this.m()` / `this::m`)，建图时解析为调用方所在类 (v9.6)
+pub const SELF_RECEIVER: &str = "this";
+
+/// Filter / Interceptor 每请求执行的方法 (FILTER_* 规则共用，v9.6)
+///
+/// 类是否为 Filter/Interceptor 由处理器检查 superclass / interfaces。
+const FILTER_METHOD_QUERY: &str = r#"
+    (class_declaration
+        body: (class_body
+            (method_declaration
+                name: (identifier) @method
+                (#match? @method "^(doFilter|doFilterInternal|preHandle|postHandle|afterCompletion|filter)$")
+            ) @decl
+        )
+    ) @class
+"#;
+
+/// `@KafkaListener` / `@RabbitListener` (及类级监听器的 `@*Handler`) 方法 (LISTENER_* 规则共用，v9.6)
+const LISTENER_METHOD_QUERY: &str = r#"
+    (method_declaration
+        (modifiers
+            [
+                (annotation name: (identifier) @ann)
+                (marker_annotation name: (identifier) @ann)
+            ]
+        )
+        name: (identifier) @method
+        (#match? @ann "^(KafkaListener|RabbitListener|KafkaHandler|RabbitHandler)$")
+    ) @decl
+"#;
+
 thread_local! {
     /// 线程本地 Parser 实例 (避免重复创建)
     static JAVA_PARSER: RefCell<Option<Parser>> = const { RefCell::new(None) };
@@ -66,14 +101,106 @@ pub struct JavaTreeSitterAnalyzer {
     import_query: Query,
     /// package 声明查询 (用于 FQN 构建) - v9.6
     package_query: Query,
+    /// 每条规则的累计查询+处理耗时 (`--timing`，为 None 时不计时) - v9.6
+    rule_timings: Option<Mutex<HashMap<&'static str, Duration>>>,
+}
+
+/// 自定义规则定义 (v9.6): (id, 严重级别, Tree-sitter 查询, 描述)
+type RuleDef = (&'static str, Severity, &'static str, &'static str);
+
+/// 分析器构建器 (v9.6)
+///
+/// 下游可以在不修改 `create_handler` 工厂的情况下注册自己的规则处理器：
+///
+/// ```ignore
+/// let analyzer = JavaTreeSitterAnalyzer::builder()
+///     // 替换内置规则的处理器
+///     .with_handler("N_PLUS_ONE", Box::new(CorpDaoAwareHandler))
+///     // 新增规则 (查询 + 处理器)
+///     .with_rule("CORP_DAO_IN_LOOP", Severity::P0, QUERY, "循环内调用 CorpDao", Box::new(CorpDaoHandler))
+///     .build()?;
+/// ```
+#[derive(Default)]
+pub struct JavaTreeSitterAnalyzerBuilder {
+    handlers: HashMap<&'static str, Box<dyn RuleHandler>>,
+    custom_rules: Vec<RuleDef>,
+    rule_timing: bool,
+}
+
+#[allow(dead_code)] // 库接口 (下游扩展使用)，CLI 只用内置规则
+impl JavaTreeSitterAnalyzerBuilder {
+    /// 为规则 ID 注册处理器 (覆盖 `create_handler` 的默认映射)
+    pub fn with_handler(mut self, rule_id: &'static str, handler: Box<dyn RuleHandler>) -> Self {
+        self.handlers.insert(rule_id, handler);
+        self
+    }
+
+    /// 新增自定义规则，查询编译失败时 `build()` 返回错误
+    pub fn with_rule(
+        mut self,
+        rule_id: &'static str,
+        severity: Severity,
+        query: &'static str,
+        description: &'static str,
+        handler: Box<dyn RuleHandler>,
+    ) -> Self {
+        self.custom_rules.push((rule_id, severity, query, description));
+        self.handlers.insert(rule_id, handler);
+        self
+    }
+
+    /// 加载目录中的 WASM 脚本规则 (v9.6)
+    ///
+    /// 规则在进程生命周期内有效，id/查询/描述字符串会被 leak 为 `'static`。
+    #[cfg(feature = "script-rules")]
+    pub fn with_scripted_rules(mut self, dir: &Path) -> Result<Self> {
+        for rule in super::script_rules::load_dir(dir)? {
+            let leak = |s: String| -> &'static str { Box::leak(s.into_boxed_str()) };
+            self = self.with_rule(
+                leak(rule.id),
+                rule.severity,
+                leak(rule.query),
+                leak(rule.description),
+                Box::new(rule.handler),
+            );
+        }
+        Ok(self)
+    }
+
+    /// 开启每条规则的耗时统计 (`scan --timing`)，通过 `rule_timings()` 读取
+    pub fn with_rule_timing(mut self, enabled: bool) -> Self {
+        self.rule_timing = enabled;
+        self
+    }
+
+    pub fn build(self) -> Result<JavaTreeSitterAnalyzer> {
+        let mut analyzer = JavaTreeSitterAnalyzer::with_registry(self.handlers, self.custom_rules)?;
+        if self.rule_timing {
+            analyzer.rule_timings = Some(Mutex::new(HashMap::new()));
+        }
+        Ok(analyzer)
+    }
 }
 
 impl JavaTreeSitterAnalyzer {
     pub fn new() -> Result<Self> {
+        Self::builder().build()
+    }
+
+    /// 创建构建器，用于注册自定义规则处理器 (v9.6)
+    pub fn builder() -> JavaTreeSitterAnalyzerBuilder {
+        JavaTreeSitterAnalyzerBuilder::default()
+    }
+
+    fn with_registry(
+        mut handlers: HashMap<&'static str, Box<dyn RuleHandler>>,
+        custom_rules: Vec<RuleDef>,
+    ) -> Result<Self> {
         let language = tree_sitter_java::language();
         
         // 预编译所有查询
-        let compiled_rules = Self::compile_rules(&language)?;
+        let mut compiled_rules = Self::compile_rules(&language, &mut handlers)?;
+        compiled_rules.extend(Self::compile_custom_rules(&language, &mut handlers, custom_rules)?);
         let structure_query = Self::compile_structure_query(&language)?;
         let call_site_query = Self::compile_call_site_query(&language)?; // v9.4: 调用点提取
         let import_query = Self::compile_import_query(&language)?;       // v9.5: import 解析
@@ -86,11 +213,38 @@ impl JavaTreeSitterAnalyzer {
             call_site_query,
             import_query,
             package_query,
+            rule_timings: None,
         })
     }
 
+    /// 每条规则的累计耗时，按耗时降序 (未开启计时时为空) - v9.6
+    pub fn rule_timings(&self) -> Vec<(&'static str, Duration)> {
+        let Some(timings) = &self.rule_timings else {
+            return Vec::new();
+        };
+        let mut list: Vec<_> = timings.lock().unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|(id, d)| (*id, *d))
+            .collect();
+        list.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        list
+    }
+
+    /// 已注册的规则 ID (内置 + 自定义)，按注册顺序 - v9.6
+    pub fn rule_ids(&self) -> Vec<&'static str> {
+        self.compiled_rules.iter().map(|r| r.id).collect()
+    }
+
+    /// 已注册的规则 (ID, 默认级别, 描述)，按注册顺序 - v9.6
+    pub fn rule_definitions(&self) -> Vec<(&'static str, Severity, &'static str)> {
+        self.compiled_rules.iter().map(|r| (r.id, r.severity, r.description)).collect()
+    }
+
     /// 编译规则查询 (只在初始化时调用一次)
-    fn compile_rules(language: &tree_sitter::Language) -> Result<Vec<CompiledRule>> {
+    fn compile_rules(
+        language: &tree_sitter::Language,
+        handlers: &mut HashMap<&'static str, Box<dyn RuleHandler>>,
+    ) -> Result<Vec<CompiledRule>> {
         let rule_defs = vec![
             // 规则1: N_PLUS_ONE - for 循环内的调用
             ("N_PLUS_ONE", Severity::P0, r#"
@@ -159,10 +313,10 @@ impl JavaTreeSitterAnalyzer {
             // 规则4: THREADLOCAL_LEAK (P0)
             ("THREADLOCAL_LEAK", Severity::P0, r#"
                 (method_invocation
-                    object: (identifier) @var_name
+                    object: (identifier) @target
                     name: (identifier) @method
                     (#eq? @method "set")
-                ) @set_call
+                ) @acquire
             "#, "ThreadLocal.set() 后未在同一方法内调用 remove()"),
             
             // 规则5: STREAM_RESOURCE_LEAK - try 块内创建流但未在 finally 中关闭
@@ -199,10 +353,10 @@ impl JavaTreeSitterAnalyzer {
             // 规则7: LOCK_METHOD_CALL - 检测 ReentrantLock.lock() 调用 (P0)
             ("LOCK_METHOD_CALL", Severity::P0, r#"
                 (method_invocation
-                    object: (identifier) @lock_var
+                    object: (identifier) @target
                     name: (identifier) @method
-                    (#eq? @method "lock")
-                ) @lock_call
+                    (#match? @method "^(lock|lockInterruptibly)$")
+                ) @acquire
             "#, "ReentrantLock.lock() 调用，请确保 unlock() 在 finally 块中"),
             
             // ====== v7.0 AST 迁移规则 ======
@@ -228,7 +382,7 @@ impl JavaTreeSitterAnalyzer {
                             arguments: (annotation_argument_list
                                 (element_value_pair
                                     key: (identifier) @key
-                                    (#eq? @key "fixedRate")
+                                    (#match? @key "^fixedRate(String)?$")
                                 )
                             )
                             (#eq? @ann_name "Scheduled")
@@ -238,7 +392,7 @@ impl JavaTreeSitterAnalyzer {
             "#, "@Scheduled(fixedRate) 任务可能堆积，考虑使用 fixedDelay"),
             
             // 规则10: @Autowired 字段注入
-            ("AUTOWIRED_FIELD", Severity::P1, r#"
+            ("AUTOWIRED_FIELD", Severity::Info, r#"
                 (field_declaration
                     (modifiers
                         (marker_annotation
@@ -321,13 +475,14 @@ impl JavaTreeSitterAnalyzer {
             "#, "循环内创建对象，可能导致 GC 压力"),
             
             // 规则19: @Cacheable 未指定 key
-            ("CACHEABLE_NO_KEY", Severity::P1, r#"
+            ("CACHEABLE_NO_KEY", Severity::P2, r#"
                 (method_declaration
                     (modifiers
                         (annotation
                             name: (identifier) @ann_name
                             arguments: (annotation_argument_list) @args
                             (#eq? @ann_name "Cacheable")
+                            (#not-match? @args "\\b(key|keyGenerator)\\s*=")
                         )
                     )
                 ) @method
@@ -366,9 +521,11 @@ impl JavaTreeSitterAnalyzer {
             // 规则22: await()/acquire() 无超时
             ("AWAIT_NO_TIMEOUT", Severity::P0, r#"
                 (method_invocation
+                    object: (_) @obj
                     name: (identifier) @method_name
                     arguments: (argument_list) @args
                     (#match? @method_name "^(await|acquire)$")
+                    (#not-eq? @obj "Awaitility")
                 ) @call
             "#, "await()/acquire() 无超时参数，可能永久阻塞"),
             
@@ -376,6 +533,7 @@ impl JavaTreeSitterAnalyzer {
             ("COMPLETABLE_JOIN", Severity::P1, r#"
                 (method_invocation
                     name: (identifier) @method_name
+                    arguments: (argument_list) @args
                     (#eq? @method_name "join")
                 ) @call
             "#, "CompletableFuture.join() 无超时，可能永久阻塞"),
@@ -442,7 +600,7 @@ impl JavaTreeSitterAnalyzer {
             "#, "FileInputStream/FileOutputStream 同步阻塞 IO，考虑使用 NIO"),
             
             // 规则30: AtomicInteger/AtomicLong 高竞争
-            ("ATOMIC_SPIN", Severity::P1, r#"
+            ("ATOMIC_SPIN", Severity::P2, r#"
                 (object_creation_expression
                     type: (type_identifier) @type_name
                     (#match? @type_name "^Atomic(Integer|Long)$")
@@ -635,27 +793,523 @@ impl JavaTreeSitterAnalyzer {
 
             // 规则49: SELECT * 检测 - 匹配包含 "SELECT *" 的字符串字面量
             ("SELECT_STAR", Severity::P1, r#"
-                (string_literal) @str
-                (#match? @str "SELECT\\s+\\*\\s+FROM")
+                ((string_literal) @str
+                 (#match? @str "SELECT\\s+\\*\\s+FROM"))
             "#, "SELECT * 查询，建议明确指定字段以减少数据传输"),
 
             // 规则50: LIKE 前导通配符 - 匹配 LIKE '%xxx' 模式
             ("LIKE_LEADING_WILDCARD", Severity::P0, r#"
-                (string_literal) @str
-                (#match? @str "LIKE\\s+['\"]%")
+                ((string_literal) @str
+                 (#match? @str "LIKE\\s+['\"]%"))
             "#, "LIKE '%xxx' 前导通配符导致无法使用索引，引发全表扫描"),
 
-            // 规则51: HTTP 客户端使用检测 - 提醒检查超时配置
+            // 规则51: HTTP 客户端创建处未配置超时 (v9.6: 项目级全局超时配置在扫描后处理)
             ("HTTP_CLIENT_TIMEOUT", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @type
+                    name: (identifier) @method
+                    arguments: (argument_list) @args
+                    (#match? @type "^(WebClient|RestClient|HttpClient|HttpClients)$")
+                    (#match? @method "^(builder|create|newHttpClient|newBuilder|createDefault|custom)$")
+                ) @call
+
+                (object_creation_expression
+                    type: [
+                        (type_identifier)
+                        (scoped_type_identifier)
+                    ] @type
+                    arguments: (argument_list) @args
+                    (#match? @type "^(RestTemplate|RestTemplateBuilder|OkHttpClient|OkHttpClient\\.Builder)$")
+                ) @call
+            "#, "HTTP 客户端创建时未配置连接/读取超时，且项目中没有全局超时配置：下游变慢时请求线程无限期挂起，应设置 connectTimeout 与 responseTimeout/readTimeout"),
+
+            // ====== v9.6: Filter / Interceptor 每请求开销 ======
+
+            // 规则52: Filter/Interceptor 每个请求都访问数据库 (直接或经调用链到达 Repository)
+            ("FILTER_DB_CALL", Severity::P0, FILTER_METHOD_QUERY,
+                "Filter/Interceptor 每个请求都执行数据库调用，请改为缓存或移出请求链路"),
+
+            // 规则53: Filter/Interceptor 每个请求都发起 HTTP 调用
+            ("FILTER_HTTP_CALL", Severity::P0, FILTER_METHOD_QUERY,
+                "Filter/Interceptor 每个请求都发起远程 HTTP 调用，请缓存结果或改为异步"),
+
+            // ====== v9.6: Kafka / RabbitMQ 监听器 (消费积压) ======
+
+            // 规则54: 监听方法内长阻塞调用
+            ("LISTENER_BLOCKING_CALL", Severity::P0, LISTENER_METHOD_QUERY,
+                "消息监听方法内有阻塞调用 (sleep/join/block/无超时 get/HTTP)，拖慢消费导致积压"),
+
+            // 规则55: 慢处理且无手动 ack
+            ("LISTENER_AUTO_ACK_SLOW", Severity::P1, LISTENER_METHOD_QUERY,
+                "消息监听方法处理较慢但未手动 ack，超时重平衡/重投递会放大积压"),
+
+            // 规则56: 消息缓存到无界集合
+            ("LISTENER_UNBOUNDED_BUFFER", Severity::P0, LISTENER_METHOD_QUERY,
+                "消息监听方法把消息追加到无界集合且无大小检查，消费快于处理时内存无限增长"),
+
+            // 规则57: 监听方法上的 @Transactional
+            ("LISTENER_TRANSACTIONAL", Severity::P1, LISTENER_METHOD_QUERY,
+                "@Transactional 监听方法在整个消息处理期间持有数据库连接，降低消费吞吐"),
+
+            // ====== v9.6: 正则 ======
+
+            // 规则58: 灾难性回溯正则 (嵌套量词 / 重叠分支)
+            ("REGEX_REDOS", Severity::P0, r#"
+                (method_invocation
+                    name: (identifier) @method
+                    arguments: (argument_list . [(string_literal) (identifier)] @regex)
+                    (#match? @method "^(compile|matches|replaceAll|replaceFirst|split)$")
+                ) @call
+            "#, "正则含嵌套量词或重叠分支，恶意输入可触发指数级回溯 (ReDoS) 耗尽 CPU"),
+
+            // 规则59: 循环内 / 每请求编译正则
+            ("PATTERN_COMPILE_HOT_PATH", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    (#eq? @obj "Pattern")
+                    (#eq? @method "compile")
+                ) @call
+            "#, "Pattern.compile 位于循环或每请求路径中，应提取为 static final 常量复用"),
+
+            // ====== v9.6: 集合误用 ======
+
+            // 规则60: 循环内 List.contains (O(N²))
+            ("LIST_CONTAINS_IN_LOOP", Severity::P1, r#"
+                (method_invocation
+                    name: (identifier) @method
+                    (#eq? @method "contains")
+                ) @call
+            "#, "循环内对 List 调用 contains() 是线性查找，整体 O(N²)，应先转为 HashSet"),
+
+            // 规则61: 已知大小却未指定初始容量
+            ("COLLECTION_NO_CAPACITY", Severity::P1, r#"
+                (local_variable_declaration
+                    declarator: (variable_declarator
+                        name: (identifier) @var
+                        value: (object_creation_expression
+                            type: (_) @type
+                            arguments: (argument_list) @args))
+                    (#match? @type "^(ArrayList|HashMap|HashSet|LinkedHashMap|LinkedHashSet)")
+                ) @decl
+            "#, "集合创建后立即装入已知大小的数据却未指定初始容量，导致多次扩容复制"),
+
+            // 规则62: LinkedList 随机访问
+            ("LINKEDLIST_RANDOM_ACCESS", Severity::P1, r#"
+                (method_invocation
+                    name: (identifier) @method
+                    arguments: (argument_list (_))
+                    (#match? @method "^(get|set)$")
+                ) @call
+            "#, "LinkedList.get(index)/set(index) 需要遍历链表 (O(N))，循环中访问为 O(N²)，应改用 ArrayList"),
+
+            // 规则63: 高并发类中的 Collections.synchronizedMap
+            ("SYNCHRONIZED_MAP", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    (#eq? @obj "Collections")
+                    (#match? @method "^synchronized(Map|SortedMap|NavigableMap)$")
+                ) @call
+            "#, "Collections.synchronizedMap 所有操作串行在同一把锁上，高并发下争用严重，建议 ConcurrentHashMap"),
+
+            // ====== v9.6: 序列化 ======
+
+            // 规则64: 方法/循环内新建 ObjectMapper / Gson
+            ("SERIALIZER_PER_CALL", Severity::P1, r#"
+                (object_creation_expression
+                    type: (_) @type
+                    (#match? @type "^(ObjectMapper|JsonMapper|XmlMapper|YAMLMapper|CsvMapper|Gson|GsonBuilder)$")
+                ) @creation
+            "#, "每次调用都新建 ObjectMapper/Gson，丢失内部序列化器缓存，应作为单例共享 (线程安全)"),
+
+            // 规则65: 循环内序列化整个集合
+            ("SERIALIZE_IN_LOOP", Severity::P1, r#"
+                (method_invocation
+                    name: (identifier) @method
+                    arguments: (argument_list . (_) @arg)
+                    (#match? @method "^(writeValueAsString|writeValueAsBytes|toJson|toJSONString)$")
+                ) @call
+            "#, "循环内反复序列化整个集合，开销随集合大小 × 循环次数增长，应移到循环外"),
+
+            // 规则66: 双向 JPA 关联缺少 @JsonIgnore 等处理
+            ("JPA_BIDIRECTIONAL_JSON_CYCLE", Severity::P1, r#"
+                (field_declaration
+                    (modifiers
+                        (annotation
+                            name: (identifier) @ann
+                            arguments: (annotation_argument_list) @args) @relation)
+                    declarator: (variable_declarator name: (identifier) @field)
+                    (#match? @ann "^(OneToMany|ManyToMany|OneToOne)$")
+                    (#match? @args "mappedBy")
+                ) @decl
+            "#, "双向 JPA 关联 (mappedBy) 未使用 @JsonIgnore/@JsonManagedReference，序列化时循环引用并触发懒加载"),
+
+            // ====== v9.6: 日期时间 ======
+
+            // 规则67: 业务逻辑依赖 JVM 默认时区
+            ("DEFAULT_TIMEZONE", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    (#match? @obj "^(TimeZone|ZoneId)$")
+                    (#match? @method "^(getDefault|systemDefault)$")
+                ) @call
+            "#, "业务逻辑依赖 JVM 默认时区，部署环境不同结果不同 (TimeZone.getDefault 每次还会克隆对象)，应显式指定 ZoneId"),
+
+            // 规则68: 紧凑循环内 new Date()
+            ("DATE_IN_LOOP", Severity::P1, r#"
+                (object_creation_expression
+                    type: (type_identifier) @type
+                    arguments: (argument_list) @args
+                    (#eq? @type "Date")
+                ) @creation
+            "#, "循环内反复 new Date() 产生大量短命对象，应在循环外取一次时间"),
+
+            // 规则69: 每次调用 Calendar.getInstance()
+            ("CALENDAR_PER_CALL", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    (#eq? @obj "Calendar")
+                    (#eq? @method "getInstance")
+                ) @call
+            "#, "Calendar.getInstance() 每次创建重量级对象 (时区/Locale 查找)，建议改用 java.time API"),
+
+            // 规则70: 循环内 DateTimeFormatter.ofPattern
+            ("DATE_FORMATTER_IN_LOOP", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    (#eq? @obj "DateTimeFormatter")
+                    (#eq? @method "ofPattern")
+                ) @call
+            "#, "循环内反复 DateTimeFormatter.ofPattern 重新解析格式串，DateTimeFormatter 线程安全，应提取为常量"),
+
+            // ====== v9.6: 线程创建 ======
+
+            // 规则71: Controller / Service 方法内 new Thread / new Timer
+            ("THREAD_IN_REQUEST_SCOPE", Severity::P0, r#"
+                (object_creation_expression
+                    type: (type_identifier) @type
+                    (#match? @type "^(Thread|Timer)$")
+                ) @creation
+            "#, "请求链路 (Controller/Service) 中直接创建线程/Timer，并发量大时线程数失控，应使用受管线程池"),
+
+            // 规则72: 未命名线程
+            ("THREAD_NO_NAME", Severity::P1, r#"
+                (object_creation_expression
+                    type: (type_identifier) @type
+                    arguments: (argument_list) @args
+                    (#eq? @type "Thread")
+                ) @creation
+            "#, "线程未命名 (Thread-N)，线程 dump 中无法定位来源，应指定名称或使用命名 ThreadFactory"),
+
+            // 规则73: 核心线程数为 0 的 ScheduledThreadPoolExecutor
+            ("SCHEDULED_POOL_ZERO_CORE", Severity::P0, r#"
+                (object_creation_expression
+                    type: (type_identifier) @type
+                    arguments: (argument_list . (decimal_integer_literal) @core)
+                    (#eq? @type "ScheduledThreadPoolExecutor")
+                ) @call
+
+                (method_invocation
+                    object: (identifier) @obj
+                    name: (identifier) @method
+                    arguments: (argument_list . (decimal_integer_literal) @core)
+                    (#eq? @obj "Executors")
+                    (#eq? @method "newScheduledThreadPool")
+                ) @call
+            "#, "ScheduledThreadPoolExecutor 核心线程数为 0 时 (JDK 8) 工作线程空转占满 CPU，且延迟任务依赖临时线程"),
+
+            // ====== v9.6: Optional / Stream ======
+
+            // 规则74: Optional.get() 未检查
+            ("OPTIONAL_GET_UNCHECKED", Severity::P1, r#"
+                (method_invocation
+                    object: (_) @obj
+                    name: (identifier) @method
+                    arguments: (argument_list) @args
+                    (#eq? @method "get")
+                ) @call
+            "#, "Optional.get() 前未检查 isPresent()，空值时抛 NoSuchElementException，应使用 orElse/orElseThrow"),
+
+            // 规则75: filter().count() > 0 判断存在性
+            ("STREAM_COUNT_FOR_EXISTENCE", Severity::P1, r#"
+                (binary_expression
+                    left: (method_invocation
+                        object: (method_invocation name: (identifier) @filter)
+                        name: (identifier) @method)
+                    right: (decimal_integer_literal) @n
+                    (#eq? @filter "filter")
+                    (#eq? @method "count")
+                ) @expr
+            "#, "用 filter().count() 判断是否存在需要遍历全部元素，anyMatch/noneMatch 可短路返回"),
+
+            // 规则76: collect(toList()).size()
+            ("STREAM_COLLECT_SIZE", Severity::P1, r#"
+                (method_invocation
+                    object: (method_invocation name: (identifier) @inner)
+                    name: (identifier) @method
+                    (#match? @inner "^(collect|toList)$")
+                    (#eq? @method "size")
+                ) @call
+            "#, "为计数而把 Stream 收集成 List 再取 size()，多余的中间集合分配，应使用 count()"),
+
+            // 规则77: 同一方法内反复对同一集合 stream()
+            ("STREAM_REPEATED", Severity::P1, r#"
+                (method_declaration
+                    name: (identifier) @method
+                    body: (block) @body
+                ) @decl
+            "#, "同一方法内多次对同一集合调用 stream() 反复遍历，应合并为一次遍历 (如 groupingBy/partitioningBy)"),
+
+            // ====== v9.6: Java 17/21 (record / sealed / 模式匹配 switch) ======
+
+            // 规则78: record 组件为可变集合/数组
+            ("RECORD_MUTABLE_COMPONENT", Severity::P1, r#"
+                (record_declaration
+                    name: (identifier) @record
+                    parameters: (formal_parameters
+                        (formal_parameter
+                            type: (_) @type
+                            name: (identifier) @component) @param)
+                    body: (class_body) @body
+                ) @decl
+            "#, "record 组件为可变集合/数组：equals/hashCode 每次遍历整个集合 (作为 Map key 时开销放大)，外部修改还会破坏不可变语义，应在紧凑构造器中 List.copyOf"),
+
+            // 规则79: 热路径中的模式匹配 switch 无 default
+            ("SWITCH_PATTERN_NO_DEFAULT", Severity::P1, r#"
+                (switch_expression
+                    body: (switch_block) @body
+                ) @switch
+            "#, "循环/lambda 热路径中的模式匹配 switch 无 default 分支：依赖密封类型穷举，新增子类型后运行时抛 MatchException；case 按顺序逐个类型测试，高频类型应前置"),
+
+            // ====== v9.6: 测试专用规则 (只在测试源码上触发，见 rules::test_code) ======
+
+            // 规则80: 测试中固定时长 sleep
+            ("TEST_THREAD_SLEEP", Severity::P1, r#"
+                (method_invocation
+                    object: (_) @obj
+                    name: (identifier) @method
+                    (#eq? @method "sleep")
+                ) @call
+            "#, "测试中用固定时长 sleep 等待异步结果：机器慢时随机失败，机器快时白白拖慢构建，应使用 Awaitility 等条件等待"),
+
+            // 规则81: 测试中写死端口
+            ("TEST_FIXED_PORT", Severity::P1, r#"
+                ((string_literal) @call
+                 (#match? @call "(localhost|127\\.0\\.0\\.1):[0-9]{2,5}|server\\.port\\s*=\\s*[1-9]"))
+
+                ((identifier) @call
+                 (#eq? @call "DEFINED_PORT"))
+            "#, "测试写死端口：并行构建或端口被占用时随机失败，应使用 RANDOM_PORT / @LocalServerPort 或端口 0"),
+
+            // 规则82: 测试类中的可变静态状态
+            ("TEST_SHARED_STATIC_STATE", Severity::P1, r#"
+                (field_declaration
+                    (modifiers) @mods
+                    type: (_) @type
+                    declarator: (variable_declarator name: (identifier) @var)
+                ) @field
+            "#, "测试类中的可变静态字段在用例之间共享状态：结果依赖执行顺序，并行执行时互相干扰，应改为实例字段并在 @BeforeEach 中初始化"),
+
+            // ====== v9.6: 启动耗时 ======
+
+            // 规则83: 组件扫描根包过宽
+            ("COMPONENT_SCAN_BROAD", Severity::P1, r#"
+                (annotation
+                    name: (identifier) @ann
+                    arguments: (annotation_argument_list) @args
+                    (#match? @ann "^(ComponentScan|SpringBootApplication|EntityScan|EnableJpaRepositories|MapperScan)$")
+                ) @call
+            "#, "组件扫描根包过宽 (如 \"com\")：启动时遍历整个 classpath 下的依赖 jar，扫描和注册大量无关类，应收窄到应用自身的包"),
+
+            // 规则84: @PostConstruct / afterPropertiesSet 中远程调用
+            ("POSTCONSTRUCT_REMOTE_CALL", Severity::P0, r#"
+                (method_declaration
+                    (modifiers (marker_annotation name: (identifier) @ann))
+                    name: (identifier) @method
+                    (#eq? @ann "PostConstruct")
+                ) @decl
+
+                (method_declaration
+                    name: (identifier) @method
+                    (#eq? @method "afterPropertiesSet")
+                ) @decl
+            "#, "Bean 初始化回调 (@PostConstruct / afterPropertiesSet) 中同步调用远程服务或数据库：拖慢启动，依赖不可用时应用无法启动，应改为异步预热或 ApplicationReadyEvent"),
+
+            // 规则85: 未延迟初始化的重量级客户端 @Bean
+            ("EAGER_HEAVY_BEAN", Severity::P1, r#"
+                (method_declaration
+                    (modifiers
+                        [
+                            (marker_annotation name: (identifier) @ann)
+                            (annotation name: (identifier) @ann)
+                        ]
+                    )
+                    type: (_) @type
+                    name: (identifier) @method
+                    (#eq? @ann "Bean")
+                ) @decl
+            "#, "重量级客户端 (ES/Kafka/Mongo/S3 等) 的 @Bean 在启动时立即建连，且未标注 @Lazy：拖慢启动，应延迟初始化或开启 spring.main.lazy-initialization"),
+
+            // ====== v9.6: SQL 字符串拼接 ======
+
+            // 规则86: 变量拼接进 SQL 后执行
+            ("SQL_STRING_CONCAT", Severity::P0, r#"
+                (method_invocation
+                    name: (identifier) @method
+                    arguments: (argument_list . (_) @sql)
+                    (#match? @method "^(createQuery|createNativeQuery|createSQLQuery|executeQuery|executeUpdate|executeLargeUpdate|execute|addBatch|prepareStatement|prepareCall|query|queryForObject|queryForList|queryForMap|queryForRowSet|queryForStream|update|batchUpdate)$")
+                ) @call
+            "#, "SQL 由字符串拼接变量构造：存在 SQL 注入风险，且每个不同取值都是一条新语句，数据库反复硬解析，执行计划缓存与 PreparedStatement 缓存失效，应改用 ? / :name 参数绑定"),
+
+            // ====== v9.6: 列表接口未分页 ======
+
+            // 规则87: Controller 调用链上返回无上限集合的 Repository / Mapper 查询
+            ("UNBOUNDED_QUERY_RESULT", Severity::P1, r#"
                 (method_invocation
                     object: [
-                        (identifier) @obj
-                        (method_invocation) @obj
+                        (identifier) @recv
+                        (field_access object: (this) field: (identifier) @recv)
                     ]
                     name: (identifier) @method
-                    (#match? @obj "(HttpClient|RestTemplate|OkHttp|WebClient)")
+                    arguments: (argument_list) @args
+                ) @call
+            "#, "列表接口调用的 Repository/Mapper 查询返回无上限集合 (无 Pageable / LIMIT)：数据量增长后单次请求加载全表，响应变慢、内存暴涨，应改为分页或限定条数"),
+
+            // ====== v9.6: 重试 / 熔断配置 ======
+
+            // 规则88: @Retryable 未配置退避
+            ("RETRY_NO_BACKOFF", Severity::P1, r#"
+                (method_declaration
+                    (modifiers
+                        [
+                            (marker_annotation name: (identifier) @ann)
+                            (annotation name: (identifier) @ann)
+                        ]
+                    )
+                    name: (identifier) @method
+                    body: (block)
+                    (#eq? @ann "Retryable")
+                ) @decl
+            "#, "重试未配置退避 (或退避为 0)：下游故障时各实例同步重试，放大故障期流量形成重试风暴，应配置指数退避 (@Backoff(delay, multiplier) / resilience4j enable-exponential-backoff)"),
+
+            // 规则89: 远程调用重试次数过多
+            ("RETRY_EXCESSIVE_ATTEMPTS", Severity::P1, r#"
+                (method_declaration
+                    (modifiers
+                        (annotation name: (identifier) @ann)
+                    )
+                    name: (identifier) @method
+                    body: (block)
+                    (#eq? @ann "Retryable")
+                ) @decl
+            "#, "远程调用重试次数超过 5 次：下游故障时单个请求放大为多次调用，请求线程长时间占用，应降低 maxAttempts 并配合熔断"),
+
+            // 规则90: 重试包裹非幂等写操作
+            ("RETRY_NON_IDEMPOTENT", Severity::P1, r#"
+                (method_declaration
+                    (modifiers
+                        [
+                            (marker_annotation name: (identifier) @ann)
+                            (annotation name: (identifier) @ann)
+                        ]
+                    )
+                    name: (identifier) @method
+                    body: (block)
+                    (#match? @ann "^(Retryable|Retry)$")
+                ) @decl
+            "#, "重试包裹非幂等写操作 (save/insert)：首次写入已提交但响应超时时重试会重复插入，应只重试幂等操作，或以业务唯一键去重"),
+
+            // ====== v9.6: Redis 规则组 (符号表中出现 Redis 客户端类型时启用) ======
+
+            // 规则91: SETNX 加锁未设置过期时间
+            ("REDIS_SETNX_NO_EXPIRE", Severity::P0, r#"
+                (method_invocation
+                    object: (_)
+                    name: (identifier) @method
+                    arguments: (argument_list) @args
+                    (#match? @method "^(setnx|setNX|setIfAbsent|msetnx)$")
+                ) @call
+            "#, "SETNX 加锁未原子地设置过期时间：持锁进程崩溃或超时后锁永不释放，后续请求全部拿不到锁，应使用 SET key value NX EX / setIfAbsent(key, value, timeout)"),
+
+            // 规则92: Jedis 实例作为字段被多线程共享
+            ("REDIS_JEDIS_SHARED", Severity::P0, r#"
+                (field_declaration
+                    type: (type_identifier) @type
+                    declarator: (variable_declarator name: (identifier) @name)
+                    (#match? @type "^(Jedis|ShardedJedis|BinaryJedis)$")
+                ) @decl
+            "#, "Jedis 实例非线程安全却作为字段被多线程共享：并发读写同一连接导致响应错乱与协议异常，应每次从 JedisPool 获取并归还，或改用 JedisPooled / Lettuce"),
+
+            // 规则93: 请求路径中的 KEYS / SMEMBERS
+            ("REDIS_FULL_SCAN_IN_REQUEST", Severity::P0, r#"
+                (method_invocation
+                    object: (_)
+                    name: (identifier) @method
+                    arguments: (argument_list) @args
+                    (#match? @method "^(keys|smembers|members)$")
+                ) @call
+            "#, "请求路径中执行 KEYS / SMEMBERS：O(N) 遍历阻塞 Redis 单线程，所有客户端排队等待，应改用 SCAN / SSCAN 游标或维护有界索引"),
+
+            // 规则94: 循环内逐条 Redis 操作
+            ("REDIS_OP_IN_LOOP", Severity::P1, r#"
+                (method_invocation
+                    object: (_)
+                    name: (identifier) @method
+                    arguments: (argument_list) @args
+                ) @call
+            "#, "循环内逐条执行 Redis 命令：每次一个网络往返，应改用 multiGet / MSET 或 executePipelined 批量提交"),
+
+            // 规则95: MDC 泄漏 (与 THREADLOCAL_LEAK 共用成对调用处理器)
+            ("MDC_LEAK", Severity::P0, r#"
+                (method_invocation
+                    object: (identifier) @target
+                    name: (identifier) @method
+                    arguments: (argument_list . (_) @key)
+                    (#eq? @target "MDC")
+                    (#eq? @method "put")
+                ) @acquire
+            "#, "MDC.put() 后未在 finally 中 MDC.remove()/clear()：线程池复用线程时后续任务继承旧的 traceId/用户信息，日志串号"),
+
+            // 规则96-98: 成对调用 (定义见 rule_handlers::PAIRED_CALLS)
+            ("SEMAPHORE_NO_RELEASE", Severity::P0, r#"
+                (method_invocation
+                    object: (identifier) @target
+                    name: (identifier) @method
+                    (#match? @method "^(acquire|acquireUninterruptibly|tryAcquire)$")
+                ) @acquire
+            "#, "Semaphore 获取许可后未在 finally 中 release()：异常路径丢失许可，许可耗尽后所有调用方永久阻塞"),
+
+            ("CONNECTION_NOT_CLOSED", Severity::P0, r#"
+                (local_variable_declaration
+                    declarator: (variable_declarator
+                        name: (identifier) @target
+                        value: (method_invocation
+                            name: (identifier) @method
+                            (#eq? @method "getConnection")
+                        ) @acquire
+                    )
+                )
+            "#, "getConnection() 获取的连接未在 finally 中 close()：异常路径连接不归还，连接池耗尽，应使用 try-with-resources"),
+
+            ("LATCH_COUNTDOWN_NOT_IN_FINALLY", Severity::P1, r#"
+                (method_invocation
+                    object: (identifier) @target
+                    name: (identifier) @method
+                    (#eq? @method "countDown")
+                ) @acquire
+            "#, "CountDownLatch.countDown() 不在 finally 中：任务抛异常时计数不归零，await() 无超时则永久阻塞"),
+
+            // 规则99: 测试中 Testcontainers 容器泄漏 (只在测试源码上触发，见 rules::test_code)
+            ("TEST_CONTAINER_LEAK", Severity::P1, r#"
+                (object_creation_expression
+                    type: (_) @type
+                    (#match? @type "Container")
                 ) @call
-            "#, "HTTP 客户端使用，请确认已配置连接超时和读取超时"),
+            "#, "测试中手动创建的 Testcontainers 容器没有停止：每个用例/测试类各启动一个容器直到 JVM 退出才由 Ryuk 回收 (CI 禁用 Ryuk 时永久残留)，内存与端口逐渐耗尽，应使用 @Container + @Testcontainers、try-with-resources 或在 @AfterEach/@AfterAll 中 stop()"),
         ];
 
         let mut compiled = Vec::with_capacity(rule_defs.len());
@@ -671,14 +1325,15 @@ impl JavaTreeSitterAnalyzer {
                 }
             };
 
-            // v9.3: 使用 create_handler 获取规则处理器
-            let handler = super::rule_handlers::create_handler(id);
+            // v9.3: 使用 create_handler 获取规则处理器 (v9.6: 构建器注册的优先)
+            let handler = handlers.remove(id).unwrap_or_else(|| super::rule_handlers::create_handler(id));
 
             compiled.push(CompiledRule {
                 id,
                 severity,
                 query,
-                description,
+                // v9.6: 按 --lang 本地化规则描述
+                description: crate::i18n::text(&format!("rule.{id}"), description),
                 handler,
             });
         }
@@ -686,16 +1341,56 @@ impl JavaTreeSitterAnalyzer {
         Ok(compiled)
     }
 
+    /// 编译构建器注册的自定义规则 (v9.6)
+    ///
+    /// 与内置规则不同，自定义查询编译失败直接返回错误，避免规则被静默跳过。
+    fn compile_custom_rules(
+        language: &tree_sitter::Language,
+        handlers: &mut HashMap<&'static str, Box<dyn RuleHandler>>,
+        rule_defs: Vec<RuleDef>,
+    ) -> Result<Vec<CompiledRule>> {
+        rule_defs.into_iter().map(|(id, severity, query_str, description)| {
+            let query = Query::new(language, query_str)
+                .map_err(|e| anyhow!("Failed to compile query for custom rule '{id}': {e}"))?;
+            let handler = handlers.remove(id)
+                .ok_or_else(|| anyhow!("No handler registered for custom rule '{id}'"))?;
+            Ok(CompiledRule { id, severity, query, description, handler })
+        }).collect()
+    }
+
+    /// 类/接口声明的 `extends` / `implements` 类型 (简单类名，去掉泛型参数与包名) - v9.6
+    fn supertypes_of(decl: Node, code: &str) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut cursor = decl.walk();
+        for clause in decl.named_children(&mut cursor) {
+            if !matches!(clause.kind(), "superclass" | "super_interfaces" | "extends_interfaces") {
+                continue;
+            }
+            let mut stack = vec![clause];
+            while let Some(node) = stack.pop() {
+                if matches!(node.kind(), "type_identifier" | "generic_type" | "scoped_type_identifier") {
+                    let text = node.utf8_text(code.as_bytes()).unwrap_or("");
+                    let base = text.split('<').next().unwrap_or(text);
+                    names.push(base.rsplit('.').next().unwrap_or(base).trim().to_string());
+                    continue;
+                }
+                let mut inner = node.walk();
+                stack.extend(node.named_children(&mut inner));
+            }
+        }
+        names
+    }
+
     /// 编译结构化查询 (Phase 1)
     fn compile_structure_query(language: &tree_sitter::Language) -> Result<Query> {
         let query_str = r#"
             (class_declaration 
-                name: (identifier) @class_name
                 (modifiers (marker_annotation name: (identifier) @class_ann))?
+                name: (identifier) @class_name
             )
             (interface_declaration 
-                name: (identifier) @iface_name
                 (modifiers (marker_annotation name: (identifier) @iface_ann))?
+                name: (identifier) @iface_name
             )
             (field_declaration
                 (modifiers (marker_annotation name: (identifier) @field_ann))?
@@ -711,14 +1406,7 @@ impl JavaTreeSitterAnalyzer {
         let query_str = r#"
             (method_declaration
                 name: (identifier) @caller_method
-                body: (block
-                    (expression_statement
-                        (method_invocation
-                            object: (identifier) @receiver
-                            name: (identifier) @callee_method
-                        ) @call
-                    )
-                )
+                body: (block) @body
             )
         "#;
         Query::new(language, query_str).map_err(|e| anyhow!("Failed to compile call site query: {e}"))
@@ -828,6 +1516,11 @@ impl JavaTreeSitterAnalyzer {
         })
     }
 
+    /// 解析源码 (v9.6: 供 native-config 等需要自行遍历语法树的模块复用线程本地 Parser)
+    pub fn parse(&self, code: &str) -> Result<Tree> {
+        with_parser(&self.language, |parser| parser.parse(code, None).ok_or_else(|| anyhow!("Failed to parse code")))
+    }
+
     /// 从已解析的 Tree 中提取符号 (支持单次解析优化)
     /// 
     /// v9.6: Now also extracts package declaration and builds ImportIndex
@@ -847,38 +1540,38 @@ impl JavaTreeSitterAnalyzer {
 
         for m in matches {
             // Class/Interface Declaration
-            if let Some(idx) = self.structure_query.capture_index_for_name("class_name")
-                .or_else(|| self.structure_query.capture_index_for_name("iface_name")) {
-                
-                for capture in m.captures {
-                    if capture.index == idx {
-                        let name = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
-                        if type_info.is_none() {
-                            // v9.6: Create TypeInfo with package for proper FQN
-                            type_info = Some(TypeInfo::new_with_package(
-                                &name,
-                                package.as_deref(),
-                                file_path.to_path_buf(),
-                                capture.node.start_position().row + 1,
-                            ));
-                            // Add local class to ImportIndex for same-package resolution
-                            import_index.add_local_class(&name);
-                        }
+            // v9.6: class 与 interface 各自有 capture，不能用 or_else 只取其一
+            let name_idx = [self.structure_query.capture_index_for_name("class_name"),
+                            self.structure_query.capture_index_for_name("iface_name")];
+            for capture in m.captures {
+                if name_idx.contains(&Some(capture.index)) && type_info.is_none() {
+                    let name = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+                    // v9.6: Create TypeInfo with package for proper FQN
+                    let mut info = TypeInfo::new_with_package(
+                        &name,
+                        package.as_deref(),
+                        file_path.to_path_buf(),
+                        capture.node.start_position().row + 1,
+                    );
+                    if let Some(decl) = capture.node.parent() {
+                        info.supertypes = Self::supertypes_of(decl, code);
                     }
+                    type_info = Some(info);
+                    // Add local class to ImportIndex for same-package resolution
+                    import_index.add_local_class(&name);
                 }
             }
-            
+
             // Annotations (Add to TypeInfo)
-            if let Some(idx) = self.structure_query.capture_index_for_name("class_ann")
-                .or_else(|| self.structure_query.capture_index_for_name("iface_ann")) {
-                 for capture in m.captures {
-                    if capture.index == idx {
-                        let ann = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
-                        if let Some(info) = &mut type_info {
-                            info.add_annotation(&ann);
-                        }
+            let ann_idx = [self.structure_query.capture_index_for_name("class_ann"),
+                           self.structure_query.capture_index_for_name("iface_ann")];
+            for capture in m.captures {
+                if ann_idx.contains(&Some(capture.index)) {
+                    let ann = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+                    if let Some(info) = &mut type_info {
+                        info.add_annotation(&ann);
                     }
-                 }
+                }
             }
 
             // Fields
@@ -932,7 +1625,14 @@ impl JavaTreeSitterAnalyzer {
         for m in matches {
             for capture in m.captures {
                 if let Ok(text) = capture.node.utf8_text(code.as_bytes()) {
-                    imports.push(text.to_string());
+                    // `import com.acme.*;` 的 `*` 是独立节点，补回后 ImportIndex 才会按通配符处理
+                    let declaration = capture.node.parent();
+                    let wildcard = declaration.is_some_and(|d| {
+                        let mut cursor = d.walk();
+                        let has_asterisk = d.children(&mut cursor).any(|c| c.kind() == "asterisk");
+                        has_asterisk
+                    });
+                    imports.push(if wildcard { format!("{text}.*") } else { text.to_string() });
                 }
             }
         }
@@ -940,6 +1640,52 @@ impl JavaTreeSitterAnalyzer {
         Ok(imports)
     }
 
+    /// 提取方法声明 (返回类型、参数、注解)，用于 SymbolTable 方法注册 - v9.6
+    ///
+    /// 注解保留参数原文 (不含 `@`)，如 `Query("select o from Order o")`
+    pub fn extract_methods(&self, code: &str, class: &str) -> Result<Vec<MethodInfo>> {
+        with_parser(&self.language, |parser| {
+            let tree = parser.parse(code, None).ok_or_else(|| anyhow!("Failed to parse code"))?;
+            let text = |n: Node| n.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+            let mut methods = Vec::new();
+            let mut stack = vec![tree.root_node()];
+            while let Some(node) = stack.pop() {
+                if node.kind() == "method_declaration" {
+                    let Some(name) = node.child_by_field_name("name") else { continue };
+                    let mut method = MethodInfo {
+                        name: text(name),
+                        class: class.to_string(),
+                        return_type: node.child_by_field_name("type").map(text),
+                        params: Vec::new(),
+                        annotations: Vec::new(),
+                        line: node.start_position().row + 1,
+                    };
+                    if let Some(params) = node.child_by_field_name("parameters") {
+                        let mut cursor = params.walk();
+                        for param in params.named_children(&mut cursor).filter(|p| p.kind() == "formal_parameter") {
+                            if let (Some(name), Some(type_name)) = (param.child_by_field_name("name"), param.child_by_field_name("type")) {
+                                method.params.push(ParamInfo { name: text(name), type_name: text(type_name) });
+                            }
+                        }
+                    }
+                    let mut cursor = node.walk();
+                    for modifiers in node.children(&mut cursor).filter(|c| c.kind() == "modifiers") {
+                        let mut cursor = modifiers.walk();
+                        method.annotations.extend(modifiers.named_children(&mut cursor)
+                            .filter(|a| matches!(a.kind(), "annotation" | "marker_annotation"))
+                            .map(|a| text(a).trim_start_matches('@').to_string()));
+                    }
+                    methods.push(method);
+                    continue;
+                }
+                let mut cursor = node.walk();
+                stack.extend(node.named_children(&mut cursor));
+            }
+            methods.sort_by_key(|m| m.line);
+            Ok(methods)
+        })
+    }
+
     /// 提取调用点信息 (用于 CallGraph 构建) - v9.4
     /// 
     /// 返回: Vec<(caller_method, receiver, callee_method, line)>
@@ -957,39 +1703,57 @@ impl JavaTreeSitterAnalyzer {
         let matches = query_cursor.matches(&self.call_site_query, tree.root_node(), code.as_bytes());
 
         let caller_idx = self.call_site_query.capture_index_for_name("caller_method");
-        let receiver_idx = self.call_site_query.capture_index_for_name("receiver");
-        let callee_idx = self.call_site_query.capture_index_for_name("callee_method");
-        let call_idx = self.call_site_query.capture_index_for_name("call");
+        let body_idx = self.call_site_query.capture_index_for_name("body");
 
         for m in matches {
-            let mut caller_method = String::new();
-            let mut receiver = String::new();
-            let mut callee_method = String::new();
-            let mut line = 0;
-
-            for capture in m.captures {
-                if Some(capture.index) == caller_idx {
-                    caller_method = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
-                }
-                if Some(capture.index) == receiver_idx {
-                    receiver = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
-                }
-                if Some(capture.index) == callee_idx {
-                    callee_method = capture.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
-                }
-                if Some(capture.index) == call_idx {
-                    line = capture.node.start_position().row + 1;
+            let caller = m.captures.iter().find(|c| Some(c.index) == caller_idx);
+            let body = m.captures.iter().find(|c| Some(c.index) == body_idx);
+            let (Some(caller), Some(body)) = (caller, body) else { continue };
+            let caller_method = caller.node.utf8_text(code.as_bytes()).unwrap_or("").to_string();
+
+            // v9.6: 遍历整个方法体 (循环/return/赋值中的调用也计入调用图)
+            let mut stack = vec![body.node];
+            while let Some(node) = stack.pop() {
+                if matches!(node.kind(), "method_invocation" | "method_reference") {
+                    if let Some(site) = Self::call_site_of(node, code) {
+                        call_sites.push((caller_method.clone(), site.0, site.1, node.start_position().row + 1));
+                    }
                 }
-            }
-
-            if !caller_method.is_empty() && !callee_method.is_empty() {
-                call_sites.push((caller_method, receiver, callee_method, line));
+                // 逆序入栈 → 先序遍历，保持源码顺序
+                let mut cursor = node.walk();
+                let children: Vec<Node> = node.children(&mut cursor).collect();
+                stack.extend(children.into_iter().rev());
             }
         }
-
         Ok(call_sites)
     }
 
+    /// `recv.m(..)` / `this.recv.m(..)` / `recv::m` → (receiver, method)；链式调用不计入
+    ///
+    /// v9.6: 无接收者的 `m(..)`、`this.m(..)` 与 `this::m` 记为 [`SELF_RECEIVER`] (调用本类方法)
+    fn call_site_of(node: Node, code: &str) -> Option<(String, String)> {
+        let (object, name) = if node.kind() == "method_reference" {
+            let mut cursor = node.walk();
+            let children: Vec<Node> = node.named_children(&mut cursor).collect();
+            let name = children.last().filter(|n| n.kind() == "identifier" && children.len() > 1)?;
+            (children.first().copied(), *name)
+        } else {
+            (node.child_by_field_name("object"), node.child_by_field_name("name")?)
+        };
+        let receiver = match object {
+            None => SELF_RECEIVER,
+            Some(object) => match object.kind() {
+                "this" => SELF_RECEIVER,
+                "identifier" => object.utf8_text(code.as_bytes()).ok()?,
+                "field_access" if object.child_by_field_name("object")?.kind() == "this" => {
+                    object.child_by_field_name("field")?.utf8_text(code.as_bytes()).ok()?
+                }
+                _ => return None,
+            },
+        };
+        Some((receiver.to_string(), name.utf8_text(code.as_bytes()).ok()?.to_string()))
+    }
+
     /// Phase 2: 深度分析 (带上下文，使用 thread_local Parser)
     /// 
     /// v9.4: 添加 call_graph 参数用于 N+1 验证增强
@@ -1006,6 +1770,22 @@ impl JavaTreeSitterAnalyzer {
         })
     }
 
+    /// Phase 2 + 方法度量 (v9.6): 同一棵语法树上同时计算 `metrics::MethodMetrics`，不重复解析
+    pub fn analyze_with_metrics(
+        &self,
+        code: &str,
+        file_path: &Path,
+        symbol_table: Option<&SymbolTable>,
+        call_graph: Option<&crate::taint::CallGraph>,
+    ) -> Result<(Vec<Issue>, Vec<MethodMetrics>)> {
+        with_parser(&self.language, |parser| {
+            let tree = parser.parse(code, None).ok_or_else(|| anyhow!("Failed to parse code"))?;
+            let issues = self.analyze_tree_with_context(&tree, code, file_path, symbol_table, call_graph)?;
+            let file = file_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+            Ok((issues, metrics::collect(tree.root_node(), code, &file)))
+        })
+    }
+
     /// 从已解析的 Tree 中进行深度分析 (支持单次解析优化)
     /// v9.4: 添加 call_graph 参数
     fn analyze_tree_with_context(
@@ -1031,8 +1811,18 @@ impl JavaTreeSitterAnalyzer {
             call_graph,
         };
 
+        // v9.6: 计时开启时先在本地累计，文件结束后一次性合并 (减少锁竞争)
+        let mut local_timings: Vec<(&'static str, Duration)> = Vec::new();
+
+        // v9.6: Redis 规则组只在符号表中出现 Redis 客户端类型时启用 (单文件扫描由处理器按声明类型判断)
+        let redis_pack = symbol_table.is_none_or(RedisHandler::client_present);
+
         // 使用预编译的查询 (不再每次编译)
         for rule in &self.compiled_rules {
+            if !redis_pack && REDIS_RULES.contains(&rule.id) {
+                continue;
+            }
+            let started = self.rule_timings.is_some().then(Instant::now);
             let mut query_cursor = QueryCursor::new();
             let matches = query_cursor.matches(&rule.query, root_node, code.as_bytes());
 
@@ -1049,6 +1839,17 @@ impl JavaTreeSitterAnalyzer {
                     issues.push(issue);
                 }
             }
+
+            if let Some(started) = started {
+                local_timings.push((rule.id, started.elapsed()));
+            }
+        }
+
+        if let Some(timings) = &self.rule_timings {
+            let mut global = timings.lock().unwrap_or_else(|e| e.into_inner());
+            for (id, elapsed) in local_timings {
+                *global.entry(id).or_default() += elapsed;
+            }
         }
 
         // 应用规则抑制机制 - 过滤被抑制的问题
@@ -1072,6 +1873,7 @@ impl JavaTreeSitterAnalyzer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use super::super::rule_handlers::{PairedCall, PairedCallHandler, LOCK_PAIR};
     use std::path::PathBuf;
 
     #[test]
@@ -1100,6 +1902,146 @@ mod tests {
         assert!(issues[1].context.as_ref().unwrap().contains("findById"));
     }
 
+    /// 自定义处理器: 只报告 CorpDao 调用
+    struct CorpDaoHandler;
+
+    impl RuleHandler for CorpDaoHandler {
+        fn handle(
+            &self,
+            query: &Query,
+            m: &tree_sitter::QueryMatch,
+            rule_id: &str,
+            severity: Severity,
+            description: &str,
+            ctx: &RuleContext,
+        ) -> Option<Issue> {
+            let call = m.captures.iter().find(|c| query.capture_names()[c.index as usize] == "call")?;
+            let text = call.node.utf8_text(ctx.code.as_bytes()).ok()?;
+            if !text.starts_with("corpDao.") {
+                return None;
+            }
+            let span = super::super::Span::from_node(&call.node);
+            Some(Issue {
+                id: rule_id.to_string(),
+                severity,
+                file: "Test.java".to_string(),
+                line: span.line,
+                column: span.column,
+                end_line: span.end_line,
+                end_column: span.end_column,
+                description: description.to_string(),
+                context: Some(text.to_string()),
+                confidence: None,
+            })
+        }
+    }
+
+    #[test]
+    fn test_builder_custom_rule() {
+        let code = r#"
+            public class Test {
+                void f() {
+                    corpDao.load(1);
+                    other.load(2);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::builder()
+            .with_rule(
+                "CORP_DAO_CALL",
+                Severity::P1,
+                "(method_invocation object: (identifier) name: (identifier)) @call",
+                "CorpDao 调用",
+                Box::new(CorpDaoHandler),
+            )
+            .build()
+            .unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Test.java")).unwrap();
+        let custom: Vec<_> = issues.iter().filter(|i| i.id == "CORP_DAO_CALL").collect();
+        assert_eq!(custom.len(), 1);
+        assert_eq!(custom[0].line, 4);
+    }
+
+    #[test]
+    fn test_builder_overrides_builtin_handler() {
+        struct NoopHandler;
+        impl RuleHandler for NoopHandler {
+            fn handle(
+                &self,
+                _: &Query,
+                _: &tree_sitter::QueryMatch,
+                _: &str,
+                _: Severity,
+                _: &str,
+                _: &RuleContext,
+            ) -> Option<Issue> {
+                None
+            }
+        }
+
+        let code = "class T { void f() { for (int i = 0; i < 3; i++) { dao.find(i); } } }";
+        let analyzer = JavaTreeSitterAnalyzer::builder()
+            .with_handler("N_PLUS_ONE", Box::new(NoopHandler))
+            .build()
+            .unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("T.java")).unwrap();
+        assert!(issues.iter().all(|i| i.id != "N_PLUS_ONE"));
+    }
+
+    #[test]
+    fn test_builder_rejects_invalid_custom_query() {
+        let result = JavaTreeSitterAnalyzer::builder()
+            .with_rule("BROKEN", Severity::P1, "(not_a_node) @x", "broken", Box::new(CorpDaoHandler))
+            .build();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_rule_timing() {
+        let code = "public class T { void f() { for (int i = 0; i < 3; i++) { userDao.findById(i); } } }";
+        let file = PathBuf::from("T.java");
+
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        analyzer.analyze_with_context(code, &file, None, None).unwrap();
+        assert!(analyzer.rule_timings().is_empty());
+
+        let timed = JavaTreeSitterAnalyzer::builder().with_rule_timing(true).build().unwrap();
+        timed.analyze_with_context(code, &file, None, None).unwrap();
+        let timings = timed.rule_timings();
+        assert_eq!(timings.len(), timed.compiled_rules.len());
+        assert!(timings.iter().any(|(id, _)| *id == "N_PLUS_ONE"));
+        assert!(timings.windows(2).all(|w| w[0].1 >= w[1].1));
+    }
+
+    #[test]
+    fn test_all_rules_have_english_description() {
+        use crate::i18n::{lookup_in, Lang};
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        for rule in &analyzer.compiled_rules {
+            assert!(
+                lookup_in(Lang::En, &format!("rule.{}", rule.id)).is_some(),
+                "missing English description for {}",
+                rule.id
+            );
+        }
+    }
+
+    #[test]
+    fn test_issue_span_from_node() {
+        let code = "public class Test {\n    void run() {\n        for (int i = 0; i < 3; i++) {\n            userDao.findById(i);\n        }\n    }\n}\n";
+
+        let file = PathBuf::from("Test.java");
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &file).unwrap();
+
+        let issue = issues.iter().find(|i| i.id == "N_PLUS_ONE").expect("N_PLUS_ONE expected");
+        assert_eq!(issue.line, 4);
+        assert_eq!(issue.column, 13);
+        assert_eq!(issue.end_line, 4);
+        // `userDao.findById(i)` 共 19 字节，end_column 为开区间
+        assert_eq!(issue.end_column, 13 + 19);
+    }
+
     #[test]
     fn test_extract_call_sites() {
         let code = r#"
@@ -1138,6 +2080,33 @@ mod tests {
         assert_eq!(call_sites[2].2, "save");
     }
 
+    #[test]
+    fn test_extract_self_call_sites() {
+        let code = r#"
+            public class OrderService {
+                public void process(List<Order> orders) {
+                    validate(orders);
+                    this.audit();
+                    orders.forEach(this::save);
+                    orders.stream().map(Order::getId);
+                    repo.find().size();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let call_sites = analyzer.extract_call_sites(code, &PathBuf::from("OrderService.java")).unwrap();
+        let sites: Vec<(&str, &str)> = call_sites.iter().map(|s| (s.1.as_str(), s.2.as_str())).collect();
+        assert_eq!(sites, vec![
+            (SELF_RECEIVER, "validate"),
+            (SELF_RECEIVER, "audit"),
+            ("orders", "forEach"),
+            (SELF_RECEIVER, "save"),
+            ("orders", "stream"),
+            ("Order", "getId"),
+            ("repo", "find"),
+        ]);
+    }
+
     #[test]
     fn test_nested_loop_detection() {
         let code = r#"
@@ -1361,6 +2330,77 @@ mod tests {
         assert!(!issues.iter().any(|i| i.id == "LOCK_METHOD_CALL"), "Should NOT detect when unlock() is in finally");
     }
 
+    #[test]
+    fn test_reentrant_lock_finally_structure() {
+        let code = r#"
+            public class Ledger {
+                void nested() {
+                    outer.lock();
+                    try {
+                        inner.lock();
+                        try {
+                            post();
+                        } finally {
+                            outer.unlock();
+                        }
+                    } finally {
+                        inner.unlock();
+                    }
+                }
+
+                void enclosing() {
+                    try {
+                        lock.lock();
+                        post();
+                    } finally {
+                        lock.unlock();
+                    }
+                }
+
+                void earlyReturn(Entry e) {
+                    lock.lock();
+                    if (e == null) {
+                        return;
+                    }
+                    try {
+                        post();
+                    } finally {
+                        lock.unlock();
+                    }
+                }
+
+                void unrelatedFinally() {
+                    try {
+                        load();
+                    } finally {
+                        lock.unlock();
+                    }
+                    lock.lock();
+                    post();
+                }
+
+                void resource() {
+                    try (LockGuard guard = locks.lock()) {
+                        post();
+                    }
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Ledger.java")).unwrap();
+        let locks: Vec<_> = issues.iter()
+            .filter(|i| i.id == "LOCK_METHOD_CALL")
+            .map(|i| (i.line, i.context.clone().unwrap_or_default(), i.description.clone()))
+            .collect();
+
+        // outer.unlock() 在内层 try 的 finally 中，内层 lock() 之前抛异常时不会执行；inner 由外层 finally 保护
+        assert_eq!(locks.iter().map(|(line, var, _)| (*line, var.as_str())).collect::<Vec<_>>(), vec![
+            (4, "outer"), (27, "lock"), (44, "lock"),
+        ]);
+        assert!(locks[0].2.contains("unlock() not in finally of the try guarding lock()"));
+        assert!(locks[1].2.contains("return/throw before try"));
+    }
+
     // ====== v7.0 AST 迁移规则测试 ======
 
     #[test]
@@ -1615,6 +2655,1212 @@ mod tests {
         assert!(issues.iter().any(|i| i.id == "LIKE_LEADING_WILDCARD"), "Should detect LIKE '%' leading wildcard");
     }
 
+    #[test]
+    fn test_filter_direct_db_and_http_calls() {
+        let code = r#"
+            public class AuthFilter extends OncePerRequestFilter {
+                private final TokenRepository tokenRepository;
+                private final RestTemplate restTemplate;
+
+                protected void doFilterInternal(HttpServletRequest req, HttpServletResponse res, FilterChain chain) {
+                    Token token = this.tokenRepository.findByValue(req.getHeader("X-Token"));
+                    restTemplate.getForObject("http://audit/log", String.class);
+                    chain.doFilter(req, res);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("AuthFilter.java")).unwrap();
+
+        let db = issues.iter().find(|i| i.id == "FILTER_DB_CALL").expect("FILTER_DB_CALL");
+        assert_eq!(db.line, 6);
+        assert_eq!(db.context.as_deref(), Some("doFilterInternal(): tokenRepository.findByValue()"));
+        let http = issues.iter().find(|i| i.id == "FILTER_HTTP_CALL").expect("FILTER_HTTP_CALL");
+        assert_eq!(http.context.as_deref(), Some("doFilterInternal(): restTemplate.getForObject()"));
+    }
+
+    #[test]
+    fn test_filter_rules_require_filter_type() {
+        // 同名方法但不是 Filter/Interceptor → 不报告
+        let code = r#"
+            public class ReportService {
+                private OrderRepository orderRepository;
+                public void filter(Long id) { orderRepository.findById(id); }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("ReportService.java")).unwrap();
+        assert!(!issues.iter().any(|i| i.id.starts_with("FILTER_")));
+
+        // Interceptor 但只做内存操作 → 不报告
+        let code = r#"
+            public class TraceInterceptor implements HandlerInterceptor {
+                public boolean preHandle(HttpServletRequest req, HttpServletResponse res, Object handler) {
+                    req.setAttribute("start", System.nanoTime());
+                    return true;
+                }
+            }
+        "#;
+        let issues = analyzer.analyze(code, &PathBuf::from("TraceInterceptor.java")).unwrap();
+        assert!(!issues.iter().any(|i| i.id.starts_with("FILTER_")));
+    }
+
+    #[test]
+    fn test_filter_db_call_via_call_graph() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, code: &str| std::fs::write(dir.path().join(name), code).unwrap();
+        write("TenantRepository.java", "package com.acme;\n@Repository\npublic interface TenantRepository { Tenant findByHost(String host); }\n");
+        write("TenantService.java", "package com.acme;\n@Service\npublic class TenantService {\n    private TenantRepository tenantRepository;\n    public Tenant resolve(String host) { return tenantRepository.findByHost(host); }\n}\n");
+        let filter = "package com.acme;\npublic class TenantInterceptor implements HandlerInterceptor {\n    private TenantService tenantService;\n    public boolean preHandle(HttpServletRequest req, HttpServletResponse res, Object h) {\n        tenantService.resolve(req.getServerName());\n        return true;\n    }\n}\n";
+        write("TenantInterceptor.java", filter);
+
+        let index = crate::ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let file = dir.path().join("TenantInterceptor.java");
+
+        let issues = analyzer.analyze_with_context(filter, &file, Some(&index.symbol_table), Some(&index.call_graph)).unwrap();
+        let issue = issues.iter().find(|i| i.id == "FILTER_DB_CALL").expect("FILTER_DB_CALL via call graph");
+        assert_eq!(issue.line, 4);
+        assert_eq!(
+            issue.context.as_deref(),
+            Some("preHandle(): [调用链: TenantInterceptor.preHandle → TenantService.resolve → TenantRepository.findByHost]")
+        );
+
+        // 无上下文时无法确认间接调用
+        let issues = analyzer.analyze(filter, &file).unwrap();
+        assert!(!issues.iter().any(|i| i.id == "FILTER_DB_CALL"));
+    }
+
+    #[test]
+    fn test_unbounded_query_result() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, code: &str| std::fs::write(dir.path().join(name), code).unwrap();
+        write("OrderRepository.java", r#"package com.acme;
+@Repository
+public interface OrderRepository {
+    List<Order> findByStatus(String status);
+    List<Order> findByStatus(String status, Pageable pageable);
+    List<Order> findTop20ByCustomer(String customer);
+    @Query("select o from Order o order by o.createdAt desc limit 50")
+    List<Order> findRecent();
+    Order findByCode(String code);
+}
+"#);
+        let service = r#"package com.acme;
+@Service
+public class OrderService {
+    private OrderRepository orderRepository;
+    public List<Order> byStatus(String status) { return orderRepository.findByStatus(status); }
+    public List<Order> page(String status, Pageable p) { return orderRepository.findByStatus(status, p); }
+    public List<Order> top(String c) { return orderRepository.findTop20ByCustomer(c); }
+    public List<Order> recent() { return orderRepository.findRecent(); }
+    public List<Order> nightly(String status) { return orderRepository.findByStatus(status); }
+}
+"#;
+        write("OrderService.java", service);
+        write("OrderController.java", r#"package com.acme;
+@RestController
+public class OrderController {
+    private OrderService orderService;
+    @GetMapping("/orders")
+    public List<Order> list(String status) {
+        orderService.page(status, PageRequest.of(0, 20));
+        orderService.top(status);
+        orderService.recent();
+        return orderService.byStatus(status);
+    }
+}
+"#);
+
+        let index = crate::ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        // Phase 1 注册方法返回类型与参数 (含重载)
+        let overloads = index.symbol_table.lookup_methods("OrderRepository", "findByStatus");
+        assert_eq!(overloads.len(), 2);
+        assert_eq!(overloads[0].return_type.as_deref(), Some("List<Order>"));
+        assert_eq!(overloads[1].params[1].type_name, "Pageable");
+
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let file = dir.path().join("OrderService.java");
+        let issues = analyzer.analyze_with_context(service, &file, Some(&index.symbol_table), Some(&index.call_graph)).unwrap();
+        let unbounded: Vec<_> = issues.iter().filter(|i| i.id == "UNBOUNDED_QUERY_RESULT").collect();
+
+        // 分页参数 / findTop / 注解 LIMIT 不报告；nightly() 不在 Controller 调用链上
+        assert_eq!(unbounded.len(), 1);
+        assert_eq!(unbounded[0].line, 5);
+        assert_eq!(unbounded[0].confidence, Some(crate::scanner::Confidence::High));
+        assert_eq!(
+            unbounded[0].context.as_deref(),
+            Some("orderRepository.findByStatus() 返回 List<Order>，无 Pageable/LIMIT [调用链: OrderController.list → OrderService.byStatus → OrderRepository.findByStatus]")
+        );
+
+        // Controller 直接调用继承的 findAll()，无需调用图
+        let controller = r#"
+            @RestController
+            public class CustomerController {
+                private CustomerRepository customerRepository;
+                @GetMapping("/customers")
+                public List<Customer> all() { return customerRepository.findAll(); }
+            }
+        "#;
+        let mut table = SymbolTable::new();
+        let (type_info, bindings, _) = analyzer.extract_symbols(controller, &PathBuf::from("CustomerController.java")).unwrap();
+        table.register_class_fqn(type_info.unwrap());
+        for binding in bindings {
+            table.register_field("CustomerController", binding);
+        }
+        let issues = analyzer.analyze_with_context(controller, &PathBuf::from("CustomerController.java"), Some(&table), None).unwrap();
+        let issue = issues.iter().find(|i| i.id == "UNBOUNDED_QUERY_RESULT").expect("findAll from controller");
+        assert_eq!(issue.confidence, Some(crate::scanner::Confidence::Medium));
+        assert_eq!(
+            issue.context.as_deref(),
+            Some("customerRepository.findAll() 返回 List，无 Pageable/LIMIT [调用链: CustomerController.all → CustomerRepository.findAll]")
+        );
+    }
+
+    #[test]
+    fn test_listener_rules() {
+        let code = r#"
+            public class OrderConsumer {
+                private final List<OrderEvent> pending = new ArrayList<>();
+                private final RestTemplate restTemplate;
+                private final OrderRepository orderRepository;
+
+                @Transactional
+                @KafkaListener(topics = "orders")
+                public void onOrder(OrderEvent event) {
+                    pending.add(event);
+                    restTemplate.postForObject("http://billing/charge", event, Void.class);
+                }
+
+                @RabbitListener(queues = "audit")
+                public void onAudit(AuditEvent event) {
+                    orderRepository.save(event.toEntity());
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderConsumer.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+
+        let blocking = find("LISTENER_BLOCKING_CALL");
+        assert_eq!(blocking.len(), 1);
+        assert_eq!(blocking[0].line, 11);
+        assert_eq!(blocking[0].context.as_deref(), Some("onOrder(): restTemplate.postForObject()"));
+
+        let buffer = find("LISTENER_UNBOUNDED_BUFFER");
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer[0].line, 10);
+
+        let tx = find("LISTENER_TRANSACTIONAL");
+        assert_eq!(tx.len(), 1);
+        assert_eq!(tx[0].line, 9);
+
+        // onOrder 阻塞、onAudit 写库，两者都没有手动 ack
+        let ack = find("LISTENER_AUTO_ACK_SLOW");
+        assert_eq!(ack.len(), 2);
+        assert!(ack.iter().any(|i| i.context.as_deref() == Some("onAudit(): orderRepository.save() (无 Acknowledgment/Channel 参数)")));
+    }
+
+    #[test]
+    fn test_listener_rules_negative() {
+        let code = r#"
+            public class BatchConsumer {
+                private final BlockingQueue<Event> queue = new LinkedBlockingQueue<>(10_000);
+                private final List<Event> batch = new ArrayList<>();
+                private final EventRepository eventRepository;
+
+                @KafkaListener(topics = "events")
+                public void onEvent(Event event, Acknowledgment ack) {
+                    queue.offer(event);
+                    batch.add(event);
+                    if (batch.size() >= 500) {
+                        eventRepository.saveAll(batch);
+                        batch.clear();
+                    }
+                    ack.acknowledge();
+                }
+
+                public void notAListener(Event event) {
+                    Thread.sleep(1000);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("BatchConsumer.java")).unwrap();
+        assert!(!issues.iter().any(|i| i.id.starts_with("LISTENER_")), "{issues:?}");
+    }
+
+    #[test]
+    fn test_regex_rules() {
+        let code = r#"
+            public class InputValidator {
+                private static final String NAME_REGEX = "^(\\w+\\s?)*$";
+                private static final Pattern EMAIL = Pattern.compile("^[\\w.+-]+@[\\w-]+\\.[a-z]{2,}$");
+
+                public boolean validName(String name) {
+                    return Pattern.compile(NAME_REGEX).matcher(name).matches();
+                }
+
+                public boolean token(String s) {
+                    return s.matches("(\\d|\\w)+$");
+                }
+
+                @GetMapping("/search")
+                public List<String> search(String q) {
+                    Pattern p = Pattern.compile(q);
+                    return List.of();
+                }
+
+                public int count(List<String> lines) {
+                    int n = 0;
+                    for (String line : lines) {
+                        if (Pattern.compile("ERROR").matcher(line).find()) n++;
+                    }
+                    return n;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("InputValidator.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+
+        let redos = find("REGEX_REDOS");
+        assert_eq!(redos.len(), 2, "{redos:?}");
+        assert_eq!(redos[0].line, 7);
+        assert_eq!(redos[0].context.as_deref(), Some("嵌套量词: (\\w+\\s?)* (正则: ^(\\w+\\s?)*$)"));
+        assert_eq!(redos[0].confidence, Some(crate::scanner::Confidence::High));
+        assert_eq!(redos[1].line, 11);
+        assert!(redos[1].context.as_deref().unwrap().starts_with("重叠分支: (\\d|\\w)+"));
+
+        // 静态字段初始化只执行一次，不报告；validName 不在热路径
+        let hot = find("PATTERN_COMPILE_HOT_PATH");
+        assert_eq!(hot.iter().map(|i| i.line).collect::<Vec<_>>(), vec![16, 23]);
+        assert_eq!(hot[0].context.as_deref(), Some("search(): 请求处理方法"));
+        assert_eq!(hot[1].context.as_deref(), Some("循环内"));
+    }
+
+    #[test]
+    fn test_collection_misuse_rules() {
+        let code = r#"
+            @Service
+            public class OrderService {
+                private final Map<String, Order> cache = Collections.synchronizedMap(new HashMap<>());
+                private final LinkedList<Order> recent = new LinkedList<>();
+
+                public List<Order> filter(List<Order> orders, List<String> blocked) {
+                    List<Order> result = new ArrayList<>();
+                    for (Order o : orders) {
+                        if (!blocked.contains(o.getId())) result.add(o);
+                    }
+                    return orders.stream().filter(o -> blocked.contains(o.getId())).toList();
+                }
+
+                public List<Order> copy(List<Order> orders) {
+                    List<Order> all = new ArrayList<>();
+                    all.addAll(orders);
+                    Map<String, Order> byId = new HashMap<>();
+                    for (Order o : orders) {
+                        byId.put(o.getId(), o);
+                    }
+                    return all;
+                }
+
+                public long total() {
+                    long sum = 0;
+                    for (int i = 0; i < recent.size(); i++) {
+                        sum += recent.get(i).getAmount();
+                    }
+                    return sum;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderService.java")).unwrap();
+        let lines = |id: &str| issues.iter().filter(|i| i.id == id).map(|i| i.line).collect::<Vec<_>>();
+
+        assert_eq!(lines("LIST_CONTAINS_IN_LOOP"), vec![10, 12]);
+        // result 在循环中按条件 add，大小未知，不报告
+        assert_eq!(lines("COLLECTION_NO_CAPACITY"), vec![16, 18]);
+        assert_eq!(lines("LINKEDLIST_RANDOM_ACCESS"), vec![28]);
+        assert_eq!(lines("SYNCHRONIZED_MAP"), vec![4]);
+
+        let capacity = issues.iter().find(|i| i.id == "COLLECTION_NO_CAPACITY").unwrap();
+        assert_eq!(capacity.context.as_deref(), Some("all.addAll(orders) → 改为 new ...(orders)"));
+    }
+
+    #[test]
+    fn test_collection_misuse_rules_negative() {
+        let code = r#"
+            public class Lookup {
+                private final Map<String, String> legacy = Collections.synchronizedMap(new HashMap<>());
+
+                public int count(List<String> names, Set<String> allowed, LinkedList<String> queue) {
+                    int n = 0;
+                    for (String name : names) {
+                        if (allowed.contains(name)) n++;
+                    }
+                    names.contains("x");
+                    queue.getFirst();
+                    return n;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Lookup.java")).unwrap();
+        let ids = ["LIST_CONTAINS_IN_LOOP", "COLLECTION_NO_CAPACITY", "LINKEDLIST_RANDOM_ACCESS", "SYNCHRONIZED_MAP"];
+        assert!(!issues.iter().any(|i| ids.contains(&i.id.as_str())), "{issues:?}");
+    }
+
+    #[test]
+    fn test_serialization_rules() {
+        let code = r#"
+            public class ExportService {
+                private static final ObjectMapper SHARED = new ObjectMapper();
+                private ObjectMapper injected;
+
+                public ExportService() {
+                    this.injected = new ObjectMapper();
+                }
+
+                @Bean
+                public Gson gson() {
+                    return new GsonBuilder().create();
+                }
+
+                public String toJson(Object o) throws Exception {
+                    return new ObjectMapper().writeValueAsString(o);
+                }
+
+                public void export(List<Order> orders, List<Long> ids) throws Exception {
+                    for (Long id : ids) {
+                        Gson gson = new Gson();
+                        log.info(SHARED.writeValueAsString(orders));
+                        log.info(SHARED.writeValueAsString(id));
+                    }
+                }
+            }
+
+            @Entity
+            @JsonIgnoreProperties({"tags"})
+            public class Customer {
+                @OneToMany(mappedBy = "customer", fetch = FetchType.LAZY)
+                private List<Order> orders;
+
+                @JsonManagedReference
+                @OneToMany(mappedBy = "customer")
+                private List<Address> addresses;
+
+                @ManyToMany(mappedBy = "customers")
+                private Set<Tag> tags;
+
+                @ManyToOne
+                private Region region;
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("ExportService.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+
+        let per_call = find("SERIALIZER_PER_CALL");
+        assert_eq!(per_call.iter().map(|i| i.line).collect::<Vec<_>>(), vec![16, 21], "{per_call:?}");
+        assert_eq!(per_call[0].context.as_deref(), Some("new ObjectMapper() toJson(): 每次调用新建"));
+        assert_eq!(per_call[1].context.as_deref(), Some("new Gson() export(): 位于 for 内"));
+
+        let in_loop = find("SERIALIZE_IN_LOOP");
+        assert_eq!(in_loop.len(), 1);
+        assert_eq!(in_loop[0].line, 22);
+        assert_eq!(in_loop[0].context.as_deref(), Some("writeValueAsString(orders) 位于 for 内 (List<Order>)"));
+
+        let cycle = find("JPA_BIDIRECTIONAL_JSON_CYCLE");
+        assert_eq!(cycle.len(), 1);
+        assert_eq!(cycle[0].line, 32);
+        assert_eq!(cycle[0].context.as_deref(), Some("orders: @OneToMany(mappedBy = \"customer\", fetch = FetchType.LAZY)"));
+    }
+
+    #[test]
+    fn test_date_time_rules() {
+        let code = r#"
+            public class BillingService {
+                private static final DateTimeFormatter DAY = DateTimeFormatter.ofPattern("yyyy-MM-dd");
+                private final ZoneId zone = ZoneId.systemDefault();
+
+                public static void main(String[] args) {
+                    TimeZone.setDefault(TimeZone.getDefault());
+                }
+
+                public LocalDate cutoff() {
+                    return LocalDate.now(ZoneId.systemDefault());
+                }
+
+                public int dayOfWeek() {
+                    Calendar cal = Calendar.getInstance();
+                    return cal.get(Calendar.DAY_OF_WEEK);
+                }
+
+                public List<LocalDate> parse(List<String> rows) {
+                    List<LocalDate> out = new ArrayList<>();
+                    for (String row : rows) {
+                        Date seen = new Date();
+                        Date fixed = new Date(0L);
+                        out.add(LocalDate.parse(row, DateTimeFormatter.ofPattern("yyyy-MM-dd")));
+                    }
+                    rows.forEach(r -> audit(new Date(), r));
+                    return out;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("BillingService.java")).unwrap();
+        let lines = |id: &str| issues.iter().filter(|i| i.id == id).map(|i| i.line).collect::<Vec<_>>();
+
+        // 字段初始化与 main 不算业务逻辑
+        assert_eq!(lines("DEFAULT_TIMEZONE"), vec![11]);
+        assert_eq!(lines("CALENDAR_PER_CALL"), vec![15]);
+        // new Date(0L) 有参数、lambda 回调不算紧凑循环
+        assert_eq!(lines("DATE_IN_LOOP"), vec![22]);
+        // 字段常量不报告
+        assert_eq!(lines("DATE_FORMATTER_IN_LOOP"), vec![24]);
+
+        let tz = issues.iter().find(|i| i.id == "DEFAULT_TIMEZONE").unwrap();
+        assert_eq!(tz.context.as_deref(), Some("cutoff(): ZoneId.systemDefault()"));
+    }
+
+    #[test]
+    fn test_thread_creation_rules() {
+        let code = r#"
+            @RestController
+            public class ReportController {
+                private final ScheduledExecutorService ticker = Executors.newScheduledThreadPool(0);
+                private final ScheduledExecutorService ok = new ScheduledThreadPoolExecutor(2);
+
+                @PostMapping("/reports")
+                public void generate(Report report) {
+                    new Thread(() -> render(report)).start();
+                    Thread named = new Thread(() -> render(report));
+                    named.setName("report-render");
+                    named.start();
+                    new Timer("report-timer").schedule(task, 1000);
+                }
+            }
+
+            public class Worker {
+                public void run() {
+                    new Thread(this::loop, "worker").start();
+                    new Thread(this::loop).start();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("ReportController.java")).unwrap();
+        let lines = |id: &str| issues.iter().filter(|i| i.id == id).map(|i| i.line).collect::<Vec<_>>();
+
+        // Worker 无层级注解，不属于请求链路
+        assert_eq!(lines("THREAD_IN_REQUEST_SCOPE"), vec![9, 10, 13]);
+        assert_eq!(lines("THREAD_NO_NAME"), vec![9, 20]);
+        assert_eq!(lines("SCHEDULED_POOL_ZERO_CORE"), vec![4]);
+
+        let scoped = issues.iter().find(|i| i.id == "THREAD_IN_REQUEST_SCOPE").unwrap();
+        assert_eq!(scoped.context.as_deref(), Some("new Thread() generate(): 每次调用新建 (Controller)"));
+    }
+
+    #[test]
+    fn test_future_get_requires_future_receiver() {
+        let code = r#"
+            public class Aggregator {
+                private Future<String> pending;
+                public String run(ExecutorService pool, Optional<String> name, Map<String, String> cache, Supplier<String> s, CompletableFuture<String> cf) throws Exception {
+                    String a = pending.get();
+                    String b = pool.submit(task).get();
+                    String c = cf.get();
+                    String d = CompletableFuture.supplyAsync(s).get();
+                    String e = name.get() + cache.get("k") + s.get();
+                    String f = cf.get(1, TimeUnit.SECONDS);
+                    return load().get();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Aggregator.java")).unwrap();
+        let lines = |id: &str| issues.iter().filter(|i| i.id == id).map(|i| i.line).collect::<Vec<_>>();
+
+        // Optional/Map/Supplier 的 get() 与类型未知的接收者不报告；两条规则互不重叠
+        assert_eq!(lines("FUTURE_GET_NO_TIMEOUT"), vec![5, 6]);
+        assert_eq!(lines("COMPLETABLE_GET_NO_TIMEOUT"), vec![7, 8]);
+        assert_eq!(lines("OPTIONAL_GET_UNCHECKED"), vec![9]);
+    }
+
+    #[test]
+    fn test_optional_get_requires_dominating_check() {
+        let code = r#"
+            public class UserView {
+                public String show(Optional<User> user) {
+                    if (user.isPresent()) { log(user); }
+                    return user.get().getName();
+                }
+                public String guarded(Optional<User> user, Optional<User> other) {
+                    if (user.isEmpty()) return "-";
+                    String a = other.isPresent() && other.get().isActive() ? other.get().getName() : "";
+                    if (!user.isPresent() || user.get().isBlocked()) { return a; } else { return user.get().getName(); }
+                }
+                public String late(Optional<User> user) {
+                    String name = user.get().getName();
+                    if (!user.isPresent()) { return "-"; }
+                    return name;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("UserView.java")).unwrap();
+        let lines = issues.iter().filter(|i| i.id == "OPTIONAL_GET_UNCHECKED").map(|i| i.line).collect::<Vec<_>>();
+
+        // 检查后未受保护的 get()、检查之前的 get() 仍报告
+        assert_eq!(lines, vec![5, 13]);
+    }
+
+    #[test]
+    fn test_optional_stream_rules() {
+        let code = r#"
+            public class OrderQuery {
+                public Order load(Long id) {
+                    return orderRepository.findById(id).get();
+                }
+
+                public String name(Optional<User> user, Optional<User> other) {
+                    if (other.isPresent()) {
+                        log.info(other.get().getName());
+                    }
+                    return user.get().getName() + map.get();
+                }
+
+                public void stats(List<Order> orders) {
+                    boolean any = orders.stream().filter(Order::isPaid).count() > 0;
+                    boolean none = orders.stream().filter(Order::isPaid).count() == 0;
+                    long total = orders.stream().filter(Order::isPaid).count();
+                    int n = orders.stream().map(Order::getId).collect(Collectors.toList()).size();
+                    int distinct = orders.stream().map(Order::getId).collect(Collectors.toSet()).size();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderQuery.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+        let lines = |id: &str| find(id).iter().map(|i| i.line).collect::<Vec<_>>();
+
+        assert_eq!(lines("OPTIONAL_GET_UNCHECKED"), vec![4, 11]);
+        assert_eq!(find("OPTIONAL_GET_UNCHECKED")[1].context.as_deref(), Some("user.get() 前无 isPresent() 检查"));
+
+        let count = find("STREAM_COUNT_FOR_EXISTENCE");
+        assert_eq!(count.iter().map(|i| i.line).collect::<Vec<_>>(), vec![15, 16]);
+        assert!(count[0].context.as_deref().unwrap().ends_with("→ anyMatch(...)"));
+        assert!(count[1].context.as_deref().unwrap().ends_with("→ noneMatch(...)"));
+
+        assert_eq!(lines("STREAM_COLLECT_SIZE"), vec![18]);
+
+        // stats() 内 orders.stream() 出现 5 次
+        let repeated = find("STREAM_REPEATED");
+        assert_eq!(repeated.len(), 1);
+        assert_eq!(repeated[0].line, 15);
+        assert_eq!(repeated[0].context.as_deref(), Some("stats(): orders.stream() × 5 → 合并为一次遍历"));
+    }
+
+    #[test]
+    fn test_java21_syntax_parses_without_errors() {
+        let code = r#"
+            public sealed interface Shape permits Circle, Square {}
+            record Circle(double radius) implements Shape {}
+            record Square(double side) implements Shape {}
+            record Pair<A, B>(A left, B right) {}
+
+            public class Geometry {
+                static final String SQL = """
+                    SELECT id, name
+                    FROM shapes
+                    """;
+
+                double area(Object o) {
+                    if (o instanceof Pair(Circle c, Square s)) {
+                        return c.radius() + s.side();
+                    }
+                    return switch (o) {
+                        case Circle c when c.radius() > 10 -> Math.PI * c.radius() * c.radius();
+                        case Circle c -> 0;
+                        case Square(double side) -> side * side;
+                        case null, default -> 0;
+                    };
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let has_error = with_parser(&analyzer.language, |parser| {
+            Ok(parser.parse(code, None).unwrap().root_node().has_error())
+        }).unwrap();
+        assert!(!has_error, "Java 17/21 syntax should parse cleanly");
+    }
+
+    #[test]
+    fn test_record_and_pattern_switch_rules() {
+        let code = r#"
+            public class Dispatcher {
+                record Batch(String id, List<Item> items, int[] sizes) {}
+                record Safe(List<Item> items) {
+                    Safe {
+                        items = List.copyOf(items);
+                    }
+                }
+
+                void dispatch(List<Shape> shapes) {
+                    for (Shape shape : shapes) {
+                        double a = switch (shape) {
+                            case Circle c -> c.area();
+                            case Square s -> s.area();
+                        };
+                    }
+                    shapes.forEach(s -> handle(switch (s) {
+                        case Circle c -> 1;
+                        default -> 0;
+                    }));
+                }
+
+                double once(Shape shape) {
+                    return switch (shape) {
+                        case Circle c -> c.area();
+                        case Square s -> s.area();
+                    };
+                }
+
+                String sql() {
+                    return """
+                        SELECT *
+                        FROM orders
+                        WHERE name LIKE '%foo'
+                        """;
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Dispatcher.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+
+        let records = find("RECORD_MUTABLE_COMPONENT");
+        assert_eq!(records.iter().map(|i| i.line).collect::<Vec<_>>(), vec![3, 3]);
+        assert_eq!(records[0].context.as_deref(), Some("record Batch(List<Item> items) → 紧凑构造器中 items = List.copyOf(items)"));
+        assert!(records[1].context.as_deref().unwrap().contains("int[] sizes"));
+
+        // 只有 for 循环内无 default 的那一个
+        let switches = find("SWITCH_PATTERN_NO_DEFAULT");
+        assert_eq!(switches.len(), 1);
+        assert_eq!(switches[0].line, 12);
+        assert_eq!(switches[0].context.as_deref(), Some("switch (shape) 位于 for 内: 2 个类型模式, 无 default"));
+
+        // 文本块中的 SQL 折叠为单行上下文
+        let select = find("SELECT_STAR");
+        assert_eq!(select.len(), 1);
+        assert!(select[0].context.as_deref().unwrap().starts_with(r#"""" SELECT * FROM orders"#));
+        assert_eq!(find("LIKE_LEADING_WILDCARD").len(), 1);
+    }
+
+    #[test]
+    fn test_startup_rules() {
+        let code = r#"
+            @SpringBootApplication(scanBasePackages = {"com.acme.order", "com.acme"})
+            @ComponentScan(basePackages = "org.acme.billing", excludeFilters = @Filter(pattern = "com"))
+            public class OrderApplication {
+                private final RestTemplate restTemplate;
+                private final OrderRepository orderRepository;
+
+                @PostConstruct
+                public void warmUp() {
+                    CompletableFuture.runAsync(() -> restTemplate.getForObject("/rates", Map.class));
+                    orderRepository.findAll();
+                }
+
+                @Bean
+                public RestHighLevelClient searchClient() {
+                    return new RestHighLevelClient(builder);
+                }
+
+                @Bean
+                @Lazy
+                public KafkaProducer<String, String> producer() {
+                    return new KafkaProducer<>(props);
+                }
+
+                @Bean
+                public ObjectMapper objectMapper() {
+                    return new ObjectMapper();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderApplication.java")).unwrap();
+        let find = |id: &str| issues.iter().filter(|i| i.id == id).collect::<Vec<_>>();
+
+        // excludeFilters 中的字符串不是扫描包
+        let scans = find("COMPONENT_SCAN_BROAD");
+        assert_eq!(scans.len(), 1);
+        assert_eq!(scans[0].line, 2);
+        assert_eq!(scans[0].confidence, Some(crate::scanner::Confidence::Medium));
+        assert_eq!(scans[0].context.as_deref(), Some("@SpringBootApplication(\"com.acme\") → 扫描 com.acme.*，收窄到应用自身的包"));
+
+        // lambda 中的异步调用不阻塞初始化
+        let remote = find("POSTCONSTRUCT_REMOTE_CALL");
+        assert_eq!(remote.len(), 1);
+        assert_eq!(remote[0].line, 11);
+        assert_eq!(remote[0].context.as_deref(), Some("warmUp(): orderRepository.findAll()"));
+
+        let beans = find("EAGER_HEAVY_BEAN");
+        assert_eq!(beans.iter().map(|i| i.line).collect::<Vec<_>>(), vec![15]);
+        assert_eq!(beans[0].context.as_deref(), Some("@Bean RestHighLevelClient searchClient() → @Lazy"));
+    }
+
+    #[test]
+    fn test_sql_string_concat() {
+        let code = r#"
+            public class OrderDao {
+                private static final String TABLE = "orders";
+
+                public List<Order> byStatus(String status) {
+                    return em.createQuery("select o from Order o where o.status = '" + status + "'").getResultList();
+                }
+
+                public void search(String name, Statement stmt) {
+                    String sql = "SELECT * FROM " + TABLE + " WHERE 1 = 1";
+                    sql += " AND name = '" + name + "'";
+                    stmt.executeQuery(sql);
+                }
+
+                public int count(String region) {
+                    return jdbcTemplate.queryForObject(String.format("SELECT count(*) FROM t WHERE region = '%s'", region), Integer.class);
+                }
+
+                public List<Order> safe(long id) {
+                    jdbcTemplate.update("DELETE FROM " + TABLE + " WHERE id = ?", id);
+                    cache.update("key" + id);
+                    return jdbcTemplate.query("SELECT * FROM orders WHERE id = ?", mapper, id);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderDao.java")).unwrap();
+        let concat: Vec<_> = issues.iter().filter(|i| i.id == "SQL_STRING_CONCAT").collect();
+
+        // 常量拼接 / 非 SQL 字符串 / 参数绑定不报告
+        assert_eq!(concat.iter().map(|i| i.line).collect::<Vec<_>>(), vec![6, 12, 16]);
+        assert_eq!(concat[0].context.as_deref(), Some("createQuery(): SQL 拼接 `status` → 改用 ? / :name 参数绑定"));
+        assert_eq!(concat[0].confidence, Some(crate::scanner::Confidence::High));
+        assert_eq!(concat[1].context.as_deref(), Some("executeQuery(): SQL 拼接 `name` → 改用 ? / :name 参数绑定"));
+        assert_eq!(concat[1].confidence, Some(crate::scanner::Confidence::Medium));
+        assert_eq!(concat[2].context.as_deref(), Some("queryForObject(): SQL 拼接 `region` → 改用 ? / :name 参数绑定"));
+    }
+
+    #[test]
+    fn test_cache_no_expire_chain() {
+        let code = r#"
+            public class CacheConfig {
+                public Cache<String, User> users() {
+                    return Caffeine.newBuilder().recordStats().build();
+                }
+
+                public Caffeine<Object, Object> managed() {
+                    Caffeine<Object, Object> builder = Caffeine.newBuilder();
+                    builder.recordStats();
+                    return builder;
+                }
+
+                public Cache<String, User> sized() {
+                    Caffeine<Object, Object> builder = Caffeine.newBuilder();
+                    builder.maximumSize(1000);
+                    return builder.build();
+                }
+
+                public LoadingCache<String, User> guava() {
+                    return CacheBuilder.newBuilder().expireAfterWrite(10, TimeUnit.MINUTES).build(loader);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("CacheConfig.java")).unwrap();
+        let caches: Vec<_> = issues.iter().filter(|i| i.id == "CACHE_NO_EXPIRE").collect();
+        assert_eq!(caches.iter().map(|i| i.line).collect::<Vec<_>>(), vec![4, 8]);
+        assert_eq!(caches[0].context.as_deref(), Some("Caffeine.newBuilder().recordStats().build()"));
+        assert_eq!(caches[1].context.as_deref(), Some("Caffeine.newBuilder(); builder.recordStats()"));
+        assert!(caches[0].description.ends_with("[调用链: Caffeine.newBuilder().recordStats().build()]"));
+    }
+
+    #[test]
+    fn test_annotation_argument_rules() {
+        let code = r#"
+            public class ReportJob {
+                @Scheduled(fixedRate = 500)
+                public void poll() {}
+
+                @Scheduled(fixedRate = 5, timeUnit = TimeUnit.SECONDS)
+                public void report() {}
+
+                @Scheduled(fixedRateString = "${report.rate}")
+                public void configured() {}
+
+                @Scheduled(fixedDelay = 100)
+                public void delayed() {}
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("ReportJob.java")).unwrap();
+        let rates: Vec<_> = issues.iter().filter(|i| i.id == "SCHEDULED_FIXED_RATE").collect();
+        assert_eq!(rates.iter().map(|i| i.line).collect::<Vec<_>>(), vec![3, 6, 9]);
+        assert_eq!(rates[0].context.as_deref(), Some("fixedRate = 500ms < 1s，上一次执行未结束即再次触发"));
+        assert_eq!(rates[0].confidence, Some(crate::scanner::Confidence::High));
+        assert_eq!(rates[1].context.as_deref(), Some("fixedRate = 5s"));
+        assert_eq!(rates[2].context.as_deref(), Some("fixedRate = \"${report.rate}\""));
+        assert_eq!(rates[2].confidence, Some(crate::scanner::Confidence::Low));
+
+        let dir = tempfile::tempdir().unwrap();
+        let audit = r#"package com.acme;
+@Service
+public class AuditService {
+    @Transactional(propagation = Propagation.REQUIRES_NEW)
+    public void record(String action) {}
+    @Transactional(propagation = Propagation.REQUIRES_NEW, timeout = 5)
+    public void archive() {}
+    @Transactional(propagation = Propagation.REQUIRED)
+    public void touch() {}
+}
+"#;
+        std::fs::write(dir.path().join("AuditService.java"), audit).unwrap();
+        std::fs::write(dir.path().join("OrderService.java"), r#"package com.acme;
+@Service
+public class OrderService {
+    private AuditService auditService;
+    @Transactional
+    public void place(String id) { auditService.record(id); }
+}
+"#).unwrap();
+        let index = crate::ast_engine::index_project(dir.path().to_str().unwrap()).unwrap();
+        let file = dir.path().join("AuditService.java");
+        let issues = analyzer.analyze_with_context(audit, &file, Some(&index.symbol_table), Some(&index.call_graph)).unwrap();
+        let requires_new: Vec<_> = issues.iter().filter(|i| i.id == "TRANSACTIONAL_REQUIRES_NEW").collect();
+        assert_eq!(requires_new.iter().map(|i| i.line).collect::<Vec<_>>(), vec![4, 6]);
+        assert_eq!(
+            requires_new[0].context.as_deref(),
+            Some("propagation = REQUIRES_NEW；在外层事务内调用，挂起期间同时占用两个连接 [调用链: OrderService.place → AuditService.record]；未设置 timeout")
+        );
+        assert_eq!(requires_new[0].confidence, Some(crate::scanner::Confidence::High));
+        assert_eq!(requires_new[1].context.as_deref(), Some("propagation = REQUIRES_NEW；timeout = 5s"));
+        assert_eq!(requires_new[1].confidence, Some(crate::scanner::Confidence::Medium));
+    }
+
+    #[test]
+    fn test_scheduled_rate_reaching_sink() {
+        let code = r#"
+            public class SyncJob {
+                private RestTemplate restTemplate;
+                private OrderRepository orderRepository;
+
+                @Scheduled(fixedRate = 30000)
+                public void pull() {
+                    restTemplate.getForObject("http://inventory/stock", String.class);
+                }
+
+                @Scheduled(fixedRate = 60000)
+                public void heartbeat() {
+                    log.info("alive");
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("SyncJob.java")).unwrap();
+        let rates: Vec<_> = issues.iter().filter(|i| i.id == "SCHEDULED_FIXED_RATE").collect();
+        assert_eq!(rates.len(), 2);
+        assert_eq!(rates[0].severity, Severity::P0);
+        assert_eq!(
+            rates[0].context.as_deref(),
+            Some("fixedRate = 30s；pull() 调用 restTemplate.getForObject()，执行耗时取决于下游，可能超过周期而排队")
+        );
+        assert_eq!(rates[1].severity, Severity::P1);
+        assert_eq!(rates[1].context.as_deref(), Some("fixedRate = 1min"));
+    }
+
+    #[test]
+    fn test_http_client_timeout() {
+        let code = r#"
+            public class GatewayConfig {
+                private final RestTemplate plain = new RestTemplate();
+
+                public WebClient webClient() {
+                    return WebClient.builder().baseUrl("http://inventory").build();
+                }
+
+                public WebClient reactive() {
+                    HttpClient http = HttpClient.create().responseTimeout(Duration.ofSeconds(5));
+                    return WebClient.builder().clientConnector(new ReactorClientHttpConnector(http)).build();
+                }
+
+                public RestTemplate restTemplate(ClientHttpRequestFactory factory) {
+                    return new RestTemplate(factory);
+                }
+
+                public OkHttpClient ok() {
+                    return new OkHttpClient.Builder().connectTimeout(2, TimeUnit.SECONDS).build();
+                }
+
+                public void call() {
+                    restTemplate.getForObject("/stock", String.class);
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("GatewayConfig.java")).unwrap();
+        let timeouts: Vec<_> = issues.iter().filter(|i| i.id == "HTTP_CLIENT_TIMEOUT").collect();
+
+        // 已设置超时 / 交给请求工厂 / 普通调用不报告
+        assert_eq!(timeouts.iter().map(|i| i.line).collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(timeouts[0].context.as_deref(), Some("new RestTemplate() @ 字段初始化: 未设置 connect/read/response 超时"));
+        assert_eq!(timeouts[1].context.as_deref(), Some("WebClient.builder() @ webClient(): 未设置 connect/read/response 超时"));
+    }
+
+    #[test]
+    fn test_retry_rules() {
+        let code = r#"
+            public class InventoryClient {
+                private final RestTemplate restTemplate;
+                private final ReservationRepository reservationRepository;
+
+                @Retryable
+                public String stock(String sku) {
+                    return restTemplate.getForObject("/stock/" + sku, String.class);
+                }
+
+                @Retryable(maxAttempts = 8, backoff = @Backoff(0))
+                public String price(String sku) {
+                    return restTemplate.getForObject("/price/" + sku, String.class);
+                }
+
+                @Retryable(maxAttempts = 4, backoff = @Backoff(delay = 100, multiplier = 2))
+                public Reservation reserve(Reservation r) {
+                    return reservationRepository.save(r);
+                }
+
+                @Retry(name = "inventory")
+                public Reservation load(long id) {
+                    return reservationRepository.findById(id).orElseThrow();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("InventoryClient.java")).unwrap();
+        let contexts = |id: &str| issues.iter()
+            .filter(|i| i.id == id)
+            .map(|i| (i.line, i.context.clone().unwrap_or_default()))
+            .collect::<Vec<_>>();
+
+        assert_eq!(contexts("RETRY_NO_BACKOFF"), vec![
+            (6, "@Retryable stock(): 未配置 backoff (默认固定 1s 间隔，无指数退避)".to_string()),
+            (11, "@Retryable price(): @Backoff 延迟为 0 (失败后立即重试)".to_string()),
+        ]);
+        assert_eq!(contexts("RETRY_EXCESSIVE_ATTEMPTS"), vec![
+            (11, "@Retryable(maxAttempts = 8) price(): 远程调用 restTemplate.getForObject()".to_string()),
+        ]);
+        // 查询方法的重试是幂等的
+        assert_eq!(contexts("RETRY_NON_IDEMPOTENT"), vec![
+            (18, "@Retryable reserve(): reservationRepository.save() 非幂等写入，重试可能重复插入".to_string()),
+        ]);
+    }
+
+    #[test]
+    fn test_redis_rules() {
+        let code = r#"
+            @RestController
+            public class SessionController {
+                private final StringRedisTemplate redisTemplate;
+                private static Jedis jedis = new Jedis("localhost");
+
+                @GetMapping("/lock")
+                public boolean lock(String id) {
+                    Boolean ok = redisTemplate.opsForValue().setIfAbsent("lock:" + id, "1");
+                    jedis.setnx("lock2:" + id, "1");
+                    jedis.expire("lock2:" + id, 30);
+                    return redisTemplate.opsForValue().setIfAbsent("lock3:" + id, "1", Duration.ofSeconds(30));
+                }
+
+                @GetMapping("/sessions")
+                public Set<String> sessions(List<String> ids) {
+                    for (String id : ids) {
+                        redisTemplate.opsForValue().get("session:" + id);
+                    }
+                    redisTemplate.executePipelined((RedisCallback<Object>) c -> {
+                        ids.forEach(id -> c.get(id.getBytes()));
+                        return null;
+                    });
+                    redisTemplate.opsForHash().keys("user:1");
+                    return redisTemplate.keys("session:*");
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("SessionController.java")).unwrap();
+        let contexts = |id: &str| issues.iter()
+            .filter(|i| i.id == id)
+            .map(|i| (i.line, i.context.clone().unwrap_or_default()))
+            .collect::<Vec<_>>();
+
+        // 带 timeout 的 setIfAbsent 不报告
+        assert_eq!(contexts("REDIS_SETNX_NO_EXPIRE"), vec![
+            (9, "redisTemplate.setIfAbsent() 未设置过期时间 → SET NX EX / setIfAbsent(key, value, timeout)".to_string()),
+            (10, "jedis.setnx() 后单独 expire()：两条命令非原子，进程在中间崩溃时锁永不过期".to_string()),
+        ]);
+        assert_eq!(contexts("REDIS_JEDIS_SHARED"), vec![
+            (5, "Jedis jedis (static 字段) 被并发调用共享 → JedisPool.getResource() / JedisPooled".to_string()),
+        ]);
+        // pipeline 回调内的命令与 opsForValue() 取接口不报告
+        assert_eq!(contexts("REDIS_OP_IN_LOOP"), vec![
+            (18, "for 循环内 redisTemplate.opsForValue().get() → multiGet / executePipelined".to_string()),
+        ]);
+        // opsForHash().keys() 是 HKEYS
+        assert_eq!(contexts("REDIS_FULL_SCAN_IN_REQUEST"), vec![
+            (25, "redisTemplate.keys() (KEYS) [调用链: SessionController.sessions] → SCAN / SSCAN".to_string()),
+        ]);
+
+        // 符号表中没有 Redis 类型时整个规则组不执行
+        let st = SymbolTable::new();
+        let issues = analyzer.analyze_with_context(code, &PathBuf::from("SessionController.java"), Some(&st), None).unwrap();
+        assert!(issues.iter().all(|i| !i.id.starts_with("REDIS_")));
+    }
+
+    #[test]
+    fn test_mdc_leak() {
+        let code = r#"
+            public class TraceFilter extends OncePerRequestFilter {
+                protected void doFilterInternal(HttpServletRequest req, HttpServletResponse res, FilterChain chain) {
+                    MDC.put("traceId", req.getHeader("X-Trace-Id"));
+                    MDC.put("user", req.getRemoteUser());
+                    try {
+                        chain.doFilter(req, res);
+                    } finally {
+                        MDC.remove("traceId");
+                    }
+                    MDC.remove("user");
+                }
+
+                public void job() {
+                    MDC.put("job", "sync");
+                    run();
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("TraceFilter.java")).unwrap();
+        let leaks: Vec<_> = issues.iter()
+            .filter(|i| i.id == "MDC_LEAK")
+            .map(|i| (i.line, i.severity, i.context.clone().unwrap_or_default()))
+            .collect();
+
+        // finally 中按同一 key 释放的不报告
+        assert_eq!(leaks, vec![
+            (5, Severity::P1, "MDC[\"user\"]".to_string()),
+            (15, Severity::P0, "MDC[\"job\"]".to_string()),
+        ]);
+        let user = issues.iter().find(|i| i.id == "MDC_LEAK" && i.line == 5).unwrap();
+        assert!(user.description.ends_with("(Variable: MDC[\"user\"], remove() not in finally block, Filter/Interceptor: 请求线程复用，后续请求继承旧值)"));
+        let job = issues.iter().find(|i| i.id == "MDC_LEAK" && i.line == 15).unwrap();
+        assert!(!job.description.contains("Filter/Interceptor"));
+
+        // MDC 按线程/请求生效: 无关方法中的 remove 不能与本方法的 put 配对
+        let code = r#"
+            @Service
+            public class OrderService {
+                public void mdcOk() {
+                    MDC.put("k", "v");
+                    try {
+                        work();
+                    } finally {
+                        MDC.remove("k");
+                    }
+                }
+
+                public void mdcBad() {
+                    MDC.put("k", "v");
+                    work();
+                }
+
+                public void traced() {
+                    enter();
+                    work();
+                    MDC.remove("k");
+                }
+
+                private void enter() {
+                    MDC.put("k", "v");
+                }
+            }
+        "#;
+        let issues = analyzer.analyze(code, &PathBuf::from("OrderService.java")).unwrap();
+        let leaks: Vec<usize> = issues.iter().filter(|i| i.id == "MDC_LEAK").map(|i| i.line).collect();
+        assert_eq!(leaks, vec![14]);
+    }
+
+    #[test]
+    fn test_paired_call_rules() {
+        let code = r#"
+            public class Worker {
+                private final Semaphore permits = new Semaphore(2);
+                private final ObjectPool pool;
+
+                void run(CountDownLatch latch) throws Exception {
+                    permits.acquire();
+                    pool.acquire();
+                    Connection conn = DriverManager.getConnection(url);
+                    Connection tx = DataSourceUtils.getConnection(dataSource);
+                    try {
+                        work(conn);
+                    } finally {
+                        conn.close();
+                    }
+                    latch.countDown();
+                }
+
+                void done(CountDownLatch latch) {
+                    try {
+                        work();
+                    } finally {
+                        latch.countDown();
+                    }
+                }
+            }
+        "#;
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Worker.java")).unwrap();
+        let found = |id: &str| issues.iter()
+            .filter(|i| i.id == id)
+            .map(|i| (i.line, i.severity, i.context.clone().unwrap_or_default()))
+            .collect::<Vec<_>>();
+
+        // 非 Semaphore 的 acquire()、finally 中关闭的连接、非 DataSource 取得的连接不报告
+        assert_eq!(found("SEMAPHORE_NO_RELEASE"), vec![(7, Severity::P0, "permits".to_string())]);
+        assert!(found("CONNECTION_NOT_CLOSED").is_empty());
+        assert_eq!(found("LATCH_COUNTDOWN_NOT_IN_FINALLY"), vec![(16, Severity::P1, "latch".to_string())]);
+
+        // 新资源类型只需一行定义 + 查询
+        const POOL_PAIR: PairedCall = PairedCall {
+            receiver_types: &["ObjectPool"],
+            ..LOCK_PAIR
+        };
+        let analyzer = JavaTreeSitterAnalyzer::builder()
+            .with_rule(
+                "POOL_NOT_RETURNED",
+                Severity::P1,
+                "(method_invocation object: (identifier) @target name: (identifier) @method (#eq? @method \"acquire\")) @acquire",
+                "对象池借出后未归还",
+                Box::new(PairedCallHandler { pair: PairedCall { acquire: &["acquire"], release: &["giveBack"], ..POOL_PAIR } }),
+            )
+            .build()
+            .unwrap();
+        let issues = analyzer.analyze(code, &PathBuf::from("Worker.java")).unwrap();
+        let pool: Vec<_> = issues.iter().filter(|i| i.id == "POOL_NOT_RETURNED").collect();
+        assert_eq!(pool.len(), 1);
+        assert_eq!(pool[0].line, 8);
+        assert!(pool[0].description.ends_with("(Variable: pool, no giveBack() call found)"));
+    }
+
     #[test]
     fn test_extract_imports() {
         let code = r#"
@@ -1635,6 +3881,16 @@ mod tests {
         // assert_eq!(imports.len(), 4);
     }
 
+    #[test]
+    fn test_extract_wildcard_imports() {
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+        let code = "package com.acme.report;\nimport com.acme.model.*;\nimport java.util.List;\npublic class ReportService {}\n";
+        let (_, _, imports) = analyzer.extract_symbols(code, Path::new("ReportService.java")).unwrap();
+        assert_eq!(imports.wildcards, vec!["com.acme.model"]);
+        assert_eq!(imports.explicit.get("List").map(String::as_str), Some("java.util.List"));
+        assert!(!imports.explicit.contains_key("model"));
+    }
+
     // ====== v9.6 Package and FQN Tests ======
 
     #[test]
@@ -1725,6 +3981,23 @@ mod tests {
         assert!(import_index.local_classes.contains(&"SimpleClass".to_string()));
     }
 
+    #[test]
+    fn test_extract_symbols_annotations_and_interfaces() {
+        let analyzer = JavaTreeSitterAnalyzer::new().unwrap();
+
+        let code = "@Service\npublic class OrderService { private OrderDao orderDao; }";
+        let (type_info, bindings, _) = analyzer.extract_symbols(code, &PathBuf::from("OrderService.java")).unwrap();
+        let type_info = type_info.expect("Should extract class");
+        assert_eq!(type_info.layer, crate::symbol_table::LayerType::Service);
+        assert_eq!(bindings[0].type_name, "OrderDao");
+
+        let code = "@Repository\npublic interface OrderDao { Order findById(Long id); }";
+        let (type_info, _, _) = analyzer.extract_symbols(code, &PathBuf::from("OrderDao.java")).unwrap();
+        let type_info = type_info.expect("Should extract interface");
+        assert_eq!(type_info.name, "OrderDao");
+        assert!(type_info.is_dao());
+    }
+
     // ====== Property-Based Tests for v9.6 ======
 
     use proptest::prelude::*;
@@ -0,0 +1,251 @@
+//! 启动耗时取证 (Startup) - v9.6
+//!
+//! `log --file` 在流式读取日志的同时识别 Spring Boot 启动日志，日志中存在启动记录时追加一节报告：
+//! - `STARTUP_SLOW`: `Started X in N seconds` 启动总耗时过长
+//! - `STARTUP_SLOW_BEAN`: DEBUG 级 Bean 创建轨迹中，某个 Bean 创建后长时间没有下一条日志 (初始化阻塞)
+//! - `STARTUP_SCAN_WIDE`: 组件扫描识别出的候选类过多 (扫描根包过宽，扫到了依赖 jar)
+//!
+//! 每项发现关联扫描器中的启动规则 (`COMPONENT_SCAN_BROAD` / `POSTCONSTRUCT_REMOTE_CALL` / `EAGER_HEAVY_BEAN`)。
+//!
+//! 支持的格式 (Bean 轨迹需要 `logging.level.org.springframework.beans.factory=DEBUG`)：
+//! ```text
+//! 2026-10-17 10:00:42.123  INFO 1 --- [main] c.a.OrderApplication : Started OrderApplication in 41.2 seconds (process running for 43.0)
+//! 2026-10-17 10:00:03.456 DEBUG 1 --- [main] o.s.b.f.s.DefaultListableBeanFactory : Creating shared instance of singleton bean 'esClient'
+//! 2026-10-17 10:00:01.001 DEBUG 1 --- [main] o.s.c.a.ClassPathBeanDefinitionScanner : Identified candidate component class: file [...]
+//! ```
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Serialize;
+
+use crate::ast_engine::Severity;
+
+/// 启动耗时阈值 (秒): 超过 P1 阈值提示，超过 P0 阈值 (常触发 K8s 启动探针超时) 报严重
+const STARTUP_SECS_P1: f64 = 30.0;
+const STARTUP_SECS_P0: f64 = 90.0;
+/// 单个 Bean 创建后到下一条日志的间隔阈值 (秒)
+const SLOW_BEAN_SECS: f64 = 3.0;
+/// 组件扫描候选类数量阈值
+const SCAN_CANDIDATES_MIN: usize = 2000;
+/// 报告中列出的条目数
+const TOP_N: usize = 10;
+
+static STARTED_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Started (\S+) in (\d+(?:\.\d+)?) seconds(?: \((?:process|JVM) running for (\d+(?:\.\d+)?)\))?").unwrap()
+});
+
+static BEAN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"Creating (?:shared )?instance of (?:singleton )?bean '([^']+)'").unwrap()
+});
+
+/// 日志时间戳 `HH:mm:ss.SSS` / `HH:mm:ss,SSS`
+static TIME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\b(\d{2}):(\d{2}):(\d{2})[.,](\d{3})\b").unwrap()
+});
+
+/// 启动发现
+#[derive(Debug, Clone, Serialize)]
+pub struct StartupFinding {
+    pub id: &'static str,
+    pub severity: Severity,
+    pub description: String,
+    pub evidence: Vec<String>,
+    /// 关联的扫描规则
+    pub related_rules: &'static [&'static str],
+}
+
+/// 流式统计
+#[derive(Debug, Default)]
+pub struct StartupStats {
+    /// (应用名, 启动秒数, 进程运行秒数)
+    started: Option<(String, f64, Option<f64>)>,
+    /// 已创建的 Bean 数
+    beans: usize,
+    /// (Bean 名, 创建后到下一条日志的间隔秒数)
+    bean_gaps: Vec<(String, f64)>,
+    /// 最近一次 Bean 创建: (Bean 名, 时间戳秒)
+    pending_bean: Option<(String, f64)>,
+    scan_candidates: usize,
+}
+
+impl StartupStats {
+    /// 处理一行日志，返回该行是否为启动记录
+    pub fn observe(&mut self, line: &str) -> bool {
+        let time = log_time(line);
+        // 上一个 Bean 创建后的第一条带时间戳日志，间隔即该 Bean (及其依赖) 初始化的阻塞时长
+        if let Some(now) = time {
+            if let Some((bean, created)) = self.pending_bean.take() {
+                let gap = now - created;
+                if gap > 0.0 {
+                    self.bean_gaps.push((bean, gap));
+                }
+            }
+        }
+
+        if let Some(caps) = BEAN_REGEX.captures(line) {
+            self.beans += 1;
+            if let Some(now) = time {
+                self.pending_bean = Some((caps[1].to_string(), now));
+            }
+        } else if line.contains("Identified candidate component class") {
+            self.scan_candidates += 1;
+        } else if let Some(caps) = STARTED_REGEX.captures(line) {
+            let Ok(secs) = caps[2].parse() else { return false };
+            let process = caps.get(3).and_then(|m| m.as_str().parse().ok());
+            self.started = Some((caps[1].to_string(), secs, process));
+        } else {
+            return false;
+        }
+        true
+    }
+
+    /// 日志中是否包含启动信息
+    pub fn is_relevant(&self) -> bool {
+        self.started.is_some() || self.beans > 0 || self.scan_candidates > 0
+    }
+
+    /// 运行规则
+    pub fn findings(&self) -> Vec<StartupFinding> {
+        let mut findings = Vec::new();
+
+        if let Some((app, secs, process)) = &self.started {
+            if *secs >= STARTUP_SECS_P1 {
+                let mut evidence = vec![format!("Started {app} in {secs} seconds")];
+                if let Some(process) = process {
+                    // 进程运行时长与 Spring 启动耗时的差值是 JVM 启动 + main() 之前的开销
+                    evidence.push(format!("JVM 启动到 SpringApplication.run 之前: {:.1}s", process - secs));
+                }
+                findings.push(StartupFinding {
+                    id: "STARTUP_SLOW",
+                    severity: if *secs >= STARTUP_SECS_P0 { Severity::P0 } else { Severity::P1 },
+                    description: format!(
+                        "应用启动耗时 {secs}s：检查扫描范围、@PostConstruct 中的远程调用与重量级客户端的提前初始化，考虑 spring.main.lazy-initialization=true"
+                    ),
+                    evidence,
+                    related_rules: &["COMPONENT_SCAN_BROAD", "POSTCONSTRUCT_REMOTE_CALL", "EAGER_HEAVY_BEAN"],
+                });
+            }
+        }
+
+        let mut slow: Vec<&(String, f64)> = self.bean_gaps.iter().filter(|(_, gap)| *gap >= SLOW_BEAN_SECS).collect();
+        slow.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        if !slow.is_empty() {
+            let total: f64 = slow.iter().map(|(_, gap)| gap).sum();
+            findings.push(StartupFinding {
+                id: "STARTUP_SLOW_BEAN",
+                severity: Severity::P1,
+                description: format!(
+                    "{} 个 Bean 创建后阻塞 {SLOW_BEAN_SECS:.0}s 以上 (合计 {total:.1}s)：初始化中连接远程服务或预加载数据，改为 @Lazy 或异步预热",
+                    slow.len()
+                ),
+                evidence: slow.iter().take(TOP_N).map(|(bean, gap)| format!("{bean} {gap:.1}s")).collect(),
+                related_rules: &["POSTCONSTRUCT_REMOTE_CALL", "EAGER_HEAVY_BEAN"],
+            });
+        }
+
+        if self.scan_candidates >= SCAN_CANDIDATES_MIN {
+            findings.push(StartupFinding {
+                id: "STARTUP_SCAN_WIDE",
+                severity: Severity::P1,
+                description: format!(
+                    "组件扫描识别出 {} 个候选类：扫描根包过宽 (如 \"com\")，依赖 jar 中的类也被扫描和注册",
+                    self.scan_candidates
+                ),
+                evidence: Vec::new(),
+                related_rules: &["COMPONENT_SCAN_BROAD"],
+            });
+        }
+
+        findings
+    }
+
+    /// 渲染报告小节
+    pub fn render(&self) -> String {
+        let mut out = "\n## 🚀 启动耗时\n\n".to_string();
+        if let Some((app, secs, _)) = &self.started {
+            out.push_str(&format!("**应用**: {app} | **启动**: {secs}s | "));
+        }
+        out.push_str(&format!("**Bean 创建**: {} | **扫描候选类**: {}\n\n", self.beans, self.scan_candidates));
+
+        let findings = self.findings();
+        if findings.is_empty() {
+            out.push_str("✅ 未发现启动耗时问题\n");
+            return out;
+        }
+        for f in &findings {
+            let emoji = f.severity.emoji();
+            out.push_str(&format!("{emoji} **{}** - {}\n", f.id, f.description));
+            for e in &f.evidence {
+                out.push_str(&format!("  - `{e}`\n"));
+            }
+            let rules: Vec<String> = f.related_rules.iter().map(|r| format!("`{r}`")).collect();
+            out.push_str(&format!("  - 关联扫描规则: {} (`java-perf scan` 定位源码)\n", rules.join(", ")));
+        }
+        out
+    }
+}
+
+/// 行内时间戳 (当天秒数)
+fn log_time(line: &str) -> Option<f64> {
+    let caps = TIME_REGEX.captures(line)?;
+    let part = |i: usize| caps[i].parse::<f64>().ok();
+    Some(part(1)? * 3600.0 + part(2)? * 60.0 + part(3)? + part(4)? / 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn observe_all(lines: &[&str]) -> StartupStats {
+        let mut stats = StartupStats::default();
+        for line in lines {
+            stats.observe(line);
+        }
+        stats
+    }
+
+    fn ids(stats: &StartupStats) -> Vec<&'static str> {
+        stats.findings().iter().map(|f| f.id).collect()
+    }
+
+    #[test]
+    fn test_slow_startup_and_beans() {
+        let stats = observe_all(&[
+            "2026-10-17 10:00:01.000 DEBUG 1 --- [main] o.s.b.f.s.DefaultListableBeanFactory : Creating shared instance of singleton bean 'orderService'",
+            "2026-10-17 10:00:01.200 DEBUG 1 --- [main] o.s.b.f.s.DefaultListableBeanFactory : Creating shared instance of singleton bean 'esClient'",
+            "2026-10-17 10:00:13.700  INFO 1 --- [main] o.a.k.c.p.ProducerConfig : ProducerConfig values:",
+            "2026-10-17 10:00:13.800 DEBUG 1 --- [main] o.s.b.f.s.DefaultListableBeanFactory : Creating shared instance of singleton bean 'kafkaTemplate'",
+            "2026-10-17 10:00:18.000 DEBUG 1 --- [main] o.s.b.f.s.DefaultListableBeanFactory : Creating shared instance of singleton bean 'web'",
+            "2026-10-17 10:00:42.100  INFO 1 --- [main] c.a.OrderApplication : Started OrderApplication in 41.1 seconds (process running for 43.5)",
+        ]);
+        assert!(stats.is_relevant());
+        assert_eq!(ids(&stats), vec!["STARTUP_SLOW", "STARTUP_SLOW_BEAN"]);
+
+        let findings = stats.findings();
+        assert_eq!(findings[0].severity, Severity::P1);
+        assert_eq!(findings[0].evidence[1], "JVM 启动到 SpringApplication.run 之前: 2.4s");
+        // 'web' 之后直到 Started 行才有日志
+        assert_eq!(findings[1].evidence, vec!["web 24.1s", "esClient 12.5s", "kafkaTemplate 4.2s"]);
+        assert!(stats.render().contains("**应用**: OrderApplication | **启动**: 41.1s | **Bean 创建**: 4"));
+    }
+
+    #[test]
+    fn test_fast_startup_and_wide_scan() {
+        let fast = observe_all(&["10:00:03,100 INFO  [main] Started BillingApplication in 2.9 seconds (JVM running for 3.4)"]);
+        assert!(fast.is_relevant());
+        assert!(fast.findings().is_empty());
+        assert!(fast.render().contains("✅ 未发现启动耗时问题"));
+
+        let lines: Vec<String> = (0..SCAN_CANDIDATES_MIN)
+            .map(|i| format!("DEBUG o.s.c.a.ClassPathBeanDefinitionScanner : Identified candidate component class: URL [jar:file:/app/lib/x.jar!/com/vendor/C{i}.class]"))
+            .collect();
+        let refs: Vec<&str> = lines.iter().map(String::as_str).collect();
+        let wide = observe_all(&refs);
+        assert_eq!(ids(&wide), vec!["STARTUP_SCAN_WIDE"]);
+        assert_eq!(wide.findings()[0].related_rules, &["COMPONENT_SCAN_BROAD"]);
+
+        let mut stats = StartupStats::default();
+        assert!(!stats.observe("java.lang.IllegalStateException: Started too early"));
+        assert!(!stats.is_relevant());
+    }
+}
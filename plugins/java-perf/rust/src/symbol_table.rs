@@ -226,6 +226,8 @@ pub struct TypeInfo {
     pub layer: LayerType,
     pub file: PathBuf,
     pub line: usize,
+    /// 直接父类与实现/继承的接口 (简单类名，去掉泛型参数) - v9.6
+    pub supertypes: Vec<String>,
 }
 
 impl TypeInfo {
@@ -242,6 +244,7 @@ impl TypeInfo {
             layer: LayerType::Unknown,
             file,
             line,
+            supertypes: Vec::new(),
         }
     }
 
@@ -259,6 +262,7 @@ impl TypeInfo {
             layer: LayerType::Unknown,
             file,
             line,
+            supertypes: Vec::new(),
         }
     }
     
@@ -354,7 +358,7 @@ impl MethodInfo {
 }
 
 /// 符号表 - 跟踪类型和变量 (v9.2: 支持方法重载)
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct SymbolTable {
     /// 类名 -> 类型信息 (keyed by FQN for uniqueness)
     pub classes: HashMap<String, TypeInfo>,
@@ -527,7 +531,6 @@ impl SymbolTable {
     /// # Arguments
     /// * `class` - The class name (simple or FQN)
     /// * `method` - The method info to register
-    #[allow(dead_code)]
     pub fn register_method(&mut self, class: &str, method: MethodInfo) {
         let sig = format!("{}({})", method.name, 
             method.params.iter().map(|p| p.type_name.as_str()).collect::<Vec<_>>().join(","));
@@ -550,7 +553,6 @@ impl SymbolTable {
     /// 
     /// # Returns
     /// Vector of references to MethodInfo for all overloads
-    #[allow(dead_code)]
     pub fn lookup_methods(&self, class: &str, method_name: &str) -> Vec<&MethodInfo> {
         if let Some(sigs) = self.method_index.get(&(class.to_string(), method_name.to_string())) {
             sigs.iter()
@@ -162,7 +162,7 @@ pub struct CallSite {
 }
 
 /// 调用图 - 用于追踪 Controller -> Service -> DAO 链
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Serialize, Deserialize)]
 pub struct CallGraph {
     /// 方法签名 -> 该方法调用的其他方法
     pub outgoing: HashMap<MethodSig, Vec<CallSite>>,
@@ -318,6 +318,114 @@ impl CallGraph {
         }
     }
     
+    /// 方法所在类的层级 (先 FQN，后简单类名)
+    pub fn layer_of(&self, method: &MethodSig) -> LayerType {
+        self.class_layers.get(&method.class_fqn)
+            .or_else(|| self.class_layers.get(method.simple_class_name()))
+            .copied()
+            .unwrap_or(LayerType::Unknown)
+    }
+
+    /// 按类名 (FQN 或简单类名) + 方法名查找图中的方法 (v9.6)
+    pub fn find_methods(&self, class: &str, method: &str) -> Vec<MethodSig> {
+        let mut found: Vec<MethodSig> = self.outgoing.keys()
+            .chain(self.incoming.keys())
+            .filter(|m| m.name == method && !m.is_unresolved()
+                && (m.class_fqn == class || m.simple_class_name() == class))
+            .cloned()
+            .collect();
+        found.sort_by_key(MethodSig::full_name);
+        found.dedup();
+        found
+    }
+
+    /// 重新链接被调用方 (v9.6)
+    ///
+    /// Phase 1 逐文件构建时只能看到本文件的符号，`userDao.find()` 的接收者
+    /// 记为 `UNRESOLVED:userDao`。全局符号表合并后，由 `resolve` 返回真实类 FQN。
+    pub fn relink(&mut self, resolve: impl Fn(&CallSite) -> Option<String>) {
+        let sites: Vec<CallSite> = std::mem::take(&mut self.outgoing).into_values().flatten().collect();
+        self.incoming.clear();
+        for site in sites {
+            let callee = match resolve(&site) {
+                Some(fqn) => MethodSig::new_fqn(&fqn, &site.callee.name),
+                None => site.callee,
+            };
+            self.add_call(site.caller, callee, site.file, site.line);
+        }
+    }
+
+    /// 反向追踪: 从 `source_layer` (如 Controller) 出发能到达 `target` 的所有调用路径 (v9.6)
+    ///
+    /// 返回的每条路径按调用顺序排列: `[Controller 方法, ..., target]`
+    pub fn trace_from_layer(&self, target: &MethodSig, source_layer: LayerType, max_depth: usize) -> Vec<Vec<MethodSig>> {
+        let mut paths = Vec::new();
+        let mut current_path = vec![target.clone()];
+        let mut visited = std::collections::HashSet::from([target.clone()]);
+
+        self.dfs_trace_up(target, source_layer, max_depth, &mut current_path, &mut visited, &mut paths);
+
+        for path in &mut paths {
+            path.reverse();
+        }
+        paths
+    }
+
+    fn dfs_trace_up(
+        &self,
+        current: &MethodSig,
+        source_layer: LayerType,
+        remaining_depth: usize,
+        path: &mut Vec<MethodSig>,
+        visited: &mut std::collections::HashSet<MethodSig>,
+        result: &mut Vec<Vec<MethodSig>>,
+    ) {
+        if remaining_depth == 0 {
+            return;
+        }
+        if path.len() > 1 && self.layer_of(current) == source_layer {
+            result.push(path.clone());
+            return;
+        }
+
+        if let Some(callers) = self.incoming.get(current) {
+            for call_site in callers {
+                if visited.insert(call_site.caller.clone()) {
+                    path.push(call_site.caller.clone());
+                    self.dfs_trace_up(&call_site.caller, source_layer, remaining_depth - 1, path, visited, result);
+                    path.pop();
+                    visited.remove(&call_site.caller);
+                }
+            }
+        }
+    }
+
+    /// 广度优先查找从 `start` 出发、第一个满足 `is_sink` 的调用点 (v9.6)
+    ///
+    /// 返回经过的调用点 (最短路径)，最后一个即命中的 sink 调用。
+    pub fn find_path(&self, start: &MethodSig, max_depth: usize, is_sink: impl Fn(&CallSite) -> bool) -> Option<Vec<CallSite>> {
+        let mut visited = std::collections::HashSet::from([start.clone()]);
+        let mut frontier: Vec<(MethodSig, Vec<CallSite>)> = vec![(start.clone(), Vec::new())];
+
+        for _ in 0..max_depth {
+            let mut next = Vec::new();
+            for (method, path) in frontier {
+                for site in self.outgoing.get(&method).into_iter().flatten() {
+                    let mut path = path.clone();
+                    path.push(site.clone());
+                    if is_sink(site) {
+                        return Some(path);
+                    }
+                    if visited.insert(site.callee.clone()) {
+                        next.push((site.callee.clone(), path));
+                    }
+                }
+            }
+            frontier = next;
+        }
+        None
+    }
+
     /// 检测 N+1 问题：在循环内调用的方法最终是否到达 Repository
     pub fn detect_n_plus_one_chains(&self) -> Vec<CallChainReport> {
         let mut reports = Vec::new();
@@ -398,6 +506,44 @@ mod tests {
         assert_eq!(paths[0].len(), 3); // Controller -> Service -> Repository
     }
 
+    #[test]
+    fn test_call_graph_relink_and_trace_upstream() {
+        let mut graph = CallGraph::new();
+        graph.register_class("com.a.UserController", PathBuf::from("UserController.java"), LayerType::Controller);
+        graph.register_class("com.a.UserService", PathBuf::from("UserService.java"), LayerType::Service);
+        graph.register_class("com.a.UserRepository", PathBuf::from("UserRepository.java"), LayerType::Repository);
+
+        graph.add_call(
+            MethodSig::new_fqn("com.a.UserController", "get"),
+            MethodSig::new("UNRESOLVED:userService", "load"),
+            PathBuf::from("UserController.java"),
+            10,
+        );
+        graph.add_call(
+            MethodSig::new_fqn("com.a.UserService", "load"),
+            MethodSig::new("UNRESOLVED:userRepository", "findById"),
+            PathBuf::from("UserService.java"),
+            20,
+        );
+
+        // 字段名 → 类型 FQN
+        graph.relink(|site| match site.callee.simple_class_name() {
+            "userService" => Some("com.a.UserService".to_string()),
+            "userRepository" => Some("com.a.UserRepository".to_string()),
+            _ => None,
+        });
+
+        let load = graph.find_methods("UserService", "load");
+        assert_eq!(load, vec![MethodSig::new_fqn("com.a.UserService", "load")]);
+        assert_eq!(graph.layer_of(&load[0]), LayerType::Service);
+
+        let up = graph.trace_from_layer(&load[0], LayerType::Controller, 5);
+        assert_eq!(up, vec![vec![MethodSig::new_fqn("com.a.UserController", "get"), load[0].clone()]]);
+
+        let down = graph.trace_to_layer(&load[0], LayerType::Repository, 5);
+        assert_eq!(down[0].last().unwrap().full_name(), "com.a.UserRepository.findById");
+    }
+
     // ========================================================================
     // MethodSig Unit Tests
     // ========================================================================
@@ -0,0 +1,485 @@
+//! 线程 Dump 解析 (Thread Model) - v9.6
+//!
+//! 把 `jstack` 输出解析为结构化的 [`ThreadInfo`] (名称、状态、栈帧、持有/等待的锁)，
+//! 在此基础上做字符串匹配无法完成的分析：
+//! - 锁依赖图: 等待者 → 持有者，按等待线程数列出热点锁，沿等待链检测死锁环
+//! - 相同栈分组: 状态与栈帧完全相同的线程合并 (大量线程卡在同一处是最典型的症状)
+//! - 线程池聚合: 去掉名称末尾编号 (`http-nio-8080-exec-12` → `http-nio-8080-exec-*`) 后按池统计状态
+//! - 已知问题模式: 连接池耗尽、commonPool 阻塞等，见 [`thread_patterns`]
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::thread_patterns;
+
+/// 报告中每个分区列出的条目数
+const TOP_N: usize = 10;
+
+/// 相同栈分组在报告中展示的栈帧数
+const GROUP_FRAMES: usize = 5;
+
+/// 锁引用: `<0x000000070ff7d2a8> (a java.lang.Object)`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LockRef {
+    pub address: String,
+    pub class: String,
+}
+
+/// 单个线程
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ThreadInfo {
+    pub name: String,
+    pub daemon: bool,
+    /// `java.lang.Thread.State` (GC/编译器等 VM 内部线程没有)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state: Option<String>,
+    /// 栈帧 (`at` 之后的部分，栈顶在前)
+    pub frames: Vec<String>,
+    /// 持有的监视器与 `Locked ownable synchronizers`
+    pub locks_held: Vec<LockRef>,
+    /// 竞争中的锁: `waiting to lock` / `waiting to re-lock` / `parking to wait for`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_for: Option<LockRef>,
+    /// `Object.wait()` 等待通知的对象 (已释放监视器，不构成锁依赖)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub waiting_on: Option<LockRef>,
+}
+
+impl ThreadInfo {
+    fn state_name(&self) -> &str {
+        self.state.as_deref().unwrap_or("VM")
+    }
+}
+
+/// 被竞争的锁: 持有者与等待者
+#[derive(Debug, Clone, Serialize)]
+pub struct ContendedLock {
+    pub lock: LockRef,
+    /// 持有者 (ReentrantLock 等未出现在 ownable synchronizers 中时为空)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub owner: Option<String>,
+    pub waiters: Vec<String>,
+}
+
+/// 状态与栈帧完全相同的一组线程
+#[derive(Debug, Clone, Serialize)]
+pub struct StackGroup {
+    pub state: String,
+    pub frames: Vec<String>,
+    pub threads: Vec<String>,
+}
+
+/// 线程池 (名称去掉末尾编号) 的状态统计
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolSummary {
+    pub pool: String,
+    pub total: usize,
+    pub states: BTreeMap<String, usize>,
+}
+
+/// 解析 `jstack` 输出
+///
+/// ```text
+/// "http-nio-8080-exec-1" #31 daemon prio=5 os_prio=0 tid=0x... nid=0x2a waiting for monitor entry [0x...]
+///    java.lang.Thread.State: BLOCKED (on object monitor)
+///     at com.acme.OrderService.place(OrderService.java:42)
+///     - waiting to lock <0x000000070ff7d2a8> (a java.lang.Object)
+/// ```
+///
+/// `Found one Java-level deadlock` 之后的死锁摘要不是线程条目，解析到该处停止。
+pub fn parse(dump: &str) -> Vec<ThreadInfo> {
+    let mut threads = Vec::new();
+    let mut current: Option<ThreadInfo> = None;
+    for line in dump.lines() {
+        if line.starts_with("Found ") && line.contains("deadlock") {
+            break;
+        }
+        if line.starts_with('"') {
+            threads.extend(current.take());
+            current = parse_header(line);
+            continue;
+        }
+        let Some(thread) = &mut current else { continue };
+        let line = line.trim();
+        if let Some(state) = line.strip_prefix("java.lang.Thread.State:") {
+            thread.state = state.split_whitespace().next().map(str::to_string);
+        } else if let Some(frame) = line.strip_prefix("at ") {
+            thread.frames.push(frame.to_string());
+        } else if let Some(rest) = line.strip_prefix("- ") {
+            let (action, lock) = match rest.find('<') {
+                Some(i) => (rest[..i].trim(), parse_lock(&rest[i..])),
+                None => (rest, None),
+            };
+            let Some(lock) = lock else { continue };
+            match action {
+                // "- locked" 与 "Locked ownable synchronizers" 下的 "- <0x...>"
+                "locked" | "" if !thread.locks_held.contains(&lock) => thread.locks_held.push(lock),
+                "waiting to lock" | "waiting to re-lock in wait()" | "parking to wait for" => thread.waiting_for = Some(lock),
+                "waiting on" => thread.waiting_on = Some(lock),
+                _ => {}
+            }
+        }
+    }
+    threads.extend(current);
+    threads
+}
+
+fn parse_header(line: &str) -> Option<ThreadInfo> {
+    let end = line[1..].find('"')? + 1;
+    let name = line[1..end].to_string();
+    let daemon = line[end + 1..].split_whitespace().any(|w| w == "daemon");
+    Some(ThreadInfo { name, daemon, ..Default::default() })
+}
+
+/// `<0x000000070ff7d2a8> (a java.lang.Object)`
+fn parse_lock(text: &str) -> Option<LockRef> {
+    let address = text.strip_prefix('<')?.split('>').next()?.to_string();
+    let class = text.split_once("(a ")
+        .map(|(_, rest)| rest.trim_end().trim_end_matches(')').to_string())
+        .unwrap_or_default();
+    Some(LockRef { address, class })
+}
+
+/// 线程池名: 去掉名称末尾的编号 (`pool-3-thread-7` → `pool-3-thread-*`)
+pub fn pool_name(thread: &str) -> String {
+    let trimmed = thread.trim_end_matches(|c: char| c.is_ascii_digit());
+    if trimmed.len() == thread.len() || trimmed.is_empty() {
+        thread.to_string()
+    } else {
+        format!("{trimmed}*")
+    }
+}
+
+/// 锁依赖: 每个被等待的锁的持有者与等待者，按等待者数量降序
+pub fn contended_locks(threads: &[ThreadInfo]) -> Vec<ContendedLock> {
+    let owners = lock_owners(threads);
+    let mut by_lock: HashMap<&str, ContendedLock> = HashMap::new();
+    for thread in threads {
+        let Some(lock) = &thread.waiting_for else { continue };
+        by_lock.entry(&lock.address)
+            .or_insert_with(|| ContendedLock {
+                lock: lock.clone(),
+                owner: owners.get(lock.address.as_str()).map(|t| t.to_string()),
+                waiters: Vec::new(),
+            })
+            .waiters.push(thread.name.clone());
+    }
+    let mut locks: Vec<ContendedLock> = by_lock.into_values().collect();
+    locks.sort_by(|a, b| b.waiters.len().cmp(&a.waiters.len()).then_with(|| a.lock.address.cmp(&b.lock.address)));
+    locks
+}
+
+/// 死锁: 沿 "等待的锁 → 持有者" 链回到自身的线程环 (每个环只报告一次)
+pub fn deadlocks(threads: &[ThreadInfo]) -> Vec<Vec<String>> {
+    let owners = lock_owners(threads);
+    let waits: HashMap<&str, &str> = threads.iter()
+        .filter_map(|t| {
+            let owner = owners.get(t.waiting_for.as_ref()?.address.as_str())?;
+            Some((t.name.as_str(), *owner))
+        })
+        .collect();
+
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut cycles = Vec::new();
+    for thread in threads {
+        let mut path: Vec<&str> = Vec::new();
+        let mut node = thread.name.as_str();
+        let cycle_start = loop {
+            if seen.contains(node) {
+                break None;
+            }
+            if let Some(start) = path.iter().position(|n| *n == node) {
+                break Some(start);
+            }
+            path.push(node);
+            match waits.get(node) {
+                Some(next) => node = next,
+                None => break None,
+            }
+        };
+        if let Some(start) = cycle_start {
+            cycles.push(path[start..].iter().map(|n| n.to_string()).collect());
+        }
+        seen.extend(path);
+    }
+    cycles
+}
+
+/// 相同栈分组 (至少 2 个线程)，按线程数降序
+pub fn stack_groups(threads: &[ThreadInfo]) -> Vec<StackGroup> {
+    let mut groups: HashMap<(&str, &[String]), Vec<String>> = HashMap::new();
+    for thread in threads.iter().filter(|t| !t.frames.is_empty()) {
+        groups.entry((thread.state_name(), &thread.frames)).or_default().push(thread.name.clone());
+    }
+    let mut groups: Vec<StackGroup> = groups.into_iter()
+        .filter(|(_, names)| names.len() > 1)
+        .map(|((state, frames), threads)| StackGroup { state: state.to_string(), frames: frames.to_vec(), threads })
+        .collect();
+    groups.sort_by(|a, b| b.threads.len().cmp(&a.threads.len()).then_with(|| a.frames.cmp(&b.frames)));
+    groups
+}
+
+/// 按线程池聚合状态，按线程数降序
+pub fn pools(threads: &[ThreadInfo]) -> Vec<PoolSummary> {
+    let mut by_pool: HashMap<String, BTreeMap<String, usize>> = HashMap::new();
+    for thread in threads {
+        *by_pool.entry(pool_name(&thread.name)).or_default().entry(thread.state_name().to_string()).or_insert(0) += 1;
+    }
+    let mut pools: Vec<PoolSummary> = by_pool.into_iter()
+        .map(|(pool, states)| PoolSummary { pool, total: states.values().sum(), states })
+        .collect();
+    pools.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.pool.cmp(&b.pool)));
+    pools
+}
+
+/// 锁地址 → 持有线程
+fn lock_owners(threads: &[ThreadInfo]) -> HashMap<&str, &str> {
+    threads.iter()
+        .flat_map(|t| t.locks_held.iter().map(move |l| (l.address.as_str(), t.name.as_str())))
+        .collect()
+}
+
+/// 分析线程 Dump，返回结构化结果与 Markdown `report`
+pub fn analyze(pid: u32, label: &str, dump: &str) -> Value {
+    let threads = parse(dump);
+    let mut states: BTreeMap<&str, usize> = BTreeMap::new();
+    for thread in &threads {
+        *states.entry(thread.state_name()).or_insert(0) += 1;
+    }
+    let locks = contended_locks(&threads);
+    let cycles = deadlocks(&threads);
+    // jstack 自身的死锁检测还覆盖 JNI 与 ownable synchronizer 未记录持有者的情况
+    let deadlock = !cycles.is_empty() || dump.lines().any(|l| l.starts_with("Found ") && l.contains("deadlock"));
+    let groups = stack_groups(&threads);
+    let pools = pools(&threads);
+    let patterns = thread_patterns::recognize(&threads);
+
+    let count = |state: &str| states.get(state).copied().unwrap_or(0);
+    let blocked = count("BLOCKED");
+    let mut report = format!(
+        "## 🔬 线程 Dump 分析 (PID: {pid}{label})\n\n\
+        **线程**: {} | RUNNABLE: {} | WAITING: {} | TIMED_WAITING: {} | BLOCKED: {blocked}\n\n",
+        threads.len(),
+        count("RUNNABLE"),
+        count("WAITING"),
+        count("TIMED_WAITING"),
+    );
+
+    if deadlock {
+        report.push_str("> [!CAUTION]\n> ⚠️ 检测到死锁！\n");
+        for cycle in &cycles {
+            let mut chain: Vec<String> = cycle.iter().map(|n| format!("`{n}`")).collect();
+            chain.push(format!("`{}`", cycle[0]));
+            report.push_str(&format!("> - {}\n", chain.join(" → ")));
+        }
+        report.push('\n');
+    }
+    if blocked > 10 {
+        report.push_str(&format!(
+            "> [!WARNING]\n> {blocked} 个线程处于 BLOCKED 状态，可能存在锁竞争\n\n"
+        ));
+    }
+
+    if !patterns.is_empty() {
+        report.push_str("### 🩺 已知问题模式\n\n");
+        for pattern in &patterns {
+            report.push_str(&format!(
+                "- **{}** `{}`: {}\n  - 修复: {}\n  - 检查清单: §{} {} — {} (`get_checklist` 症状 `{}`)\n",
+                pattern.title,
+                pattern.id,
+                pattern.evidence,
+                pattern.fix,
+                pattern.checklist.section,
+                pattern.checklist.title,
+                pattern.checklist.item,
+                pattern.symptom,
+            ));
+        }
+        report.push('\n');
+    }
+
+    if !locks.is_empty() {
+        report.push_str("### 🔒 热点锁\n\n| 锁 | 持有者 | 等待线程 |\n|----|--------|----------|\n");
+        for lock in locks.iter().take(TOP_N) {
+            report.push_str(&format!(
+                "| `{}` ({}) | {} | {} |\n",
+                lock.lock.address,
+                lock.lock.class,
+                lock.owner.as_deref().map(|o| format!("`{o}`")).unwrap_or_else(|| "-".to_string()),
+                lock.waiters.len(),
+            ));
+        }
+        report.push('\n');
+    }
+
+    if !groups.is_empty() {
+        report.push_str("### 🧵 相同栈分组\n\n");
+        for group in groups.iter().take(TOP_N) {
+            report.push_str(&format!(
+                "**{} 个线程** ({}): {}\n\n```\n",
+                group.threads.len(),
+                group.state,
+                sample_names(&group.threads),
+            ));
+            for frame in group.frames.iter().take(GROUP_FRAMES) {
+                report.push_str(&format!("at {frame}\n"));
+            }
+            if group.frames.len() > GROUP_FRAMES {
+                report.push_str(&format!("... {} more\n", group.frames.len() - GROUP_FRAMES));
+            }
+            report.push_str("```\n\n");
+        }
+    }
+
+    report.push_str("### 🏊 线程池\n\n| 线程池 | 线程数 | 状态 |\n|--------|--------|------|\n");
+    for pool in pools.iter().take(TOP_N) {
+        let states: Vec<String> = pool.states.iter().map(|(s, n)| format!("{s}: {n}")).collect();
+        report.push_str(&format!("| `{}` | {} | {} |\n", pool.pool, pool.total, states.join(", ")));
+    }
+
+    json!({
+        "pid": pid,
+        "total": threads.len(),
+        "states": states,
+        "deadlock": deadlock,
+        "deadlocks": cycles,
+        "patterns": patterns,
+        "contended_locks": locks,
+        "stack_groups": groups,
+        "pools": pools,
+        "threads": threads,
+        "report": report,
+    })
+}
+
+/// 分组线程名示例 (最多 3 个)
+fn sample_names(names: &[String]) -> String {
+    let mut sample: Vec<String> = names.iter().take(3).map(|n| format!("`{n}`")).collect();
+    if names.len() > 3 {
+        sample.push("...".to_string());
+    }
+    sample.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DUMP: &str = r#"2026-10-17 10:00:00
+Full thread dump OpenJDK 64-Bit Server VM (17.0.9+9 mixed mode, sharing):
+
+"main" #1 prio=5 os_prio=0 cpu=120.00ms elapsed=60.00s tid=0x00007f1c2c024000 nid=0x1 waiting on condition  [0x00007f1c33ffe000]
+   java.lang.Thread.State: TIMED_WAITING (sleeping)
+	at java.lang.Thread.sleep(java.base@17.0.9/Native Method)
+	at com.acme.App.main(App.java:12)
+
+"http-nio-8080-exec-1" #31 daemon prio=5 os_prio=0 tid=0x00007f1c2c100000 nid=0x2a waiting for monitor entry  [0x00007f1bf8ffe000]
+   java.lang.Thread.State: BLOCKED (on object monitor)
+	at com.acme.OrderService.place(OrderService.java:42)
+	- waiting to lock <0x000000070ff7d2a8> (a java.lang.Object)
+	- locked <0x000000070ff7e000> (a com.acme.Inventory)
+	at com.acme.OrderController.create(OrderController.java:20)
+
+"http-nio-8080-exec-2" #32 daemon prio=5 os_prio=0 tid=0x00007f1c2c101000 nid=0x2b waiting for monitor entry  [0x00007f1bf8efe000]
+   java.lang.Thread.State: BLOCKED (on object monitor)
+	at com.acme.InventoryService.reserve(InventoryService.java:30)
+	- waiting to lock <0x000000070ff7e000> (a com.acme.Inventory)
+	- locked <0x000000070ff7d2a8> (a java.lang.Object)
+	at com.acme.OrderController.create(OrderController.java:21)
+
+"pool-2-thread-1" #40 prio=5 os_prio=0 tid=0x00007f1c2c200000 nid=0x30 waiting on condition  [0x00007f1bf7ffe000]
+   java.lang.Thread.State: WAITING (parking)
+	at jdk.internal.misc.Unsafe.park(java.base@17.0.9/Native Method)
+	- parking to wait for  <0x000000070ff90000> (a java.util.concurrent.locks.ReentrantLock$NonfairSync)
+	at java.util.concurrent.locks.LockSupport.park(java.base@17.0.9/LockSupport.java:211)
+
+"pool-2-thread-2" #41 prio=5 os_prio=0 tid=0x00007f1c2c201000 nid=0x31 waiting on condition  [0x00007f1bf7efe000]
+   java.lang.Thread.State: WAITING (parking)
+	at jdk.internal.misc.Unsafe.park(java.base@17.0.9/Native Method)
+	- parking to wait for  <0x000000070ff90000> (a java.util.concurrent.locks.ReentrantLock$NonfairSync)
+	at java.util.concurrent.locks.LockSupport.park(java.base@17.0.9/LockSupport.java:211)
+
+"pool-2-thread-3" #42 prio=5 os_prio=0 tid=0x00007f1c2c202000 nid=0x32 runnable  [0x00007f1bf7dfe000]
+   java.lang.Thread.State: RUNNABLE
+	at com.acme.Cache.rebuild(Cache.java:88)
+
+   Locked ownable synchronizers:
+	- <0x000000070ff90000> (a java.util.concurrent.locks.ReentrantLock$NonfairSync)
+
+"Reference Handler" #2 daemon prio=10 os_prio=0 tid=0x00007f1c2c0a0000 nid=0x8 waiting on condition  [0x00007f1c0c1fe000]
+   java.lang.Thread.State: WAITING (on object monitor)
+	at java.lang.Object.wait(java.base@17.0.9/Native Method)
+	- waiting on <0x000000070ff00000> (a java.lang.ref.Reference$Lock)
+
+"GC Thread#0" os_prio=0 cpu=5.00ms elapsed=60.00s tid=0x00007f1c2c050000 nid=0x3 runnable
+
+Found one Java-level deadlock:
+=============================
+"http-nio-8080-exec-1":
+  waiting to lock monitor 0x00007f1c00003f00 (object 0x000000070ff7d2a8, a java.lang.Object),
+  which is held by "http-nio-8080-exec-2"
+"#;
+
+    #[test]
+    fn test_parse_threads() {
+        let threads = parse(DUMP);
+        assert_eq!(threads.len(), 8);
+
+        let exec1 = &threads[1];
+        assert_eq!(exec1.name, "http-nio-8080-exec-1");
+        assert!(exec1.daemon);
+        assert_eq!(exec1.state.as_deref(), Some("BLOCKED"));
+        assert_eq!(exec1.frames, vec![
+            "com.acme.OrderService.place(OrderService.java:42)",
+            "com.acme.OrderController.create(OrderController.java:20)",
+        ]);
+        assert_eq!(exec1.waiting_for, Some(LockRef { address: "0x000000070ff7d2a8".into(), class: "java.lang.Object".into() }));
+        assert_eq!(exec1.locks_held[0].class, "com.acme.Inventory");
+
+        assert_eq!(threads[5].locks_held[0].address, "0x000000070ff90000");
+        assert_eq!(threads[6].waiting_on.as_ref().unwrap().class, "java.lang.ref.Reference$Lock");
+        assert!(threads[6].waiting_for.is_none());
+        assert_eq!(threads[7].name, "GC Thread#0");
+        assert!(threads[7].state.is_none());
+    }
+
+    #[test]
+    fn test_lock_graph_and_deadlock() {
+        let threads = parse(DUMP);
+        let locks = contended_locks(&threads);
+        assert_eq!(locks[0].lock.address, "0x000000070ff90000");
+        assert_eq!(locks[0].owner.as_deref(), Some("pool-2-thread-3"));
+        assert_eq!(locks[0].waiters, vec!["pool-2-thread-1", "pool-2-thread-2"]);
+
+        assert_eq!(deadlocks(&threads), vec![vec!["http-nio-8080-exec-1".to_string(), "http-nio-8080-exec-2".to_string()]]);
+    }
+
+    #[test]
+    fn test_stack_groups_and_pools() {
+        let threads = parse(DUMP);
+        let groups = stack_groups(&threads);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].state, "WAITING");
+        assert_eq!(groups[0].threads, vec!["pool-2-thread-1", "pool-2-thread-2"]);
+
+        assert_eq!(pool_name("http-nio-8080-exec-12"), "http-nio-8080-exec-*");
+        assert_eq!(pool_name("main"), "main");
+        let pools = pools(&threads);
+        assert_eq!(pools[0].pool, "pool-2-thread-*");
+        assert_eq!(pools[0].total, 3);
+        assert_eq!(pools[0].states["WAITING"], 2);
+    }
+
+    #[test]
+    fn test_analyze_report() {
+        let v = analyze(42, "", DUMP);
+        assert_eq!(v["total"], 8);
+        assert_eq!(v["states"]["BLOCKED"], 2);
+        assert_eq!(v["deadlock"], true);
+        let report = v["report"].as_str().unwrap();
+        assert!(report.contains("`http-nio-8080-exec-1` → `http-nio-8080-exec-2` → `http-nio-8080-exec-1`"));
+        assert!(report.contains("| `0x000000070ff90000` (java.util.concurrent.locks.ReentrantLock$NonfairSync) | `pool-2-thread-3` | 2 |"));
+        assert!(report.contains("**2 个线程** (WAITING)"));
+    }
+}
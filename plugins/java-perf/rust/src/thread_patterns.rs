@@ -0,0 +1,268 @@
+//! 线程 Dump 已知问题模式 (Thread Patterns) - v9.6
+//!
+//! 在结构化线程模型 ([`thread_dump::parse`]) 之上识别常见的故障现场，
+//! 每个模式映射到检查清单中的一项修复：
+//! - `HTTP_POOL_DB_WAIT`: Tomcat/Jetty/Undertow 工作线程大多卡在数据库连接池 (Hikari/Druid/DBCP) 取连接
+//! - `COMMON_POOL_BLOCKING`: `ForkJoinPool.commonPool` 被阻塞调用占满 (parallelStream / 默认 CompletableFuture)
+//! - `LOG_APPENDER_CONTENTION`: 多个线程竞争同步日志 Appender 的锁
+//! - `OBJECT_WAIT_SAME_MONITOR`: 大量线程在同一个对象上 `Object.wait()` (自研池/队列耗尽)
+//!
+//! [`thread_dump::parse`]: crate::thread_dump::parse
+
+use serde::Serialize;
+use std::collections::HashMap;
+
+use crate::checklist;
+use crate::thread_dump::{pool_name, ThreadInfo};
+
+/// Web 容器工作线程池名
+const HTTP_WORKER_POOLS: &[&str] = &["http-nio-", "http-bio-", "http-apr-", "https-jsse-nio-", "qtp", "XNIO-"];
+
+/// 数据库连接池取连接的栈帧
+const DB_POOL_FRAMES: &[&str] = &[
+    "com.zaxxer.hikari.pool.HikariPool.getConnection",
+    "com.alibaba.druid.pool.DruidDataSource.takeLast",
+    "com.alibaba.druid.pool.DruidDataSource.pollLast",
+    "org.apache.commons.pool2.impl.GenericObjectPool.borrowObject",
+    "org.apache.tomcat.jdbc.pool.ConnectionPool.borrowConnection",
+];
+
+/// 同步日志输出的栈帧 (log4j 1.x synchronized doAppend、log4j2/logback 输出流锁)
+const LOG_APPENDER_FRAMES: &[&str] = &[
+    "org.apache.log4j.AppenderSkeleton.doAppend",
+    "org.apache.log4j.Category.callAppenders",
+    "org.apache.logging.log4j.core.appender.OutputStreamManager.write",
+    "org.apache.logging.log4j.core.appender.AbstractOutputStreamAppender.directEncodeEvent",
+    "ch.qos.logback.core.OutputStreamAppender.writeBytes",
+    "ch.qos.logback.core.OutputStreamAppender.subAppend",
+];
+
+/// commonPool 工作线程空闲时停在这些栈帧
+const FORK_JOIN_IDLE_FRAMES: &[&str] = &["java.util.concurrent.ForkJoinPool.awaitWork", "java.util.concurrent.ForkJoinPool.awaitQuiescence"];
+
+/// 运行中但实际阻塞在网络 IO 的栈顶
+const BLOCKING_IO_FRAMES: &[&str] = &["java.net.SocketInputStream.socketRead", "sun.nio.ch.", "java.io.FileInputStream.read"];
+
+/// 同一监视器上 `Object.wait()` 的线程数阈值
+const SAME_MONITOR_WAITERS: usize = 5;
+
+/// 识别出的问题模式
+#[derive(Debug, Clone, Serialize)]
+pub struct PatternMatch {
+    pub id: &'static str,
+    pub title: &'static str,
+    /// 命中的依据 (线程数/比例/锁)
+    pub evidence: String,
+    pub threads: Vec<String>,
+    /// `get_checklist` 的症状参数
+    pub symptom: &'static str,
+    pub fix: &'static str,
+    pub checklist: ChecklistRef,
+}
+
+/// 对应的检查清单条目
+#[derive(Debug, Clone, Serialize)]
+pub struct ChecklistRef {
+    pub section: &'static str,
+    pub title: String,
+    pub item: String,
+}
+
+impl ChecklistRef {
+    fn new(section: &'static str, item: usize) -> Self {
+        let sections = checklist::get_checklist_data();
+        let found = sections.iter().find(|s| s.id == section);
+        Self {
+            section,
+            title: found.map(|s| s.title.clone()).unwrap_or_default(),
+            item: found.and_then(|s| s.items.get(item)).map(|i| i.desc.clone()).unwrap_or_default(),
+        }
+    }
+}
+
+/// 识别全部已知模式
+pub fn recognize(threads: &[ThreadInfo]) -> Vec<PatternMatch> {
+    [http_pool_db_wait(threads), common_pool_blocking(threads), log_appender_contention(threads)]
+        .into_iter()
+        .flatten()
+        .chain(object_wait_same_monitor(threads))
+        .collect()
+}
+
+fn has_frame(thread: &ThreadInfo, prefixes: &[&str]) -> bool {
+    thread.frames.iter().any(|f| prefixes.iter().any(|p| f.starts_with(p)))
+}
+
+fn names(threads: &[&ThreadInfo]) -> Vec<String> {
+    threads.iter().map(|t| t.name.clone()).collect()
+}
+
+/// 半数以上 (且至少 2 个) Web 工作线程在等数据库连接
+fn http_pool_db_wait(threads: &[ThreadInfo]) -> Option<PatternMatch> {
+    let workers: Vec<&ThreadInfo> = threads.iter()
+        .filter(|t| HTTP_WORKER_POOLS.iter().any(|p| t.name.starts_with(p)))
+        .collect();
+    let waiting: Vec<&ThreadInfo> = workers.iter().copied().filter(|t| has_frame(t, DB_POOL_FRAMES)).collect();
+    if waiting.len() < 2 || waiting.len() * 2 < workers.len() {
+        return None;
+    }
+    Some(PatternMatch {
+        id: "HTTP_POOL_DB_WAIT",
+        title: "HTTP 工作线程耗尽在数据库连接池上",
+        evidence: format!("{}/{} 个 HTTP 工作线程在等待数据库连接", waiting.len(), workers.len()),
+        threads: names(&waiting),
+        symptom: "resource",
+        fix: "排查连接泄漏 (未 close) 与持有连接时的慢 SQL/远程调用，按 DB 承载能力调整 maximumPoolSize，设置 connectionTimeout 快速失败",
+        checklist: ChecklistRef::new("4", 1),
+    })
+}
+
+/// commonPool 工作线程 3/4 以上 (且至少 2 个) 阻塞在 IO/等待上
+fn common_pool_blocking(threads: &[ThreadInfo]) -> Option<PatternMatch> {
+    let workers: Vec<&ThreadInfo> = threads.iter()
+        .filter(|t| pool_name(&t.name) == "ForkJoinPool.commonPool-worker-*")
+        .collect();
+    let blocked: Vec<&ThreadInfo> = workers.iter().copied()
+        .filter(|t| !has_frame(t, FORK_JOIN_IDLE_FRAMES))
+        .filter(|t| match t.state.as_deref() {
+            Some("RUNNABLE") => t.frames.first().is_some_and(|f| BLOCKING_IO_FRAMES.iter().any(|p| f.starts_with(p))),
+            Some(_) => true,
+            None => false,
+        })
+        .collect();
+    if blocked.len() < 2 || blocked.len() * 4 < workers.len() * 3 {
+        return None;
+    }
+    Some(PatternMatch {
+        id: "COMMON_POOL_BLOCKING",
+        title: "ForkJoinPool.commonPool 被阻塞调用占满",
+        evidence: format!("{}/{} 个 commonPool 工作线程阻塞在 IO/等待上", blocked.len(), workers.len()),
+        threads: names(&blocked),
+        symptom: "slow",
+        fix: "阻塞调用 (HTTP/JDBC/文件) 不要放进 parallelStream 或无 executor 参数的 CompletableFuture.*Async，改用独立的有界线程池",
+        checklist: ChecklistRef::new("2", 0),
+    })
+}
+
+/// 至少 2 个线程在同步日志 Appender 上等锁
+fn log_appender_contention(threads: &[ThreadInfo]) -> Option<PatternMatch> {
+    let contended: Vec<&ThreadInfo> = threads.iter()
+        .filter(|t| t.waiting_for.is_some() && has_frame(t, LOG_APPENDER_FRAMES))
+        .collect();
+    if contended.len() < 2 {
+        return None;
+    }
+    Some(PatternMatch {
+        id: "LOG_APPENDER_CONTENTION",
+        title: "日志 Appender 锁竞争",
+        evidence: format!("{} 个线程在等待日志输出锁", contended.len()),
+        threads: names(&contended),
+        symptom: "cpu",
+        fix: "改用 AsyncAppender / log4j2 AsyncLogger，降低热点路径的日志级别，避免在日志参数中拼接大对象",
+        checklist: ChecklistRef::new("1", 0),
+    })
+}
+
+/// 同一监视器上 `Object.wait()` 的线程达到阈值 (每个监视器一条)
+fn object_wait_same_monitor(threads: &[ThreadInfo]) -> Vec<PatternMatch> {
+    let mut by_monitor: HashMap<&str, Vec<&ThreadInfo>> = HashMap::new();
+    for thread in threads {
+        if let Some(lock) = &thread.waiting_on {
+            by_monitor.entry(&lock.address).or_default().push(thread);
+        }
+    }
+    let mut crowded: Vec<(&str, Vec<&ThreadInfo>)> = by_monitor.into_iter()
+        .filter(|(_, waiters)| waiters.len() >= SAME_MONITOR_WAITERS)
+        .collect();
+    crowded.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then_with(|| a.0.cmp(b.0)));
+    crowded.into_iter().map(|(address, waiters)| {
+        let class = waiters[0].waiting_on.as_ref().map(|l| l.class.as_str()).unwrap_or_default();
+        PatternMatch {
+            id: "OBJECT_WAIT_SAME_MONITOR",
+            title: "大量线程在同一对象上 Object.wait()",
+            evidence: format!("{} 个线程在 `{address}` ({class}) 上等待通知", waiters.len()),
+            threads: names(&waiters),
+            symptom: "resource",
+            fix: "定位负责 notify 的一方 (资源归还/生产者) 是否卡住或泄漏，wait 加超时，自研池改用有界 BlockingQueue/Semaphore",
+            checklist: ChecklistRef::new("4", 1),
+        }
+    }).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::thread_dump::LockRef;
+
+    fn thread(name: &str, state: &str, frames: &[&str]) -> ThreadInfo {
+        ThreadInfo {
+            name: name.to_string(),
+            state: Some(state.to_string()),
+            frames: frames.iter().map(|f| f.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn lock(address: &str) -> Option<LockRef> {
+        Some(LockRef { address: address.to_string(), class: "java.lang.Object".to_string() })
+    }
+
+    #[test]
+    fn test_http_pool_db_wait() {
+        let hikari = [
+            "java.util.concurrent.SynchronousQueue.poll(SynchronousQueue.java:937)",
+            "com.zaxxer.hikari.util.ConcurrentBag.borrow(ConcurrentBag.java:151)",
+            "com.zaxxer.hikari.pool.HikariPool.getConnection(HikariPool.java:180)",
+        ];
+        let mut threads: Vec<ThreadInfo> = (1..=3)
+            .map(|i| thread(&format!("http-nio-8080-exec-{i}"), "TIMED_WAITING", &hikari))
+            .collect();
+        threads.push(thread("http-nio-8080-exec-4", "RUNNABLE", &["com.acme.OrderController.list(OrderController.java:30)"]));
+        threads.push(thread("hikari-housekeeper", "TIMED_WAITING", &hikari));
+
+        let found = recognize(&threads);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].id, "HTTP_POOL_DB_WAIT");
+        assert_eq!(found[0].evidence, "3/4 个 HTTP 工作线程在等待数据库连接");
+        assert_eq!(found[0].checklist.section, "4");
+        assert_eq!(found[0].checklist.item, "池资源泄露（获取后未归还）");
+
+        // 少数线程等连接不算耗尽
+        threads[1].frames.clear();
+        threads[2].frames.clear();
+        assert!(recognize(&threads).is_empty());
+    }
+
+    #[test]
+    fn test_common_pool_blocking() {
+        let io = ["java.net.SocketInputStream.socketRead0(Native Method)", "com.acme.PriceClient.fetch(PriceClient.java:40)"];
+        let idle = ["jdk.internal.misc.Unsafe.park(Native Method)", "java.util.concurrent.ForkJoinPool.awaitWork(ForkJoinPool.java:1177)"];
+        let mut threads = vec![
+            thread("ForkJoinPool.commonPool-worker-1", "RUNNABLE", &io),
+            thread("ForkJoinPool.commonPool-worker-2", "RUNNABLE", &io),
+            thread("ForkJoinPool.commonPool-worker-3", "WAITING", &["java.util.concurrent.CompletableFuture.join(CompletableFuture.java:2043)"]),
+        ];
+        assert_eq!(recognize(&threads)[0].id, "COMMON_POOL_BLOCKING");
+
+        threads.push(thread("ForkJoinPool.commonPool-worker-4", "WAITING", &idle));
+        threads.push(thread("ForkJoinPool.commonPool-worker-5", "WAITING", &idle));
+        assert!(recognize(&threads).is_empty());
+    }
+
+    #[test]
+    fn test_log_appender_and_same_monitor() {
+        let mut threads: Vec<ThreadInfo> = (1..=2).map(|i| ThreadInfo {
+            waiting_for: lock("0x1"),
+            ..thread(&format!("worker-{i}"), "BLOCKED", &["org.apache.log4j.AppenderSkeleton.doAppend(AppenderSkeleton.java:231)"])
+        }).collect();
+        threads.extend((1..=5).map(|i| ThreadInfo {
+            waiting_on: lock("0x2"),
+            ..thread(&format!("legacy-pool-{i}"), "WAITING", &["java.lang.Object.wait(Native Method)"])
+        }));
+
+        let found = recognize(&threads);
+        let ids: Vec<&str> = found.iter().map(|p| p.id).collect();
+        assert_eq!(ids, vec!["LOG_APPENDER_CONTENTION", "OBJECT_WAIT_SAME_MONITOR"]);
+        assert_eq!(found[1].threads.len(), 5);
+    }
+}
@@ -0,0 +1,249 @@
+//! Verify 模块 - Phase 2 "Sniper" 单点验证 (v9.6)
+//!
+//! 🎯 对雷达扫描的单个问题 (id + 文件 + 行) 做二次确认：
+//! 1. 构建项目索引 (符号表 + 调用图)，在完整语义上下文中重新分析该文件
+//! 2. 对比有/无上下文的分析结果，判断问题是否被语义信息证实或排除
+//! 3. 收集证据：所在类与层级、该行引用的字段类型、调用图上下游一跳
+//!
+//! 输出 verdict (`confirmed` / `rejected` / `not_reproduced`) + confidence + evidence。
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+use crate::ast_engine;
+use crate::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+use crate::symbol_table::{SymbolTable, TypeInfo};
+use crate::taint::{CallGraph, CallSite, MethodSig};
+
+/// 证据中最多列出的上游调用者
+const MAX_CALLERS: usize = 5;
+
+/// 验证结论
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    /// 完整语义上下文下问题仍然存在
+    Confirmed,
+    /// 仅在无上下文 (启发式) 时触发，语义信息排除了它
+    Rejected,
+    /// 该位置已不再产生此问题 (代码已变更、被抑制或行号不符)
+    NotReproduced,
+}
+
+/// 该行引用的字段
+#[derive(Debug, Serialize)]
+struct FieldRef {
+    name: String,
+    type_name: String,
+    layer: Option<String>,
+    is_dao: bool,
+}
+
+/// 调用图中的一个调用点
+#[derive(Debug, Serialize)]
+struct CallRef {
+    method: String,
+    file: String,
+    line: usize,
+}
+
+/// 验证单个问题
+///
+/// `file` 可以是相对扫描根目录的路径 (与 `scan` 输出一致) 或绝对路径。
+pub fn verify_issue(root: &str, file: &str, line: usize, issue_id: &str) -> Result<Value, Box<dyn std::error::Error>> {
+    let file_path = resolve_file(Path::new(root), file);
+    let code = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Cannot read {}: {e}", file_path.display()))?;
+
+    let index = ast_engine::index_project(root)?;
+    let analyzer = JavaTreeSitterAnalyzer::new()?;
+
+    let with_ctx = analyzer.analyze_with_context(&code, &file_path, Some(&index.symbol_table), Some(&index.call_graph))?;
+    let without_ctx = analyzer.analyze_with_context(&code, &file_path, None, None)?;
+    let reproduced = with_ctx.iter().any(|i| i.id == issue_id && i.line == line);
+    let heuristic_only = !reproduced && without_ctx.iter().any(|i| i.id == issue_id && i.line == line);
+
+    let mut evidence = Vec::new();
+    let line_text = code.lines().nth(line.saturating_sub(1)).unwrap_or("").trim().to_string();
+    if !line_text.is_empty() {
+        evidence.push(format!("L{line}: `{line_text}`"));
+    }
+
+    // 所在类
+    let canonical = std::fs::canonicalize(&file_path).unwrap_or_else(|_| file_path.clone());
+    let class = class_for_file(&index.symbol_table, &canonical);
+    if let Some(class) = class {
+        evidence.push(format!("所在类 {} ({:?} 层)", class.fqn, class.layer));
+    }
+
+    // 该行引用的字段 (receiver 类型)
+    let fields = class.map(|c| fields_on_line(&index.symbol_table, &c.name, &line_text)).unwrap_or_default();
+    for f in &fields {
+        match &f.layer {
+            Some(layer) => evidence.push(format!("字段 `{}`: {} → {layer} 层{}", f.name, f.type_name, if f.is_dao { " (DAO)" } else { "" })),
+            None => evidence.push(format!("字段 `{}`: {} (项目内未找到定义)", f.name, f.type_name)),
+        }
+    }
+
+    // 调用图一跳邻域
+    let sites = call_sites_at(&index.call_graph, &canonical, line);
+    let callees: Vec<String> = sites.iter().map(|s| s.callee.full_name()).collect();
+    let enclosing: Vec<MethodSig> = dedup(sites.iter().map(|s| s.caller.clone()));
+    let callers: Vec<CallRef> = enclosing.iter()
+        .flat_map(|m| index.call_graph.incoming.get(m).into_iter().flatten())
+        .take(MAX_CALLERS)
+        .map(|s| CallRef { method: s.caller.full_name(), file: s.file.to_string_lossy().to_string(), line: s.line })
+        .collect();
+    if !callees.is_empty() {
+        evidence.push(format!("该行调用: {}", callees.join(", ")));
+    }
+    for m in &enclosing {
+        let count = index.call_graph.incoming.get(m).map(Vec::len).unwrap_or(0);
+        evidence.push(format!("所在方法 {} 被 {count} 处调用", m.full_name()));
+    }
+
+    let dao_support = fields.iter().any(|f| f.is_dao);
+    let (verdict, confidence) = if reproduced {
+        evidence.push(format!("完整语义上下文下重新分析仍报告 {issue_id}"));
+        let mut confidence: f64 = 0.75;
+        if issue_id.starts_with("N_PLUS_ONE") && dao_support {
+            confidence += 0.15;
+        }
+        if !callers.is_empty() {
+            confidence += 0.05;
+        }
+        (Verdict::Confirmed, confidence.min(0.95))
+    } else if heuristic_only {
+        evidence.push(format!("{issue_id} 仅在无语义上下文时触发，符号表/调用图信息排除了该问题"));
+        (Verdict::Rejected, 0.8)
+    } else {
+        let here: Vec<&str> = with_ctx.iter().filter(|i| i.line == line).map(|i| i.id.as_str()).collect();
+        if here.is_empty() {
+            evidence.push(format!("L{line} 未产生任何问题 (代码已变更、已抑制或行号不符)"));
+        } else {
+            evidence.push(format!("L{line} 产生的是其它问题: {}", here.join(", ")));
+        }
+        (Verdict::NotReproduced, 0.9)
+    };
+
+    let report = render_report(issue_id, file, line, verdict, confidence, &evidence);
+    Ok(json!({
+        "verdict": verdict,
+        "confidence": confidence,
+        "issue": { "id": issue_id, "file": file, "line": line },
+        "evidence": evidence,
+        "context": {
+            "class": class.map(|c| c.fqn.clone()),
+            "layer": class.map(|c| format!("{:?}", c.layer)),
+            "fields": fields,
+            "enclosing_methods": enclosing.iter().map(MethodSig::full_name).collect::<Vec<_>>(),
+            "callers": callers,
+            "callees": callees,
+        },
+        "report": report,
+    }))
+}
+
+/// 相对路径优先按扫描根目录解析
+fn resolve_file(root: &Path, file: &str) -> PathBuf {
+    let joined = root.join(file);
+    if joined.is_file() {
+        joined
+    } else {
+        PathBuf::from(file)
+    }
+}
+
+fn class_for_file<'a>(table: &'a SymbolTable, file: &Path) -> Option<&'a TypeInfo> {
+    table.classes.values().find(|c| std::fs::canonicalize(&c.file).is_ok_and(|p| p == file))
+}
+
+fn fields_on_line(table: &SymbolTable, class: &str, line_text: &str) -> Vec<FieldRef> {
+    let mut fields: Vec<FieldRef> = table.fields.iter()
+        .filter(|((owner, name), _)| owner == class && line_text.contains(&format!("{name}.")))
+        .map(|((_, name), binding)| {
+            let target = table.lookup_by_simple_name(&binding.type_name).into_iter().next()
+                .or_else(|| table.lookup_by_fqn(&binding.type_name));
+            FieldRef {
+                name: name.clone(),
+                type_name: binding.type_name.clone(),
+                layer: target.map(|t| format!("{:?}", t.layer)),
+                is_dao: target.is_some_and(TypeInfo::is_dao),
+            }
+        })
+        .collect();
+    fields.sort_by(|a, b| a.name.cmp(&b.name));
+    fields
+}
+
+fn call_sites_at<'a>(graph: &'a CallGraph, file: &Path, line: usize) -> Vec<&'a CallSite> {
+    graph.outgoing.values()
+        .flatten()
+        .filter(|s| s.line == line && std::fs::canonicalize(&s.file).is_ok_and(|p| p == file))
+        .collect()
+}
+
+fn dedup(methods: impl Iterator<Item = MethodSig>) -> Vec<MethodSig> {
+    let mut out: Vec<MethodSig> = Vec::new();
+    for m in methods {
+        if !out.contains(&m) {
+            out.push(m);
+        }
+    }
+    out
+}
+
+fn render_report(issue_id: &str, file: &str, line: usize, verdict: Verdict, confidence: f64, evidence: &[String]) -> String {
+    let label = match verdict {
+        Verdict::Confirmed => "✅ 确认 (confirmed)",
+        Verdict::Rejected => "❎ 排除 (rejected)",
+        Verdict::NotReproduced => "❔ 未复现 (not_reproduced)",
+    };
+    let mut out = format!("## 🎯 验证: {issue_id} @ {file}:{line}\n\n**结论**: {label} | **置信度**: {:.0}%\n\n**证据**:\n", confidence * 100.0);
+    for e in evidence {
+        out.push_str(&format!("- {e}\n"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("src/main/java/com/acme");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("UserDao.java"), "package com.acme;\n\n@Repository\npublic interface UserDao {\n    Object findById(Long id);\n}\n").unwrap();
+        std::fs::write(pkg.join("UserService.java"), "package com.acme;\n\n@Service\npublic class UserService {\n    @Autowired\n    private UserDao userDao;\n\n    public void load(java.util.List<Long> ids) {\n        for (Long id : ids) {\n            userDao.findById(id);\n        }\n    }\n}\n").unwrap();
+        std::fs::write(pkg.join("UserController.java"), "package com.acme;\n\n@RestController\npublic class UserController {\n    @Autowired\n    private UserService userService;\n\n    public void get() {\n        userService.load(null);\n    }\n}\n").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_verify_confirmed_with_evidence() {
+        let dir = project();
+        let root = dir.path().to_str().unwrap();
+        let v = verify_issue(root, "src/main/java/com/acme/UserService.java", 10, "N_PLUS_ONE").unwrap();
+
+        assert_eq!(v["verdict"], "confirmed");
+        assert!(v["confidence"].as_f64().unwrap() >= 0.9);
+        assert_eq!(v["context"]["class"], "com.acme.UserService");
+        assert_eq!(v["context"]["fields"][0]["name"], "userDao");
+        assert_eq!(v["context"]["fields"][0]["is_dao"], true);
+        assert_eq!(v["context"]["callees"][0], "com.acme.UserDao.findById");
+        assert_eq!(v["context"]["callers"][0]["method"], "com.acme.UserController.get");
+        assert!(v["report"].as_str().unwrap().contains("confirmed"));
+    }
+
+    #[test]
+    fn test_verify_not_reproduced() {
+        let dir = project();
+        let root = dir.path().to_str().unwrap();
+        let v = verify_issue(root, "src/main/java/com/acme/UserService.java", 4, "N_PLUS_ONE").unwrap();
+        assert_eq!(v["verdict"], "not_reproduced");
+
+        assert!(verify_issue(root, "Missing.java", 1, "N_PLUS_ONE").is_err());
+    }
+}
@@ -266,6 +266,329 @@ fn test_spring_boot_sample_full_scan() {
     eprintln!("Spring Boot Sample Scan Report:\n{}", report_str);
 }
 
+#[test]
+fn test_spring_boot_sample_timing() {
+    use java_perf::ast_engine::{collect_issues_with, render_timing, ScanOptions};
+
+    let fixture_path = common::spring_boot_sample_fixture();
+    if !fixture_path.exists() {
+        eprintln!("Skipping test: fixture directory not found at {:?}", fixture_path);
+        return;
+    }
+
+    let path = fixture_path.to_str().unwrap();
+    let untimed = collect_issues_with(path, &ScanOptions::default()).unwrap();
+    assert!(untimed.timing.is_none());
+
+    let options = ScanOptions { timing: true, ..Default::default() };
+    let result = collect_issues_with(path, &options).unwrap();
+    let timing = result.timing.expect("timing requested");
+
+    assert!(!timing.slowest_files.is_empty());
+    assert!(timing.slowest_files.len() <= 10);
+    assert!(timing.slowest_files.windows(2).all(|w| w[0].1 >= w[1].1));
+    // 路径相对扫描根目录
+    assert!(timing.slowest_files.iter().all(|(f, _)| !f.starts_with('/')));
+    assert!(timing.rules.iter().any(|(id, _)| id == "N_PLUS_ONE"));
+
+    let md = render_timing(&timing);
+    assert!(md.contains("phase-1 index"));
+    assert!(md.contains("Slowest files"));
+}
+
+#[test]
+fn test_spring_boot_sample_metrics() {
+    use java_perf::ast_engine::collect_issues;
+
+    let fixture_path = common::spring_boot_sample_fixture();
+    if !fixture_path.exists() {
+        eprintln!("Skipping test: fixture directory not found at {:?}", fixture_path);
+        return;
+    }
+
+    let result = collect_issues(fixture_path.to_str().unwrap()).unwrap();
+    let method = result.metrics.iter().find(|m| m.method == "findAllWithDetails").expect("metrics for UserService");
+    assert_eq!(method.class, "UserService");
+    assert!(method.file.ends_with("UserService.java") && !method.file.starts_with('/'));
+    assert!(method.loop_depth >= 1);
+
+    // 被标记方法按风险降序，循环内查询的方法排在最前
+    let json = java_perf::report::render_json(&result);
+    let riskiest = json["metrics"]["riskiest"].as_array().unwrap();
+    assert!(riskiest.windows(2).all(|w| w[0]["risk"].as_u64() >= w[1]["risk"].as_u64()));
+    assert_eq!(riskiest[0]["method"], "findAllWithDetails");
+    assert!(riskiest[0]["issues"].as_array().unwrap().iter().any(|id| id == "UNBOUNDED_QUERY_RESULT"));
+}
+
+#[test]
+fn test_spring_boot_sample_threads_and_io_throttle() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+
+    let fixture_path = common::spring_boot_sample_fixture();
+    if !fixture_path.exists() {
+        eprintln!("Skipping test: fixture directory not found at {:?}", fixture_path);
+        return;
+    }
+
+    let path = fixture_path.to_str().unwrap();
+    let key = |r: &java_perf::ast_engine::ScanResult| -> Vec<(String, String, usize)> {
+        r.issues.iter().map(|i| (i.issue_type.clone(), i.file.clone(), i.line)).collect()
+    };
+
+    let default = collect_issues_with(path, &ScanOptions::default()).unwrap();
+    let limited = collect_issues_with(path, &ScanOptions {
+        threads: Some(1),
+        io_throttle: Some(1),
+        ..Default::default()
+    }).unwrap();
+
+    assert_eq!(default.file_count, limited.file_count);
+    assert_eq!(key(&default), key(&limited));
+}
+
+#[test]
+fn test_spring_boot_sample_persisted_index() {
+    use java_perf::ast_engine::{collect_issues_with, write_index, ScanOptions};
+
+    let fixture_path = common::spring_boot_sample_fixture();
+    if !fixture_path.exists() {
+        eprintln!("Skipping test: fixture directory not found at {:?}", fixture_path);
+        return;
+    }
+
+    // 复制到临时目录，避免在 fixtures 中写入 .java-perf/
+    let dir = tempfile::tempdir().unwrap();
+    for entry in walkdir::WalkDir::new(&fixture_path).into_iter().filter_map(|e| e.ok()) {
+        let target = dir.path().join(entry.path().strip_prefix(&fixture_path).unwrap());
+        if entry.file_type().is_dir() {
+            std::fs::create_dir_all(&target).unwrap();
+        } else {
+            std::fs::copy(entry.path(), &target).unwrap();
+        }
+    }
+    let path = dir.path().to_str().unwrap();
+    let options = ScanOptions { timing: true, ..Default::default() };
+    let key = |r: &java_perf::ast_engine::ScanResult| -> Vec<(String, String, usize)> {
+        r.issues.iter().map(|i| (i.issue_type.clone(), i.file.clone(), i.line)).collect()
+    };
+
+    let fresh = collect_issues_with(path, &options).unwrap();
+    assert!(!fresh.timing.as_ref().unwrap().index_cached);
+
+    let summary = write_index(path, Default::default()).unwrap();
+    assert_eq!(summary["files"], 3);
+    assert!(dir.path().join(".java-perf/index.bin").is_file());
+
+    let cached = collect_issues_with(path, &options).unwrap();
+    assert!(cached.timing.as_ref().unwrap().index_cached);
+    assert_eq!(key(&fresh), key(&cached));
+
+    // 源文件变化 → 索引失效
+    let controller = walkdir::WalkDir::new(dir.path()).into_iter().filter_map(|e| e.ok())
+        .find(|e| e.file_name() == "UserController.java").unwrap();
+    let code = std::fs::read_to_string(controller.path()).unwrap();
+    std::fs::write(controller.path(), format!("{code}\n")).unwrap();
+    let stale = collect_issues_with(path, &options).unwrap();
+    assert!(!stale.timing.unwrap().index_cached);
+}
+
+#[test]
+fn test_layer_weighting_escalates_controller_issues() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions, Severity};
+    use java_perf::rules::layer_weight::LayerWeighting;
+
+    let dir = tempfile::tempdir().unwrap();
+    let pkg = dir.path().join("src/main/java/com/acme");
+    std::fs::create_dir_all(&pkg).unwrap();
+    let header = "package com.acme;\n\nimport java.text.SimpleDateFormat;\nimport java.util.Date;\n";
+    let body = "    public String format(Date d) {\n        return new SimpleDateFormat(\"yyyy\").format(d);\n    }\n}\n";
+    std::fs::write(pkg.join("ReportController.java"), format!("{header}\n@RestController\npublic class ReportController {{\n{body}")).unwrap();
+    std::fs::write(pkg.join("BatchUtil.java"), format!("{header}\npublic class BatchUtil {{\n{body}")).unwrap();
+    let path = dir.path().to_str().unwrap();
+    let date_format = |options: &ScanOptions| -> Vec<(String, Severity, String)> {
+        let result = collect_issues_with(path, options).unwrap();
+        result.issues.into_iter()
+            .filter(|i| i.issue_type == "SIMPLE_DATE_FORMAT")
+            .map(|i| (i.file.rsplit('/').next().unwrap().to_string(), i.severity, i.description))
+            .collect()
+    };
+
+    let weighted = date_format(&ScanOptions::default());
+    assert_eq!(weighted.len(), 2);
+    assert_eq!((weighted[0].0.as_str(), weighted[0].1), ("BatchUtil.java", Severity::P1));
+    assert_eq!((weighted[1].0.as_str(), weighted[1].1), ("ReportController.java", Severity::P0));
+    assert!(weighted[1].2.ends_with("[层级加权: Controller 层 P1→P0]"));
+
+    let plain = date_format(&ScanOptions { layer_weighting: LayerWeighting::disabled(), ..Default::default() });
+    assert!(plain.iter().all(|(_, severity, _)| *severity == Severity::P1));
+}
+
+#[test]
+fn test_test_code_modes() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+    use java_perf::rules::test_code::TestCodeMode;
+
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("src/main/java/com/acme");
+    let test = dir.path().join("src/test/java/com/acme");
+    std::fs::create_dir_all(&main).unwrap();
+    std::fs::create_dir_all(&test).unwrap();
+    std::fs::write(main.join("Poller.java"), "package com.acme;\n\npublic class Poller {\n    public void poll() throws Exception {\n        Thread.sleep(1000);\n    }\n}\n").unwrap();
+    std::fs::write(test.join("PollerTest.java"), concat!(
+        "package com.acme;\n\n",
+        "public class PollerTest {\n",
+        "    private static int calls;\n\n",
+        "    void pollsEventually() throws Exception {\n",
+        "        for (int i = 0; i < 3; i++) {\n",
+        "            Object probe = new Object();\n",
+        "        }\n",
+        "        Thread.sleep(500);\n",
+        "    }\n",
+        "}\n",
+    )).unwrap();
+    let path = dir.path().to_str().unwrap();
+    let scan = |mode: TestCodeMode| {
+        let result = collect_issues_with(path, &ScanOptions { test_code: mode, ..Default::default() }).unwrap();
+        let ids: Vec<(String, String)> = result.issues.iter()
+            .map(|i| (i.file.rsplit('/').next().unwrap().to_string(), i.issue_type.clone()))
+            .collect();
+        (ids, result.info.test_code.unwrap())
+    };
+    let of = |ids: &[(String, String)], file: &str| -> Vec<String> {
+        ids.iter().filter(|(f, _)| f == file).map(|(_, id)| id.clone()).collect()
+    };
+
+    // 默认跳过测试源码，生产代码不会触发 TEST_*
+    let (ids, meta) = scan(TestCodeMode::Exclude);
+    assert!(of(&ids, "PollerTest.java").is_empty());
+    assert!(!of(&ids, "Poller.java").iter().any(|id| id.starts_with("TEST_")));
+    assert_eq!(meta, "exclude (1 files skipped)");
+
+    // 只保留测试专用规则
+    let (ids, meta) = scan(TestCodeMode::TestRules);
+    assert_eq!(of(&ids, "PollerTest.java"), vec!["TEST_SHARED_STATIC_STATE", "TEST_THREAD_SLEEP"]);
+    assert_eq!(meta, "test-rules");
+
+    // 按生产代码扫描，测试专用规则同样生效
+    let (ids, _) = scan(TestCodeMode::Include);
+    let test_ids = of(&ids, "PollerTest.java");
+    assert!(test_ids.contains(&"OBJECT_IN_LOOP".to_string()));
+    assert!(test_ids.contains(&"TEST_THREAD_SLEEP".to_string()));
+}
+
+#[test]
+fn test_persisted_index_with_test_sources() {
+    use java_perf::ast_engine::{collect_issues_with, write_index, ScanOptions};
+    use java_perf::rules::test_code::TestCodeMode;
+
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("src/main/java/com/acme");
+    let test = dir.path().join("src/test/java/com/acme");
+    std::fs::create_dir_all(&main).unwrap();
+    std::fs::create_dir_all(&test).unwrap();
+    std::fs::write(main.join("Poller.java"), "package com.acme;\n\npublic class Poller {\n    public void poll() {}\n}\n").unwrap();
+    std::fs::write(test.join("PollerTest.java"), "package com.acme;\n\npublic class PollerTest {\n    void polls() { new Poller().poll(); }\n}\n").unwrap();
+    let path = dir.path().to_str().unwrap();
+    let cached = |mode: TestCodeMode| {
+        let options = ScanOptions { timing: true, test_code: mode, ..Default::default() };
+        collect_issues_with(path, &options).unwrap().timing.unwrap().index_cached
+    };
+
+    // `index` 与默认 `scan` 使用相同的文件列表 (跳过测试源码)
+    assert_eq!(write_index(path, TestCodeMode::default()).unwrap()["files"], 1);
+    assert!(cached(TestCodeMode::default()));
+    assert!(!cached(TestCodeMode::Include));
+
+    assert_eq!(write_index(path, TestCodeMode::Include).unwrap()["files"], 2);
+    assert!(cached(TestCodeMode::Include));
+    assert!(!cached(TestCodeMode::default()));
+}
+
+#[test]
+fn test_ci_pipeline_rules_in_project_scan() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    let workflows = dir.path().join(".github/workflows");
+    std::fs::create_dir_all(&workflows).unwrap();
+    std::fs::write(workflows.join("ci.yml"), "jobs:\n  build:\n    steps:\n      - run: mvn -B clean install\n").unwrap();
+    std::fs::write(dir.path().join(".gitlab-ci.yml"), "test:\n  script:\n    - ./gradlew check --parallel --scan\n").unwrap();
+
+    let result = collect_issues_with(dir.path().to_str().unwrap(), &ScanOptions::default()).unwrap();
+    let issues: Vec<(&str, &str)> = result.issues.iter()
+        .map(|i| (i.file.as_str(), i.issue_type.as_str()))
+        .collect();
+    assert_eq!(issues, vec![
+        (".github/workflows/ci.yml", "CI_MVN_CLEAN_INSTALL"),
+        (".github/workflows/ci.yml", "CI_NO_BUILD_TIMING"),
+        (".github/workflows/ci.yml", "CI_NO_DEPENDENCY_CACHE"),
+        (".github/workflows/ci.yml", "CI_TESTS_NO_PARALLEL"),
+        (".gitlab-ci.yml", "CI_NO_DEPENDENCY_CACHE"),
+    ]);
+}
+
+#[test]
+fn test_gradle_build_script_rules_in_project_scan() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("build.gradle"),
+        "repositories {\n    maven { url 'https://nexus.acme.com/maven' }\n}\n\ndependencies {\n    implementation 'com.google.guava:guava:+'\n}\n\ntest {\n    useJUnitPlatform()\n}\n").unwrap();
+    std::fs::write(dir.path().join("settings.gradle"), "rootProject.name = 'demo'\n").unwrap();
+
+    let result = collect_issues_with(dir.path().to_str().unwrap(), &ScanOptions::default()).unwrap();
+    let mut issues: Vec<(&str, &str, usize)> = result.issues.iter()
+        .map(|i| (i.file.as_str(), i.issue_type.as_str(), i.line))
+        .collect();
+    issues.sort();
+    assert_eq!(issues, vec![
+        ("build.gradle", "GRADLE_DYNAMIC_VERSION", 6),
+        ("build.gradle", "GRADLE_SLOW_REPO_FIRST", 2),
+        ("build.gradle", "GRADLE_TEST_NO_PARALLEL_FORKS", 9),
+    ]);
+}
+
+#[test]
+fn test_spark_job_rules_only_with_spark_imports() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    let scala = dir.path().join("src/main/scala/com/acme");
+    std::fs::create_dir_all(&scala).unwrap();
+    std::fs::write(scala.join("Job.scala"),
+        "package com.acme\n\nimport org.apache.spark.rdd.RDD\n\nobject Job {\n  def top(pairs: RDD[(String, Int)]) = pairs.groupByKey().collect()\n}\n").unwrap();
+    std::fs::write(scala.join("Util.scala"), "package com.acme\n\nobject Util {\n  def all(xs: Iterator[Int]) = xs.collect()\n}\n").unwrap();
+
+    let result = collect_issues_with(dir.path().to_str().unwrap(), &ScanOptions::default()).unwrap();
+    let issues: Vec<(&str, &str)> = result.issues.iter()
+        .map(|i| (i.file.as_str(), i.issue_type.as_str()))
+        .collect();
+    assert_eq!(issues, vec![
+        ("src/main/scala/com/acme/Job.scala", "SPARK_COLLECT"),
+        ("src/main/scala/com/acme/Job.scala", "SPARK_GROUP_BY_KEY"),
+    ]);
+}
+
+#[test]
+fn test_generated_sources_mapped_to_origin() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+
+    let dir = tempfile::tempdir().unwrap();
+    let main = dir.path().join("src/main/java/com/acme");
+    let generated = dir.path().join("target/generated-sources/annotations/com/acme");
+    std::fs::create_dir_all(&main).unwrap();
+    std::fs::create_dir_all(&generated).unwrap();
+    std::fs::write(main.join("OrderMapper.java"),
+        "package com.acme;\n\n@Mapper\npublic interface OrderMapper {\n    String toLabel(java.util.List<String> tags);\n}\n").unwrap();
+    std::fs::write(generated.join("OrderMapperImpl.java"),
+        "package com.acme;\n\n@Generated(value = \"org.mapstruct.ap.MappingProcessor\")\npublic class OrderMapperImpl implements OrderMapper {\n    public String toLabel(java.util.List<String> tags) {\n        String label = \"\";\n        for (String tag : tags) {\n            label += tag;\n        }\n        return label;\n    }\n}\n").unwrap();
+
+    let result = collect_issues_with(dir.path().to_str().unwrap(), &ScanOptions::default()).unwrap();
+    let issue = result.issues.iter().find(|i| i.issue_type == "STRING_CONCAT_LOOP").expect("concat in generated mapper");
+    assert_eq!((issue.file.as_str(), issue.line), ("src/main/java/com/acme/OrderMapper.java", 5));
+    assert!(issue.description.ends_with("[生成代码 (MapStruct): target/generated-sources/annotations/com/acme/OrderMapperImpl.java:8]"), "{}", issue.description);
+}
+
 #[test]
 fn test_spring_boot_sample_fqn_resolution() {
     use java_perf::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
@@ -441,3 +764,39 @@ fn test_spring_boot_sample_callgraph_accuracy() {
     // The spring-boot-sample has calls between layers, so we should have some edges
     // Note: The exact number depends on how well call site extraction works
 }
+
+/// v9.6: `scan --owners` 附加 git blame 作者与 CODEOWNERS 团队汇总
+#[test]
+fn test_scan_owners() {
+    use java_perf::ast_engine::{collect_issues_with, ScanOptions};
+    use std::process::Command;
+
+    let dir = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        Command::new("git").arg("-C").arg(dir.path()).args(args).output().map(|o| o.status.success()).unwrap_or(false)
+    };
+    if !git(&["init", "-q"]) {
+        eprintln!("git unavailable, skipping");
+        return;
+    }
+    let src = dir.path().join("order/src/main/java/com/acme");
+    std::fs::create_dir_all(&src).unwrap();
+    std::fs::create_dir_all(dir.path().join(".github")).unwrap();
+    std::fs::write(dir.path().join(".github/CODEOWNERS"), "/order/ @acme/orders\n").unwrap();
+    std::fs::write(src.join("Poller.java"), "package com.acme;\n\npublic class Poller {\n    public void poll() {\n        for (int i = 0; i < 3; i++) {\n            Object probe = new Object();\n        }\n    }\n}\n").unwrap();
+    assert!(git(&["add", "-A"]));
+    assert!(git(&["-c", "user.name=Alice", "-c", "user.email=alice@acme.io", "commit", "-q", "-m", "init"]));
+
+    let result = collect_issues_with(dir.path().to_str().unwrap(), &ScanOptions { owners: true, ..Default::default() }).unwrap();
+    assert_eq!(result.info.codeowners.as_deref(), Some(".github/CODEOWNERS"));
+    let issue = result.issues.iter().find(|i| i.issue_type == "OBJECT_IN_LOOP").unwrap();
+    let owner = issue.owner.as_ref().unwrap();
+    assert_eq!(owner.author, "Alice");
+    assert_eq!(owner.email, "alice@acme.io");
+    assert_eq!(owner.commit.len(), 40);
+    assert_eq!(owner.teams, vec!["@acme/orders"]);
+
+    let json = java_perf::report::render_json(&result);
+    assert_eq!(json["teams"]["@acme/orders"]["total"], result.issues.len());
+    assert_eq!(json["issues"][0]["owner"]["author"], "Alice");
+}
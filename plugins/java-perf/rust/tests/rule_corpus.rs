@@ -0,0 +1,134 @@
+// ============================================================================
+// Rule Corpus Tests - Per-Rule Positive / Negative Fixtures
+// ============================================================================
+//
+// Corpus layout: fixtures/rules/<RULE_ID>/{positive,negative}/*.java
+//
+// These tests verify that:
+// 1. Every registered AST rule has at least one positive and one negative fixture
+// 2. Positive fixtures trigger their rule, negative fixtures do not
+// 3. No rule leaks into another rule's fixtures (cross-contamination)
+//
+// The set of rules fired on each fixture is compared exactly (golden):
+// - positive: {RULE_ID} ∪ also-triggers
+// - negative: also-triggers
+//
+// Intended overlap is declared in the fixture itself with a header comment:
+//     // also-triggers: OBJECT_IN_LOOP, N_PLUS_ONE
+// Variant rules that report under a unified ID (N_PLUS_ONE_WHILE → N_PLUS_ONE)
+// declare it with:
+//     // reports-as: N_PLUS_ONE
+//
+// Each fixture is analyzed on its own, with a symbol table built from the same
+// file (Phase 1), the way `scan` analyzes a single-class project.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use java_perf::scanner::tree_sitter_java::JavaTreeSitterAnalyzer;
+use java_perf::symbol_table::SymbolTable;
+
+fn corpus_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("fixtures").join("rules")
+}
+
+fn java_files(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok().map(|e| e.path()))
+                .filter(|p| p.extension().is_some_and(|ext| ext == "java"))
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+/// 解析 `// <key>: A, B` 头注释
+fn header_ids(code: &str, key: &str) -> BTreeSet<String> {
+    let prefix = format!("// {key}:");
+    code.lines()
+        .filter_map(|l| l.trim().strip_prefix(prefix.as_str()))
+        .flat_map(|ids| ids.split(','))
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty())
+        .collect()
+}
+
+/// 单文件分析：先用同一文件构建符号表，再带上下文运行全部规则
+fn fired_rules(analyzer: &JavaTreeSitterAnalyzer, file: &Path) -> BTreeSet<String> {
+    let code = fs::read_to_string(file).expect("read fixture");
+    let mut symbol_table = SymbolTable::new();
+    if let Ok((Some(type_info), bindings, _)) = analyzer.extract_symbols(&code, file) {
+        let class_name = type_info.name.clone();
+        symbol_table.register_class_fqn(type_info);
+        for binding in bindings {
+            symbol_table.register_field(&class_name, binding);
+        }
+    }
+    analyzer
+        .analyze_with_context(&code, file, Some(&symbol_table), None)
+        .expect("analyze fixture")
+        .into_iter()
+        .map(|issue| issue.id)
+        .collect()
+}
+
+#[test]
+fn test_every_rule_has_positive_and_negative_fixtures() {
+    let analyzer = JavaTreeSitterAnalyzer::new().expect("Failed to create analyzer");
+    let mut missing = Vec::new();
+    for id in analyzer.rule_ids() {
+        for kind in ["positive", "negative"] {
+            if java_files(&corpus_dir().join(id).join(kind)).is_empty() {
+                missing.push(format!("{id}/{kind}"));
+            }
+        }
+    }
+    assert!(missing.is_empty(), "rules without fixtures:\n  {}", missing.join("\n  "));
+}
+
+#[test]
+fn test_corpus_has_no_unknown_rules() {
+    let analyzer = JavaTreeSitterAnalyzer::new().expect("Failed to create analyzer");
+    let known: BTreeSet<&str> = analyzer.rule_ids().into_iter().collect();
+    let unknown: Vec<String> = fs::read_dir(corpus_dir())
+        .expect("fixtures/rules exists")
+        .filter_map(|e| e.ok())
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .filter(|name| !known.contains(name.as_str()))
+        .collect();
+    assert!(unknown.is_empty(), "fixture directories for unregistered rules: {unknown:?}");
+}
+
+#[test]
+fn test_rule_corpus_golden() {
+    let analyzer = JavaTreeSitterAnalyzer::new().expect("Failed to create analyzer");
+    let mut failures = Vec::new();
+
+    for id in analyzer.rule_ids() {
+        for (kind, positive) in [("positive", true), ("negative", false)] {
+            for file in java_files(&corpus_dir().join(id).join(kind)) {
+                let code = fs::read_to_string(&file).expect("read fixture");
+                let reported = header_ids(&code, "reports-as").pop_first().unwrap_or_else(|| id.to_string());
+                let mut expected = header_ids(&code, "also-triggers");
+                let rel = file.strip_prefix(corpus_dir()).unwrap_or(&file).display().to_string();
+                if !positive && expected.contains(&reported) {
+                    failures.push(format!("{rel}: negative fixture lists its own rule in also-triggers"));
+                    continue;
+                }
+                if positive {
+                    expected.insert(reported);
+                }
+                let actual = fired_rules(&analyzer, &file);
+                if actual != expected {
+                    failures.push(format!("{rel}: expected {expected:?}, got {actual:?}"));
+                }
+            }
+        }
+    }
+
+    assert!(failures.is_empty(), "rule corpus mismatches:\n  {}", failures.join("\n  "));
+}